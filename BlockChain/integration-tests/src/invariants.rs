@@ -0,0 +1,166 @@
+//! # Cross-Pallet Invariants
+//!
+//! Checks of the state each pallet promises to keep, run between the steps of every scenario.
+//! A broken invariant panics with the name of the pallet that owns it, so a failing scenario
+//! points at the pallet to look at rather than at the step that happened to notice.
+
+use crate::mock::*;
+use crittercraft_traits::SharedNftManager;
+use pallet_critter_battle::BattleStatus;
+use pallet_user_profile::ScoreContributor;
+
+/// Panics naming `pallet` unless `condition` holds.
+macro_rules! invariant {
+    ($pallet:literal, $condition:expr, $($message:tt)+) => {
+        if !$condition {
+            panic!("[{}] invariant broken: {}", $pallet, format!($($message)+));
+        }
+    };
+}
+
+/// Checks every pallet's invariants for the given accounts.
+pub fn check_all(accounts: &[u64]) {
+    nfts(accounts);
+    battle(accounts);
+    pet_status();
+    user_profile(accounts);
+}
+
+/// Every minted pet has exactly one owner, who lists it, and locks only cover existing pets.
+pub fn nfts(accounts: &[u64]) {
+    for pet_id in 0..CritterNfts::next_pet_id() {
+        let Some(pet) = CritterNfts::pet_nfts(pet_id) else {
+            invariant!("pallet-critter-nfts", CritterNfts::locked_nfts(pet_id).is_none(), "pet {} is locked but does not exist", pet_id);
+            continue;
+        };
+        invariant!("pallet-critter-nfts", pet.id == pet_id, "pet stored under {} has id {}", pet_id, pet.id);
+
+        let owner = CritterNfts::pet_nft_owner(pet_id);
+        invariant!("pallet-critter-nfts", owner.is_some(), "pet {} has no owner", pet_id);
+        let owner = owner.unwrap();
+        invariant!(
+            "pallet-critter-nfts",
+            CritterNfts::owner_of_pet(owner).contains(&pet_id),
+            "pet {} is owned by {} but missing from their owned pets",
+            pet_id,
+            owner
+        );
+        invariant!(
+            "pallet-critter-nfts",
+            <CritterNfts as SharedNftManager<u64, u32>>::owner_of(&pet_id) == Some(owner),
+            "the shared trait reports a different owner for pet {}",
+            pet_id
+        );
+    }
+
+    for account in accounts {
+        for pet_id in CritterNfts::owner_of_pet(account).iter() {
+            invariant!(
+                "pallet-critter-nfts",
+                CritterNfts::pet_nft_owner(pet_id) == Some(*account),
+                "account {} lists pet {} it does not own",
+                account,
+                pet_id
+            );
+        }
+    }
+}
+
+/// Battle slots and pet assignments only point at open battles of their own participants.
+pub fn battle(accounts: &[u64]) {
+    for account in accounts {
+        for battle_id in CritterBattle::account_active_battles(account).iter() {
+            let battle = CritterBattle::battles(battle_id);
+            invariant!("pallet-critter-battle", battle.is_some(), "account {} holds missing battle {}", account, battle_id);
+            let battle = battle.unwrap();
+            invariant!(
+                "pallet-critter-battle",
+                matches!(battle.status, BattleStatus::Challenged | BattleStatus::Active),
+                "account {} still holds ended battle {} ({:?})",
+                account,
+                battle_id,
+                battle.status
+            );
+            invariant!(
+                "pallet-critter-battle",
+                battle.pet1_owner == *account || battle.pet2_owner == *account,
+                "account {} holds battle {} it is not part of",
+                account,
+                battle_id
+            );
+        }
+    }
+
+    for battle_id in 0..CritterBattle::battle_count() {
+        let Some(battle) = CritterBattle::battles(battle_id) else { continue };
+        let open = matches!(battle.status, BattleStatus::Challenged | BattleStatus::Active);
+        for pet_id in [battle.pet1_id, battle.pet2_id] {
+            invariant!(
+                "pallet-critter-battle",
+                (CritterBattle::pet_active_battle(pet_id) == Some(battle_id)) == open,
+                "pet {} and battle {} ({:?}) disagree on whether it is fighting",
+                pet_id,
+                battle_id,
+                battle.status
+            );
+        }
+        invariant!(
+            "pallet-critter-battle",
+            battle.outcome.is_some() || open || battle.status == BattleStatus::Expired,
+            "battle {} ended as {:?} without an outcome",
+            battle_id,
+            battle.status
+        );
+    }
+}
+
+/// Every pet with a status is a minted pet, and its needs stay in range.
+pub fn pet_status() {
+    for pet_id in 0..CritterNfts::next_pet_id() {
+        let Some(status) = PetStatus::pet_status(pet_id) else { continue };
+        invariant!("pallet-critter-pet-status", CritterNfts::pet_nfts(pet_id).is_some(), "status kept for unminted pet {}", pet_id);
+        invariant!("pallet-critter-pet-status", status.pet_id == pet_id, "status stored under {} is for pet {}", pet_id, status.pet_id);
+
+        let needs = PetStatus::pet_needs(pet_id);
+        invariant!("pallet-critter-pet-status", needs.is_some(), "pet {} has a status but no needs", pet_id);
+        let needs = needs.unwrap();
+        for (need, value) in [
+            ("hunger", needs.hunger),
+            ("energy", needs.energy),
+            ("happiness", needs.happiness),
+            ("hygiene", needs.hygiene),
+            ("social", needs.social),
+        ] {
+            invariant!("pallet-critter-pet-status", value <= 100, "pet {} has {} {} above 100", pet_id, need, value);
+        }
+    }
+}
+
+/// No profile claims activity from a block that has not happened yet.
+pub fn user_profile(accounts: &[u64]) {
+    for account in accounts {
+        let profile = UserProfile::user_profiles(account);
+        invariant!(
+            "pallet-user-profile",
+            profile.last_active_block <= System::block_number(),
+            "account {} was last active at future block {}",
+            account,
+            profile.last_active_block
+        );
+    }
+}
+
+/// Checks that an account's stored score is what its contributors report right now.
+/// Call it straight after a refresh; scores otherwise lag until the next one.
+pub fn score_is_fresh(account: u64) {
+    let expected = <Test as pallet_user_profile::Config>::ScoreContributors::get_score_contribution(&account);
+    let stored = UserProfile::score_of(&account);
+    invariant!(
+        "pallet-user-profile",
+        stored == expected,
+        "account {} stores score {} but its contributors report {}",
+        account,
+        stored,
+        expected
+    );
+}
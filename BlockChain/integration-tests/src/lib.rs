@@ -0,0 +1,25 @@
+//! # CritterCraft Integration Tests
+//!
+//! Scenario tests that run pallet-critter-nfts, pallet-critter-battle, pallet-critter-pet-status
+//! and pallet-user-profile together in one mock runtime.
+//!
+//! ## Overview
+//!
+//! Each pallet's own tests replace its neighbours with mocks, so a change that breaks the way
+//! the pallets talk to each other passes them all. Here the pallets are wired to each other
+//! through the shared traits, as in a production runtime, and every scenario checks the
+//! invariants of all of them between steps:
+//! - Two players from minting through care, a battle, reward claims and a score refresh
+//! - A neglected pet recovering in both the NFT and the status pallet
+//! - A marketplace-style sale driven only through `SharedNftManager`
+//!
+//! A broken invariant or failed call names the pallet responsible.
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod invariants;
+
+#[cfg(test)]
+mod tests;
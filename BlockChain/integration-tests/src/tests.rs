@@ -0,0 +1,678 @@
+use crate::{invariants, mock::*};
+use crittercraft_traits::{AdvancedPetManagement, LockerId, SharedNftManager};
+use frame_support::{assert_noop, traits::{Currency, ExistenceRequirement}};
+use pallet_critter_battle::{BattleMove, BattleOutcome, BattleStatus};
+use pallet_critter_nfts::kinds::CareActionKind;
+use pallet_user_profile::ScoreContributor;
+
+/// The NFT trait surface exactly as pallet-critter-battle and the marketplace see it.
+type Nfts = <Test as pallet_critter_battle::Config>::NftManager;
+
+/// Any food or toy; `UnlimitedCareItems` stocks them all.
+const ITEM: u32 = 1;
+
+/// Asserts a call succeeded, naming the pallet it was made to on failure.
+macro_rules! assert_call {
+    ($pallet:literal, $call:expr) => {
+        if let Err(error) = $call {
+            panic!("[{}] call failed: {:?}", $pallet, error);
+        }
+    };
+}
+
+// Mints a pet for `owner` and returns its id.
+fn mint(owner: u64, name: &[u8]) -> u32 {
+    let pet_id = CritterNfts::next_pet_id();
+    assert_call!("pallet-critter-nfts", CritterNfts::mint_pet_nft(RuntimeOrigin::signed(owner), b"Critter".to_vec(), name.to_vec()));
+    pet_id
+}
+
+// Mints a pet for `owner` and gives it a status, as onboarding does.
+fn adopt(owner: u64, name: &[u8]) -> u32 {
+    let pet_id = mint(owner, name);
+    assert_call!("pallet-critter-pet-status", PetStatus::initialize_pet_status(RuntimeOrigin::signed(owner), pet_id));
+    pet_id
+}
+
+// Both sides attack on their turn until the battle ends.
+fn fight_to_end(battle_id: u32, pet1_owner: u64, pet2_owner: u64) {
+    let max_turns = CritterBattle::battle_parameters().unwrap().max_turns;
+    for _ in 0..=max_turns {
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        if battle.status != BattleStatus::Active {
+            return;
+        }
+        let player = if battle.current_turn % 2 == 1 { pet1_owner } else { pet2_owner };
+        assert_call!("pallet-critter-battle", CritterBattle::execute_move(RuntimeOrigin::signed(player), battle_id, BattleMove::Attack));
+    }
+    panic!("[pallet-critter-battle] battle {} outlasted max_turns", battle_id);
+}
+
+// A pet's total progress, so XP grants show up across level-ups.
+fn progress(pet_id: u32) -> u32 {
+    let pet = CritterNfts::pet_nfts(pet_id).unwrap();
+    pet.level * 1_000 + pet.experience_points
+}
+
+#[test]
+fn two_players_from_mint_to_battle_rewards_and_score() {
+    new_test_ext().execute_with(|| {
+        let players = [ALICE, BOB];
+
+        // 1. Both players adopt a pet.
+        let alice_pet = adopt(ALICE, b"Ember");
+        let bob_pet = adopt(BOB, b"Ripple");
+        invariants::check_all(&players);
+
+        // 2. Train through play, in both the NFT and the status pallet.
+        run_to_block(20);
+        for (owner, pet_id) in [(ALICE, alice_pet), (BOB, bob_pet)] {
+            assert_call!("pallet-critter-nfts", CritterNfts::play_with_pet(RuntimeOrigin::signed(owner), pet_id, ITEM));
+            assert_call!("pallet-critter-pet-status", PetStatus::play_with_pet(RuntimeOrigin::signed(owner), pet_id));
+        }
+        invariants::check_all(&players);
+        let progress_before = [progress(alice_pet), progress(bob_pet)];
+
+        // 3. Alice challenges Bob, who accepts; the pets fight until one wins or turns run out.
+        let battle_id = CritterBattle::battle_count();
+        assert_call!("pallet-critter-battle", CritterBattle::create_challenge(RuntimeOrigin::signed(ALICE), alice_pet, bob_pet));
+        assert_eq!(Balances::reserved_balance(ALICE), CritterBattle::battle_parameters().unwrap().challenge_bond);
+        assert_call!("pallet-critter-battle", CritterBattle::accept_challenge(RuntimeOrigin::signed(BOB), battle_id));
+        invariants::check_all(&players);
+        fight_to_end(battle_id, ALICE, BOB);
+        invariants::check_all(&players);
+
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        assert_eq!(battle.status, BattleStatus::Completed);
+        let winner = match battle.outcome {
+            Some(BattleOutcome::Pet1Win) => Some(ALICE),
+            Some(BattleOutcome::Pet2Win) => Some(BOB),
+            _ => None,
+        };
+
+        // 4. Battle XP reached the NFTs through `AdvancedPetManagement`.
+        assert!(progress(alice_pet) > progress_before[0], "[pallet-critter-nfts] Alice's pet gained no battle XP");
+        assert!(progress(bob_pet) > progress_before[1], "[pallet-critter-nfts] Bob's pet gained no battle XP");
+
+        // 5. The winner (Alice on a draw) claims the reward, which also releases the bond.
+        let claimer = winner.unwrap_or(ALICE);
+        let balance_before = Balances::free_balance(claimer);
+        assert_call!("pallet-critter-battle", CritterBattle::claim_rewards(RuntimeOrigin::signed(claimer), battle_id));
+        assert!(Balances::free_balance(claimer) > balance_before, "[pallet-critter-battle] the claim paid nothing");
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_noop!(
+            CritterBattle::claim_rewards(RuntimeOrigin::signed(claimer), battle_id),
+            pallet_critter_battle::Error::<Test>::RewardsAlreadyClaimed
+        );
+
+        // 6. Refreshed scores add up levels from the NFT pallet and the record from the battle pallet.
+        for player in players {
+            UserProfile::update_profile_score(&player);
+            invariants::score_is_fresh(player);
+        }
+        match winner {
+            Some(winner) => {
+                let loser = if winner == ALICE { BOB } else { ALICE };
+                assert!(UserProfile::score_of(&winner) >= pallet_critter_battle::SCORE_PER_WIN + 1);
+                assert!(UserProfile::score_of(&winner) > UserProfile::score_of(&loser));
+            },
+            None => assert_eq!(UserProfile::score_of(&ALICE), UserProfile::score_of(&BOB)),
+        }
+        invariants::check_all(&players);
+    });
+}
+
+#[test]
+fn neglected_pet_recovers_in_both_pallets() {
+    new_test_ext().execute_with(|| {
+        let players = [ALICE, BOB];
+        let pet_id = adopt(ALICE, b"Mochi");
+        let start = System::block_number();
+
+        // 1. Leave the pet alone past the NFT neglect threshold; needs decay meanwhile.
+        let neglected_at = start + 60;
+        run_to_block(neglected_at);
+        invariants::check_all(&players);
+        assert!(<CritterNfts as AdvancedPetManagement<u64, u64>>::is_pet_neglected(&pet_id));
+        let decays = ((neglected_at - start) / 10) as u8;
+        assert_eq!(PetStatus::pet_needs(pet_id).unwrap().hunger, 100 - decays * 10);
+
+        // 2. Anyone can apply the neglect penalty.
+        let mood_before = CritterNfts::pet_nfts(pet_id).unwrap().mood_indicator;
+        assert_call!("pallet-critter-nfts", CritterNfts::apply_neglect_check(RuntimeOrigin::signed(BOB), pet_id));
+        let neglected_mood = CritterNfts::pet_nfts(pet_id).unwrap().mood_indicator;
+        assert!(neglected_mood < mood_before, "[pallet-critter-nfts] neglect did not lower mood");
+        invariants::check_all(&players);
+
+        // 3. The owner feeds and plays with the pet in both pallets.
+        let hunger_before = PetStatus::pet_needs(pet_id).unwrap().hunger;
+        assert_call!("pallet-critter-nfts", CritterNfts::feed_pet(RuntimeOrigin::signed(ALICE), pet_id, ITEM));
+        assert_call!("pallet-critter-nfts", CritterNfts::play_with_pet(RuntimeOrigin::signed(ALICE), pet_id, ITEM));
+        assert_call!("pallet-critter-pet-status", PetStatus::feed_pet(RuntimeOrigin::signed(ALICE), pet_id));
+        assert_call!("pallet-critter-pet-status", PetStatus::play_with_pet(RuntimeOrigin::signed(ALICE), pet_id));
+        invariants::check_all(&players);
+
+        // 4. The pet is no longer neglected, and its mood and needs went back up.
+        assert!(!<CritterNfts as AdvancedPetManagement<u64, u64>>::is_pet_neglected(&pet_id));
+        assert!(CritterNfts::pet_nfts(pet_id).unwrap().mood_indicator > neglected_mood);
+        assert!(PetStatus::pet_needs(pet_id).unwrap().hunger > hunger_before);
+
+        // 5. A neglect check right after care changes nothing.
+        let recovered_mood = CritterNfts::pet_nfts(pet_id).unwrap().mood_indicator;
+        assert_call!("pallet-critter-nfts", CritterNfts::apply_neglect_check(RuntimeOrigin::signed(BOB), pet_id));
+        assert_eq!(CritterNfts::pet_nfts(pet_id).unwrap().mood_indicator, recovered_mood);
+    });
+}
+
+#[test]
+fn pet_mood_follows_needs_and_is_reported_when_it_changes() {
+    use pallet_critter_pet_status::PetMood;
+
+    let mood_changes = || -> Vec<(u32, PetMood)> {
+        System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::PetStatus(pallet_critter_pet_status::Event::PetMoodChanged { pet_id, mood }) => Some((pet_id, mood)),
+                _ => None,
+            })
+            .collect()
+    };
+
+    new_test_ext().execute_with(|| {
+        let pet_id = adopt(ALICE, b"Pip");
+        assert_eq!(PetStatus::get_pet_mood(pet_id), Some(PetMood::Happy));
+        System::reset_events();
+
+        // Two decays leave every need at 80 and the pet happy; the third moves it to content, once.
+        run_to_block(30);
+        assert_eq!(PetStatus::get_pet_mood(pet_id), Some(PetMood::Happy));
+        assert!(mood_changes().is_empty());
+        run_to_block(31);
+        assert_eq!(PetStatus::get_pet_mood(pet_id), Some(PetMood::Content));
+        assert_eq!(mood_changes(), vec![(pet_id, PetMood::Content)]);
+
+        // Restoring needs restores the mood with them: feeding alone is not enough, playing is.
+        assert_call!("pallet-critter-pet-status", PetStatus::feed_pet(RuntimeOrigin::signed(ALICE), pet_id));
+        assert_eq!(PetStatus::get_pet_mood(pet_id), Some(PetMood::Content));
+        assert_call!("pallet-critter-pet-status", PetStatus::play_with_pet(RuntimeOrigin::signed(ALICE), pet_id));
+        assert_eq!(PetStatus::get_pet_mood(pet_id), Some(PetMood::Happy));
+        assert_eq!(mood_changes(), vec![(pet_id, PetMood::Content), (pet_id, PetMood::Happy)]);
+        invariants::check_all(&[ALICE]);
+    });
+}
+
+#[test]
+fn pet_statuses_stored_with_a_mood_decode_after_the_v1_migration() {
+    use codec::Encode;
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+    use frame_support::{storage::storage_prefix, Blake2_128Concat, StorageHasher};
+    use pallet_critter_pet_status::{migrations::v1::{MigrateToV1, OldPetStatus}, PetMood};
+
+    let prefix = storage_prefix(b"PetStatus", b"PetStatuses");
+    let key = |pet_id: u32| [prefix.as_slice(), &Blake2_128Concat::hash(&pet_id.encode())].concat();
+    let old_status = |pet_id: u32, owner: u64, mood: PetMood| OldPetStatus::<u64, u64> {
+        pet_id,
+        owner,
+        mood,
+        last_interaction: 7,
+        last_fed: 5,
+        last_rested: 4,
+        last_played: 6,
+        last_groomed: 3,
+        last_socialized: 2,
+    };
+
+    new_test_ext().execute_with(|| {
+        let fixture = StorageFixture { storage_version: 0, entries: Vec::new() }
+            .with_entry(key(0), old_status(0, ALICE, PetMood::Sad).encode())
+            .with_entry(key(1), old_status(1, BOB, PetMood::Happy).encode());
+        run_migration::<PetStatus, MigrateToV1<Test>>(&fixture, 1);
+
+        assert_eq!(assert_all_decode::<pallet_critter_pet_status::PetStatus<Test>>(&prefix), 2);
+        let status = PetStatus::pet_status(0).unwrap();
+        assert_eq!((status.pet_id, status.owner, status.last_interaction), (0, ALICE, 7));
+        assert_eq!((status.last_fed, status.last_rested, status.last_played), (5, 4, 6));
+        assert_eq!((status.last_groomed, status.last_socialized), (3, 2));
+        assert_eq!(PetStatus::pet_status(1).unwrap().owner, BOB);
+
+        // A second run finds version 1 and leaves the statuses alone.
+        run_migration::<PetStatus, MigrateToV1<Test>>(&StorageFixture { storage_version: 1, entries: Vec::new() }, 1);
+        assert_eq!(assert_all_decode::<pallet_critter_pet_status::PetStatus<Test>>(&prefix), 2);
+    });
+}
+
+#[test]
+fn recovered_pets_are_briefly_immune_to_the_same_condition() {
+    use frame_support::{traits::Hooks, weights::Weight};
+    use pallet_critter_pet_status::{ConditionSeverity, ConditionType, Error};
+
+    new_test_ext().execute_with(|| {
+        let pet_id = adopt(ALICE, b"Sniffles");
+        let condition_id = PetStatus::next_condition_id();
+        assert_call!(
+            "pallet-critter-pet-status",
+            PetStatus::create_condition(
+                RuntimeOrigin::root(),
+                b"Cold".to_vec().try_into().unwrap(),
+                b"A passing cold".to_vec().try_into().unwrap(),
+                ConditionType::Negative,
+                ConditionSeverity::Minor,
+                10,
+                Vec::new(),
+                Vec::new(),
+            )
+        );
+        let apply = || PetStatus::apply_condition(RuntimeOrigin::root(), pet_id, condition_id);
+        assert_call!("pallet-critter-pet-status", apply());
+
+        // The condition runs out at block 11, leaving the pet immune through block 61.
+        run_to_block(11);
+        assert!(PetStatus::pet_conditions(pet_id).is_empty());
+        assert_eq!(PetStatus::condition_immunity(pet_id, condition_id), Some(61));
+        System::assert_has_event(RuntimeEvent::PetStatus(pallet_critter_pet_status::Event::ConditionImmunityGranted {
+            pet_id,
+            condition_id,
+            expires_at: 61,
+        }));
+        assert_noop!(apply(), Error::<Test>::ConditionImmunityActive);
+        run_to_block(61);
+        assert_noop!(apply(), Error::<Test>::ConditionImmunityActive);
+
+        // Idle blocks sweep the expired record, and the pet can catch the cold again.
+        run_to_block(62);
+        PetStatus::on_idle(62, Weight::MAX);
+        assert_eq!(PetStatus::condition_immunity(pet_id, condition_id), None);
+        assert_call!("pallet-critter-pet-status", apply());
+        invariants::check_all(&[ALICE]);
+    });
+}
+
+#[test]
+fn immunity_sweeps_resume_where_the_last_idle_block_stopped() {
+    use frame_support::{traits::Hooks, weights::Weight};
+    use pallet_critter_pet_status::{ConditionSeverity, ConditionType};
+
+    new_test_ext().execute_with(|| {
+        let pets = [adopt(ALICE, b"Sniffles"), adopt(ALICE, b"Sneezy"), adopt(BOB, b"Wheezy")];
+        let condition_id = PetStatus::next_condition_id();
+        assert_call!(
+            "pallet-critter-pet-status",
+            PetStatus::create_condition(
+                RuntimeOrigin::root(),
+                b"Cold".to_vec().try_into().unwrap(),
+                b"A passing cold".to_vec().try_into().unwrap(),
+                ConditionType::Negative,
+                ConditionSeverity::Minor,
+                10,
+                Vec::new(),
+                Vec::new(),
+            )
+        );
+        let apply = |pet_id| PetStatus::apply_condition(RuntimeOrigin::root(), pet_id, condition_id);
+
+        // The first two pets are immune through block 61, the third through block 65.
+        assert_call!("pallet-critter-pet-status", apply(pets[0]));
+        assert_call!("pallet-critter-pet-status", apply(pets[1]));
+        run_to_block(5);
+        assert_call!("pallet-critter-pet-status", apply(pets[2]));
+        run_to_block(62);
+        let immune = || pets.iter().filter(|pet_id| PetStatus::condition_immunity(**pet_id, condition_id).is_some()).count();
+        assert_eq!(immune(), 3);
+
+        // Too little weight for one record sweeps nothing.
+        let one_record = PetStatus::immunity_sweep_weight(1);
+        assert_eq!(PetStatus::on_idle(62, one_record.saturating_sub(Weight::from_parts(0, 1))), Weight::zero());
+        assert_eq!(immune(), 3);
+
+        // One record per idle block: the live record does not hold up the expired ones behind it.
+        for _ in 0..pets.len() {
+            assert_eq!(PetStatus::on_idle(62, one_record), one_record);
+            assert!(PetStatus::immunity_cleanup_cursor().is_some());
+        }
+        assert_eq!(immune(), 1);
+        assert!(PetStatus::condition_immunity(pets[2], condition_id).is_some());
+
+        // The next sweep finds the end and starts over.
+        PetStatus::on_idle(62, one_record);
+        assert_eq!(PetStatus::immunity_cleanup_cursor(), None);
+        run_to_block(66);
+        PetStatus::on_idle(66, Weight::MAX);
+        assert_eq!(immune(), 0);
+    });
+}
+
+#[test]
+fn active_conditions_modify_effective_stats_until_cured_or_expired() {
+    use crittercraft_traits::nft::PetConditionProvider;
+    use pallet_critter_pet_status::{ConditionSeverity, ConditionType, Error, StatType};
+
+    let create = |name: &[u8], duration: u64, stat_modifiers: Vec<(StatType, i8)>| {
+        let condition_id = PetStatus::next_condition_id();
+        assert_call!(
+            "pallet-critter-pet-status",
+            PetStatus::create_condition(
+                RuntimeOrigin::root(),
+                name.to_vec().try_into().unwrap(),
+                b"".to_vec().try_into().unwrap(),
+                ConditionType::Negative,
+                ConditionSeverity::Moderate,
+                duration,
+                stat_modifiers,
+                Vec::new(),
+            )
+        );
+        condition_id
+    };
+
+    new_test_ext().execute_with(|| {
+        let pet_id = adopt(ALICE, b"Patch");
+        let flu = create(b"Flu", 100, vec![(StatType::Strength, -4), (StatType::Vitality, -15)]);
+        let sprain = create(b"Sprain", 5, vec![(StatType::Agility, -3), (StatType::Strength, -2)]);
+
+        // 1. Only an authorized origin applies conditions.
+        assert_noop!(PetStatus::apply_condition(RuntimeOrigin::signed(ALICE), pet_id, flu), sp_runtime::DispatchError::BadOrigin);
+        assert_call!("pallet-critter-pet-status", PetStatus::apply_condition(RuntimeOrigin::root(), pet_id, flu));
+        assert_call!("pallet-critter-pet-status", PetStatus::inflict_condition(pet_id, sprain));
+
+        // 2. Modifiers add up in the effective stats, clamped at zero, and never touch the stored stats.
+        let stored = PetStatus::pet_stats(pet_id).unwrap();
+        assert_eq!(stored.strength, 10);
+        let effective = PetStatus::effective_stats(pet_id).unwrap();
+        assert_eq!((effective.strength, effective.agility, effective.vitality), (4, 7, 0));
+        assert_eq!(StatusConditions::stat_modifier(&pet_id).strength, -6);
+
+        // 3. The sprain stops counting once it expires.
+        run_to_block(6);
+        assert_eq!(PetStatus::effective_stats(pet_id).unwrap().agility, 10);
+        assert_eq!(StatusConditions::active_conditions(&pet_id), vec![flu]);
+
+        // 4. Only the owner can cure the flu, which restores the effective stats.
+        assert_noop!(
+            PetStatus::cure_condition(RuntimeOrigin::signed(BOB), pet_id, flu),
+            Error::<Test>::PetNotOwnedBySender
+        );
+        assert_call!("pallet-critter-pet-status", PetStatus::cure_condition(RuntimeOrigin::signed(ALICE), pet_id, flu));
+        System::assert_has_event(RuntimeEvent::PetStatus(pallet_critter_pet_status::Event::PetConditionCured {
+            pet_id,
+            condition_id: flu,
+            by: ALICE,
+        }));
+        assert_eq!(PetStatus::effective_stats(pet_id), PetStatus::pet_stats(pet_id));
+        assert_eq!(PetStatus::pet_stats(pet_id), Some(stored));
+        assert_noop!(
+            PetStatus::cure_condition(RuntimeOrigin::signed(ALICE), pet_id, flu),
+            Error::<Test>::PetDoesNotHaveCondition
+        );
+        invariants::check_all(&[ALICE]);
+    });
+}
+
+#[test]
+fn v2_migration_takes_active_condition_modifiers_out_of_stored_stats() {
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+    use pallet_critter_pet_status::{migrations::v2::MigrateToV2, ConditionSeverity, ConditionType, StatType};
+
+    new_test_ext().execute_with(|| {
+        let (sick, healthy) = (adopt(ALICE, b"Patch"), adopt(BOB, b"Rex"));
+        let flu = PetStatus::next_condition_id();
+        assert_call!(
+            "pallet-critter-pet-status",
+            PetStatus::create_condition(
+                RuntimeOrigin::root(),
+                b"Flu".to_vec().try_into().unwrap(),
+                b"".to_vec().try_into().unwrap(),
+                ConditionType::Negative,
+                ConditionSeverity::Moderate,
+                100,
+                vec![(StatType::Strength, -4), (StatType::Charisma, 3)],
+                Vec::new(),
+            )
+        );
+        assert_call!("pallet-critter-pet-status", PetStatus::apply_condition(RuntimeOrigin::root(), sick, flu));
+        let base = PetStatus::pet_stats(sick).unwrap();
+
+        // Before version 2 the flu's modifiers were written into the stored stats; only the admin
+        // origin can set stats directly.
+        let bake = |origin| {
+            PetStatus::update_pet_stats(origin, sick, Some(base.strength - 4), None, None, None, Some(base.charisma + 3))
+        };
+        assert_noop!(bake(RuntimeOrigin::signed(ALICE)), sp_runtime::DispatchError::BadOrigin);
+        assert_call!("pallet-critter-pet-status", bake(RuntimeOrigin::root()));
+        let healthy_stats = PetStatus::pet_stats(healthy);
+
+        run_migration::<PetStatus, MigrateToV2<Test>>(&StorageFixture { storage_version: 1, entries: Vec::new() }, 2);
+
+        assert_eq!(PetStatus::pet_stats(sick), Some(base.clone()));
+        assert_eq!(PetStatus::pet_stats(healthy), healthy_stats);
+        let effective = PetStatus::effective_stats(sick).unwrap();
+        assert_eq!((effective.strength, effective.charisma), (base.strength - 4, base.charisma + 3));
+
+        // A second run finds version 2 and leaves the stats alone.
+        run_migration::<PetStatus, MigrateToV2<Test>>(&StorageFixture { storage_version: 2, entries: Vec::new() }, 2);
+        assert_eq!(PetStatus::pet_stats(sick), Some(base));
+    });
+}
+
+#[test]
+fn needs_decay_a_bounded_batch_per_block_and_catch_up() {
+    use frame_support::traits::Hooks;
+
+    let decays_this_block = || {
+        System::events()
+            .into_iter()
+            .filter(|record| matches!(record.event, RuntimeEvent::PetStatus(pallet_critter_pet_status::Event::PetNeedsDecayed { .. })))
+            .count()
+    };
+
+    new_test_ext().execute_with(|| {
+        let players = [ALICE, BOB];
+        let pets: Vec<u32> = [(ALICE, b"Ash"), (ALICE, b"Bay"), (ALICE, b"Cob"), (BOB, b"Dew"), (BOB, b"Elm")]
+            .into_iter()
+            .map(|(owner, name)| adopt(owner, name))
+            .collect();
+
+        // 1. Five pets are due at block 11, but no block decays more than four of them.
+        for block in 2..=12 {
+            System::reset_events();
+            run_to_block(block);
+            assert!(decays_this_block() <= 4, "{} pets decayed in block {}", decays_this_block(), block);
+        }
+        for pet_id in pets.iter() {
+            assert_eq!(PetStatus::pet_needs(pet_id).unwrap().hunger, 90);
+            assert_eq!(PetStatus::last_need_decay(pet_id), Some(11));
+        }
+
+        // 2. Three elapsed intervals decay three times, keeping the part of the next one elapsed.
+        System::set_block_number(45);
+        PetStatus::on_initialize(45);
+        PetStatus::on_initialize(45);
+        for pet_id in pets.iter() {
+            assert_eq!(PetStatus::pet_needs(pet_id).unwrap().hunger, 60);
+            assert_eq!(PetStatus::last_need_decay(pet_id), Some(41));
+        }
+
+        // 3. A long neglect decays at most `MAX_CATCH_UP_DECAYS` times, and needs stop at zero.
+        System::set_block_number(1_000);
+        PetStatus::on_initialize(1_000);
+        PetStatus::on_initialize(1_000);
+        for pet_id in pets.iter() {
+            assert_eq!(PetStatus::pet_needs(pet_id).unwrap().hunger, 0);
+            assert_eq!(PetStatus::get_pet_mood(*pet_id), Some(pallet_critter_pet_status::PetMood::Distressed));
+            assert_eq!(PetStatus::last_need_decay(pet_id), Some(1_000));
+        }
+        invariants::check_all(&players);
+    });
+}
+
+#[test]
+fn sold_pet_moves_through_the_shared_trait_only() {
+    new_test_ext().execute_with(|| {
+        let players = [ALICE, BOB];
+        let price = 500;
+        let pet_id = mint(ALICE, b"Nimbus");
+        let alice_other_pet = mint(ALICE, b"Pebble");
+
+        // 1. Listing locks the pet for the marketplace.
+        assert_call!("pallet-critter-nfts", Nfts::lock_nft(&ALICE, &pet_id, LockerId::Marketplace));
+        assert!(!Nfts::is_transferable(&pet_id));
+        invariants::check_all(&players);
+
+        // 2. While listed, the seller cannot move it and no other pallet can release it.
+        assert_noop!(
+            CritterNfts::transfer_pet_nft(RuntimeOrigin::signed(ALICE), BOB, pet_id),
+            pallet_critter_nfts::Error::<Test>::NftLocked
+        );
+        assert_noop!(
+            Nfts::unlock_nft(&ALICE, &pet_id, LockerId::Battle),
+            pallet_critter_nfts::Error::<Test>::NotLockHolder
+        );
+
+        // 3. Bob buys it: payment first, then the marketplace releases and transfers the pet.
+        assert_call!("pallet-balances", <Balances as Currency<u64>>::transfer(&BOB, &ALICE, price, ExistenceRequirement::KeepAlive));
+        assert_call!("pallet-critter-nfts", Nfts::unlock_nft(&ALICE, &pet_id, LockerId::Marketplace));
+        assert_call!("pallet-critter-nfts", Nfts::transfer_nft(&ALICE, &BOB, &pet_id));
+        invariants::check_all(&players);
+
+        assert_eq!(Nfts::owner_of(&pet_id), Some(BOB));
+        assert!(Nfts::is_transferable(&pet_id));
+        assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + price);
+        assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - price);
+
+        // 4. The battle pallet sees the new owner through the same trait: Bob can fight with
+        //    the pet, Alice no longer can, and a pet in battle cannot be listed again.
+        assert_noop!(
+            CritterBattle::create_challenge(RuntimeOrigin::signed(ALICE), pet_id, alice_other_pet),
+            pallet_critter_battle::Error::<Test>::NotPetOwner
+        );
+        assert_call!("pallet-critter-battle", CritterBattle::create_challenge(RuntimeOrigin::signed(BOB), pet_id, alice_other_pet));
+        let eligibility = CritterNfts::listing_eligibility(pet_id).unwrap();
+        assert_eq!((eligibility.owner, eligibility.transferable), (BOB, false));
+        assert_eq!(eligibility.reason, Some(crittercraft_traits::types::TransferBlockReason::InBattle));
+        invariants::check_all(&players);
+    });
+}
+
+#[test]
+fn care_through_both_pallets_is_reported_once() {
+    new_test_ext().execute_with(|| {
+        let pet_id = adopt(ALICE, b"Biscuit");
+        run_to_block(20);
+        System::reset_events();
+
+        // The owner feeds and plays with the pet in both pallets, as the app does.
+        assert_call!("pallet-critter-nfts", CritterNfts::feed_pet(RuntimeOrigin::signed(ALICE), pet_id, ITEM));
+        assert_call!("pallet-critter-pet-status", PetStatus::feed_pet(RuntimeOrigin::signed(ALICE), pet_id));
+        assert_call!("pallet-critter-nfts", CritterNfts::play_with_pet(RuntimeOrigin::signed(ALICE), pet_id, ITEM));
+        assert_call!("pallet-critter-pet-status", PetStatus::play_with_pet(RuntimeOrigin::signed(ALICE), pet_id));
+        invariants::check_all(&[ALICE]);
+
+        // One care event per action, all from the NFT pallet; the status pallet only reports needs.
+        let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+        let care_kinds: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                RuntimeEvent::CritterNfts(pallet_critter_nfts::Event::CareActionPerformed { pet_id: cared, actor, kind, .. }) => {
+                    assert_eq!((*cared, *actor), (pet_id, ALICE));
+                    Some(*kind)
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(care_kinds, vec![CareActionKind::Feed, CareActionKind::Play]);
+        assert!(events.iter().any(|event| matches!(event, RuntimeEvent::PetStatus(pallet_critter_pet_status::Event::PetFed { .. }))));
+        assert!(events.iter().any(|event| matches!(event, RuntimeEvent::PetStatus(pallet_critter_pet_status::Event::PetPlayed { .. }))));
+
+        // With legacy events off, the NFT pallet's old care events are gone.
+        assert!(!events.iter().any(|event| matches!(
+            event,
+            RuntimeEvent::CritterNfts(pallet_critter_nfts::Event::PetFed { .. })
+                | RuntimeEvent::CritterNfts(pallet_critter_nfts::Event::PetPlayedWith { .. })
+        )));
+    });
+}
+
+#[test]
+fn round_up_donations_reach_the_treasury_and_the_score() {
+    use pallet_critter_nfts::{auto_care::AUTO_CARE_FEED, donations::DonationTarget};
+
+    new_test_ext().execute_with(|| {
+        let pet_id = adopt(ALICE, b"Ember");
+        assert_call!(
+            "pallet-critter-nfts",
+            CritterNfts::set_donation_roundup(
+                RuntimeOrigin::signed(ALICE),
+                true,
+                sp_runtime::Perbill::from_percent(10),
+                DonationTarget::Treasury,
+            )
+        );
+
+        // Five intervals at 100 PTCN each, rounded up by 10%.
+        let balance_before = Balances::free_balance(ALICE);
+        assert_call!("pallet-critter-nfts", CritterNfts::subscribe_auto_care(RuntimeOrigin::signed(ALICE), pet_id, AUTO_CARE_FEED, 5));
+        assert_eq!(Balances::free_balance(ALICE), balance_before - 550);
+        assert_eq!(Balances::free_balance(TREASURY), 50);
+        assert_eq!(CritterNfts::total_donated(ALICE), 50);
+
+        // Donations count towards the Zoologist score.
+        UserProfile::update_profile_score(&ALICE);
+        invariants::score_is_fresh(ALICE);
+        assert_eq!(DonationScore::get_score_contribution(&ALICE), 5);
+        assert_eq!(DonationScore::get_score_contribution(&BOB), 0);
+        invariants::check_all(&[ALICE]);
+    });
+}
+
+#[test]
+fn pallet_contributors_score_levels_achievements_and_battle_records() {
+    type BattleScore = pallet_critter_battle::BattleRecordScore<Test, OwnedPets>;
+
+    new_test_ext().execute_with(|| {
+        let pet_id = adopt(ALICE, b"Ember");
+        let level = CritterNfts::pet_nfts(pet_id).unwrap().level as u64;
+        assert_eq!(CritterNfts::get_score_contribution(&ALICE), level);
+        assert_eq!(BattleScore::get_score_contribution(&ALICE), 0);
+
+        // Achievements add to the NFT pallet's part, wins and draws to the battle pallet's.
+        pallet_critter_nfts::PetAchievements::<Test>::insert(pet_id, sp_runtime::BoundedVec::truncate_from(vec![(1, 0), (2, 0)]));
+        pallet_critter_battle::PetBattleStats::<Test>::insert(pet_id, (2, 5, 1, 1000));
+        let battle_score = 2 * pallet_critter_battle::SCORE_PER_WIN + pallet_critter_battle::SCORE_PER_DRAW;
+        assert_eq!(CritterNfts::get_score_contribution(&ALICE), level + 2);
+        assert_eq!(BattleScore::get_score_contribution(&ALICE), battle_score);
+
+        assert_eq!(UserProfile::update_profile_score(&ALICE), level + 2 + battle_score);
+        invariants::score_is_fresh(ALICE);
+        assert_eq!(UserProfile::user_profiles(ALICE).last_active_block, 1);
+
+        // Only the account's own pets count.
+        assert_eq!(CritterNfts::get_score_contribution(&BOB), 0);
+        assert_eq!(BattleScore::get_score_contribution(&BOB), 0);
+        invariants::check_all(&[ALICE, BOB]);
+    });
+}
+
+#[test]
+fn pets_in_battle_cannot_be_released_and_released_pets_leave_no_battle() {
+    use crittercraft_traits::nft::ExistenceChecker;
+
+    new_test_ext().execute_with(|| {
+        let alice_pet = mint(ALICE, b"Ember");
+        let bob_pet = mint(BOB, b"Ripple");
+        let battle_id = CritterBattle::battle_count();
+        assert_call!("pallet-critter-battle", CritterBattle::create_challenge(RuntimeOrigin::signed(ALICE), alice_pet, bob_pet));
+
+        // Both pets are held by the challenge.
+        assert_noop!(
+            CritterNfts::release_pet(RuntimeOrigin::signed(BOB), bob_pet),
+            pallet_critter_nfts::Error::<Test>::ReleaseBlockedByBattle
+        );
+
+        // Root can still force the release; the battle is voided and Alice's pet is free again.
+        assert_call!("pallet-critter-nfts", CritterNfts::force_release(RuntimeOrigin::root(), bob_pet));
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().status, BattleStatus::Expired);
+        assert!(CritterBattle::pet_active_battle(alice_pet).is_none());
+        assert!(CritterNfts::was_released(&bob_pet));
+        assert_call!("pallet-critter-nfts", CritterNfts::release_pet(RuntimeOrigin::signed(ALICE), alice_pet));
+        invariants::check_all(&[ALICE, BOB]);
+    });
+}
@@ -20,7 +20,11 @@ use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
 
 // --- Crate-level Type Aliases for Clarity (K - Keep it Clear) ---
-use critterchain_runtime::{opaque::Block, AccountId, Balance, Nonce, RuntimeApi};
+use critterchain_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Nonce, RuntimeApi};
+use pallet_critter_nfts::{
+    rpc::{PetNftsRpc, PetNftsRpcApiServer},
+    runtime_api::PetNftsApi,
+};
 pub use sc_rpc_api::DenyUnsafe;
 
 /// A type representing all client dependencies required by the RPC builder.
@@ -44,7 +48,7 @@ pub struct RpcModuleBuilder<C, P> {
 impl<C, P> RpcModuleBuilder<C, P>
 where
     C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
-    C::Api: RuntimeApi,
+    C::Api: RuntimeApi + PetNftsApi<Block, AccountId, BlockNumber>,
     P: TransactionPool<Block = Block> + 'static,
 {
     /// Creates a new `RpcModuleBuilder`.
@@ -112,12 +116,15 @@ where
         // This assumes a `critterchain_runtime_rpc` crate with these API servers defined.
         use critterchain_runtime_rpc::{
             CritterchainApiServer, // A generic API for top-level info
-            PetNftApiServer,     // Specific API for querying Pet NFTs
             MarketplaceApiServer, // Specific API for marketplace listings
         };
 
+        // Pet profiles, owned pets and pet summaries, served from `PetNftsApi`.
+        self.module
+            .merge(PetNftsRpc::new(self.deps.client.clone()).into_rpc())
+            .map_err(|e| format!("Failed to merge Pet NFTs RPC: {}", e))?;
+
         // These would be implemented in your runtime-rpc crate.
-        // self.module.merge(PetNftApiServer::new(self.deps.client.clone()).into_rpc())?;
         // self.module.merge(MarketplaceApiServer::new(self.deps.client.clone()).into_rpc())?;
         
         // For now, we merge the placeholder top-level API.
@@ -143,7 +150,8 @@ where
         + Send
         + Sync
         + 'static,
-    C::Api: critterchain_runtime_rpc::CritterchainRuntimeApi<Block>, // Ensure runtime implements the custom API trait
+    C::Api: critterchain_runtime_rpc::CritterchainRuntimeApi<Block> // Ensure runtime implements the custom API traits
+        + PetNftsApi<Block, AccountId, BlockNumber>,
     P: TransactionPool<Block = Block> + 'static,
 {
     RpcModuleBuilder::new(deps)
@@ -164,7 +172,7 @@ where
         + Send
         + Sync
         + 'static,
-    C::Api: critterchain_runtime_rpc::CritterchainRuntimeApi<Block>,
+    C::Api: critterchain_runtime_rpc::CritterchainRuntimeApi<Block> + PetNftsApi<Block, AccountId, BlockNumber>,
     P: TransactionPool<Block = Block> + 'static,
 {
     // Light clients get system and game-specific RPCs, but not consensus authoring RPCs.
@@ -0,0 +1,31 @@
+//! # Critter Battle Runtime API
+//!
+//! Declares `CritterBattleRuntimeApi`, through which players predict a battle between two pets
+//! before committing a challenge bond. The battle pallet implements the prediction in
+//! `Pallet::simulate_battle`; nodes serve it over JSON-RPC with the pallet's `rpc` module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use crittercraft_traits::PetId;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+// Define the predicted outcome of a battle between two pets, from repeated simulated battles
+#[derive(Clone, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationResult {
+    pub pet1_win_probability: u8, // The percentage of simulated battles pet1 won
+    pub expected_turns: u8, // The average length of the simulated battles; zero if there were none
+    pub elemental_advantage: bool, // Whether pet1's element has the advantage over pet2's
+}
+
+sp_api::decl_runtime_apis! {
+    /// The API to predict battles before challenging.
+    pub trait CritterBattleRuntimeApi {
+        /// Returns the predicted outcome of a battle between two pets under the current
+        /// parameters, from their current stats. A pet whose stats cannot be read gives the
+        /// default result, with no simulated battles.
+        fn simulate_battle(pet1_id: PetId, pet2_id: PetId) -> SimulationResult;
+    }
+}
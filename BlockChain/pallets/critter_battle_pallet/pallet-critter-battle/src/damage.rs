@@ -0,0 +1,98 @@
+//! # Damage Formulas
+//!
+//! The damage a battle move deals, and the rolls that decide critical hits, misses and dodges.
+//! Every move of a turn rolls once: the first byte of the `BattleRandomness` seed, modulo 100.
+//!
+//! - Attack: `5 + strength / 10`, doubled on a critical hit (a roll below
+//!   `CRITICAL_HIT_CHANCE`).
+//! - Special attack: `15 + strength / 5`, if it hits (a roll below
+//!   `SPECIAL_ATTACK_HIT_CHANCE`, less the defender's dodge bonus).
+//! - Elemental attack: `10 + strength / 8`, scaled by `elemental_advantage_multiplier` when the
+//!   attacker's element has the advantage.
+//! - Ultimate: `20 + strength / 5 + intelligence / 10`.
+//!
+//! A pet that dodged in its previous turn adds `DODGE_BONUS` to the rolls that miss it.
+//!
+//! The formulas are free of runtime state, so off-chain simulators can reproduce them from the
+//! `test_vectors` fixtures.
+
+use sp_runtime::Perbill;
+
+/// Rolls below this are critical hits.
+pub const CRITICAL_HIT_CHANCE: u8 = 20;
+
+/// Rolls below this, less the defender's dodge bonus, hit with a special attack.
+pub const SPECIAL_ATTACK_HIT_CHANCE: u8 = 70;
+
+/// The chance a pet that dodged in its previous turn adds to the rolls that miss it.
+pub const DODGE_BONUS: u8 = 30;
+
+/// Gets a move's roll from the battle randomness seed.
+///
+/// # Parameters
+///
+/// * `seed` - The randomness seed
+///
+/// # Returns
+///
+/// * `u8` - The roll, 0-99; zero for an empty seed
+pub fn move_roll(seed: &[u8]) -> u8 {
+    seed.first().map_or(0, |byte| byte % 100)
+}
+
+/// Whether a roll is a critical hit.
+pub fn is_critical(roll: u8) -> bool {
+    roll < CRITICAL_HIT_CHANCE
+}
+
+/// Whether a roll misses a defender with `dodge_bonus`.
+pub fn is_dodged(roll: u8, dodge_bonus: u8) -> bool {
+    roll < dodge_bonus
+}
+
+/// Whether a roll hits a defender with `dodge_bonus` with a special attack.
+pub fn special_attack_hits(roll: u8, dodge_bonus: u8) -> bool {
+    roll < SPECIAL_ATTACK_HIT_CHANCE.saturating_sub(dodge_bonus)
+}
+
+/// The damage of an attack, doubled on a critical hit.
+pub fn attack_damage(strength: u8, roll: u8) -> u8 {
+    let base_damage = 5 + strength / 10;
+    if is_critical(roll) { base_damage * 2 } else { base_damage }
+}
+
+/// The damage of a special attack that hits.
+pub fn special_attack_damage(strength: u8) -> u8 {
+    15 + strength / 5
+}
+
+/// The damage of an elemental attack.
+///
+/// # Parameters
+///
+/// * `strength` - The attacker's strength
+/// * `advantage` - Whether the attacker's element has the advantage
+/// * `multiplier` - The battle's `elemental_advantage_multiplier`
+///
+/// # Returns
+///
+/// * `u8` - The damage
+pub fn elemental_attack_damage(strength: u8, advantage: bool, multiplier: Perbill) -> u8 {
+    let base_damage = 10 + strength / 8;
+    if advantage { multiplier.mul_floor(base_damage as u32) as u8 } else { base_damage }
+}
+
+/// The damage of an ultimate move.
+pub fn ultimate_damage(strength: u8, intelligence: u8) -> u8 {
+    20 + strength / 5 + intelligence / 10
+}
+
+/// Whether an attacker's element has the advantage over a defender's, by element number
+/// modulo 8: 1 beats 3, 2 beats 1 and 3 beats 2; 4 beats 6, 5 beats 4 and 6 beats 5.
+/// 0 and 7 have no advantages or disadvantages.
+pub fn has_elemental_advantage(attacker: u8, defender: u8) -> bool {
+    matches!(
+        (attacker % 8, defender % 8),
+        (1, 3) | (2, 1) | (3, 2) | (4, 6) | (5, 4) | (6, 5)
+    )
+}
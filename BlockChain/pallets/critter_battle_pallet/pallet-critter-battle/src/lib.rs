@@ -20,31 +20,164 @@
 //! * `decline_challenge` - Decline a battle challenge
 //! * `execute_move` - Execute a battle move during a battle
 //! * `forfeit_battle` - Forfeit an ongoing battle
+//! * `claim_turn_timeout` - End a battle whose opponent has let their turn time out
 //! * `claim_rewards` - Claim rewards from a completed battle
 //! * `enter_tournament` - Enter a pet into a tournament
-//! * `set_battle_params` - Update battle parameters
+//! * `set_battle_params` - Schedule new battle parameters after the notice period
+//! * `set_battle_params_immediately` - Apply new battle parameters at once (root only)
+//! * `repair_active_battles` - Prune ended battles from an account's active battles
+//! * `place_bet` - Bet on the outcome of another account's battle
+//! * `wager_on_battle` - Bet on a pet winning another account's battle
+//! * `claim_bet_winnings` - Claim a winning bet on a settled battle
+//! * `generate_bracket` - Pair a started tournament's entrants into its first-round battles
+//! * `resume_bracket` - Retry moving on the winner of a stalled bracket match
+//! * `claim_tournament_prize` - Claim a tournament prize that could not be paid
+//! * `create_team_challenge` - Challenge another account's two pets with two of your own
+//! * `accept_team_challenge` - Accept a team battle challenge
+//! * `execute_team_move` - Execute a move with the team member whose turn it is
+//!
+//! ### Telemetry
+//!
+//! Every battle that ends with a result goes through `conclude_battle`, which counts it once in
+//! `ElementBattleStats` by the pets' canonical elemental affinity. The `BattleTelemetryApi`
+//! runtime API exposes the counters and per-element win rates.
+//!
+//! ### Battle Predictions
+//!
+//! The `CritterBattleRuntimeApi` runtime API, declared in the `runtime-api` crate, predicts a
+//! battle between two pets from their current stats, so players can weigh a challenge before
+//! committing its bond. `simulate_battle` plays repeated battles with deterministic rolls (see
+//! `simulation`) and writes no storage. With the `rpc` feature, `rpc` serves it over JSON-RPC.
+//!
+//! ### Parameter Changes
+//!
+//! `set_battle_params` schedules a parameter set at least `ParamsNoticePeriod` blocks ahead,
+//! so balance cannot flip under a running tournament. `on_initialize` promotes it at its
+//! activation block and bumps `BattleParamsVersion`; the set it replaces goes into
+//! `ParamsHistory`, which keeps the last three with the blocks they were active for. Root can
+//! still change parameters at once with `set_battle_params_immediately`, which also drops any
+//! scheduled set.
+//!
+//! Each battle records the version it was created under, and settles with that version's
+//! parameters (bond, penalty, rewards, experience and rating change), so a disputed battle can
+//! be audited against the history. A battle whose version has left the history settles with
+//! the current parameters.
+//!
+//! ### Spectator Betting
+//!
+//! Accounts other than the participants can bet on a battle's outcome while its challenge is
+//! pending and for its first `max_bet_turn` turns, with the stake reserved; `wager_on_battle`
+//! bets on a pet's win by naming the pet, and is recorded in `BattleWagers` as well. When the
+//! battle concludes, the losing stakes move to the bets account, which passes the
+//! `bet_house_cut` of losing bets and the `WagerHouseFee` of losing wagers to the
+//! `EcosystemTreasury`, and pays the rest out to winning bets in proportion to their stakes as
+//! they are claimed. A cut the treasury transfer fails for is kept
+//! in `UnpaidHouseCut` and paid with the next settlement. Every bet is
+//! refunded if the battle expires, is declined or voided, is forfeited before bets close, or no
+//! bet predicted its outcome. `MaxBetsPerBattle` bounds the bets settled with a battle, and
+//! `MaxWagerPerBattle` the stake of each.
+//!
+//! ### Energy
+//!
+//! Every move costs energy, set per move in the parameters' `move_energy_costs` (the ultimate
+//! move costs `ultimate_move_energy_cost`), and a pet without enough energy for a move cannot
+//! make it. From its second turn on, a pet regains `energy_per_turn` at the start of each of
+//! its turns, up to `MAX_ENERGY`, and defending restores `defend_restore` on top.
+//!
+//! ### Turn Timeouts
+//!
+//! A player who stops moving cannot hold a battle open: once `turn_timeout_blocks` have passed
+//! since the last move (or the battle's start, recorded in `updated_at`), the opponent can end
+//! the battle with `claim_turn_timeout`, and `on_initialize` ends up to
+//! `MaxTurnTimeoutsPerBlock` such battles each block. Either way the stalled player forfeits,
+//! with the same penalty and rewards as `forfeit_battle`, and `BattleTurnTimedOut` names them.
+//!
+//! ### Rating Decay
+//!
+//! Every `EloDecayIntervalBlocks` blocks, `on_initialize` decays the ratings of inactive pets,
+//! so a rating cannot be parked at its peak. A pet waiting in the matchmaking queue for more
+//! than `MatchmakingQueueTimeoutBlocks` is removed from it and loses `EloInactivityDecay`, and a
+//! rated pet with no battle concluded in the last `EloDecayWindowBlocks` blocks loses
+//! `EloPassiveDecay`. Neither takes a rating below `EloFloor`, and a rating already at or below
+//! it does not decay.
+//!
+//! ### Tournament Brackets
+//!
+//! Once a tournament has started, `generate_bracket` shuffles its entrants with
+//! `BattleRandomness` and pairs them into a single-elimination bracket of
+//! `ceil(log2(entrants))` rounds, recorded in `TournamentBracket`. Each first-round pair fights
+//! in a battle created already active, with no challenge bond; when the entrants do not fill
+//! the bracket, the unpaired ones get a bye and win their first-round match outright.
+//!
+//! When a bracket battle ends, by any route, `conclude_battle` moves its winner into the next
+//! round (a draw goes to pet1), and the next match's battle starts as soon as both its pets
+//! are known. A pet that no longer belongs to the account that entered it, or is in another
+//! battle, forfeits its match when it comes up. The winner of the final ends the tournament
+//! as its champion: the tournament is `Completed` with its `winner_pet_id`, and the prize pool
+//! is paid out by `TOURNAMENT_PRIZE_SHARES`, 70% to the champion, 20% to the runner-up and 10%
+//! shared by the semi-final losers. A prize the pallet account cannot pay is kept for its owner
+//! to claim with `claim_tournament_prize`.
+//!
+//! If the next match's battle cannot start, moving the winner on is undone and the match is
+//! recorded in `StalledBracketMatches`, for anyone to retry with `resume_bracket`.
+//!
+//! ### Team Battles
+//!
+//! A team battle pits two pets against two, in `TeamBattles` under ids shared with 1v1
+//! battles. Its pets are committed once the challenge is accepted, and move in the order
+//! team1_pet1, team2_pet1, team1_pet2, team2_pet2, passing over fainted pets, for twice the
+//! 1v1 turn limit. Each pet has its own share of its team's health pool: attacks hit the
+//! opposing pet in the same position, or its teammate once it has fainted, and
+//! `apply_status_effect` targets a single pet. A team loses once both its pets faint, or at the
+//! turn limit with the smaller combined health, and `TeamBattleCompleted` reports the
+//! experience awarded.
 , and 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
+pub mod runtime_api;
+
+#[cfg(feature = "rpc")]
+pub mod rpc;
+
+pub use pallet_critter_battle_runtime_api::CritterBattleRuntimeApi;
+
+pub mod migrations;
+
+pub mod damage;
+
+pub mod simulation;
+
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
     use crittercraft_traits::{
-        AdvancedPetManagement, AttributeType, BattleSystemIntegration, PetId, PetStats, SharedNftManager,
+        AdvancedPetManagement, AttributeType, BattleSystemIntegration, NftCallError, PetId, PetStats, SharedNftManager,
     };
     use frame_support::{
         dispatch::{DispatchResult, DispatchResultWithPostInfo},
         pallet_prelude::*,
-        traits::{Currency, ExistenceRequirement, Get, Randomness, ReservableCurrency},
+        traits::{BalanceStatus, Currency, ExistenceRequirement, Get, Randomness, ReservableCurrency},
         Blake2_128Concat,
     };
     use frame_system::pallet_prelude::*;
     use sp_runtime::{
-        traits::{AccountIdConversion, CheckedAdd, CheckedSub, Zero, Saturating},
-        Perbill,
+        traits::{AccountIdConversion, CheckedAdd, CheckedSub, Hash as HashT, Zero, Saturating},
+        FixedPointNumber, FixedU128, Perbill,
     };
+    use crittercraft_traits::nft::{PetDisplayVersionProvider, PetElementProvider};
+    use crittercraft_traits::bounded::push_rotating;
     use sp_std::{prelude::*, vec::Vec};
+    pub use pallet_critter_battle_runtime_api::SimulationResult;
 
     // Define the battle ID type
     pub type BattleId = u32;
@@ -74,6 +207,21 @@ pub mod pallet {
         Forfeited,
     }
 
+    // Define the reason a pet's rating decayed
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum EloDecayReason {
+        QueueTimeout, // Waited in the matchmaking queue past the timeout
+        Inactivity,   // No battle concluded within the decay window
+    }
+
+    // Define how far a rating decay pass has got: through the matchmaking queue, then through
+    // the rated pets, each resuming after the pet it visited last
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RatingDecayProgress {
+        MatchmakingQueue(Option<PetId>),
+        Ratings(Option<PetId>),
+    }
+
     // Define the battle move enum
     #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub enum BattleMove {
@@ -146,6 +294,156 @@ pub mod pallet {
         pub completed_at: Option<BlockNumber>,
         pub reward_claimed: bool,
         pub battle_rating: Option<u16>, // For matchmaking and ranking
+        pub params_version: u32, // The battle parameters version the battle settles with
+    }
+
+    // Define one side of a team battle, each field holding the team's two pets in order
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct TeamState {
+        pub health: [u8; 2], // The team's health pool, split between its pets
+        pub energy: [u8; 2],
+        pub status_effects: [BoundedVec<StatusEffect, ConstU32<5>>; 2],
+        pub last_moves: [Option<BattleMove>; 2],
+    }
+
+    impl TeamState {
+        /// A team starting with each pet's health and `energy` apiece.
+        pub fn new(health: [u8; 2], energy: u8) -> Self {
+            Self {
+                health,
+                energy: [energy; 2],
+                status_effects: Default::default(),
+                last_moves: [None, None],
+            }
+        }
+
+        /// The team's combined health.
+        pub fn total_health(&self) -> u16 {
+            self.health.iter().map(|health| *health as u16).sum()
+        }
+
+        /// Whether both of the team's pets have fainted.
+        pub fn is_defeated(&self) -> bool {
+            self.health == [0, 0]
+        }
+    }
+
+    // Define the 2v2 team battle struct. Teams are numbered 1 and 2, and their pets move in
+    // the order team1_pet1, team2_pet1, team1_pet2, team2_pet2, passing over fainted pets.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct TeamBattle<AccountId, BlockNumber> {
+        pub id: BattleId, // Drawn from the same ids as 1v1 battles
+        pub team1_owner: AccountId,
+        pub team2_owner: AccountId,
+        pub team1_pet1_id: PetId,
+        pub team1_pet2_id: PetId,
+        pub team2_pet1_id: PetId,
+        pub team2_pet2_id: PetId,
+        pub status: BattleStatus,
+        pub current_turn: u8,
+        pub team1: TeamState,
+        pub team2: TeamState,
+        pub winning_team: Option<u8>, // None until the battle ends, and for a draw
+        pub created_at: BlockNumber,
+        pub updated_at: BlockNumber,
+        pub completed_at: Option<BlockNumber>,
+        pub params_version: u32, // The battle parameters version the battle settles with
+    }
+
+    impl<AccountId, BlockNumber> TeamBattle<AccountId, BlockNumber> {
+        /// Each turn's team and team member (0 or 1), repeating from the fifth turn.
+        pub const TURN_ORDER: [(u8, usize); 4] = [(1, 0), (2, 0), (1, 1), (2, 1)];
+
+        /// The team and team member that move on `turn`.
+        pub fn slot_of_turn(turn: u8) -> (u8, usize) {
+            Self::TURN_ORDER[(turn.saturating_sub(1) % 4) as usize]
+        }
+
+        /// The ID of a team member.
+        pub fn pet_id(&self, team: u8, member: usize) -> PetId {
+            match (team, member) {
+                (1, 0) => self.team1_pet1_id,
+                (1, _) => self.team1_pet2_id,
+                (_, 0) => self.team2_pet1_id,
+                _ => self.team2_pet2_id,
+            }
+        }
+
+        /// Finds a pet in the battle, as its team and team member.
+        pub fn slot_of(&self, pet_id: PetId) -> Option<(u8, usize)> {
+            Self::TURN_ORDER
+                .into_iter()
+                .find(|(team, member)| self.pet_id(*team, *member) == pet_id)
+        }
+
+        /// The IDs of all four pets, in turn order.
+        pub fn pet_ids(&self) -> [PetId; 4] {
+            Self::TURN_ORDER.map(|(team, member)| self.pet_id(team, member))
+        }
+
+        /// The owner of a team.
+        pub fn owner(&self, team: u8) -> &AccountId {
+            if team == 1 { &self.team1_owner } else { &self.team2_owner }
+        }
+
+        /// The state of a team.
+        pub fn team(&self, team: u8) -> &TeamState {
+            if team == 1 { &self.team1 } else { &self.team2 }
+        }
+
+        /// The state of a team, for changing.
+        pub fn team_mut(&mut self, team: u8) -> &mut TeamState {
+            if team == 1 { &mut self.team1 } else { &mut self.team2 }
+        }
+
+        /// Moves to the next turn of a pet that has not fainted.
+        pub fn advance_turn(&mut self) {
+            for _ in 0..4 {
+                self.current_turn = self.current_turn.saturating_add(1);
+                let (team, member) = Self::slot_of_turn(self.current_turn);
+                if self.team(team).health[member] > 0 {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Define the per-element battle record, for game balance telemetry
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ElementBattleRecord {
+        pub wins: u32,
+        pub losses: u32,
+        pub draws: u32,
+    }
+
+    impl ElementBattleRecord {
+        /// The number of battle results recorded for the element.
+        pub fn total(&self) -> u32 {
+            self.wins.saturating_add(self.losses).saturating_add(self.draws)
+        }
+
+        /// The share of recorded results that were wins (draws count as non-wins),
+        /// or `None` if the element has no results yet.
+        pub fn win_rate(&self) -> Option<FixedU128> {
+            FixedU128::checked_from_rational(self.wins, self.total())
+        }
+    }
+
+    // Define the battle snapshot served to spectators. Display data (names, themes, bios) is
+    // not copied; clients re-fetch it from the NFT pallet when a pet's display version moves.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BattleSnapshot {
+        pub battle_id: BattleId,
+        pub status: BattleStatus,
+        pub current_turn: u8,
+        pub pet1_id: PetId,
+        pub pet2_id: PetId,
+        pub pet1_health: u8,
+        pub pet2_health: u8,
+        pub pet1_energy: u8,
+        pub pet2_energy: u8,
+        pub pet1_display_version: u32,
+        pub pet2_display_version: u32,
     }
 
     // Define the tournament struct
@@ -166,6 +464,28 @@ pub mod pallet {
         pub winner_pet_id: Option<PetId>,
     }
 
+    /// A tournament scheduled in the chain spec, created at genesis.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase", deny_unknown_fields))]
+    pub struct GenesisTournament<Balance, BlockNumber> {
+        /// At most 64 bytes
+        pub name: Vec<u8>,
+        /// At most 256 bytes
+        pub description: Vec<u8>,
+        /// Between 1 and `MaxTournamentParticipants`
+        pub max_participants: u32,
+        pub min_pet_level: u16,
+        pub max_pet_level: u16,
+        pub entry_fee: Balance,
+        /// Blocks after genesis at which the tournament starts; at least 1
+        pub start_offset: BlockNumber,
+        /// The prize pool the tournament starts with, drawn from the genesis-funded pallet account
+        pub prize_seed: Balance,
+    }
+
+    pub type GenesisTournamentOf<T> = GenesisTournament<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
     // Define the tournament status enum
     #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub enum TournamentStatus {
@@ -175,6 +495,31 @@ pub mod pallet {
         Cancelled,
     }
 
+    // Define the tournament bracket match struct
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BracketMatch {
+        pub round: u8, // Starting from 1
+        pub slot: u32, // The match's position within its round
+        pub pet1_id: Option<PetId>,
+        pub pet2_id: Option<PetId>, // None for a bye
+        pub battle_id: Option<BattleId>,
+        pub winner_pet_id: Option<PetId>,
+    }
+
+    impl BracketMatch {
+        /// The pet knocked out of the tournament by a decided match; `None` for a bye.
+        pub fn loser_pet_id(&self) -> Option<PetId> {
+            let winner = self.winner_pet_id?;
+            [self.pet1_id, self.pet2_id].into_iter().flatten().find(|pet_id| *pet_id != winner)
+        }
+    }
+
+    /// The shares of a tournament's prize pool paid to its champion, its runner-up and its two
+    /// semi-final losers, who split the third share. Shares without a pet to take them go to
+    /// the champion.
+    pub const TOURNAMENT_PRIZE_SHARES: [Perbill; 3] =
+        [Perbill::from_percent(70), Perbill::from_percent(20), Perbill::from_percent(10)];
+
     // Define the battle parameters struct
     #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub struct BattleParameters<Balance> {
@@ -194,6 +539,129 @@ pub mod pallet {
         pub energy_per_turn: u8,
         pub ultimate_move_energy_cost: u8,
         pub matchmaking_rating_change: u16,
+        pub max_bet_turn: u8, // Bets close once an active battle passes this turn
+        pub bet_house_cut: Perbill, // The share of losing bet stakes routed to the ecosystem treasury
+        pub move_energy_costs: MoveEnergyCosts,
+        pub turn_timeout_blocks: u32, // Blocks a player has to move before the opponent can claim a timeout
+    }
+
+    impl<Balance> BattleParameters<Balance> {
+        /// The energy a pet needs, and spends, to make a move.
+        pub fn energy_cost(&self, move_type: &BattleMove) -> u8 {
+            let costs = &self.move_energy_costs;
+            match move_type {
+                BattleMove::Attack => costs.attack,
+                BattleMove::Defend => costs.defend,
+                BattleMove::SpecialAttack | BattleMove::StatusEffect | BattleMove::Combo => costs.special_attack,
+                BattleMove::Heal => costs.heal,
+                BattleMove::Dodge => costs.dodge,
+                BattleMove::ElementalAttack => costs.elemental_attack,
+                BattleMove::Ultimate => self.ultimate_move_energy_cost,
+            }
+        }
+    }
+
+    /// The most energy a pet can have.
+    pub const MAX_ENERGY: u8 = 100;
+
+    // Define the energy costs of the regular moves
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct MoveEnergyCosts {
+        pub attack: u8,
+        pub defend: u8,
+        pub special_attack: u8, // Also the cost of the other special moves
+        pub heal: u8,
+        pub dodge: u8,
+        pub elemental_attack: u8,
+        pub defend_restore: u8, // The energy a pet regains when it defends, after paying for it
+    }
+
+    /// The default costs, also given to parameter sets that predate move energy costs.
+    impl Default for MoveEnergyCosts {
+        fn default() -> Self {
+            Self {
+                attack: 5,
+                defend: 0,
+                special_attack: 25,
+                heal: 15,
+                dodge: 5,
+                elemental_attack: 15,
+                defend_restore: 5,
+            }
+        }
+    }
+
+    /// The betting cutoff of the default parameters, also given to sets that predate betting.
+    pub const DEFAULT_MAX_BET_TURN: u8 = 2;
+
+    /// The house cut of the default parameters, also given to sets that predate betting.
+    pub const DEFAULT_BET_HOUSE_CUT: Perbill = Perbill::from_percent(5);
+
+    /// The turn timeout of the default parameters, also given to sets that predate turn timeouts.
+    pub const DEFAULT_TURN_TIMEOUT_BLOCKS: u32 = 100;
+
+    // Define a battle parameter set waiting for its activation block
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ScheduledParameters<Balance, BlockNumber> {
+        pub params: BattleParameters<Balance>,
+        pub activates_at: BlockNumber,
+    }
+
+    // Define a replaced battle parameter set, with the blocks it was active for
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ParamsHistoryEntry<Balance, BlockNumber> {
+        pub version: u32,
+        pub params: BattleParameters<Balance>,
+        pub active_from: BlockNumber,
+        pub active_until: BlockNumber, // The block the next set took effect
+    }
+
+    // Define a spectator's bet on a battle
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Bet<Balance> {
+        pub predicted_outcome: BattleOutcome,
+        pub amount: Balance, // Reserved until the battle settles
+    }
+
+    // Define the result of settling a battle's bets
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BetSettlement<Balance> {
+        pub outcome: BattleOutcome,
+        pub winning_stake: Balance, // The total stake of the bets that predicted the outcome
+        pub winnings: Balance, // The losing stakes left for the winning bets after the house cut
+    }
+
+    // Define the bets on a battle: the stake on each outcome, and the settlement once it ends
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct BetPool<Balance> {
+        pub bettors: u32, // Bets placed, or winning bets left to claim once settled
+        pub pet1_win_stake: Balance,
+        pub pet2_win_stake: Balance,
+        pub draw_stake: Balance,
+        pub settlement: Option<BetSettlement<Balance>>,
+    }
+
+    impl<Balance: Copy + Zero + Saturating> BetPool<Balance> {
+        /// The total stake on an outcome. Nothing can be staked on `Forfeited`.
+        pub fn stake_on(&self, outcome: &BattleOutcome) -> Balance {
+            match outcome {
+                BattleOutcome::Pet1Win => self.pet1_win_stake,
+                BattleOutcome::Pet2Win => self.pet2_win_stake,
+                BattleOutcome::Draw => self.draw_stake,
+                BattleOutcome::Forfeited => Zero::zero(),
+            }
+        }
+
+        /// Add a stake on an outcome.
+        fn add_stake(&mut self, outcome: &BattleOutcome, amount: Balance) {
+            let stake = match outcome {
+                BattleOutcome::Pet1Win => &mut self.pet1_win_stake,
+                BattleOutcome::Pet2Win => &mut self.pet2_win_stake,
+                BattleOutcome::Draw => &mut self.draw_stake,
+                BattleOutcome::Forfeited => return,
+            };
+            *stake = stake.saturating_add(amount);
+        }
     }
 
     // Define the pallet's configuration trait
@@ -214,10 +682,21 @@ pub mod pallet {
         /// The pet manager for pet stats and experience
         type PetManager: AdvancedPetManagement<Self::AccountId, Self::BlockNumber>;
 
+        /// The canonical elemental affinity of pets (pallet-critter-nfts), for element telemetry
+        type PetElements: PetElementProvider<PetId>;
+
+        /// The display versions of pets (pallet-critter-nfts), for battle snapshots
+        type PetDisplay: PetDisplayVersionProvider<PetId>;
+
         /// The maximum number of active battles per account
         #[pallet::constant]
         type MaxActiveBattles: Get<u32>;
 
+        /// The maximum number of team battles challenged or in progress at once, which bounds
+        /// processing them each block
+        #[pallet::constant]
+        type MaxActiveTeamBattles: Get<u32>;
+
         /// The maximum number of active tournaments
         #[pallet::constant]
         type MaxActiveTournaments: Get<u32>;
@@ -229,6 +708,59 @@ pub mod pallet {
         /// The origin that can update battle parameters
         type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+        /// The minimum number of blocks between scheduling battle parameters and their activation
+        #[pallet::constant]
+        type ParamsNoticePeriod: Get<Self::BlockNumber>;
+
+        /// The maximum number of spectator bets on one battle, which bounds settling them
+        #[pallet::constant]
+        type MaxBetsPerBattle: Get<u32>;
+
+        /// The maximum stake of one spectator bet or wager on a battle
+        #[pallet::constant]
+        type MaxWagerPerBattle: Get<BalanceOf<Self>>;
+
+        /// The share of losing `wager_on_battle` stakes routed to the ecosystem treasury; losing
+        /// `place_bet` stakes pay the parameters' `bet_house_cut` instead
+        #[pallet::constant]
+        type WagerHouseFee: Get<Perbill>;
+
+        /// The ecosystem treasury account, which receives the house cut of settled bets
+        #[pallet::constant]
+        type EcosystemTreasury: Get<Self::AccountId>;
+
+        /// The number of blocks between rating decay passes; zero turns decay off
+        #[pallet::constant]
+        type EloDecayIntervalBlocks: Get<u32>;
+
+        /// The number of blocks a pet can wait in the matchmaking queue before it is removed
+        #[pallet::constant]
+        type MatchmakingQueueTimeoutBlocks: Get<u32>;
+
+        /// The rating a pet loses when it is removed from the matchmaking queue on timeout
+        #[pallet::constant]
+        type EloInactivityDecay: Get<u16>;
+
+        /// The number of blocks without a concluded battle after which a pet's rating decays
+        #[pallet::constant]
+        type EloDecayWindowBlocks: Get<u32>;
+
+        /// The rating an inactive pet loses each decay pass
+        #[pallet::constant]
+        type EloPassiveDecay: Get<u16>;
+
+        /// The rating below which decay never takes a pet
+        #[pallet::constant]
+        type EloFloor: Get<u16>;
+
+        /// The maximum number of pets a rating decay pass visits each block
+        #[pallet::constant]
+        type MaxRatingDecaysPerBlock: Get<u32>;
+
+        /// The maximum number of timed-out battles `on_initialize` forfeits each block
+        #[pallet::constant]
+        type MaxTurnTimeoutsPerBlock: Get<u32>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
     }
@@ -267,8 +799,12 @@ pub mod pallet {
         TournamentStarted(TournamentId),
         /// A tournament has ended. [tournament_id, winner_pet_id]
         TournamentEnded(TournamentId, PetId),
-        /// Battle parameters have been updated.
-        BattleParametersUpdated,
+        /// Battle parameters have been scheduled. [activates_at]
+        BattleParametersScheduled(T::BlockNumber),
+        /// Scheduled battle parameters have taken effect. [version]
+        BattleParametersUpdated(u32),
+        /// Battle parameters have been changed immediately by root. [version]
+        BattleParametersOverridden(u32),
         /// A pet has been added to the matchmaking queue. [pet_id, owner, rating]
         PetAddedToMatchmaking(PetId, T::AccountId, u16),
         /// A pet has been removed from the matchmaking queue. [pet_id, owner]
@@ -277,6 +813,83 @@ pub mod pallet {
         MatchmakingBattleCreated(BattleId, PetId, PetId),
         /// A pet's battle rating has changed. [pet_id, old_rating, new_rating]
         PetBattleRatingChanged(PetId, u16, u16),
+        /// Stale battle ids have been pruned from an account's active battles. [account, removed]
+        ActiveBattlesRepaired(T::AccountId, u32),
+        /// A battle was voided because one of its pets was force released. [battle_id, pet_id]
+        BattleVoided(BattleId, PetId),
+        /// A spectator has bet on a battle. [battle_id, bettor, predicted_outcome, amount]
+        BetPlaced(BattleId, T::AccountId, BattleOutcome, BalanceOf<T>),
+        /// A winning bet has been paid out, stake included. [battle_id, bettor, payout]
+        BetWon(BattleId, T::AccountId, BalanceOf<T>),
+        /// A spectator has wagered on a pet winning a battle.
+        BattleWagerPlaced {
+            battle_id: BattleId,
+            bettor: T::AccountId,
+            bet_on_pet: PetId,
+            amount: BalanceOf<T>,
+        },
+        /// A winning wager has been paid out, stake included.
+        BattleWagerPayout {
+            battle_id: BattleId,
+            bettor: T::AccountId,
+            winnings: BalanceOf<T>,
+        },
+        /// A bet has been refunded because its battle ended without a result to settle it, or a
+        /// losing stake that could not be collected has been returned. [battle_id, bettor, amount]
+        BetRefunded(BattleId, T::AccountId, BalanceOf<T>),
+        /// A pet's rating has decayed for inactivity.
+        PetEloDecayed {
+            pet_id: PetId,
+            old_rating: u16,
+            new_rating: u16,
+            reason: EloDecayReason,
+        },
+        /// A share of a tournament's prize pool has been paid to the owner of a placed pet.
+        TournamentPrizeAwarded {
+            tournament_id: TournamentId,
+            pet_id: PetId,
+            owner: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// A tournament prize could not be paid, and is left for the owner to claim with
+        /// `claim_tournament_prize`.
+        TournamentPrizeUnpaid {
+            tournament_id: TournamentId,
+            pet_id: PetId,
+            owner: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// The winner of a bracket match could not be moved on, for instance because the next
+        /// match's battle could not start; `resume_bracket` retries it.
+        BracketStalled {
+            tournament_id: TournamentId,
+            match_number: u32,
+        },
+        /// A tournament's bracket has been generated and its first-round battles created.
+        TournamentBracketGenerated {
+            tournament_id: TournamentId,
+            participant_count: u32,
+            round_count: u8,
+        },
+        /// A team battle challenge has been created.
+        TeamChallengeCreated {
+            battle_id: BattleId,
+            challenger: T::AccountId,
+            challenged: T::AccountId,
+        },
+        /// A team battle challenge has been accepted.
+        TeamChallengeAccepted { battle_id: BattleId },
+        /// The active player of a battle let their turn time out, and forfeits the battle.
+        BattleTurnTimedOut {
+            battle_id: BattleId,
+            stalled: T::AccountId,
+        },
+        /// A team battle has ended. `winning_team` is `None` for a draw.
+        TeamBattleCompleted {
+            battle_id: BattleId,
+            winning_team: Option<u8>,
+            xp_distributed: u32,
+        },
     }
 
     // Define the pallet's errors
@@ -316,6 +929,8 @@ pub mod pallet {
         InsufficientBalance,
         /// Invalid battle parameters
         InvalidBattleParameters,
+        /// The activation block is within the parameter notice period
+        ActivationTooSoon,
         /// The battle ID has overflowed
         BattleIdOverflow,
         /// The tournament ID has overflowed
@@ -334,14 +949,57 @@ pub mod pallet {
         MatchmakingQueueEmpty,
         /// No suitable match found in the matchmaking queue
         NoSuitableMatchFound,
-        /// The battle history is too long
-        BattleHistoryTooLong,
         /// The combo counter has reached its maximum
         ComboCounterMaximum,
         /// The move is not available to this pet
         MoveNotAvailable,
         /// The battle move result is invalid
         InvalidBattleMoveResult,
+        /// The pet does not exist
+        PetNotFound,
+        /// The pet is locked by the NFT pallet
+        PetLocked,
+        /// The NFT pallet failed the call for another reason
+        PetManagerCallFailed,
+        /// Bets on the battle are closed
+        BettingClosed,
+        /// A bet must predict a win or a draw, with a nonzero stake
+        InvalidBet,
+        /// A battle participant cannot bet on their own battle
+        ParticipantCannotBet,
+        /// The account already has a bet on the battle
+        BetAlreadyPlaced,
+        /// The bet's stake is above `MaxWagerPerBattle`
+        WagerCapExceeded,
+        /// The battle already has `MaxBetsPerBattle` bets
+        TooManyBets,
+        /// The account has no bet to claim on the battle
+        BetNotFound,
+        /// The battle's bets have not been settled yet
+        BetsNotSettled,
+        /// The tournament's bracket has already been generated
+        BracketAlreadyGenerated,
+        /// A team must be two different pets
+        InvalidTeam,
+        /// The active player's turn has not timed out yet
+        TurnNotTimedOut,
+        /// `MaxActiveTeamBattles` team battles are already challenged or in progress
+        TooManyTeamBattles,
+        /// The bracket match has not stalled
+        BracketNotStalled,
+        /// The pet has no unpaid prize in the tournament
+        PrizeNotFound,
+    }
+
+    impl<T> From<NftCallError> for Error<T> {
+        fn from(error: NftCallError) -> Self {
+            match error {
+                NftCallError::PetNotFound => Error::<T>::PetNotFound,
+                NftCallError::NotOwner => Error::<T>::NotPetOwner,
+                NftCallError::PetLocked => Error::<T>::PetLocked,
+                NftCallError::Failed => Error::<T>::PetManagerCallFailed,
+            }
+        }
     }
 
     // Define the pallet's storage items
@@ -418,6 +1076,34 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn battle_parameters)]
     pub type BattleParams<T: Config> = StorageValue<_, BattleParameters<BalanceOf<T>>, OptionQuery>;
+
+    /// The version of `BattleParams`, bumped each time a new set takes effect.
+    #[pallet::storage]
+    #[pallet::getter(fn battle_params_version)]
+    pub type BattleParamsVersion<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// The block the current `BattleParams` took effect.
+    #[pallet::storage]
+    #[pallet::getter(fn battle_params_active_since)]
+    pub type BattleParamsActiveSince<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// The parameter set waiting for its activation block, if any.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_battle_params)]
+    pub type ScheduledBattleParams<T: Config> = StorageValue<
+        _,
+        ScheduledParameters<BalanceOf<T>, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// The last three replaced parameter sets, oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn params_history)]
+    pub type ParamsHistory<T: Config> = StorageValue<
+        _,
+        BoundedVec<ParamsHistoryEntry<BalanceOf<T>, T::BlockNumber>, ConstU32<3>>,
+        ValueQuery,
+    >;
     
     #[pallet::storage]
     #[pallet::getter(fn battle_history)]
@@ -439,6 +1125,22 @@ pub mod pallet {
         ValueQuery,
     >;
     
+    /// The block each pet's last battle concluded, for rating decay.
+    #[pallet::storage]
+    #[pallet::getter(fn pet_last_battle)]
+    pub type PetLastBattle<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        PetId,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+    
+    /// How far the rating decay pass in progress has got, if one is running.
+    #[pallet::storage]
+    #[pallet::getter(fn rating_decay_cursor)]
+    pub type RatingDecayCursor<T: Config> = StorageValue<_, RatingDecayProgress, OptionQuery>;
+    
     #[pallet::storage]
     #[pallet::getter(fn battle_matchmaking_queue)]
     pub type BattleMatchmakingQueue<T: Config> = StorageMap<
@@ -449,34 +1151,159 @@ pub mod pallet {
         OptionQuery,
     >;
 
-    // Define the pallet itself
-    #[pallet::pallet]
-    #[pallet::generate_store(pub(super) trait Store)]
-    pub struct Pallet<T>(_);
+    /// Lifetime battle results per element (index of the NFT pallet's `ElementType`).
+    /// A mirror match counts one win and one loss for the element.
+    #[pallet::storage]
+    #[pallet::getter(fn element_battle_stats)]
+    pub type ElementBattleStats<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u8,
+        ElementBattleRecord,
+        ValueQuery,
+    >;
 
-    // Define the balance type
-    pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    /// Spectator bets on each battle, by bettor. Losing bets are removed when the battle
+    /// settles, and winning ones when they are claimed.
+    #[pallet::storage]
+    #[pallet::getter(fn battle_bets)]
+    pub type BattleBets<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BattleId,
+        Blake2_128Concat,
+        T::AccountId,
+        Bet<BalanceOf<T>>,
+        OptionQuery,
+    >;
 
-    // Define the pallet's call (dispatchable functions)
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Create a battle challenge
-        #[pallet::weight(T::WeightInfo::create_challenge())]
-        pub fn create_challenge(
-            origin: OriginFor<T>,
-            pet_id: PetId,
-            target_pet_id: PetId,
-        ) -> DispatchResultWithPostInfo {
-            let challenger = ensure_signed(origin)?;
-            
-            // Ensure the challenger owns the pet
-            ensure!(
-                T::NftManager::owner_of(&pet_id) == Some(challenger.clone()),
-                Error::<T>::NotPetOwner
-            );
-            
-            // Get the target pet owner
-            let target_owner = T::NftManager::owner_of(&target_pet_id).ok_or(Error::<T>::NotPetOwner)?;
+    /// The stakes on each battle with open bets, and its settlement once it ends.
+    #[pallet::storage]
+    #[pallet::getter(fn battle_bet_pool)]
+    pub type BattleBetPools<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BattleId,
+        BetPool<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// The pet and stake of each wager, by battle and bettor. A wager is also a bet in
+    /// `BattleBets`, and is removed with it.
+    #[pallet::storage]
+    #[pallet::getter(fn battle_wagers)]
+    pub type BattleWagers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BattleId,
+        Blake2_128Concat,
+        T::AccountId,
+        (PetId, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// House cuts the bets account could not pass to the ecosystem treasury yet, retried
+    /// with the next battle's settlement.
+    #[pallet::storage]
+    #[pallet::getter(fn unpaid_house_cut)]
+    pub type UnpaidHouseCut<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Each tournament's bracket matches, by match number. Round one's matches are numbered by
+    /// their slot, and each later round's follow on from the round before (see
+    /// `bracket_match_number`).
+    #[pallet::storage]
+    #[pallet::getter(fn tournament_bracket)]
+    pub type TournamentBracket<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        TournamentId,
+        Blake2_128Concat,
+        u32,
+        BracketMatch,
+        OptionQuery,
+    >;
+
+    /// Decided bracket matches whose winner could not be moved on, by tournament and match
+    /// number, for `resume_bracket` to retry.
+    #[pallet::storage]
+    #[pallet::getter(fn stalled_bracket_matches)]
+    pub type StalledBracketMatches<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        TournamentId,
+        Blake2_128Concat,
+        u32,
+        (),
+        OptionQuery,
+    >;
+
+    /// Tournament prizes the pallet account could not pay when the tournament ended, by
+    /// tournament and placed pet, with the owner who can claim them.
+    #[pallet::storage]
+    #[pallet::getter(fn unpaid_tournament_prizes)]
+    pub type UnpaidTournamentPrizes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        TournamentId,
+        Blake2_128Concat,
+        PetId,
+        (T::AccountId, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Team battles, sharing their ids with the 1v1 battles in `Battles`.
+    #[pallet::storage]
+    #[pallet::getter(fn team_battles)]
+    pub type TeamBattles<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BattleId,
+        TeamBattle<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// The team battles that are challenged or in progress, which `on_initialize` processes.
+    #[pallet::storage]
+    #[pallet::getter(fn active_team_battles)]
+    pub type ActiveTeamBattles<T: Config> = StorageValue<
+        _,
+        BoundedVec<BattleId, T::MaxActiveTeamBattles>,
+        ValueQuery,
+    >;
+
+    /// The in-code storage version. Bump this together with a new `OnRuntimeUpgrade` migration
+    /// built on `crittercraft_traits::migration::VersionedMigration`.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
+    // Define the pallet itself
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    // Define the balance type
+    pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    // Define the pallet's call (dispatchable functions)
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a battle challenge
+        #[pallet::weight(T::WeightInfo::create_challenge())]
+        pub fn create_challenge(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            target_pet_id: PetId,
+        ) -> DispatchResultWithPostInfo {
+            let challenger = ensure_signed(origin)?;
+            
+            // Ensure the challenger owns the pet
+            ensure!(
+                T::NftManager::owner_of(&pet_id) == Some(challenger.clone()),
+                Error::<T>::NotPetOwner
+            );
+            
+            // Get the target pet owner
+            let target_owner = T::NftManager::owner_of(&target_pet_id).ok_or(Error::<T>::NotPetOwner)?;
             
             // Ensure the pets are not the same
             ensure!(pet_id != target_pet_id, Error::<T>::InvalidBattleStatus);
@@ -508,57 +1335,16 @@ pub mod pallet {
             
             // Create the battle
             let battle_id = Self::next_battle_id()?;
-            let now = <frame_system::Pallet<T>>::block_number();
-            
-            // Get pet stats
-            let pet1_stats = T::PetManager::get_pet_attributes(&pet_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
-            let pet2_stats = T::PetManager::get_pet_attributes(&target_pet_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
-            
-            // Calculate initial health based on vitality
-            let pet1_health = pet1_stats.iter()
-                .find_map(|(attr, val)| if *attr == AttributeType::Vitality { Some(*val) } else { None })
-                .unwrap_or(50);
-            
-            let pet2_health = pet2_stats.iter()
-                .find_map(|(attr, val)| if *attr == AttributeType::Vitality { Some(*val) } else { None })
-                .unwrap_or(50);
-            
-            // Create empty status effect vectors
-            let pet1_status_effects: BoundedVec<StatusEffect, ConstU32<5>> = BoundedVec::default();
-            let pet2_status_effects: BoundedVec<StatusEffect, ConstU32<5>> = BoundedVec::default();
-            
-            let battle = Battle {
-                id: battle_id,
-                pet1_id: pet_id,
-                pet2_id: target_pet_id,
-                pet1_owner: challenger.clone(),
-                pet2_owner: target_owner.clone(),
-                status: BattleStatus::Challenged,
-                current_turn: 0,
-                pet1_health,
-                pet2_health,
-                pet1_energy: params.initial_energy,
-                pet2_energy: params.initial_energy,
-                pet1_status_effects,
-                pet2_status_effects,
-                last_move_pet1: None,
-                last_move_pet2: None,
-                last_move_result: None,
-                combo_counter_pet1: 0,
-                combo_counter_pet2: 0,
-                outcome: None,
-                created_at: now,
-                updated_at: now,
-                completed_at: None,
-                reward_claimed: false,
-                battle_rating: None,
-            };
+            let battle = Self::new_battle(
+                battle_id,
+                (pet_id, challenger.clone()),
+                (target_pet_id, target_owner.clone()),
+                BattleStatus::Challenged,
+                &params,
+            )?;
             
             // Store the battle
             Battles::<T>::insert(battle_id, battle);
-            BattleCount::<T>::put(battle_id + 1);
             
             // Update pet active battles
             PetActiveBattle::<T>::insert(&pet_id, battle_id);
@@ -600,7 +1386,7 @@ pub mod pallet {
             
             // Ensure the battle hasn't expired
             let now = <frame_system::Pallet<T>>::block_number();
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            let params = Self::params_of(&battle)?;
             ensure!(
                 now <= battle.created_at + params.challenge_expiry_blocks.into(),
                 Error::<T>::BattleExpired
@@ -626,7 +1412,7 @@ pub mod pallet {
         }
         
         /// Decline a battle challenge
-        #[pallet::weight(T::WeightInfo::decline_challenge())]
+        #[pallet::weight(T::WeightInfo::decline_challenge().saturating_add(T::WeightInfo::refund_bets(T::MaxBetsPerBattle::get())))]
         pub fn decline_challenge(
             origin: OriginFor<T>,
             battle_id: BattleId,
@@ -653,10 +1439,17 @@ pub mod pallet {
             PetActiveBattle::<T>::remove(&battle.pet1_id);
             PetActiveBattle::<T>::remove(&battle.pet2_id);
             
+            // Free the battle slot for both participants
+            Self::remove_account_active_battle(&battle.pet1_owner, battle_id);
+            Self::remove_account_active_battle(&battle.pet2_owner, battle_id);
+            
             // Unreserve the challenger's bond
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            let params = Self::params_of(&battle)?;
             T::Currency::unreserve(&battle.pet1_owner, params.challenge_bond);
             
+            // Refund the spectators' bets
+            Self::refund_bets(battle_id);
+            
             // Emit event
             Self::deposit_event(Event::BattleChallengeDeclined(battle_id));
             
@@ -664,7 +1457,7 @@ pub mod pallet {
         }
         
         /// Execute a battle move
-        #[pallet::weight(T::WeightInfo::execute_move())]
+        #[pallet::weight(T::WeightInfo::execute_move().saturating_add(Pallet::<T>::conclude_battle_weight()))]
         pub fn execute_move(
             origin: OriginFor<T>,
             battle_id: BattleId,
@@ -689,84 +1482,65 @@ pub mod pallet {
             // Ensure it's the player's turn
             ensure!(active_owner == player, Error::<T>::NotYourTurn);
             
+            // Regenerate the pet's energy, then pay for the move
+            let params = Self::params_of(&battle)?;
+            Self::regenerate_energy(&mut battle, &params);
+            let energy_cost = params.energy_cost(&move_type);
+            let energy = if is_pet1_turn { &mut battle.pet1_energy } else { &mut battle.pet2_energy };
+            *energy = energy.checked_sub(energy_cost).ok_or(Error::<T>::InsufficientEnergy)?;
+            if move_type == BattleMove::Defend {
+                *energy = energy.saturating_add(params.move_energy_costs.defend_restore).min(MAX_ENERGY);
+            }
+            
             // Process the move
-            if is_pet1_turn {
+            let result = if is_pet1_turn {
                 battle.last_move_pet1 = Some(move_type.clone());
-                Self::process_pet1_move(&mut battle, &move_type)?;
+                Self::process_pet1_move(&mut battle, &move_type)?
             } else {
                 battle.last_move_pet2 = Some(move_type.clone());
-                Self::process_pet2_move(&mut battle, &move_type)?;
-            }
+                Self::process_pet2_move(&mut battle, &move_type)?
+            };
+            
+            // Record the move result
+            battle.last_move_result = Some(result.clone());
+            
+            // Add to battle history
+            Self::add_to_battle_history(
+                battle_id,
+                BattleMoveHistoryEntry {
+                    turn: battle.current_turn,
+                    pet_id: active_pet_id,
+                    move_type: move_type.clone(),
+                    result: result.clone(),
+                },
+            );
             
             // Increment turn counter
             battle.current_turn += 1;
             battle.updated_at = <frame_system::Pallet<T>>::block_number();
             
             // Check if the battle is over
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
             if battle.pet1_health == 0 || battle.pet2_health == 0 || battle.current_turn > params.max_turns {
-                // Determine the outcome
-                let outcome = if battle.pet1_health == 0 && battle.pet2_health == 0 {
-                    BattleOutcome::Draw
-                } else if battle.pet1_health == 0 {
-                    BattleOutcome::Pet2Win
-                } else if battle.pet2_health == 0 {
-                    BattleOutcome::Pet1Win
-                } else if battle.pet1_health > battle.pet2_health {
-                    BattleOutcome::Pet1Win
-                } else if battle.pet2_health > battle.pet1_health {
-                    BattleOutcome::Pet2Win
-                } else {
-                    BattleOutcome::Draw
-                };
-                
-                // Update battle status
-                battle.status = BattleStatus::Completed;
-                battle.outcome = Some(outcome.clone());
-                battle.completed_at = Some(battle.updated_at);
-                
-                // Award experience to pets
-                let xp_reward = params.base_experience_reward;
-                match outcome {
-                    BattleOutcome::Pet1Win => {
-                        let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward);
-                        let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward / 2);
-                    },
-                    BattleOutcome::Pet2Win => {
-                        let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward);
-                        let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward / 2);
-                    },
-                    BattleOutcome::Draw => {
-                        let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward / 2);
-                        let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward / 2);
-                    },
-                    _ => {},
-                }
-                
-                // Remove pet active battles
-                PetActiveBattle::<T>::remove(&battle.pet1_id);
-                PetActiveBattle::<T>::remove(&battle.pet2_id);
-                
-                // Emit battle completed event
-                Self::deposit_event(Event::BattleCompleted(battle_id, outcome));
+                Self::finalize_battle(&mut battle)?;
             }
             
             // Update the battle
             Battles::<T>::insert(battle_id, battle.clone());
             
-            // Emit move executed event
+            // Emit events
             Self::deposit_event(Event::BattleMoveExecuted(
                 battle_id,
                 player,
                 active_pet_id,
                 move_type,
             ));
+            Self::deposit_event(Event::BattleMoveResult(battle_id, active_pet_id, result));
             
             Ok(().into())
         }
         
         /// Forfeit a battle
-        #[pallet::weight(T::WeightInfo::forfeit_battle())]
+        #[pallet::weight(T::WeightInfo::forfeit_battle().saturating_add(Pallet::<T>::conclude_battle_weight()))]
         pub fn forfeit_battle(
             origin: OriginFor<T>,
             battle_id: BattleId,
@@ -785,44 +1559,42 @@ pub mod pallet {
                 Error::<T>::NotBattleParticipant
             );
             
-            // Determine the outcome
-            let outcome = if battle.pet1_owner == forfeiter {
-                BattleOutcome::Pet2Win
-            } else {
-                BattleOutcome::Pet1Win
-            };
+            // Conclude the battle in the opponent's favour
+            let params = Self::params_of(&battle)?;
+            Self::settle_forfeit(&mut battle, forfeiter, &params);
             
-            // Update battle status
-            battle.status = BattleStatus::Forfeited;
-            battle.outcome = Some(outcome.clone());
-            battle.completed_at = Some(<frame_system::Pallet<T>>::block_number());
+            Ok(().into())
+        }
+        
+        /// Claim a battle whose opponent has let their turn time out
+        ///
+        /// Once `turn_timeout_blocks` have passed since the last move without the active player
+        /// moving, their opponent can end the battle, which the stalled player forfeits.
+        #[pallet::weight(Pallet::<T>::turn_timeout_weight())]
+        pub fn claim_turn_timeout(
+            origin: OriginFor<T>,
+            battle_id: BattleId,
+        ) -> DispatchResultWithPostInfo {
+            let claimer = ensure_signed(origin)?;
             
-            // Apply forfeit penalty
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
-            T::Currency::slash_reserved(&forfeiter, params.forfeit_penalty);
+            // Get the battle
+            let mut battle = Self::battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
             
-            // Award experience to the winner
-            let xp_reward = params.base_experience_reward;
-            match outcome {
-                BattleOutcome::Pet1Win => {
-                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward);
-                },
-                BattleOutcome::Pet2Win => {
-                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward);
-                },
-                _ => {},
-            }
+            // Ensure the battle is active
+            ensure!(battle.status == BattleStatus::Active, Error::<T>::InvalidBattleStatus);
             
-            // Remove pet active battles
-            PetActiveBattle::<T>::remove(&battle.pet1_id);
-            PetActiveBattle::<T>::remove(&battle.pet2_id);
+            // Ensure the claimer is the participant waiting on the stalled player
+            let (stalled, waiting) = Self::stalled_and_waiting(&battle);
+            ensure!(waiting == claimer, Error::<T>::NotBattleParticipant);
             
-            // Update the battle
-            Battles::<T>::insert(battle_id, battle.clone());
+            // Ensure the turn has timed out
+            let params = Self::params_of(&battle)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(Self::turn_timed_out(&battle, &params, now), Error::<T>::TurnNotTimedOut);
             
-            // Emit events
-            Self::deposit_event(Event::BattleForfeited(battle_id, forfeiter));
-            Self::deposit_event(Event::BattleCompleted(battle_id, outcome));
+            // The stalled player forfeits
+            Self::deposit_event(Event::BattleTurnTimedOut { battle_id, stalled: stalled.clone() });
+            Self::settle_forfeit(&mut battle, stalled, &params);
             
             Ok(().into())
         }
@@ -872,10 +1644,10 @@ pub mod pallet {
             
             // Mark rewards as claimed
             battle.reward_claimed = true;
-            Battles::<T>::insert(battle_id, battle);
+            Battles::<T>::insert(battle_id, battle.clone());
             
             // Unreserve the challenger's bond
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            let params = Self::params_of(&battle)?;
             T::Currency::unreserve(&battle.pet1_owner, params.challenge_bond);
             
             // Transfer the reward
@@ -930,7 +1702,7 @@ pub mod pallet {
             );
             
             // Ensure the pet meets the level requirements
-            let pet_level = T::PetManager::get_pet_level(&pet_id).ok_or(Error::<T>::NotPetOwner)?;
+            let pet_level = T::PetManager::get_pet_level(&pet_id).map_err(Error::<T>::from)?;
             ensure!(
                 pet_level >= tournament.min_pet_level && pet_level <= tournament.max_pet_level,
                 Error::<T>::PetDoesNotMeetRequirements
@@ -1012,63 +1784,203 @@ pub mod pallet {
             Ok(().into())
         }
         
-        /// Set battle parameters
-        #[pallet::weight(T::WeightInfo::set_battle_params())]
-        pub fn set_battle_params(
+        /// Generate a started tournament's single-elimination bracket: shuffle its entrants,
+        /// pair them into first-round matches and start a battle for each pair. Entrants left
+        /// unpaired get a bye, winning their first-round match outright and moving on to the
+        /// second round.
+        #[pallet::weight(T::WeightInfo::generate_bracket(T::MaxTournamentParticipants::get()))]
+        pub fn generate_bracket(
             origin: OriginFor<T>,
-            params: BattleParameters<BalanceOf<T>>,
-        ) -> DispatchResultWithPostInfo {
+            tournament_id: TournamentId,
+        ) -> DispatchResult {
             T::UpdateOrigin::ensure_origin(origin)?;
             
-            // Validate parameters
-            ensure!(params.max_turns > 0, Error::<T>::InvalidBattleParameters);
-            ensure!(params.challenge_expiry_blocks > 0, Error::<T>::InvalidBattleParameters);
-            ensure!(params.initial_energy > 0, Error::<T>::InvalidBattleParameters);
-            ensure!(params.energy_per_turn > 0, Error::<T>::InvalidBattleParameters);
-            ensure!(params.ultimate_move_energy_cost > 0, Error::<T>::InvalidBattleParameters);
+            // Ensure the tournament has started and has no bracket yet
+            let tournament = Self::tournaments(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+            ensure!(
+                tournament.status == TournamentStatus::InProgress,
+                Error::<T>::InvalidTournamentStatus
+            );
+            ensure!(
+                TournamentBracket::<T>::iter_prefix(tournament_id).next().is_none(),
+                Error::<T>::BracketAlreadyGenerated
+            );
             
-            // Update parameters
-            BattleParams::<T>::put(params);
+            // Shuffle the entrants, from a canonical order
+            let mut entrants: Vec<(PetId, T::AccountId)> =
+                TournamentParticipants::<T>::iter_prefix(tournament_id).collect();
+            entrants.sort_by_key(|(pet_id, _)| *pet_id);
+            Self::shuffle(&mut entrants, &(b"bracket", tournament_id).encode());
+            
+            // Size the bracket: the first round has a slot for every pair of a full bracket
+            let participant_count = entrants.len() as u32;
+            let round_count = Self::bracket_round_count(participant_count);
+            let slot_count = 1usize << round_count.saturating_sub(1);
+            let (first_pets, second_pets) = entrants.split_at(slot_count.min(entrants.len()));
+            
+            // Pair the entrants and play the first-round matches
+            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            for (slot, (pet1_id, _)) in first_pets.iter().enumerate() {
+                let bracket_match = BracketMatch {
+                    round: 1,
+                    slot: slot as u32,
+                    pet1_id: Some(*pet1_id),
+                    pet2_id: second_pets.get(slot).map(|(pet2_id, _)| *pet2_id),
+                    battle_id: None,
+                    winner_pet_id: None,
+                };
+                Self::play_bracket_match(tournament_id, round_count, bracket_match, &params)?;
+            }
             
             // Emit event
-            Self::deposit_event(Event::BattleParametersUpdated);
+            Self::deposit_event(Event::TournamentBracketGenerated {
+                tournament_id,
+                participant_count,
+                round_count,
+            });
             
-            Ok(().into())
+            Ok(())
         }
         
-        /// Enter matchmaking queue
-        #[pallet::weight(T::WeightInfo::enter_matchmaking())]
-        pub fn enter_matchmaking(
+        /// Retry moving on the winner of a stalled bracket match (see `BracketStalled`), once
+        /// whatever kept the next match's battle from starting has cleared. Anyone can call this.
+        #[pallet::weight(T::WeightInfo::advance_bracket(T::MaxTournamentParticipants::get()))]
+        pub fn resume_bracket(
             origin: OriginFor<T>,
-            pet_id: PetId,
-        ) -> DispatchResultWithPostInfo {
-            let owner = ensure_signed(origin)?;
+            tournament_id: TournamentId,
+            match_number: u32,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
             
-            // Ensure the owner owns the pet
+            // Ensure the match stalled in a tournament still in progress
             ensure!(
-                T::NftManager::owner_of(&pet_id) == Some(owner.clone()),
-                Error::<T>::NotPetOwner
+                StalledBracketMatches::<T>::contains_key(tournament_id, match_number),
+                Error::<T>::BracketNotStalled
             );
-            
-            // Ensure the pet is not already in a battle
+            let tournament = Self::tournaments(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
             ensure!(
-                !PetActiveBattle::<T>::contains_key(&pet_id),
-                Error::<T>::PetAlreadyInBattle
+                tournament.status == TournamentStatus::InProgress,
+                Error::<T>::InvalidTournamentStatus
             );
+            let decided = TournamentBracket::<T>::get(tournament_id, match_number).ok_or(Error::<T>::BracketNotStalled)?;
             
-            // Ensure the pet is not already in the matchmaking queue
-            ensure!(
-                !BattleMatchmakingQueue::<T>::contains_key(&pet_id),
-                Error::<T>::AlreadyInMatchmakingQueue
-            );
+            // Move the winner on again
+            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            let round_count = Self::bracket_round_count(tournament.current_participants);
+            Self::advance_bracket_winner(tournament_id, round_count, &decided, &params)?;
+            StalledBracketMatches::<T>::remove(tournament_id, match_number);
             
-            // Get or initialize pet battle stats
-            let (wins, losses, draws, rating) = PetBattleStats::<T>::get(&pet_id);
-            let rating = if rating == 0 { 1000 } else { rating }; // Default rating is 1000
+            Ok(())
+        }
+        
+        /// Claim a tournament prize the pallet account could not pay when the tournament ended
+        /// (see `TournamentPrizeUnpaid`).
+        #[pallet::weight(T::WeightInfo::claim_tournament_prize())]
+        pub fn claim_tournament_prize(
+            origin: OriginFor<T>,
+            tournament_id: TournamentId,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            let claimer = ensure_signed(origin)?;
             
-            // Add pet to matchmaking queue
-            let now = <frame_system::Pallet<T>>::block_number();
-            BattleMatchmakingQueue::<T>::insert(&pet_id, (owner.clone(), rating, now));
+            // Ensure the prize is the claimer's
+            let (owner, amount) =
+                UnpaidTournamentPrizes::<T>::get(tournament_id, pet_id).ok_or(Error::<T>::PrizeNotFound)?;
+            ensure!(owner == claimer, Error::<T>::NotPetOwner);
+            
+            // Pay it
+            T::Currency::transfer(&Self::account_id(), &owner, amount, ExistenceRequirement::KeepAlive)?;
+            UnpaidTournamentPrizes::<T>::remove(tournament_id, pet_id);
+            
+            // Emit event
+            Self::deposit_event(Event::TournamentPrizeAwarded { tournament_id, pet_id, owner, amount });
+            
+            Ok(())
+        }
+        
+        /// Schedule battle parameters to take effect at `activates_at`, which must be at least
+        /// `ParamsNoticePeriod` blocks away. Replaces any set already scheduled.
+        #[pallet::weight(T::WeightInfo::set_battle_params())]
+        pub fn set_battle_params(
+            origin: OriginFor<T>,
+            params: BattleParameters<BalanceOf<T>>,
+            activates_at: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            
+            // Validate parameters
+            Self::ensure_valid_params(&params)?;
+            
+            // Ensure the notice period is respected
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                activates_at >= now.saturating_add(T::ParamsNoticePeriod::get()),
+                Error::<T>::ActivationTooSoon
+            );
+            
+            // Schedule parameters
+            ScheduledBattleParams::<T>::put(ScheduledParameters { params, activates_at });
+            
+            // Emit event
+            Self::deposit_event(Event::BattleParametersScheduled(activates_at));
+            
+            Ok(().into())
+        }
+        
+        /// Set battle parameters immediately, for emergencies. Drops any scheduled set.
+        #[pallet::weight(T::WeightInfo::set_battle_params_immediately())]
+        pub fn set_battle_params_immediately(
+            origin: OriginFor<T>,
+            params: BattleParameters<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            
+            // Validate parameters
+            Self::ensure_valid_params(&params)?;
+            
+            // Update parameters
+            ScheduledBattleParams::<T>::kill();
+            let version = Self::activate_params(params, <frame_system::Pallet<T>>::block_number());
+            
+            // Emit event
+            Self::deposit_event(Event::BattleParametersOverridden(version));
+            
+            Ok(().into())
+        }
+        
+        /// Enter matchmaking queue
+        #[pallet::weight(T::WeightInfo::enter_matchmaking())]
+        pub fn enter_matchmaking(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            
+            // Ensure the owner owns the pet
+            ensure!(
+                T::NftManager::owner_of(&pet_id) == Some(owner.clone()),
+                Error::<T>::NotPetOwner
+            );
+            
+            // Ensure the pet is not already in a battle
+            ensure!(
+                !PetActiveBattle::<T>::contains_key(&pet_id),
+                Error::<T>::PetAlreadyInBattle
+            );
+            
+            // Ensure the pet is not already in the matchmaking queue
+            ensure!(
+                !BattleMatchmakingQueue::<T>::contains_key(&pet_id),
+                Error::<T>::AlreadyInMatchmakingQueue
+            );
+            
+            // Get or initialize pet battle stats
+            let (wins, losses, draws, rating) = PetBattleStats::<T>::get(&pet_id);
+            let rating = if rating == 0 { 1000 } else { rating }; // Default rating is 1000
+            
+            // Add pet to matchmaking queue
+            let now = <frame_system::Pallet<T>>::block_number();
+            BattleMatchmakingQueue::<T>::insert(&pet_id, (owner.clone(), rating, now));
             
             // Emit event
             Self::deposit_event(Event::PetAddedToMatchmaking(pet_id, owner, rating));
@@ -1138,6 +2050,12 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let player = ensure_signed(origin)?;
             
+            // Team battles keep their status effects per pet
+            if TeamBattles::<T>::contains_key(battle_id) {
+                Self::apply_team_status_effect(player, battle_id, target_pet_id, effect)?;
+                return Ok(().into());
+            }
+            
             // Get the battle
             let mut battle = Self::battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
             
@@ -1187,7 +2105,7 @@ pub mod pallet {
         }
         
         /// Use ultimate move
-        #[pallet::weight(T::WeightInfo::use_ultimate_move())]
+        #[pallet::weight(T::WeightInfo::use_ultimate_move().saturating_add(Pallet::<T>::conclude_battle_weight()))]
         pub fn use_ultimate_move(
             origin: OriginFor<T>,
             battle_id: BattleId,
@@ -1202,19 +2120,21 @@ pub mod pallet {
             
             // Determine whose turn it is
             let is_pet1_turn = battle.current_turn % 2 == 1;
-            let (active_pet_id, active_owner, active_energy, target_pet_id) = if is_pet1_turn {
-                (battle.pet1_id, battle.pet1_owner.clone(), battle.pet1_energy, battle.pet2_id)
+            let (active_pet_id, active_owner, target_pet_id) = if is_pet1_turn {
+                (battle.pet1_id, battle.pet1_owner.clone(), battle.pet2_id)
             } else {
-                (battle.pet2_id, battle.pet2_owner.clone(), battle.pet2_energy, battle.pet1_id)
+                (battle.pet2_id, battle.pet2_owner.clone(), battle.pet1_id)
             };
             
             // Ensure it's the player's turn
             ensure!(active_owner == player, Error::<T>::NotYourTurn);
             
             // Get battle parameters
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            let params = Self::params_of(&battle)?;
             
-            // Ensure the pet has enough energy
+            // Ensure the pet has enough energy, once regenerated
+            Self::regenerate_energy(&mut battle, &params);
+            let active_energy = if is_pet1_turn { battle.pet1_energy } else { battle.pet2_energy };
             ensure!(
                 active_energy >= params.ultimate_move_energy_cost,
                 Error::<T>::InsufficientEnergy
@@ -1243,7 +2163,7 @@ pub mod pallet {
             
             // Execute the ultimate move (high damage based on pet stats)
             let pet_stats = T::PetManager::get_pet_attributes(&active_pet_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
+                .map_err(Error::<T>::from)?;
             
             let strength = pet_stats.iter()
                 .find_map(|(attr, val)| if *attr == AttributeType::Strength { Some(*val) } else { None })
@@ -1254,7 +2174,7 @@ pub mod pallet {
                 .unwrap_or(50);
             
             // Calculate damage based on both strength and intelligence
-            let damage = 20 + (strength / 5) + (intelligence / 10);
+            let damage = crate::damage::ultimate_damage(strength, intelligence);
             
             // Apply damage to target
             if target_pet_id == battle.pet1_id {
@@ -1285,7 +2205,7 @@ pub mod pallet {
                     move_type: BattleMove::Ultimate,
                     result: result.clone(),
                 },
-            )?;
+            );
             
             // Increment turn counter
             battle.current_turn += 1;
@@ -1305,6 +2225,324 @@ pub mod pallet {
             
             Ok(().into())
         }
+        
+        /// Prune an account's active battle list of battles that have already ended.
+        /// Permissionless, so anyone can repair an account whose list filled up with stale ids.
+        #[pallet::weight(T::WeightInfo::repair_active_battles())]
+        pub fn repair_active_battles(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            
+            // Keep only battles that still exist and have not reached a terminal state
+            let removed = AccountActiveBattles::<T>::mutate(&account, |battles| {
+                let before = battles.len();
+                battles.retain(|battle_id| {
+                    Self::battles(battle_id).map_or(false, |battle| !Self::is_terminal(&battle.status))
+                });
+                (before - battles.len()) as u32
+            });
+            
+            // Emit event
+            if removed > 0 {
+                Self::deposit_event(Event::ActiveBattlesRepaired(account, removed));
+            }
+            
+            Ok(().into())
+        }
+        
+        /// Bet `amount` on the outcome of another account's battle, reserving it until the
+        /// battle settles. Bets are open while the challenge is pending and for the first
+        /// `max_bet_turn` turns of the battle.
+        #[pallet::weight(T::WeightInfo::place_bet())]
+        pub fn place_bet(
+            origin: OriginFor<T>,
+            battle_id: BattleId,
+            predicted_outcome: BattleOutcome,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let bettor = ensure_signed(origin)?;
+            
+            // Ensure the battle takes the bet, then reserve the stake and record it
+            let battle = Self::battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
+            Self::record_bet(&battle, &bettor, predicted_outcome.clone(), amount)?;
+            
+            // Emit event
+            Self::deposit_event(Event::BetPlaced(battle_id, bettor, predicted_outcome, amount));
+            
+            Ok(().into())
+        }
+        
+        /// Wager on a pet winning a battle: a bet on its win, settled like any other bet
+        #[pallet::weight(T::WeightInfo::place_bet())]
+        pub fn wager_on_battle(
+            origin: OriginFor<T>,
+            battle_id: BattleId,
+            bet_on_pet: PetId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let bettor = ensure_signed(origin)?;
+            
+            // Bet on the win of the pet's side
+            let battle = Self::battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
+            let predicted_outcome = if bet_on_pet == battle.pet1_id {
+                BattleOutcome::Pet1Win
+            } else if bet_on_pet == battle.pet2_id {
+                BattleOutcome::Pet2Win
+            } else {
+                return Err(Error::<T>::InvalidBet.into());
+            };
+            Self::record_bet(&battle, &bettor, predicted_outcome, amount)?;
+            BattleWagers::<T>::insert(battle_id, &bettor, (bet_on_pet, amount));
+            
+            // Emit event
+            Self::deposit_event(Event::BattleWagerPlaced { battle_id, bettor, bet_on_pet, amount });
+            
+            Ok(().into())
+        }
+        
+        /// Claim a winning bet on a settled battle: the stake back, plus a share of the losing
+        /// stakes after the house cut, in proportion to the stake.
+        #[pallet::weight(T::WeightInfo::claim_bet_winnings())]
+        pub fn claim_bet_winnings(
+            origin: OriginFor<T>,
+            battle_id: BattleId,
+        ) -> DispatchResultWithPostInfo {
+            let bettor = ensure_signed(origin)?;
+            
+            // Losing bets are removed at settlement, so a bet left on a settled battle won
+            let bet = Self::battle_bets(battle_id, &bettor).ok_or(Error::<T>::BetNotFound)?;
+            let mut pool = BattleBetPools::<T>::get(battle_id);
+            let settlement = pool.settlement.clone().ok_or(Error::<T>::BetsNotSettled)?;
+            
+            // Pay the share of the winnings and return the stake
+            let share = Perbill::from_rational(bet.amount, settlement.winning_stake).mul_floor(settlement.winnings);
+            T::Currency::transfer(&Self::bets_account_id(), &bettor, share, ExistenceRequirement::AllowDeath)?;
+            T::Currency::unreserve(&bettor, bet.amount);
+            
+            // Remove the bet, and the pool with the last one
+            BattleBets::<T>::remove(battle_id, &bettor);
+            pool.bettors = pool.bettors.saturating_sub(1);
+            if pool.bettors == 0 {
+                BattleBetPools::<T>::remove(battle_id);
+            } else {
+                BattleBetPools::<T>::insert(battle_id, pool);
+            }
+            
+            // Emit event
+            let winnings = bet.amount.saturating_add(share);
+            if BattleWagers::<T>::take(battle_id, &bettor).is_some() {
+                Self::deposit_event(Event::BattleWagerPayout { battle_id, bettor, winnings });
+            } else {
+                Self::deposit_event(Event::BetWon(battle_id, bettor, winnings));
+            }
+            
+            Ok(().into())
+        }
+        
+        /// Challenge another account's pets to a 2v2 team battle. The pets are only committed
+        /// to the battle once the challenge is accepted.
+        #[pallet::weight(T::WeightInfo::create_team_challenge())]
+        pub fn create_team_challenge(
+            origin: OriginFor<T>,
+            my_pet1: PetId,
+            my_pet2: PetId,
+            target_account: T::AccountId,
+            target_pet1: PetId,
+            target_pet2: PetId,
+        ) -> DispatchResultWithPostInfo {
+            let challenger = ensure_signed(origin)?;
+            
+            // Ensure the challenger is not challenging themselves
+            ensure!(challenger != target_account, Error::<T>::InvalidBattleStatus);
+            
+            // Ensure the challenger doesn't have too many active battles
+            ensure!(
+                AccountActiveBattles::<T>::get(&challenger).len() < T::MaxActiveBattles::get() as usize,
+                Error::<T>::TooManyActiveBattles
+            );
+            
+            // Ensure each team is two different pets of its owner
+            ensure!(my_pet1 != my_pet2 && target_pet1 != target_pet2, Error::<T>::InvalidTeam);
+            for (pet_id, owner) in [
+                (my_pet1, &challenger),
+                (my_pet2, &challenger),
+                (target_pet1, &target_account),
+                (target_pet2, &target_account),
+            ] {
+                ensure!(T::NftManager::owner_of(&pet_id).as_ref() == Some(owner), Error::<T>::NotPetOwner);
+                ensure!(!PetActiveBattle::<T>::contains_key(pet_id), Error::<T>::PetAlreadyInBattle);
+            }
+            
+            // Get the battle parameters
+            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            
+            // Reserve the challenge bond
+            T::Currency::reserve(&challenger, params.challenge_bond)?;
+            
+            // Create the battle
+            let battle_id = Self::next_battle_id()?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let team1_health = [Self::initial_health(my_pet1)?, Self::initial_health(my_pet2)?];
+            let team2_health = [Self::initial_health(target_pet1)?, Self::initial_health(target_pet2)?];
+            let battle = TeamBattle {
+                id: battle_id,
+                team1_owner: challenger.clone(),
+                team2_owner: target_account.clone(),
+                team1_pet1_id: my_pet1,
+                team1_pet2_id: my_pet2,
+                team2_pet1_id: target_pet1,
+                team2_pet2_id: target_pet2,
+                status: BattleStatus::Challenged,
+                current_turn: 0,
+                team1: TeamState::new(team1_health, params.initial_energy),
+                team2: TeamState::new(team2_health, params.initial_energy),
+                winning_team: None,
+                created_at: now,
+                updated_at: now,
+                completed_at: None,
+                params_version: Self::battle_params_version(),
+            };
+            
+            // Store the battle, and take up a team battle slot and the challenger's battle slot
+            TeamBattles::<T>::insert(battle_id, battle);
+            ActiveTeamBattles::<T>::try_append(battle_id).map_err(|_| Error::<T>::TooManyTeamBattles)?;
+            AccountActiveBattles::<T>::try_mutate(&challenger, |battles| {
+                battles.try_push(battle_id).map_err(|_| Error::<T>::TooManyActiveBattles)
+            })?;
+            
+            // Emit event
+            Self::deposit_event(Event::TeamChallengeCreated {
+                battle_id,
+                challenger,
+                challenged: target_account,
+            });
+            
+            Ok(().into())
+        }
+        
+        /// Accept a team battle challenge, committing all four pets to the battle
+        #[pallet::weight(T::WeightInfo::accept_team_challenge())]
+        pub fn accept_team_challenge(
+            origin: OriginFor<T>,
+            battle_id: BattleId,
+        ) -> DispatchResultWithPostInfo {
+            let acceptor = ensure_signed(origin)?;
+            
+            // Get the battle
+            let mut battle = Self::team_battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
+            
+            // Ensure the battle is in the challenged state
+            ensure!(battle.status == BattleStatus::Challenged, Error::<T>::InvalidBattleStatus);
+            
+            // Ensure the acceptor is the challenged team's owner
+            ensure!(battle.team2_owner == acceptor, Error::<T>::NotBattleParticipant);
+            
+            // Ensure the battle hasn't expired
+            let now = <frame_system::Pallet<T>>::block_number();
+            let params = Self::params_at_version(battle.params_version)?;
+            ensure!(
+                now <= battle.created_at + params.challenge_expiry_blocks.into(),
+                Error::<T>::BattleExpired
+            );
+            
+            // Ensure no pet has entered another battle since the challenge
+            let pet_ids = battle.pet_ids();
+            ensure!(
+                pet_ids.iter().all(|pet_id| !PetActiveBattle::<T>::contains_key(pet_id)),
+                Error::<T>::PetAlreadyInBattle
+            );
+            for pet_id in pet_ids {
+                PetActiveBattle::<T>::insert(pet_id, battle_id);
+            }
+            
+            // Update battle status
+            battle.status = BattleStatus::Active;
+            battle.current_turn = 1; // Team 1's first pet goes first
+            battle.updated_at = now;
+            TeamBattles::<T>::insert(battle_id, battle);
+            
+            // Update account active battles
+            AccountActiveBattles::<T>::try_mutate(&acceptor, |battles| {
+                battles.try_push(battle_id).map_err(|_| Error::<T>::TooManyActiveBattles)
+            })?;
+            
+            // Emit event
+            Self::deposit_event(Event::TeamChallengeAccepted { battle_id });
+            
+            Ok(().into())
+        }
+        
+        /// Execute a move in a team battle with the pet whose turn it is
+        #[pallet::weight(T::WeightInfo::execute_team_move().saturating_add(Pallet::<T>::conclude_team_battle_weight()))]
+        pub fn execute_team_move(
+            origin: OriginFor<T>,
+            battle_id: BattleId,
+            active_pet_id: PetId,
+            move_type: BattleMove,
+        ) -> DispatchResultWithPostInfo {
+            let player = ensure_signed(origin)?;
+            
+            // Get the battle
+            let mut battle = Self::team_battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
+            
+            // Ensure the battle is active
+            ensure!(battle.status == BattleStatus::Active, Error::<T>::InvalidBattleStatus);
+            
+            // Ensure it's the player's pet's turn
+            let (team, member) = TeamBattle::<T::AccountId, T::BlockNumber>::slot_of_turn(battle.current_turn);
+            ensure!(
+                *battle.owner(team) == player && battle.pet_id(team, member) == active_pet_id,
+                Error::<T>::NotYourTurn
+            );
+            
+            // Regenerate the pet's energy after its first turn, then pay for the move
+            let params = Self::params_at_version(battle.params_version)?;
+            let regenerates = battle.current_turn > 4;
+            let energy = &mut battle.team_mut(team).energy[member];
+            if regenerates {
+                *energy = energy.saturating_add(params.energy_per_turn).min(MAX_ENERGY);
+            }
+            *energy = energy.checked_sub(params.energy_cost(&move_type)).ok_or(Error::<T>::InsufficientEnergy)?;
+            if move_type == BattleMove::Defend {
+                *energy = energy.saturating_add(params.move_energy_costs.defend_restore).min(MAX_ENERGY);
+            }
+            
+            // Process the move
+            battle.team_mut(team).last_moves[member] = Some(move_type.clone());
+            let result = Self::process_team_move(&mut battle, (team, member), &move_type, &params)?;
+            
+            // Add to battle history
+            Self::add_to_battle_history(
+                battle_id,
+                BattleMoveHistoryEntry {
+                    turn: battle.current_turn,
+                    pet_id: active_pet_id,
+                    move_type: move_type.clone(),
+                    result: result.clone(),
+                },
+            );
+            
+            // Move to the next pet, unless the battle is over
+            battle.updated_at = <frame_system::Pallet<T>>::block_number();
+            if battle.team1.is_defeated() || battle.team2.is_defeated() {
+                Self::conclude_team_battle(&mut battle, &params)?;
+            } else {
+                battle.advance_turn();
+                // Each pet gets the turns it would in a 1v1 battle
+                if battle.current_turn > params.max_turns.saturating_mul(2) {
+                    Self::conclude_team_battle(&mut battle, &params)?;
+                }
+            }
+            TeamBattles::<T>::insert(battle_id, battle);
+            
+            // Emit events
+            Self::deposit_event(Event::BattleMoveExecuted(battle_id, player, active_pet_id, move_type));
+            Self::deposit_event(Event::BattleMoveResult(battle_id, active_pet_id, result));
+            
+            Ok(().into())
+        }
     }
 
     // Define hooks for the pallet
@@ -1312,6 +2550,17 @@ pub mod pallet {
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         /// Called at the beginning of a new block
         fn on_initialize(n: T::BlockNumber) -> Weight {
+            let mut weight = Weight::zero();
+            
+            // Promote scheduled battle parameters that are due
+            if let Some(scheduled) = ScheduledBattleParams::<T>::get() {
+                if n >= scheduled.activates_at {
+                    ScheduledBattleParams::<T>::kill();
+                    let version = Self::activate_params(scheduled.params, n);
+                    Self::deposit_event(Event::BattleParametersUpdated(version));
+                }
+            }
+            
             // Check for tournaments that should start
             for (tournament_id, tournament) in Tournaments::<T>::iter() {
                 if tournament.status == TournamentStatus::Registration && n >= tournament.start_block {
@@ -1341,10 +2590,11 @@ pub mod pallet {
                 }
             }
             
-            // Check for expired battle challenges
-            let params = if let Some(p) = BattleParams::<T>::get() { p } else { return Weight::zero() };
-            
+            // Check for expired battle challenges, and forfeit a bounded number of stalled battles
+            let mut timeouts_left = T::MaxTurnTimeoutsPerBlock::get();
             for (battle_id, battle) in Battles::<T>::iter() {
+                let params = if let Ok(p) = Self::params_of(&battle) { p } else { continue };
+                
                 if battle.status == BattleStatus::Challenged {
                     if n > battle.created_at + params.challenge_expiry_blocks.into() {
                         // Expire the challenge
@@ -1357,8 +2607,16 @@ pub mod pallet {
                         PetActiveBattle::<T>::remove(&updated_battle.pet1_id);
                         PetActiveBattle::<T>::remove(&updated_battle.pet2_id);
                         
+                        // Free the battle slot for both participants
+                        Self::remove_account_active_battle(&updated_battle.pet1_owner, battle_id);
+                        Self::remove_account_active_battle(&updated_battle.pet2_owner, battle_id);
+                        
                         // Unreserve the challenger's bond
                         T::Currency::unreserve(&updated_battle.pet1_owner, params.challenge_bond);
+                        
+                        // Refund the spectators' bets
+                        Self::refund_bets(battle_id);
+                        weight = weight.saturating_add(T::WeightInfo::refund_bets(T::MaxBetsPerBattle::get()));
                     }
                 } else if battle.status == BattleStatus::Active {
                     // Process status effects for active battles
@@ -1375,25 +2633,77 @@ pub mod pallet {
                         continue;
                     }
                     
-                    // Add energy each turn (only at the start of a new turn)
-                    if n > updated_battle.updated_at {
-                        updated_battle.pet1_energy = (updated_battle.pet1_energy + params.energy_per_turn).min(100);
-                        updated_battle.pet2_energy = (updated_battle.pet2_energy + params.energy_per_turn).min(100);
-                    }
-                    
                     // Check if the battle is over due to status effects
                     if updated_battle.pet1_health == 0 || updated_battle.pet2_health == 0 {
+                        weight = weight.saturating_add(Self::conclude_battle_weight());
                         if let Err(_) = Self::finalize_battle(&mut updated_battle) {
                             // If there's an error, just continue to the next battle
                             continue;
                         }
                     }
                     
+                    // Forfeit the battle for the active player if their turn has timed out
+                    if updated_battle.status == BattleStatus::Active
+                        && timeouts_left > 0
+                        && Self::turn_timed_out(&updated_battle, &params, n)
+                    {
+                        timeouts_left -= 1;
+                        weight = weight.saturating_add(Self::turn_timeout_weight());
+                        let (stalled, _) = Self::stalled_and_waiting(&updated_battle);
+                        Self::deposit_event(Event::BattleTurnTimedOut { battle_id, stalled: stalled.clone() });
+                        Self::settle_forfeit(&mut updated_battle, stalled, &params);
+                        continue;
+                    }
+                    
                     // Update the battle
                     Battles::<T>::insert(battle_id, updated_battle);
                 }
             }
             
+            // Expire team challenges, and process status effects in active team battles
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+            for battle_id in ActiveTeamBattles::<T>::get() {
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+                let mut battle = if let Some(battle) = TeamBattles::<T>::get(battle_id) { battle } else { continue };
+                let params = if let Ok(p) = Self::params_at_version(battle.params_version) { p } else { continue };
+                
+                if battle.status == BattleStatus::Challenged {
+                    if n > battle.created_at + params.challenge_expiry_blocks.into() {
+                        battle.status = BattleStatus::Expired;
+                        battle.updated_at = n;
+                        Self::release_team_battle(&battle, &params);
+                        TeamBattles::<T>::insert(battle_id, battle);
+                    }
+                } else if battle.status == BattleStatus::Active {
+                    for (team, member) in TeamBattle::<T::AccountId, T::BlockNumber>::TURN_ORDER {
+                        let pet_id = battle.pet_id(team, member);
+                        let state = battle.team_mut(team);
+                        Self::tick_status_effects(
+                            battle_id,
+                            pet_id,
+                            &mut state.status_effects[member],
+                            &mut state.health[member],
+                        );
+                    }
+                    
+                    // End the battle if a team has fallen to its status effects, or pass over
+                    // the pet whose turn it is if it has fainted
+                    if battle.team1.is_defeated() || battle.team2.is_defeated() {
+                        weight = weight.saturating_add(Self::conclude_team_battle_weight());
+                        if let Err(_) = Self::conclude_team_battle(&mut battle, &params) {
+                            continue;
+                        }
+                    } else {
+                        let (team, member) = TeamBattle::<T::AccountId, T::BlockNumber>::slot_of_turn(battle.current_turn);
+                        if battle.team(team).health[member] == 0 {
+                            battle.advance_turn();
+                        }
+                    }
+                    
+                    TeamBattles::<T>::insert(battle_id, battle);
+                }
+            }
+            
             // Process matchmaking queue
             // Every 10 blocks, try to match pets that have been waiting the longest
             if n % 10u32.into() == 0u32.into() {
@@ -1418,7 +2728,15 @@ pub mod pallet {
                 }
             }
             
-            Weight::zero()
+            // Start a rating decay pass every interval, unless the last one is still running, and
+            // decay the ratings of a batch of inactive pets
+            let decay_interval = T::EloDecayIntervalBlocks::get();
+            if decay_interval > 0 && n % decay_interval.into() == 0u32.into() && !RatingDecayCursor::<T>::exists() {
+                RatingDecayCursor::<T>::put(RatingDecayProgress::MatchmakingQueue(None));
+            }
+            weight = weight.saturating_add(Self::decay_ratings(n));
+            
+            weight
         }
     }
 
@@ -1426,6 +2744,8 @@ pub mod pallet {
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub battle_parameters: BattleParameters<BalanceOf<T>>,
+        /// Launch tournaments, created with sequential ids from 0
+        pub tournaments: Vec<GenesisTournamentOf<T>>,
     }
 
     #[cfg(feature = "std")]
@@ -1449,7 +2769,12 @@ pub mod pallet {
                     energy_per_turn: 10,
                     ultimate_move_energy_cost: 40,
                     matchmaking_rating_change: 25,
+                    max_bet_turn: DEFAULT_MAX_BET_TURN,
+                    bet_house_cut: DEFAULT_BET_HOUSE_CUT,
+                    move_energy_costs: MoveEnergyCosts::default(),
+                    turn_timeout_blocks: DEFAULT_TURN_TIMEOUT_BLOCKS,
                 },
+                tournaments: Vec::new(),
             }
         }
     }
@@ -1460,16 +2785,223 @@ pub mod pallet {
             BattleParams::<T>::put(&self.battle_parameters);
             BattleCount::<T>::put(0);
             TournamentCount::<T>::put(0);
+
+            // Prize seeds come out of the pallet account, which must be funded at genesis.
+            let total_seed = self.tournaments.iter()
+                .fold(BalanceOf::<T>::zero(), |total, tournament| total.saturating_add(tournament.prize_seed));
+            assert!(
+                T::Currency::free_balance(&Pallet::<T>::account_id()) >= total_seed,
+                "genesis tournaments: prize seeds exceed the pallet account's genesis balance",
+            );
+
+            let genesis_block = <frame_system::Pallet<T>>::block_number();
+            for (index, tournament) in self.tournaments.iter().enumerate() {
+                assert!(tournament.max_participants > 0, "genesis tournament {}: max_participants is zero", index);
+                assert!(
+                    tournament.max_participants <= T::MaxTournamentParticipants::get(),
+                    "genesis tournament {}: max_participants exceeds MaxTournamentParticipants",
+                    index,
+                );
+                assert!(
+                    tournament.min_pet_level <= tournament.max_pet_level,
+                    "genesis tournament {}: min_pet_level is above max_pet_level",
+                    index,
+                );
+                assert!(!tournament.start_offset.is_zero(), "genesis tournament {}: start_offset is zero", index);
+                let name: BoundedVec<u8, ConstU32<64>> = tournament.name.clone().try_into()
+                    .unwrap_or_else(|_| panic!("genesis tournament {}: name is longer than 64 bytes", index));
+                let description: BoundedVec<u8, ConstU32<256>> = tournament.description.clone().try_into()
+                    .unwrap_or_else(|_| panic!("genesis tournament {}: description is longer than 256 bytes", index));
+
+                let tournament_id = Pallet::<T>::next_tournament_id()
+                    .unwrap_or_else(|_| panic!("genesis tournament {}: tournament ids exhausted", index));
+                Tournaments::<T>::insert(tournament_id, Tournament {
+                    id: tournament_id,
+                    name,
+                    description,
+                    max_participants: tournament.max_participants,
+                    current_participants: 0,
+                    min_pet_level: tournament.min_pet_level,
+                    max_pet_level: tournament.max_pet_level,
+                    entry_fee: tournament.entry_fee,
+                    prize_pool: tournament.prize_seed,
+                    status: TournamentStatus::Registration,
+                    start_block: genesis_block.saturating_add(tournament.start_offset),
+                    end_block: None,
+                    winner_pet_id: None,
+                });
+                TournamentCount::<T>::put(tournament_id + 1);
+            }
         }
     }
 
     // Helper functions
     impl<T: Config> Pallet<T> {
-        /// Get the next battle ID
+        /// Remove a battle from an account's active battles, if present
+        fn remove_account_active_battle(account: &T::AccountId, battle_id: BattleId) {
+            AccountActiveBattles::<T>::mutate(account, |battles| {
+                battles.retain(|id| *id != battle_id);
+            });
+        }
+        
+        /// Whether a battle in this status can no longer change
+        fn is_terminal(status: &BattleStatus) -> bool {
+            matches!(status, BattleStatus::Completed | BattleStatus::Forfeited | BattleStatus::Expired)
+        }
+        
+        /// Get the parameters a battle settles with: those of the version it was created under,
+        /// or the current ones once that version has left `ParamsHistory`.
+        pub fn params_of(
+            battle: &Battle<T::AccountId, T::BlockNumber>,
+        ) -> Result<BattleParameters<BalanceOf<T>>, Error<T>> {
+            Self::params_at_version(battle.params_version)
+        }
+        
+        /// Get the parameters of `version`, or the current ones once it has left `ParamsHistory`.
+        pub(crate) fn params_at_version(version: u32) -> Result<BattleParameters<BalanceOf<T>>, Error<T>> {
+            if version != Self::battle_params_version() {
+                let recorded = ParamsHistory::<T>::get()
+                    .into_iter()
+                    .find(|entry| entry.version == version);
+                if let Some(entry) = recorded {
+                    return Ok(entry.params);
+                }
+            }
+            Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)
+        }
+        
+        /// Ensure a parameter set can be used for battles
+        fn ensure_valid_params(params: &BattleParameters<BalanceOf<T>>) -> Result<(), Error<T>> {
+            ensure!(params.max_turns > 0, Error::<T>::InvalidBattleParameters);
+            ensure!(params.challenge_expiry_blocks > 0, Error::<T>::InvalidBattleParameters);
+            ensure!(params.turn_timeout_blocks > 0, Error::<T>::InvalidBattleParameters);
+            ensure!(params.initial_energy > 0, Error::<T>::InvalidBattleParameters);
+            ensure!(params.energy_per_turn > 0, Error::<T>::InvalidBattleParameters);
+            ensure!(params.ultimate_move_energy_cost > 0, Error::<T>::InvalidBattleParameters);
+            let costs = &params.move_energy_costs;
+            ensure!(
+                [costs.attack, costs.defend, costs.special_attack, costs.heal, costs.dodge, costs.elemental_attack]
+                    .into_iter()
+                    .all(|cost| cost <= MAX_ENERGY),
+                Error::<T>::InvalidBattleParameters
+            );
+            Ok(())
+        }
+        
+        /// Credits the pet whose turn it is with `energy_per_turn`, from its second turn on.
+        fn regenerate_energy(battle: &mut Battle<T::AccountId, T::BlockNumber>, params: &BattleParameters<BalanceOf<T>>) {
+            if battle.current_turn <= 2 {
+                return;
+            }
+            let energy = if battle.current_turn % 2 == 1 { &mut battle.pet1_energy } else { &mut battle.pet2_energy };
+            *energy = energy.saturating_add(params.energy_per_turn).min(MAX_ENERGY);
+        }
+        
+        /// Make `params` the current battle parameters as of block `now`, moving the replaced
+        /// set into `ParamsHistory`. Returns the new version.
+        fn activate_params(params: BattleParameters<BalanceOf<T>>, now: T::BlockNumber) -> u32 {
+            let version = Self::battle_params_version();
+            if let Some(previous) = BattleParams::<T>::get() {
+                ParamsHistory::<T>::mutate(|history| {
+                    push_rotating(history, ParamsHistoryEntry {
+                        version,
+                        params: previous,
+                        active_from: BattleParamsActiveSince::<T>::get(),
+                        active_until: now,
+                    });
+                });
+            }
+            
+            let next_version = version.wrapping_add(1);
+            BattleParams::<T>::put(params);
+            BattleParamsVersion::<T>::put(next_version);
+            BattleParamsActiveSince::<T>::put(now);
+            next_version
+        }
+        
+        /// Builds a battle between two pets, each given as its ID and owner, with their health
+        /// from their vitality. An active battle starts at the first turn.
+        fn new_battle(
+            battle_id: BattleId,
+            (pet1_id, pet1_owner): (PetId, T::AccountId),
+            (pet2_id, pet2_owner): (PetId, T::AccountId),
+            status: BattleStatus,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) -> Result<Battle<T::AccountId, T::BlockNumber>, Error<T>> {
+            let now = <frame_system::Pallet<T>>::block_number();
+            
+            // Calculate initial health based on vitality
+            let pet1_health = Self::initial_health(pet1_id)?;
+            let pet2_health = Self::initial_health(pet2_id)?;
+            
+            let current_turn = if status == BattleStatus::Active { 1 } else { 0 }; // Pet1 goes first
+            
+            Ok(Battle {
+                id: battle_id,
+                pet1_id,
+                pet2_id,
+                pet1_owner,
+                pet2_owner,
+                status,
+                current_turn,
+                pet1_health,
+                pet2_health,
+                pet1_energy: params.initial_energy,
+                pet2_energy: params.initial_energy,
+                pet1_status_effects: BoundedVec::default(),
+                pet2_status_effects: BoundedVec::default(),
+                last_move_pet1: None,
+                last_move_pet2: None,
+                last_move_result: None,
+                combo_counter_pet1: 0,
+                combo_counter_pet2: 0,
+                outcome: None,
+                created_at: now,
+                updated_at: now,
+                completed_at: None,
+                reward_claimed: false,
+                battle_rating: None,
+                params_version: Self::battle_params_version(),
+            })
+        }
+        
+        /// A pet's health at the start of a battle: its vitality.
+        fn initial_health(pet_id: PetId) -> Result<u8, Error<T>> {
+            let stats = T::PetManager::get_pet_attributes(&pet_id).map_err(Error::<T>::from)?;
+            Ok(Self::attribute(&stats, AttributeType::Vitality, 50))
+        }
+        
+        /// A pet's value of `attribute`, or `default` if the pet has none.
+        fn attribute(stats: &[(AttributeType, u8)], attribute: AttributeType, default: u8) -> u8 {
+            stats.iter()
+                .find_map(|(attr, val)| if *attr == attribute { Some(*val) } else { None })
+                .unwrap_or(default)
+        }
+        
+        /// The rounds of a single-elimination bracket for `participant_count` entrants:
+        /// `ceil(log2(participant_count))`.
+        pub fn bracket_round_count(participant_count: u32) -> u8 {
+            (u32::BITS - participant_count.saturating_sub(1).leading_zeros()) as u8
+        }
+        
+        /// Shuffles `items` with `BattleRandomness` for `subject`, by Fisher-Yates.
+        fn shuffle<Item>(items: &mut [Item], subject: &[u8]) {
+            let (random_seed, _) = T::BattleRandomness::random(subject);
+            for index in (1..items.len()).rev() {
+                let entropy = T::Hashing::hash_of(&(random_seed, index as u32));
+                let draw = entropy.as_ref().iter().take(4).fold(0u32, |draw, byte| draw << 8 | *byte as u32);
+                let pick = draw as usize % (index + 1);
+                items.swap(index, pick);
+            }
+        }
+        
+        /// Take the next battle ID, moving `BattleCount` past it
         fn next_battle_id() -> Result<BattleId, Error<T>> {
-            let battle_id = Self::battle_count();
-            let next_id = battle_id.checked_add(1).ok_or(Error::<T>::BattleIdOverflow)?;
-            Ok(battle_id)
+            BattleCount::<T>::try_mutate(|next_id| {
+                let battle_id = *next_id;
+                *next_id = next_id.checked_add(1).ok_or(Error::<T>::BattleIdOverflow)?;
+                Ok(battle_id)
+            })
         }
         
         /// Get the next tournament ID
@@ -1479,14 +3011,14 @@ pub mod pallet {
             Ok(tournament_id)
         }
         
-        /// Add an entry to battle history
-        fn add_to_battle_history(
+        /// Add an entry to battle history, dropping the oldest entry when the history is full
+        pub(crate) fn add_to_battle_history(
             battle_id: BattleId,
             entry: BattleMoveHistoryEntry,
-        ) -> Result<(), Error<T>> {
-            BattleHistory::<T>::try_mutate(battle_id, |history| {
-                history.try_push(entry).map_err(|_| Error::<T>::BattleHistoryTooLong)
-            })
+        ) {
+            BattleHistory::<T>::mutate(battle_id, |history| {
+                push_rotating(history, entry);
+            });
         }
         
         /// Try to find a match for a pet in the matchmaking queue
@@ -1528,60 +3060,20 @@ pub mod pallet {
             if let Some((match_pet_id, match_owner, match_rating, _)) = best_match {
                 // Only match if rating difference is reasonable (within 200 points)
                 if best_rating_diff <= 200 {
-                    // Create the battle
+                    // Create the battle; matchmaking battles start immediately
                     let battle_id = Self::next_battle_id()?;
-                    let now = <frame_system::Pallet<T>>::block_number();
                     let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
-                    
-                    // Get pet stats
-                    let pet1_stats = T::PetManager::get_pet_attributes(&pet_id)
-                        .ok_or(Error::<T>::NotPetOwner)?;
-                    let pet2_stats = T::PetManager::get_pet_attributes(&match_pet_id)
-                        .ok_or(Error::<T>::NotPetOwner)?;
-                    
-                    // Calculate initial health based on vitality
-                    let pet1_health = pet1_stats.iter()
-                        .find_map(|(attr, val)| if *attr == AttributeType::Vitality { Some(*val) } else { None })
-                        .unwrap_or(50);
-                    
-                    let pet2_health = pet2_stats.iter()
-                        .find_map(|(attr, val)| if *attr == AttributeType::Vitality { Some(*val) } else { None })
-                        .unwrap_or(50);
-                    
-                    // Create empty status effect vectors
-                    let pet1_status_effects: BoundedVec<StatusEffect, ConstU32<5>> = BoundedVec::default();
-                    let pet2_status_effects: BoundedVec<StatusEffect, ConstU32<5>> = BoundedVec::default();
-                    
-                    let battle = Battle {
-                        id: battle_id,
-                        pet1_id: pet_id,
-                        pet2_id: match_pet_id,
-                        pet1_owner: pet_owner.clone(),
-                        pet2_owner: match_owner.clone(),
-                        status: BattleStatus::Active, // Matchmaking battles start immediately
-                        current_turn: 1, // Pet1 goes first
-                        pet1_health,
-                        pet2_health,
-                        pet1_energy: params.initial_energy,
-                        pet2_energy: params.initial_energy,
-                        pet1_status_effects,
-                        pet2_status_effects,
-                        last_move_pet1: None,
-                        last_move_pet2: None,
-                        last_move_result: None,
-                        combo_counter_pet1: 0,
-                        combo_counter_pet2: 0,
-                        outcome: None,
-                        created_at: now,
-                        updated_at: now,
-                        completed_at: None,
-                        reward_claimed: false,
-                        battle_rating: Some((pet_rating + match_rating) / 2), // Average rating
-                    };
+                    let mut battle = Self::new_battle(
+                        battle_id,
+                        (pet_id, pet_owner.clone()),
+                        (match_pet_id, match_owner.clone()),
+                        BattleStatus::Active,
+                        &params,
+                    )?;
+                    battle.battle_rating = Some((pet_rating + match_rating) / 2); // Average rating
                     
                     // Store the battle
                     Battles::<T>::insert(battle_id, battle);
-                    BattleCount::<T>::put(battle_id + 1);
                     
                     // Update pet active battles
                     PetActiveBattle::<T>::insert(&pet_id, battle_id);
@@ -1609,69 +3101,626 @@ pub mod pallet {
             Ok(())
         }
         
-        /// Finalize a battle (determine outcome, update stats)
-        fn finalize_battle(battle: &mut Battle<T::AccountId, T::BlockNumber>) -> Result<(), Error<T>> {
-            // Ensure the battle is active
-            ensure!(battle.status == BattleStatus::Active, Error::<T>::InvalidBattleStatus);
+        /// Finalize a battle (determine outcome, award experience, update stats, emit `BattleCompleted`).
+        /// This is the only path that completes a battle on health or turns, so a battle that
+        /// has already ended is left untouched rather than rewarded a second time.
+        fn finalize_battle(battle: &mut Battle<T::AccountId, T::BlockNumber>) -> Result<(), Error<T>> {
+            // A battle that has already ended must not be finalized again
+            if Self::is_terminal(&battle.status) {
+                return Ok(());
+            }
+            
+            // Ensure the battle is active
+            ensure!(battle.status == BattleStatus::Active, Error::<T>::InvalidBattleStatus);
+            
+            // Determine the outcome
+            let outcome = if battle.pet1_health == 0 && battle.pet2_health == 0 {
+                BattleOutcome::Draw
+            } else if battle.pet1_health == 0 {
+                BattleOutcome::Pet2Win
+            } else if battle.pet2_health == 0 {
+                BattleOutcome::Pet1Win
+            } else if battle.pet1_health > battle.pet2_health {
+                BattleOutcome::Pet1Win
+            } else if battle.pet2_health > battle.pet1_health {
+                BattleOutcome::Pet2Win
+            } else {
+                BattleOutcome::Draw
+            };
+            
+            // Get battle parameters
+            let params = Self::params_of(battle)?;
+            
+            // Conclude the battle
+            let completed_at = battle.updated_at;
+            Self::conclude_battle(battle, BattleStatus::Completed, outcome.clone(), completed_at);
+            
+            // Award experience to pets
+            let xp_reward = params.base_experience_reward;
+            let rating_change = params.matchmaking_rating_change;
+            match outcome {
+                BattleOutcome::Pet1Win => {
+                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward);
+                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward / 2);
+                    
+                    // Update battle stats
+                    Self::update_battle_stats(battle.pet1_id, true, false, false, battle.battle_rating, rating_change)?;
+                    Self::update_battle_stats(battle.pet2_id, false, true, false, battle.battle_rating, rating_change)?;
+                },
+                BattleOutcome::Pet2Win => {
+                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward);
+                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward / 2);
+                    
+                    // Update battle stats
+                    Self::update_battle_stats(battle.pet1_id, false, true, false, battle.battle_rating, rating_change)?;
+                    Self::update_battle_stats(battle.pet2_id, true, false, false, battle.battle_rating, rating_change)?;
+                },
+                BattleOutcome::Draw => {
+                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward / 2);
+                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward / 2);
+                    
+                    // Update battle stats
+                    Self::update_battle_stats(battle.pet1_id, false, false, true, battle.battle_rating, rating_change)?;
+                    Self::update_battle_stats(battle.pet2_id, false, false, true, battle.battle_rating, rating_change)?;
+                },
+                _ => {},
+            }
+            
+            // Emit battle completed event
+            Self::deposit_event(Event::BattleCompleted(battle.id, outcome));
+            
+            Ok(())
+        }
+        
+        /// Conclude an active battle with an outcome: record it, free the pets and the
+        /// participants' battle slots, settle its bets, and count it in the element telemetry.
+        /// Every battle that ends with a result goes through here exactly once.
+        fn conclude_battle(
+            battle: &mut Battle<T::AccountId, T::BlockNumber>,
+            status: BattleStatus,
+            outcome: BattleOutcome,
+            completed_at: T::BlockNumber,
+        ) {
+            battle.status = status;
+            battle.outcome = Some(outcome.clone());
+            battle.completed_at = Some(completed_at);
+            
+            // Remove pet active battles
+            PetActiveBattle::<T>::remove(&battle.pet1_id);
+            PetActiveBattle::<T>::remove(&battle.pet2_id);
+            
+            // Free the battle slot for both participants
+            Self::remove_account_active_battle(&battle.pet1_owner, battle.id);
+            Self::remove_account_active_battle(&battle.pet2_owner, battle.id);
+            
+            // Settle the spectators' bets
+            Self::settle_bets(battle, &outcome);
+            
+            // Record the battle for rating decay
+            PetLastBattle::<T>::insert(battle.pet1_id, completed_at);
+            PetLastBattle::<T>::insert(battle.pet2_id, completed_at);
+            
+            // Move the winner of a bracket battle on; a draw goes to pet1
+            let winner_pet_id = if outcome == BattleOutcome::Pet2Win { battle.pet2_id } else { battle.pet1_id };
+            Self::settle_bracket_battle(battle, winner_pet_id);
+            
+            // Count the result for each pet's element
+            let (pet1_result, pet2_result) = match outcome {
+                BattleOutcome::Pet1Win => ((true, false), (false, true)),
+                BattleOutcome::Pet2Win => ((false, true), (true, false)),
+                BattleOutcome::Draw => ((false, false), (false, false)),
+                BattleOutcome::Forfeited => return,
+            };
+            Self::record_element_result(battle.pet1_id, pet1_result);
+            Self::record_element_result(battle.pet2_id, pet2_result);
+        }
+        
+        /// The worst-case weight of concluding a battle, on top of the call or hook that
+        /// concludes it: settling as many bets as a battle can take, and moving the winner of a
+        /// bracket battle on through a full bracket to the end of its tournament.
+        fn conclude_battle_weight() -> Weight {
+            T::WeightInfo::settle_bets(T::MaxBetsPerBattle::get())
+                .saturating_add(T::WeightInfo::advance_bracket(T::MaxTournamentParticipants::get()))
+        }
+        
+        /// The worst-case weight of voiding a battle: refunding its bets, and moving the other
+        /// pet of a bracket battle on.
+        fn void_battle_weight() -> Weight {
+            T::DbWeight::get()
+                .reads_writes(3, 6)
+                .saturating_add(T::WeightInfo::refund_bets(T::MaxBetsPerBattle::get()))
+                .saturating_add(T::WeightInfo::advance_bracket(T::MaxTournamentParticipants::get()))
+        }
+        
+        /// Ends a battle that `forfeiter` forfeits: the opponent wins, the forfeiter's reserved
+        /// `forfeit_penalty` is slashed, and the winning pet earns the base experience reward.
+        fn settle_forfeit(
+            battle: &mut Battle<T::AccountId, T::BlockNumber>,
+            forfeiter: T::AccountId,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) {
+            // Determine the outcome
+            let outcome = if battle.pet1_owner == forfeiter {
+                BattleOutcome::Pet2Win
+            } else {
+                BattleOutcome::Pet1Win
+            };
+            
+            // Conclude the battle
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::conclude_battle(battle, BattleStatus::Forfeited, outcome.clone(), now);
+            
+            // Apply forfeit penalty
+            T::Currency::slash_reserved(&forfeiter, params.forfeit_penalty);
+            
+            // Award experience to the winner
+            let xp_reward = params.base_experience_reward;
+            match outcome {
+                BattleOutcome::Pet1Win => {
+                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward);
+                },
+                BattleOutcome::Pet2Win => {
+                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward);
+                },
+                _ => {},
+            }
+            
+            // Update the battle
+            Battles::<T>::insert(battle.id, battle.clone());
+            
+            // Emit events
+            Self::deposit_event(Event::BattleForfeited(battle.id, forfeiter));
+            Self::deposit_event(Event::BattleCompleted(battle.id, outcome));
+        }
+        
+        /// The owners of a battle's active player, who stalls if the turn times out, and of the
+        /// opponent waiting on them.
+        fn stalled_and_waiting(battle: &Battle<T::AccountId, T::BlockNumber>) -> (T::AccountId, T::AccountId) {
+            if battle.current_turn % 2 == 1 {
+                (battle.pet1_owner.clone(), battle.pet2_owner.clone())
+            } else {
+                (battle.pet2_owner.clone(), battle.pet1_owner.clone())
+            }
+        }
+        
+        /// The weight of forfeiting a battle on a turn timeout, the same whether a player claims
+        /// it or `on_initialize` sweeps it: the slash, the winner's experience and concluding the
+        /// battle.
+        fn turn_timeout_weight() -> Weight {
+            T::WeightInfo::claim_turn_timeout().saturating_add(Self::conclude_battle_weight())
+        }
+        
+        /// Whether the active player of a battle has gone `turn_timeout_blocks` without moving
+        /// since the last move, or since the battle began.
+        fn turn_timed_out(
+            battle: &Battle<T::AccountId, T::BlockNumber>,
+            params: &BattleParameters<BalanceOf<T>>,
+            now: T::BlockNumber,
+        ) -> bool {
+            now > battle.updated_at + params.turn_timeout_blocks.into()
+        }
+        
+        /// Voids the battle of a pet that is about to be destroyed: the battle expires without
+        /// a result, and the challenger's bond and the spectators' bets are returned.
+        fn void_battle(battle_id: BattleId, pet_id: PetId) {
+            if let Some(mut battle) = Self::team_battles(battle_id) {
+                battle.status = BattleStatus::Expired;
+                battle.updated_at = <frame_system::Pallet<T>>::block_number();
+                for pet_id in battle.pet_ids() {
+                    PetActiveBattle::<T>::remove(pet_id);
+                }
+                if let Ok(params) = Self::params_at_version(battle.params_version) {
+                    Self::release_team_battle(&battle, &params);
+                }
+                TeamBattles::<T>::insert(battle_id, battle);
+                Self::deposit_event(Event::BattleVoided(battle_id, pet_id));
+                return;
+            }
+            
+            let mut battle = match Self::battles(battle_id) {
+                Some(battle) => battle,
+                None => return,
+            };
+            battle.status = BattleStatus::Expired;
+            battle.updated_at = <frame_system::Pallet<T>>::block_number();
+            
+            PetActiveBattle::<T>::remove(&battle.pet1_id);
+            PetActiveBattle::<T>::remove(&battle.pet2_id);
+            Self::remove_account_active_battle(&battle.pet1_owner, battle_id);
+            Self::remove_account_active_battle(&battle.pet2_owner, battle_id);
+            if let Ok(params) = Self::params_of(&battle) {
+                T::Currency::unreserve(&battle.pet1_owner, params.challenge_bond);
+            }
+            Self::refund_bets(battle_id);
+            
+            // A bracket battle goes to the pet that is not being released
+            let winner_pet_id = if battle.pet1_id == pet_id { battle.pet2_id } else { battle.pet1_id };
+            Self::settle_bracket_battle(&battle, winner_pet_id);
+            
+            Battles::<T>::insert(battle_id, battle);
+            Self::deposit_event(Event::BattleVoided(battle_id, pet_id));
+        }
+        
+        /// The number of a bracket match in `TournamentBracket`: round one's matches are
+        /// numbered by their slot, and each later round's follow on from the round before.
+        pub fn bracket_match_number(round_count: u8, round: u8, slot: u32) -> u32 {
+            let first_of_round = (1u32 << round_count) - (1u32 << (round_count + 1).saturating_sub(round));
+            first_of_round + slot
+        }
+        
+        /// Whether a tournament pet can play its next match: it is still owned by the account
+        /// that entered it, and is not in another battle.
+        fn bracket_pet_available(tournament_id: TournamentId, pet_id: PetId) -> bool {
+            let entrant = TournamentParticipants::<T>::get(tournament_id, pet_id);
+            entrant.is_some() && T::NftManager::owner_of(&pet_id) == entrant && !PetActiveBattle::<T>::contains_key(pet_id)
+        }
+        
+        /// Plays a bracket match whose pets are known. A bye is won outright, as is a match
+        /// whose opponent cannot play (see `bracket_pet_available`); when neither can, pet1
+        /// moves on. Otherwise the match's battle starts at once, with no challenge bond and
+        /// without taking up the owners' active battle slots.
+        fn play_bracket_match(
+            tournament_id: TournamentId,
+            round_count: u8,
+            mut bracket_match: BracketMatch,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) -> Result<(), Error<T>> {
+            let number = Self::bracket_match_number(round_count, bracket_match.round, bracket_match.slot);
+            let (pet1_id, pet2_id) = match (bracket_match.pet1_id, bracket_match.pet2_id) {
+                (Some(pet1_id), Some(pet2_id)) => (pet1_id, pet2_id),
+                (pet1_id, pet2_id) => {
+                    bracket_match.winner_pet_id = pet1_id.or(pet2_id);
+                    TournamentBracket::<T>::insert(tournament_id, number, bracket_match.clone());
+                    return Self::advance_bracket_winner(tournament_id, round_count, &bracket_match, params);
+                },
+            };
+            
+            // A pet that cannot play forfeits the match
+            let pet1_available = Self::bracket_pet_available(tournament_id, pet1_id);
+            let pet2_available = Self::bracket_pet_available(tournament_id, pet2_id);
+            if !pet1_available || !pet2_available {
+                bracket_match.winner_pet_id = Some(if pet1_available || !pet2_available { pet1_id } else { pet2_id });
+                TournamentBracket::<T>::insert(tournament_id, number, bracket_match.clone());
+                return Self::advance_bracket_winner(tournament_id, round_count, &bracket_match, params);
+            }
+            
+            // Start the match's battle
+            let owner = |pet_id: PetId| TournamentParticipants::<T>::get(tournament_id, pet_id).ok_or(Error::<T>::NotPetOwner);
+            let battle_id = Self::next_battle_id()?;
+            let battle = Self::new_battle(
+                battle_id,
+                (pet1_id, owner(pet1_id)?),
+                (pet2_id, owner(pet2_id)?),
+                BattleStatus::Active,
+                params,
+            )?;
+            Battles::<T>::insert(battle_id, battle);
+            PetActiveBattle::<T>::insert(pet1_id, battle_id);
+            PetActiveBattle::<T>::insert(pet2_id, battle_id);
+            
+            bracket_match.battle_id = Some(battle_id);
+            TournamentBracket::<T>::insert(tournament_id, number, bracket_match);
+            Ok(())
+        }
+        
+        /// Records the winner of a bracket battle, if the battle is one, and moves them on.
+        fn settle_bracket_battle(battle: &Battle<T::AccountId, T::BlockNumber>, winner_pet_id: PetId) {
+            let tournament = match PetActiveTournament::<T>::get(battle.pet1_id).and_then(Self::tournaments) {
+                Some(tournament) if tournament.status == TournamentStatus::InProgress => tournament,
+                _ => return,
+            };
+            let found = TournamentBracket::<T>::iter_prefix(tournament.id)
+                .find(|(_, bracket_match)| bracket_match.battle_id == Some(battle.id) && bracket_match.winner_pet_id.is_none());
+            let (number, mut bracket_match) = match found {
+                Some(found) => found,
+                None => return,
+            };
+            
+            bracket_match.winner_pet_id = Some(winner_pet_id);
+            TournamentBracket::<T>::insert(tournament.id, number, bracket_match.clone());
+            
+            // Move the winner on, or undo the attempt and leave the match for `resume_bracket`
+            let round_count = Self::bracket_round_count(tournament.current_participants);
+            let advanced = frame_support::storage::with_storage_layer(|| -> DispatchResult {
+                let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+                Ok(Self::advance_bracket_winner(tournament.id, round_count, &bracket_match, &params)?)
+            });
+            if advanced.is_err() {
+                StalledBracketMatches::<T>::insert(tournament.id, number, ());
+                Self::deposit_event(Event::BracketStalled { tournament_id: tournament.id, match_number: number });
+            }
+        }
+        
+        /// Knocks the loser of a decided match out of the tournament, and moves the winner into
+        /// their next-round match, playing it once both its pets are known. The winner of the
+        /// final ends the tournament as its champion.
+        fn advance_bracket_winner(
+            tournament_id: TournamentId,
+            round_count: u8,
+            decided: &BracketMatch,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) -> Result<(), Error<T>> {
+            if let Some(loser_pet_id) = decided.loser_pet_id() {
+                PetActiveTournament::<T>::remove(loser_pet_id);
+            }
+            let winner_pet_id = match decided.winner_pet_id {
+                Some(winner_pet_id) => winner_pet_id,
+                None => return Ok(()),
+            };
+            if decided.round >= round_count {
+                Self::end_tournament(tournament_id, round_count, decided);
+                return Ok(());
+            }
             
-            // Determine the outcome
-            let outcome = if battle.pet1_health == 0 && battle.pet2_health == 0 {
-                BattleOutcome::Draw
-            } else if battle.pet1_health == 0 {
-                BattleOutcome::Pet2Win
-            } else if battle.pet2_health == 0 {
-                BattleOutcome::Pet1Win
-            } else if battle.pet1_health > battle.pet2_health {
-                BattleOutcome::Pet1Win
-            } else if battle.pet2_health > battle.pet1_health {
-                BattleOutcome::Pet2Win
+            // Take the winner's place in the next round
+            let (round, slot) = (decided.round + 1, decided.slot / 2);
+            let number = Self::bracket_match_number(round_count, round, slot);
+            let mut next = TournamentBracket::<T>::get(tournament_id, number).unwrap_or(BracketMatch {
+                round,
+                slot,
+                pet1_id: None,
+                pet2_id: None,
+                battle_id: None,
+                winner_pet_id: None,
+            });
+            if decided.slot % 2 == 0 {
+                next.pet1_id = Some(winner_pet_id);
             } else {
-                BattleOutcome::Draw
-            };
+                next.pet2_id = Some(winner_pet_id);
+            }
             
-            // Update battle status
-            battle.status = BattleStatus::Completed;
-            battle.outcome = Some(outcome.clone());
-            battle.completed_at = Some(battle.updated_at);
+            if next.pet1_id.is_some() && next.pet2_id.is_some() {
+                Self::play_bracket_match(tournament_id, round_count, next, params)
+            } else {
+                TournamentBracket::<T>::insert(tournament_id, number, next);
+                Ok(())
+            }
+        }
+        
+        /// Completes a tournament whose final is decided, and pays its prize pool out by
+        /// `TOURNAMENT_PRIZE_SHARES` to the owners who entered the placed pets. A prize the
+        /// pallet account cannot pay is kept in `UnpaidTournamentPrizes` for its owner to claim.
+        fn end_tournament(tournament_id: TournamentId, round_count: u8, final_match: &BracketMatch) {
+            let mut tournament = match Self::tournaments(tournament_id) {
+                Some(tournament) => tournament,
+                None => return,
+            };
+            let champion = match final_match.winner_pet_id {
+                Some(champion) => champion,
+                None => return,
+            };
             
-            // Get battle parameters
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            // Place the runner-up and the semi-final losers
+            let [_, second_share, third_share] = TOURNAMENT_PRIZE_SHARES;
+            let pool = tournament.prize_pool;
+            let mut placed: Vec<(PetId, BalanceOf<T>)> = Vec::new();
+            if let Some(runner_up) = final_match.loser_pet_id() {
+                placed.push((runner_up, second_share.mul_floor(pool)));
+            }
+            if round_count >= 2 {
+                let semi_final_losers: Vec<PetId> = (0..2)
+                    .filter_map(|slot| {
+                        let number = Self::bracket_match_number(round_count, round_count - 1, slot);
+                        TournamentBracket::<T>::get(tournament_id, number)?.loser_pet_id()
+                    })
+                    .collect();
+                let amount = third_share.mul_floor(pool) / BalanceOf::<T>::from(semi_final_losers.len().max(1) as u32);
+                for pet_id in semi_final_losers {
+                    placed.push((pet_id, amount));
+                }
+            }
+            let awarded = placed.iter().fold(Zero::zero(), |total: BalanceOf<T>, (_, amount)| total.saturating_add(*amount));
+            placed.insert(0, (champion, pool.saturating_sub(awarded)));
+            
+            // Pay the prizes
+            for (pet_id, amount) in placed {
+                let owner = match TournamentParticipants::<T>::get(tournament_id, pet_id) {
+                    Some(owner) => owner,
+                    None => continue,
+                };
+                if amount.is_zero() {
+                    continue;
+                }
+                if T::Currency::transfer(&Self::account_id(), &owner, amount, ExistenceRequirement::KeepAlive).is_ok() {
+                    Self::deposit_event(Event::TournamentPrizeAwarded { tournament_id, pet_id, owner, amount });
+                } else {
+                    // Leave the prize for the owner to claim
+                    UnpaidTournamentPrizes::<T>::insert(tournament_id, pet_id, (owner.clone(), amount));
+                    Self::deposit_event(Event::TournamentPrizeUnpaid { tournament_id, pet_id, owner, amount });
+                }
+            }
             
-            // Award experience to pets
-            let xp_reward = params.base_experience_reward;
-            match outcome {
-                BattleOutcome::Pet1Win => {
-                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward);
-                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward / 2);
-                    
-                    // Update battle stats
-                    Self::update_battle_stats(battle.pet1_id, true, false, false, battle.battle_rating)?;
-                    Self::update_battle_stats(battle.pet2_id, false, true, false, battle.battle_rating)?;
-                },
-                BattleOutcome::Pet2Win => {
-                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward);
-                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward / 2);
-                    
-                    // Update battle stats
-                    Self::update_battle_stats(battle.pet1_id, false, true, false, battle.battle_rating)?;
-                    Self::update_battle_stats(battle.pet2_id, true, false, false, battle.battle_rating)?;
-                },
-                BattleOutcome::Draw => {
-                    let _ = T::PetManager::add_experience(&battle.pet1_id, xp_reward / 2);
-                    let _ = T::PetManager::add_experience(&battle.pet2_id, xp_reward / 2);
-                    
-                    // Update battle stats
-                    Self::update_battle_stats(battle.pet1_id, false, false, true, battle.battle_rating)?;
-                    Self::update_battle_stats(battle.pet2_id, false, false, true, battle.battle_rating)?;
-                },
-                _ => {},
+            // Complete the tournament and free its pets
+            for (pet_id, _) in TournamentParticipants::<T>::iter_prefix(tournament_id) {
+                PetActiveTournament::<T>::remove(pet_id);
+            }
+            tournament.status = TournamentStatus::Completed;
+            tournament.end_block = Some(<frame_system::Pallet<T>>::block_number());
+            tournament.winner_pet_id = Some(champion);
+            Tournaments::<T>::insert(tournament_id, tournament);
+            Self::deposit_event(Event::TournamentEnded(tournament_id, champion));
+        }
+        
+        /// Whether a battle takes bets: while its challenge is pending, and until it passes the
+        /// `max_bet_turn` of its parameters.
+        fn bets_open(battle: &Battle<T::AccountId, T::BlockNumber>) -> Result<bool, Error<T>> {
+            match battle.status {
+                BattleStatus::Challenged => Ok(true),
+                BattleStatus::Active => Ok(battle.current_turn <= Self::params_of(battle)?.max_bet_turn),
+                _ => Ok(false),
+            }
+        }
+        
+        /// Ensure a battle takes a bet from `bettor`, then reserve its stake and record it.
+        fn record_bet(
+            battle: &Battle<T::AccountId, T::BlockNumber>,
+            bettor: &T::AccountId,
+            predicted_outcome: BattleOutcome,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            ensure!(Self::bets_open(battle)?, Error::<T>::BettingClosed);
+            ensure!(
+                battle.pet1_owner != *bettor && battle.pet2_owner != *bettor,
+                Error::<T>::ParticipantCannotBet
+            );
+            ensure!(
+                predicted_outcome != BattleOutcome::Forfeited && !amount.is_zero(),
+                Error::<T>::InvalidBet
+            );
+            ensure!(!BattleBets::<T>::contains_key(battle.id, bettor), Error::<T>::BetAlreadyPlaced);
+            let mut pool = BattleBetPools::<T>::get(battle.id);
+            ensure!(pool.bettors < T::MaxBetsPerBattle::get(), Error::<T>::TooManyBets);
+            ensure!(amount <= T::MaxWagerPerBattle::get(), Error::<T>::WagerCapExceeded);
+            
+            T::Currency::reserve(bettor, amount)?;
+            pool.bettors += 1;
+            pool.add_stake(&predicted_outcome, amount);
+            BattleBetPools::<T>::insert(battle.id, pool);
+            BattleBets::<T>::insert(battle.id, bettor, Bet { predicted_outcome, amount });
+            Ok(())
+        }
+        
+        /// Settle the bets on a concluded battle. Losing stakes move to the bets account, which
+        /// passes the house cut to the ecosystem treasury and pays the rest out to winning bets
+        /// as they are claimed. A battle forfeited before bets closed, or one no bet predicted,
+        /// refunds every bet instead.
+        fn settle_bets(battle: &Battle<T::AccountId, T::BlockNumber>, outcome: &BattleOutcome) {
+            let mut pool = BattleBetPools::<T>::get(battle.id);
+            if pool.bettors == 0 {
+                return;
+            }
+            let params = match Self::params_of(battle) {
+                Ok(params) => params,
+                Err(_) => return Self::refund_bets(battle.id),
+            };
+            let forfeited_early = battle.status == BattleStatus::Forfeited && battle.current_turn <= params.max_bet_turn;
+            let winning_stake = pool.stake_on(outcome);
+            if forfeited_early || winning_stake.is_zero() {
+                return Self::refund_bets(battle.id);
             }
             
-            // Remove pet active battles
-            PetActiveBattle::<T>::remove(&battle.pet1_id);
-            PetActiveBattle::<T>::remove(&battle.pet2_id);
+            // Collect the losing stakes, leaving the winning bets to be claimed
+            let bets_account = Self::bets_account_id();
+            let mut losing_stake = BalanceOf::<T>::zero();
+            let mut house_cut = BalanceOf::<T>::zero();
+            let mut winners = 0u32;
+            for (bettor, bet) in BattleBets::<T>::iter_prefix(battle.id).collect::<Vec<_>>() {
+                if bet.predicted_outcome == *outcome {
+                    winners += 1;
+                    continue;
+                }
+                let unmoved = T::Currency::repatriate_reserved(&bettor, &bets_account, bet.amount, BalanceStatus::Free)
+                    .unwrap_or(bet.amount);
+                let collected = bet.amount.saturating_sub(unmoved);
+                losing_stake = losing_stake.saturating_add(collected);
+                BattleBets::<T>::remove(battle.id, &bettor);
+                let fee = if BattleWagers::<T>::take(battle.id, &bettor).is_some() {
+                    T::WagerHouseFee::get()
+                } else {
+                    params.bet_house_cut
+                };
+                house_cut = house_cut.saturating_add(fee.mul_floor(collected));
+                // A stake that could not be collected, such as one below the existential deposit
+                // sent to an empty bets account, is returned rather than left reserved with no
+                // bet to free it.
+                if !unmoved.is_zero() {
+                    T::Currency::unreserve(&bettor, unmoved);
+                    Self::deposit_event(Event::BetRefunded(battle.id, bettor, unmoved));
+                }
+            }
             
-            Ok(())
+            // Route the house cut, with any left unpaid before, to the ecosystem treasury. A cut
+            // the transfer fails for stays in the bets account and is recorded for the next one.
+            let owed = UnpaidHouseCut::<T>::take().saturating_add(house_cut);
+            let paid = owed.is_zero() ||
+                T::Currency::transfer(&bets_account, &T::EcosystemTreasury::get(), owed, ExistenceRequirement::KeepAlive)
+                    .is_ok();
+            if !paid {
+                UnpaidHouseCut::<T>::put(owed);
+            }
+            pool.bettors = winners;
+            pool.settlement = Some(BetSettlement {
+                outcome: outcome.clone(),
+                winning_stake,
+                winnings: losing_stake.saturating_sub(house_cut),
+            });
+            BattleBetPools::<T>::insert(battle.id, pool);
+        }
+        
+        /// Refund every bet on a battle that ended without a result to settle them.
+        fn refund_bets(battle_id: BattleId) {
+            for (bettor, bet) in BattleBets::<T>::drain_prefix(battle_id) {
+                BattleWagers::<T>::remove(battle_id, &bettor);
+                T::Currency::unreserve(&bettor, bet.amount);
+                Self::deposit_event(Event::BetRefunded(battle_id, bettor, bet.amount));
+            }
+            BattleBetPools::<T>::remove(battle_id);
+        }
+        
+        /// Count a `(won, lost)` result for the pet's element; neither counts as a draw.
+        fn record_element_result(pet_id: PetId, (won, lost): (bool, bool)) {
+            let element = match T::PetElements::element_of(&pet_id) {
+                Some(element) => element,
+                None => return,
+            };
+            ElementBattleStats::<T>::mutate(element, |record| {
+                if won {
+                    record.wins = record.wins.saturating_add(1);
+                } else if lost {
+                    record.losses = record.losses.saturating_add(1);
+                } else {
+                    record.draws = record.draws.saturating_add(1);
+                }
+            });
+        }
+        
+        /// Get an element's battle win rate. Backs the `element_win_rate` runtime API.
+        pub fn element_win_rate(element: u8) -> Option<FixedU128> {
+            ElementBattleStats::<T>::get(element).win_rate()
+        }
+        
+        /// Get a live snapshot of a battle with each pet's current display version.
+        /// Backs the `battle_snapshot` runtime API.
+        pub fn battle_snapshot(battle_id: BattleId) -> Option<BattleSnapshot> {
+            let battle = Battles::<T>::get(battle_id)?;
+            Some(BattleSnapshot {
+                battle_id,
+                status: battle.status,
+                current_turn: battle.current_turn,
+                pet1_id: battle.pet1_id,
+                pet2_id: battle.pet2_id,
+                pet1_health: battle.pet1_health,
+                pet2_health: battle.pet2_health,
+                pet1_energy: battle.pet1_energy,
+                pet2_energy: battle.pet2_energy,
+                pet1_display_version: T::PetDisplay::display_version(&battle.pet1_id),
+                pet2_display_version: T::PetDisplay::display_version(&battle.pet2_id),
+            })
+        }
+        
+        /// Predict the outcome of a battle between two pets under the current parameters,
+        /// without writing storage. Backs the `simulate_battle` runtime API. A pet whose stats
+        /// cannot be read gives the default result, with no simulated battles.
+        pub fn simulate_battle(pet1_id: PetId, pet2_id: PetId) -> SimulationResult {
+            Self::try_simulate_battle(pet1_id, pet2_id).unwrap_or_default()
+        }
+        
+        /// The prediction, or `None` if the parameters or either pet's stats cannot be read.
+        fn try_simulate_battle(pet1_id: PetId, pet2_id: PetId) -> Option<SimulationResult> {
+            let params = Self::battle_parameters()?;
+            let combatant = |pet_id: PetId| {
+                let stats = T::PetManager::get_pet_attributes(&pet_id).ok()?;
+                Some(crate::simulation::Combatant {
+                    health: Self::attribute(&stats, AttributeType::Vitality, 50),
+                    strength: Self::attribute(&stats, AttributeType::Strength, 50),
+                    element: Self::attribute(&stats, AttributeType::Elemental, 1),
+                })
+            };
+            let (pet1, pet2) = (combatant(pet1_id)?, combatant(pet2_id)?);
+            Some(crate::simulation::simulate(pet1, pet2, &params, |trial, turn| {
+                let entropy = T::Hashing::hash_of(&(b"simulate", pet1_id, pet2_id, trial, turn));
+                crate::damage::move_roll(entropy.as_ref())
+            }))
         }
         
         /// Update battle stats for a pet
@@ -1681,6 +3730,7 @@ pub mod pallet {
             is_loss: bool,
             is_draw: bool,
             battle_rating: Option<u16>,
+            rating_change: u16,
         ) -> Result<(), Error<T>> {
             PetBattleStats::<T>::try_mutate(pet_id, |(wins, losses, draws, rating)| {
                 if is_win {
@@ -1695,13 +3745,12 @@ pub mod pallet {
                 
                 // Update rating if available
                 if let Some(battle_rating) = battle_rating {
-                    let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
                     let old_rating = *rating;
                     
                     if is_win {
-                        *rating = rating.saturating_add(params.matchmaking_rating_change);
+                        *rating = rating.saturating_add(rating_change);
                     } else if is_loss {
-                        *rating = rating.saturating_sub(params.matchmaking_rating_change);
+                        *rating = rating.saturating_sub(rating_change);
                     }
                     
                     // Ensure minimum rating of 100
@@ -1719,20 +3768,124 @@ pub mod pallet {
             })
         }
         
+        /// Runs a batch of the rating decay pass in progress, if any, and returns its weight.
+        /// The pass first removes the pets that have waited in the matchmaking queue past its
+        /// timeout, which lose `EloInactivityDecay`, then takes `EloPassiveDecay` from the rated
+        /// pets with no battle concluded within `EloDecayWindowBlocks`. It visits at most
+        /// `MaxRatingDecaysPerBlock` pets a block, and resumes after the last of them in the next.
+        fn decay_ratings(now: T::BlockNumber) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let mut weight = db_weight.reads(1);
+            let mut progress = match RatingDecayCursor::<T>::get() {
+                Some(progress) => progress,
+                None => return weight,
+            };
+            let mut limit = T::MaxRatingDecaysPerBlock::get() as usize;
+            
+            // Remove the pets that have waited too long in the matchmaking queue
+            if let RatingDecayProgress::MatchmakingQueue(last_pet_id) = progress {
+                let queue_timeout: T::BlockNumber = T::MatchmakingQueueTimeoutBlocks::get().into();
+                let batch: Vec<(PetId, (T::AccountId, u16, T::BlockNumber))> = match last_pet_id {
+                    Some(last_pet_id) => BattleMatchmakingQueue::<T>::iter_from(
+                        BattleMatchmakingQueue::<T>::hashed_key_for(last_pet_id),
+                    ).take(limit).collect(),
+                    None => BattleMatchmakingQueue::<T>::iter().take(limit).collect(),
+                };
+                weight = weight.saturating_add(db_weight.reads(batch.len() as u64));
+                for (pet_id, (owner, _, enqueue_time)) in batch.iter() {
+                    if now.saturating_sub(*enqueue_time) > queue_timeout {
+                        BattleMatchmakingQueue::<T>::remove(pet_id);
+                        Self::deposit_event(Event::PetRemovedFromMatchmaking(*pet_id, owner.clone()));
+                        let decay = Self::decay_rating(*pet_id, T::EloInactivityDecay::get(), EloDecayReason::QueueTimeout);
+                        weight = weight.saturating_add(decay).saturating_add(db_weight.writes(1));
+                    }
+                }
+                
+                progress = match batch.last() {
+                    Some((last_pet_id, _)) if batch.len() == limit => RatingDecayProgress::MatchmakingQueue(Some(*last_pet_id)),
+                    _ => RatingDecayProgress::Ratings(None),
+                };
+                limit -= batch.len();
+            }
+            
+            // Decay the pets with no recent battle. A pet that has left play keeps its record,
+            // but no longer decays, and its last battle is forgotten.
+            if let RatingDecayProgress::Ratings(last_pet_id) = progress {
+                let window: T::BlockNumber = T::EloDecayWindowBlocks::get().into();
+                let floor = T::EloFloor::get();
+                let batch: Vec<(PetId, (u32, u32, u32, u16))> = match last_pet_id {
+                    Some(last_pet_id) => PetBattleStats::<T>::iter_from(
+                        PetBattleStats::<T>::hashed_key_for(last_pet_id),
+                    ).take(limit).collect(),
+                    None => PetBattleStats::<T>::iter().take(limit).collect(),
+                };
+                weight = weight.saturating_add(db_weight.reads(3 * batch.len() as u64));
+                for (pet_id, (_, _, _, rating)) in batch.iter() {
+                    if T::NftManager::owner_of(pet_id).is_none() {
+                        PetLastBattle::<T>::remove(pet_id);
+                        weight = weight.saturating_add(db_weight.writes(1));
+                        continue;
+                    }
+                    let inactive = PetLastBattle::<T>::get(pet_id).map_or(true, |last| now.saturating_sub(last) > window);
+                    if *rating > floor && inactive {
+                        let decay = Self::decay_rating(*pet_id, T::EloPassiveDecay::get(), EloDecayReason::Inactivity);
+                        weight = weight.saturating_add(decay);
+                    }
+                }
+                
+                match batch.last() {
+                    Some((last_pet_id, _)) if batch.len() == limit => progress = RatingDecayProgress::Ratings(Some(*last_pet_id)),
+                    _ => {
+                        RatingDecayCursor::<T>::kill();
+                        return weight.saturating_add(db_weight.writes(1));
+                    },
+                }
+            }
+            
+            RatingDecayCursor::<T>::put(progress);
+            weight.saturating_add(db_weight.writes(1))
+        }
+        
+        /// Lowers a pet's rating by `decay`, but not below `EloFloor`, and returns the weight. A
+        /// rating at or below the floor is left alone.
+        fn decay_rating(pet_id: PetId, decay: u16, reason: EloDecayReason) -> Weight {
+            let floor = T::EloFloor::get();
+            let (wins, losses, draws, old_rating) = PetBattleStats::<T>::get(pet_id);
+            if old_rating <= floor {
+                return T::DbWeight::get().reads(1);
+            }
+            
+            let new_rating = old_rating.saturating_sub(decay).max(floor);
+            if new_rating != old_rating {
+                PetBattleStats::<T>::insert(pet_id, (wins, losses, draws, new_rating));
+                Self::deposit_event(Event::PetEloDecayed { pet_id, old_rating, new_rating, reason });
+            }
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+        
         /// Process status effects for a pet
         fn process_status_effects(
             battle: &mut Battle<T::AccountId, T::BlockNumber>,
             pet_id: PetId,
         ) -> Result<(), Error<T>> {
-            let is_pet1 = pet_id == battle.pet1_id;
-            
-            // Get the status effects
-            let status_effects = if is_pet1 {
-                &mut battle.pet1_status_effects
+            let (status_effects, health) = if pet_id == battle.pet1_id {
+                (&mut battle.pet1_status_effects, &mut battle.pet1_health)
             } else {
-                &mut battle.pet2_status_effects
+                (&mut battle.pet2_status_effects, &mut battle.pet2_health)
             };
+            Self::tick_status_effects(battle.id, pet_id, status_effects, health);
             
+            Ok(())
+        }
+        
+        /// Process a pet's status effects, from a 1v1 or team battle: apply their damage to its
+        /// health and remove the ones that expire.
+        fn tick_status_effects(
+            battle_id: BattleId,
+            pet_id: PetId,
+            status_effects: &mut BoundedVec<StatusEffect, ConstU32<5>>,
+            health: &mut u8,
+        ) {
             // Process each status effect
             let mut i = 0;
             while i < status_effects.len() {
@@ -1742,32 +3895,24 @@ pub mod pallet {
                 match &mut effect {
                     StatusEffect::Burn(turns) => {
                         // Apply damage
-                        if is_pet1 {
-                            battle.pet1_health = battle.pet1_health.saturating_sub(5);
-                        } else {
-                            battle.pet2_health = battle.pet2_health.saturating_sub(5);
-                        }
+                        *health = health.saturating_sub(5);
                         
                         // Decrement turns
                         *turns = turns.saturating_sub(1);
                         if *turns == 0 {
                             remove = true;
-                            Self::deposit_event(Event::StatusEffectExpired(battle.id, pet_id, effect.clone()));
+                            Self::deposit_event(Event::StatusEffectExpired(battle_id, pet_id, effect.clone()));
                         }
                     },
                     StatusEffect::Poison(turns) => {
                         // Apply damage
-                        if is_pet1 {
-                            battle.pet1_health = battle.pet1_health.saturating_sub(3);
-                        } else {
-                            battle.pet2_health = battle.pet2_health.saturating_sub(3);
-                        }
+                        *health = health.saturating_sub(3);
                         
                         // Decrement turns
                         *turns = turns.saturating_sub(1);
                         if *turns == 0 {
                             remove = true;
-                            Self::deposit_event(Event::StatusEffectExpired(battle.id, pet_id, effect.clone()));
+                            Self::deposit_event(Event::StatusEffectExpired(battle_id, pet_id, effect.clone()));
                         }
                     },
                     StatusEffect::Freeze(turns) | StatusEffect::Stun(turns) => {
@@ -1775,7 +3920,7 @@ pub mod pallet {
                         *turns = turns.saturating_sub(1);
                         if *turns == 0 {
                             remove = true;
-                            Self::deposit_event(Event::StatusEffectExpired(battle.id, pet_id, effect.clone()));
+                            Self::deposit_event(Event::StatusEffectExpired(battle_id, pet_id, effect.clone()));
                         }
                     },
                     StatusEffect::Strengthen(turns) | StatusEffect::Shield(turns) => {
@@ -1783,7 +3928,7 @@ pub mod pallet {
                         *turns = turns.saturating_sub(1);
                         if *turns == 0 {
                             remove = true;
-                            Self::deposit_event(Event::StatusEffectExpired(battle.id, pet_id, effect.clone()));
+                            Self::deposit_event(Event::StatusEffectExpired(battle_id, pet_id, effect.clone()));
                         }
                     },
                 }
@@ -1795,17 +3940,18 @@ pub mod pallet {
                     i += 1;
                 }
             }
-            
-            Ok(())
         }
         
         /// Process a move from pet 1
-        fn process_pet1_move(battle: &mut Battle<T::AccountId, T::BlockNumber>, move_type: &BattleMove) -> DispatchResult {
+        fn process_pet1_move(
+            battle: &mut Battle<T::AccountId, T::BlockNumber>,
+            move_type: &BattleMove,
+        ) -> Result<BattleMoveResult, DispatchError> {
             // Get pet stats
             let pet1_stats = T::PetManager::get_pet_attributes(&battle.pet1_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
+                .map_err(Error::<T>::from)?;
             let pet2_stats = T::PetManager::get_pet_attributes(&battle.pet2_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
+                .map_err(Error::<T>::from)?;
             
             // Get strength and elemental values
             let pet1_strength = pet1_stats.iter()
@@ -1822,74 +3968,78 @@ pub mod pallet {
             
             // Get randomness for move outcome
             let (random_seed, _) = T::BattleRandomness::random_seed();
-            let random_value = (random_seed.as_ref()[0] % 100) as u8;
+            let random_value = crate::damage::move_roll(random_seed.as_ref());
             
             // Process the move
-            match move_type {
+            let result = match move_type {
                 BattleMove::Attack => {
                     // Basic attack: 5-15 damage based on strength
-                    let base_damage = 5 + (pet1_strength / 10);
-                    let damage = if random_value < 20 {
-                        // Critical hit (20% chance)
-                        base_damage * 2
-                    } else {
-                        base_damage
-                    };
-                    
+                    let damage = crate::damage::attack_damage(pet1_strength, random_value);
                     battle.pet2_health = battle.pet2_health.saturating_sub(damage);
+                    Self::attack_result(damage, random_value)
                 },
                 BattleMove::Defend => {
                     // Defend: Recover 5-10 health
                     let heal_amount = 5 + (random_value % 6);
                     battle.pet1_health = (battle.pet1_health + heal_amount).min(100);
+                    BattleMoveResult::Heal(heal_amount)
                 },
                 BattleMove::SpecialAttack => {
                     // Special attack: High damage but can miss
-                    if random_value < 70 {
+                    if crate::damage::special_attack_hits(random_value, 0) {
                         // 70% chance to hit
-                        let damage = 15 + (pet1_strength / 5);
+                        let damage = crate::damage::special_attack_damage(pet1_strength);
                         battle.pet2_health = battle.pet2_health.saturating_sub(damage);
+                        BattleMoveResult::Hit(damage)
+                    } else {
+                        BattleMoveResult::Miss
                     }
                 },
                 BattleMove::Heal => {
                     // Heal: Recover 10-20 health
                     let heal_amount = 10 + (random_value % 11);
                     battle.pet1_health = (battle.pet1_health + heal_amount).min(100);
+                    BattleMoveResult::Heal(heal_amount)
                 },
                 BattleMove::Dodge => {
                     // Dodge: Small heal and increased chance to avoid next attack
                     let heal_amount = 3 + (random_value % 4);
                     battle.pet1_health = (battle.pet1_health + heal_amount).min(100);
                     // The dodge effect is handled in the next turn
+                    BattleMoveResult::Heal(heal_amount)
                 },
                 BattleMove::ElementalAttack => {
                     // Elemental attack: Damage based on elemental advantage
-                    let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
-                    let base_damage = 10 + (pet1_strength / 8);
-                    
-                    // Calculate elemental advantage
-                    let elemental_advantage = Self::calculate_elemental_advantage(pet1_elemental, pet2_elemental);
-                    let damage = if elemental_advantage {
-                        // Apply elemental advantage multiplier
-                        params.elemental_advantage_multiplier.mul_floor(base_damage.into()) as u8
-                    } else {
-                        base_damage
-                    };
+                    let params = Self::params_of(battle)?;
+                    let elemental_advantage = crate::damage::has_elemental_advantage(pet1_elemental, pet2_elemental);
+                    let damage = crate::damage::elemental_attack_damage(
+                        pet1_strength,
+                        elemental_advantage,
+                        params.elemental_advantage_multiplier,
+                    );
                     
                     battle.pet2_health = battle.pet2_health.saturating_sub(damage);
+                    BattleMoveResult::Hit(damage)
                 },
-            }
+                // The other special moves have their own calls
+                BattleMove::StatusEffect | BattleMove::Combo | BattleMove::Ultimate => {
+                    return Err(Error::<T>::MoveNotAvailable.into());
+                },
+            };
             
-            Ok(())
+            Ok(result)
         }
         
         /// Process a move from pet 2
-        fn process_pet2_move(battle: &mut Battle<T::AccountId, T::BlockNumber>, move_type: &BattleMove) -> DispatchResult {
+        fn process_pet2_move(
+            battle: &mut Battle<T::AccountId, T::BlockNumber>,
+            move_type: &BattleMove,
+        ) -> Result<BattleMoveResult, DispatchError> {
             // Get pet stats
             let pet1_stats = T::PetManager::get_pet_attributes(&battle.pet1_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
+                .map_err(Error::<T>::from)?;
             let pet2_stats = T::PetManager::get_pet_attributes(&battle.pet2_id)
-                .ok_or(Error::<T>::NotPetOwner)?;
+                .map_err(Error::<T>::from)?;
             
             // Get strength and elemental values
             let pet2_strength = pet2_stats.iter()
@@ -1906,96 +4056,259 @@ pub mod pallet {
             
             // Get randomness for move outcome
             let (random_seed, _) = T::BattleRandomness::random_seed();
-            let random_value = (random_seed.as_ref()[0] % 100) as u8;
+            let random_value = crate::damage::move_roll(random_seed.as_ref());
             
             // Check if pet1 used dodge in the previous turn
             let dodge_bonus = if let Some(BattleMove::Dodge) = battle.last_move_pet1 {
-                30 // 30% additional chance to miss
+                crate::damage::DODGE_BONUS // 30% additional chance to miss
             } else {
                 0
             };
             
             // Process the move
-            match move_type {
+            let result = match move_type {
                 BattleMove::Attack => {
                     // Basic attack: 5-15 damage based on strength
-                    if random_value >= dodge_bonus {
-                        let base_damage = 5 + (pet2_strength / 10);
-                        let damage = if random_value < 20 {
-                            // Critical hit (20% chance)
-                            base_damage * 2
-                        } else {
-                            base_damage
-                        };
-                        
+                    if !crate::damage::is_dodged(random_value, dodge_bonus) {
+                        let damage = crate::damage::attack_damage(pet2_strength, random_value);
                         battle.pet1_health = battle.pet1_health.saturating_sub(damage);
+                        Self::attack_result(damage, random_value)
+                    } else {
+                        BattleMoveResult::Miss
                     }
                 },
                 BattleMove::Defend => {
                     // Defend: Recover 5-10 health
                     let heal_amount = 5 + (random_value % 6);
                     battle.pet2_health = (battle.pet2_health + heal_amount).min(100);
+                    BattleMoveResult::Heal(heal_amount)
                 },
                 BattleMove::SpecialAttack => {
                     // Special attack: High damage but can miss
-                    if random_value < (70 - dodge_bonus) {
+                    if crate::damage::special_attack_hits(random_value, dodge_bonus) {
                         // 70% chance to hit (reduced by dodge bonus)
-                        let damage = 15 + (pet2_strength / 5);
+                        let damage = crate::damage::special_attack_damage(pet2_strength);
                         battle.pet1_health = battle.pet1_health.saturating_sub(damage);
+                        BattleMoveResult::Hit(damage)
+                    } else {
+                        BattleMoveResult::Miss
                     }
                 },
                 BattleMove::Heal => {
                     // Heal: Recover 10-20 health
                     let heal_amount = 10 + (random_value % 11);
                     battle.pet2_health = (battle.pet2_health + heal_amount).min(100);
+                    BattleMoveResult::Heal(heal_amount)
                 },
                 BattleMove::Dodge => {
                     // Dodge: Small heal and increased chance to avoid next attack
                     let heal_amount = 3 + (random_value % 4);
                     battle.pet2_health = (battle.pet2_health + heal_amount).min(100);
                     // The dodge effect is handled in the next turn
+                    BattleMoveResult::Heal(heal_amount)
                 },
                 BattleMove::ElementalAttack => {
                     // Elemental attack: Damage based on elemental advantage
-                    if random_value >= dodge_bonus {
-                        let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
-                        let base_damage = 10 + (pet2_strength / 8);
-                        
-                        // Calculate elemental advantage
-                        let elemental_advantage = Self::calculate_elemental_advantage(pet2_elemental, pet1_elemental);
-                        let damage = if elemental_advantage {
-                            // Apply elemental advantage multiplier
-                            params.elemental_advantage_multiplier.mul_floor(base_damage.into()) as u8
-                        } else {
-                            base_damage
-                        };
+                    if !crate::damage::is_dodged(random_value, dodge_bonus) {
+                        let params = Self::params_of(battle)?;
+                        let elemental_advantage = crate::damage::has_elemental_advantage(pet2_elemental, pet1_elemental);
+                        let damage = crate::damage::elemental_attack_damage(
+                            pet2_strength,
+                            elemental_advantage,
+                            params.elemental_advantage_multiplier,
+                        );
                         
                         battle.pet1_health = battle.pet1_health.saturating_sub(damage);
+                        BattleMoveResult::Hit(damage)
+                    } else {
+                        BattleMoveResult::Miss
                     }
                 },
+                // The other special moves have their own calls
+                BattleMove::StatusEffect | BattleMove::Combo | BattleMove::Ultimate => {
+                    return Err(Error::<T>::MoveNotAvailable.into());
+                },
+            };
+
+            Ok(result)
+        }
+
+        /// Gets the result of an attack that dealt `damage` with move roll `roll`.
+        fn attack_result(damage: u8, roll: u8) -> BattleMoveResult {
+            if crate::damage::is_critical(roll) {
+                BattleMoveResult::Critical(damage)
+            } else {
+                BattleMoveResult::Hit(damage)
             }
+        }
+
+        /// Apply a status effect to a pet in a team battle, on the turn of one of its owner's pets.
+        fn apply_team_status_effect(
+            player: T::AccountId,
+            battle_id: BattleId,
+            target_pet_id: PetId,
+            effect: StatusEffect,
+        ) -> DispatchResult {
+            let mut battle = Self::team_battles(battle_id).ok_or(Error::<T>::BattleNotFound)?;
+            ensure!(battle.status == BattleStatus::Active, Error::<T>::InvalidBattleStatus);
+            
+            // Ensure it's the player's turn
+            let (active_team, _) = TeamBattle::<T::AccountId, T::BlockNumber>::slot_of_turn(battle.current_turn);
+            ensure!(*battle.owner(active_team) == player, Error::<T>::NotYourTurn);
+            
+            // Apply the effect to the target pet alone
+            let (team, member) = battle.slot_of(target_pet_id).ok_or(Error::<T>::NotBattleParticipant)?;
+            battle.team_mut(team).status_effects[member]
+                .try_push(effect.clone())
+                .map_err(|_| Error::<T>::TooManyStatusEffects)?;
+            
+            battle.updated_at = <frame_system::Pallet<T>>::block_number();
+            TeamBattles::<T>::insert(battle_id, battle);
+            Self::deposit_event(Event::StatusEffectApplied(battle_id, target_pet_id, effect));
             
             Ok(())
         }
-        
-        /// Calculate elemental advantage
-        fn calculate_elemental_advantage(attacker: u8, defender: u8) -> bool {
-            // Simple elemental advantage calculation:
-            // 1 (Fire) > 2 (Water) > 3 (Earth) > 1 (Fire)
-            // 4 (Air) > 5 (Tech) > 6 (Nature) > 4 (Air)
-            // 7 (Mystic) has no advantages or disadvantages
-            // 0 (Neutral) has no advantages or disadvantages
+
+        /// Process a move by a team member. Attacks target the opposing pet in the same
+        /// position, or its teammate once it has fainted.
+        fn process_team_move(
+            battle: &mut TeamBattle<T::AccountId, T::BlockNumber>,
+            (team, member): (u8, usize),
+            move_type: &BattleMove,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) -> Result<BattleMoveResult, DispatchError> {
+            let opponent = 3 - team;
+            let target = if battle.team(opponent).health[member] > 0 { member } else { 1 - member };
             
-            match (attacker % 8, defender % 8) {
-                (1, 3) | (2, 1) | (3, 2) => true, // First cycle
-                (4, 6) | (5, 4) | (6, 5) => true, // Second cycle
-                _ => false,
+            // Get pet stats
+            let attacker_stats = T::PetManager::get_pet_attributes(&battle.pet_id(team, member))
+                .map_err(Error::<T>::from)?;
+            let defender_stats = T::PetManager::get_pet_attributes(&battle.pet_id(opponent, target))
+                .map_err(Error::<T>::from)?;
+            let strength = Self::attribute(&attacker_stats, AttributeType::Strength, 50);
+            let attacker_elemental = Self::attribute(&attacker_stats, AttributeType::Elemental, 1);
+            let defender_elemental = Self::attribute(&defender_stats, AttributeType::Elemental, 1);
+            
+            // Get randomness for move outcome
+            let (random_seed, _) = T::BattleRandomness::random_seed();
+            let random_value = crate::damage::move_roll(random_seed.as_ref());
+            
+            // A target that dodged on its last turn is harder to hit
+            let dodge_bonus = if battle.team(opponent).last_moves[target] == Some(BattleMove::Dodge) {
+                crate::damage::DODGE_BONUS
+            } else {
+                0
+            };
+            
+            // Work out the move's result
+            let result = match move_type {
+                BattleMove::Attack | BattleMove::ElementalAttack
+                    if crate::damage::is_dodged(random_value, dodge_bonus) => BattleMoveResult::Miss,
+                BattleMove::Attack => {
+                    let damage = crate::damage::attack_damage(strength, random_value);
+                    Self::attack_result(damage, random_value)
+                },
+                BattleMove::SpecialAttack if crate::damage::special_attack_hits(random_value, dodge_bonus) => {
+                    BattleMoveResult::Hit(crate::damage::special_attack_damage(strength))
+                },
+                BattleMove::SpecialAttack => BattleMoveResult::Miss,
+                BattleMove::ElementalAttack => {
+                    let elemental_advantage = crate::damage::has_elemental_advantage(attacker_elemental, defender_elemental);
+                    BattleMoveResult::Hit(crate::damage::elemental_attack_damage(
+                        strength,
+                        elemental_advantage,
+                        params.elemental_advantage_multiplier,
+                    ))
+                },
+                BattleMove::Defend => BattleMoveResult::Heal(5 + (random_value % 6)),
+                BattleMove::Heal => BattleMoveResult::Heal(10 + (random_value % 11)),
+                BattleMove::Dodge => BattleMoveResult::Heal(3 + (random_value % 4)),
+                BattleMove::StatusEffect | BattleMove::Combo | BattleMove::Ultimate => {
+                    return Err(Error::<T>::MoveNotAvailable.into());
+                },
+            };
+            
+            // Deal the damage to the target, or heal the pet
+            match result {
+                BattleMoveResult::Hit(damage) | BattleMoveResult::Critical(damage) => {
+                    let health = &mut battle.team_mut(opponent).health[target];
+                    *health = health.saturating_sub(damage);
+                },
+                BattleMoveResult::Heal(amount) => {
+                    let health = &mut battle.team_mut(team).health[member];
+                    *health = health.saturating_add(amount).min(100);
+                },
+                _ => {},
             }
+            
+            Ok(result)
+        }
+        
+        /// The worst-case weight of concluding a team battle, on top of the call or hook that
+        /// concludes it: experience, stats and the last battle of all four pets, and releasing
+        /// the battle.
+        fn conclude_team_battle_weight() -> Weight {
+            T::DbWeight::get().reads_writes(14, 22)
+        }
+        
+        /// Releases a team battle that has ended: it leaves `ActiveTeamBattles`, the owners'
+        /// battle slots are freed and the challenger's bond is returned.
+        fn release_team_battle(
+            battle: &TeamBattle<T::AccountId, T::BlockNumber>,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) {
+            ActiveTeamBattles::<T>::mutate(|battles| battles.retain(|id| *id != battle.id));
+            Self::remove_account_active_battle(&battle.team1_owner, battle.id);
+            Self::remove_account_active_battle(&battle.team2_owner, battle.id);
+            T::Currency::unreserve(&battle.team1_owner, params.challenge_bond);
         }
         
+        /// Conclude a team battle: a team wins once the other is defeated, or at the turn limit
+        /// with the larger combined health. Pets on the winning team earn the battle's
+        /// `base_experience_reward` and the others half of it, and all four are freed.
+        fn conclude_team_battle(
+            battle: &mut TeamBattle<T::AccountId, T::BlockNumber>,
+            params: &BattleParameters<BalanceOf<T>>,
+        ) -> Result<(), Error<T>> {
+            let winning_team = match (battle.team1.is_defeated(), battle.team2.is_defeated()) {
+                (true, true) => None,
+                (true, false) => Some(2),
+                (false, true) => Some(1),
+                (false, false) => match battle.team1.total_health().cmp(&battle.team2.total_health()) {
+                    sp_std::cmp::Ordering::Greater => Some(1),
+                    sp_std::cmp::Ordering::Less => Some(2),
+                    sp_std::cmp::Ordering::Equal => None,
+                },
+            };
+            battle.status = BattleStatus::Completed;
+            battle.winning_team = winning_team;
+            battle.completed_at = Some(battle.updated_at);
+            
+            // Award experience, update stats and free the pets
+            let mut xp_distributed = 0u32;
+            for (team, member) in TeamBattle::<T::AccountId, T::BlockNumber>::TURN_ORDER {
+                let pet_id = battle.pet_id(team, member);
+                let is_win = winning_team == Some(team);
+                let is_draw = winning_team.is_none();
+                let xp = if is_win { params.base_experience_reward } else { params.base_experience_reward / 2 };
+                if T::PetManager::add_experience(&pet_id, xp).is_ok() {
+                    xp_distributed = xp_distributed.saturating_add(xp);
+                }
+                Self::update_battle_stats(pet_id, is_win, !is_win && !is_draw, is_draw, None, params.matchmaking_rating_change)?;
+                
+                PetActiveBattle::<T>::remove(pet_id);
+                PetLastBattle::<T>::insert(pet_id, battle.updated_at);
+            }
+            Self::release_team_battle(battle, params);
+            
+            Self::deposit_event(Event::TeamBattleCompleted { battle_id: battle.id, winning_team, xp_distributed });
+            
+            Ok(())
+        }
+
         /// Calculate battle reward
         fn calculate_reward(battle: &Battle<T::AccountId, T::BlockNumber>) -> Result<BalanceOf<T>, Error<T>> {
-            let params = Self::battle_parameters().ok_or(Error::<T>::InvalidBattleParameters)?;
+            let params = Self::params_of(battle)?;
             
             // Base reward plus any additional based on battle duration
             let base_reward = params.base_reward;
@@ -2009,6 +4322,13 @@ pub mod pallet {
             T::AccountId::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
                 .expect("infinite length input; no invalid inputs for type; qed")
         }
+        
+        /// Get the account holding the losing stakes of settled bets until their winners claim
+        /// them, apart from the rewards and prize pools in `account_id`
+        pub fn bets_account_id() -> T::AccountId {
+            T::AccountId::decode(&mut sp_runtime::traits::TrailingZeroInput::new(b"critter-battle/bets"))
+                .expect("infinite length input; no invalid inputs for type; qed")
+        }
     }
 
     // Implement BattleSystemIntegration trait
@@ -2119,6 +4439,68 @@ pub mod pallet {
             battle_ids
         }
     }
+    
+    // Implement the read-only battle stats view for other pallets
+    impl<T: Config> crittercraft_traits::battle::BattleStatsProvider<PetId> for Pallet<T> {
+        fn battle_rating(pet_id: &PetId) -> Option<u16> {
+            let (wins, losses, draws, rating) = PetBattleStats::<T>::get(pet_id);
+            if wins == 0 && losses == 0 && draws == 0 {
+                return None;
+            }
+            Some(rating)
+        }
+
+        fn battle_record(pet_id: &PetId) -> Option<crittercraft_traits::battle::BattleRecord> {
+            let (wins, losses, draws, rating) = PetBattleStats::<T>::get(pet_id);
+            if wins == 0 && losses == 0 && draws == 0 {
+                return None;
+            }
+            Some(crittercraft_traits::battle::BattleRecord { wins, losses, draws, rating })
+        }
+    }
+
+    // Implement the release blocker that keeps pets in a battle from being released
+    impl<T: Config> crittercraft_traits::nft::ReleaseBlocker<PetId> for Pallet<T> {
+        fn blocks_release(pet_id: &PetId) -> Option<crittercraft_traits::types::ReleaseBlockReason> {
+            PetActiveBattle::<T>::contains_key(pet_id).then_some(crittercraft_traits::types::ReleaseBlockReason::InBattle)
+        }
+        
+        fn on_forced_release(pet_id: &PetId) {
+            if let Some(battle_id) = PetActiveBattle::<T>::get(pet_id) {
+                Self::void_battle(battle_id, *pet_id);
+            }
+            PetLastBattle::<T>::remove(pet_id);
+        }
+        
+        fn on_forced_release_weight() -> Weight {
+            Self::void_battle_weight().saturating_add(T::DbWeight::get().writes(1))
+        }
+    }
+
+    /// Score points per battle won, for `BattleRecordScore`.
+    pub const SCORE_PER_WIN: u64 = 2;
+
+    /// Score points per battle drawn, for `BattleRecordScore`.
+    pub const SCORE_PER_DRAW: u64 = 1;
+
+    // Define the Zoologist score contribution of the battle records of an account's pets. This
+    // pallet does not index pets by owner, so `OwnedPets` lists them, e.g. from the NFT pallet.
+    pub struct BattleRecordScore<T, OwnedPets>(PhantomData<(T, OwnedPets)>);
+
+    impl<T: Config, OwnedPets> pallet_user_profile::ScoreContributor<T::AccountId> for BattleRecordScore<T, OwnedPets>
+    where
+        OwnedPets: sp_runtime::traits::Convert<T::AccountId, Vec<PetId>>,
+    {
+        fn get_score_contribution(user: &T::AccountId) -> u64 {
+            OwnedPets::convert(user.clone())
+                .iter()
+                .map(|pet_id| {
+                    let (wins, _, draws, _) = PetBattleStats::<T>::get(pet_id);
+                    (wins as u64).saturating_mul(SCORE_PER_WIN).saturating_add((draws as u64).saturating_mul(SCORE_PER_DRAW))
+                })
+                .fold(0u64, |score, pet_score| score.saturating_add(pet_score))
+        }
+    }
 
     // Define the weight information trait
     pub trait WeightInfo {
@@ -2131,10 +4513,23 @@ pub mod pallet {
         fn enter_tournament() -> Weight;
         fn create_tournament() -> Weight;
         fn set_battle_params() -> Weight;
+        fn set_battle_params_immediately() -> Weight;
         fn enter_matchmaking() -> Weight;
         fn leave_matchmaking() -> Weight;
         fn get_battle_history() -> Weight;
         fn apply_status_effect() -> Weight;
         fn use_ultimate_move() -> Weight;
+        fn repair_active_battles() -> Weight;
+        fn place_bet() -> Weight;
+        fn claim_bet_winnings() -> Weight;
+        fn generate_bracket(participants: u32) -> Weight;
+        fn create_team_challenge() -> Weight;
+        fn accept_team_challenge() -> Weight;
+        fn execute_team_move() -> Weight;
+        fn claim_turn_timeout() -> Weight;
+        fn settle_bets(bets: u32) -> Weight;
+        fn refund_bets(bets: u32) -> Weight;
+        fn advance_bracket(participants: u32) -> Weight;
+        fn claim_tournament_prize() -> Weight;
     }
 }
\ No newline at end of file
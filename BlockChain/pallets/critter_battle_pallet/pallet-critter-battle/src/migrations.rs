@@ -0,0 +1,689 @@
+//! # Storage Migrations
+//!
+//! This module contains the storage migrations for pallet-critter-battle.
+//! Each migration lives in its own versioned submodule as a `MigrationStep`, wrapped in
+//! `VersionedMigration` so it only runs when the on-chain storage version matches the
+//! version it migrates from.
+
+use crittercraft_traits::migration::{MigrationStep, VersionedMigration};
+use crittercraft_traits::PetId;
+use frame_support::{
+    pallet_prelude::*,
+    weights::Weight,
+    log,
+};
+use sp_std::vec::Vec;
+use crate::{
+    Battle, BattleId, BattleMove, BattleMoveResult, BattleOutcome, BattleStatus, Battles, Config, Pallet, StatusEffect,
+};
+
+/// Migration to storage version 1: records the battle parameters version on every `Battle`.
+pub mod v1 {
+    use super::*;
+
+    /// The `Battle` layout before battles recorded their parameters version.
+    #[derive(Encode, Decode)]
+    pub struct OldBattle<AccountId, BlockNumber> {
+        pub id: BattleId,
+        pub pet1_id: PetId,
+        pub pet2_id: PetId,
+        pub pet1_owner: AccountId,
+        pub pet2_owner: AccountId,
+        pub status: BattleStatus,
+        pub current_turn: u8,
+        pub pet1_health: u8,
+        pub pet2_health: u8,
+        pub pet1_energy: u8,
+        pub pet2_energy: u8,
+        pub pet1_status_effects: BoundedVec<StatusEffect, ConstU32<5>>,
+        pub pet2_status_effects: BoundedVec<StatusEffect, ConstU32<5>>,
+        pub last_move_pet1: Option<BattleMove>,
+        pub last_move_pet2: Option<BattleMove>,
+        pub last_move_result: Option<BattleMoveResult>,
+        pub combo_counter_pet1: u8,
+        pub combo_counter_pet2: u8,
+        pub outcome: Option<BattleOutcome>,
+        pub created_at: BlockNumber,
+        pub updated_at: BlockNumber,
+        pub completed_at: Option<BlockNumber>,
+        pub reward_claimed: bool,
+        pub battle_rating: Option<u16>,
+    }
+
+    /// Translates every stored `Battle` to the new layout. Parameters were never versioned
+    /// before, so every existing battle was created under the current version.
+    pub struct AddParamsVersion<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddParamsVersion<T> {
+        fn migrate() -> Weight {
+            let params_version = Pallet::<T>::battle_params_version();
+            let mut translated = 0u64;
+            Battles::<T>::translate::<OldBattle<T::AccountId, T::BlockNumber>, _>(|_battle_id, old| {
+                translated = translated.saturating_add(1);
+                Some(Battle {
+                    id: old.id,
+                    pet1_id: old.pet1_id,
+                    pet2_id: old.pet2_id,
+                    pet1_owner: old.pet1_owner,
+                    pet2_owner: old.pet2_owner,
+                    status: old.status,
+                    current_turn: old.current_turn,
+                    pet1_health: old.pet1_health,
+                    pet2_health: old.pet2_health,
+                    pet1_energy: old.pet1_energy,
+                    pet2_energy: old.pet2_energy,
+                    pet1_status_effects: old.pet1_status_effects,
+                    pet2_status_effects: old.pet2_status_effects,
+                    last_move_pet1: old.last_move_pet1,
+                    last_move_pet2: old.last_move_pet2,
+                    last_move_result: old.last_move_result,
+                    combo_counter_pet1: old.combo_counter_pet1,
+                    combo_counter_pet2: old.combo_counter_pet2,
+                    outcome: old.outcome,
+                    created_at: old.created_at,
+                    updated_at: old.updated_at,
+                    completed_at: old.completed_at,
+                    reward_claimed: old.reward_claimed,
+                    battle_rating: old.battle_rating,
+                    params_version,
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_battle_pallet",
+                "Recorded the parameters version of {} battles for storage version 1",
+                translated
+            );
+
+            T::DbWeight::get().reads_writes(translated.saturating_add(1), translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((Battles::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let battles_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode battle count"))?;
+            // `iter` skips undecodable values, so a matching count means every battle decodes.
+            ensure!(Battles::<T>::iter().count() as u64 == battles_before, "battles lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 0 -> 1, safe to queue unconditionally in the runtime.
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        AddParamsVersion<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 2: adds the betting parameters to every stored parameter set.
+pub mod v2 {
+    use super::*;
+    use crate::{DEFAULT_BET_HOUSE_CUT, DEFAULT_MAX_BET_TURN};
+    use sp_runtime::Perbill;
+
+    /// The `BattleParameters` layout before spectator betting.
+    #[derive(Encode, Decode)]
+    pub struct OldBattleParameters<Balance> {
+        pub challenge_bond: Balance,
+        pub forfeit_penalty: Balance,
+        pub base_reward: Balance,
+        pub challenge_expiry_blocks: u32,
+        pub max_turns: u8,
+        pub base_experience_reward: u32,
+        pub elemental_advantage_multiplier: Perbill,
+        pub critical_hit_chance: Perbill,
+        pub critical_hit_multiplier: Perbill,
+        pub combo_threshold: u8,
+        pub combo_bonus_multiplier: Perbill,
+        pub status_effect_duration: u8,
+        pub initial_energy: u8,
+        pub energy_per_turn: u8,
+        pub ultimate_move_energy_cost: u8,
+        pub matchmaking_rating_change: u16,
+    }
+
+    impl<Balance> OldBattleParameters<Balance> {
+        /// The same set in the version 2 layout, with the default betting parameters.
+        pub fn upgrade(self) -> super::v3::OldBattleParameters<Balance> {
+            super::v3::OldBattleParameters {
+                challenge_bond: self.challenge_bond,
+                forfeit_penalty: self.forfeit_penalty,
+                base_reward: self.base_reward,
+                challenge_expiry_blocks: self.challenge_expiry_blocks,
+                max_turns: self.max_turns,
+                base_experience_reward: self.base_experience_reward,
+                elemental_advantage_multiplier: self.elemental_advantage_multiplier,
+                critical_hit_chance: self.critical_hit_chance,
+                critical_hit_multiplier: self.critical_hit_multiplier,
+                combo_threshold: self.combo_threshold,
+                combo_bonus_multiplier: self.combo_bonus_multiplier,
+                status_effect_duration: self.status_effect_duration,
+                initial_energy: self.initial_energy,
+                energy_per_turn: self.energy_per_turn,
+                ultimate_move_energy_cost: self.ultimate_move_energy_cost,
+                matchmaking_rating_change: self.matchmaking_rating_change,
+                max_bet_turn: DEFAULT_MAX_BET_TURN,
+                bet_house_cut: DEFAULT_BET_HOUSE_CUT,
+            }
+        }
+    }
+
+    /// The `ScheduledParameters` layout before spectator betting.
+    #[derive(Encode, Decode)]
+    pub struct OldScheduledParameters<Balance, BlockNumber> {
+        pub params: OldBattleParameters<Balance>,
+        pub activates_at: BlockNumber,
+    }
+
+    /// The `ParamsHistoryEntry` layout before spectator betting.
+    #[derive(Encode, Decode)]
+    pub struct OldParamsHistoryEntry<Balance, BlockNumber> {
+        pub version: u32,
+        pub params: OldBattleParameters<Balance>,
+        pub active_from: BlockNumber,
+        pub active_until: BlockNumber,
+    }
+
+    /// Translates the current, scheduled and historical parameter sets to the version 2 layout,
+    /// with the default betting parameters. That layout has no move energy costs yet, which
+    /// version 3 adds.
+    pub struct AddBettingParams<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddBettingParams<T> {
+        fn migrate() -> Weight {
+            let _ = super::v3::BattleParams::<T>::translate::<OldBattleParameters<crate::BalanceOf<T>>, _>(|old| {
+                old.map(OldBattleParameters::upgrade)
+            });
+            let _ = super::v3::ScheduledBattleParams::<T>::translate::<
+                OldScheduledParameters<crate::BalanceOf<T>, T::BlockNumber>,
+                _,
+            >(|old| {
+                old.map(|old| super::v3::OldScheduledParameters {
+                    params: old.params.upgrade(),
+                    activates_at: old.activates_at,
+                })
+            });
+            let _ = super::v3::ParamsHistory::<T>::translate::<
+                Vec<OldParamsHistoryEntry<crate::BalanceOf<T>, T::BlockNumber>>,
+                _,
+            >(|old| {
+                old.map(|entries| {
+                    entries
+                        .into_iter()
+                        .map(|old| super::v3::OldParamsHistoryEntry {
+                            version: old.version,
+                            params: old.params.upgrade(),
+                            active_from: old.active_from,
+                            active_until: old.active_until,
+                        })
+                        .collect()
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_battle_pallet",
+                "Added the betting parameters to every parameter set for storage version 2"
+            );
+
+            T::DbWeight::get().reads_writes(3, 3)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            ensure!(super::v3::BattleParams::<T>::exists(), "battle parameters lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 1 -> 2, safe to queue unconditionally in the runtime.
+    pub type MigrateToV2<T> = VersionedMigration<
+        1,
+        2,
+        AddBettingParams<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 3: adds the move energy costs to every stored parameter set.
+pub mod v3 {
+    use super::*;
+    use crate::{BalanceOf, MoveEnergyCosts};
+    use sp_runtime::Perbill;
+
+    /// The `BattleParameters` layout of storage version 2, before move energy costs.
+    #[derive(Encode, Decode)]
+    pub struct OldBattleParameters<Balance> {
+        pub challenge_bond: Balance,
+        pub forfeit_penalty: Balance,
+        pub base_reward: Balance,
+        pub challenge_expiry_blocks: u32,
+        pub max_turns: u8,
+        pub base_experience_reward: u32,
+        pub elemental_advantage_multiplier: Perbill,
+        pub critical_hit_chance: Perbill,
+        pub critical_hit_multiplier: Perbill,
+        pub combo_threshold: u8,
+        pub combo_bonus_multiplier: Perbill,
+        pub status_effect_duration: u8,
+        pub initial_energy: u8,
+        pub energy_per_turn: u8,
+        pub ultimate_move_energy_cost: u8,
+        pub matchmaking_rating_change: u16,
+        pub max_bet_turn: u8,
+        pub bet_house_cut: Perbill,
+    }
+
+    impl<Balance> OldBattleParameters<Balance> {
+        /// The same set in the version 3 layout, with the default move energy costs.
+        pub fn upgrade(self) -> super::v4::OldBattleParameters<Balance> {
+            super::v4::OldBattleParameters {
+                challenge_bond: self.challenge_bond,
+                forfeit_penalty: self.forfeit_penalty,
+                base_reward: self.base_reward,
+                challenge_expiry_blocks: self.challenge_expiry_blocks,
+                max_turns: self.max_turns,
+                base_experience_reward: self.base_experience_reward,
+                elemental_advantage_multiplier: self.elemental_advantage_multiplier,
+                critical_hit_chance: self.critical_hit_chance,
+                critical_hit_multiplier: self.critical_hit_multiplier,
+                combo_threshold: self.combo_threshold,
+                combo_bonus_multiplier: self.combo_bonus_multiplier,
+                status_effect_duration: self.status_effect_duration,
+                initial_energy: self.initial_energy,
+                energy_per_turn: self.energy_per_turn,
+                ultimate_move_energy_cost: self.ultimate_move_energy_cost,
+                matchmaking_rating_change: self.matchmaking_rating_change,
+                max_bet_turn: self.max_bet_turn,
+                bet_house_cut: self.bet_house_cut,
+                move_energy_costs: MoveEnergyCosts::default(),
+            }
+        }
+    }
+
+    /// The `ScheduledParameters` layout of storage version 2.
+    #[derive(Encode, Decode)]
+    pub struct OldScheduledParameters<Balance, BlockNumber> {
+        pub params: OldBattleParameters<Balance>,
+        pub activates_at: BlockNumber,
+    }
+
+    /// The `ParamsHistoryEntry` layout of storage version 2.
+    #[derive(Encode, Decode)]
+    pub struct OldParamsHistoryEntry<Balance, BlockNumber> {
+        pub version: u32,
+        pub params: OldBattleParameters<Balance>,
+        pub active_from: BlockNumber,
+        pub active_until: BlockNumber,
+    }
+
+    /// `BattleParams` as stored in version 2.
+    #[frame_support::storage_alias]
+    pub type BattleParams<T: Config> = StorageValue<Pallet<T>, OldBattleParameters<BalanceOf<T>>>;
+
+    /// `ScheduledBattleParams` as stored in version 2.
+    #[frame_support::storage_alias]
+    pub type ScheduledBattleParams<T: Config> = StorageValue<
+        Pallet<T>,
+        OldScheduledParameters<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>,
+    >;
+
+    /// `ParamsHistory` as stored in version 2.
+    #[frame_support::storage_alias]
+    pub type ParamsHistory<T: Config> = StorageValue<
+        Pallet<T>,
+        Vec<OldParamsHistoryEntry<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>>,
+    >;
+
+    /// Translates the current, scheduled and historical parameter sets to the version 3 layout,
+    /// with the default move energy costs. That layout has no turn timeout yet, which version 4
+    /// adds.
+    pub struct AddMoveEnergyCosts<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddMoveEnergyCosts<T> {
+        fn migrate() -> Weight {
+            let _ = super::v4::BattleParams::<T>::translate::<OldBattleParameters<BalanceOf<T>>, _>(|old| {
+                old.map(OldBattleParameters::upgrade)
+            });
+            let _ = super::v4::ScheduledBattleParams::<T>::translate::<
+                OldScheduledParameters<BalanceOf<T>, T::BlockNumber>,
+                _,
+            >(|old| {
+                old.map(|old| super::v4::OldScheduledParameters {
+                    params: old.params.upgrade(),
+                    activates_at: old.activates_at,
+                })
+            });
+            let _ = super::v4::ParamsHistory::<T>::translate::<
+                Vec<OldParamsHistoryEntry<BalanceOf<T>, T::BlockNumber>>,
+                _,
+            >(|old| {
+                old.map(|entries| {
+                    entries
+                        .into_iter()
+                        .map(|old| super::v4::OldParamsHistoryEntry {
+                            version: old.version,
+                            params: old.params.upgrade(),
+                            active_from: old.active_from,
+                            active_until: old.active_until,
+                        })
+                        .collect()
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_battle_pallet",
+                "Added the move energy costs to every parameter set for storage version 3"
+            );
+
+            T::DbWeight::get().reads_writes(3, 3)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            ensure!(super::v4::BattleParams::<T>::exists(), "battle parameters lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 2 -> 3, safe to queue unconditionally in the runtime.
+    pub type MigrateToV3<T> = VersionedMigration<
+        2,
+        3,
+        AddMoveEnergyCosts<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 4: adds the turn timeout to every stored parameter set.
+pub mod v4 {
+    use super::*;
+    use crate::{
+        BalanceOf, BattleParameters, MoveEnergyCosts, ParamsHistoryEntry, ScheduledParameters,
+        DEFAULT_TURN_TIMEOUT_BLOCKS,
+    };
+    use sp_runtime::Perbill;
+
+    /// The `BattleParameters` layout of storage version 3, before turn timeouts.
+    #[derive(Encode, Decode)]
+    pub struct OldBattleParameters<Balance> {
+        pub challenge_bond: Balance,
+        pub forfeit_penalty: Balance,
+        pub base_reward: Balance,
+        pub challenge_expiry_blocks: u32,
+        pub max_turns: u8,
+        pub base_experience_reward: u32,
+        pub elemental_advantage_multiplier: Perbill,
+        pub critical_hit_chance: Perbill,
+        pub critical_hit_multiplier: Perbill,
+        pub combo_threshold: u8,
+        pub combo_bonus_multiplier: Perbill,
+        pub status_effect_duration: u8,
+        pub initial_energy: u8,
+        pub energy_per_turn: u8,
+        pub ultimate_move_energy_cost: u8,
+        pub matchmaking_rating_change: u16,
+        pub max_bet_turn: u8,
+        pub bet_house_cut: Perbill,
+        pub move_energy_costs: MoveEnergyCosts,
+    }
+
+    impl<Balance> OldBattleParameters<Balance> {
+        /// The same set with the default turn timeout.
+        pub fn upgrade(self) -> BattleParameters<Balance> {
+            BattleParameters {
+                challenge_bond: self.challenge_bond,
+                forfeit_penalty: self.forfeit_penalty,
+                base_reward: self.base_reward,
+                challenge_expiry_blocks: self.challenge_expiry_blocks,
+                max_turns: self.max_turns,
+                base_experience_reward: self.base_experience_reward,
+                elemental_advantage_multiplier: self.elemental_advantage_multiplier,
+                critical_hit_chance: self.critical_hit_chance,
+                critical_hit_multiplier: self.critical_hit_multiplier,
+                combo_threshold: self.combo_threshold,
+                combo_bonus_multiplier: self.combo_bonus_multiplier,
+                status_effect_duration: self.status_effect_duration,
+                initial_energy: self.initial_energy,
+                energy_per_turn: self.energy_per_turn,
+                ultimate_move_energy_cost: self.ultimate_move_energy_cost,
+                matchmaking_rating_change: self.matchmaking_rating_change,
+                max_bet_turn: self.max_bet_turn,
+                bet_house_cut: self.bet_house_cut,
+                move_energy_costs: self.move_energy_costs,
+                turn_timeout_blocks: DEFAULT_TURN_TIMEOUT_BLOCKS,
+            }
+        }
+    }
+
+    /// The `ScheduledParameters` layout of storage version 3.
+    #[derive(Encode, Decode)]
+    pub struct OldScheduledParameters<Balance, BlockNumber> {
+        pub params: OldBattleParameters<Balance>,
+        pub activates_at: BlockNumber,
+    }
+
+    /// The `ParamsHistoryEntry` layout of storage version 3.
+    #[derive(Encode, Decode)]
+    pub struct OldParamsHistoryEntry<Balance, BlockNumber> {
+        pub version: u32,
+        pub params: OldBattleParameters<Balance>,
+        pub active_from: BlockNumber,
+        pub active_until: BlockNumber,
+    }
+
+    /// `BattleParams` as stored in version 3.
+    #[frame_support::storage_alias]
+    pub type BattleParams<T: Config> = StorageValue<Pallet<T>, OldBattleParameters<BalanceOf<T>>>;
+
+    /// `ScheduledBattleParams` as stored in version 3.
+    #[frame_support::storage_alias]
+    pub type ScheduledBattleParams<T: Config> = StorageValue<
+        Pallet<T>,
+        OldScheduledParameters<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>,
+    >;
+
+    /// `ParamsHistory` as stored in version 3.
+    #[frame_support::storage_alias]
+    pub type ParamsHistory<T: Config> = StorageValue<
+        Pallet<T>,
+        Vec<OldParamsHistoryEntry<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>>,
+    >;
+
+    /// Translates the current, scheduled and historical parameter sets to the new layout,
+    /// with the default turn timeout.
+    pub struct AddTurnTimeout<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddTurnTimeout<T> {
+        fn migrate() -> Weight {
+            let _ = crate::BattleParams::<T>::translate::<OldBattleParameters<BalanceOf<T>>, _>(|old| {
+                old.map(OldBattleParameters::upgrade)
+            });
+            let _ = crate::ScheduledBattleParams::<T>::translate::<OldScheduledParameters<BalanceOf<T>, T::BlockNumber>, _>(
+                |old| old.map(|old| ScheduledParameters { params: old.params.upgrade(), activates_at: old.activates_at }),
+            );
+            let _ = crate::ParamsHistory::<T>::translate::<Vec<OldParamsHistoryEntry<BalanceOf<T>, T::BlockNumber>>, _>(
+                |old| {
+                    old.map(|entries| {
+                        let entries: Vec<_> = entries
+                            .into_iter()
+                            .map(|old| ParamsHistoryEntry {
+                                version: old.version,
+                                params: old.params.upgrade(),
+                                active_from: old.active_from,
+                                active_until: old.active_until,
+                            })
+                            .collect();
+                        BoundedVec::truncate_from(entries)
+                    })
+                },
+            );
+
+            log::info!(
+                target: "runtime::critter_battle_pallet",
+                "Added the turn timeout to every parameter set for storage version 4"
+            );
+
+            T::DbWeight::get().reads_writes(3, 3)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            ensure!(crate::BattleParams::<T>::get().is_some(), "battle parameters lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 3 -> 4, safe to queue unconditionally in the runtime.
+    pub type MigrateToV4<T> = VersionedMigration<
+        3,
+        4,
+        AddTurnTimeout<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 5: moves the winnings of settled bets still to be claimed from
+/// the pallet account to the bets account.
+pub mod v5 {
+    use super::*;
+    use crate::{BalanceOf, BattleBetPools, BattleBets};
+    use frame_support::traits::{Currency, ExistenceRequirement};
+    use sp_runtime::{traits::{Saturating, Zero}, Perbill};
+
+    /// Sums each settled battle's unclaimed winnings, as `claim_bet_winnings` would pay them,
+    /// and moves the total to the bets account.
+    pub struct MoveBetWinnings<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for MoveBetWinnings<T> {
+        fn migrate() -> Weight {
+            let mut reads = 0u64;
+            let mut unclaimed = BalanceOf::<T>::zero();
+            for (battle_id, pool) in BattleBetPools::<T>::iter() {
+                reads += 1;
+                let settlement = match pool.settlement {
+                    Some(settlement) => settlement,
+                    None => continue,
+                };
+                for (_, bet) in BattleBets::<T>::iter_prefix(battle_id) {
+                    reads += 1;
+                    let share = Perbill::from_rational(bet.amount, settlement.winning_stake).mul_floor(settlement.winnings);
+                    unclaimed = unclaimed.saturating_add(share);
+                }
+            }
+
+            if !unclaimed.is_zero() {
+                if let Err(error) = T::Currency::transfer(
+                    &Pallet::<T>::account_id(),
+                    &Pallet::<T>::bets_account_id(),
+                    unclaimed,
+                    ExistenceRequirement::KeepAlive,
+                ) {
+                    log::error!(
+                        target: "runtime::critter_battle_pallet",
+                        "Could not move the unclaimed bet winnings to the bets account: {:?}",
+                        error
+                    );
+                }
+            }
+
+            log::info!(
+                target: "runtime::critter_battle_pallet",
+                "Moved the unclaimed bet winnings to the bets account for storage version 5"
+            );
+
+            T::DbWeight::get().reads_writes(reads.saturating_add(2), 2)
+        }
+    }
+
+    /// Storage version 4 -> 5, safe to queue unconditionally in the runtime.
+    pub type MigrateToV5<T> = VersionedMigration<
+        4,
+        5,
+        MoveBetWinnings<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 6: indexes the team battles that are challenged or in progress
+/// in `ActiveTeamBattles`, and gives them the bond and battle slots team challenges now take.
+pub mod v6 {
+    use super::*;
+    use crate::{AccountActiveBattles, ActiveTeamBattles, TeamBattles};
+    use frame_support::traits::ReservableCurrency;
+
+    /// Indexes every live team battle. Each takes its owners' battle slots where they have one
+    /// free, and reserves the challenge bond of its parameters from the challenger; a battle
+    /// whose challenger cannot cover the bond, or that does not fit `MaxActiveTeamBattles`,
+    /// expires and frees its pets.
+    pub struct IndexActiveTeamBattles<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for IndexActiveTeamBattles<T> {
+        fn migrate() -> Weight {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            let mut indexed = 0u32;
+            let mut expired = 0u32;
+            let live: Vec<_> = TeamBattles::<T>::iter()
+                .inspect(|_| reads += 1)
+                .filter(|(_, battle)| matches!(battle.status, BattleStatus::Challenged | BattleStatus::Active))
+                .collect();
+            for (battle_id, mut battle) in live {
+                reads += 2;
+                writes += 3;
+                let bond = Pallet::<T>::params_at_version(battle.params_version).ok().map(|params| params.challenge_bond);
+                let bonded = bond.filter(|bond| T::Currency::reserve(&battle.team1_owner, *bond).is_ok());
+                if let Some(bond) = bonded {
+                    if ActiveTeamBattles::<T>::try_append(battle_id).is_ok() {
+                        for owner in [&battle.team1_owner, &battle.team2_owner] {
+                            AccountActiveBattles::<T>::mutate(owner, |battles| {
+                                let _ = battles.try_push(battle_id);
+                            });
+                        }
+                        indexed += 1;
+                        continue;
+                    }
+                    T::Currency::unreserve(&battle.team1_owner, bond);
+                }
+
+                for pet_id in battle.pet_ids() {
+                    crate::PetActiveBattle::<T>::remove(pet_id);
+                }
+                battle.status = BattleStatus::Expired;
+                battle.updated_at = now;
+                TeamBattles::<T>::insert(battle_id, battle);
+                expired += 1;
+            }
+
+            log::info!(
+                target: "runtime::critter_battle_pallet",
+                "Indexed {} live team battles and expired {} for storage version 6",
+                indexed,
+                expired
+            );
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Storage version 5 -> 6, safe to queue unconditionally in the runtime.
+    pub type MigrateToV6<T> = VersionedMigration<
+        5,
+        6,
+        IndexActiveTeamBattles<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
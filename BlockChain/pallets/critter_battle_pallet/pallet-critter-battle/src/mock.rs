@@ -0,0 +1,256 @@
+use crate as pallet_critter_battle;
+use crittercraft_traits::{AdvancedPetManagement, AttributeType, EnhancedPetInfo, LockerId, NftCallError, PetId, PetStats, SharedNftManager};
+use frame_support::{
+    dispatch::DispatchResult,
+    parameter_types,
+    traits::{ConstU128, ConstU16, ConstU32, ConstU64, GenesisBuild, Randomness},
+    weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+use sp_std::vec::Vec;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        CritterBattle: pallet_critter_battle,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = u128;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+// Mock randomness source
+pub struct MockRandomness;
+impl Randomness<H256, u64> for MockRandomness {
+    fn random(_subject: &[u8]) -> (H256, u64) {
+        (H256::default(), 0)
+    }
+}
+
+/// Pets below this id belong to account 1, the rest to account 2.
+pub const FIRST_PET_OF_ACCOUNT_2: PetId = 10;
+
+parameter_types! {
+    pub static ExistentialDeposit: u128 = 1;
+}
+
+parameter_types! {
+    pub static MockTransferredPets: Vec<(PetId, u64)> = Vec::new();
+}
+
+// Mock NFT manager with a fixed ownership split, unless a pet is listed as transferred
+pub struct MockNftManager;
+impl SharedNftManager<u64, PetId> for MockNftManager {
+    fn owner_of(token_id: &PetId) -> Option<u64> {
+        let transferred = MockTransferredPets::get().into_iter().find(|(pet_id, _)| pet_id == token_id);
+        Some(transferred.map_or(if *token_id < FIRST_PET_OF_ACCOUNT_2 { 1 } else { 2 }, |(_, owner)| owner))
+    }
+    fn is_transferable(_token_id: &PetId) -> bool { true }
+    fn lock_nft(_owner: &u64, _token_id: &PetId, _locker: LockerId) -> DispatchResult { Ok(()) }
+    fn unlock_nft(_owner: &u64, _token_id: &PetId, _locker: LockerId) -> DispatchResult { Ok(()) }
+    fn transfer_nft(_from: &u64, _to: &u64, _token_id: &PetId) -> DispatchResult { Ok(()) }
+    fn can_receive_pet(_account: &u64) -> bool { true }
+    fn remaining_capacity(_account: &u64) -> u32 { u32::MAX }
+}
+
+// Mock pet manager where every pet has default stats, unless a failure is set
+parameter_types! {
+    pub static MockPetManagerFailure: Option<NftCallError> = None;
+    pub static AwardedExperience: Vec<(PetId, u32)> = Vec::new();
+    pub static MockDisplayVersions: Vec<(PetId, u32)> = Vec::new();
+}
+
+pub struct MockPetManager;
+impl MockPetManager {
+    fn result() -> Result<(), NftCallError> {
+        MockPetManagerFailure::get().map_or(Ok(()), Err)
+    }
+}
+impl AdvancedPetManagement<u64, u64> for MockPetManager {
+    fn get_enhanced_pet_info(_pet_id: &PetId) -> Option<EnhancedPetInfo<u64, u64>> { None }
+    fn update_pet_stats(_pet_id: &PetId, _stats: PetStats) -> Result<(), NftCallError> { Self::result() }
+    fn add_experience(pet_id: &PetId, experience: u32) -> Result<(), NftCallError> {
+        Self::result()?;
+        AwardedExperience::mutate(|awarded| awarded.push((*pet_id, experience)));
+        Ok(())
+    }
+    fn get_pet_level(_pet_id: &PetId) -> Result<u16, NftCallError> { Self::result().map(|_| 1) }
+    fn get_pet_attributes(_pet_id: &PetId) -> Result<Vec<(AttributeType, u8)>, NftCallError> { Self::result().map(|_| Vec::new()) }
+    fn update_pet_state(_pet_id: &PetId) -> Result<(), NftCallError> { Self::result() }
+    fn is_pet_neglected(_pet_id: &PetId) -> bool { false }
+    fn is_eligible_for_evolution(_pet_id: &PetId) -> bool { false }
+    fn evolve_pet(_owner: &u64, _pet_id: &PetId) -> Result<(), NftCallError> { Self::result() }
+}
+
+// Mock elements: a pet's element is its id modulo the number of elements
+pub struct MockPetElements;
+impl crittercraft_traits::nft::PetElementProvider<PetId> for MockPetElements {
+    fn element_of(pet_id: &PetId) -> Option<u8> {
+        Some((*pet_id % 8) as u8)
+    }
+}
+
+// Mock display versions, read from `MockDisplayVersions` (zero for unlisted pets)
+pub struct MockPetDisplay;
+impl crittercraft_traits::nft::PetDisplayVersionProvider<PetId> for MockPetDisplay {
+    fn display_version(pet_id: &PetId) -> u32 {
+        MockDisplayVersions::get()
+            .into_iter()
+            .find(|(id, _)| id == pet_id)
+            .map(|(_, version)| version)
+            .unwrap_or_default()
+    }
+}
+
+// Mock weights
+pub struct MockWeightInfo;
+impl crate::WeightInfo for MockWeightInfo {
+    fn create_challenge() -> Weight { Weight::zero() }
+    fn accept_challenge() -> Weight { Weight::zero() }
+    fn decline_challenge() -> Weight { Weight::zero() }
+    fn execute_move() -> Weight { Weight::zero() }
+    fn forfeit_battle() -> Weight { Weight::zero() }
+    fn claim_rewards() -> Weight { Weight::zero() }
+    fn enter_tournament() -> Weight { Weight::zero() }
+    fn create_tournament() -> Weight { Weight::zero() }
+    fn set_battle_params() -> Weight { Weight::zero() }
+    fn set_battle_params_immediately() -> Weight { Weight::zero() }
+    fn enter_matchmaking() -> Weight { Weight::zero() }
+    fn leave_matchmaking() -> Weight { Weight::zero() }
+    fn get_battle_history() -> Weight { Weight::zero() }
+    fn apply_status_effect() -> Weight { Weight::zero() }
+    fn use_ultimate_move() -> Weight { Weight::zero() }
+    fn repair_active_battles() -> Weight { Weight::zero() }
+    fn place_bet() -> Weight { Weight::zero() }
+    fn claim_bet_winnings() -> Weight { Weight::zero() }
+    fn generate_bracket(_participants: u32) -> Weight { Weight::zero() }
+    fn create_team_challenge() -> Weight { Weight::zero() }
+    fn accept_team_challenge() -> Weight { Weight::zero() }
+    fn execute_team_move() -> Weight { Weight::zero() }
+    fn claim_turn_timeout() -> Weight { Weight::zero() }
+    fn settle_bets(_bets: u32) -> Weight { Weight::zero() }
+    fn refund_bets(_bets: u32) -> Weight { Weight::zero() }
+    fn advance_bracket(_participants: u32) -> Weight { Weight::zero() }
+    fn claim_tournament_prize() -> Weight { Weight::zero() }
+}
+
+parameter_types! {
+    pub const MaxActiveBattles: u32 = 1;
+    pub const EcosystemTreasury: u64 = ECOSYSTEM_TREASURY;
+    pub const WagerHouseFee: Perbill = Perbill::from_percent(10);
+    pub static MaxRatingDecaysPerBlock: u32 = 16;
+}
+
+impl pallet_critter_battle::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type BattleRandomness = MockRandomness;
+    type NftManager = MockNftManager;
+    type PetManager = MockPetManager;
+    type PetElements = MockPetElements;
+    type PetDisplay = MockPetDisplay;
+    type MaxActiveBattles = MaxActiveBattles;
+    type MaxActiveTeamBattles = ConstU32<4>;
+    type MaxActiveTournaments = ConstU32<4>;
+    type MaxTournamentParticipants = ConstU32<8>;
+    type UpdateOrigin = frame_system::EnsureRoot<u64>;
+    type ParamsNoticePeriod = ConstU64<10>;
+    type MaxBetsPerBattle = ConstU32<3>;
+    type MaxWagerPerBattle = ConstU128<1000>;
+    type WagerHouseFee = WagerHouseFee;
+    type EcosystemTreasury = EcosystemTreasury;
+    type EloDecayIntervalBlocks = ConstU32<10>;
+    type MatchmakingQueueTimeoutBlocks = ConstU32<20>;
+    type EloInactivityDecay = ConstU16<50>;
+    type EloDecayWindowBlocks = ConstU32<100>;
+    type EloPassiveDecay = ConstU16<10>;
+    type EloFloor = ConstU16<800>;
+    type MaxRatingDecaysPerBlock = MaxRatingDecaysPerBlock;
+    type MaxTurnTimeoutsPerBlock = ConstU32<1>;
+    type WeightInfo = MockWeightInfo;
+}
+
+/// The ecosystem treasury account, which starts without a balance.
+pub const ECOSYSTEM_TREASURY: u64 = 6;
+
+/// The genesis balance of the pallet account (account 0), which funds genesis prize seeds.
+pub const GENESIS_PRIZE_FUND: u128 = 500;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    new_test_ext_with_tournaments(Vec::new())
+}
+
+// Build genesis storage with launch tournaments.
+pub fn new_test_ext_with_tournaments(
+    tournaments: Vec<pallet_critter_battle::GenesisTournamentOf<Test>>,
+) -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(0, GENESIS_PRIZE_FUND), (1, 1000), (2, 1000), (3, 1000), (4, 1000), (5, 1000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let battle_genesis = pallet_critter_battle::GenesisConfig { tournaments, ..Default::default() };
+    GenesisBuild::<Test>::assimilate_storage(&battle_genesis, &mut t).unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
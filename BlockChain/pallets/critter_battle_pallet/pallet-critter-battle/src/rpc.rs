@@ -0,0 +1,64 @@
+//! # Battle RPC
+//!
+//! Serves `CritterBattleRuntimeApi` over JSON-RPC, so clients can predict a battle without
+//! building `state_call` payloads. Nodes merge `CritterBattleRpc::new(client).into_rpc()` into
+//! their RPC module.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use crittercraft_traits::PetId;
+use jsonrpsee::{
+    core::{Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use pallet_critter_battle_runtime_api::{CritterBattleRuntimeApi, SimulationResult};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// The error code of a failed runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+
+#[rpc(client, server)]
+pub trait CritterBattleApi<BlockHash> {
+    /// Predicts a battle between two pets at the given block, or the best block.
+    #[method(name = "critterBattle_simulateBattle")]
+    fn simulate_battle(&self, pet1_id: PetId, pet2_id: PetId, at: Option<BlockHash>) -> RpcResult<SimulationResult>;
+}
+
+/// The battle RPC handler, backed by a client of the runtime.
+pub struct CritterBattleRpc<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> CritterBattleRpc<C, Block> {
+    /// Creates a handler calling into `client`'s runtime.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: PhantomData }
+    }
+}
+
+impl<C, Block> CritterBattleApiServer<<Block as BlockT>::Hash> for CritterBattleRpc<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: CritterBattleRuntimeApi<Block>,
+{
+    fn simulate_battle(
+        &self,
+        pet1_id: PetId,
+        pet2_id: PetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<SimulationResult> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().simulate_battle(at, pet1_id, pet2_id).map_err(|error| {
+            JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                RUNTIME_ERROR,
+                "Unable to simulate the battle",
+                Some(error.to_string()),
+            )))
+        })
+    }
+}
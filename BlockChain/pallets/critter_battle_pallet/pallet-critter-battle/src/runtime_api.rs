@@ -0,0 +1,43 @@
+//! # Runtime API
+//!
+//! This module declares the runtime APIs exposed by pallet-critter-battle.
+//! They give balance designers read access to battle telemetry and parameter changes,
+//! and spectators live battle snapshots, without indexing events. Clients reach them over
+//! JSON-RPC through `state_call`. Battle predictions have their own `CritterBattleRuntimeApi`,
+//! in the `runtime-api` crate.
+
+use codec::Codec;
+use sp_runtime::FixedU128;
+use sp_std::vec::Vec;
+use crate::{BattleId, BattleSnapshot, ElementBattleRecord, ParamsHistoryEntry, ScheduledParameters};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query battle telemetry.
+    pub trait BattleTelemetryApi {
+        /// Returns the lifetime wins, losses and draws of pets with the given element.
+        fn element_battle_stats(element: u8) -> ElementBattleRecord;
+
+        /// Returns the element's win rate (draws count as non-wins), or `None` if it has no results yet.
+        fn element_win_rate(element: u8) -> Option<FixedU128>;
+    }
+
+    /// The API to follow battles as a spectator.
+    pub trait BattleSnapshotApi {
+        /// Returns a live snapshot of a battle, or `None` if it does not exist. Each pet's
+        /// display version tells clients when to re-fetch its name, theme and bio.
+        fn battle_snapshot(battle_id: BattleId) -> Option<BattleSnapshot>;
+    }
+
+    /// The API to audit battle parameter changes.
+    pub trait BattleParamsApi<Balance, BlockNumber> where
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns the last three replaced parameter sets with the blocks they were active for,
+        /// oldest first.
+        fn params_history() -> Vec<ParamsHistoryEntry<Balance, BlockNumber>>;
+
+        /// Returns the parameter set waiting for its activation block, if any.
+        fn scheduled_params() -> Option<ScheduledParameters<Balance, BlockNumber>>;
+    }
+}
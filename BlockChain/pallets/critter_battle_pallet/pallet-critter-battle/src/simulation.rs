@@ -0,0 +1,103 @@
+//! # Battle Simulation
+//!
+//! Predicts the outcome of a battle between two pets, so players can weigh a challenge before
+//! committing its bond. The simulation plays `SIMULATION_TRIALS` battles under the given
+//! parameters with the formulas of `damage`, each pet acting every turn: with an elemental
+//! attack when its element has the advantage and it can afford one, with a basic attack
+//! otherwise, and defending when it cannot afford either.
+//!
+//! Each trial's rolls come from the caller, so the simulation is free of runtime state: the
+//! pallet hashes them from the pets, the trial and the turn, and the same pets with the same
+//! stats always get the same prediction.
+
+use crate::damage::{attack_damage, elemental_attack_damage, has_elemental_advantage};
+use crate::{BattleMove, BattleParameters, SimulationResult, MAX_ENERGY};
+
+/// The number of battles played for a prediction.
+pub const SIMULATION_TRIALS: u32 = 100;
+
+/// A pet's stats as they bear on a simulated battle.
+#[derive(Clone, Copy)]
+pub struct Combatant {
+    pub health: u8,
+    pub strength: u8,
+    pub element: u8,
+}
+
+/// Plays `SIMULATION_TRIALS` battles between two pets and summarizes them.
+///
+/// # Parameters
+///
+/// * `pet1` - The challenging pet, which moves first
+/// * `pet2` - The challenged pet
+/// * `params` - The battle parameters to play under
+/// * `roll` - The roll, 0-99, of a trial's turn
+///
+/// # Returns
+///
+/// * `SimulationResult` - The share of trials pet1 won, the average battle length, and
+///   whether pet1 has the elemental advantage
+pub fn simulate<Balance>(
+    pet1: Combatant,
+    pet2: Combatant,
+    params: &BattleParameters<Balance>,
+    roll: impl Fn(u32, u8) -> u8,
+) -> SimulationResult {
+    let (mut pet1_wins, mut total_turns) = (0u32, 0u32);
+    for trial in 0..SIMULATION_TRIALS {
+        let (pet1_won, turns) = play([pet1, pet2], params, |turn| roll(trial, turn));
+        pet1_wins += pet1_won as u32;
+        total_turns += turns as u32;
+    }
+    SimulationResult {
+        pet1_win_probability: (pet1_wins * 100 / SIMULATION_TRIALS) as u8,
+        expected_turns: (total_turns / SIMULATION_TRIALS) as u8,
+        elemental_advantage: has_elemental_advantage(pet1.element, pet2.element),
+    }
+}
+
+/// Plays one battle, returning whether pet1 won it and the number of turns it lasted.
+fn play<Balance>(pets: [Combatant; 2], params: &BattleParameters<Balance>, roll: impl Fn(u8) -> u8) -> (bool, u8) {
+    let mut health = [pets[0].health, pets[1].health];
+    let mut energy = [params.initial_energy; 2];
+    let mut turns = 0;
+    for turn in 1..=params.max_turns {
+        if health.contains(&0) {
+            break;
+        }
+        let (attacker, defender) = if turn % 2 == 1 { (0, 1) } else { (1, 0) };
+        let roll = roll(turn);
+
+        // Regenerate from the pet's second turn on, then pick the move it can afford
+        if turn > 2 {
+            energy[attacker] = energy[attacker].saturating_add(params.energy_per_turn).min(MAX_ENERGY);
+        }
+        let advantage = has_elemental_advantage(pets[attacker].element, pets[defender].element);
+        let move_type = if advantage && energy[attacker] >= params.energy_cost(&BattleMove::ElementalAttack) {
+            BattleMove::ElementalAttack
+        } else if energy[attacker] >= params.energy_cost(&BattleMove::Attack) {
+            BattleMove::Attack
+        } else {
+            BattleMove::Defend
+        };
+        energy[attacker] = energy[attacker].saturating_sub(params.energy_cost(&move_type));
+
+        match move_type {
+            BattleMove::ElementalAttack => {
+                let damage = elemental_attack_damage(pets[attacker].strength, true, params.elemental_advantage_multiplier);
+                health[defender] = health[defender].saturating_sub(damage);
+            },
+            BattleMove::Attack => {
+                health[defender] = health[defender].saturating_sub(attack_damage(pets[attacker].strength, roll));
+            },
+            _ => {
+                energy[attacker] = energy[attacker].saturating_add(params.move_energy_costs.defend_restore).min(MAX_ENERGY);
+                health[attacker] = health[attacker].saturating_add(5 + roll % 6).min(100);
+            },
+        }
+        turns = turn;
+    }
+
+    // As when a battle is finalized: a knockout, or the larger health at the turn limit
+    (health[0] > health[1], turns)
+}
@@ -0,0 +1,115 @@
+//! # Test Vectors
+//!
+//! Frozen reference vectors for the damage formulas in `damage`, so off-chain battle simulators
+//! can match the chain exactly. Each fixture in `test-vectors/` has `VECTOR_COUNT` vectors,
+//! inputs and outputs together, spanning edge cases (zero and maximum stats, the roll
+//! thresholds, every element pair kind) and pseudo-random inputs from a fixed seed:
+//!
+//! - `damage.json`: the damage of every attacking move for given stats, roll and elements
+//! - `critical.json`: the roll a randomness seed gives, and whether it is a critical hit, is
+//!   dodged and hits with a special attack, against a defender with and without the dodge bonus
+//!
+//! Bytes are `0x`-prefixed hex and multipliers are in parts per billion.
+//!
+//! The pallet's tests check the fixtures with `crittercraft_traits::golden::check_fixture`, so
+//! a formula change fails them until the fixtures are regenerated on purpose with
+//! `CRITTERCRAFT_REGENERATE_VECTORS=1 cargo test`. The module is compiled for tests and with the
+//! `test-vectors` feature, for clients that want the canonical functions themselves.
+
+use std::path::PathBuf;
+use crittercraft_traits::golden::{hex, VectorEntropy};
+use sp_runtime::Perbill;
+use crate::damage::{
+    attack_damage, elemental_attack_damage, has_elemental_advantage, is_critical, is_dodged, move_roll,
+    special_attack_damage, special_attack_hits, ultimate_damage, DODGE_BONUS,
+};
+
+/// The number of vectors in each fixture.
+pub const VECTOR_COUNT: usize = 64;
+
+/// Gets the path of a fixture file.
+///
+/// # Parameters
+///
+/// * `name` - The fixture's name, without the extension
+///
+/// # Returns
+///
+/// * `PathBuf` - The path of `test-vectors/<name>.json` in this crate
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-vectors").join(format!("{}.json", name))
+}
+
+/// The vectors of `damage.json`.
+pub fn damage_vectors() -> Vec<String> {
+    // (strength, intelligence, roll, attacker element, defender element, multiplier)
+    const EDGES: [(u8, u8, u8, u8, u8, u32); 10] = [
+        (0, 0, 0, 0, 0, 0),
+        (u8::MAX, u8::MAX, 99, 7, 7, 1_000_000_000),
+        (u8::MAX, u8::MAX, 0, 1, 3, 1_000_000_000),
+        (u8::MAX, 0, 19, 2, 1, 250_000_000),
+        (0, u8::MAX, 20, 3, 2, 250_000_000),
+        (9, 9, 19, 4, 6, 0),
+        (10, 10, 20, 5, 4, 500_000_000),
+        (79, 99, 50, 6, 5, 999_999_999),
+        (80, 100, 1, 9, 11, 250_000_000),
+        (u8::MAX, u8::MAX, 99, 6, 4, 1_000_000_000),
+    ];
+    let mut entropy = VectorEntropy::new(6);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            let (strength, intelligence, roll, attacker, defender, multiplier) = match EDGES.get(index) {
+                Some(edge) => *edge,
+                None => (
+                    entropy.below(256) as u8,
+                    entropy.below(256) as u8,
+                    entropy.below(100) as u8,
+                    entropy.below(8) as u8,
+                    entropy.below(8) as u8,
+                    entropy.below(Perbill::ACCURACY as u64 + 1) as u32,
+                ),
+            };
+            let advantage = has_elemental_advantage(attacker, defender);
+            format!(
+                "{{\"strength\":{},\"intelligence\":{},\"roll\":{},\"attacker_element\":{},\"defender_element\":{},\"elemental_multiplier\":{},\"advantage\":{},\"attack\":{},\"special_attack\":{},\"elemental_attack\":{},\"ultimate\":{}}}",
+                strength,
+                intelligence,
+                roll,
+                attacker,
+                defender,
+                multiplier,
+                advantage,
+                attack_damage(strength, roll),
+                special_attack_damage(strength),
+                elemental_attack_damage(strength, advantage, Perbill::from_parts(multiplier)),
+                ultimate_damage(strength, intelligence),
+            )
+        })
+        .collect()
+}
+
+/// The vectors of `critical.json`.
+pub fn critical_vectors() -> Vec<String> {
+    // Seed bytes on both sides of every roll threshold, and where the modulo wraps.
+    const EDGES: [u8; 16] = [0, 19, 20, 29, 30, 39, 40, 69, 70, 99, 100, 119, 120, 199, 200, u8::MAX];
+    let mut entropy = VectorEntropy::new(7);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            let seed = match EDGES.get(index / 2) {
+                Some(byte) => [*byte; 32],
+                None => entropy.bytes(),
+            };
+            let dodge_bonus = if index % 2 == 0 { 0 } else { DODGE_BONUS };
+            let roll = move_roll(&seed);
+            format!(
+                "{{\"seed\":\"{}\",\"dodge_bonus\":{},\"roll\":{},\"critical\":{},\"dodged\":{},\"special_attack_hits\":{}}}",
+                hex(&seed),
+                dodge_bonus,
+                roll,
+                is_critical(roll),
+                is_dodged(roll, dodge_bonus),
+                special_attack_hits(roll, dodge_bonus),
+            )
+        })
+        .collect()
+}
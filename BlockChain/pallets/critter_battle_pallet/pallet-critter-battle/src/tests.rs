@@ -0,0 +1,1755 @@
+use crate::{mock::*, AccountActiveBattles, BattleMove, BattleOutcome, BattleStatus, Battles, ElementBattleRecord, Error, Event, StatusEffect};
+use crate::test_vectors;
+use crittercraft_traits::golden::check_fixture;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+// Account 1 challenges account 2; returns the battle id.
+fn challenge(pet_id: u32, target_pet_id: u32) -> u32 {
+    let battle_id = CritterBattle::battle_count();
+    assert_ok!(CritterBattle::create_challenge(RuntimeOrigin::signed(1), pet_id, target_pet_id));
+    battle_id
+}
+
+// Runs the hooks far enough past creation for every open challenge to expire.
+fn expire_challenges() {
+    let expiry = CritterBattle::battle_parameters().unwrap().challenge_expiry_blocks as u64;
+    let block = System::block_number() + expiry + 1;
+    System::set_block_number(block);
+    CritterBattle::on_initialize(block);
+}
+
+#[test]
+fn expiry_frees_active_battle_slot() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_eq!(AccountActiveBattles::<Test>::get(1).into_inner(), vec![battle_id]);
+
+        expire_challenges();
+
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().status, BattleStatus::Expired);
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        assert!(AccountActiveBattles::<Test>::get(2).is_empty());
+    });
+}
+
+#[test]
+fn decline_frees_active_battle_slot() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+
+        assert_ok!(CritterBattle::decline_challenge(RuntimeOrigin::signed(2), battle_id));
+
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        assert!(AccountActiveBattles::<Test>::get(2).is_empty());
+    });
+}
+
+#[test]
+fn challenger_can_challenge_again_right_after_expiry() {
+    new_test_ext().execute_with(|| {
+        // MaxActiveBattles is 1, so a leaked id would block the second challenge.
+        challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        expire_challenges();
+
+        let battle_id = challenge(2, FIRST_PET_OF_ACCOUNT_2 + 1);
+        assert_eq!(AccountActiveBattles::<Test>::get(1).into_inner(), vec![battle_id]);
+    });
+}
+
+#[test]
+fn battle_ids_increase_across_every_way_of_creating_a_battle() {
+    new_test_ext().execute_with(|| {
+        // A challenge, declined to free both battle slots
+        let challenged = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::decline_challenge(RuntimeOrigin::signed(2), challenged));
+
+        // A matchmaking battle
+        assert_ok!(CritterBattle::enter_matchmaking(RuntimeOrigin::signed(1), 2));
+        assert_ok!(CritterBattle::enter_matchmaking(RuntimeOrigin::signed(2), FIRST_PET_OF_ACCOUNT_2 + 2));
+        let matched = CritterBattle::pet_active_battle(2).unwrap();
+        assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(1), matched));
+
+        // A team challenge
+        let team = CritterBattle::battle_count();
+        let target_pet = FIRST_PET_OF_ACCOUNT_2 + 3;
+        assert_ok!(CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 3, 4, 2, target_pet, target_pet + 1));
+
+        // A bracket battle
+        let tournament_id = tournament_with_entrants(&[5, FIRST_PET_OF_ACCOUNT_2 + 5]);
+        run_to_block(5);
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        let bracketed = CritterBattle::pet_active_battle(5).unwrap();
+
+        assert_eq!(vec![challenged, matched, team, bracketed], vec![0, 1, 2, 3]);
+        assert_eq!(CritterBattle::battle_count(), 4);
+        // No battle overwrote another.
+        assert_eq!(CritterBattle::battles(challenged).unwrap().pet1_id, 1);
+        assert_eq!(CritterBattle::battles(matched).unwrap().pet2_id, 2);
+        assert_eq!(CritterBattle::team_battles(team).unwrap().team1_pet1_id, 3);
+        let bracket_battle = CritterBattle::battles(bracketed).unwrap();
+        assert!([bracket_battle.pet1_id, bracket_battle.pet2_id].contains(&5));
+    });
+}
+
+#[test]
+fn repair_prunes_ended_and_missing_battles() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::decline_challenge(RuntimeOrigin::signed(2), battle_id));
+
+        // Simulate an account corrupted before the fix: an ended battle and an unknown id.
+        AccountActiveBattles::<Test>::insert(1, frame_support::BoundedVec::truncate_from(vec![battle_id, 99]));
+
+        // Anyone can repair the account.
+        assert_ok!(CritterBattle::repair_active_battles(RuntimeOrigin::signed(3), 1));
+
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::ActiveBattlesRepaired(1, 2)));
+        challenge(2, FIRST_PET_OF_ACCOUNT_2 + 1);
+    });
+}
+
+#[test]
+fn repair_keeps_open_battles() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+
+        assert_ok!(CritterBattle::repair_active_battles(RuntimeOrigin::signed(3), 1));
+
+        assert_eq!(AccountActiveBattles::<Test>::get(1).into_inner(), vec![battle_id]);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::CritterBattle(Event::ActiveBattlesRepaired(..))
+        )));
+    });
+}
+
+// Element ids of the NFT pallet's `ElementType`, as assigned by `MockPetElements`.
+const FIRE: u8 = 1;
+const WATER: u8 = 2;
+const EARTH: u8 = 3;
+
+// Account 2 accepts the battle, then the owners alternate moves starting with account 1.
+fn fight(battle_id: u32, moves: &[(BattleMove, BattleMove)]) {
+    assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+    for (pet1_move, pet2_move) in moves {
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(1), battle_id, pet1_move.clone()));
+        if CritterBattle::battles(battle_id).unwrap().status != BattleStatus::Active {
+            return;
+        }
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(2), battle_id, pet2_move.clone()));
+    }
+}
+
+fn record(wins: u32, losses: u32, draws: u32) -> ElementBattleRecord {
+    ElementBattleRecord { wins, losses, draws }
+}
+
+#[test]
+fn element_stats_count_scripted_battles() {
+    new_test_ext().execute_with(|| {
+        // Every attack crits for 20 and every defend heals 5, starting from 50 health.
+        // Fire (pet 1) beats Water (pet 10) in three attacks.
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().outcome, Some(BattleOutcome::Pet1Win));
+
+        // Earth (pet 11) beats Water (pet 2), which only defends.
+        let battle_id = challenge(2, FIRST_PET_OF_ACCOUNT_2 + 1);
+        fight(battle_id, &[(BattleMove::Defend, BattleMove::Attack); 4]);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().outcome, Some(BattleOutcome::Pet2Win));
+
+        // Earth (pet 3) and Fire (pet 17) both defend until the turn limit.
+        let battle_id = challenge(3, FIRST_PET_OF_ACCOUNT_2 + 7);
+        fight(battle_id, &[(BattleMove::Defend, BattleMove::Defend); 5]);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().outcome, Some(BattleOutcome::Draw));
+
+        assert_eq!(CritterBattle::element_battle_stats(FIRE), record(1, 0, 1));
+        assert_eq!(CritterBattle::element_battle_stats(WATER), record(0, 2, 0));
+        assert_eq!(CritterBattle::element_battle_stats(EARTH), record(1, 0, 1));
+        assert_eq!(CritterBattle::element_win_rate(FIRE), Some(FixedU128::from_rational(1, 2)));
+        assert_eq!(CritterBattle::element_win_rate(WATER), Some(FixedU128::from_u32(0)));
+    });
+}
+
+#[test]
+fn forfeit_and_claim_count_battle_once() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+
+        assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(2), battle_id));
+        assert_noop!(
+            CritterBattle::forfeit_battle(RuntimeOrigin::signed(1), battle_id),
+            Error::<Test>::InvalidBattleStatus
+        );
+        assert_ok!(CritterBattle::claim_rewards(RuntimeOrigin::signed(1), battle_id));
+
+        assert_eq!(CritterBattle::element_battle_stats(FIRE), record(1, 0, 0));
+        assert_eq!(CritterBattle::element_battle_stats(WATER), record(0, 1, 0));
+        // Concluding the battle also freed both battle slots.
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        assert!(AccountActiveBattles::<Test>::get(2).is_empty());
+    });
+}
+
+#[test]
+fn stalled_player_forfeits_once_their_turn_times_out() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        AwardedExperience::take();
+        let params = CritterBattle::battle_parameters().unwrap();
+
+        // Account 1 moves first and stalls; the timeout counts from the battle's start at block 1.
+        System::set_block_number(1 + params.turn_timeout_blocks as u64);
+        assert_noop!(
+            CritterBattle::claim_turn_timeout(RuntimeOrigin::signed(2), battle_id),
+            Error::<Test>::TurnNotTimedOut
+        );
+        System::set_block_number(2 + params.turn_timeout_blocks as u64);
+        assert_noop!(
+            CritterBattle::claim_turn_timeout(RuntimeOrigin::signed(1), battle_id),
+            Error::<Test>::NotBattleParticipant
+        );
+        assert_ok!(CritterBattle::claim_turn_timeout(RuntimeOrigin::signed(2), battle_id));
+
+        // The battle settles as if account 1 had forfeited it.
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        assert_eq!((battle.status, battle.outcome), (BattleStatus::Forfeited, Some(BattleOutcome::Pet2Win)));
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::BattleTurnTimedOut { battle_id, stalled: 1 }));
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::BattleForfeited(battle_id, 1)));
+        assert_eq!(Balances::reserved_balance(1), params.challenge_bond - params.forfeit_penalty);
+        assert_eq!(AwardedExperience::get(), vec![(FIRST_PET_OF_ACCOUNT_2, params.base_experience_reward)]);
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        assert!(AccountActiveBattles::<Test>::get(2).is_empty());
+    });
+}
+
+#[test]
+fn on_initialize_forfeits_timed_out_battles() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        let timeout = CritterBattle::battle_parameters().unwrap().turn_timeout_blocks as u64;
+
+        // A move restarts the timeout, for account 2 now.
+        System::set_block_number(50);
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(1), battle_id, BattleMove::Defend));
+        run_to_block(50 + timeout);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().status, BattleStatus::Active);
+
+        run_to_block(51 + timeout);
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        assert_eq!((battle.status, battle.outcome), (BattleStatus::Forfeited, Some(BattleOutcome::Pet1Win)));
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::BattleTurnTimedOut { battle_id, stalled: 2 }));
+    });
+}
+
+#[test]
+fn element_win_rate_counts_draws_as_non_wins() {
+    assert_eq!(record(0, 0, 0).win_rate(), None);
+    assert_eq!(record(1, 2, 1).win_rate(), Some(FixedU128::from_rational(1, 4)));
+    assert_eq!(record(3, 0, 0).win_rate(), Some(FixedU128::from_u32(1)));
+}
+
+#[test]
+fn pet_manager_failures_surface_as_distinct_errors() {
+    use crittercraft_traits::NftCallError;
+
+    new_test_ext().execute_with(|| {
+        for (failure, expected) in [
+            (NftCallError::PetNotFound, Error::<Test>::PetNotFound),
+            (NftCallError::NotOwner, Error::<Test>::NotPetOwner),
+            (NftCallError::PetLocked, Error::<Test>::PetLocked),
+            (NftCallError::Failed, Error::<Test>::PetManagerCallFailed),
+        ] {
+            MockPetManagerFailure::set(Some(failure));
+            assert_noop!(
+                CritterBattle::create_challenge(RuntimeOrigin::signed(1), 1, FIRST_PET_OF_ACCOUNT_2),
+                expected
+            );
+        }
+
+        MockPetManagerFailure::set(None);
+        challenge(1, FIRST_PET_OF_ACCOUNT_2);
+    });
+}
+
+#[test]
+fn battle_ending_on_a_move_is_finalized_once() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+
+        // Pet 10 is one attack from defeat, and its burn would also finish it this block.
+        Battles::<Test>::mutate(battle_id, |battle| {
+            let battle = battle.as_mut().unwrap();
+            battle.pet2_health = 5;
+            battle.pet2_status_effects = vec![StatusEffect::Burn(3)].try_into().unwrap();
+        });
+        AwardedExperience::take();
+
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(1), battle_id, BattleMove::Attack));
+        CritterBattle::on_initialize(System::block_number());
+
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        assert_eq!(battle.status, BattleStatus::Completed);
+        assert_eq!(battle.outcome, Some(BattleOutcome::Pet1Win));
+
+        let xp = CritterBattle::battle_parameters().unwrap().base_experience_reward;
+        assert_eq!(AwardedExperience::get(), vec![(1, xp), (FIRST_PET_OF_ACCOUNT_2, xp / 2)]);
+        assert_eq!(CritterBattle::pet_battle_stats(1).0, 1);
+        assert_eq!(CritterBattle::pet_battle_stats(FIRST_PET_OF_ACCOUNT_2).1, 1);
+        assert_eq!(CritterBattle::element_battle_stats(FIRE), record(1, 0, 0));
+
+        let completions = System::events()
+            .iter()
+            .filter(|record| matches!(record.event, RuntimeEvent::CritterBattle(Event::BattleCompleted(..))))
+            .count();
+        assert_eq!(completions, 1);
+    });
+}
+
+#[test]
+fn full_battle_history_drops_oldest_move() {
+    use crate::{BattleMoveHistoryEntry, BattleMoveResult};
+
+    new_test_ext().execute_with(|| {
+        let entry = |turn: u8| BattleMoveHistoryEntry {
+            turn,
+            pet_id: 1,
+            move_type: BattleMove::Attack,
+            result: BattleMoveResult::Hit(turn),
+        };
+
+        // The history holds 100 moves; recording a 101st never fails.
+        for turn in 0..=100 {
+            CritterBattle::add_to_battle_history(0, entry(turn));
+        }
+
+        let history = CritterBattle::battle_history(0);
+        assert_eq!(history.len(), 100);
+        assert_eq!(history.first(), Some(&entry(1)));
+        assert_eq!(history.last(), Some(&entry(100)));
+    });
+}
+
+#[test]
+fn every_move_of_a_battle_is_recorded_in_history() {
+    use crate::{BattleMoveHistoryEntry, BattleMoveResult as MoveResult};
+    use BattleMove::{Attack, Defend, Heal};
+
+    new_test_ext().execute_with(|| {
+        // Attacks crit for 20, heals restore 10 and defends 5; neither pet faints in 10 turns.
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        fight(battle_id, &[(Attack, Heal), (Defend, Attack), (Attack, Heal), (Defend, Attack), (Attack, Defend)]);
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        assert_eq!(battle.status, BattleStatus::Completed);
+        assert_eq!((battle.pet1_health, battle.pet2_health), (20, 15));
+        assert_eq!(battle.last_move_result, Some(MoveResult::Heal(5)));
+
+        let moves = [
+            (Attack, MoveResult::Critical(20)),
+            (Heal, MoveResult::Heal(10)),
+            (Defend, MoveResult::Heal(5)),
+            (Attack, MoveResult::Critical(20)),
+            (Attack, MoveResult::Critical(20)),
+            (Heal, MoveResult::Heal(10)),
+            (Defend, MoveResult::Heal(5)),
+            (Attack, MoveResult::Critical(20)),
+            (Attack, MoveResult::Critical(20)),
+            (Defend, MoveResult::Heal(5)),
+        ];
+        let expected: Vec<_> = moves
+            .into_iter()
+            .zip(1u8..)
+            .map(|((move_type, result), turn)| BattleMoveHistoryEntry {
+                turn,
+                pet_id: if turn % 2 == 1 { 1 } else { FIRST_PET_OF_ACCOUNT_2 },
+                move_type,
+                result,
+            })
+            .collect();
+        assert_eq!(CritterBattle::battle_history(battle_id).into_inner(), expected);
+
+        // Each move's result is also announced.
+        let results = System::events()
+            .into_iter()
+            .filter(|record| matches!(record.event, RuntimeEvent::CritterBattle(Event::BattleMoveResult(..))))
+            .count();
+        assert_eq!(results, 10);
+    });
+}
+
+#[test]
+fn battle_snapshot_exposes_live_display_versions() {
+    use crate::BattleSnapshot;
+
+    new_test_ext().execute_with(|| {
+        assert_eq!(CritterBattle::battle_snapshot(0), None);
+
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        fight(battle_id, &[]);
+        let snapshot = CritterBattle::battle_snapshot(battle_id).unwrap();
+        let battle = CritterBattle::battles(battle_id).unwrap();
+        assert_eq!(
+            snapshot,
+            BattleSnapshot {
+                battle_id,
+                status: BattleStatus::Active,
+                current_turn: battle.current_turn,
+                pet1_id: 1,
+                pet2_id: FIRST_PET_OF_ACCOUNT_2,
+                pet1_health: battle.pet1_health,
+                pet2_health: battle.pet2_health,
+                pet1_energy: battle.pet1_energy,
+                pet2_energy: battle.pet2_energy,
+                pet1_display_version: 0,
+                pet2_display_version: 0,
+            }
+        );
+
+        // A rename mid-battle shows up in the next snapshot.
+        MockDisplayVersions::set(vec![(FIRST_PET_OF_ACCOUNT_2, 3)]);
+        let snapshot = CritterBattle::battle_snapshot(battle_id).unwrap();
+        assert_eq!((snapshot.pet1_display_version, snapshot.pet2_display_version), (0, 3));
+    });
+}
+
+#[test]
+fn simulated_battles_are_deterministic_and_leave_storage_untouched() {
+    use crate::SimulationResult;
+    use sp_runtime::StateVersion;
+
+    new_test_ext().execute_with(|| {
+        let storage_root = sp_io::storage::root(StateVersion::V1);
+        let prediction = CritterBattle::simulate_battle(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_eq!(sp_io::storage::root(StateVersion::V1), storage_root);
+        assert_eq!(CritterBattle::simulate_battle(1, FIRST_PET_OF_ACCOUNT_2), prediction);
+
+        // The mock pets have the default stats and element, and trade basic attacks of 10, or
+        // 20 on a critical hit. Moving first, pet 1 wins 66 of the 100 seeded battles, which
+        // last 7 turns on average.
+        assert_eq!(
+            prediction,
+            SimulationResult { pet1_win_probability: 66, expected_turns: 7, elemental_advantage: false }
+        );
+
+        // A pet the pet manager cannot read gives the default result.
+        MockPetManagerFailure::set(Some(crittercraft_traits::NftCallError::PetNotFound));
+        assert_eq!(CritterBattle::simulate_battle(1, FIRST_PET_OF_ACCOUNT_2), SimulationResult::default());
+    });
+}
+
+#[test]
+fn simulation_favours_the_stronger_pet() {
+    use crate::simulation::{simulate, Combatant};
+
+    new_test_ext().execute_with(|| {
+        let params = CritterBattle::battle_parameters().unwrap();
+        let weak = Combatant { health: 50, strength: 0, element: 0 };
+        let strong = Combatant { health: 50, strength: 100, element: 0 };
+        // With no critical hits, a strength of 100 deals 15 a hit against 5.
+        let no_crits = |_, _| 99;
+        let result = simulate(strong, weak, &params, no_crits);
+        assert_eq!((result.pet1_win_probability, result.expected_turns), (100, 7));
+        assert_eq!(simulate(weak, strong, &params, no_crits).pet1_win_probability, 0);
+
+        // An element with the advantage is reported, and attacks with it.
+        let fire = Combatant { health: 50, strength: 50, element: 1 };
+        let water = Combatant { health: 50, strength: 50, element: 2 };
+        assert!(simulate(water, fire, &params, no_crits).elemental_advantage);
+        assert!(!simulate(fire, water, &params, no_crits).elemental_advantage);
+    });
+}
+
+// Current battle parameters with a different turn limit, to tell parameter sets apart.
+fn params_with_max_turns(max_turns: u8) -> crate::BattleParameters<u128> {
+    crate::BattleParameters { max_turns, ..CritterBattle::battle_parameters().unwrap() }
+}
+
+fn run_to_block(block: u64) {
+    System::set_block_number(block);
+    CritterBattle::on_initialize(block);
+}
+
+#[test]
+fn scheduled_params_activate_at_activation_block() {
+    new_test_ext().execute_with(|| {
+        let params = params_with_max_turns(20);
+
+        // Only the update origin can schedule, and only past the notice period of 10 blocks.
+        assert_noop!(
+            CritterBattle::set_battle_params(RuntimeOrigin::signed(1), params.clone(), 11),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CritterBattle::set_battle_params(RuntimeOrigin::root(), params.clone(), 10),
+            Error::<Test>::ActivationTooSoon
+        );
+        assert_ok!(CritterBattle::set_battle_params(RuntimeOrigin::root(), params.clone(), 11));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BattleParametersScheduled(11)));
+
+        run_to_block(10);
+        assert_eq!(CritterBattle::battle_parameters().unwrap().max_turns, 10);
+        assert_eq!(CritterBattle::battle_params_version(), 0);
+
+        run_to_block(11);
+        assert_eq!(CritterBattle::battle_parameters(), Some(params));
+        assert_eq!(CritterBattle::battle_params_version(), 1);
+        assert_eq!(CritterBattle::battle_params_active_since(), 11);
+        assert_eq!(CritterBattle::scheduled_battle_params(), None);
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BattleParametersUpdated(1)));
+    });
+}
+
+#[test]
+fn in_flight_battle_settles_with_recorded_params() {
+    new_test_ext().execute_with(|| {
+        let old = CritterBattle::battle_parameters().unwrap();
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().params_version, 0);
+
+        // The bond, reward and experience all change before the battle ends.
+        let new = crate::BattleParameters {
+            challenge_bond: 300,
+            base_reward: 500,
+            base_experience_reward: 1000,
+            ..old.clone()
+        };
+        assert_ok!(CritterBattle::set_battle_params(RuntimeOrigin::root(), new, 11));
+        run_to_block(11);
+        AwardedExperience::take();
+
+        assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(2), battle_id));
+        assert_ok!(CritterBattle::claim_rewards(RuntimeOrigin::signed(1), battle_id));
+
+        // The old bond is released and the old reward paid: base reward plus one turn.
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1000 + old.base_reward + 1);
+        assert_eq!(AwardedExperience::get(), vec![(1, old.base_experience_reward)]);
+
+        // A battle created now records the new version.
+        let battle_id = challenge(2, FIRST_PET_OF_ACCOUNT_2 + 1);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().params_version, 1);
+        assert_eq!(Balances::reserved_balance(1), 300);
+    });
+}
+
+#[test]
+fn params_history_keeps_last_three_sets() {
+    use crate::ParamsHistoryEntry;
+
+    new_test_ext().execute_with(|| {
+        for (block, max_turns) in [(5, 11), (8, 12), (13, 13), (21, 14)] {
+            run_to_block(block);
+            assert_ok!(CritterBattle::set_battle_params_immediately(
+                RuntimeOrigin::root(),
+                params_with_max_turns(max_turns)
+            ));
+        }
+
+        // The genesis set (version 0) has been rotated out.
+        let entry = |version, params, active_from, active_until| ParamsHistoryEntry {
+            version,
+            params,
+            active_from,
+            active_until,
+        };
+        assert_eq!(
+            CritterBattle::params_history().into_inner(),
+            vec![
+                entry(1, params_with_max_turns(11), 5, 8),
+                entry(2, params_with_max_turns(12), 8, 13),
+                entry(3, params_with_max_turns(13), 13, 21),
+            ]
+        );
+        assert_eq!(CritterBattle::battle_parameters(), Some(params_with_max_turns(14)));
+        assert_eq!(CritterBattle::battle_params_version(), 4);
+        assert_eq!(CritterBattle::battle_params_active_since(), 21);
+    });
+}
+
+#[test]
+fn emergency_override_applies_at_once_and_drops_schedule() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(CritterBattle::set_battle_params(RuntimeOrigin::root(), params_with_max_turns(20), 11));
+
+        assert_noop!(
+            CritterBattle::set_battle_params_immediately(RuntimeOrigin::signed(1), params_with_max_turns(5)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CritterBattle::set_battle_params_immediately(RuntimeOrigin::root(), params_with_max_turns(0)),
+            Error::<Test>::InvalidBattleParameters
+        );
+        assert_ok!(CritterBattle::set_battle_params_immediately(RuntimeOrigin::root(), params_with_max_turns(5)));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BattleParametersOverridden(1)));
+        assert_eq!(CritterBattle::battle_parameters().unwrap().max_turns, 5);
+        assert_eq!(CritterBattle::scheduled_battle_params(), None);
+
+        // The dropped schedule does not activate later.
+        run_to_block(11);
+        assert_eq!(CritterBattle::battle_parameters().unwrap().max_turns, 5);
+        assert_eq!(CritterBattle::battle_params_version(), 1);
+    });
+}
+
+#[test]
+fn migrate_to_v1_records_params_version() {
+    use codec::Encode;
+    use crate::migrations::v1::{MigrateToV1, OldBattle};
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+    use frame_support::storage::StoragePrefixedMap;
+
+    new_test_ext().execute_with(|| {
+        let old_battle = |id| OldBattle::<u64, u64> {
+            id,
+            pet1_id: 1,
+            pet2_id: FIRST_PET_OF_ACCOUNT_2,
+            pet1_owner: 1,
+            pet2_owner: 2,
+            status: BattleStatus::Active,
+            current_turn: 1,
+            pet1_health: 50,
+            pet2_health: 50,
+            pet1_energy: 50,
+            pet2_energy: 50,
+            pet1_status_effects: Default::default(),
+            pet2_status_effects: Default::default(),
+            last_move_pet1: None,
+            last_move_pet2: None,
+            last_move_result: None,
+            combo_counter_pet1: 0,
+            combo_counter_pet2: 0,
+            outcome: None,
+            created_at: 1,
+            updated_at: 1,
+            completed_at: None,
+            reward_claimed: false,
+            battle_rating: None,
+        };
+        let fixture = [0u32, 3].iter().fold(
+            StorageFixture { storage_version: 0, entries: Vec::new() },
+            |fixture, id| fixture.with_entry(Battles::<Test>::hashed_key_for(id), old_battle(*id).encode()),
+        );
+        run_migration::<CritterBattle, MigrateToV1<Test>>(&fixture, 1);
+
+        assert_eq!(assert_all_decode::<crate::Battle<u64, u64>>(&Battles::<Test>::final_prefix()), 2);
+        assert_eq!(CritterBattle::battles(3).unwrap().params_version, 0);
+        assert_eq!(CritterBattle::battles(3).unwrap().pet2_id, FIRST_PET_OF_ACCOUNT_2);
+    });
+}
+
+// A parameter set in the layout before betting, with the default values and `max_turns`.
+fn params_before_betting(max_turns: u8) -> crate::migrations::v2::OldBattleParameters<u128> {
+    use sp_runtime::Perbill;
+
+    crate::migrations::v2::OldBattleParameters {
+        challenge_bond: 100,
+        forfeit_penalty: 50,
+        base_reward: 200,
+        challenge_expiry_blocks: 100,
+        max_turns,
+        base_experience_reward: 100,
+        elemental_advantage_multiplier: Perbill::from_percent(25),
+        critical_hit_chance: Perbill::from_percent(15),
+        critical_hit_multiplier: Perbill::from_percent(200),
+        combo_threshold: 3,
+        combo_bonus_multiplier: Perbill::from_percent(150),
+        status_effect_duration: 3,
+        initial_energy: 50,
+        energy_per_turn: 10,
+        ultimate_move_energy_cost: 40,
+        matchmaking_rating_change: 25,
+    }
+}
+
+#[test]
+fn migrate_to_v2_adds_betting_params() {
+    use codec::Encode;
+    use crate::migrations::{v2::{MigrateToV2, OldParamsHistoryEntry}, v3};
+    use crate::{BattleParams, ParamsHistory, DEFAULT_BET_HOUSE_CUT, DEFAULT_MAX_BET_TURN};
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+
+    new_test_ext().execute_with(|| {
+        let history = vec![OldParamsHistoryEntry::<u128, u64> {
+            version: 0,
+            params: params_before_betting(20),
+            active_from: 0,
+            active_until: 1,
+        }];
+        let fixture = StorageFixture { storage_version: 1, entries: Vec::new() }
+            .with_entry(BattleParams::<Test>::hashed_key().to_vec(), params_before_betting(10).encode())
+            .with_entry(ParamsHistory::<Test>::hashed_key().to_vec(), history.encode());
+        run_migration::<CritterBattle, MigrateToV2<Test>>(&fixture, 2);
+
+        // The sets are left in the version 2 layout, for `MigrateToV3`.
+        let params = v3::BattleParams::<Test>::get().unwrap();
+        assert_eq!(
+            (params.max_turns, params.max_bet_turn, params.bet_house_cut),
+            (10, DEFAULT_MAX_BET_TURN, DEFAULT_BET_HOUSE_CUT),
+        );
+        let replaced = &v3::ParamsHistory::<Test>::get().unwrap()[0].params;
+        assert_eq!((replaced.max_turns, replaced.max_bet_turn), (20, DEFAULT_MAX_BET_TURN));
+    });
+}
+
+#[test]
+fn migrate_to_v3_adds_move_energy_costs() {
+    use codec::Encode;
+    use crate::migrations::{v3::{MigrateToV3, OldScheduledParameters}, v4};
+    use crate::{BattleParams, MoveEnergyCosts, ScheduledBattleParams, DEFAULT_MAX_BET_TURN};
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+
+    new_test_ext().execute_with(|| {
+        let scheduled = OldScheduledParameters::<u128, u64> { params: params_before_betting(30).upgrade(), activates_at: 50 };
+        let fixture = StorageFixture { storage_version: 2, entries: Vec::new() }
+            .with_entry(BattleParams::<Test>::hashed_key().to_vec(), params_before_betting(10).upgrade().encode())
+            .with_entry(ScheduledBattleParams::<Test>::hashed_key().to_vec(), scheduled.encode());
+        run_migration::<CritterBattle, MigrateToV3<Test>>(&fixture, 3);
+
+        // The sets are left in the version 3 layout, for `MigrateToV4`.
+        let params = v4::BattleParams::<Test>::get().unwrap();
+        assert_eq!((params.max_turns, params.max_bet_turn), (10, DEFAULT_MAX_BET_TURN));
+        assert_eq!(params.move_energy_costs, MoveEnergyCosts::default());
+        let scheduled = v4::ScheduledBattleParams::<Test>::get().unwrap();
+        assert_eq!((scheduled.params.max_turns, scheduled.activates_at), (30, 50));
+        assert_eq!(scheduled.params.move_energy_costs, MoveEnergyCosts::default());
+    });
+}
+
+#[test]
+fn migrate_to_v4_adds_turn_timeout() {
+    use codec::Encode;
+    use crate::migrations::v4::{MigrateToV4, OldParamsHistoryEntry};
+    use crate::{BattleParams, MoveEnergyCosts, ParamsHistory, DEFAULT_TURN_TIMEOUT_BLOCKS};
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+
+    new_test_ext().execute_with(|| {
+        let history = vec![OldParamsHistoryEntry::<u128, u64> {
+            version: 0,
+            params: params_before_betting(20).upgrade().upgrade(),
+            active_from: 0,
+            active_until: 1,
+        }];
+        let fixture = StorageFixture { storage_version: 3, entries: Vec::new() }
+            .with_entry(BattleParams::<Test>::hashed_key().to_vec(), params_before_betting(10).upgrade().upgrade().encode())
+            .with_entry(ParamsHistory::<Test>::hashed_key().to_vec(), history.encode());
+        run_migration::<CritterBattle, MigrateToV4<Test>>(&fixture, 4);
+
+        let params = CritterBattle::battle_parameters().unwrap();
+        assert_eq!((params.max_turns, params.turn_timeout_blocks), (10, DEFAULT_TURN_TIMEOUT_BLOCKS));
+        assert_eq!(params.move_energy_costs, MoveEnergyCosts::default());
+        let replaced = &CritterBattle::params_history()[0].params;
+        assert_eq!((replaced.max_turns, replaced.turn_timeout_blocks), (20, DEFAULT_TURN_TIMEOUT_BLOCKS));
+    });
+}
+
+#[test]
+fn migrate_to_v5_moves_unclaimed_winnings_to_the_bets_account() {
+    use crate::migrations::v5::MigrateToV5;
+    use crate::{Bet, BetPool, BetSettlement, BattleBetPools, BattleBets};
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+
+    new_test_ext().execute_with(|| {
+        // A settled battle with 300 of winnings on 400 of winning stake, one winner of 100 yet
+        // to claim, and a battle whose bets are still open.
+        BattleBetPools::<Test>::insert(0, BetPool {
+            bettors: 1,
+            pet1_win_stake: 400,
+            pet2_win_stake: 300,
+            draw_stake: 0,
+            settlement: Some(BetSettlement { outcome: BattleOutcome::Pet1Win, winning_stake: 400, winnings: 300 }),
+        });
+        BattleBets::<Test>::insert(0, 3, Bet { predicted_outcome: BattleOutcome::Pet1Win, amount: 100 });
+        BattleBetPools::<Test>::insert(1, BetPool { bettors: 1, draw_stake: 50, ..Default::default() });
+        BattleBets::<Test>::insert(1, 4, Bet { predicted_outcome: BattleOutcome::Draw, amount: 50 });
+
+        run_migration::<CritterBattle, MigrateToV5<Test>>(&StorageFixture { storage_version: 4, entries: Vec::new() }, 5);
+
+        assert_eq!(Balances::free_balance(CritterBattle::bets_account_id()), 75);
+        assert_eq!(Balances::free_balance(0), GENESIS_PRIZE_FUND - 75);
+        assert_ok!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(3), 0));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BetWon(0, 3, 175)));
+    });
+}
+
+// --- Genesis tournaments ---
+
+fn genesis_tournament(name: &[u8], start_offset: u64, prize_seed: u128) -> crate::GenesisTournamentOf<Test> {
+    crate::GenesisTournament {
+        name: name.to_vec(),
+        description: b"Launch tournament".to_vec(),
+        max_participants: 4,
+        min_pet_level: 1,
+        max_pet_level: 10,
+        entry_fee: 10,
+        start_offset,
+        prize_seed,
+    }
+}
+
+#[test]
+fn genesis_tournaments_are_created_with_sequential_ids() {
+    use crate::TournamentStatus;
+    let tournaments = vec![genesis_tournament(b"Opening Cup", 5, 100), genesis_tournament(b"Second Cup", 20, 0)];
+    new_test_ext_with_tournaments(tournaments).execute_with(|| {
+        assert_eq!(CritterBattle::tournament_count(), 2);
+
+        let first = CritterBattle::tournaments(0).unwrap();
+        assert_eq!(first.name.to_vec(), b"Opening Cup".to_vec());
+        assert_eq!((first.max_participants, first.min_pet_level, first.max_pet_level), (4, 1, 10));
+        assert_eq!((first.entry_fee, first.prize_pool), (10, 100));
+        assert_eq!(first.status, TournamentStatus::Registration);
+        // Offsets are counted from the genesis block.
+        assert_eq!(first.start_block, 5);
+        assert_eq!(CritterBattle::tournaments(1).unwrap().start_block, 20);
+
+        // Tournaments created later continue the ids.
+        assert_ok!(CritterBattle::create_tournament(RuntimeOrigin::root(), b"Later".to_vec(), Vec::new(), 2, 1, 5, 0, 30));
+        assert_eq!(CritterBattle::tournament_count(), 3);
+    });
+}
+
+#[test]
+fn genesis_tournaments_start_through_on_initialize() {
+    use crate::TournamentStatus;
+    let tournaments = vec![genesis_tournament(b"Opening Cup", 5, 100), genesis_tournament(b"Second Cup", 20, 0)];
+    new_test_ext_with_tournaments(tournaments).execute_with(|| {
+        assert_ok!(CritterBattle::enter_tournament(RuntimeOrigin::signed(1), 0, 1));
+        assert_ok!(CritterBattle::enter_tournament(RuntimeOrigin::signed(2), 0, FIRST_PET_OF_ACCOUNT_2));
+        assert_eq!(CritterBattle::tournaments(0).unwrap().prize_pool, 120);
+
+        run_to_block(4);
+        assert_eq!(CritterBattle::tournaments(0).unwrap().status, TournamentStatus::Registration);
+        run_to_block(5);
+        assert_eq!(CritterBattle::tournaments(0).unwrap().status, TournamentStatus::InProgress);
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::TournamentStarted(0)));
+
+        // The second tournament has no entrants, so it is cancelled when it would start.
+        run_to_block(19);
+        assert_eq!(CritterBattle::tournaments(1).unwrap().status, TournamentStatus::Registration);
+        run_to_block(20);
+        assert_eq!(CritterBattle::tournaments(1).unwrap().status, TournamentStatus::Cancelled);
+    });
+}
+
+#[test]
+#[should_panic(expected = "genesis tournament 1: min_pet_level is above max_pet_level")]
+fn genesis_tournament_level_bounds_are_validated() {
+    let mut inverted = genesis_tournament(b"Inverted", 5, 0);
+    inverted.min_pet_level = 11;
+    new_test_ext_with_tournaments(vec![genesis_tournament(b"Fine", 5, 0), inverted]);
+}
+
+#[test]
+#[should_panic(expected = "genesis tournament 0: max_participants is zero")]
+fn genesis_tournament_participants_are_validated() {
+    let mut empty = genesis_tournament(b"Empty", 5, 0);
+    empty.max_participants = 0;
+    new_test_ext_with_tournaments(vec![empty]);
+}
+
+#[test]
+#[should_panic(expected = "prize seeds exceed the pallet account's genesis balance")]
+fn genesis_prize_seeds_must_be_funded() {
+    new_test_ext_with_tournaments(vec![genesis_tournament(b"Rich", 5, GENESIS_PRIZE_FUND + 1)]);
+}
+
+#[test]
+fn pets_in_battle_block_release_until_forced() {
+    use crittercraft_traits::{nft::ReleaseBlocker, types::ReleaseBlockReason};
+
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_eq!(CritterBattle::blocks_release(&1), Some(ReleaseBlockReason::InBattle));
+        assert_eq!(CritterBattle::blocks_release(&FIRST_PET_OF_ACCOUNT_2), Some(ReleaseBlockReason::InBattle));
+        assert_eq!(CritterBattle::blocks_release(&2), None);
+        assert!(Balances::reserved_balance(1) > 0);
+
+        // A forced release voids the battle and returns the challenger's bond.
+        CritterBattle::on_forced_release(&FIRST_PET_OF_ACCOUNT_2);
+
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().status, BattleStatus::Expired);
+        assert_eq!(CritterBattle::blocks_release(&1), None);
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        assert_eq!(Balances::reserved_balance(1), 0);
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BattleVoided(battle_id, FIRST_PET_OF_ACCOUNT_2)));
+    });
+}
+
+#[test]
+fn damage_matches_golden_vectors() {
+    check_fixture(&test_vectors::fixture_path("damage"), &test_vectors::damage_vectors());
+}
+
+#[test]
+fn critical_rolls_match_golden_vectors() {
+    check_fixture(&test_vectors::fixture_path("critical"), &test_vectors::critical_vectors());
+}
+
+// --- Spectator betting ---
+
+// `bettor` bets `amount` on `outcome`.
+fn bet(bettor: u64, battle_id: u32, outcome: BattleOutcome, amount: u128) -> frame_support::dispatch::DispatchResultWithPostInfo {
+    CritterBattle::place_bet(RuntimeOrigin::signed(bettor), battle_id, outcome, amount)
+}
+
+#[test]
+fn winning_bets_share_the_losing_stakes_pro_rata() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(3, battle_id, BattleOutcome::Pet1Win, 100));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BetPlaced(battle_id, 3, BattleOutcome::Pet1Win, 100)));
+        assert_ok!(bet(4, battle_id, BattleOutcome::Pet1Win, 300));
+        assert_ok!(bet(5, battle_id, BattleOutcome::Pet2Win, 400));
+        assert_eq!(Balances::reserved_balance(5), 400);
+        assert_noop!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(3), battle_id), Error::<Test>::BetsNotSettled);
+
+        // Pet 1 wins; the losing 400 moves to the bets account, which passes a 5% cut of 20
+        // to the treasury. The prize fund in the pallet account is left alone.
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().outcome, Some(BattleOutcome::Pet1Win));
+        assert_eq!(Balances::total_balance(&5), 600);
+        assert_eq!(Balances::free_balance(CritterBattle::bets_account_id()), 380);
+        assert_eq!(Balances::free_balance(0), GENESIS_PRIZE_FUND);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 20);
+        assert_noop!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(5), battle_id), Error::<Test>::BetNotFound);
+
+        // The remaining 380 is split 1:3 by stake.
+        assert_ok!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(3), battle_id));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BetWon(battle_id, 3, 195)));
+        assert_ok!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(4), battle_id));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BetWon(battle_id, 4, 585)));
+        assert_noop!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(4), battle_id), Error::<Test>::BetNotFound);
+
+        assert_eq!((Balances::free_balance(3), Balances::reserved_balance(3)), (1095, 0));
+        assert_eq!((Balances::free_balance(4), Balances::reserved_balance(4)), (1285, 0));
+        assert_eq!(Balances::free_balance(CritterBattle::bets_account_id()), 0);
+        assert_eq!(Balances::free_balance(0), GENESIS_PRIZE_FUND);
+        assert_eq!(CritterBattle::battle_bet_pool(battle_id), Default::default());
+    });
+}
+
+#[test]
+fn wagers_name_the_pet_and_are_capped_per_battle() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        let wager = |bettor, pet_id, amount| {
+            CritterBattle::wager_on_battle(RuntimeOrigin::signed(bettor), battle_id, pet_id, amount)
+        };
+
+        assert_noop!(wager(3, 2, 100), Error::<Test>::InvalidBet);
+        assert_ok!(wager(3, FIRST_PET_OF_ACCOUNT_2, 600));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BattleWagerPlaced {
+            battle_id,
+            bettor: 3,
+            bet_on_pet: FIRST_PET_OF_ACCOUNT_2,
+            amount: 600,
+        }));
+        assert_eq!(
+            CritterBattle::battle_bets(battle_id, 3),
+            Some(crate::Bet { predicted_outcome: BattleOutcome::Pet2Win, amount: 600 })
+        );
+        assert_eq!(CritterBattle::battle_wagers(battle_id, 3), Some((FIRST_PET_OF_ACCOUNT_2, 600)));
+
+        // MaxWagerPerBattle caps each stake at 1000, not the battle's total.
+        assert_noop!(wager(4, 1, 1001), Error::<Test>::WagerCapExceeded);
+        assert_noop!(bet(4, battle_id, BattleOutcome::Draw, 1001), Error::<Test>::WagerCapExceeded);
+        assert_ok!(wager(4, 1, 900));
+
+        // Pet 1 wins; the treasury takes the 10% WagerHouseFee of the losing 600 and bettor 4
+        // the rest.
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 60);
+        assert!(CritterBattle::battle_wagers(battle_id, 3).is_none());
+        assert_ok!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(4), battle_id));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BattleWagerPayout {
+            battle_id,
+            bettor: 4,
+            winnings: 1440,
+        }));
+        assert!(CritterBattle::battle_wagers(battle_id, 4).is_none());
+    });
+}
+
+#[test]
+fn a_losing_stake_the_bets_account_cannot_take_is_returned() {
+    new_test_ext().execute_with(|| {
+        // A losing stake of 10 is below the existential deposit of the empty bets account, so
+        // it cannot be moved there.
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(3, battle_id, BattleOutcome::Pet1Win, 100));
+        assert_ok!(bet(4, battle_id, BattleOutcome::Pet2Win, 10));
+        ExistentialDeposit::set(50);
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+
+        // The loser gets the stake back instead of keeping it reserved with no bet to free it.
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::CritterBattle(Event::BetRefunded(battle_id, 4, 10))));
+        assert_eq!((Balances::free_balance(4), Balances::reserved_balance(4)), (1000, 0));
+        assert!(CritterBattle::battle_bets(battle_id, 4).is_none());
+        assert_eq!(Balances::free_balance(CritterBattle::bets_account_id()), 0);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 0);
+
+        // The winner is left with nothing to share but their own stake.
+        assert_ok!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(3), battle_id));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BetWon(battle_id, 3, 100)));
+        assert_eq!((Balances::free_balance(3), Balances::reserved_balance(3)), (1000, 0));
+        ExistentialDeposit::set(1);
+    });
+}
+
+#[test]
+fn an_unpaid_house_cut_is_kept_for_the_next_settlement() {
+    use sp_runtime::Perbill;
+
+    new_test_ext().execute_with(|| {
+        let house_cut = |bet_house_cut| crate::BattleParameters { bet_house_cut, ..CritterBattle::battle_parameters().unwrap() };
+
+        // A cut of every losing stake would empty the bets account, so the transfer fails and
+        // the cut stays behind.
+        assert_ok!(CritterBattle::set_battle_params_immediately(RuntimeOrigin::root(), house_cut(Perbill::one())));
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(3, battle_id, BattleOutcome::Pet1Win, 100));
+        assert_ok!(bet(4, battle_id, BattleOutcome::Pet2Win, 400));
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 0);
+        assert_eq!(CritterBattle::unpaid_house_cut(), 400);
+        assert_eq!(Balances::free_balance(CritterBattle::bets_account_id()), 400);
+
+        // The next battle's settlement pays it along with its own cut of 20.
+        assert_ok!(CritterBattle::set_battle_params_immediately(RuntimeOrigin::root(), house_cut(Perbill::from_percent(5))));
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(3, battle_id, BattleOutcome::Pet1Win, 100));
+        assert_ok!(bet(4, battle_id, BattleOutcome::Pet2Win, 400));
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 420);
+        assert_eq!(CritterBattle::unpaid_house_cut(), 0);
+
+        assert_ok!(CritterBattle::claim_bet_winnings(RuntimeOrigin::signed(3), battle_id));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::BetWon(battle_id, 3, 480)));
+        assert_eq!(Balances::free_balance(CritterBattle::bets_account_id()), 0);
+    });
+}
+
+#[test]
+fn bets_are_validated_and_close_after_max_bet_turn() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+
+        assert_noop!(bet(3, battle_id + 1, BattleOutcome::Pet1Win, 10), Error::<Test>::BattleNotFound);
+        assert_noop!(bet(1, battle_id, BattleOutcome::Pet1Win, 10), Error::<Test>::ParticipantCannotBet);
+        assert_noop!(bet(2, battle_id, BattleOutcome::Pet2Win, 10), Error::<Test>::ParticipantCannotBet);
+        assert_noop!(bet(3, battle_id, BattleOutcome::Forfeited, 10), Error::<Test>::InvalidBet);
+        assert_noop!(bet(3, battle_id, BattleOutcome::Pet1Win, 0), Error::<Test>::InvalidBet);
+        assert_ok!(bet(3, battle_id, BattleOutcome::Draw, 10));
+        assert_noop!(bet(3, battle_id, BattleOutcome::Pet1Win, 10), Error::<Test>::BetAlreadyPlaced);
+        assert_ok!(bet(4, battle_id, BattleOutcome::Pet2Win, 10));
+
+        // Bets stay open for the first two turns of the battle.
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(1), battle_id, BattleMove::Defend));
+        assert_ok!(bet(5, battle_id, BattleOutcome::Pet1Win, 10));
+        // MaxBetsPerBattle is 3.
+        assert_noop!(bet(6, battle_id, BattleOutcome::Pet1Win, 10), Error::<Test>::TooManyBets);
+
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(2), battle_id, BattleMove::Defend));
+        assert_noop!(bet(6, battle_id, BattleOutcome::Pet1Win, 10), Error::<Test>::BettingClosed);
+        assert_eq!(CritterBattle::battle_bet_pool(battle_id).bettors, 3);
+    });
+}
+
+#[test]
+fn bets_are_refunded_when_a_battle_ends_before_bets_close() {
+    new_test_ext().execute_with(|| {
+        let refunded = |battle_id, bettor, amount| {
+            System::assert_has_event(RuntimeEvent::CritterBattle(Event::BetRefunded(battle_id, bettor, amount)));
+            assert_eq!((Balances::free_balance(bettor), Balances::reserved_balance(bettor)), (1000, 0));
+            assert!(CritterBattle::battle_bets(battle_id, bettor).is_none());
+        };
+
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(3, battle_id, BattleOutcome::Pet1Win, 100));
+        assert_ok!(CritterBattle::decline_challenge(RuntimeOrigin::signed(2), battle_id));
+        refunded(battle_id, 3, 100);
+
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(4, battle_id, BattleOutcome::Draw, 50));
+        expire_challenges();
+        refunded(battle_id, 4, 50);
+
+        // Pet 10 wins by forfeit on the first turn, while bets are still open.
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(bet(5, battle_id, BattleOutcome::Pet2Win, 200));
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(1), battle_id));
+        refunded(battle_id, 5, 200);
+        assert_eq!(CritterBattle::battle_bet_pool(battle_id), Default::default());
+    });
+}
+
+// --- Tournament brackets ---
+
+// Creates a free tournament starting at block 5 and enters `pets`, each by its owner.
+fn tournament_with_entrants(pets: &[u32]) -> u32 {
+    let tournament_id = CritterBattle::tournament_count();
+    assert_ok!(CritterBattle::create_tournament(RuntimeOrigin::root(), b"Cup".to_vec(), Vec::new(), 8, 1, 10, 0, 5));
+    for pet_id in pets {
+        let owner = if *pet_id < FIRST_PET_OF_ACCOUNT_2 { 1 } else { 2 };
+        assert_ok!(CritterBattle::enter_tournament(RuntimeOrigin::signed(owner), tournament_id, *pet_id));
+    }
+    tournament_id
+}
+
+#[test]
+fn bracket_pairs_entrants_and_starts_first_round_battles() {
+    new_test_ext().execute_with(|| {
+        let tournament_id = tournament_with_entrants(&[1, 2, 3, FIRST_PET_OF_ACCOUNT_2, FIRST_PET_OF_ACCOUNT_2 + 1]);
+        run_to_block(5);
+
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::TournamentBracketGenerated {
+            tournament_id,
+            participant_count: 5,
+            round_count: 3,
+        }));
+
+        // Five entrants fill a bracket of eight: one first-round battle and three byes.
+        let matches: Vec<_> = crate::TournamentBracket::<Test>::iter_prefix_values(tournament_id)
+            .filter(|m| m.round == 1)
+            .collect();
+        assert_eq!(matches.len(), 4);
+        let mut seeded: Vec<u32> = matches.iter().flat_map(|m| [m.pet1_id, m.pet2_id]).flatten().collect();
+        seeded.sort();
+        assert_eq!(seeded, vec![1, 2, 3, FIRST_PET_OF_ACCOUNT_2, FIRST_PET_OF_ACCOUNT_2 + 1]);
+
+        let (fights, byes): (Vec<_>, Vec<_>) = matches.into_iter().partition(|m| m.pet2_id.is_some());
+        assert_eq!((fights.len(), byes.len()), (1, 3));
+        assert!(byes.iter().all(|m| m.round == 1 && m.battle_id.is_none() && m.winner_pet_id == m.pet1_id));
+
+        let fight = &fights[0];
+        assert_eq!(fight.winner_pet_id, None);
+        let battle = CritterBattle::battles(fight.battle_id.unwrap()).unwrap();
+        assert_eq!((battle.status, battle.current_turn), (BattleStatus::Active, 1));
+        assert_eq!((Some(battle.pet1_id), Some(battle.pet2_id)), (fight.pet1_id, fight.pet2_id));
+        assert_eq!(CritterBattle::pet_active_battle(battle.pet1_id), Some(battle.id));
+        assert_eq!(CritterBattle::pet_active_battle(battle.pet2_id), Some(battle.id));
+        // Bracket battles take no bond.
+        assert_eq!(Balances::reserved_balance(1), 0);
+
+        // The byes move on at once: two of them already meet in the second round.
+        let second_round: Vec<_> = crate::TournamentBracket::<Test>::iter_prefix_values(tournament_id)
+            .filter(|m| m.round == 2)
+            .collect();
+        assert_eq!(second_round.len(), 2);
+        assert_eq!(second_round.iter().filter(|m| m.battle_id.is_some()).count(), 1);
+    });
+}
+
+// Forfeits every open battle of a tournament's bracket, by its pet2's owner, until none is left.
+fn play_out_bracket(tournament_id: u32) {
+    let open_battle = || {
+        crate::TournamentBracket::<Test>::iter_prefix_values(tournament_id)
+            .filter_map(|m| m.battle_id)
+            .find(|battle_id| CritterBattle::battles(battle_id).unwrap().status == BattleStatus::Active)
+    };
+    while let Some(battle_id) = open_battle() {
+        let forfeiter = CritterBattle::battles(battle_id).unwrap().pet2_owner;
+        assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(forfeiter), battle_id));
+    }
+}
+
+#[test]
+fn bracket_winners_advance_to_a_champion_who_takes_most_of_the_pool() {
+    use crate::TournamentStatus;
+
+    new_test_ext().execute_with(|| {
+        let tournament_id = CritterBattle::tournament_count();
+        assert_ok!(CritterBattle::create_tournament(RuntimeOrigin::root(), b"Cup".to_vec(), Vec::new(), 4, 1, 10, 100, 5));
+        for (owner, pet_id) in [(1, 1), (1, 2), (2, FIRST_PET_OF_ACCOUNT_2), (2, FIRST_PET_OF_ACCOUNT_2 + 1)] {
+            assert_ok!(CritterBattle::enter_tournament(RuntimeOrigin::signed(owner), tournament_id, pet_id));
+        }
+        run_to_block(5);
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        play_out_bracket(tournament_id);
+
+        // Four entrants play two semi-finals and a final, numbered after them.
+        assert_eq!(CritterBattle::bracket_match_number(2, 2, 0), 2);
+        let final_match = CritterBattle::tournament_bracket(tournament_id, 2).unwrap();
+        let champion = final_match.winner_pet_id.unwrap();
+        let tournament = CritterBattle::tournaments(tournament_id).unwrap();
+        assert_eq!((tournament.status, tournament.winner_pet_id), (TournamentStatus::Completed, Some(champion)));
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::TournamentEnded(tournament_id, champion)));
+
+        // The pool of 400 is split 70/20/10, the semi-final losers sharing the third place.
+        let mut prizes: Vec<(u32, u128)> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::CritterBattle(Event::TournamentPrizeAwarded { pet_id, amount, .. }) => Some((pet_id, amount)),
+                _ => None,
+            })
+            .collect();
+        prizes.sort_by_key(|(_, amount)| *amount);
+        assert_eq!(prizes.iter().map(|(_, amount)| *amount).collect::<Vec<_>>(), vec![20, 20, 80, 280]);
+        assert_eq!(prizes[3].0, champion);
+        assert_eq!(Some(prizes[2].0), final_match.loser_pet_id());
+        assert_eq!(Balances::free_balance(1) + Balances::free_balance(2), 2000);
+
+        // Every pet is free to enter another tournament.
+        assert!(crate::PetActiveTournament::<Test>::iter().next().is_none());
+    });
+}
+
+#[test]
+fn a_stalled_bracket_resumes_once_its_next_battle_can_start() {
+    new_test_ext().execute_with(|| {
+        let tournament_id = tournament_with_entrants(&[1, 2, FIRST_PET_OF_ACCOUNT_2, FIRST_PET_OF_ACCOUNT_2 + 1]);
+        run_to_block(5);
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        let forfeit_semi_final = |number| {
+            let battle_id = CritterBattle::tournament_bracket(tournament_id, number).unwrap().battle_id.unwrap();
+            let forfeiter = CritterBattle::battles(battle_id).unwrap().pet2_owner;
+            assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(forfeiter), battle_id));
+        };
+        forfeit_semi_final(0);
+
+        // The final cannot start while the pet manager fails, so the second semi-final's winner
+        // stalls and the final is left as it was.
+        MockPetManagerFailure::set(Some(crittercraft_traits::NftCallError::Failed));
+        forfeit_semi_final(1);
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::BracketStalled { tournament_id, match_number: 1 }));
+        let final_match = CritterBattle::tournament_bracket(tournament_id, 2).unwrap();
+        assert_eq!((final_match.pet2_id, final_match.battle_id), (None, None));
+        assert_noop!(
+            CritterBattle::resume_bracket(RuntimeOrigin::signed(3), tournament_id, 0),
+            Error::<Test>::BracketNotStalled
+        );
+        assert_noop!(
+            CritterBattle::resume_bracket(RuntimeOrigin::signed(3), tournament_id, 1),
+            Error::<Test>::PetManagerCallFailed
+        );
+
+        // Anyone can resume it once the battle can start.
+        MockPetManagerFailure::set(None);
+        assert_ok!(CritterBattle::resume_bracket(RuntimeOrigin::signed(3), tournament_id, 1));
+        let final_match = CritterBattle::tournament_bracket(tournament_id, 2).unwrap();
+        assert!(final_match.pet2_id.is_some() && final_match.battle_id.is_some());
+        assert!(CritterBattle::stalled_bracket_matches(tournament_id, 1).is_none());
+    });
+}
+
+#[test]
+fn unpaid_tournament_prizes_are_left_to_claim() {
+    use crate::TournamentStatus;
+
+    new_test_ext().execute_with(|| {
+        let tournament_id = CritterBattle::tournament_count();
+        assert_ok!(CritterBattle::create_tournament(RuntimeOrigin::root(), b"Cup".to_vec(), Vec::new(), 2, 1, 10, 100, 5));
+        assert_ok!(CritterBattle::enter_tournament(RuntimeOrigin::signed(1), tournament_id, 1));
+        assert_ok!(CritterBattle::enter_tournament(RuntimeOrigin::signed(2), tournament_id, FIRST_PET_OF_ACCOUNT_2));
+        run_to_block(5);
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        let final_battle = CritterBattle::battles(CritterBattle::tournament_bracket(tournament_id, 0).unwrap().battle_id.unwrap()).unwrap();
+
+        // The pallet account cannot pay the pool of 200, so the prizes are kept.
+        assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 0, 1));
+        assert_ok!(CritterBattle::forfeit_battle(RuntimeOrigin::signed(final_battle.pet2_owner), final_battle.id));
+        assert_eq!(CritterBattle::tournaments(tournament_id).unwrap().status, TournamentStatus::Completed);
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::TournamentPrizeUnpaid {
+            tournament_id,
+            pet_id: final_battle.pet1_id,
+            owner: final_battle.pet1_owner,
+            amount: 160,
+        }));
+        assert_eq!(
+            CritterBattle::unpaid_tournament_prizes(tournament_id, final_battle.pet2_id),
+            Some((final_battle.pet2_owner, 40))
+        );
+
+        // The owners claim them once the account is funded.
+        assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 0, 1000));
+        assert_noop!(
+            CritterBattle::claim_tournament_prize(RuntimeOrigin::signed(final_battle.pet2_owner), tournament_id, final_battle.pet1_id),
+            Error::<Test>::NotPetOwner
+        );
+        let champion_balance = Balances::free_balance(final_battle.pet1_owner);
+        assert_ok!(CritterBattle::claim_tournament_prize(
+            RuntimeOrigin::signed(final_battle.pet1_owner),
+            tournament_id,
+            final_battle.pet1_id
+        ));
+        assert_eq!(Balances::free_balance(final_battle.pet1_owner), champion_balance + 160);
+        System::assert_last_event(RuntimeEvent::CritterBattle(Event::TournamentPrizeAwarded {
+            tournament_id,
+            pet_id: final_battle.pet1_id,
+            owner: final_battle.pet1_owner,
+            amount: 160,
+        }));
+        assert_noop!(
+            CritterBattle::claim_tournament_prize(RuntimeOrigin::signed(final_battle.pet1_owner), tournament_id, final_battle.pet1_id),
+            Error::<Test>::PrizeNotFound
+        );
+    });
+}
+
+#[test]
+fn transferred_tournament_pets_forfeit_their_match() {
+    new_test_ext().execute_with(|| {
+        let tournament_id = tournament_with_entrants(&[1, FIRST_PET_OF_ACCOUNT_2]);
+        run_to_block(5);
+
+        // Pet 10 changed hands after it was entered, so pet 1 wins the final without a battle.
+        MockTransferredPets::set(vec![(FIRST_PET_OF_ACCOUNT_2, 3)]);
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        let only_match = CritterBattle::tournament_bracket(tournament_id, 0).unwrap();
+        assert_eq!((only_match.battle_id, only_match.winner_pet_id), (None, Some(1)));
+        assert_eq!(CritterBattle::tournaments(tournament_id).unwrap().winner_pet_id, Some(1));
+        assert_eq!(CritterBattle::pet_active_battle(1), None);
+    });
+}
+
+#[test]
+fn bracket_is_generated_once_for_a_started_tournament() {
+    new_test_ext().execute_with(|| {
+        let tournament_id = tournament_with_entrants(&[1, FIRST_PET_OF_ACCOUNT_2]);
+
+        assert_noop!(
+            CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id + 1),
+            Error::<Test>::TournamentNotFound
+        );
+        assert_noop!(
+            CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id),
+            Error::<Test>::InvalidTournamentStatus
+        );
+        run_to_block(5);
+        assert_noop!(
+            CritterBattle::generate_bracket(RuntimeOrigin::signed(1), tournament_id),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id));
+        let only_match = CritterBattle::tournament_bracket(tournament_id, 0).unwrap();
+        assert!(only_match.battle_id.is_some());
+        assert_noop!(
+            CritterBattle::generate_bracket(RuntimeOrigin::root(), tournament_id),
+            Error::<Test>::BracketAlreadyGenerated
+        );
+    });
+}
+
+#[test]
+fn bracket_round_count_covers_every_entrant() {
+    let rounds: Vec<u8> = [2, 3, 4, 5, 8, 9].into_iter().map(CritterBattle::bracket_round_count).collect();
+    assert_eq!(rounds, vec![1, 2, 2, 3, 3, 4]);
+}
+
+// --- Rating decay ---
+
+fn rating_of(pet_id: u32) -> u16 {
+    CritterBattle::pet_battle_stats(pet_id).3
+}
+
+#[test]
+fn queued_pets_time_out_and_lose_rating() {
+    use crate::{EloDecayReason, PetBattleStats, PetLastBattle};
+
+    new_test_ext().execute_with(|| {
+        // Both pets battled recently, so only the queue timeout applies. Their ratings are too
+        // far apart to be matched.
+        for (pet_id, rating) in [(1, 1100), (FIRST_PET_OF_ACCOUNT_2, 820)] {
+            PetBattleStats::<Test>::insert(pet_id, (1, 0, 0, rating));
+            PetLastBattle::<Test>::insert(pet_id, 1);
+        }
+        assert_ok!(CritterBattle::enter_matchmaking(RuntimeOrigin::signed(1), 1));
+        assert_ok!(CritterBattle::enter_matchmaking(RuntimeOrigin::signed(2), FIRST_PET_OF_ACCOUNT_2));
+
+        // The timeout is 20 blocks.
+        run_to_block(20);
+        assert!(CritterBattle::battle_matchmaking_queue(1).is_some());
+        run_to_block(30);
+        assert!(CritterBattle::battle_matchmaking_queue(1).is_none());
+        assert!(CritterBattle::battle_matchmaking_queue(FIRST_PET_OF_ACCOUNT_2).is_none());
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::PetRemovedFromMatchmaking(1, 1)));
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::PetEloDecayed {
+            pet_id: 1,
+            old_rating: 1100,
+            new_rating: 1050,
+            reason: EloDecayReason::QueueTimeout,
+        }));
+
+        // The penalty stops at the floor of 800.
+        assert_eq!(rating_of(FIRST_PET_OF_ACCOUNT_2), 800);
+    });
+}
+
+#[test]
+fn inactive_pets_decay_passively_to_the_floor() {
+    use crate::{EloDecayReason, PetBattleStats, PetLastBattle};
+
+    new_test_ext().execute_with(|| {
+        PetBattleStats::<Test>::insert(1, (1, 0, 0, 815));
+        PetLastBattle::<Test>::insert(1, 1);
+        // A pet rated before decay has no last battle, and counts as inactive.
+        PetBattleStats::<Test>::insert(2, (1, 0, 0, 900));
+        // A rating below the floor never decays.
+        PetBattleStats::<Test>::insert(3, (0, 1, 0, 790));
+
+        run_to_block(10);
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::PetEloDecayed {
+            pet_id: 2,
+            old_rating: 900,
+            new_rating: 890,
+            reason: EloDecayReason::Inactivity,
+        }));
+        assert_eq!((rating_of(1), rating_of(3)), (815, 790));
+
+        // Pet 1 falls out of the 100 block window, and decays every pass to the floor.
+        run_to_block(110);
+        assert_eq!(rating_of(1), 805);
+        run_to_block(115);
+        assert_eq!(rating_of(1), 805);
+        run_to_block(120);
+        assert_eq!(rating_of(1), 800);
+        System::reset_events();
+        run_to_block(130);
+        assert_eq!(rating_of(1), 800);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::CritterBattle(Event::PetEloDecayed { pet_id: 1, .. })
+        )));
+        assert_eq!(rating_of(3), 790);
+
+        // A concluded battle restarts the window.
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(CritterBattle::pet_last_battle(1), Some(130));
+        assert_eq!(CritterBattle::pet_last_battle(FIRST_PET_OF_ACCOUNT_2), Some(130));
+    });
+}
+
+#[test]
+fn rating_decay_visits_a_bounded_batch_of_pets_per_block() {
+    use crate::{PetBattleStats, PetLastBattle, RatingDecayProgress};
+    use crittercraft_traits::nft::ReleaseBlocker;
+
+    new_test_ext().execute_with(|| {
+        MaxRatingDecaysPerBlock::set(2);
+        for pet_id in 1..=3 {
+            PetBattleStats::<Test>::insert(pet_id, (1, 0, 0, 900));
+        }
+
+        // The pass visits two pets at block 10, and resumes with the third at block 11.
+        run_to_block(10);
+        assert!(matches!(CritterBattle::rating_decay_cursor(), Some(RatingDecayProgress::Ratings(Some(_)))));
+        assert_eq!((1..=3).filter(|pet_id| rating_of(*pet_id) == 890).count(), 2);
+        run_to_block(11);
+        assert_eq!(CritterBattle::rating_decay_cursor(), None);
+        assert_eq!((rating_of(1), rating_of(2), rating_of(3)), (890, 890, 890));
+
+        // No pass runs until the next interval.
+        run_to_block(12);
+        assert_eq!((rating_of(1), rating_of(2), rating_of(3)), (890, 890, 890));
+
+        // A pet that leaves play forgets its last battle.
+        PetLastBattle::<Test>::insert(1, 12);
+        CritterBattle::on_forced_release(&1);
+        assert_eq!(CritterBattle::pet_last_battle(1), None);
+    });
+}
+
+// --- Move energy ---
+
+fn energies(battle_id: u32) -> (u8, u8) {
+    let battle = CritterBattle::battles(battle_id).unwrap();
+    (battle.pet1_energy, battle.pet2_energy)
+}
+
+#[test]
+fn drained_pet_cannot_spam_special_attack() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        let special_attack = |who| CritterBattle::execute_move(RuntimeOrigin::signed(who), battle_id, BattleMove::SpecialAttack);
+        let heal = || CritterBattle::execute_move(RuntimeOrigin::signed(2), battle_id, BattleMove::Heal);
+
+        // Special attacks cost 25 and heals 15; each pet regains 10 from its second turn on.
+        assert_ok!(special_attack(1));
+        assert_ok!(heal());
+        assert_eq!(energies(battle_id), (25, 35));
+        assert_ok!(special_attack(1));
+        assert_ok!(heal());
+        assert_eq!(energies(battle_id), (10, 30));
+
+        // Regenerated to 20, pet 1 is short of another special attack.
+        assert_noop!(special_attack(1), Error::<Test>::InsufficientEnergy);
+        assert_eq!(CritterBattle::battles(battle_id).unwrap().current_turn, 5);
+
+        // Defending costs nothing and restores 5, so pet 1 can attack again next turn.
+        assert_ok!(CritterBattle::execute_move(RuntimeOrigin::signed(1), battle_id, BattleMove::Defend));
+        assert_ok!(heal());
+        assert_eq!(energies(battle_id), (25, 25));
+        assert_ok!(special_attack(1));
+        assert_eq!(energies(battle_id), (10, 25));
+    });
+}
+
+#[test]
+fn energy_regenerates_on_each_pets_own_turns() {
+    new_test_ext().execute_with(|| {
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        assert_ok!(CritterBattle::accept_challenge(RuntimeOrigin::signed(2), battle_id));
+        let attack = |who| CritterBattle::execute_move(RuntimeOrigin::signed(who), battle_id, BattleMove::Attack);
+
+        // Blocks passing regenerate nothing.
+        run_to_block(5);
+        assert_eq!(energies(battle_id), (50, 50));
+
+        // Each pet's first turn starts at its initial energy; attacks cost 5.
+        assert_ok!(attack(1));
+        assert_eq!(energies(battle_id), (45, 50));
+        assert_ok!(attack(2));
+        assert_eq!(energies(battle_id), (45, 45));
+
+        // Later turns regain 10 before paying, for the pet whose turn it is only.
+        assert_ok!(attack(1));
+        assert_eq!(energies(battle_id), (50, 45));
+        assert_ok!(attack(2));
+        assert_eq!(energies(battle_id), (50, 50));
+    });
+}
+
+#[test]
+fn energy_costs_above_max_energy_are_rejected() {
+    new_test_ext().execute_with(|| {
+        let mut params = params_with_max_turns(10);
+        params.move_energy_costs.heal = crate::MAX_ENERGY + 1;
+        assert_noop!(
+            CritterBattle::set_battle_params_immediately(RuntimeOrigin::root(), params),
+            Error::<Test>::InvalidBattleParameters
+        );
+    });
+}
+
+// Account 1's pets 1 and 2 challenge account 2's first two pets, and account 2 accepts.
+fn team_battle() -> u32 {
+    let battle_id = CritterBattle::battle_count();
+    let target_pet = FIRST_PET_OF_ACCOUNT_2;
+    assert_ok!(CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 1, 2, 2, target_pet, target_pet + 1));
+    assert_ok!(CritterBattle::accept_team_challenge(RuntimeOrigin::signed(2), battle_id));
+    battle_id
+}
+
+#[test]
+fn team_challenges_need_two_pets_of_each_owner() {
+    new_test_ext().execute_with(|| {
+        let target_pet = FIRST_PET_OF_ACCOUNT_2;
+        assert_noop!(
+            CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 1, 1, 2, target_pet, target_pet + 1),
+            Error::<Test>::InvalidTeam
+        );
+        assert_noop!(
+            CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 1, target_pet, 2, target_pet + 1, target_pet + 2),
+            Error::<Test>::NotPetOwner
+        );
+
+        // The pets are only committed once the challenge is accepted.
+        let battle_id = CritterBattle::battle_count();
+        assert_ok!(CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 1, 2, 2, target_pet, target_pet + 1));
+        assert_eq!(CritterBattle::pet_active_battle(1), None);
+        assert_noop!(
+            CritterBattle::accept_team_challenge(RuntimeOrigin::signed(1), battle_id),
+            Error::<Test>::NotBattleParticipant
+        );
+        assert_ok!(CritterBattle::accept_team_challenge(RuntimeOrigin::signed(2), battle_id));
+        for pet_id in [1, 2, target_pet, target_pet + 1] {
+            assert_eq!(CritterBattle::pet_active_battle(pet_id), Some(battle_id));
+        }
+    });
+}
+
+#[test]
+fn team_battle_ends_when_both_pets_of_a_team_faint() {
+    new_test_ext().execute_with(|| {
+        let battle_id = team_battle();
+        let (pet1, pet2, pet10, pet11) = (1, 2, FIRST_PET_OF_ACCOUNT_2, FIRST_PET_OF_ACCOUNT_2 + 1);
+        let team_move = |who, pet_id, move_type| {
+            CritterBattle::execute_team_move(RuntimeOrigin::signed(who), battle_id, pet_id, move_type)
+        };
+        AwardedExperience::take();
+
+        // Team 1's first pet opens, and only the pet whose turn it is can move.
+        assert_noop!(team_move(1, pet2, BattleMove::Attack), Error::<Test>::NotYourTurn);
+        assert_noop!(team_move(2, pet10, BattleMove::Attack), Error::<Test>::NotYourTurn);
+
+        // Attacks crit for 20 on the opposing pet in the same position; defends heal 5.
+        for _ in 0..2 {
+            assert_ok!(team_move(1, pet1, BattleMove::Attack));
+            assert_ok!(team_move(2, pet10, BattleMove::Defend));
+            assert_ok!(team_move(1, pet2, BattleMove::Attack));
+            assert_ok!(team_move(2, pet11, BattleMove::Defend));
+        }
+        let battle = CritterBattle::team_battles(battle_id).unwrap();
+        assert_eq!((battle.team1.health, battle.team2.health), ([50, 50], [20, 20]));
+        assert_eq!(battle.team2.total_health(), 40);
+
+        // Pet 10 faints, so its turn is passed over.
+        assert_ok!(team_move(1, pet1, BattleMove::Attack));
+        assert_noop!(team_move(2, pet10, BattleMove::Defend), Error::<Test>::NotYourTurn);
+        assert_ok!(team_move(1, pet2, BattleMove::Attack));
+
+        let battle = CritterBattle::team_battles(battle_id).unwrap();
+        assert_eq!(battle.status, BattleStatus::Completed);
+        assert_eq!(battle.winning_team, Some(1));
+        assert_eq!(battle.team2.health, [0, 0]);
+
+        let xp = CritterBattle::battle_parameters().unwrap().base_experience_reward;
+        assert_eq!(AwardedExperience::get(), vec![(pet1, xp), (pet10, xp / 2), (pet2, xp), (pet11, xp / 2)]);
+        System::assert_has_event(RuntimeEvent::CritterBattle(Event::TeamBattleCompleted {
+            battle_id,
+            winning_team: Some(1),
+            xp_distributed: 2 * xp + 2 * (xp / 2),
+        }));
+        for pet_id in [pet1, pet2, pet10, pet11] {
+            assert_eq!(CritterBattle::pet_active_battle(pet_id), None);
+        }
+        assert_eq!(CritterBattle::pet_battle_stats(pet1).0, 1);
+        assert_eq!(CritterBattle::pet_battle_stats(pet11).1, 1);
+        assert_eq!(CritterBattle::battle_history(battle_id).len(), 10);
+    });
+}
+
+#[test]
+fn team_status_effects_target_a_single_pet() {
+    new_test_ext().execute_with(|| {
+        let battle_id = team_battle();
+        let target_pet = FIRST_PET_OF_ACCOUNT_2 + 1;
+        let apply = |who, pet_id| {
+            CritterBattle::apply_status_effect(RuntimeOrigin::signed(who), battle_id, pet_id, StatusEffect::Burn(2))
+        };
+
+        assert_noop!(apply(2, target_pet), Error::<Test>::NotYourTurn);
+        assert_noop!(apply(1, 3), Error::<Test>::NotBattleParticipant);
+        assert_ok!(apply(1, target_pet));
+
+        // The burn ticks each block, on its target alone.
+        run_to_block(2);
+        let battle = CritterBattle::team_battles(battle_id).unwrap();
+        assert_eq!((battle.team1.health, battle.team2.health), ([50, 50], [50, 45]));
+        run_to_block(3);
+        let battle = CritterBattle::team_battles(battle_id).unwrap();
+        assert_eq!(battle.team2.health, [50, 40]);
+        assert!(battle.team2.status_effects[1].is_empty());
+    });
+}
+
+#[test]
+fn team_challenges_take_a_bond_and_battle_slots_like_1v1_challenges() {
+    use crate::ActiveTeamBattles;
+
+    new_test_ext().execute_with(|| {
+        let target_pet = FIRST_PET_OF_ACCOUNT_2;
+        let bond = CritterBattle::battle_parameters().unwrap().challenge_bond;
+
+        // MaxActiveBattles is 1, so a pending 1v1 challenge leaves no slot for a team battle.
+        let battle_id = challenge(3, target_pet + 2);
+        assert_noop!(
+            CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 1, 2, 2, target_pet, target_pet + 1),
+            Error::<Test>::TooManyActiveBattles
+        );
+        assert_ok!(CritterBattle::decline_challenge(RuntimeOrigin::signed(2), battle_id));
+
+        let battle_id = CritterBattle::battle_count();
+        assert_ok!(CritterBattle::create_team_challenge(RuntimeOrigin::signed(1), 1, 2, 2, target_pet, target_pet + 1));
+        assert_eq!(Balances::reserved_balance(1), bond);
+        assert_eq!(AccountActiveBattles::<Test>::get(1).into_inner(), vec![battle_id]);
+        assert_eq!(ActiveTeamBattles::<Test>::get().into_inner(), vec![battle_id]);
+
+        // An expired team challenge returns the bond and frees the slots.
+        expire_challenges();
+        assert_eq!(CritterBattle::team_battles(battle_id).unwrap().status, BattleStatus::Expired);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty());
+        assert!(ActiveTeamBattles::<Test>::get().is_empty());
+
+        // So does a concluded team battle, which is no longer processed each block.
+        let battle_id = team_battle();
+        assert_eq!(AccountActiveBattles::<Test>::get(2).into_inner(), vec![battle_id]);
+        let mut battle = CritterBattle::team_battles(battle_id).unwrap();
+        battle.team2.health = [0, 0];
+        crate::TeamBattles::<Test>::insert(battle_id, battle);
+        run_to_block(System::block_number() + 1);
+        assert_eq!(CritterBattle::team_battles(battle_id).unwrap().status, BattleStatus::Completed);
+        assert_eq!((Balances::reserved_balance(1), Balances::free_balance(1)), (0, 1000));
+        assert!(AccountActiveBattles::<Test>::get(1).is_empty() && AccountActiveBattles::<Test>::get(2).is_empty());
+        assert!(ActiveTeamBattles::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn migrate_to_v6_indexes_live_team_battles() {
+    use crate::migrations::v6::MigrateToV6;
+    use crate::{ActiveTeamBattles, TeamBattles};
+    use crittercraft_traits::migration::{harness::*, StorageFixture};
+    use frame_support::traits::ReservableCurrency;
+
+    new_test_ext().execute_with(|| {
+        // Two team battles as storage version 5 left them: unindexed, and without bonds or
+        // battle slots. Account 7 cannot cover the bond of its challenge.
+        let bond = CritterBattle::battle_parameters().unwrap().challenge_bond;
+        let active = team_battle();
+        let mut unbonded = CritterBattle::team_battles(active).unwrap();
+        unbonded.id = active + 1;
+        unbonded.team1_owner = 7;
+        unbonded.team1_pet1_id = 3;
+        unbonded.team1_pet2_id = 4;
+        unbonded.status = BattleStatus::Challenged;
+        TeamBattles::<Test>::insert(unbonded.id, unbonded);
+        ActiveTeamBattles::<Test>::kill();
+        AccountActiveBattles::<Test>::remove(1);
+        AccountActiveBattles::<Test>::remove(2);
+        Balances::unreserve(&1, bond);
+
+        run_migration::<CritterBattle, MigrateToV6<Test>>(&StorageFixture { storage_version: 5, entries: Vec::new() }, 6);
+
+        assert_eq!(ActiveTeamBattles::<Test>::get().into_inner(), vec![active]);
+        assert_eq!(Balances::reserved_balance(1), bond);
+        assert_eq!(AccountActiveBattles::<Test>::get(1).into_inner(), vec![active]);
+        assert_eq!(AccountActiveBattles::<Test>::get(2).into_inner(), vec![active]);
+        assert_eq!(CritterBattle::team_battles(active + 1).unwrap().status, BattleStatus::Expired);
+        assert_eq!(CritterBattle::pet_active_battle(1), Some(active));
+    });
+}
+
+// Account 1's pets, as the NFT pallet would list them.
+struct AccountOnePets;
+impl sp_runtime::traits::Convert<u64, Vec<u32>> for AccountOnePets {
+    fn convert(who: u64) -> Vec<u32> {
+        if who == 1 { vec![1, 2] } else { Vec::new() }
+    }
+}
+
+#[test]
+fn battle_record_score_counts_wins_and_draws_of_owned_pets() {
+    use pallet_user_profile::ScoreContributor;
+    type Score = crate::BattleRecordScore<Test, AccountOnePets>;
+
+    new_test_ext().execute_with(|| {
+        assert_eq!(Score::get_score_contribution(&1), 0);
+
+        // Fire (pet 1) beats Water (pet 10) in three attacks.
+        let battle_id = challenge(1, FIRST_PET_OF_ACCOUNT_2);
+        fight(battle_id, &[(BattleMove::Attack, BattleMove::Attack); 3]);
+        assert_eq!(Score::get_score_contribution(&1), crate::SCORE_PER_WIN);
+
+        // Losses score nothing, and pets the account does not own are not counted.
+        crate::PetBattleStats::<Test>::insert(2, (0, 4, 3, 1000));
+        crate::PetBattleStats::<Test>::insert(3, (9, 0, 0, 1000));
+        assert_eq!(Score::get_score_contribution(&1), crate::SCORE_PER_WIN + 3 * crate::SCORE_PER_DRAW);
+        assert_eq!(Score::get_score_contribution(&2), 0);
+    });
+}
@@ -0,0 +1,399 @@
+//! # Account Migration
+//!
+//! Moves everything an account holds in this pallet to a new account, for users who rotate
+//! keys. The old account records its intent with `initiate_account_migration`, and the new
+//! account executes it with `accept_account_migration`, so neither side can be surprised.
+//!
+//! The migration runs one category at a time, in `AccountMigrationCategory` order. Accepting
+//! runs up to `AccountMigrationStepsPerBlock` categories at once; the rest continue in `on_idle`,
+//! from the category recorded in `AccountMigrations`. Each completed category emits
+//! `AccountMigrationCategoryMigrated` naming both accounts.
+//!
+//! Per-pet data is keyed by `PetId` and stays untouched; only the pets' owner changes, and each
+//! pet keeps its `acquired_at_block`, since the collection stays with the same person. What
+//! is merged into a new account that already holds something:
+//!
+//! - pets and parked pets are appended in their old order; auto-care subscriptions move too
+//! - achievements are merged by ID, keeping the further progress
+//! - notifications are merged oldest first, evicting as a full inbox does
+//! - where both accounts have a setting (UX flow, digest settings, rate limit windows), the new
+//!   account's wins; the later daily claim wins; digest counts add up
+//!
+//! Pets, parked pets and achievements that would not fit the new account block the migration up
+//! front, as do locked pets: their lock holders would not find them under the old account. If
+//! the new account fills up after accepting, the migration waits until it has room again.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    pallet_prelude::RuntimeDebug,
+    storage::with_storage_layer,
+    traits::Get,
+    weights::Weight,
+    BoundedVec,
+};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use crittercraft_traits::bounded::push_evicting_by;
+use crate::user_experience::UserAchievement;
+use crate::{Config, Error, Event, Pallet};
+
+/// A category of account state, migrated in this order.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AccountMigrationCategory {
+    /// Owned pets (`OwnerOfPet`, `PetNftOwner`, the pets' embedded owner) and their auto-care
+    /// subscriptions
+    Pets,
+
+    /// Bred pets parked for the account (`PendingClaimsOf`, `PendingClaims`)
+    PendingClaims,
+
+    /// User achievements, the UX flow and the starter pet grant
+    Achievements,
+
+    /// Notifications, digest settings and pending digest counts
+    Notifications,
+
+    /// The daily claim time and rate limit windows
+    Cooldowns,
+}
+
+impl AccountMigrationCategory {
+    /// The category migrated after this one, if any.
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Pets => Some(Self::PendingClaims),
+            Self::PendingClaims => Some(Self::Achievements),
+            Self::Achievements => Some(Self::Notifications),
+            Self::Notifications => Some(Self::Cooldowns),
+            Self::Cooldowns => None,
+        }
+    }
+}
+
+/// An accepted account migration in progress.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AccountMigration<AccountId> {
+    /// The account everything moves to
+    pub new_account: AccountId,
+
+    /// The next category to migrate
+    pub next_category: AccountMigrationCategory,
+}
+
+/// A system for migrating accounts.
+pub struct AccountMigrationSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> AccountMigrationSystem<T> {
+    /// The weight charged for migrating one category. Pets dominate: each moved pet reads and
+    /// writes `PetNfts`, `PetNftOwner` and `CareSubscriptions`.
+    pub fn step_weight() -> Weight {
+        let per_pet = 3u64.saturating_mul(T::MaxOwnedPets::get() as u64);
+        // Reads and writes: AccountMigrations and both accounts' OwnerOfPet.
+        Weight::from_parts(20_000, 0).saturating_add(T::DbWeight::get().reads_writes(per_pet + 3, per_pet + 3))
+    }
+
+    /// The weight of accepting a migration: the up-front checks, one read of `LockedNfts` per
+    /// pet, and the categories migrated at once.
+    pub fn accept_weight() -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(T::MaxOwnedPets::get() as u64 + 8, 2))
+            .saturating_add(Self::step_weight().saturating_mul(T::AccountMigrationStepsPerBlock::get() as u64))
+    }
+
+    /// Records that `old_account` wants to move everything to `new_account`. A later request
+    /// replaces an earlier one.
+    ///
+    /// # Parameters
+    ///
+    /// * `old_account` - The account to migrate from
+    /// * `new_account` - The account to migrate to
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the request was recorded, Err otherwise
+    pub fn initiate(old_account: T::AccountId, new_account: T::AccountId) -> DispatchResult {
+        ensure!(old_account != new_account, Error::<T>::CannotMigrateToSelf);
+        ensure!(!crate::AccountMigrations::<T>::contains_key(&old_account), Error::<T>::AccountMigrationInProgress);
+
+        crate::AccountMigrationRequests::<T>::insert(&old_account, new_account.clone());
+        Pallet::<T>::deposit_event(Event::AccountMigrationRequested { old_account, new_account });
+        Ok(())
+    }
+
+    /// Accepts a migration `old_account` requested to `new_account`, and migrates the first
+    /// `AccountMigrationStepsPerBlock` categories.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_account` - The account accepting; must be the one requested
+    /// * `old_account` - The account to migrate from
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the migration started, Err if it was not requested, either
+    ///   account is already migrating, a pet is locked or the new account lacks room
+    pub fn accept(new_account: T::AccountId, old_account: T::AccountId) -> DispatchResult {
+        // 1. Validate the request.
+        ensure!(
+            crate::AccountMigrationRequests::<T>::get(&old_account) == Some(new_account.clone()),
+            Error::<T>::AccountMigrationNotRequested
+        );
+        ensure!(!crate::AccountMigrations::<T>::contains_key(&new_account), Error::<T>::AccountMigrationInProgress);
+        ensure!(
+            !crate::AccountMigrations::<T>::iter_values().any(|migration| migration.new_account == old_account),
+            Error::<T>::AccountMigrationInProgress
+        );
+
+        // 2. Check everything will fit before moving anything.
+        let pets = crate::OwnerOfPet::<T>::get(&old_account);
+        ensure!(
+            pets.iter().all(|pet_id| !crate::LockedNfts::<T>::contains_key(pet_id)),
+            Error::<T>::NftLocked
+        );
+        Self::ensure_room(&old_account, &new_account)?;
+
+        // 3. Record the migration and start it.
+        crate::AccountMigrationRequests::<T>::remove(&old_account);
+        crate::AccountMigrations::<T>::insert(&old_account, AccountMigration {
+            new_account,
+            next_category: AccountMigrationCategory::Pets,
+        });
+        Self::advance(&old_account, T::AccountMigrationStepsPerBlock::get());
+        Ok(())
+    }
+
+    /// Continues migrations in progress until the weight limit or `AccountMigrationStepsPerBlock`
+    /// categories are reached. A migration whose next category does not fit waits for a later
+    /// block.
+    ///
+    /// # Parameters
+    ///
+    /// * `limit` - The weight available for processing
+    ///
+    /// # Returns
+    ///
+    /// * `Weight` - The weight consumed
+    pub fn process(limit: Weight) -> Weight {
+        let read = T::DbWeight::get().reads(1);
+        let step_weight = Self::step_weight();
+        let mut used = Weight::zero();
+        let mut steps = T::AccountMigrationStepsPerBlock::get();
+
+        let old_accounts: Vec<T::AccountId> = crate::AccountMigrations::<T>::iter_keys().collect();
+        used.saturating_accrue(read.saturating_mul(old_accounts.len() as u64));
+        for old_account in old_accounts {
+            // A failed attempt costs as much as a migrated category, and moves on to the next
+            // migration.
+            while steps > 0 && !used.saturating_add(step_weight).any_gt(limit) {
+                used.saturating_accrue(step_weight);
+                steps -= 1;
+                if Self::advance(&old_account, 1) == 0
+                    || !crate::AccountMigrations::<T>::contains_key(&old_account)
+                {
+                    break;
+                }
+            }
+        }
+        used
+    }
+
+    /// Migrates up to `steps` categories of `old_account`, stopping at one that does not fit.
+    /// Returns the number of categories migrated.
+    fn advance(old_account: &T::AccountId, steps: u32) -> u32 {
+        let mut taken = 0;
+        while taken < steps {
+            let Some(migration) = crate::AccountMigrations::<T>::get(old_account) else {
+                break;
+            };
+            let category = migration.next_category;
+            let new_account = migration.new_account;
+            if with_storage_layer(|| Self::migrate_category(old_account, &new_account, category)).is_err() {
+                break;
+            }
+            taken += 1;
+
+            Pallet::<T>::deposit_event(Event::AccountMigrationCategoryMigrated {
+                old_account: old_account.clone(),
+                new_account: new_account.clone(),
+                category,
+            });
+            match category.next() {
+                Some(next_category) => crate::AccountMigrations::<T>::insert(old_account, AccountMigration {
+                    new_account,
+                    next_category,
+                }),
+                None => {
+                    crate::AccountMigrations::<T>::remove(old_account);
+                    Pallet::<T>::deposit_event(Event::AccountMigrationCompleted {
+                        old_account: old_account.clone(),
+                        new_account,
+                    });
+                }
+            }
+        }
+        taken
+    }
+
+    /// Checks that the old account's pets, parked pets and achievements fit the new account.
+    fn ensure_room(old_account: &T::AccountId, new_account: &T::AccountId) -> DispatchResult {
+        let pets = crate::OwnerOfPet::<T>::decode_len(old_account).unwrap_or(0)
+            .saturating_add(crate::OwnerOfPet::<T>::decode_len(new_account).unwrap_or(0));
+        let parked = crate::PendingClaimsOf::<T>::decode_len(old_account).unwrap_or(0)
+            .saturating_add(crate::PendingClaimsOf::<T>::decode_len(new_account).unwrap_or(0));
+        let achievements = Self::merged_achievements(old_account, new_account).len();
+        ensure!(
+            pets <= T::MaxOwnedPets::get() as usize
+                && parked <= T::MaxPendingClaims::get() as usize
+                && achievements <= T::MaxAchievements::get() as usize,
+            Error::<T>::AccountMigrationExceedsCapacity
+        );
+        Ok(())
+    }
+
+    fn migrate_category(
+        old_account: &T::AccountId,
+        new_account: &T::AccountId,
+        category: AccountMigrationCategory,
+    ) -> DispatchResult {
+        match category {
+            AccountMigrationCategory::Pets => Self::migrate_pets(old_account, new_account),
+            AccountMigrationCategory::PendingClaims => Self::migrate_pending_claims(old_account, new_account),
+            AccountMigrationCategory::Achievements => Self::migrate_achievements(old_account, new_account),
+            AccountMigrationCategory::Notifications => Self::migrate_notifications(old_account, new_account),
+            AccountMigrationCategory::Cooldowns => Self::migrate_cooldowns(old_account, new_account),
+        }
+    }
+
+    fn migrate_pets(old_account: &T::AccountId, new_account: &T::AccountId) -> DispatchResult {
+        let pets = crate::OwnerOfPet::<T>::take(old_account);
+        crate::OwnerOfPet::<T>::try_mutate(new_account, |owned| -> DispatchResult {
+            for pet_id in pets.iter() {
+                owned.try_push(*pet_id).map_err(|_| Error::<T>::AccountMigrationExceedsCapacity)?;
+            }
+            Ok(())
+        })?;
+
+        for pet_id in pets {
+            // Not a transfer: the pet keeps its `acquired_at_block`.
+            crate::PetNfts::<T>::mutate(pet_id, |pet_opt| {
+                if let Some(pet) = pet_opt {
+                    pet.owner = new_account.clone();
+                }
+            });
+            crate::PetNftOwner::<T>::insert(pet_id, new_account.clone());
+            crate::CareSubscriptions::<T>::mutate(pet_id, |subscription_opt| {
+                if let Some(subscription) = subscription_opt {
+                    if subscription.owner == *old_account {
+                        subscription.owner = new_account.clone();
+                    }
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn migrate_pending_claims(old_account: &T::AccountId, new_account: &T::AccountId) -> DispatchResult {
+        let parked = crate::PendingClaimsOf::<T>::take(old_account);
+        crate::PendingClaimsOf::<T>::try_mutate(new_account, |claims| -> DispatchResult {
+            for pet_id in parked.iter() {
+                claims.try_push(*pet_id).map_err(|_| Error::<T>::AccountMigrationExceedsCapacity)?;
+            }
+            Ok(())
+        })?;
+
+        for pet_id in parked {
+            crate::PendingClaims::<T>::mutate(pet_id, |claim_opt| {
+                if let Some(claim) = claim_opt {
+                    claim.recipient = new_account.clone();
+                    claim.pet.owner = new_account.clone();
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn migrate_achievements(old_account: &T::AccountId, new_account: &T::AccountId) -> DispatchResult {
+        let merged: BoundedVec<UserAchievement, T::MaxAchievements> =
+            Self::merged_achievements(old_account, new_account)
+                .try_into()
+                .map_err(|_| Error::<T>::AccountMigrationExceedsCapacity)?;
+        crate::UserAchievements::<T>::remove(old_account);
+        if !merged.is_empty() {
+            crate::UserAchievements::<T>::insert(new_account, merged);
+        }
+
+        let flow = crate::UserUxFlow::<T>::take(old_account);
+        if crate::UserUxFlow::<T>::get(new_account) == Default::default() {
+            crate::UserUxFlow::<T>::insert(new_account, flow);
+        }
+
+        if let Some(pet_id) = crate::StarterGranted::<T>::take(old_account) {
+            if !crate::StarterGranted::<T>::contains_key(new_account) {
+                crate::StarterGranted::<T>::insert(new_account, pet_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// The new account's achievements followed by the old account's it lacks. An achievement
+    /// both hold keeps the further progress and the earlier unlock.
+    fn merged_achievements(old_account: &T::AccountId, new_account: &T::AccountId) -> Vec<UserAchievement> {
+        let mut merged = crate::UserAchievements::<T>::get(new_account).into_inner();
+        for achievement in crate::UserAchievements::<T>::get(old_account) {
+            match merged.iter_mut().find(|held| held.achievement_id == achievement.achievement_id) {
+                Some(held) => {
+                    held.progress = held.progress.max(achievement.progress);
+                    held.unlock_timestamp = match (held.unlock_timestamp, achievement.unlock_timestamp) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+                    held.unlocked |= achievement.unlocked;
+                }
+                None => merged.push(achievement),
+            }
+        }
+        merged
+    }
+
+    fn migrate_notifications(old_account: &T::AccountId, new_account: &T::AccountId) -> DispatchResult {
+        let old_notifications = crate::UserNotifications::<T>::take(old_account);
+        if !old_notifications.is_empty() {
+            crate::UserNotifications::<T>::mutate(new_account, |inbox| {
+                let mut all: Vec<_> = inbox.drain(..).chain(old_notifications).collect();
+                all.sort_by_key(|notification| notification.notification_id);
+                for notification in all {
+                    push_evicting_by(inbox, notification, |existing| (!existing.read, existing.priority));
+                }
+            });
+        }
+
+        if let Some(settings) = crate::NotificationDigestSettings::<T>::take(old_account) {
+            if !crate::NotificationDigestSettings::<T>::contains_key(new_account) {
+                crate::NotificationDigestSettings::<T>::insert(new_account, settings);
+            }
+        }
+        for (notification_type, count) in crate::PendingDigest::<T>::drain_prefix(old_account) {
+            crate::PendingDigest::<T>::mutate(new_account, notification_type, |pending| {
+                *pending = pending.saturating_add(count)
+            });
+        }
+        Ok(())
+    }
+
+    fn migrate_cooldowns(old_account: &T::AccountId, new_account: &T::AccountId) -> DispatchResult {
+        let last_claim = crate::LastClaimTime::<T>::take(old_account);
+        crate::LastClaimTime::<T>::mutate(new_account, |claimed| *claimed = (*claimed).max(last_claim));
+
+        for (action, window) in crate::RateLimits::<T>::drain_prefix(old_account) {
+            if !crate::RateLimits::<T>::contains_key(new_account, action) {
+                crate::RateLimits::<T>::insert(new_account, action, window);
+            }
+        }
+        Ok(())
+    }
+}
+
@@ -2,6 +2,19 @@
 //!
 //! This module provides a system for pets to earn achievements for reaching various milestones,
 //! encouraging different play styles and giving owners goals to work toward.
+//!
+//! Achievements with one countable goal (memories formed, distinct social partners, or a skill's
+//! level) track each pet's progress in `AchievementProgress`. The actions that advance a goal
+//! update it as they happen, and `AchievementProgressed` is emitted each time the progress crosses
+//! a multiple of `AchievementMilestoneStep`. Checking achievements then only compares the tracked
+//! progress with the target, and is idempotent: earned achievements are skipped.
+//!
+//! Pets that existed before progress was tracked are backfilled lazily, from their stored
+//! memories, skills and recent social partners, the first time an action or a check touches
+//! them. The backfill announces no milestones.
+//!
+//! A check is charged for the achievements it actually evaluated and awarded, see
+//! `AchievementSystem::check_weight`; a pet that has earned everything costs little to check.
 
 use frame_support::{
     dispatch::DispatchResult,
@@ -12,7 +25,9 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use sp_runtime::PerThing;
 use crate::{Config, Error, PetId, PetNft};
+use crate::kinds::SkillKind;
 
 /// Represents an achievement that a pet can earn.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -71,7 +86,7 @@ pub struct AchievementRequirements {
     pub min_stats: Vec<(u8, u8)>, // (stat_type, min_value)
     
     /// The skills required
-    pub required_skills: Vec<(u8, u8)>, // (skill_type, min_level)
+    pub required_skills: Vec<(SkillKind, u8)>, // (skill_type, min_level)
     
     /// The achievements required as prerequisites
     pub prerequisite_achievements: Vec<u32>, // achievement_ids
@@ -86,6 +101,56 @@ pub struct AchievementRequirements {
     pub required_memories: u32,
 }
 
+/// The actions that progress towards a countable goal is tracked from.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProgressSource {
+    /// Memories formed; each stored memory counts, including ones that replace older memories
+    Memories,
+    
+    /// Distinct social partners; an interaction counts if the partner is not among the pet's
+    /// recent interactions
+    SocialPartners,
+    
+    /// A skill's level
+    Skill(SkillKind),
+}
+
+/// How an action changes the progress of the goals on its source.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProgressUpdate {
+    /// Adds to the progress
+    Add(u32),
+    
+    /// Raises the progress to at least the value
+    Raise(u32),
+}
+
+/// A pet's tracked progress towards an achievement.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProgressRecord {
+    /// The progress so far, capped at the achievement's target
+    pub progress: u32,
+    
+    /// The number of milestones reached so far
+    pub milestones: u8,
+}
+
+/// What a check of a pet's achievements did, used to compute its actual weight.
+#[derive(Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct AchievementCheck {
+    /// Whether the pet's progress was backfilled during the check
+    pub backfilled: bool,
+    
+    /// The number of achievements the pet did not have yet, which were evaluated
+    pub evaluated: u32,
+    
+    /// The number of achievements awarded
+    pub awarded: u32,
+}
+
+/// The number of achievements `AchievementSystem` defines, which bounds the work of a check.
+pub const ACHIEVEMENT_COUNT: u32 = 4;
+
 /// Achievement categories.
 pub enum AchievementCategory {
     General = 0,
@@ -112,13 +177,19 @@ impl<T: Config> AchievementSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `DispatchResult` - Ok if successful, Err otherwise
+    /// * `Result<AchievementCheck, DispatchError>` - What the check did, or an error
     pub fn check_achievements(
         pet_id: PetId,
-    ) -> DispatchResult {
+    ) -> Result<AchievementCheck, DispatchError> {
         // Get the pet from storage
         let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
         
+        // Make sure the pet's progress covers what it did before progress was tracked
+        let mut check = AchievementCheck {
+            backfilled: Self::backfill_progress(pet_id),
+            ..Default::default()
+        };
+        
         // Get all available achievements
         let achievements = Self::get_all_achievements();
         
@@ -131,15 +202,67 @@ impl<T: Config> AchievementSystem<T> {
             if current_achievements.iter().any(|(id, _)| *id == achievement.id) {
                 continue;
             }
+            check.evaluated += 1;
             
-            // Check if the pet meets the requirements
-            if Self::meets_requirements(&pet, &achievement.requirements)? {
-                // Award the achievement
+            // Countable goals are met once the tracked progress reaches the target
+            let goal = Self::progress_goal(&achievement.requirements);
+            if let Some((_, target)) = goal {
+                let progress = crate::AchievementProgress::<T>::get(pet_id, achievement.id)
+                    .map_or(0, |record| record.progress);
+                if progress < target {
+                    continue;
+                }
+            }
+            
+            // Check the remaining requirements
+            if Self::meets_requirements(&pet, &achievement.requirements, goal.map(|(source, _)| source))? {
+                // Award the achievement; its progress is no longer needed
                 Self::award_achievement(pet_id, achievement.id, &achievement.rewards)?;
+                crate::AchievementProgress::<T>::remove(pet_id, achievement.id);
+                check.awarded += 1;
             }
         }
         
-        Ok(())
+        Ok(check)
+    }
+    
+    /// The weight of a check that did the work described by `check`.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `check` - What the check did
+    /// 
+    /// # Returns
+    /// 
+    /// * `Weight` - The weight to charge
+    pub fn check_weight(
+        check: &AchievementCheck,
+    ) -> Weight {
+        let db = T::DbWeight::get();
+        // Reads: PetNfts, AchievementProgressBackfilled, PetAchievements.
+        let mut weight = Weight::from_parts(10_000, 0).saturating_add(db.reads(3));
+        if check.backfilled {
+            // Reads: the progress source per achievement. Writes: AchievementProgress per achievement, AchievementProgressBackfilled.
+            weight = weight.saturating_add(db.reads_writes(ACHIEVEMENT_COUNT as u64, ACHIEVEMENT_COUNT as u64 + 1));
+        }
+        // Reads: AchievementProgress and the requirements' sources, per evaluated achievement.
+        weight = weight.saturating_add(
+            Weight::from_parts(10_000, 0).saturating_add(db.reads(2)).saturating_mul(check.evaluated as u64),
+        );
+        // Reads: PetAchievements, PetNfts, PetStorageSize, PetMemories. Writes: the same and AchievementProgress, per award.
+        weight.saturating_add(
+            Weight::from_parts(20_000, 0).saturating_add(db.reads_writes(4, 5)).saturating_mul(check.awarded as u64),
+        )
+    }
+    
+    /// The weight of the most expensive check: a backfill, then every achievement evaluated
+    /// and awarded.
+    pub fn max_check_weight() -> Weight {
+        Self::check_weight(&AchievementCheck {
+            backfilled: true,
+            evaluated: ACHIEVEMENT_COUNT,
+            awarded: ACHIEVEMENT_COUNT,
+        })
     }
     
     /// Gets all available achievements.
@@ -149,7 +272,7 @@ impl<T: Config> AchievementSystem<T> {
     /// * `Vec<Achievement>` - All available achievements
     fn get_all_achievements() -> Vec<Achievement> {
         // In a real implementation, this would get all achievements from storage
-        // For now, we'll just return a few hardcoded achievements
+        // For now, we'll just return a few hardcoded achievements; keep `ACHIEVEMENT_COUNT` in step
         
         vec![
             Achievement {
@@ -193,7 +316,7 @@ impl<T: Config> AchievementSystem<T> {
                     min_level: 0,
                     min_experience: 0,
                     min_stats: Vec::new(),
-                    required_skills: vec![(0, 5)], // Any skill at level 5
+                    required_skills: vec![(SkillKind::Hunting, 5)], // Hunting at level 5
                     prerequisite_achievements: Vec::new(),
                     required_social_interactions: 0,
                     required_environmental_adaptations: 0,
@@ -224,15 +347,213 @@ impl<T: Config> AchievementSystem<T> {
                     required_memories: 0,
                 },
             },
+            Achievement {
+                id: 4,
+                name: b"Memory Keeper".to_vec(),
+                description: b"Form 10 memories.".to_vec(),
+                category: AchievementCategory::General as u8,
+                difficulty: 75,
+                rewards: AchievementRewards {
+                    experience: 150,
+                    mood_boost: 10,
+                    stat_boost: 5,
+                    boosted_stat: 2, // Intelligence
+                    special_ability: 0, // None
+                },
+                requirements: AchievementRequirements {
+                    min_level: 0,
+                    min_experience: 0,
+                    min_stats: Vec::new(),
+                    required_skills: Vec::new(),
+                    prerequisite_achievements: Vec::new(),
+                    required_social_interactions: 0,
+                    required_environmental_adaptations: 0,
+                    required_memories: 10,
+                },
+            },
         ]
     }
     
+    /// Gets the countable goal of an achievement: the source its progress is tracked from, and
+    /// the target. Only achievements with exactly one countable requirement have a goal; the
+    /// others are checked against the pet's state in full.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `requirements` - The achievement requirements
+    /// 
+    /// # Returns
+    /// 
+    /// * `Option<(ProgressSource, u32)>` - The source and target, or `None` if not tracked
+    pub fn progress_goal(
+        requirements: &AchievementRequirements,
+    ) -> Option<(ProgressSource, u32)> {
+        let mut goals = Vec::new();
+        for (skill, level) in requirements.required_skills.iter() {
+            goals.push((ProgressSource::Skill(*skill), *level as u32));
+        }
+        if requirements.required_social_interactions > 0 {
+            goals.push((ProgressSource::SocialPartners, requirements.required_social_interactions));
+        }
+        if requirements.required_memories > 0 {
+            goals.push((ProgressSource::Memories, requirements.required_memories));
+        }
+        
+        if goals.len() == 1 { goals.pop() } else { None }
+    }
+    
+    /// Records that an action advanced a pet's countable goals on `source`, and emits
+    /// `AchievementProgressed` for each goal that crossed a milestone. Called after the action's
+    /// own write. Goals of achievements the pet has earned are left alone.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// * `source` - The kind of action
+    /// * `update` - How the action changes the progress
+    pub fn note_progress(
+        pet_id: PetId,
+        source: ProgressSource,
+        update: ProgressUpdate,
+    ) {
+        let goals: Vec<(u32, u32)> = Self::get_all_achievements()
+            .iter()
+            .filter_map(|achievement| match Self::progress_goal(&achievement.requirements) {
+                Some((goal_source, target)) if goal_source == source => Some((achievement.id, target)),
+                _ => None,
+            })
+            .collect();
+        if goals.is_empty() {
+            return;
+        }
+        
+        // A backfill reads the pet's state after the action, so it already counts it
+        if Self::backfill_progress(pet_id) {
+            return;
+        }
+        
+        let earned = crate::PetAchievements::<T>::get(pet_id);
+        for (achievement_id, target) in goals {
+            if earned.iter().any(|(id, _)| *id == achievement_id) {
+                continue;
+            }
+            
+            let mut record = crate::AchievementProgress::<T>::get(pet_id, achievement_id).unwrap_or_default();
+            let progress = match update {
+                ProgressUpdate::Add(amount) => record.progress.saturating_add(amount),
+                ProgressUpdate::Raise(value) => record.progress.max(value),
+            }
+            .min(target);
+            if progress == record.progress {
+                continue;
+            }
+            
+            record.progress = progress;
+            let milestones = Self::milestones_reached(progress, target);
+            if milestones > record.milestones {
+                record.milestones = milestones;
+                crate::Pallet::<T>::deposit_event(crate::Event::AchievementProgressed {
+                    pet_id,
+                    achievement_id,
+                    progress,
+                    target,
+                });
+            }
+            crate::AchievementProgress::<T>::insert(pet_id, achievement_id, record);
+        }
+    }
+    
+    /// Backfills a pet's progress from its current memories, skills and recent social
+    /// partners, once. Milestones already passed are recorded without events.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// 
+    /// # Returns
+    /// 
+    /// * `bool` - Whether the progress was backfilled now
+    pub fn backfill_progress(
+        pet_id: PetId,
+    ) -> bool {
+        if crate::AchievementProgressBackfilled::<T>::get(pet_id) {
+            return false;
+        }
+        
+        let earned = crate::PetAchievements::<T>::get(pet_id);
+        for achievement in Self::get_all_achievements() {
+            if earned.iter().any(|(id, _)| *id == achievement.id) {
+                continue;
+            }
+            let (source, target) = match Self::progress_goal(&achievement.requirements) {
+                Some(goal) => goal,
+                None => continue,
+            };
+            
+            let progress = Self::current_progress(pet_id, source).min(target);
+            if progress > 0 {
+                crate::AchievementProgress::<T>::insert(pet_id, achievement.id, ProgressRecord {
+                    progress,
+                    milestones: Self::milestones_reached(progress, target),
+                });
+            }
+        }
+        
+        crate::AchievementProgressBackfilled::<T>::insert(pet_id, true);
+        true
+    }
+    
+    /// Measures a pet's progress on `source` from its stored state.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// * `source` - The kind of action
+    /// 
+    /// # Returns
+    /// 
+    /// * `u32` - The stored memories, distinct recent partners or skill level
+    fn current_progress(
+        pet_id: PetId,
+        source: ProgressSource,
+    ) -> u32 {
+        match source {
+            ProgressSource::Memories => crate::PetMemories::<T>::decode_len(pet_id).unwrap_or(0) as u32,
+            ProgressSource::SocialPartners => {
+                let mut partners: Vec<PetId> = Vec::new();
+                for (other_pet_id, _, _, _) in crate::PetSocialInteractions::<T>::get(pet_id).iter() {
+                    if !partners.contains(other_pet_id) {
+                        partners.push(*other_pet_id);
+                    }
+                }
+                partners.len() as u32
+            },
+            ProgressSource::Skill(kind) => crate::PetSkills::<T>::get(pet_id)
+                .iter()
+                .find(|(skill, _)| *skill == kind)
+                .map_or(0, |(_, level)| *level as u32),
+        }
+    }
+    
+    /// The number of milestones, multiples of `AchievementMilestoneStep` below 100%, that
+    /// `progress` has reached towards `target`.
+    pub fn milestones_reached(progress: u32, target: u32) -> u8 {
+        let step = T::AchievementMilestoneStep::get().deconstruct() as u64;
+        if step == 0 || target == 0 {
+            return 0;
+        }
+        let reached = (progress as u64).saturating_mul(100) / step.saturating_mul(target as u64);
+        reached.min(99 / step) as u8
+    }
+    
     /// Checks if a pet meets the requirements for an achievement.
     /// 
     /// # Parameters
     /// 
     /// * `pet` - The pet
     /// * `requirements` - The achievement requirements
+    /// * `tracked` - The source of the achievement's countable goal, whose requirement is met
+    ///   through tracked progress instead and is skipped here
     /// 
     /// # Returns
     /// 
@@ -240,6 +561,7 @@ impl<T: Config> AchievementSystem<T> {
     fn meets_requirements(
         pet: &PetNft<T>,
         requirements: &AchievementRequirements,
+        tracked: Option<ProgressSource>,
     ) -> Result<bool, DispatchError> {
         // Check level requirement
         if pet.level < requirements.min_level {
@@ -267,7 +589,7 @@ impl<T: Config> AchievementSystem<T> {
         }
         
         // Check skill requirements
-        if !requirements.required_skills.is_empty() {
+        if !requirements.required_skills.is_empty() && !matches!(tracked, Some(ProgressSource::Skill(_))) {
             let skills = crate::PetSkills::<T>::get(pet.id);
             
             for (required_skill, min_level) in &requirements.required_skills {
@@ -298,7 +620,7 @@ impl<T: Config> AchievementSystem<T> {
         }
         
         // Check social interaction requirement
-        if requirements.required_social_interactions > 0 {
+        if requirements.required_social_interactions > 0 && tracked != Some(ProgressSource::SocialPartners) {
             let interactions = crate::PetSocialInteractions::<T>::get(pet.id);
             
             // Count unique pets interacted with
@@ -324,7 +646,7 @@ impl<T: Config> AchievementSystem<T> {
         }
         
         // Check memory requirement
-        if requirements.required_memories > 0 {
+        if requirements.required_memories > 0 && tracked != Some(ProgressSource::Memories) {
             let memories = crate::PetMemories::<T>::get(pet.id);
             
             if memories.len() < requirements.required_memories as usize {
@@ -353,6 +675,8 @@ impl<T: Config> AchievementSystem<T> {
     ) -> DispatchResult {
         // Record the achievement
         crate::PetAchievements::<T>::try_mutate(pet_id, |achievements| -> DispatchResult {
+            let size_before = crate::footprint::stored_size(achievements);
+            
             // Get the current block number
             let current_block = frame_system::Pallet::<T>::block_number();
             
@@ -360,6 +684,7 @@ impl<T: Config> AchievementSystem<T> {
             achievements.try_push((achievement_id, current_block.saturated_into::<u64>()))
                 .map_err(|_| Error::<T>::TooManyAchievements)?;
             
+            crate::footprint::FootprintSystem::<T>::note_resize(pet_id, size_before, crate::footprint::stored_size(achievements));
             Ok(())
         })?;
         
@@ -409,12 +734,13 @@ impl<T: Config> AchievementSystem<T> {
             pet_id,
             achievement_id,
             timestamp: current_block,
+            unix_time_ms: crate::Pallet::<T>::unix_time_ms(),
         });
         
         // Record a memory of the achievement
         crate::memory::PetMemorySystem::<T>::record_memory(
             pet_id,
-            crate::memory::MemoryType::Achievement as u8,
+            crate::memory::MemoryType::Achievement,
             200, // High significance
             achievement_id.encode(), // Store the achievement ID as associated data
         )?;
@@ -13,6 +13,7 @@ use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
 use crate::{Config, Error, PetId, PetNft};
+use crate::kinds::{LifecycleEventKind, MemoryKind};
 
 /// Represents an analytics report for a pet.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -306,6 +307,26 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         Ok(encoded_report)
     }
     
+    /// The weight of generating and storing a report of `report_len` bytes. The reads are the
+    /// same for every pet; the proof size grows with the stored report.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `report_len` - The length of the encoded report
+    /// 
+    /// # Returns
+    /// 
+    /// * `Weight` - The weight to charge
+    pub fn report_weight(
+        report_len: u32,
+    ) -> Weight {
+        // Reads: PetNfts, PetSkills, PetAchievements, PetSocialInteractions, PetEnvironmentalAdaptations,
+        // PetLifecycleEvents, PetMemories, PetAnalyticsReports, PetStorageSize.
+        // Writes: PetAnalyticsReports, PetStorageSize.
+        Weight::from_parts(10_000, report_len as u64)
+            .saturating_add(T::DbWeight::get().reads_writes(9, 2))
+    }
+    
     /// Generates basic stats for a pet.
     /// 
     /// # Parameters
@@ -398,13 +419,13 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         // In a real implementation, we would determine the most recent and most frequent skills
         // For now, we'll just use placeholders
         let most_recent_skill = if let Some((skill, _)) = skills.last() {
-            *skill
+            *skill as u8
         } else {
             0
         };
         
         let most_frequent_skill = if let Some((skill, _)) = skills.first() {
-            *skill
+            *skill as u8
         } else {
             0
         };
@@ -495,7 +516,7 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         // In a real implementation, we would determine the most frequent interaction type and outcome
         // For now, we'll just use placeholders
         let most_frequent_interaction_type = if let Some((_, interaction_type, _, _)) = interactions.first() {
-            *interaction_type
+            *interaction_type as u8
         } else {
             0
         };
@@ -560,7 +581,7 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         };
         
         let most_recent_adaptation = if let Some((environment_type, _)) = adaptations.last() {
-            *environment_type
+            *environment_type as u8
         } else {
             0
         };
@@ -592,17 +613,17 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         let event_count = events.len() as u32;
         
         let most_recent_event = if let Some((event_type, _)) = events.last() {
-            *event_type
+            *event_type as u8
         } else {
             0
         };
         
         // Determine the pet's current lifecycle stage
-        let current_stage = if events.iter().any(|(event_type, _)| *event_type == 2) {
+        let current_stage = if events.iter().any(|(event_type, _)| *event_type == LifecycleEventKind::Adulthood) {
             2 // Adulthood
-        } else if events.iter().any(|(event_type, _)| *event_type == 1) {
+        } else if events.iter().any(|(event_type, _)| *event_type == LifecycleEventKind::Adolescence) {
             1 // Adolescence
-        } else if events.iter().any(|(event_type, _)| *event_type == 0) {
+        } else if events.iter().any(|(event_type, _)| *event_type == LifecycleEventKind::Birth) {
             0 // Birth
         } else {
             0 // Default to Birth
@@ -652,27 +673,25 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         for (memory_type, significance, _, _) in memories.iter() {
             if *significance > highest_significance {
                 highest_significance = *significance;
-                most_significant_memory = *memory_type;
+                most_significant_memory = *memory_type as u8;
             }
         }
         
         let most_recent_memory = if let Some((memory_type, _, _, _)) = memories.last() {
-            *memory_type
+            *memory_type as u8
         } else {
             0
         };
         
         // Categorize memories
         let mut categories = Vec::new();
-        let mut category_counts = [0u32; 12]; // 12 memory types
+        let mut category_counts = [0u32; MemoryKind::ALL.len()];
         
         for (memory_type, _, _, _) in memories.iter() {
-            if *memory_type < 12 {
-                category_counts[*memory_type as usize] += 1;
-            }
+            category_counts[*memory_type as usize] += 1;
         }
         
-        for i in 0..12 {
+        for i in 0..category_counts.len() {
             if category_counts[i] > 0 {
                 categories.push((i as u8, category_counts[i]));
             }
@@ -803,6 +822,8 @@ impl<T: Config> PetAnalyticsDashboard<T> {
         let bounded_report: BoundedVec<u8, T::MaxAnalyticsReportSize> = 
             report.to_vec().try_into().map_err(|_| Error::<T>::AnalyticsReportTooLarge)?;
         
+        let size_before = crate::footprint::blob_size(crate::PetAnalyticsReports::<T>::decode_len(pet_id).unwrap_or(0));
+        crate::footprint::FootprintSystem::<T>::note_resize(pet_id, size_before, crate::footprint::blob_size(bounded_report.len()));
         crate::PetAnalyticsReports::<T>::insert(pet_id, bounded_report);
         
         Ok(())
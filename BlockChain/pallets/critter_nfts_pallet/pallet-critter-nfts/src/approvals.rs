@@ -0,0 +1,115 @@
+//! # Transfer Approvals
+//!
+//! ERC-721-style approvals let marketplaces and custodial game services move pets without
+//! owning them. An owner approves one account to transfer a pet with `approve_transfer`
+//! (replacing any earlier approval, recorded in `PetApprovals`), or approves an operator for all
+//! of their pets with `set_operator` (recorded in `OperatorApprovals`).
+//!
+//! The approved account or an operator can then transfer the pet with `transfer_pet_nft`, or
+//! through `SharedNftManager::transfer_nft` by passing itself as `from`. The pet moves from its
+//! owner, with the same checks as an owner's transfer.
+//!
+//! A pet's approval is cleared when the pet changes hands or is locked, so an approval never
+//! outlives the owner who gave it and a locked pet cannot be moved by an approval given before
+//! the lock. Operator approvals belong to the owner's account and stay until the owner clears
+//! them.
+
+use frame_support::{dispatch::DispatchResult, ensure};
+use crate::{Config, Error, Event, Pallet, PetId};
+
+/// A system for approving other accounts to transfer pets.
+pub struct ApprovalSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> ApprovalSystem<T> {
+    /// Approves `delegate` to transfer a pet, replacing any approval it has.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account approving; must own the pet
+    /// * `pet_id` - The ID of the pet
+    /// * `delegate` - The account approved to transfer the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the approval was recorded, Err if the pet is locked
+    pub fn approve(owner: T::AccountId, pet_id: PetId, delegate: T::AccountId) -> DispatchResult {
+        Pallet::<T>::owned_pet(&owner, pet_id)?;
+        ensure!(delegate != owner, Error::<T>::CannotApproveOwner);
+        ensure!(!crate::LockedNfts::<T>::contains_key(pet_id), Error::<T>::NftLocked);
+
+        crate::PetApprovals::<T>::insert(pet_id, delegate.clone());
+        Pallet::<T>::deposit_event(Event::TransferApproved { owner, pet_id, delegate });
+        Ok(())
+    }
+
+    /// Revokes a pet's approval.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account that gave the approval; must own the pet
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the approval was revoked, Err if there is none or the caller
+    ///   does not own the pet
+    pub fn revoke(owner: T::AccountId, pet_id: PetId) -> DispatchResult {
+        Pallet::<T>::owned_pet(&owner, pet_id)?;
+        let delegate = crate::PetApprovals::<T>::take(pet_id).ok_or(Error::<T>::ApprovalNotFound)?;
+
+        Pallet::<T>::deposit_event(Event::ApprovalRevoked { owner, pet_id, delegate });
+        Ok(())
+    }
+
+    /// Approves or unapproves `operator` to transfer every pet of `owner`.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account approving
+    /// * `operator` - The account approved or unapproved
+    /// * `approved` - Whether the operator is approved
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the operator was set, Err if it is the owner
+    pub fn set_operator(owner: T::AccountId, operator: T::AccountId, approved: bool) -> DispatchResult {
+        ensure!(operator != owner, Error::<T>::CannotApproveOwner);
+
+        if approved {
+            crate::OperatorApprovals::<T>::insert(&owner, &operator, ());
+        } else {
+            crate::OperatorApprovals::<T>::remove(&owner, &operator);
+        }
+        Pallet::<T>::deposit_event(Event::OperatorSet { owner, operator, approved });
+        Ok(())
+    }
+
+    /// Checks whether `who` may transfer a pet `owner` owns: as its owner, its approved account,
+    /// or an operator of the owner.
+    ///
+    /// # Parameters
+    ///
+    /// * `who` - The account initiating the transfer
+    /// * `owner` - The pet's owner
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether `who` may transfer the pet
+    pub fn can_transfer(who: &T::AccountId, owner: &T::AccountId, pet_id: PetId) -> bool {
+        who == owner
+            || crate::PetApprovals::<T>::get(pet_id).as_ref() == Some(who)
+            || crate::OperatorApprovals::<T>::contains_key(owner, who)
+    }
+
+    /// Clears a pet's approval, when it changes hands or is locked.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    pub fn clear(pet_id: PetId) {
+        crate::PetApprovals::<T>::remove(pet_id);
+    }
+}
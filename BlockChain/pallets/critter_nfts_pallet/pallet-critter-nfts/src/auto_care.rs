@@ -0,0 +1,265 @@
+//! # Auto-Care Subscriptions
+//!
+//! This module lets owners prepay PTCN for automated care of a pet. The prepayment is held
+//! in the pallet's sovereign account; anyone (typically an off-chain worker) can execute care
+//! once it is due and is paid a small bounty out of the subscription's fee. Automated care
+//! goes through the same item-less care helpers as manual care, at a reduced effect, and is
+//! reported by `CareActionPerformed` with the executor as the actor.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    pallet_prelude::RuntimeDebug,
+    traits::{Currency, ExistenceRequirement, Get, WithdrawReasons},
+};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{AccountIdConversion, CheckedMul, Saturating, Zero};
+use sp_std::vec::Vec;
+use crate::care_events::CareEventSystem;
+use crate::donations::DonationSystem;
+use crate::kinds::CareActionKind;
+use crate::{BalanceOf, Config, Error, Event, Pallet, PetId};
+
+/// Care kind flag: automated feeding.
+pub const AUTO_CARE_FEED: u8 = 1 << 0;
+
+/// Care kind flag: automated play.
+pub const AUTO_CARE_PLAY: u8 = 1 << 1;
+
+/// All care kinds that can be subscribed to.
+pub const AUTO_CARE_ALL: u8 = AUTO_CARE_FEED | AUTO_CARE_PLAY;
+
+/// A pet's auto-care subscription.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CareSubscription<AccountId, Balance, BlockNumber> {
+    /// The account that paid for the subscription and receives any refund
+    pub owner: AccountId,
+
+    /// The fee charged per executed interval, fixed at subscription time
+    pub fee_per_interval: Balance,
+
+    /// The subscribed care kinds (bitmask of `AUTO_CARE_*` flags)
+    pub kinds: u8,
+
+    /// The block from which the next care can be executed
+    pub next_due_block: BlockNumber,
+
+    /// The prepaid balance remaining in the pallet account for this subscription
+    pub prepaid_balance: Balance,
+
+    /// Whether care is still being executed; lapsed subscriptions only hold a refund
+    pub active: bool,
+}
+
+/// The subscription type stored for a runtime.
+pub type CareSubscriptionOf<T> = CareSubscription<
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
+/// A system for managing auto-care subscriptions.
+pub struct AutoCareSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> AutoCareSystem<T> {
+    /// The pallet's sovereign account, which holds all prepaid balances.
+    pub fn account_id() -> T::AccountId {
+        T::AutoCarePalletId::get().into_account_truncating()
+    }
+
+    /// Subscribes a pet to automated care, prepaying for a number of intervals.
+    /// A lapsed subscription on the pet is refunded to its owner and replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The owner of the pet, who pays the prepayment
+    /// * `pet_id` - The ID of the pet
+    /// * `kinds` - The care kinds to perform (bitmask of `AUTO_CARE_*` flags)
+    /// * `intervals_prepaid` - The number of intervals to prepay
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the subscription was created, Err otherwise
+    pub fn subscribe(
+        owner: T::AccountId,
+        pet_id: PetId,
+        kinds: u8,
+        intervals_prepaid: u32,
+    ) -> DispatchResult {
+        // 1. Validate the request.
+        ensure!(crate::PetNftOwner::<T>::get(pet_id) == Some(owner.clone()), Error::<T>::NotOwner);
+        ensure!(kinds != 0 && kinds & !AUTO_CARE_ALL == 0, Error::<T>::InvalidAutoCareKinds);
+        ensure!(intervals_prepaid > 0, Error::<T>::InvalidAutoCarePrepayment);
+
+        // 2. Refund and replace a lapsed subscription; reject an active one.
+        if let Some(existing) = crate::CareSubscriptions::<T>::get(pet_id) {
+            ensure!(!existing.active, Error::<T>::AutoCareAlreadySubscribed);
+            Self::refund(&existing)?;
+        }
+
+        // 3. Move the prepayment into the pallet account.
+        let fee_per_interval = T::AutoCareFee::get();
+        let prepaid_balance = fee_per_interval
+            .checked_mul(&BalanceOf::<T>::from(intervals_prepaid))
+            .ok_or(Error::<T>::InvalidAutoCarePrepayment)?;
+        T::Currency::transfer(&owner, &Self::account_id(), prepaid_balance, ExistenceRequirement::KeepAlive)?;
+
+        // 4. Store the subscription; the first care is due one interval from now.
+        let next_due_block = frame_system::Pallet::<T>::block_number().saturating_add(T::AutoCareInterval::get());
+        crate::CareSubscriptions::<T>::insert(pet_id, CareSubscription {
+            owner: owner.clone(),
+            fee_per_interval,
+            kinds,
+            next_due_block,
+            prepaid_balance,
+            active: true,
+        });
+
+        // 5. Emit event.
+        Pallet::<T>::deposit_event(Event::AutoCareSubscribed {
+            owner: owner.clone(),
+            pet_id,
+            kinds,
+            prepaid_balance,
+            next_due_block,
+        });
+
+        // 6. Donate the owner's round-up on the prepayment; this never fails the subscription.
+        DonationSystem::<T>::donate_on_fee(&owner, prepaid_balance);
+
+        Ok(())
+    }
+
+    /// Performs due care for a pet and pays the executor's bounty.
+    /// The rest of the interval fee is burned. The subscription lapses once its balance cannot
+    /// cover another interval, or if the pet has changed hands.
+    ///
+    /// # Parameters
+    ///
+    /// * `executor` - The account executing the care, who receives the bounty
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if care was executed or the subscription lapsed, Err otherwise
+    pub fn execute_due_care(executor: T::AccountId, pet_id: PetId) -> DispatchResult {
+        let mut subscription = crate::CareSubscriptions::<T>::get(pet_id)
+            .ok_or(Error::<T>::AutoCareSubscriptionNotFound)?;
+        ensure!(subscription.active, Error::<T>::AutoCareInactive);
+
+        // 1. Check the care is due.
+        let current_block = frame_system::Pallet::<T>::block_number();
+        ensure!(current_block >= subscription.next_due_block, Error::<T>::AutoCareNotDue);
+
+        // 2. Lapse instead of caring for a pet the subscriber no longer owns.
+        if crate::PetNftOwner::<T>::get(pet_id) != Some(subscription.owner.clone()) {
+            Self::lapse(pet_id, &mut subscription);
+            return Ok(());
+        }
+
+        // 3. Perform the subscribed care at reduced effect.
+        let effect = T::AutoCareEffect::get();
+        let outcomes = crate::PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| -> Result<_, DispatchError> {
+            let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+            let mut outcomes = Vec::new();
+            if subscription.kinds & AUTO_CARE_FEED != 0 {
+                outcomes.push((CareActionKind::Feed, Pallet::<T>::apply_feed(pet, effect)?));
+            }
+            if subscription.kinds & AUTO_CARE_PLAY != 0 {
+                outcomes.push((CareActionKind::Play, Pallet::<T>::apply_play(pet, effect)?));
+            }
+            Ok(outcomes)
+        })?;
+        for (kind, outcome) in outcomes {
+            CareEventSystem::<T>::emit(pet_id, executor.clone(), kind, None, outcome, None);
+        }
+
+        // 4. Charge the fee: the bounty goes to the executor and the remainder is burned.
+        let fee = subscription.fee_per_interval;
+        let bounty = T::AutoCareBounty::get().min(fee);
+        let account = Self::account_id();
+        if !bounty.is_zero() {
+            T::Currency::transfer(&account, &executor, bounty, ExistenceRequirement::AllowDeath)?;
+        }
+        let burned = fee.saturating_sub(bounty);
+        if !burned.is_zero() {
+            let _ = T::Currency::withdraw(&account, burned, WithdrawReasons::FEE, ExistenceRequirement::AllowDeath)?;
+        }
+
+        // 5. Advance the schedule from now, so missed intervals are not executed back to back.
+        subscription.prepaid_balance = subscription.prepaid_balance.saturating_sub(fee);
+        subscription.next_due_block = current_block.saturating_add(T::AutoCareInterval::get());
+
+        Pallet::<T>::deposit_event(Event::AutoCareExecuted {
+            pet_id,
+            executor,
+            kinds: subscription.kinds,
+            fee,
+            bounty,
+            next_due_block: subscription.next_due_block,
+        });
+
+        // 6. Lapse once the balance cannot cover another interval.
+        if subscription.prepaid_balance < subscription.fee_per_interval {
+            Self::lapse(pet_id, &mut subscription);
+        } else {
+            crate::CareSubscriptions::<T>::insert(pet_id, subscription);
+        }
+
+        Ok(())
+    }
+
+    /// Cancels a subscription and refunds its remaining balance to the subscriber.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The subscriber
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the subscription was cancelled, Err otherwise
+    pub fn cancel(owner: T::AccountId, pet_id: PetId) -> DispatchResult {
+        let subscription = crate::CareSubscriptions::<T>::get(pet_id)
+            .ok_or(Error::<T>::AutoCareSubscriptionNotFound)?;
+        ensure!(subscription.owner == owner, Error::<T>::NotOwner);
+
+        Self::refund(&subscription)?;
+        crate::CareSubscriptions::<T>::remove(pet_id);
+
+        Pallet::<T>::deposit_event(Event::AutoCareCancelled {
+            owner,
+            pet_id,
+            refunded: subscription.prepaid_balance,
+        });
+
+        Ok(())
+    }
+
+    /// Returns a subscription's remaining balance to its owner.
+    fn refund(subscription: &CareSubscriptionOf<T>) -> DispatchResult {
+        if subscription.prepaid_balance.is_zero() {
+            return Ok(());
+        }
+        T::Currency::transfer(
+            &Self::account_id(),
+            &subscription.owner,
+            subscription.prepaid_balance,
+            ExistenceRequirement::AllowDeath,
+        )
+    }
+
+    /// Marks a subscription inactive, keeping its balance for a refund.
+    fn lapse(pet_id: PetId, subscription: &mut CareSubscriptionOf<T>) {
+        subscription.active = false;
+        crate::CareSubscriptions::<T>::insert(pet_id, subscription.clone());
+
+        Pallet::<T>::deposit_event(Event::AutoCareLapsed {
+            pet_id,
+            remaining_balance: subscription.prepaid_balance,
+        });
+    }
+}
@@ -0,0 +1,63 @@
+//! # Batch Transfers
+//!
+//! `batch_transfer_pet_nfts` moves up to `MaxBatchTransferSize` of an owner's pets to one
+//! recipient in a single call, e.g. a whole collection to a cold wallet. Every pet is checked
+//! before any moves, as `transfer_pet_nft` would check it: owned by the sender, unlocked and past
+//! the transfer gates. The recipient must also have room for the whole batch. So the call is
+//! all-or-nothing: one locked pet fails it and no pet changes hands.
+//!
+//! Each moved pet gets a `PetNftTransferred` event, followed by one `BatchTransferCompleted`.
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use sp_std::vec::Vec;
+use crate::transfer_gates::TransferGateSystem;
+use crate::{Config, Error, Event, Pallet, PetId};
+
+/// A system for transferring several pets at once.
+pub struct BatchTransferSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> BatchTransferSystem<T> {
+    /// Transfers `pet_ids` from `from` to `to`, all of them or none.
+    ///
+    /// # Parameters
+    ///
+    /// * `from` - The account sending the pets; must own every one of them
+    /// * `to` - The account receiving the pets
+    /// * `pet_ids` - The pets to transfer
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if every pet was transferred, Err if any pet cannot be
+    pub fn transfer(from: T::AccountId, to: T::AccountId, pet_ids: Vec<PetId>) -> DispatchResult {
+        // 1. Validate the list.
+        ensure!(from != to, Error::<T>::CannotTransferToSelf);
+        ensure!(!pet_ids.is_empty(), Error::<T>::EmptyBatchTransfer);
+        ensure!(pet_ids.len() as u32 <= T::MaxBatchTransferSize::get(), Error::<T>::BatchTransferTooLarge);
+        for (index, pet_id) in pet_ids.iter().enumerate() {
+            ensure!(!pet_ids[index + 1..].contains(pet_id), Error::<T>::DuplicatePetInBatch);
+        }
+
+        // 2. Check every pet and the recipient's room before anything moves.
+        for pet_id in &pet_ids {
+            Pallet::<T>::owned_pet(&from, *pet_id)?;
+            ensure!(!crate::LockedNfts::<T>::contains_key(pet_id), Error::<T>::NftLocked);
+            TransferGateSystem::<T>::ensure_passes(*pet_id)?;
+        }
+        ensure!(
+            Pallet::<T>::remaining_pet_capacity(&to) >= pet_ids.len() as u32,
+            Error::<T>::RecipientExceedMaxOwnedPets
+        );
+
+        // 3. Move the pets.
+        let count = pet_ids.len() as u32;
+        for pet_id in pet_ids {
+            Pallet::<T>::do_transfer_nft(&from, &to, &pet_id)?;
+            Pallet::<T>::deposit_event(Event::PetNftTransferred { from: from.clone(), to: to.clone(), pet_id });
+        }
+
+        Pallet::<T>::deposit_event(Event::BatchTransferCompleted { from, to, count });
+        Ok(())
+    }
+}
@@ -5,308 +5,903 @@
 //! which are then used to generate accurate dispatch weights for transaction fees
 //! and to ensure the economic integrity of the CritterChain network.
 //!
-//! Run `cargo test --features=runtime-benchmarks` and `cargo benchmark` to generate weights.
+//! Each benchmark sets up its worst case: the caller or recipient one pet short of
+//! `MaxOwnedPets`, names and traits at their maximum lengths, and a care action that levels the
+//! pet up. `batch_mint_pet_nfts` is measured over the batch size, up to `MaxOwnedPets`, so its
+//! weight scales linearly with the pets minted.
+//!
+//! The care benchmarks consume items through the runtime's `ItemHandler`, which must let the
+//! whitelisted caller use item 1 as food and item 2 as a toy in a benchmark build.
+//!
+//! Run `cargo test --features=runtime-benchmarks` to check the benchmarks against the mock, and
+//! see `weights.rs` for how to regenerate the weights from them.
 //! Meticulously crafted to align with The Architect's vision for
 //! performance optimization and resource management in the CritterCraft digital ecosystem.
 
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use frame_benchmarking::{benchmarks, whitelisted_caller, account};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::traits::{Currency, EnsureOrigin, Get, Hooks};
 use frame_system::RawOrigin;
+use crittercraft_traits::LockerId;
+use crate::auto_care::AUTO_CARE_ALL;
+use crate::donations::DonationTarget;
+use crate::input_catalog::InputKind;
+use crate::kinds::{CareActionKind, EnvironmentKind};
+use crate::pending_claims::PendingClaimSystem;
+use crate::species_care::CarePreferences;
+use crate::sync::SyncFlags;
+use crate::traits::{ItemId, TraitTypeString};
+use sp_runtime::{traits::Zero, Perbill};
 use sp_std::prelude::*;
-use frame_support::traits::Get;
 
 // Helper functions and constants
 const SEED: u32 = 0;
 
-fn get_account<T: Config>(name: &'static str) -> T::AccountId {
-    account(name, SEED, 0)
+fn get_species<T: Config>() -> Vec<u8> {
+    vec![b'C'; T::MaxSpeciesNameLen::get() as usize]
+}
+
+fn get_name<T: Config>() -> Vec<u8> {
+    vec![b'N'; T::MaxPetNameLen::get() as usize]
 }
 
-fn get_bounded_species<T: Config>() -> BoundedVec<u8, T::MaxSpeciesNameLen> {
-    vec![b'C'; T::MaxSpeciesNameLen::get() as usize].try_into().unwrap()
+/// A full set of personality traits, each at the maximum trait length, starting with `letter`.
+fn get_bounded_traits<T: Config>(letter: u8) -> BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits> {
+    let trait_len = T::MaxTraitStringLen::get() as usize;
+    (0..T::MaxPetPersonalityTraits::get())
+        .map(|index| {
+            let mut trait_string = vec![letter; trait_len];
+            trait_string[0] = b'a' + (index % 26) as u8;
+            trait_string.try_into().unwrap()
+        })
+        .collect::<Vec<TraitTypeString>>()
+        .try_into()
+        .unwrap()
 }
 
-fn get_bounded_name<T: Config>() -> BoundedVec<u8, T::MaxPetNameLen> {
-    vec![b'N'; T::MaxPetNameLen::get() as usize].try_into().unwrap()
+/// Mints `count` pets for `owner` and returns the ID of the last one.
+fn mint_pets<T: Config>(owner: &T::AccountId, count: u32) -> Result<PetId, &'static str> {
+    for _ in 0..count {
+        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(owner.clone()).into(), get_species::<T>(), get_name::<T>())
+            .map_err(|_| "minting a benchmark pet failed")?;
+    }
+    Ok(NextPetId::<T>::get().saturating_sub(1))
 }
 
-fn get_bounded_traits<T: Config>() -> BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits> {
-    let max_traits = T::MaxPetPersonalityTraits::get() as usize;
-    let trait_strings = [
-        "Brave", "Clever", "Energetic", "Friendly", "Gentle", "Happy", "Kind",
-        "Loyal", "Mighty", "Nimble", "Obedient", "Patient", "Quiet", "Rambunctious",
-        "Sociable", "Tough", "Understanding", "Vivacious", "Wise", "Xenial", "Yearning", "Zealous"
-    ];
-
-    let mut traits: Vec<TraitTypeString> = Vec::new();
-    for i in 0..max_traits {
-        let trait_str = trait_strings[i % trait_strings.len()];
-        let bounded_trait = trait_str.as_bytes().to_vec().try_into().unwrap();
-        traits.try_push(bounded_trait).unwrap();
-    }
-    traits.try_into().unwrap()
+/// Gives a pet just enough XP that the next care action levels it up.
+fn set_up_level_up<T: Config>(pet_id: PetId) {
+    PetNfts::<T>::mutate(pet_id, |pet_opt| {
+        if let Some(pet) = pet_opt {
+            pet.experience_points = crate::leveling::LevelingSystem::<T>::xp_to_next_level(pet.level);
+        }
+    });
+}
+
+/// A bio of the maximum length, made of `letter`.
+fn get_bio<T: Config>(letter: u8) -> BoundedVec<u8, T::MaxPetBioLen> {
+    vec![letter; T::MaxPetBioLen::get() as usize].try_into().unwrap()
+}
+
+/// Gives `who` enough to prepay auto-care and pay fees many times over.
+fn fund<T: Config>(who: &T::AccountId) {
+    let amount = (T::Currency::minimum_balance() + T::AutoCareFee::get()) * 1000u32.into();
+    T::Currency::make_free_balance_be(who, amount);
+}
+
+/// Puts `who` in digest mode with a notification pending for every type, due for flushing.
+fn set_up_digest<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+    Pallet::<T>::set_notification_digest(RawOrigin::Signed(who.clone()).into(), true, 1u32.into())
+        .map_err(|_| "enabling the benchmark digest failed")?;
+    for notification_type in 0..8u8 {
+        PendingDigest::<T>::insert(who, notification_type, u32::MAX);
+    }
+    let due = frame_system::Pallet::<T>::block_number() + 1u32.into();
+    frame_system::Pallet::<T>::set_block_number(due);
+    Ok(())
+}
+
+/// Parks a pet bred from two fresh parents for `recipient`, and returns its ID.
+fn park_pet<T: Config>(recipient: &T::AccountId) -> Result<PetId, &'static str> {
+    let breeder: T::AccountId = account("breeder", 0, SEED);
+    let parent2_id = mint_pets::<T>(&breeder, 2)?;
+    let mut pet = PetNfts::<T>::get(parent2_id).ok_or("benchmark parent missing")?;
+    let pet_id = NextPetId::<T>::get();
+    NextPetId::<T>::put(pet_id + 1);
+    pet.id = pet_id;
+    pet.owner = recipient.clone();
+    PendingClaimSystem::<T>::park(recipient, pet, (parent2_id - 1, parent2_id))
+        .map_err(|_| "parking a benchmark pet failed")?;
+    Ok(pet_id)
 }
 
 benchmarks! {
     // Benchmark for minting a pet NFT
     mint_pet_nft {
         let caller: T::AccountId = whitelisted_caller();
-        let species = get_bounded_species::<T>();
-        let name = get_bounded_name::<T>();
-        
-        // Fill caller's pet collection to near capacity
-        let max_owned_pets = T::MaxOwnedPets::get();
-        for i in 0..max_owned_pets - 1 {
-            Pallet::<T>::mint_pet_nft(
-                RawOrigin::Signed(caller.clone()).into(),
-                species.clone(),
-                name.clone(),
-            )?;
-        }
-        
-        // Ensure caller has enough balance for existential deposit
         T::Currency::deposit_creating(&caller, T::Currency::minimum_balance());
+
+        // Fill the caller's collection to one short of capacity
+        let max_owned_pets = T::MaxOwnedPets::get();
+        mint_pets::<T>(&caller, max_owned_pets - 1)?;
     }: {
-        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), species, name)?;
+        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), get_species::<T>(), get_name::<T>())?;
     }
     verify {
-        let pet_id = max_owned_pets - 1;
-        assert_eq!(<NextPetId<T>>::get(), max_owned_pets);
-        assert!(<PetNfts<T>>::contains_key(pet_id));
-        assert!(<OwnerOfPet<T>>::get(&caller).contains(&pet_id));
-        assert_eq!(<PetNftOwner<T>>::get(pet_id), Some(caller));
-        
-        // Verify pet attributes
-        let pet = <PetNfts<T>>::get(pet_id).unwrap();
-        assert_eq!(pet.current_pet_name, name);
-        assert_eq!(pet.initial_species, species);
-        assert_eq!(pet.mood_indicator, T::MaxMoodValue::get());
+        let pet_id = NextPetId::<T>::get() - 1;
+        assert_eq!(OwnerOfPet::<T>::get(&caller).len() as u32, max_owned_pets);
+        assert_eq!(PetNftOwner::<T>::get(pet_id), Some(caller));
+
+        let pet = PetNfts::<T>::get(pet_id).unwrap();
+        assert_eq!(pet.current_pet_name.to_vec(), get_name::<T>());
+        assert_eq!(pet.initial_species.to_vec(), get_species::<T>());
         assert_eq!(pet.level, 1);
-        assert_eq!(pet.experience_points, 0);
-        assert_eq!(pet.base_strength, 5); // Default base stats
-        assert_eq!(pet.base_agility, 5);
-        assert_eq!(pet.base_intelligence, 5);
-        assert_eq!(pet.base_vitality, 5);
-        assert_eq!(pet.primary_elemental_affinity, ElementType::Neutral);
     }
 
-    // Benchmark for transferring a pet NFT
     // Benchmark for transferring a pet NFT
     transfer_pet_nft {
         let caller: T::AccountId = whitelisted_caller();
-        let recipient: T::AccountId = account("recipient", SEED, 0);
-        let species = get_bounded_species::<T>();
-        let name = get_bounded_name::<T>();
-        
-        // Setup: Fill both caller's and recipient's pet collections to near capacity
-        let max_owned_pets = T::MaxOwnedPets::get();
-
-        // Mint pets for caller
-        for i in 0..max_owned_pets {
-            Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), species.clone(), name.clone())?;
-        }
-        let pet_id_to_transfer = 0u32;
-
-        // Fill recipient's collection
-        for i in 0..max_owned_pets - 1 {
-            let dummy_creator: T::AccountId = account("dummy_creator", i, SEED);
-            Pallet::<T>::mint_pet_nft(RawOrigin::Signed(dummy_creator.clone()).into(), species.clone(), name.clone())?;
-            let dummy_pet_id = NextPetId::<T>::get().saturating_sub(1);
-            Pallet::<T>::transfer_pet_nft(RawOrigin::Signed(dummy_creator).into(), recipient.clone(), dummy_pet_id)?;
-        }
-        
-        // Unlock the pet for transfer
-        <LockedNfts<T>>::remove(pet_id_to_transfer);
-        
-        // Ensure recipient has enough balance for existential deposit
+        let recipient: T::AccountId = account("recipient", 0, SEED);
         T::Currency::deposit_creating(&recipient, T::Currency::minimum_balance());
+
+        // Both collections are near capacity, so both ownership lists are as long as they get
+        let max_owned_pets = T::MaxOwnedPets::get();
+        let pet_id = mint_pets::<T>(&caller, max_owned_pets)?;
+        mint_pets::<T>(&recipient, max_owned_pets - 1)?;
     }: {
-        Pallet::<T>::transfer_pet_nft(RawOrigin::Signed(caller.clone()).into(), recipient.clone(), pet_id_to_transfer)?;
+        Pallet::<T>::transfer_pet_nft(RawOrigin::Signed(caller.clone()).into(), recipient.clone(), pet_id)?;
     }
     verify {
-        assert_eq!(<PetNftOwner<T>>::get(pet_id_to_transfer), Some(recipient));
-        assert!(!<OwnerOfPet<T>>::get(&caller).contains(&pet_id_to_transfer));
-        assert!(<OwnerOfPet<T>>::get(&recipient).contains(&pet_id_to_transfer));
-        
-        // Verify pet state
-        let pet = <PetNfts<T>>::get(pet_id_to_transfer).unwrap();
-        assert_eq!(pet.last_state_update_block, frame_system::Pallet::<T>::block_number());
-        assert_eq!(pet.last_fed_block, frame_system::Pallet::<T>::block_number());
-        assert_eq!(pet.last_played_block, frame_system::Pallet::<T>::block_number());
+        assert_eq!(PetNftOwner::<T>::get(pet_id), Some(recipient.clone()));
+        assert!(!OwnerOfPet::<T>::get(&caller).contains(&pet_id));
+        assert!(OwnerOfPet::<T>::get(&recipient).contains(&pet_id));
     }
 
-    // Benchmark for updating pet metadata
     // Benchmark for updating pet metadata
     update_pet_metadata {
         let caller: T::AccountId = whitelisted_caller();
-        let species = get_bounded_species::<T>();
-        let name = get_bounded_name::<T>();
-        
-        // Mint a pet for the caller
-        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), species, name)?;
-        let pet_id = 0u32;
-        
-        // Prepare worst-case inputs
-        let new_name = Some(vec![b'X'; T::MaxPetNameLen::get() as usize]);
-        let new_traits = Some(get_bounded_traits::<T>());
-        
-        // Ensure pet has maximum traits before update
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+
+        // The pet already has a full set of traits, all of which are replaced
         PetNfts::<T>::mutate(pet_id, |pet_opt| {
             if let Some(pet) = pet_opt {
-                pet.personality_traits = new_traits.clone().unwrap();
+                pet.personality_traits = get_bounded_traits::<T>(b'O');
             }
         });
+        let new_name = vec![b'X'; T::MaxPetNameLen::get() as usize];
+        let new_traits = get_bounded_traits::<T>(b'Y');
+        let expected_version = PetStateVersions::<T>::get(pet_id);
     }: {
-        Pallet::<T>::update_pet_metadata(RawOrigin::Signed(caller), pet_id, new_name, new_traits)?;
+        Pallet::<T>::update_pet_metadata(
+            RawOrigin::Signed(caller).into(),
+            pet_id,
+            Some(new_name.clone()),
+            Some(new_traits.clone()),
+            expected_version,
+        )?;
     }
     verify {
-        let pet = <PetNfts<T>>::get(pet_id).unwrap();
-        assert_eq!(pet.current_pet_name, new_name.unwrap().try_into().unwrap());
-        assert_eq!(pet.personality_traits, new_traits.unwrap());
-        assert_eq!(pet.last_state_update_block, frame_system::Pallet::<T>::block_number());
-        
-        // Verify trait lengths
-        for trait_str in pet.personality_traits.iter() {
-            assert!(trait_str.len() <= T::MaxTraitStringLen::get() as usize);
-        }
+        let pet = PetNfts::<T>::get(pet_id).unwrap();
+        assert_eq!(pet.current_pet_name.to_vec(), new_name);
+        assert_eq!(pet.personality_traits, new_traits);
+        assert_eq!(PetStateVersions::<T>::get(pet_id), expected_version + 1);
     }
 
-    // Benchmark for claiming daily PTCN
     // Benchmark for claiming daily PTCN
     claim_daily_ptcn {
         let caller: T::AccountId = whitelisted_caller();
-        
-        // Setup: Ensure caller can claim
-        <LastClaimTime<T>>::insert(
-            &caller,
-            frame_system::Pallet::<T>::block_number().saturating_sub(T::ClaimCooldownPeriod::get() + 1u32.into())
-        );
-        
-        // Ensure caller has enough balance for existential deposit
         T::Currency::deposit_creating(&caller, T::Currency::minimum_balance());
+
+        // The caller claimed once before, exactly one cooldown ago
+        let now = T::ClaimCooldownPeriod::get() + 1u32.into();
+        frame_system::Pallet::<T>::set_block_number(now);
+        LastClaimTime::<T>::insert(&caller, now - T::ClaimCooldownPeriod::get());
     }: {
         Pallet::<T>::claim_daily_ptcn(RawOrigin::Signed(caller.clone()).into())?;
     }
     verify {
-        let balance = T::Currency::total_balance(&caller);
-        assert!(balance > 0u32.into());
-        assert_eq!(<LastClaimTime<T>>::get(&caller), frame_system::Pallet::<T>::block_number());
-        assert_eq!(balance, T::DailyClaimAmount::get());
-        
-        // Verify no double claim
-        let result = Pallet::<T>::claim_daily_ptcn(RawOrigin::Signed(caller).into());
-        assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), Error::<T>::ClaimCooldownNotMet);
+        assert_eq!(LastClaimTime::<T>::get(&caller), now);
     }
 
-    // Benchmark for feeding a pet
     // Benchmark for feeding a pet
     feed_pet {
         let caller: T::AccountId = whitelisted_caller();
-        let species = get_bounded_species::<T>();
-        let name = get_bounded_name::<T>();
-        
-        // Mint a pet for the caller
-        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), species, name)?;
-        let pet_id = 0u32;
-        
-        // Mock the ItemHandler trait
+        let pet_id = mint_pets::<T>(&caller, 1)?;
         let food_item_id: ItemId = 1u32;
-        
-        // Set up for level up
-        PetNfts::<T>::mutate(pet_id, |pet_opt| {
-            if let Some(pet) = pet_opt {
-                pet.experience_points = 100u32.saturating_mul(pet.level); // Set XP to level up
-            }
-        });
+
+        // The feed levels the pet up
+        set_up_level_up::<T>(pet_id);
     }: {
-        Pallet::<T>::feed_pet(RawOrigin::Signed(caller), pet_id, food_item_id)?;
+        // Ownership is checked against the pet's embedded owner, so the pet is read once.
+        Pallet::<T>::feed_pet(RawOrigin::Signed(caller).into(), pet_id, food_item_id)?;
     }
     verify {
-        let pet = <PetNfts<T>>::get(pet_id).unwrap();
-        assert_eq!(pet.mood_indicator, T::MaxMoodValue::get().min(pet.mood_indicator.saturating_add(T::FeedMoodBoost::get())));
-        assert_eq!(pet.experience_points, T::FeedXpGain::get());
+        let pet = PetNfts::<T>::get(pet_id).unwrap();
+        assert!(pet.level > 1);
         assert_eq!(pet.last_fed_block, frame_system::Pallet::<T>::block_number());
-        assert_eq!(pet.last_state_update_block, frame_system::Pallet::<T>::block_number());
-        assert_eq!(pet.level, 2); // Verify level up
-        
-        // Verify mood doesn't exceed max
         assert!(pet.mood_indicator <= T::MaxMoodValue::get());
     }
 
-    // Benchmark for playing with a pet
     // Benchmark for playing with a pet
     play_with_pet {
         let caller: T::AccountId = whitelisted_caller();
-        let species = get_bounded_species::<T>();
-        let name = get_bounded_name::<T>();
-        
-        // Mint a pet for the caller
-        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), species, name)?;
-        let pet_id = 0u32;
-        
-        // Mock the ItemHandler trait
+        let pet_id = mint_pets::<T>(&caller, 1)?;
         let toy_item_id: ItemId = 2u32;
-        
-        // Set up for level up
-        PetNfts::<T>::mutate(pet_id, |pet_opt| {
-            if let Some(pet) = pet_opt {
-                pet.experience_points = 100u32.saturating_mul(pet.level); // Set XP to level up
-            }
-        });
+
+        // The play session levels the pet up
+        set_up_level_up::<T>(pet_id);
     }: {
-        Pallet::<T>::play_with_pet(RawOrigin::Signed(caller), pet_id, toy_item_id)?;
+        // Ownership is checked against the pet's embedded owner, so the pet is read once.
+        Pallet::<T>::play_with_pet(RawOrigin::Signed(caller).into(), pet_id, toy_item_id)?;
     }
     verify {
-        let pet = <PetNfts<T>>::get(pet_id).unwrap();
-        assert_eq!(pet.mood_indicator, T::MaxMoodValue::get().min(pet.mood_indicator.saturating_add(T::PlayMoodBoost::get())));
-        assert_eq!(pet.experience_points, T::PlayXpGain::get());
+        let pet = PetNfts::<T>::get(pet_id).unwrap();
+        assert!(pet.level > 1);
         assert_eq!(pet.last_played_block, frame_system::Pallet::<T>::block_number());
-        assert_eq!(pet.last_state_update_block, frame_system::Pallet::<T>::block_number());
-        assert_eq!(pet.level, 2); // Verify level up
-        
-        // Verify mood doesn't exceed max
         assert!(pet.mood_indicator <= T::MaxMoodValue::get());
     }
 
-    // Benchmark for neglect check
     // Benchmark for applying neglect check
     apply_neglect_check {
         let caller: T::AccountId = whitelisted_caller();
-        let species = get_bounded_species::<T>();
-        let name = get_bounded_name::<T>();
-        
-        // Mint a pet for the caller
-        Pallet::<T>::mint_pet_nft(RawOrigin::Signed(caller.clone()).into(), species, name)?;
-        let pet_id = 0u32;
-        
-        // Set up for neglect trigger
-        let neglect_trigger_block = frame_system::Pallet::<T>::block_number()
-            .saturating_sub(T::NeglectThresholdBlocks::get())
-            .saturating_sub(1u32.into());
+        let pet_id = mint_pets::<T>(&caller, 1)?;
 
+        // The pet was last played with past the neglect threshold, so the penalty applies
+        let params = Pallet::<T>::params();
+        frame_system::Pallet::<T>::set_block_number(params.neglect_threshold_blocks + 2u32.into());
         PetNfts::<T>::mutate(pet_id, |pet_opt| {
             if let Some(pet) = pet_opt {
-                pet.last_played_block = neglect_trigger_block;
-                pet.mood_indicator = T::NeglectMoodPenalty::get().saturating_add(1);
-                pet.last_state_update_block = neglect_trigger_block; // Set to old block for verification
+                pet.last_played_block = 0u32.into();
+                pet.mood_indicator = T::MaxMoodValue::get();
             }
         });
-        
-        let old_mood = PetNfts::<T>::get(pet_id).unwrap().mood_indicator;
     }: {
-        Pallet::<T>::apply_neglect_check(RawOrigin::Signed(caller), pet_id)?;
+        Pallet::<T>::apply_neglect_check(RawOrigin::Signed(caller).into(), pet_id)?;
     }
     verify {
-        let pet = <PetNfts<T>>::get(pet_id).unwrap();
-        assert_eq!(pet.mood_indicator, old_mood.saturating_sub(T::NeglectMoodPenalty::get()));
+        let pet = PetNfts::<T>::get(pet_id).unwrap();
+        assert_eq!(pet.mood_indicator, T::MaxMoodValue::get().saturating_sub(params.neglect_mood_penalty));
         assert_eq!(pet.last_state_update_block, frame_system::Pallet::<T>::block_number());
-        assert!(pet.mood_indicator < old_mood); // Verify mood actually decreased
-        
-        // Verify mood doesn't go below 0
-        assert!(pet.mood_indicator >= 0);
+    }
+
+    // Benchmark for an unsigned neglect batch of `n` pets, all due a penalty
+    apply_neglect_check_unsigned {
+        let n in 1 .. crate::neglect::NEGLECT_PAGE_SIZE.min(T::MaxNeglectBatchPerBlock::get());
+
+        // One pet per owner, to stay within `MaxOwnedPets`
+        let mut pet_ids = Vec::new();
+        for index in 0..n {
+            let owner: T::AccountId = account("owner", index, SEED);
+            pet_ids.push(mint_pets::<T>(&owner, 1)?);
+        }
+        let params = Pallet::<T>::params();
+        frame_system::Pallet::<T>::set_block_number(params.neglect_threshold_blocks + 2u32.into());
+        for pet_id in &pet_ids {
+            PetNfts::<T>::mutate(pet_id, |pet_opt| {
+                if let Some(pet) = pet_opt {
+                    pet.last_played_block = 0u32.into();
+                    pet.last_state_update_block = 0u32.into();
+                    pet.mood_indicator = T::MaxMoodValue::get();
+                }
+            });
+        }
+        let batch: crate::neglect::NeglectBatch = pet_ids.clone().try_into().unwrap();
+    }: {
+        Pallet::<T>::apply_neglect_check_unsigned(RawOrigin::None.into(), batch)?;
+    }
+    verify {
+        for pet_id in pet_ids {
+            let pet = PetNfts::<T>::get(pet_id).unwrap();
+            assert_eq!(pet.mood_indicator, T::MaxMoodValue::get().saturating_sub(params.neglect_mood_penalty));
+        }
+    }
+
+    // Benchmark for batch minting `n` pets, up to a full collection
+    batch_mint_pet_nfts {
+        let n in 1 .. T::MaxOwnedPets::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::deposit_creating(&caller, T::Currency::minimum_balance());
+        let pets: Vec<(Vec<u8>, Vec<u8>)> = (0..n).map(|_| (get_species::<T>(), get_name::<T>())).collect();
+    }: {
+        Pallet::<T>::batch_mint_pet_nfts(RawOrigin::Signed(caller.clone()).into(), pets)?;
+    }
+    verify {
+        assert_eq!(OwnerOfPet::<T>::get(&caller).len() as u32, n);
+    }
+
+    // Benchmark for the block hooks on a block without a featured rotation
+    on_initialize {
+        // Notifications were sent and throttled in the previous block
+        NotificationsThisBlock::<T>::put(u32::MAX);
+        ThrottledNotifications::<T>::put(u32::MAX);
+        // Rotations report their own weight, so measure a block none is due at
+        let now = T::FeatureRotationPeriod::get() + 1u32.into();
+    }: {
+        Pallet::<T>::on_initialize(now);
+        Pallet::<T>::on_finalize(now);
+    }
+    verify {
+        assert_eq!(NotificationsThisBlock::<T>::get(), 0);
+        assert_eq!(ThrottledNotifications::<T>::get(), 0);
+    }
+
+    // Benchmark for approving an account to transfer a pet
+    approve_transfer {
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, SEED);
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+    }: {
+        Pallet::<T>::approve_transfer(RawOrigin::Signed(caller).into(), pet_id, delegate.clone())?;
+    }
+    verify {
+        assert_eq!(PetApprovals::<T>::get(pet_id), Some(delegate));
+    }
+
+    // Benchmark for revoking a pet's transfer approval
+    revoke_approval {
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, SEED);
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        PetApprovals::<T>::insert(pet_id, delegate);
+    }: {
+        Pallet::<T>::revoke_approval(RawOrigin::Signed(caller).into(), pet_id)?;
+    }
+    verify {
+        assert!(PetApprovals::<T>::get(pet_id).is_none());
+    }
+
+    // Benchmark for approving an operator of every pet the caller owns
+    set_operator {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, SEED);
+    }: {
+        Pallet::<T>::set_operator(RawOrigin::Signed(caller.clone()).into(), operator.clone(), true)?;
+    }
+    verify {
+        assert!(OperatorApprovals::<T>::contains_key(&caller, &operator));
+    }
+    // Benchmark for replacing a pet's bio with one of the maximum length
+    set_pet_bio {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        PetBios::<T>::insert(pet_id, get_bio::<T>(b'O'));
+        let bio = get_bio::<T>(b'B').to_vec();
+        let expected_version = PetStateVersions::<T>::get(pet_id);
+    }: {
+        Pallet::<T>::set_pet_bio(RawOrigin::Signed(caller).into(), pet_id, bio.clone(), Some(expected_version))?;
+    }
+    verify {
+        assert_eq!(PetBios::<T>::get(pet_id).map(|bio| bio.to_vec()), Some(bio));
+    }
+
+    // Benchmark for clearing a pet's bio
+    clear_pet_bio {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        PetBios::<T>::insert(pet_id, get_bio::<T>(b'B'));
+        let expected_version = PetStateVersions::<T>::get(pet_id);
+    }: {
+        Pallet::<T>::clear_pet_bio(RawOrigin::Signed(caller).into(), pet_id, Some(expected_version))?;
+    }
+    verify {
+        assert!(!PetBios::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for clearing a pet's bio through moderation
+    force_clear_pet_bio {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        PetBios::<T>::insert(pet_id, get_bio::<T>(b'B'));
+        let origin = T::ModerationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::force_clear_pet_bio(origin, pet_id)?;
+    }
+    verify {
+        assert!(!PetBios::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for leaving digest mode, which flushes a digest pending for every type
+    set_notification_digest {
+        let caller: T::AccountId = whitelisted_caller();
+        set_up_digest::<T>(&caller)?;
+    }: {
+        Pallet::<T>::set_notification_digest(RawOrigin::Signed(caller.clone()).into(), false, 1u32.into())?;
+    }
+    verify {
+        assert!(NotificationDigestSettings::<T>::get(&caller).is_none());
+        assert_eq!(PendingDigest::<T>::iter_prefix(&caller).count(), 0);
+    }
+
+    // Benchmark for flushing a digest pending for every notification type
+    flush_digest {
+        let caller: T::AccountId = whitelisted_caller();
+        let account: T::AccountId = account("digest", 0, SEED);
+        set_up_digest::<T>(&account)?;
+    }: {
+        Pallet::<T>::flush_digest(RawOrigin::Signed(caller).into(), account.clone())?;
+    }
+    verify {
+        assert_eq!(PendingDigest::<T>::iter_prefix(&account).count(), 0);
+    }
+
+    // Benchmark for claiming a deferred lifecycle reward
+    claim_deferred_lifecycle_reward {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        fund::<T>(&caller);
+        fund::<T>(&T::LifecycleRewardPot::get());
+        DeferredLifecycleRewards::<T>::insert(pet_id, T::Currency::minimum_balance());
+    }: {
+        Pallet::<T>::claim_deferred_lifecycle_reward(RawOrigin::Signed(caller).into(), pet_id)?;
+    }
+    verify {
+        assert!(DeferredLifecycleRewards::<T>::get(pet_id).is_zero());
+    }
+
+    // Benchmark for registering a social interaction type
+    set_social_interaction_type {
+        let (type_id, info) = crate::social::default_interaction_types().remove(0);
+        let origin = T::CatalogOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_social_interaction_type(origin, type_id, info)?;
+    }
+    verify {
+        assert!(SocialInteractionTypes::<T>::contains_key(type_id));
+    }
+
+    // Benchmark for setting the onboarding starter pet, with a name and species of the maximum length
+    set_onboarding_starter_config {
+        let origin = T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_onboarding_starter_config(origin, get_species::<T>(), get_name::<T>())?;
+    }
+    verify {
+        assert!(OnboardingStarterConfig::<T>::get().is_some());
+    }
+
+    // Benchmark for removing a social interaction type
+    remove_social_interaction_type {
+        let (type_id, info) = crate::social::default_interaction_types().remove(0);
+        SocialInteractionTypes::<T>::insert(type_id, info);
+        let origin = T::CatalogOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::remove_social_interaction_type(origin, type_id)?;
+    }
+    verify {
+        assert!(!SocialInteractionTypes::<T>::contains_key(type_id));
+    }
+
+    // Benchmark for subscribing a pet to auto-care, replacing a lapsed subscription
+    subscribe_auto_care {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        fund::<T>(&caller);
+
+        // The lapsed subscription's balance is refunded first
+        Pallet::<T>::subscribe_auto_care(RawOrigin::Signed(caller.clone()).into(), pet_id, AUTO_CARE_ALL, 1)?;
+        CareSubscriptions::<T>::mutate(pet_id, |subscription| {
+            if let Some(subscription) = subscription {
+                subscription.active = false;
+            }
+        });
+    }: {
+        Pallet::<T>::subscribe_auto_care(RawOrigin::Signed(caller).into(), pet_id, AUTO_CARE_ALL, 2)?;
+    }
+    verify {
+        assert!(CareSubscriptions::<T>::get(pet_id).map_or(false, |subscription| subscription.active));
+    }
+
+    // Benchmark for performing a pet's due auto-care of every kind
+    execute_due_care {
+        let caller: T::AccountId = whitelisted_caller();
+        let executor: T::AccountId = account("executor", 0, SEED);
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        fund::<T>(&caller);
+        fund::<T>(&executor);
+        Pallet::<T>::subscribe_auto_care(RawOrigin::Signed(caller).into(), pet_id, AUTO_CARE_ALL, 2)?;
+        let due = frame_system::Pallet::<T>::block_number() + T::AutoCareInterval::get();
+        frame_system::Pallet::<T>::set_block_number(due);
+    }: {
+        Pallet::<T>::execute_due_care(RawOrigin::Signed(executor).into(), pet_id)?;
+    }
+    verify {
+        assert!(CareSubscriptions::<T>::get(pet_id).map_or(false, |subscription| subscription.next_due_block > due));
+    }
+
+    // Benchmark for cancelling an auto-care subscription and refunding it
+    cancel_auto_care {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        fund::<T>(&caller);
+        Pallet::<T>::subscribe_auto_care(RawOrigin::Signed(caller.clone()).into(), pet_id, AUTO_CARE_ALL, 2)?;
+    }: {
+        Pallet::<T>::cancel_auto_care(RawOrigin::Signed(caller).into(), pet_id)?;
+    }
+    verify {
+        assert!(!CareSubscriptions::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for proposing a pet swap
+    propose_trade {
+        let caller: T::AccountId = whitelisted_caller();
+        let counterparty: T::AccountId = account("counterparty", 0, SEED);
+        let offered_pet = mint_pets::<T>(&caller, 1)?;
+        let requested_pet = mint_pets::<T>(&counterparty, 1)?;
+    }: {
+        Pallet::<T>::propose_trade(RawOrigin::Signed(caller).into(), offered_pet, requested_pet)?;
+    }
+    verify {
+        assert!(PendingTrades::<T>::contains_key(NextTradeId::<T>::get() - 1));
+        assert!(LockedNfts::<T>::contains_key(offered_pet));
+    }
+
+    // Benchmark for accepting a trade, with both collections as long as a trade allows
+    accept_trade {
+        let caller: T::AccountId = whitelisted_caller();
+        let proposer: T::AccountId = account("proposer", 0, SEED);
+        let max_owned_pets = T::MaxOwnedPets::get();
+        let offered_pet = mint_pets::<T>(&proposer, max_owned_pets - 1)?;
+        let requested_pet = mint_pets::<T>(&caller, max_owned_pets - 1)?;
+        Pallet::<T>::propose_trade(RawOrigin::Signed(proposer.clone()).into(), offered_pet, requested_pet)?;
+        let trade_id = NextTradeId::<T>::get() - 1;
+    }: {
+        Pallet::<T>::accept_trade(RawOrigin::Signed(caller.clone()).into(), trade_id)?;
+    }
+    verify {
+        assert_eq!(PetNftOwner::<T>::get(offered_pet), Some(caller));
+        assert_eq!(PetNftOwner::<T>::get(requested_pet), Some(proposer));
+    }
+
+    // Benchmark for withdrawing a trade
+    cancel_trade {
+        let caller: T::AccountId = whitelisted_caller();
+        let counterparty: T::AccountId = account("counterparty", 0, SEED);
+        let offered_pet = mint_pets::<T>(&caller, 1)?;
+        let requested_pet = mint_pets::<T>(&counterparty, 1)?;
+        Pallet::<T>::propose_trade(RawOrigin::Signed(caller.clone()).into(), offered_pet, requested_pet)?;
+        let trade_id = NextTradeId::<T>::get() - 1;
+    }: {
+        Pallet::<T>::cancel_trade(RawOrigin::Signed(caller).into(), trade_id)?;
+    }
+    verify {
+        assert!(!PendingTrades::<T>::contains_key(trade_id));
+        assert!(!LockedNfts::<T>::contains_key(offered_pet));
+    }
+
+    // Benchmark for replacing the game parameters
+    set_game_parameters {
+        // Every parameter changes, so the event lists them all
+        let current = Pallet::<T>::params();
+        let params = game_params::GameParameters {
+            feed_mood_boost: if current.feed_mood_boost == 1 { 2 } else { 1 },
+            play_mood_boost: if current.play_mood_boost == 1 { 2 } else { 1 },
+            feed_xp_gain: if current.feed_xp_gain == 1 { 2 } else { 1 },
+            play_xp_gain: if current.play_xp_gain == 1 { 2 } else { 1 },
+            neglect_mood_penalty: if current.neglect_mood_penalty == 1 { 2 } else { 1 },
+            neglect_threshold_blocks: current.neglect_threshold_blocks + 1u32.into(),
+        };
+        let origin = T::ParamsOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_game_parameters(origin, params.clone())?;
+    }
+    verify {
+        assert!(Pallet::<T>::params() == params);
+    }
+
+    // Benchmark for force-unlocking a locked pet
+    force_unlock {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        Pallet::<T>::do_lock_nft(&caller, &pet_id, LockerId::Trade).map_err(|_| "locking a benchmark pet failed")?;
+    }: {
+        Pallet::<T>::force_unlock(RawOrigin::Root.into(), pet_id)?;
+    }
+    verify {
+        assert!(!LockedNfts::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for claiming a parked pet into a collection one short of capacity
+    claim_pending_pet {
+        let caller: T::AccountId = whitelisted_caller();
+        mint_pets::<T>(&caller, T::MaxOwnedPets::get() - 1)?;
+        let pet_id = park_pet::<T>(&caller)?;
+    }: {
+        Pallet::<T>::claim_pending_pet(RawOrigin::Signed(caller.clone()).into(), pet_id)?;
+    }
+    verify {
+        assert_eq!(PetNftOwner::<T>::get(pet_id), Some(caller));
+        assert!(!PendingClaims::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for burning an expired parked pet
+    clear_expired_pending_pet {
+        let caller: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let pet_id = park_pet::<T>(&recipient)?;
+        let expired = frame_system::Pallet::<T>::block_number() + T::PendingClaimExpiry::get() + 1u32.into();
+        frame_system::Pallet::<T>::set_block_number(expired);
+    }: {
+        Pallet::<T>::clear_expired_pending_pet(RawOrigin::Signed(caller).into(), pet_id)?;
+    }
+    verify {
+        assert!(!PendingClaims::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for setting a trait's inheritance chance, for a trait of the maximum length
+    set_trait_inheritance_chance {
+        let trait_name: TraitTypeString = vec![b'T'; T::MaxTraitStringLen::get() as usize].try_into().unwrap();
+        let origin = T::CatalogOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_trait_inheritance_chance(origin, trait_name.clone(), Some(Perbill::from_percent(50)))?;
+    }
+    verify {
+        assert!(InheritanceTable::<T>::contains_key(&trait_name));
+    }
+
+    // Benchmark for acknowledging a pet's sync flags with `h` hooks registered
+    acknowledge_sync {
+        let h in 1 .. T::MaxRegisteredHooks::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        for hook_id in 0..h {
+            Pallet::<T>::register_sync_hook(RawOrigin::Signed(caller.clone()).into(), hook_id, u8::MAX, 0)?;
+        }
+        let mask = SyncFlags::mask_for_interests(u8::MAX);
+        let version = PetNfts::<T>::get(pet_id).unwrap().state_version;
+    }: {
+        Pallet::<T>::acknowledge_sync(RawOrigin::Signed(caller).into(), pet_id, mask, version)?;
+    }
+    verify {
+        assert_eq!(PetNfts::<T>::get(pet_id).unwrap().sync_flags.bits() & mask, 0);
+    }
+
+    // Benchmark for cataloguing a gesture
+    set_input_catalog_entry {
+        let (id, entry) = crate::input_catalog::default_gestures().remove(0);
+        let origin = T::CatalogOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_input_catalog_entry(origin, InputKind::Gesture, id, entry)?;
+    }
+    verify {
+        assert!(GestureCatalog::<T>::contains_key(id));
+    }
+
+    // Benchmark for removing a gesture from the catalog
+    remove_input_catalog_entry {
+        let (id, entry) = crate::input_catalog::default_gestures().remove(0);
+        GestureCatalog::<T>::insert(id, entry);
+        let origin = T::CatalogOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::remove_input_catalog_entry(origin, InputKind::Gesture, id)?;
+    }
+    verify {
+        assert!(!GestureCatalog::<T>::contains_key(id));
+    }
+
+    // Benchmark for setting a species' care preferences, with a multiplier for every care action
+    set_species_care_preferences {
+        let species: BoundedVec<u8, T::MaxSpeciesNameLen> = get_species::<T>().try_into().unwrap();
+        let preferences = CarePreferences {
+            multipliers: vec![
+                (CareActionKind::Feed, Perbill::from_percent(50)),
+                (CareActionKind::Play, Perbill::from_percent(60)),
+                (CareActionKind::Gesture, Perbill::from_percent(70)),
+                (CareActionKind::Touch, Perbill::from_percent(80)),
+            ].try_into().unwrap(),
+            preferred_environment: Some(EnvironmentKind::Forest),
+        };
+        let origin = T::CatalogOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_species_care_preferences(origin, species.clone(), Some(preferences))?;
+    }
+    verify {
+        assert!(SpeciesCarePreferences::<T>::contains_key(&species));
+    }
+
+    // Benchmark for sacrificing `n` pets to another
+    sacrifice_pets {
+        let n in 1 .. T::MaxSacrificesPerCall::get().min(T::MaxOwnedPets::get() - 1);
+
+        let caller: T::AccountId = whitelisted_caller();
+        let target_pet_id = mint_pets::<T>(&caller, 1)?;
+        let mut sacrificed = Vec::new();
+        for _ in 0..n {
+            sacrificed.push(mint_pets::<T>(&caller, 1)?);
+        }
+    }: {
+        Pallet::<T>::sacrifice_pets(RawOrigin::Signed(caller.clone()).into(), target_pet_id, sacrificed)?;
+    }
+    verify {
+        assert_eq!(OwnerOfPet::<T>::get(&caller).to_vec(), vec![target_pet_id]);
+    }
+
+    // Benchmark for releasing a pet from a full collection
+    release_pet {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, T::MaxOwnedPets::get())?;
+        PetBios::<T>::insert(pet_id, get_bio::<T>(b'B'));
+    }: {
+        Pallet::<T>::release_pet(RawOrigin::Signed(caller.clone()).into(), pet_id)?;
+    }
+    verify {
+        assert!(!PetNfts::<T>::contains_key(pet_id));
+        assert!(!OwnerOfPet::<T>::get(&caller).contains(&pet_id));
+    }
+
+    // Benchmark for force-releasing a locked pet with a lapsed auto-care subscription and an
+    // unclaimed lifecycle reward, all of which are dropped
+    force_release {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, T::MaxOwnedPets::get())?;
+        fund::<T>(&caller);
+        Pallet::<T>::subscribe_auto_care(RawOrigin::Signed(caller.clone()).into(), pet_id, AUTO_CARE_ALL, 2)?;
+        CareSubscriptions::<T>::mutate(pet_id, |subscription| {
+            if let Some(subscription) = subscription {
+                subscription.active = false;
+            }
+        });
+        DeferredLifecycleRewards::<T>::insert(pet_id, T::Currency::minimum_balance());
+        Pallet::<T>::do_lock_nft(&caller, &pet_id, LockerId::Trade).map_err(|_| "locking a benchmark pet failed")?;
+    }: {
+        Pallet::<T>::force_release(RawOrigin::Root.into(), pet_id)?;
+    }
+    verify {
+        assert!(!PetNfts::<T>::contains_key(pet_id));
+        assert!(!LockedNfts::<T>::contains_key(pet_id));
+        assert!(!CareSubscriptions::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for burning a pet from a full collection
+    burn_pet {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, T::MaxOwnedPets::get())?;
+        PetBios::<T>::insert(pet_id, get_bio::<T>(b'B'));
+        let pot = T::LifecycleRewardPot::get();
+        T::Currency::make_free_balance_be(&pot, T::Currency::minimum_balance() + T::BurnCompensation::get());
+    }: {
+        Pallet::<T>::burn_pet(RawOrigin::Signed(caller.clone()).into(), pet_id)?;
+    }
+    verify {
+        assert!(!PetNfts::<T>::contains_key(pet_id));
+        assert!(PetCemetery::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for a marketplace transfer between two collections near capacity
+    marketplace_transfer_pet {
+        let caller: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let max_owned_pets = T::MaxOwnedPets::get();
+        let pet_id = mint_pets::<T>(&caller, max_owned_pets)?;
+        mint_pets::<T>(&recipient, max_owned_pets - 1)?;
+        let origin = T::MarketplaceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::marketplace_transfer_pet(origin, caller, recipient.clone(), pet_id, false)?;
+    }
+    verify {
+        assert_eq!(PetNftOwner::<T>::get(pet_id), Some(recipient));
+    }
+
+    // Benchmark for requesting an account migration
+    initiate_account_migration {
+        let caller: T::AccountId = whitelisted_caller();
+        let new_account: T::AccountId = account("new_account", 0, SEED);
+    }: {
+        Pallet::<T>::initiate_account_migration(RawOrigin::Signed(caller.clone()).into(), new_account.clone())?;
+    }
+    verify {
+        assert_eq!(AccountMigrationRequests::<T>::get(&caller), Some(new_account));
+    }
+
+    // Benchmark for free care that levels the pet up
+    pet_basic_care {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        set_up_level_up::<T>(pet_id);
+    }: {
+        Pallet::<T>::pet_basic_care(RawOrigin::Signed(caller).into(), pet_id, CareActionKind::Feed)?;
+    }
+    verify {
+        let pet = PetNfts::<T>::get(pet_id).unwrap();
+        assert!(pet.level > 1);
+        assert_eq!(pet.last_fed_block, frame_system::Pallet::<T>::block_number());
+    }
+
+    // Benchmark for rounding up fees to a community pot
+    set_donation_roundup {
+        let caller: T::AccountId = whitelisted_caller();
+        CommunityPots::<T>::insert(0, account::<T::AccountId>("pot", 0, SEED));
+    }: {
+        Pallet::<T>::set_donation_roundup(
+            RawOrigin::Signed(caller.clone()).into(),
+            true,
+            T::MaxDonationPercent::get(),
+            DonationTarget::CommunityPot(0),
+        )?;
+    }
+    verify {
+        assert!(DonationPreferences::<T>::contains_key(&caller));
+    }
+
+    // Benchmark for registering a community pot
+    set_community_pot {
+        let pot: T::AccountId = account("pot", 0, SEED);
+        let origin = T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: {
+        Pallet::<T>::set_community_pot(origin, 0, Some(pot.clone()))?;
+    }
+    verify {
+        assert_eq!(CommunityPots::<T>::get(0), Some(pot));
+    }
+
+    // Benchmark for removing one trait from a full set of owner traits
+    remove_personality_trait {
+        let caller: T::AccountId = whitelisted_caller();
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        let traits = get_bounded_traits::<T>(b'O');
+        let trait_to_remove = traits.last().cloned().unwrap();
+        PetNfts::<T>::mutate(pet_id, |pet_opt| {
+            if let Some(pet) = pet_opt {
+                pet.personality_traits = traits;
+            }
+        });
+        let expected_version = PetStateVersions::<T>::get(pet_id);
+    }: {
+        Pallet::<T>::remove_personality_trait(
+            RawOrigin::Signed(caller).into(),
+            pet_id,
+            trait_to_remove.clone(),
+            expected_version,
+        )?;
+    }
+    verify {
+        assert!(!PetNfts::<T>::get(pet_id).unwrap().personality_traits.contains(&trait_to_remove));
+    }
+
+    // Benchmark for delegating a pet's care
+    delegate_pet {
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, SEED);
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        let expiry_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+    }: {
+        Pallet::<T>::delegate_pet(RawOrigin::Signed(caller).into(), pet_id, delegate, expiry_block)?;
+    }
+    verify {
+        assert!(PetDelegations::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for ending a pet's delegation
+    revoke_delegation {
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, SEED);
+        let pet_id = mint_pets::<T>(&caller, 1)?;
+        let expiry_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+        Pallet::<T>::delegate_pet(RawOrigin::Signed(caller.clone()).into(), pet_id, delegate, expiry_block)?;
+    }: {
+        Pallet::<T>::revoke_delegation(RawOrigin::Signed(caller).into(), pet_id)?;
+    }
+    verify {
+        assert!(!PetDelegations::<T>::contains_key(pet_id));
+    }
+
+    // Benchmark for transferring `n` pets at once
+    batch_transfer_pet_nfts {
+        let n in 1 .. T::MaxBatchTransferSize::get().min(T::MaxOwnedPets::get());
+
+        let caller: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let mut pet_ids = Vec::new();
+        for _ in 0..n {
+            pet_ids.push(mint_pets::<T>(&caller, 1)?);
+        }
+    }: {
+        Pallet::<T>::batch_transfer_pet_nfts(RawOrigin::Signed(caller).into(), recipient.clone(), pet_ids)?;
+    }
+    verify {
+        assert_eq!(OwnerOfPet::<T>::get(&recipient).len() as u32, n);
     }
 }
 
@@ -315,5 +910,7 @@ mod tests {
     use super::Pallet as CritterNfts;
     frame_benchmarking::impl_benchmark_test_suite!(
         CritterNfts,
-        crate::mock::new_test_ext(),
-        crate::mock::Test,
\ No newline at end of file
+        crate::test::new_test_ext(),
+        crate::test::Test,
+    );
+}
@@ -0,0 +1,90 @@
+//! # Breeding Genetics
+//!
+//! A bred pet's DNA is a crossover of its parents' DNA, computed by the pallet rather than
+//! supplied by the breeding pallet, so no caller can pick a favourable DNA. Each of the 32 bytes
+//! comes from one parent or the other, chosen by one bit of the randomness: bit `i` (counting
+//! from the least significant bit of the first byte) picks byte `i` from the first parent when
+//! clear and from the second when set. Bits past the end of the randomness count as clear.
+//!
+//! Each byte then mutates to a random value with the runtime's `MutationRate`. The mutation
+//! rolls and values are hashed from the randomness and the byte's position, so the offspring's
+//! DNA is deterministic given its parents and the randomness.
+
+use codec::Encode;
+use frame_support::traits::{Get, Randomness};
+use sp_runtime::Perbill;
+use crate::traits::DnaHashType;
+use crate::{Config, PetId};
+
+/// Computes an offspring's DNA from its parents' by byte-level crossover, with a `MutationRate`
+/// chance of each byte mutating.
+///
+/// # Parameters
+///
+/// * `parent1_dna` - The first parent's DNA
+/// * `parent2_dna` - The second parent's DNA
+/// * `randomness` - The entropy behind the crossover and mutations
+///
+/// # Returns
+///
+/// * `DnaHashType` - The offspring's DNA
+pub fn compute_offspring_dna<MutationRate: Get<Perbill>>(
+    parent1_dna: DnaHashType,
+    parent2_dna: DnaHashType,
+    randomness: &[u8],
+) -> DnaHashType {
+    let mutation_rate = MutationRate::get();
+    let mut dna = parent1_dna;
+    for (index, byte) in dna.iter_mut().enumerate() {
+        let selector = randomness.get(index / 8).copied().unwrap_or(0);
+        if selector >> (index % 8) & 1 == 1 {
+            *byte = parent2_dna[index];
+        }
+        if let Some(mutated) = mutation(randomness, index as u8, mutation_rate) {
+            *byte = mutated;
+        }
+    }
+    dna
+}
+
+/// Rolls against `rate` for the byte at `index`, returning the byte's new value if it mutates.
+fn mutation(randomness: &[u8], index: u8, rate: Perbill) -> Option<u8> {
+    if rate.is_zero() {
+        return None;
+    }
+    let entropy = sp_io::hashing::blake2_256(&(b"mutate", randomness, index).encode());
+    let draw = u32::from_le_bytes([entropy[0], entropy[1], entropy[2], entropy[3]]) % Perbill::ACCURACY;
+    (draw < rate.deconstruct()).then_some(entropy[4])
+}
+
+/// A system for deriving the DNA of bred pets.
+pub struct BreedingGeneticsSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> BreedingGeneticsSystem<T> {
+    /// Computes the DNA of a pet bred as `pet_id` from two parents, with randomness drawn from
+    /// `PetRandomness` for the breeding.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The offspring's ID
+    /// * `parents` - The IDs of the two parents
+    /// * `parent_dnas` - The DNA of the two parents, in the same order
+    ///
+    /// # Returns
+    ///
+    /// * `DnaHashType` - The offspring's DNA
+    pub fn offspring_dna(
+        pet_id: PetId,
+        parents: (PetId, PetId),
+        (parent1_dna, parent2_dna): (DnaHashType, DnaHashType),
+    ) -> DnaHashType {
+        let subject = (b"breeding", pet_id, parents).encode();
+        let (random, _) = T::PetRandomness::random(&subject);
+        // Hash the subject in too, so providers returning one value per block still give each
+        // offspring its own crossover.
+        let randomness = sp_io::hashing::blake2_256(&(random, subject).encode());
+        compute_offspring_dna::<T::MutationRate>(parent1_dna, parent2_dna, &randomness)
+    }
+}
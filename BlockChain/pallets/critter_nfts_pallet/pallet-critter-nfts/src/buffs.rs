@@ -0,0 +1,226 @@
+//! # Temporary Buffs
+//!
+//! Interactive sessions pay off with temporary buffs. When a session ends, its quality score
+//! is the share of its interactions that were positive, counting at least
+//! `SESSION_QUALITY_MIN_INTERACTIONS` interactions so a short session cannot score high. A
+//! session scoring at least `SessionBuffQualityThreshold` grants the pet an XP gain buff and a
+//! mood gain buff of `SessionBuffMagnitude`, each lasting `BuffDurationBlocks`.
+//!
+//! Buffs are kept in `TemporaryBuffs`, at most `MaxActiveBuffs` per pet. A buff granted to a
+//! pet with no room replaces its weakest buff (lowest magnitude, then soonest to expire) if it
+//! is at least as strong, and is dropped otherwise.
+//!
+//! Buffs are applied lazily where gains are computed: `Pallet::grant_xp` boosts XP grants,
+//! social interactions boost mood gains and `effective_stats` applies stat buffs last. Only the
+//! strongest buff of a kind applies; buffs of the same kind do not stack. Expired buffs are
+//! skipped, and pruned when the pet's buffs are next read for a gain.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::Get};
+use scale_info::TypeInfo;
+use sp_runtime::Percent;
+use sp_std::vec::Vec;
+use crate::kinds::BuffKind;
+use crate::{Config, Event, Pallet, PetId};
+
+/// The fewest interactions a session's quality score is computed over.
+pub const SESSION_QUALITY_MIN_INTERACTIONS: u32 = 5;
+
+/// The `SessionInteraction` outcome of a positive interaction.
+pub const POSITIVE_OUTCOME: u8 = 1;
+
+/// A temporary buff on a pet.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TemporaryBuff<BlockNumber> {
+    /// What the buff boosts
+    pub kind: BuffKind,
+
+    /// How much the buff boosts gains of its kind by
+    pub magnitude: Percent,
+
+    /// The block the buff expires at; it applies before this block only
+    pub expires_at: BlockNumber,
+}
+
+/// A pet's buffs, bounded by `MaxActiveBuffs`.
+pub type TemporaryBuffsOf<T> = frame_support::BoundedVec<
+    TemporaryBuff<<T as frame_system::Config>::BlockNumber>,
+    <T as crate::config::SessionConfig>::MaxActiveBuffs,
+>;
+
+/// A system for granting and applying temporary buffs.
+pub struct BuffSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> BuffSystem<T> {
+    /// Returns a pet's unexpired buffs without writing to storage.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet to check
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<TemporaryBuff<T::BlockNumber>>` - The pet's active buffs
+    pub fn active_buffs(pet_id: PetId) -> Vec<TemporaryBuff<T::BlockNumber>> {
+        let now = frame_system::Pallet::<T>::block_number();
+        crate::TemporaryBuffs::<T>::get(pet_id)
+            .into_iter()
+            .filter(|buff| buff.expires_at > now)
+            .collect()
+    }
+
+    /// Returns the magnitude of the strongest buff of `kind` among `buffs`, or zero.
+    pub fn strongest(buffs: &[TemporaryBuff<T::BlockNumber>], kind: BuffKind) -> Percent {
+        buffs
+            .iter()
+            .filter(|buff| buff.kind == kind)
+            .map(|buff| buff.magnitude)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns a mood effect boosted by `magnitude`. Only gains are boosted; drops are not.
+    pub fn boosted_mood_effect(effect: i8, magnitude: Percent) -> i8 {
+        if effect > 0 {
+            effect.saturating_add(magnitude.mul_floor(effect as u8) as i8)
+        } else {
+            effect
+        }
+    }
+
+    /// Computes the bonus an XP grant earns from the pet's XP gain buff, pruning expired buffs.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet receiving the grant
+    /// * `amount` - The XP being granted, before any bonus
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The bonus XP to add on top of `amount`
+    pub fn xp_bonus(pet_id: PetId, amount: u32) -> u32 {
+        Self::strongest(&Self::prune(pet_id), BuffKind::XpGain).mul_floor(amount)
+    }
+
+    /// Boosts a mood effect by the pet's mood gain buff, pruning expired buffs.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet whose mood changes
+    /// * `effect` - The mood effect, before any boost
+    ///
+    /// # Returns
+    ///
+    /// * `i8` - The boosted mood effect
+    pub fn boost_mood_effect(pet_id: PetId, effect: i8) -> i8 {
+        if effect <= 0 {
+            return effect;
+        }
+        Self::boosted_mood_effect(effect, Self::strongest(&Self::prune(pet_id), BuffKind::MoodGain))
+    }
+
+    /// Grants a pet a buff lasting `BuffDurationBlocks`. A pet with `MaxActiveBuffs` buffs has
+    /// its weakest buff replaced if the new one is at least as strong.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet to buff
+    /// * `kind` - What the buff boosts
+    /// * `magnitude` - How much the buff boosts gains by
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the buff was granted
+    pub fn grant(pet_id: PetId, kind: BuffKind, magnitude: Percent) -> bool {
+        let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(T::BuffDurationBlocks::get());
+        let buff = TemporaryBuff { kind, magnitude, expires_at };
+
+        let mut buffs = Self::prune(pet_id);
+        if buffs.try_push(buff).is_err() {
+            let weakest = buffs
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, buff)| (buff.magnitude, buff.expires_at))
+                .map(|(index, buff)| (index, buff.magnitude));
+            match weakest {
+                Some((index, weakest_magnitude)) if magnitude >= weakest_magnitude => buffs[index] = buff,
+                _ => return false,
+            }
+        }
+        crate::TemporaryBuffs::<T>::insert(pet_id, buffs);
+
+        Pallet::<T>::deposit_event(Event::TemporaryBuffGranted { pet_id, kind, magnitude, expires_at });
+        true
+    }
+
+    /// Scores a session by the share of its interactions that were positive, counting at least
+    /// `SESSION_QUALITY_MIN_INTERACTIONS` interactions.
+    ///
+    /// # Parameters
+    ///
+    /// * `positive` - The number of positive interactions in the session
+    /// * `total` - The number of interactions in the session
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - The quality score, from 0 to 100
+    pub fn quality_score(positive: u32, total: u32) -> u8 {
+        let counted = total.max(SESSION_QUALITY_MIN_INTERACTIONS);
+        Percent::from_rational(positive.min(counted), counted).deconstruct()
+    }
+
+    /// Scores an ended session with `quality_score`.
+    #[cfg(feature = "interactive-sessions")]
+    pub fn session_quality(session: &crate::interactive::InteractiveSession) -> u8 {
+        let positive = session
+            .interactions
+            .iter()
+            .filter(|interaction| interaction.outcome == POSITIVE_OUTCOME)
+            .count() as u32;
+        Self::quality_score(positive, session.interactions.len() as u32)
+    }
+
+    /// Buffs a pet for a session that scored at least `SessionBuffQualityThreshold`.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The session's pet
+    /// * `quality` - The session's quality score
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the session earned its pet buffs
+    pub fn reward(pet_id: PetId, quality: u8) -> bool {
+        if quality < T::SessionBuffQualityThreshold::get() {
+            return false;
+        }
+        let magnitude = T::SessionBuffMagnitude::get();
+        Self::grant(pet_id, BuffKind::XpGain, magnitude);
+        Self::grant(pet_id, BuffKind::MoodGain, magnitude);
+        true
+    }
+
+    /// Buffs the pet of an ended session if the session earned it. Called when a session ends.
+    #[cfg(feature = "interactive-sessions")]
+    pub fn reward_session(session: &crate::interactive::InteractiveSession) -> bool {
+        Self::reward(session.pet_id, Self::session_quality(session))
+    }
+
+    /// Returns a pet's buffs with the expired ones removed, writing them back if any expired.
+    fn prune(pet_id: PetId) -> TemporaryBuffsOf<T> {
+        let mut buffs = crate::TemporaryBuffs::<T>::get(pet_id);
+        let now = frame_system::Pallet::<T>::block_number();
+        let before = buffs.len();
+        buffs.retain(|buff| buff.expires_at > now);
+        if buffs.len() != before {
+            if buffs.is_empty() {
+                crate::TemporaryBuffs::<T>::remove(pet_id);
+            } else {
+                crate::TemporaryBuffs::<T>::insert(pet_id, buffs.clone());
+            }
+        }
+        buffs
+    }
+}
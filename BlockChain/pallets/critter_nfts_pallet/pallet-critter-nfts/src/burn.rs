@@ -0,0 +1,68 @@
+//! # Pet Burning
+//!
+//! Burning destroys a pet for good, like a release, and pays its owner `BurnCompensation` PTCN
+//! from `LifecycleRewardPot`. The same release blockers apply, so a locked, traded, leased or
+//! otherwise held pet cannot be burned.
+//!
+//! The pet's final state is kept in `PetCemetery` as a `BurnRecord`, so former owners can still
+//! look it up. Pet IDs are never reused, so a record is never overwritten.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, pallet_prelude::RuntimeDebug, traits::Get};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use crate::release::ReleaseSystem;
+use crate::{BalanceOf, Config, Error, Event, Pallet, PetId, PetNft};
+
+/// The final snapshot of a burned pet.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct BurnRecord<T: Config> {
+    /// The pet as it was when it was burned
+    pub pet: PetNft<T>,
+
+    /// The account that burned the pet
+    pub owner: T::AccountId,
+
+    /// The block the pet was burned at
+    pub burned_at: BlockNumberFor<T>,
+
+    /// The PTCN paid to the owner
+    pub compensation: BalanceOf<T>,
+}
+
+/// A system for burning pets.
+pub struct BurnSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> BurnSystem<T> {
+    /// Burns a pet, pays its owner the compensation and keeps its final state in the cemetery.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account burning the pet; must own it
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was burned, Err otherwise
+    pub fn burn(owner: T::AccountId, pet_id: PetId) -> DispatchResult {
+        let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        ensure!(pet.owner == owner, Error::<T>::NotOwner);
+        ReleaseSystem::<T>::ensure_releasable(pet_id)?;
+
+        let compensation = T::BurnCompensation::get();
+        Pallet::<T>::pay_from_lifecycle_pot(&owner, compensation)?;
+        Pallet::<T>::do_release_pet(pet_id)?;
+
+        crate::PetCemetery::<T>::insert(pet_id, BurnRecord {
+            pet,
+            owner: owner.clone(),
+            burned_at: frame_system::Pallet::<T>::block_number(),
+            compensation,
+        });
+        Pallet::<T>::deposit_event(Event::PetNftBurned { owner, pet_id, compensation });
+        Ok(())
+    }
+}
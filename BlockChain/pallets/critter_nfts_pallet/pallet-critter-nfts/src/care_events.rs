@@ -0,0 +1,101 @@
+//! # Care Events
+//!
+//! Every care action a pet receives in this pallet is reported by a single event,
+//! `CareActionPerformed`, with a typed `CareActionKind`. Before it, indexers had to follow
+//! `PetFed`, `PetPlayedWith`, `GestureInteraction` and `TouchInteraction`, each with its own
+//! fields, and tell them apart from the status pallet's `PetFed` and `PetPlayed`.
+//!
+//! The care paths (`feed_pet`, `play_with_pet`, auto-care, gestures and touches) all report
+//! through `CareEventSystem::emit`, so each action emits exactly one `CareActionPerformed`.
+//! While `EmitLegacyCareEvents` is on, the legacy event of the same action is emitted next to
+//! it. The flag is kept for one release cycle, so indexers can switch over before the legacy
+//! events go. `pallet-critter-pet-status` keeps its needs events, which report how the pet's
+//! needs changed, and does not report the care action again.
+//!
+//! See CONFIG_MIGRATION.md for the indexer migration path.
+
+use frame_support::{pallet_prelude::RuntimeDebug, traits::Get};
+use crate::kinds::CareActionKind;
+use crate::traits::ItemId;
+use crate::{Config, Event, Pallet, PetId};
+
+/// How a care action changed a pet.
+#[derive(Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct CareOutcome {
+    /// The change in mood
+    pub mood_delta: i8,
+
+    /// The XP granted, including the rested bonus
+    pub xp_delta: u32,
+
+    /// The part of `xp_delta` paid from rested XP
+    pub rested_bonus_xp: u32,
+
+    /// The pet's mood after the action
+    pub new_mood: u8,
+}
+
+impl CareOutcome {
+    /// The outcome of moving a pet's mood from `old_mood` to `new_mood`, granting `xp` and
+    /// `rested_bonus_xp` on top.
+    pub fn new(old_mood: u8, new_mood: u8, xp: u32, rested_bonus_xp: u32) -> Self {
+        let mood_delta = (new_mood as i16 - old_mood as i16).clamp(i8::MIN as i16, i8::MAX as i16) as i8;
+        Self {
+            mood_delta,
+            xp_delta: xp.saturating_add(rested_bonus_xp),
+            rested_bonus_xp,
+            new_mood,
+        }
+    }
+}
+
+/// A system for reporting care actions.
+pub struct CareEventSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> CareEventSystem<T> {
+    /// Reports a care action: `CareActionPerformed`, then the legacy event of the same action
+    /// while `EmitLegacyCareEvents` is on.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet that received the care
+    /// * `actor` - The account that performed the action
+    /// * `kind` - The kind of care action
+    /// * `item` - The item consumed, if any
+    /// * `outcome` - How the action changed the pet
+    /// * `legacy` - The legacy event of the action, if it has one
+    pub fn emit(
+        pet_id: PetId,
+        actor: T::AccountId,
+        kind: CareActionKind,
+        item: Option<ItemId>,
+        outcome: CareOutcome,
+        legacy: Option<Event<T>>,
+    ) {
+        Pallet::<T>::deposit_event(Event::CareActionPerformed {
+            pet_id,
+            actor,
+            kind,
+            item,
+            mood_delta: outcome.mood_delta,
+            xp_delta: outcome.xp_delta,
+            block: frame_system::Pallet::<T>::block_number(),
+        });
+
+        if let Some(legacy) = legacy.filter(|_| T::EmitLegacyCareEvents::get()) {
+            Pallet::<T>::deposit_event(legacy);
+        }
+    }
+
+    /// The mood boost a legacy event reports; negative changes report zero.
+    pub fn legacy_mood_boost(outcome: &CareOutcome) -> u8 {
+        outcome.mood_delta.max(0) as u8
+    }
+
+    /// The XP a legacy event reports, before the rested bonus.
+    pub fn legacy_xp_gain(outcome: &CareOutcome) -> u32 {
+        outcome.xp_delta.saturating_sub(outcome.rested_bonus_xp)
+    }
+}
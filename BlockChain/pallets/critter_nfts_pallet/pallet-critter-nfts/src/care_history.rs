@@ -0,0 +1,135 @@
+//! # Care History
+//!
+//! This module counts the care actions each pet receives, so quests like "feed your pet 5
+//! times this week" can be checked without scraping events. Time is divided into epochs of
+//! `CareEpochBlocks` blocks, and each (pet, epoch) pair has a bucket in `CareActionBuckets`
+//! with a count of feeding and of play.
+//!
+//! Only the last `CareHistoryEpochs` epochs are kept. Pruning is lazy: the first action a pet
+//! receives in a new epoch removes its expired buckets, so a pet holds at most
+//! `CareHistoryEpochs` buckets and no hook is needed.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::Get};
+use scale_info::TypeInfo;
+use sp_runtime::SaturatedConversion;
+use sp_std::vec::Vec;
+use crate::{kinds::CareActionKind, Config, PetId};
+
+/// The care actions a pet received in one epoch, counted per kind.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CareActionCounts {
+    /// Times the pet was fed
+    pub feed: u32,
+
+    /// Times the pet was played with
+    pub play: u32,
+}
+
+impl CareActionCounts {
+    /// Returns the count of a kind. Kinds the history does not count are always zero.
+    pub fn get(&self, kind: CareActionKind) -> u32 {
+        match kind {
+            CareActionKind::Feed => self.feed,
+            CareActionKind::Play => self.play,
+            CareActionKind::Gesture | CareActionKind::Touch => 0,
+        }
+    }
+
+    /// Counts one more action of a kind. Kinds the history does not count are ignored.
+    pub fn bump(&mut self, kind: CareActionKind) {
+        let count = match kind {
+            CareActionKind::Feed => &mut self.feed,
+            CareActionKind::Play => &mut self.play,
+            CareActionKind::Gesture | CareActionKind::Touch => return,
+        };
+        *count = count.saturating_add(1);
+    }
+}
+
+/// A system for counting care actions per epoch.
+pub struct CareHistorySystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> CareHistorySystem<T> {
+    /// Returns the index of the epoch the current block falls in.
+    /// An epoch length of zero is treated as one block.
+    pub fn current_epoch() -> u32 {
+        let block = frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
+        let epoch_blocks = T::CareEpochBlocks::get().saturated_into::<u64>().max(1);
+        (block / epoch_blocks).saturated_into::<u32>()
+    }
+
+    /// Counts a care action in the pet's bucket for the current epoch.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet that received the care
+    /// * `kind` - The kind of care action
+    pub fn record(pet_id: PetId, kind: CareActionKind) {
+        let epoch = Self::current_epoch();
+
+        // 1. Prune the expired buckets when the pet's first action of an epoch opens a new one.
+        if !crate::CareActionBuckets::<T>::contains_key(pet_id, epoch) {
+            Self::prune(pet_id, epoch);
+        }
+
+        // 2. Count the action.
+        crate::CareActionBuckets::<T>::mutate(pet_id, epoch, |counts| counts.bump(kind));
+    }
+
+    /// Removes a pet's buckets that fall outside the last `CareHistoryEpochs` epochs.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet whose buckets to prune
+    /// * `current_epoch` - The current epoch index
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The number of buckets removed
+    pub fn prune(pet_id: PetId, current_epoch: u32) -> u32 {
+        let oldest_kept = Self::oldest_kept_epoch(current_epoch);
+        let expired: Vec<u32> = crate::CareActionBuckets::<T>::iter_key_prefix(pet_id)
+            .filter(|epoch| *epoch < oldest_kept)
+            .collect();
+
+        for epoch in &expired {
+            crate::CareActionBuckets::<T>::remove(pet_id, epoch);
+        }
+
+        expired.len() as u32
+    }
+
+    /// Sums the care actions of a kind a pet received in the last `window_epochs` epochs,
+    /// including the current one. The window is capped at `CareHistoryEpochs`, so buckets
+    /// awaiting pruning are never counted.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet to count for
+    /// * `kind` - The kind of care action
+    /// * `window_epochs` - How many epochs to look back over
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The number of actions in the window
+    pub fn count_in_window(pet_id: PetId, kind: CareActionKind, window_epochs: u32) -> u32 {
+        let window_epochs = window_epochs.min(T::CareHistoryEpochs::get());
+        if window_epochs == 0 {
+            return 0;
+        }
+
+        let current_epoch = Self::current_epoch();
+        let first_epoch = current_epoch.saturating_sub(window_epochs - 1);
+        (first_epoch..=current_epoch)
+            .map(|epoch| crate::CareActionBuckets::<T>::get(pet_id, epoch).get(kind))
+            .fold(0u32, |total, count| total.saturating_add(count))
+    }
+
+    /// Returns the oldest epoch still kept when `current_epoch` is the current one.
+    fn oldest_kept_epoch(current_epoch: u32) -> u32 {
+        current_epoch.saturating_sub(T::CareHistoryEpochs::get().saturating_sub(1))
+    }
+}
@@ -0,0 +1,492 @@
+//! # Pallet Configuration
+//!
+//! The pallet's configuration is split into capability-scoped sub-traits so each subsystem
+//! only declares what it needs, and a runtime can see at a glance which knobs belong together.
+//! The pallet's `Config` is the umbrella over all of them:
+//!
+//! - `NftCoreConfig`: currency, randomness, NFT bounds, care tuning, valuation, featured pets and
+//!   origins.
+//! - `SyncConfig`: synchronization hook bounds and execution.
+//! - `SocialConfig`: memories, skills, achievements, social interactions, environments and seasons.
+//! - `SessionConfig`: interaction history, behavior prediction, session buffs and (with the
+//!   `analytics` feature) analytics reports.
+//! - `UxConfig`: notifications, user achievements and onboarding.
+//!
+//! See `CONFIG_MIGRATION.md` for how to move an existing runtime onto this layout.
+
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Currency, Randomness, UnixTime},
+    PalletId,
+};
+use sp_runtime::{Perbill, Percent};
+use crate::traits::{BasicCareItemConsumer, CareError, ItemCategoryTag, ItemId as SharedItemId};
+use crate::leveling::LevelCurve;
+use crate::mint_preview::MintPreviewMode;
+use crate::pending_claims::OverflowPolicy;
+use crate::weights::WeightInfo;
+use crate::{BalanceOf, PetId};
+
+/// A `TimeProvider` for minimal runtimes without a timestamp pallet. Always reports zero,
+/// so events carry `unix_time_ms: 0`.
+pub struct NoTimeProvider;
+
+impl UnixTime for NoTimeProvider {
+    fn now() -> core::time::Duration {
+        core::time::Duration::ZERO
+    }
+}
+
+/// Core NFT configuration: tokens, randomness, NFT bounds, care tuning, auto-care, pending effects,
+/// trades, transfer gates, pending claims, sacrifices, mint previews, trait inheritance, lineage,
+/// rate limits, valuation and origins.
+pub trait NftCoreConfig: frame_system::Config {
+    /// The currency trait for handling PTCN token balances.
+    type Currency: Currency<Self::AccountId>;
+
+    /// The randomness trait for generating deterministic DNA hashes.
+    type PetRandomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+    /// Source of the unix time carried by user-facing events next to the block number
+    /// (e.g., `pallet_timestamp::Pallet<Runtime>`). Use `NoTimeProvider` to report zero.
+    type TimeProvider: UnixTime;
+
+    /// Maximum number of Pet NFTs an account can own. Crucial for limiting state bloat.
+    type MaxOwnedPets: Get<u32>;
+
+    /// Maximum length of a pet's species name (in bytes). Crucial for input validation.
+    type MaxSpeciesNameLen: Get<u32>;
+
+    /// Maximum length of a pet's current name (in bytes). Crucial for input validation.
+    type MaxPetNameLen: Get<u32>;
+
+    /// Maximum length of a single personality trait string (in bytes).
+    type MaxTraitStringLen: Get<u32>;
+
+    /// Maximum number of personality traits a pet can have.
+    type MaxPetPersonalityTraits: Get<u32>;
+
+    /// Maximum number of temporary traits a pet can hold. Temporary traits do not count
+    /// against `MaxPetPersonalityTraits`.
+    type MaxTemporaryTraits: Get<u32>;
+
+    /// Maximum value for `mood_indicator` (e.g., 100 or 200).
+    type MaxMoodValue: Get<u8>;
+
+    /// Amount of mood restored from basic feeding.
+    type FeedMoodBoost: Get<u8>;
+
+    /// Amount of mood restored from basic playing.
+    type PlayMoodBoost: Get<u8>;
+
+    /// XP gained from basic feeding.
+    type FeedXpGain: Get<u32>;
+
+    /// XP gained from basic playing.
+    type PlayXpGain: Get<u32>;
+
+    /// Mood penalty applied due to neglect.
+    type NeglectMoodPenalty: Get<u8>;
+
+    /// Number of blocks after which neglect effects might apply.
+    /// These care tuning constants are the defaults of the governable game parameters.
+    type NeglectThresholdBlocks: Get<Self::BlockNumber>;
+
+    /// Amount of PTCN claimed daily by users.
+    type DailyClaimAmount: Get<BalanceOf<Self>>;
+
+    /// Cooldown period (in blocks) for daily PTCN claims.
+    type ClaimCooldownPeriod: Get<Self::BlockNumber>;
+
+    /// Maximum number of lifecycle events a pet can experience.
+    type MaxLifecycleEvents: Get<u32>;
+
+    /// Maximum number of visual attributes a pet can have.
+    type MaxVisualAttributes: Get<u32>;
+
+    /// Maximum length of a pet's free-text bio (in bytes).
+    type MaxPetBioLen: Get<u32>;
+
+    /// Quality score weight per pet level.
+    type QualityLevelWeight: Get<u32>;
+
+    /// Quality score weight per point of base stat total.
+    type QualityStatWeight: Get<u32>;
+
+    /// Quality score weight per point of personality trait rarity.
+    type QualityTraitRarityWeight: Get<u32>;
+
+    /// Quality score weight per earned achievement.
+    type QualityAchievementWeight: Get<u32>;
+
+    /// Quality score weight per point of battle rating.
+    type QualityBattleRatingWeight: Get<u32>;
+
+    /// Quality score weight per prestige level.
+    type QualityPrestigeWeight: Get<u32>;
+
+    /// Quality score penalty per time the pet has been bred.
+    type QualityBreedPenalty: Get<u32>;
+
+    /// Quality score weight per breeding generation. Positive values make later generations
+    /// more valuable, negative values less.
+    type QualityGenerationWeight: Get<i32>;
+
+    /// Source of battle ratings for the quality score (e.g., pallet-critter-battle).
+    type BattleStats: crittercraft_traits::battle::BattleStatsProvider<PetId>;
+
+    /// Reports transfer restrictions held by other pallets (leases, quarantine, pending guarded
+    /// transfers, battles) for listing eligibility.
+    type TransferRestrictions: crittercraft_traits::nft::TransferRestrictionProvider<PetId>;
+
+    /// The subsystems of other pallets that hold references to pets (leases, parties, social
+    /// permissions), asked before a pet is released after the pallet's own. A tuple combines
+    /// several; `()` for none.
+    type ReleaseBlockers: crittercraft_traits::nft::ReleaseBlocker<PetId>;
+
+    /// Source of a pet's active conditions and their stat modifiers, for pet dossiers and effective
+    /// stats (e.g., pallet-critter-pet-status).
+    type PetConditions: crittercraft_traits::nft::PetConditionProvider<PetId>;
+
+    /// Source of the flat stat bonuses of a pet's equipment for effective stats (e.g., an
+    /// equipment pallet).
+    type Equipment: crittercraft_traits::nft::PetEquipmentProvider<PetId>;
+
+    /// Source of trait rarity, prestige and breed counts for the quality score.
+    type ValuationInputs: crate::valuation::ValuationInputs;
+
+    /// Origin allowed to force-clear pet bios flagged through content moderation.
+    type ModerationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Origin allowed to maintain game catalogs such as the social interaction type registry.
+    type CatalogOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Origin allowed to update pallet settings such as the onboarding starter pet.
+    type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Origin allowed to adjust the game parameters (mood boosts, XP gains, neglect tuning).
+    type ParamsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Origin allowed to move pets for a marketplace, e.g. to deliver a sale agreed off-chain.
+    /// It alone may skip the transfer gates; see `marketplace_transfer_pet`.
+    type MarketplaceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Origin of a collective, such as a guild council, acting as the account it resolves to.
+    /// It controls the pets that account owns in the owner-gated care, metadata and trade calls.
+    type CollectiveOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+    /// Provides the one-time stat growth and PTCN reward for each lifecycle stage.
+    type LifecycleStageRewards: crate::lifecycle::LifecycleStageRewards<BalanceOf<Self>>;
+
+    /// Account holding the pot that lifecycle stage PTCN rewards are paid from.
+    type LifecycleRewardPot: Get<Self::AccountId>;
+
+    /// Identifier of the account holding prepaid auto-care balances.
+    type AutoCarePalletId: Get<PalletId>;
+
+    /// The fee charged per auto-care interval.
+    type AutoCareFee: Get<BalanceOf<Self>>;
+
+    /// The part of each auto-care fee paid to whoever executes the care. The rest is burned.
+    type AutoCareBounty: Get<BalanceOf<Self>>;
+
+    /// Number of blocks between automated care executions.
+    type AutoCareInterval: Get<Self::BlockNumber>;
+
+    /// The effect of automated feeding and play relative to manual care.
+    type AutoCareEffect: Get<Percent>;
+
+    /// Maximum number of deferred effects other pallets can queue for a single block.
+    type MaxPendingEffectsPerBlock: Get<u32>;
+
+    /// Number of blocks between featured pet rotations. Zero turns the rotation off.
+    type FeatureRotationPeriod: Get<Self::BlockNumber>;
+
+    /// Maximum number of pets featured per rotation.
+    type FeaturedCount: Get<u32>;
+
+    /// Maximum number of drawn candidates a rotation may skip (missing, quarantined or already
+    /// drawn) before it settles for fewer than `FeaturedCount` pets.
+    type FeaturedMaxRetries: Get<u32>;
+
+    /// Maximum number of pet IDs `on_initialize` checks for neglect per block. Zero turns the
+    /// scan off.
+    type MaxNeglectChecksPerBlock: Get<u32>;
+
+    /// Maximum number of pets in one unsigned neglect batch, submitted by the off-chain worker
+    /// at most once per block. Capped at `neglect::NEGLECT_PAGE_SIZE`; zero turns the worker off.
+    type MaxNeglectBatchPerBlock: Get<u32>;
+
+    /// The one-time bonus paid from `LifecycleRewardPot` to the owner of each featured pet.
+    type FeaturedPetBonus: Get<BalanceOf<Self>>;
+
+    /// Number of blocks a proposed pet trade can be accepted for.
+    type TradeExpiryBlocks: Get<Self::BlockNumber>;
+
+    /// Number of blocks a pet must have been owned before it can be transferred or listed.
+    /// Zero turns the gate off.
+    type MinOwnershipBlocksBeforeTransfer: Get<Self::BlockNumber>;
+
+    /// Level a pet must have reached before it can be transferred or listed. Zero turns the
+    /// gate off.
+    type MinLevelForTransfer: Get<u32>;
+
+    /// What breeding does when the recipient already owns `MaxOwnedPets` pets: fail the mint,
+    /// or park the pet for the recipient to claim once they have room.
+    type BreedingOverflowPolicy: Get<OverflowPolicy>;
+
+    /// Number of blocks a parked pet can be claimed for before it is burned.
+    type PendingClaimExpiry: Get<Self::BlockNumber>;
+
+    /// Maximum number of pets parked for one recipient.
+    type MaxPendingClaims: Get<u32>;
+
+    /// Maximum number of pets `sacrifice_pets` burns in one call.
+    type MaxSacrificesPerCall: Get<u32>;
+
+    /// Maximum bonus a pet can receive from sacrifices over its lifetime, per bonus stat.
+    type MaxSacrificeBonus: Get<u8>;
+
+    /// Maximum number of account migration categories migrated per block, counting those
+    /// `accept_account_migration` migrates at once.
+    type AccountMigrationStepsPerBlock: Get<u32>;
+
+    /// The effect of free, item-less feeding and play relative to item-based care.
+    type FreeCareEffect: Get<Percent>;
+
+    /// Maximum number of free care actions per pet per care epoch (`CareEpochBlocks`).
+    type MaxFreeCarePerDay: Get<u32>;
+
+    /// The largest share of a fee an account can round up as a donation.
+    type MaxDonationPercent: Get<Perbill>;
+
+    /// The treasury account round-up donations to `DonationTarget::Treasury` go to.
+    type DonationTreasury: Get<Self::AccountId>;
+
+    /// The PTCN paid from `LifecycleRewardPot` to the owner of a burned pet.
+    type BurnCompensation: Get<BalanceOf<Self>>;
+
+    /// Maximum number of pets `batch_transfer_pet_nfts` moves in one call.
+    type MaxBatchTransferSize: Get<u32>;
+
+    /// How much of a mint's outcome the `mint_preview` runtime API reveals: the exact result of
+    /// a mint in the current block, or stat ranges and affinity odds over candidate outcomes.
+    type MintPreviewMode: Get<MintPreviewMode>;
+
+    /// Number of candidate outcomes a `Distribution` mint preview covers.
+    type MintPreviewSamples: Get<u32>;
+
+    /// Chance that a parent passes each of its traits on when breeding, unless the
+    /// `InheritanceTable` sets one for the trait.
+    type BaseInheritanceChance: Get<Perbill>;
+
+    /// Chance that each byte of a bred pet's DNA mutates to a random value after crossover.
+    type MutationRate: Get<Perbill>;
+
+    /// Maximum number of children indexed per pet for descendant queries.
+    type MaxChildrenPerPet: Get<u32>;
+
+    /// Maximum number of pets returned by a single lineage query.
+    type MaxLineageNodes: Get<u32>;
+
+    /// Rested XP a pet accrues per block without gaining XP.
+    type RestedXpPerBlock: Get<u32>;
+
+    /// Maximum rested XP a pet can accrue.
+    type MaxRestedXp: Get<u32>;
+
+    /// The bonus paid on XP matched by rested XP, relative to the matched XP.
+    type RestedXpBonus: Get<Percent>;
+
+    /// The XP curve: the XP a pet at each level needs for its next one. `LinearLevelCurve` is
+    /// 100 XP per level; `PowerLevelCurve` and `ExponentialLevelCurve` grow faster. Keep the curve
+    /// steep enough that a large XP grant cannot level a pet up thousands of times at once;
+    /// every level gained emits an event.
+    type LevelCurve: LevelCurve;
+
+    /// The highest level a pet can reach. XP a pet earns at this level is kept, but levels
+    /// nothing up.
+    type MaxLevel: Get<u32>;
+
+    /// Number of blocks in a care history epoch, the granularity of care action counts.
+    type CareEpochBlocks: Get<Self::BlockNumber>;
+
+    /// Number of epochs of care history kept per pet; older buckets are pruned.
+    type CareHistoryEpochs: Get<u32>;
+
+    /// Whether calls into deprecated interfaces are counted in `DeprecatedCallCounts`.
+    /// Each counted call costs a storage write; production runtimes can turn this off.
+    type TrackDeprecatedCalls: Get<bool>;
+
+    /// Whether care actions also emit their legacy events (`PetFed`, `PetPlayedWith`,
+    /// `GestureInteraction`, `TouchInteraction`) next to `CareActionPerformed`. Kept for one
+    /// release cycle while indexers move to the unified event.
+    type EmitLegacyCareEvents: Get<bool>;
+
+    /// Number of blocks in a rate limit window. Zero disables rate limiting.
+    type RateLimitWindow: Get<Self::BlockNumber>;
+
+    /// Feeds an account may make per rate limit window. Zero disables the limit.
+    type FeedRateLimit: Get<u32>;
+
+    /// Play sessions an account may make per rate limit window. Zero disables the limit.
+    type PlayRateLimit: Get<u32>;
+
+    /// Training sessions an account may make per rate limit window. Zero disables the limit.
+    type TrainRateLimit: Get<u32>;
+
+    /// Gestures an account may make per rate limit window. Zero disables the limit.
+    type GestureRateLimit: Get<u32>;
+
+    /// Touches an account may make per rate limit window. Zero disables the limit.
+    type TouchRateLimit: Get<u32>;
+
+    /// Social interactions an account may start per rate limit window. Zero disables the limit.
+    type SocialRateLimit: Get<u32>;
+
+    /// Memory writes an account may make per rate limit window. Zero disables the limit.
+    type MemoryRateLimit: Get<u32>;
+
+    /// Handler for consuming basic care items (Food, Toys).
+    /// This trait is from `crate::traits` and MUST be implemented by `pallet-items`.
+    /// Failures are reported as a `CareError` so each cause maps to its own pallet error.
+    type ItemHandler: BasicCareItemConsumer<Self::AccountId, SharedItemId, ItemCategoryTag, CareError>
+        + Get<ItemCategoryTag>;
+
+    /// Weight information for the benchmarked extrinsics and hooks. Use
+    /// `crate::weights::SubstrateWeights<Runtime>`, or `()` in tests.
+    type WeightInfo: WeightInfo;
+}
+
+/// Synchronization hook configuration.
+pub trait SyncConfig: frame_system::Config {
+    /// Maximum number of execution statistics entries to store per hook.
+    type MaxHookExecutionStats: Get<u32>;
+
+    /// Maximum number of synchronization status entries to store per pet.
+    type MaxSyncStatusEntries: Get<u32>;
+
+    /// Maximum number of hooks that can be registered.
+    type MaxRegisteredHooks: Get<u32>;
+
+    /// Maximum execution time for a hook in milliseconds.
+    type MaxHookExecutionTimeMs: Get<u32>;
+
+    /// Weighted consecutive failures after which a hook is disabled. Timeouts count double.
+    type HookAutoDisableThreshold: Get<u32>;
+
+    /// Maximum number of failed notifications queued for retry per hook. When full, the
+    /// oldest is dropped.
+    type MaxQueuedFailuresPerHook: Get<u32>;
+
+    /// Number of failed retries after which a failed notification is dropped.
+    type MaxNotificationRetries: Get<u32>;
+
+    /// Blocks before the first retry of a failed notification. The n-th retry waits
+    /// `2^n * RetryBaseBlocks` blocks.
+    type RetryBaseBlocks: Get<Self::BlockNumber>;
+
+    /// Delivers state change notifications to registered hooks.
+    type HookExecutor: crate::sync::HookExecutor<Self>;
+}
+
+/// Configuration for a pet's social life and growth: memories, skills, achievements,
+/// social interactions, environments and seasonal events.
+pub trait SocialConfig: frame_system::Config {
+    /// Maximum number of memories a pet can have.
+    type MaxPetMemories: Get<u32>;
+
+    /// Maximum significance of a memory.
+    type MaxMemorySignificance: Get<u8>;
+
+    /// Maximum number of skills a pet can have.
+    type MaxPetSkills: Get<u32>;
+
+    /// Maximum level of a skill.
+    type MaxSkillLevel: Get<u8>;
+
+    /// Maximum number of achievements a pet can earn.
+    type MaxPetAchievements: Get<u32>;
+
+    /// The spacing of achievement progress milestones: `AchievementProgressed` is emitted each
+    /// time a pet's progress towards a countable achievement crosses a multiple of it below
+    /// 100%. Zero turns the milestone events off.
+    type AchievementMilestoneStep: Get<Percent>;
+
+    /// Maximum number of social interactions a pet can have per block.
+    type MaxSocialInteractionsPerBlock: Get<u32>;
+
+    /// Maximum mood change from a social interaction.
+    type MaxMoodChangeFromSocialInteraction: Get<u8>;
+
+    /// Maximum number of environments a pet can adapt to.
+    type MaxEnvironmentalAdaptations: Get<u32>;
+
+    /// Maximum adaptation level to an environment.
+    type MaxAdaptationLevel: Get<u8>;
+
+    /// Adaptation level a pet must exceed in its species' preferred environment to get
+    /// `PreferredEnvironmentBonus`.
+    type PreferredEnvironmentThreshold: Get<u8>;
+
+    /// Extra mood and XP gains from care for a pet adapted to its species' preferred
+    /// environment, as a share of the scaled gains.
+    type PreferredEnvironmentBonus: Get<Percent>;
+
+    /// Maximum number of seasonal events that can be active at once.
+    type MaxActiveSeasonalEvents: Get<u32>;
+
+    /// Maximum effect magnitude of a seasonal event.
+    type MaxSeasonalEventEffectMagnitude: Get<u8>;
+}
+
+/// Configuration for interaction history, behavior prediction, session buffs and analytics.
+pub trait SessionConfig: frame_system::Config {
+    /// Maximum size of a pet's compressed interaction history.
+    type MaxInteractionHistorySize: Get<u32>;
+
+    /// Maximum number of behavior predictions to store for a pet.
+    type MaxBehaviorPredictions: Get<u32>;
+
+    /// Maximum number of state transition probabilities to store for a pet.
+    type MaxTransitionProbabilities: Get<u32>;
+
+    /// Threshold for adaptive behavior adjustments.
+    type AdaptiveBehaviorThreshold: Get<u32>;
+
+    /// Maximum number of temporary buffs a pet can have at once.
+    type MaxActiveBuffs: Get<u32>;
+
+    /// Number of blocks a temporary buff lasts.
+    type BuffDurationBlocks: Get<Self::BlockNumber>;
+
+    /// The session quality score, from 0 to 100, a session needs to earn its pet buffs.
+    type SessionBuffQualityThreshold: Get<u8>;
+
+    /// How much the buffs earned by a session boost XP and mood gains by.
+    type SessionBuffMagnitude: Get<Percent>;
+
+    /// Maximum size of an analytics report.
+    #[cfg(feature = "analytics")]
+    type MaxAnalyticsReportSize: Get<u32>;
+}
+
+/// User experience configuration: notifications, user achievements and onboarding.
+pub trait UxConfig: frame_system::Config {
+    /// Maximum number of notifications a user can have.
+    type MaxNotifications: Get<u32>;
+
+    /// Maximum number of achievements a user can have.
+    type MaxAchievements: Get<u32>;
+
+    /// Notifications with a priority below this are folded into the digest for users in digest mode.
+    /// Notifications at or above it are critical and always delivered individually.
+    type DigestPriorityThreshold: Get<u8>;
+
+    /// Maximum number of notifications generated per block across all users. Further
+    /// non-critical notifications in the block are dropped; critical ones still go through.
+    type MaxNotificationsPerBlock: Get<u32>;
+
+    /// The UX flow whose completion grants new users a starter pet.
+    type OnboardingFlowId: Get<u16>;
+}
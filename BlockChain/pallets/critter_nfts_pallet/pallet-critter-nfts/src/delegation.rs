@@ -0,0 +1,139 @@
+//! # Pet Delegation
+//!
+//! An owner can lend a pet to a friend for care without transferring it. `delegate_pet` records
+//! a `PetDelegation` in `PetDelegations` until `expiry_block`; while it lasts, only the delegate
+//! can run the care calls (`feed_pet`, `play_with_pet`, `pet_basic_care` and `train_pet`), with
+//! their own items and rate limits. The owner keeps every other right: transfers, trades, locks,
+//! metadata and release.
+//!
+//! The owner can end a delegation early with `revoke_delegation`. An expired delegation no
+//! longer counts in the care calls' guard and is removed at the owner's next care call. Any
+//! transfer of the pet ends its delegation; an account migration keeps it, since only the pet's
+//! owner changes.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::{DispatchError, DispatchResult}, ensure, pallet_prelude::RuntimeDebug};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use crate::{Config, Error, Event, Pallet, PetId, PetNft};
+
+/// A pet lent to another account for care.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct PetDelegation<T: Config> {
+    /// The account caring for the pet
+    pub delegate: T::AccountId,
+
+    /// The block the delegation was made at
+    pub delegated_at: BlockNumberFor<T>,
+
+    /// The first block the delegation no longer applies at
+    pub expiry_block: BlockNumberFor<T>,
+}
+
+/// A system for lending pets for care.
+pub struct DelegationSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> DelegationSystem<T> {
+    /// Lends a pet to `delegate` until `expiry_block`, replacing any delegation it has.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account lending the pet; must own it
+    /// * `pet_id` - The ID of the pet
+    /// * `delegate` - The account caring for the pet
+    /// * `expiry_block` - The first block the delegation no longer applies at
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was delegated, Err otherwise
+    pub fn delegate(
+        owner: T::AccountId,
+        pet_id: PetId,
+        delegate: T::AccountId,
+        expiry_block: BlockNumberFor<T>,
+    ) -> DispatchResult {
+        Pallet::<T>::owned_pet(&owner, pet_id)?;
+        ensure!(delegate != owner, Error::<T>::CannotDelegateToOwner);
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(expiry_block > now, Error::<T>::DelegationExpiryInPast);
+
+        crate::PetDelegations::<T>::insert(pet_id, PetDelegation {
+            delegate: delegate.clone(),
+            delegated_at: now,
+            expiry_block,
+        });
+        Pallet::<T>::deposit_event(Event::PetDelegated { owner, pet_id, delegate, expiry_block });
+        Ok(())
+    }
+
+    /// Ends a pet's delegation before it expires.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account that lent the pet; must own it
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the delegation was ended, Err if there is none or the caller
+    ///   does not own the pet
+    pub fn revoke(owner: T::AccountId, pet_id: PetId) -> DispatchResult {
+        Pallet::<T>::owned_pet(&owner, pet_id)?;
+        let delegation = crate::PetDelegations::<T>::take(pet_id).ok_or(Error::<T>::DelegationNotFound)?;
+
+        Pallet::<T>::deposit_event(Event::DelegationRevoked { owner, pet_id, delegate: delegation.delegate });
+        Ok(())
+    }
+
+    /// Gets the account a pet is delegated to, if its delegation has not expired.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T::AccountId>` - The delegate, or `None` if the pet is not delegated
+    pub fn active_delegate(pet_id: PetId) -> Option<T::AccountId> {
+        crate::PetDelegations::<T>::get(pet_id)
+            .filter(|delegation| frame_system::Pallet::<T>::block_number() < delegation.expiry_block)
+            .map(|delegation| delegation.delegate)
+    }
+
+    /// Checks that `who` may care for a pet: its delegate while it is delegated, its owner
+    /// otherwise. An expired delegation is removed once its owner cares for the pet again.
+    ///
+    /// # Parameters
+    ///
+    /// * `who` - The account calling a care extrinsic
+    /// * `pet` - The pet
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), DispatchError>` - Ok if `who` may care for the pet, `PetIsDelegated` for
+    ///   the owner of a delegated pet, `NotOwner` for anyone else
+    pub fn ensure_caretaker(who: &T::AccountId, pet: &PetNft<T>) -> Result<(), DispatchError> {
+        let now = frame_system::Pallet::<T>::block_number();
+        match crate::PetDelegations::<T>::get(pet.id) {
+            Some(delegation) if now < delegation.expiry_block => {
+                if delegation.delegate == *who {
+                    Ok(())
+                } else if pet.owner == *who {
+                    Err(Error::<T>::PetIsDelegated.into())
+                } else {
+                    Err(Error::<T>::NotOwner.into())
+                }
+            }
+            expired => {
+                ensure!(pet.owner == *who, Error::<T>::NotOwner);
+                if expired.is_some() {
+                    crate::PetDelegations::<T>::remove(pet.id);
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! # Deprecated Call Tracking
+//!
+//! This module counts calls into the legacy trait surface that is kept for backward
+//! compatibility, so we can see which interfaces external pallets still use before removing
+//! them. Each legacy method bumps its (interface, method) counter in `DeprecatedCallCounts`
+//! exactly once per call; the counters are read through the `deprecated_call_counts` runtime
+//! API. Counting costs a storage write per call, so production runtimes can turn it off with
+//! `TrackDeprecatedCalls`.
+//!
+//! Calls made by this pallet itself go through internal helpers and are not counted.
+//!
+//! | Interface            | Code | Method                   | Code |
+//! |----------------------|------|--------------------------|------|
+//! | `SharedNftManager`   | 0    | `owner_of`               | 0    |
+//! | `SharedNftManager`   | 0    | `is_transferable`        | 1    |
+//! | `SharedNftManager`   | 0    | `lock_nft`               | 2    |
+//! | `SharedNftManager`   | 0    | `unlock_nft`             | 3    |
+//! | `SharedNftManager`   | 0    | `transfer_nft`           | 4    |
+//! | `SharedNftManager`   | 0    | `can_receive_pet`        | 5    |
+//! | `SharedNftManager`   | 0    | `remaining_capacity`     | 6    |
+//! | `SharedNftManager`   | 0    | `minter_of` (retired)    | 7    |
+//! | `NftBreedingHandler` | 1    | `mint_pet_from_breeding` | 0    |
+//!
+//! `mint_pet_from_breeding` is the old mint path, superseded by `NftManagement::mint`.
+//! `minter_of` moved to the non-deprecated `PetMinterProvider` and is no longer counted; its
+//! code stays reserved.
+//!
+//! Codes are part of the runtime API and must never change; new ones are appended.
+
+use frame_support::traits::Get;
+use sp_std::vec::Vec;
+use crate::Config;
+
+/// Interface: the legacy `SharedNftManager` trait.
+pub const INTERFACE_SHARED_NFT_MANAGER: u8 = 0;
+
+/// Interface: the legacy mint path of `NftBreedingHandler`.
+pub const INTERFACE_NFT_BREEDING_HANDLER: u8 = 1;
+
+/// `SharedNftManager::owner_of`.
+pub const METHOD_OWNER_OF: u8 = 0;
+
+/// `SharedNftManager::is_transferable`.
+pub const METHOD_IS_TRANSFERABLE: u8 = 1;
+
+/// `SharedNftManager::lock_nft`.
+pub const METHOD_LOCK_NFT: u8 = 2;
+
+/// `SharedNftManager::unlock_nft`.
+pub const METHOD_UNLOCK_NFT: u8 = 3;
+
+/// `SharedNftManager::transfer_nft`.
+pub const METHOD_TRANSFER_NFT: u8 = 4;
+
+/// `SharedNftManager::can_receive_pet`.
+pub const METHOD_CAN_RECEIVE_PET: u8 = 5;
+
+/// `SharedNftManager::remaining_capacity`.
+pub const METHOD_REMAINING_CAPACITY: u8 = 6;
+
+/// `SharedNftManager::minter_of`, retired: `PetMinterProvider::minter_of` is not counted.
+pub const METHOD_MINTER_OF: u8 = 7;
+
+/// `NftBreedingHandler::mint_pet_from_breeding`.
+pub const METHOD_MINT_PET_FROM_BREEDING: u8 = 0;
+
+/// A counter of calls into deprecated interfaces.
+pub struct DeprecationTracker<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> DeprecationTracker<T> {
+    /// Counts one call of a legacy method, unless tracking is disabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `interface` - The legacy interface (one of the `INTERFACE_*` constants)
+    /// * `method` - The method within the interface (one of the `METHOD_*` constants)
+    pub fn record(interface: u8, method: u8) {
+        if !T::TrackDeprecatedCalls::get() {
+            return;
+        }
+        crate::DeprecatedCallCounts::<T>::mutate(interface, method, |count| *count = count.saturating_add(1));
+    }
+
+    /// Lists every counter that has been bumped, as (interface, method, calls).
+    pub fn counts() -> Vec<(u8, u8, u64)> {
+        crate::DeprecatedCallCounts::<T>::iter().collect()
+    }
+}
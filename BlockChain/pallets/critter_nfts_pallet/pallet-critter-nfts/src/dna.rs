@@ -0,0 +1,201 @@
+//! # Mint DNA
+//!
+//! A minted pet's DNA is the SHA256 hash of an entropy preimage, and its charter attributes
+//! are derived from the DNA. The preimage combines:
+//!
+//! - the block's `PetRandomness` seed;
+//! - the owner, the pet ID, the species and the name;
+//! - the index of the minting extrinsic in the block (zero outside an extrinsic);
+//! - the owner's account nonce;
+//! - the number of pets minted earlier in the same block.
+//!
+//! Some randomness providers return the same seed for a whole block, or even a whole session.
+//! The extrinsic index, nonce and mint counter keep two mints in one block apart, even by the
+//! same account with the same species and name.
+//!
+//! As a last resort, a derived DNA is checked against the `RECENT_DNA_CAPACITY` most recently
+//! minted DNAs. On an exact collision the hash is perturbed with a retry nonce and checked
+//! again, up to `MAX_DNA_RETRIES` times, so no two recent pets are twins.
+
+use codec::Encode;
+use frame_support::{
+    pallet_prelude::RuntimeDebug,
+    traits::{ConstU32, Randomness},
+    BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use crate::traits::DnaHashType;
+use crate::{Config, PetId};
+
+/// How many of the most recently minted DNAs are kept for collision checks.
+pub const RECENT_DNA_CAPACITY: u32 = 16;
+
+/// How many times a colliding DNA is perturbed before it is accepted as is.
+pub const MAX_DNA_RETRIES: u8 = 8;
+
+/// The recently minted DNAs, oldest first.
+pub type RecentDnaBuffer = BoundedVec<DnaHashType, ConstU32<RECENT_DNA_CAPACITY>>;
+
+/// The entropy a mint mixes in besides the randomness seed and the mint inputs.
+#[derive(Clone, Encode, PartialEq, Eq, RuntimeDebug)]
+pub struct MintEntropy<Nonce> {
+    /// The index of the minting extrinsic in the block
+    pub extrinsic_index: u32,
+
+    /// The owner's account nonce
+    pub account_nonce: Nonce,
+
+    /// The number of pets minted earlier in the block
+    pub mint_counter: u32,
+}
+
+/// The `MintEntropy` of a mint by an account of runtime `T`.
+pub type MintEntropyOf<T> = MintEntropy<<T as frame_system::Config>::Index>;
+
+/// Hashes a DNA preimage. This is the whole DNA formula: stable for fixed inputs and free of
+/// runtime state, so off-chain simulators can reproduce it from the `test_vectors` fixtures.
+///
+/// # Parameters
+///
+/// * `seed` - The block's randomness seed
+/// * `owner` - The minting account
+/// * `pet_id` - The pet's ID
+/// * `species` - The pet's species
+/// * `name` - The pet's name
+/// * `entropy` - The mint's extrinsic index, nonce and mint counter
+///
+/// # Returns
+///
+/// * `DnaHashType` - The DNA, before collision checks
+pub fn derive_dna<Seed: Encode, AccountId: Encode, Species: Encode, Name: Encode, Nonce: Encode>(
+    seed: &Seed,
+    owner: &AccountId,
+    pet_id: PetId,
+    species: &Species,
+    name: &Name,
+    entropy: &MintEntropy<Nonce>,
+) -> DnaHashType {
+    // A full SHA256 hash (32 bytes) for DnaHashType, not Blake2_128 (16 bytes).
+    sp_io::hashing::sha256(&(seed, owner, pet_id, species, name, entropy).encode())
+}
+
+/// A system for deriving the DNA of minted pets.
+pub struct DnaSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> DnaSystem<T> {
+    /// Derives the DNA of a pet minted by `owner` as `pet_id` in the current block, avoiding
+    /// the DNA of recent mints. Does not record the DNA; minting does that with `note_minted`.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The minting account
+    /// * `pet_id` - The pet's ID
+    /// * `species` - The pet's species
+    /// * `name` - The pet's name
+    ///
+    /// # Returns
+    ///
+    /// * `DnaHashType` - The pet's DNA
+    pub fn mint_dna<Species: Encode, Name: Encode>(
+        owner: &T::AccountId,
+        pet_id: PetId,
+        species: &Species,
+        name: &Name,
+    ) -> DnaHashType {
+        let (dna_seed, _) = T::PetRandomness::random_seed();
+        let dna = Self::derive(&dna_seed, owner, pet_id, species, name, &Self::current_entropy(owner));
+        Self::avoid_recent(dna)
+    }
+
+    /// Hashes the DNA preimage with `derive_dna`. Stable for fixed inputs.
+    ///
+    /// # Parameters
+    ///
+    /// * `seed` - The block's randomness seed
+    /// * `owner` - The minting account
+    /// * `pet_id` - The pet's ID
+    /// * `species` - The pet's species
+    /// * `name` - The pet's name
+    /// * `entropy` - The mint's extrinsic index, nonce and mint counter
+    ///
+    /// # Returns
+    ///
+    /// * `DnaHashType` - The DNA, before collision checks
+    pub fn derive<Species: Encode, Name: Encode>(
+        seed: &T::Hash,
+        owner: &T::AccountId,
+        pet_id: PetId,
+        species: &Species,
+        name: &Name,
+        entropy: &MintEntropyOf<T>,
+    ) -> DnaHashType {
+        derive_dna(seed, owner, pet_id, species, name, entropy)
+    }
+
+    /// The entropy of a mint by `owner` at this point of the current block.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The minting account
+    ///
+    /// # Returns
+    ///
+    /// * `MintEntropyOf<T>` - The extrinsic index, nonce and mint counter
+    pub fn current_entropy(owner: &T::AccountId) -> MintEntropyOf<T> {
+        MintEntropy {
+            extrinsic_index: frame_system::Pallet::<T>::extrinsic_index().unwrap_or(0),
+            account_nonce: frame_system::Pallet::<T>::account_nonce(owner),
+            mint_counter: Self::mints_this_block(),
+        }
+    }
+
+    /// The number of pets minted so far in the current block.
+    pub fn mints_this_block() -> u32 {
+        let (block, count) = crate::MintsThisBlock::<T>::get();
+        if block == frame_system::Pallet::<T>::block_number() { count } else { 0 }
+    }
+
+    /// Perturbs `dna` with a retry nonce until it differs from every recently minted DNA, at
+    /// most `MAX_DNA_RETRIES` times.
+    ///
+    /// # Parameters
+    ///
+    /// * `dna` - The derived DNA
+    ///
+    /// # Returns
+    ///
+    /// * `DnaHashType` - `dna` if it is new, or its first perturbation that is
+    pub fn avoid_recent(dna: DnaHashType) -> DnaHashType {
+        let recent = crate::RecentDna::<T>::get();
+        let mut candidate = dna;
+        for retry in 1..=MAX_DNA_RETRIES {
+            if !recent.contains(&candidate) {
+                break;
+            }
+            candidate = sp_io::hashing::sha256(&(dna, retry).encode());
+        }
+        candidate
+    }
+
+    /// Records the DNA of a minted pet: counts the mint towards the block's mint counter and
+    /// adds the DNA to the recent DNAs, evicting the oldest when full.
+    ///
+    /// # Parameters
+    ///
+    /// * `dna` - The minted pet's DNA
+    pub fn note_minted(dna: DnaHashType) {
+        let now: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let count = Self::mints_this_block().saturating_add(1);
+        crate::MintsThisBlock::<T>::put((now, count));
+
+        crate::RecentDna::<T>::mutate(|recent| {
+            if recent.is_full() {
+                recent.remove(0);
+            }
+            // Cannot fail: there is room after the eviction.
+            let _ = recent.try_push(dna);
+        });
+    }
+}
@@ -0,0 +1,130 @@
+//! # Round-Up Donations
+//!
+//! An opt-in way to fund public goods. An account that sets a round-up with
+//! `set_donation_roundup` adds `percent` (at most `MaxDonationPercent`) on top of every PTCN fee
+//! it pays this pallet, sent to its beneficiary: the treasury (`DonationTreasury`) or a
+//! community pot registered by `UpdateOrigin` with `set_community_pot`. The fees rounded up are
+//! the auto-care prepayments of `subscribe_auto_care`.
+//!
+//! A donation never fails the action it rounds up. If the transfer fails, or the pot has been
+//! unregistered since, the donation is skipped with `DonationSkipped` and the action goes ahead.
+//! What an account has donated in total is kept in `TotalDonated`, for reputation scores.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    pallet_prelude::RuntimeDebug,
+    storage::with_storage_layer,
+    traits::{Currency, ExistenceRequirement, Get},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::{Saturating, Zero}, Perbill};
+use crate::{BalanceOf, Config, Error, Event, Pallet};
+
+/// Identifier of a registered community pot.
+pub type CommunityPotId = u32;
+
+/// Where an account's round-up donations go.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DonationTarget {
+    /// The treasury account, `DonationTreasury`
+    Treasury,
+
+    /// A community pot registered with `set_community_pot`
+    CommunityPot(CommunityPotId),
+}
+
+/// An account's round-up donation preference.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DonationPreference {
+    /// The share of each fee added on top as a donation
+    pub percent: Perbill,
+
+    /// Where the donations go
+    pub beneficiary: DonationTarget,
+}
+
+/// A system for round-up donations.
+pub struct DonationSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> DonationSystem<T> {
+    /// Sets or clears an account's round-up preference.
+    ///
+    /// # Parameters
+    ///
+    /// * `account` - The account
+    /// * `preference` - The preference, or `None` to stop donating
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the preference was stored, Err if the percent is above
+    ///   `MaxDonationPercent` or the pot is not registered
+    pub fn set_preference(account: T::AccountId, preference: Option<DonationPreference>) -> DispatchResult {
+        match preference {
+            Some(preference) => {
+                ensure!(preference.percent <= T::MaxDonationPercent::get(), Error::<T>::DonationPercentTooHigh);
+                ensure!(Self::beneficiary_account(preference.beneficiary).is_some(), Error::<T>::CommunityPotNotFound);
+                crate::DonationPreferences::<T>::insert(&account, preference);
+            }
+            None => crate::DonationPreferences::<T>::remove(&account),
+        }
+
+        Pallet::<T>::deposit_event(Event::DonationPreferenceSet { account, preference });
+        Ok(())
+    }
+
+    /// Gets the round-up an account adds to a fee under its preference.
+    ///
+    /// # Parameters
+    ///
+    /// * `account` - The account paying the fee
+    /// * `fee` - The fee
+    ///
+    /// # Returns
+    ///
+    /// * `BalanceOf<T>` - The donation, zero if the account does not round up
+    pub fn round_up(account: &T::AccountId, fee: BalanceOf<T>) -> BalanceOf<T> {
+        crate::DonationPreferences::<T>::get(account)
+            .map(|preference| preference.percent.min(T::MaxDonationPercent::get()) * fee)
+            .unwrap_or_else(Zero::zero)
+    }
+
+    /// Donates the round-up on a fee `donor` paid, if it rounds up. Never fails: a donation
+    /// that cannot be made is skipped with `DonationSkipped`.
+    ///
+    /// # Parameters
+    ///
+    /// * `donor` - The account that paid the fee
+    /// * `fee` - The fee
+    pub fn donate_on_fee(donor: &T::AccountId, fee: BalanceOf<T>) {
+        let Some(preference) = crate::DonationPreferences::<T>::get(donor) else {
+            return;
+        };
+        let amount = Self::round_up(donor, fee);
+        if amount.is_zero() {
+            return;
+        }
+
+        let beneficiary = preference.beneficiary;
+        let donated = Self::beneficiary_account(beneficiary).map_or(false, |account| {
+            with_storage_layer(|| T::Currency::transfer(donor, &account, amount, ExistenceRequirement::KeepAlive)).is_ok()
+        });
+        if donated {
+            crate::TotalDonated::<T>::mutate(donor, |total| *total = total.saturating_add(amount));
+            Pallet::<T>::deposit_event(Event::DonationMade { donor: donor.clone(), beneficiary, amount });
+        } else {
+            Pallet::<T>::deposit_event(Event::DonationSkipped { donor: donor.clone(), beneficiary, amount });
+        }
+    }
+
+    /// Gets the account a donation target pays into, or `None` for an unregistered pot.
+    fn beneficiary_account(target: DonationTarget) -> Option<T::AccountId> {
+        match target {
+            DonationTarget::Treasury => Some(T::DonationTreasury::get()),
+            DonationTarget::CommunityPot(pot_id) => crate::CommunityPots::<T>::get(pot_id),
+        }
+    }
+}
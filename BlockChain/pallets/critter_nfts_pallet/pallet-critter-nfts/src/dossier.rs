@@ -0,0 +1,263 @@
+//! # Pet Dossiers
+//!
+//! A pet dossier bundles everything the chain knows about one pet into a single response, for
+//! data export requests and support tickets. It backs the `pet_dossier` runtime API, so support
+//! staff and owners can fetch it from any node without scraping storage.
+//!
+//! The caller picks the sections it needs with a `DOSSIER_*` bitmask; sections left out are
+//! `None`. Every section is bounded so a dossier always fits in one response:
+//!
+//! - List sections (skills, memories, achievements, lifecycle events, conditions) are cut at
+//!   `SECTION_SIZE_LIMIT` encoded bytes each, oldest entries first, and flagged as truncated
+//!   when cut short. Memories carry their metadata, the length of their data and only the first
+//!   `MEMORY_DATA_PREVIEW_LEN` bytes of it.
+//! - Fixed-size records (the pet, its owner, status, social summary and battle record) are
+//!   bounded by their `MaxEncodedLen`, which `integrity_test` checks against
+//!   `RECORD_SIZE_LIMIT`.
+//!
+//! Together, an encoded dossier never exceeds `DOSSIER_SIZE_LIMIT` bytes.
+//!
+//! Quarantine and leases are reported through the status section: a lease holds the pet's lock,
+//! and quarantine is reported by `TransferRestrictions`. Active conditions come from
+//! `PetConditions` and the battle record from `BattleStats`.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use crittercraft_traits::battle::{BattleRecord, BattleStatsProvider};
+use crittercraft_traits::nft::{PetConditionProvider, TransferRestrictionProvider};
+use crittercraft_traits::types::TransferBlockReason;
+use frame_support::pallet_prelude::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use crate::kinds::{LifecycleEventKind, MemoryKind, SkillKind};
+use crate::{Config, LockInfo, PetId, PetNft};
+
+/// Dossier section: the pet record.
+pub const DOSSIER_PET: u32 = 1 << 0;
+/// Dossier section: the pet's owner.
+pub const DOSSIER_OWNER: u32 = 1 << 1;
+/// Dossier section: lock, lease and quarantine status.
+pub const DOSSIER_STATUS: u32 = 1 << 2;
+/// Dossier section: skills.
+pub const DOSSIER_SKILLS: u32 = 1 << 3;
+/// Dossier section: memories.
+pub const DOSSIER_MEMORIES: u32 = 1 << 4;
+/// Dossier section: achievements.
+pub const DOSSIER_ACHIEVEMENTS: u32 = 1 << 5;
+/// Dossier section: lifecycle events.
+pub const DOSSIER_LIFECYCLE: u32 = 1 << 6;
+/// Dossier section: social bond summary.
+pub const DOSSIER_SOCIAL: u32 = 1 << 7;
+/// Dossier section: battle record.
+pub const DOSSIER_BATTLE: u32 = 1 << 8;
+/// Dossier section: active conditions.
+pub const DOSSIER_CONDITIONS: u32 = 1 << 9;
+/// Every dossier section.
+pub const DOSSIER_ALL: u32 = DOSSIER_PET
+    | DOSSIER_OWNER
+    | DOSSIER_STATUS
+    | DOSSIER_SKILLS
+    | DOSSIER_MEMORIES
+    | DOSSIER_ACHIEVEMENTS
+    | DOSSIER_LIFECYCLE
+    | DOSSIER_SOCIAL
+    | DOSSIER_BATTLE
+    | DOSSIER_CONDITIONS;
+
+/// The most encoded bytes one list section takes, including its length prefix, its truncation
+/// flag and the `Option` around it.
+pub const SECTION_SIZE_LIMIT: usize = 1024;
+
+/// The most encoded bytes the fixed-size records of a dossier take together.
+pub const RECORD_SIZE_LIMIT: usize = 2048;
+
+/// The most encoded bytes a dossier takes: five list sections, the records and the pet ID.
+pub const DOSSIER_SIZE_LIMIT: usize = 8192;
+
+/// How many bytes of a memory's data a dossier includes.
+pub const MEMORY_DATA_PREVIEW_LEN: usize = 64;
+
+/// Bytes a list section spends besides its entries: the `Option` tag, a compact length prefix
+/// of up to four bytes and the truncation flag, rounded up.
+const SECTION_OVERHEAD: usize = 8;
+
+/// A list section of a dossier.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DossierSection<V> {
+    /// The entries that fit, oldest first
+    pub entries: Vec<V>,
+
+    /// Whether entries were left out to respect `SECTION_SIZE_LIMIT`
+    pub truncated: bool,
+}
+
+/// A memory's metadata and the start of its data.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MemorySummary {
+    /// The kind of memory
+    pub kind: MemoryKind,
+
+    /// How significant the memory is to the pet
+    pub significance: u8,
+
+    /// When the memory was formed
+    pub timestamp: u64,
+
+    /// The full length of the memory's data
+    pub data_len: u32,
+
+    /// The first `MEMORY_DATA_PREVIEW_LEN` bytes of the memory's data
+    pub data_preview: Vec<u8>,
+}
+
+/// Whether a pet is locked, leased or otherwise unable to move.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DossierStatus<BlockNumber> {
+    /// The pet's lock, if any; a leased pet is locked by the lease
+    pub lock: Option<LockInfo<BlockNumber>>,
+
+    /// Why another pallet blocks the pet's transfer (quarantine, battle, ...), if it does
+    pub transfer_restriction: Option<TransferBlockReason>,
+}
+
+/// A summary of a pet's social bonds.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SocialBondSummary {
+    /// The number of pets this pet has bonded with
+    pub bonds: u32,
+
+    /// The strongest bond, as (other pet, bond strength)
+    pub strongest: Option<(PetId, u8)>,
+
+    /// The interactions counted across all bonds
+    pub total_interactions: u32,
+}
+
+/// Everything the chain knows about one pet. Sections not requested are `None`.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PetDossier<AccountId, BlockNumber, Pet> {
+    /// The pet the dossier is about
+    pub pet_id: PetId,
+
+    /// The pet record
+    pub pet: Option<Pet>,
+
+    /// The pet's owner
+    pub owner: Option<AccountId>,
+
+    /// Lock, lease and quarantine status
+    pub status: Option<DossierStatus<BlockNumber>>,
+
+    /// Skills, as (skill, level)
+    pub skills: Option<DossierSection<(SkillKind, u8)>>,
+
+    /// Memories, with their data capped
+    pub memories: Option<DossierSection<MemorySummary>>,
+
+    /// Achievements, as (achievement ID, timestamp)
+    pub achievements: Option<DossierSection<(u32, u64)>>,
+
+    /// Lifecycle events, as (event, block)
+    pub lifecycle_events: Option<DossierSection<(LifecycleEventKind, BlockNumber)>>,
+
+    /// A summary of the pet's social bonds
+    pub social: Option<SocialBondSummary>,
+
+    /// The pet's battle record; `None` inside if it has never battled
+    pub battle_record: Option<Option<BattleRecord>>,
+
+    /// The IDs of the pet's active conditions
+    pub conditions: Option<DossierSection<u32>>,
+}
+
+/// The dossier type returned for a runtime.
+pub type PetDossierOf<T> = PetDossier<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber, PetNft<T>>;
+
+/// A system for assembling pet dossiers.
+pub struct DossierSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> DossierSystem<T> {
+    /// Assembles the requested sections of a pet's dossier.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet to report on
+    /// * `sections` - A bitmask of `DOSSIER_*` sections to include
+    ///
+    /// # Returns
+    ///
+    /// * `Option<PetDossierOf<T>>` - The dossier, or `None` if the pet does not exist
+    pub fn dossier(pet_id: PetId, sections: u32) -> Option<PetDossierOf<T>> {
+        let pet = crate::PetNfts::<T>::get(pet_id)?;
+        let wants = |section: u32| sections & section != 0;
+
+        Some(PetDossier {
+            pet_id,
+            owner: wants(DOSSIER_OWNER).then(|| pet.owner.clone()),
+            status: wants(DOSSIER_STATUS).then(|| DossierStatus {
+                lock: crate::LockedNfts::<T>::get(pet_id),
+                transfer_restriction: T::TransferRestrictions::transfer_restriction(&pet_id),
+            }),
+            skills: wants(DOSSIER_SKILLS).then(|| Self::section(crate::PetSkills::<T>::get(pet_id))),
+            memories: wants(DOSSIER_MEMORIES).then(|| {
+                Self::section(crate::PetMemories::<T>::get(pet_id).into_iter().map(
+                    |(kind, significance, timestamp, data)| MemorySummary {
+                        kind,
+                        significance,
+                        timestamp,
+                        data_len: data.len() as u32,
+                        data_preview: data.into_iter().take(MEMORY_DATA_PREVIEW_LEN).collect(),
+                    },
+                ))
+            }),
+            achievements: wants(DOSSIER_ACHIEVEMENTS).then(|| Self::section(crate::PetAchievements::<T>::get(pet_id))),
+            lifecycle_events: wants(DOSSIER_LIFECYCLE)
+                .then(|| Self::section(crate::PetLifecycleEvents::<T>::get(pet_id))),
+            social: wants(DOSSIER_SOCIAL).then(|| Self::social_summary(pet_id)),
+            battle_record: wants(DOSSIER_BATTLE).then(|| T::BattleStats::battle_record(&pet_id)),
+            conditions: wants(DOSSIER_CONDITIONS).then(|| Self::section(T::PetConditions::active_conditions(&pet_id))),
+            pet: wants(DOSSIER_PET).then_some(pet),
+        })
+    }
+
+    /// The encoded size of the largest fixed-size records a dossier can carry.
+    pub fn max_record_size() -> usize {
+        PetNft::<T>::max_encoded_len()
+            .saturating_add(T::AccountId::max_encoded_len())
+            .saturating_add(DossierStatus::<<T as frame_system::Config>::BlockNumber>::max_encoded_len())
+            .saturating_add(SocialBondSummary::max_encoded_len())
+            .saturating_add(Option::<BattleRecord>::max_encoded_len())
+            // The `Option` tag around each record.
+            .saturating_add(5)
+    }
+
+    /// Collects entries in order until the next one would push the section past
+    /// `SECTION_SIZE_LIMIT`.
+    fn section<V: Encode>(entries: impl IntoIterator<Item = V>) -> DossierSection<V> {
+        let mut section = DossierSection { entries: Vec::new(), truncated: false };
+        let mut size = SECTION_OVERHEAD;
+        for entry in entries {
+            size = size.saturating_add(entry.encoded_size());
+            if size > SECTION_SIZE_LIMIT {
+                section.truncated = true;
+                break;
+            }
+            section.entries.push(entry);
+        }
+        section
+    }
+
+    /// Summarizes a pet's social bonds.
+    fn social_summary(pet_id: PetId) -> SocialBondSummary {
+        let bonds = crate::PetSocialBonds::<T>::get(pet_id);
+        SocialBondSummary {
+            bonds: bonds.len() as u32,
+            strongest: bonds
+                .iter()
+                .max_by_key(|bond| bond.bond_strength)
+                .map(|bond| (bond.other_pet_id, bond.bond_strength)),
+            total_interactions: bonds.iter().fold(0u32, |total, bond| total.saturating_add(bond.interaction_count)),
+        }
+    }
+}
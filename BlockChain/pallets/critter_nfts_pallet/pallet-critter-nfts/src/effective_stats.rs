@@ -0,0 +1,170 @@
+//! # Effective Stats
+//!
+//! The one place a pet's effective strength, agility, intelligence and vitality are computed.
+//! Battle (`AdvancedPetManagement::get_enhanced_pet_info`), quests (`get_pet_stats_for_quest`),
+//! `NftManagement::pet_stats`, the quality score and the UI profile all read them from
+//! `EffectiveStatsSystem::effective_stats`, so they cannot disagree.
+//!
+//! The modifiers apply in a fixed order, each layer to the result of the one before:
+//!
+//! 1. Base + bonus: the charter stats plus the growth the pet has earned (lifecycle stage
+//!    rewards, sacrifices).
+//! 2. Lifecycle %: the percentage `LifecycleStageRewards::stage_stat_percent` gives the pet's
+//!    latest lifecycle stage, clamped to ±`MAX_LIFECYCLE_PERCENT`.
+//! 3. Equipment flat: the bonus `Equipment` reports for the pet's equipment, clamped to
+//!    0..=`MAX_EQUIPMENT_BONUS` per stat.
+//! 4. Conditions: the modifier `PetConditions` reports for the pet's active conditions, clamped
+//!    to ±`MAX_CONDITION_MODIFIER` per stat.
+//! 5. Temporary buffs %: the pet's strongest unexpired `BuffKind::Stats` buff.
+//!
+//! Percentages round toward zero. After every layer each stat is clamped to 0..=255, so a layer
+//! never sees an out-of-range stat from the one before. Reading effective stats writes nothing;
+//! expired buffs are skipped, not pruned.
+//!
+//! `EffectiveStats::compute` runs the layers on modifiers already read, free of runtime state,
+//! so off-chain simulators can reproduce it from the `test_vectors` fixtures.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_runtime::Percent;
+use crittercraft_traits::nft::{PetConditionProvider, PetEquipmentProvider};
+use crittercraft_traits::types::StatModifier;
+use crate::buffs::BuffSystem;
+use crate::kinds::BuffKind;
+use crate::lifecycle::{LifecycleEventSystem, LifecycleStageRewards};
+use crate::{Config, PetId, PetNft};
+
+/// The largest percentage, up or down, a lifecycle stage changes stats by.
+pub const MAX_LIFECYCLE_PERCENT: i32 = 50;
+
+/// The largest flat bonus equipment adds to one stat.
+pub const MAX_EQUIPMENT_BONUS: i32 = 50;
+
+/// The largest flat change, up or down, conditions make to one stat.
+pub const MAX_CONDITION_MODIFIER: i32 = 50;
+
+/// A pet's core stats after every modifier.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EffectiveStats {
+    /// The pet's effective strength
+    pub strength: u8,
+
+    /// The pet's effective agility
+    pub agility: u8,
+
+    /// The pet's effective intelligence
+    pub intelligence: u8,
+
+    /// The pet's effective vitality
+    pub vitality: u8,
+}
+
+impl EffectiveStats {
+    /// The sum of the four stats.
+    pub fn total(&self) -> u32 {
+        self.strength as u32 + self.agility as u32 + self.intelligence as u32 + self.vitality as u32
+    }
+
+    /// Runs the modifier layers in order on a pet's grown stats (base + bonus).
+    ///
+    /// # Parameters
+    ///
+    /// * `grown` - The base stats plus the bonus stats
+    /// * `stage_percent` - The lifecycle stage percentage, before clamping
+    /// * `equipment` - The equipment bonus, before clamping
+    /// * `conditions` - The condition modifier, before clamping
+    /// * `buff` - The magnitude of the strongest stats buff
+    ///
+    /// # Returns
+    ///
+    /// * `EffectiveStats` - The effective stats
+    pub fn compute(
+        grown: EffectiveStats,
+        stage_percent: i32,
+        equipment: StatModifier,
+        conditions: StatModifier,
+        buff: Percent,
+    ) -> Self {
+        // 2. Lifecycle %.
+        let stage_percent = stage_percent.clamp(-MAX_LIFECYCLE_PERCENT, MAX_LIFECYCLE_PERCENT);
+        let staged = grown.layer([stage_percent; 4], |stat, percent| stat + stat * percent / 100);
+
+        // 3. Equipment flat.
+        let equipped = staged.layer(clamped(equipment, 0, MAX_EQUIPMENT_BONUS), |stat, bonus| stat + bonus);
+
+        // 4. Conditions.
+        let conditions = clamped(conditions, -MAX_CONDITION_MODIFIER, MAX_CONDITION_MODIFIER);
+        let conditioned = equipped.layer(conditions, |stat, modifier| stat + modifier);
+
+        // 5. Temporary buffs %.
+        conditioned.layer([0; 4], |stat, _| stat + buff.mul_floor(stat as u32) as i32)
+    }
+
+    /// Applies one layer: `apply` combines each stat with its modifier, and the result is
+    /// clamped to a `u8`.
+    fn layer(self, modifiers: [i32; 4], apply: impl Fn(i32, i32) -> i32) -> Self {
+        let stats = [self.strength, self.agility, self.intelligence, self.vitality];
+        let [strength, agility, intelligence, vitality] =
+            [0, 1, 2, 3].map(|index| apply(stats[index] as i32, modifiers[index]).clamp(0, u8::MAX as i32) as u8);
+        Self { strength, agility, intelligence, vitality }
+    }
+}
+
+/// Spreads a stat modifier over the four stats, clamping each to `min..=max`.
+fn clamped(modifier: StatModifier, min: i32, max: i32) -> [i32; 4] {
+    [modifier.strength, modifier.agility, modifier.intelligence, modifier.vitality]
+        .map(|value| (value as i32).clamp(min, max))
+}
+
+/// A system for computing effective stats.
+pub struct EffectiveStatsSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> EffectiveStatsSystem<T> {
+    /// Gets a pet's effective stats.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `Option<EffectiveStats>` - The effective stats, or `None` if the pet does not exist
+    pub fn effective_stats(pet_id: PetId) -> Option<EffectiveStats> {
+        crate::PetNfts::<T>::get(pet_id).map(|pet| Self::of(&pet))
+    }
+
+    /// Computes the effective stats of a pet the caller has already read.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    ///
+    /// # Returns
+    ///
+    /// * `EffectiveStats` - The effective stats
+    pub fn of(pet: &PetNft<T>) -> EffectiveStats {
+        // 1. Base + bonus.
+        let grown = EffectiveStats {
+            strength: pet.base_strength.saturating_add(pet.bonus_strength),
+            agility: pet.base_agility.saturating_add(pet.bonus_agility),
+            intelligence: pet.base_intelligence.saturating_add(pet.bonus_intelligence),
+            vitality: pet.base_vitality.saturating_add(pet.bonus_vitality),
+        };
+
+        // 2.-5. The modifiers, read here and applied by `compute`.
+        let stage_percent = LifecycleEventSystem::<T>::current_stage(pet.id)
+            .map(|stage| T::LifecycleStageRewards::stage_stat_percent(stage) as i32)
+            .unwrap_or(0);
+        let buff = BuffSystem::<T>::strongest(&BuffSystem::<T>::active_buffs(pet.id), BuffKind::Stats);
+        EffectiveStats::compute(
+            grown,
+            stage_percent,
+            T::Equipment::equipment_bonus(&pet.id),
+            T::PetConditions::stat_modifier(&pet.id),
+            buff,
+        )
+    }
+}
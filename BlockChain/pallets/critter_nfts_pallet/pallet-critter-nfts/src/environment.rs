@@ -12,13 +12,14 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use crate::kinds::EnvironmentKind;
 use crate::{Config, Error, PetId, PetNft, ElementType};
 
 /// Represents an environment that pets can adapt to.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Environment {
     /// The environment type
-    pub environment_type: u8,
+    pub environment_type: EnvironmentKind,
     
     /// The primary element of the environment
     pub primary_element: u8,
@@ -68,19 +69,8 @@ pub struct EnvironmentChallenges {
     pub experience_penalty: u8,
 }
 
-/// Environment types.
-pub enum EnvironmentType {
-    Forest = 0,
-    Mountain = 1,
-    Desert = 2,
-    Ocean = 3,
-    Tundra = 4,
-    Volcano = 5,
-    City = 6,
-    Space = 7,
-    Digital = 8,
-    Ethereal = 9,
-}
+/// Environment types, now the typed `crate::kinds::EnvironmentKind`.
+pub type EnvironmentType = crate::kinds::EnvironmentKind;
 
 /// A system for managing pet adaptations to different environments.
 pub struct EnvironmentalAdaptationSystem<T: Config> {
@@ -100,7 +90,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     pub fn adapt_to_environment(
         pet_id: PetId,
-        environment_type: u8,
+        environment_type: EnvironmentKind,
     ) -> DispatchResult {
         // Get the pet from storage
         let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
@@ -153,12 +143,12 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// # Returns
     /// 
     /// * `Result<Environment, DispatchError>` - The environment, or an error
-    fn get_environment(environment_type: u8) -> Result<Environment, DispatchError> {
+    fn get_environment(environment_type: EnvironmentKind) -> Result<Environment, DispatchError> {
         // In a real implementation, this would get the environment from storage
         // For now, we'll just return a hardcoded environment based on the type
         
         match environment_type {
-            0 => { // Forest
+            EnvironmentKind::Forest => {
                 Ok(Environment {
                     environment_type,
                     primary_element: ElementType::Nature as u8,
@@ -178,7 +168,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
                     },
                 })
             },
-            1 => { // Mountain
+            EnvironmentKind::Mountain => {
                 Ok(Environment {
                     environment_type,
                     primary_element: ElementType::Earth as u8,
@@ -198,7 +188,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
                     },
                 })
             },
-            2 => { // Desert
+            EnvironmentKind::Desert => {
                 Ok(Environment {
                     environment_type,
                     primary_element: ElementType::Fire as u8,
@@ -425,7 +415,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     fn record_adaptation(
         pet_id: PetId,
-        environment_type: u8,
+        environment_type: EnvironmentKind,
         adaptation_level: u8,
     ) -> DispatchResult {
         crate::PetEnvironmentalAdaptations::<T>::try_mutate(pet_id, |adaptations| -> DispatchResult {
@@ -460,7 +450,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// * `Result<u8, DispatchError>` - The adaptation level (0-255), or an error
     pub fn get_adaptation_level(
         pet_id: PetId,
-        environment_type: u8,
+        environment_type: EnvironmentKind,
     ) -> Result<u8, DispatchError> {
         let adaptations = crate::PetEnvironmentalAdaptations::<T>::get(pet_id);
         
@@ -486,10 +476,10 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, u8)>` - The environmental adaptations (environment_type, adaptation_level)
+    /// * `Vec<(EnvironmentKind, u8)>` - The environmental adaptations (environment_type, adaptation_level)
     pub fn get_all_adaptations(
         pet_id: PetId,
-    ) -> Vec<(u8, u8)> {
+    ) -> Vec<(EnvironmentKind, u8)> {
         crate::PetEnvironmentalAdaptations::<T>::get(pet_id).to_vec()
     }
 }//! # Environmental Adaptation System
@@ -506,13 +496,14 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use crate::kinds::EnvironmentKind;
 use crate::{Config, Error, PetId, PetNft, ElementType};
 
 /// Represents an environment that pets can adapt to.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Environment {
     /// The environment type
-    pub environment_type: u8,
+    pub environment_type: EnvironmentKind,
     
     /// The primary element of the environment
     pub primary_element: u8,
@@ -562,19 +553,8 @@ pub struct EnvironmentChallenges {
     pub experience_penalty: u8,
 }
 
-/// Environment types.
-pub enum EnvironmentType {
-    Forest = 0,
-    Mountain = 1,
-    Desert = 2,
-    Ocean = 3,
-    Tundra = 4,
-    Volcano = 5,
-    City = 6,
-    Space = 7,
-    Digital = 8,
-    Ethereal = 9,
-}
+/// Environment types, now the typed `crate::kinds::EnvironmentKind`.
+pub type EnvironmentType = crate::kinds::EnvironmentKind;
 
 /// A system for managing pet adaptations to different environments.
 pub struct EnvironmentalAdaptationSystem<T: Config> {
@@ -594,7 +574,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     pub fn adapt_to_environment(
         pet_id: PetId,
-        environment_type: u8,
+        environment_type: EnvironmentKind,
     ) -> DispatchResult {
         // Get the pet from storage
         let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
@@ -647,12 +627,12 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// # Returns
     /// 
     /// * `Result<Environment, DispatchError>` - The environment, or an error
-    fn get_environment(environment_type: u8) -> Result<Environment, DispatchError> {
+    fn get_environment(environment_type: EnvironmentKind) -> Result<Environment, DispatchError> {
         // In a real implementation, this would get the environment from storage
         // For now, we'll just return a hardcoded environment based on the type
         
         match environment_type {
-            0 => { // Forest
+            EnvironmentKind::Forest => {
                 Ok(Environment {
                     environment_type,
                     primary_element: ElementType::Nature as u8,
@@ -672,7 +652,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
                     },
                 })
             },
-            1 => { // Mountain
+            EnvironmentKind::Mountain => {
                 Ok(Environment {
                     environment_type,
                     primary_element: ElementType::Earth as u8,
@@ -692,7 +672,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
                     },
                 })
             },
-            2 => { // Desert
+            EnvironmentKind::Desert => {
                 Ok(Environment {
                     environment_type,
                     primary_element: ElementType::Fire as u8,
@@ -919,7 +899,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     fn record_adaptation(
         pet_id: PetId,
-        environment_type: u8,
+        environment_type: EnvironmentKind,
         adaptation_level: u8,
     ) -> DispatchResult {
         crate::PetEnvironmentalAdaptations::<T>::try_mutate(pet_id, |adaptations| -> DispatchResult {
@@ -954,7 +934,7 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// * `Result<u8, DispatchError>` - The adaptation level (0-255), or an error
     pub fn get_adaptation_level(
         pet_id: PetId,
-        environment_type: u8,
+        environment_type: EnvironmentKind,
     ) -> Result<u8, DispatchError> {
         let adaptations = crate::PetEnvironmentalAdaptations::<T>::get(pet_id);
         
@@ -980,10 +960,10 @@ impl<T: Config> EnvironmentalAdaptationSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, u8)>` - The environmental adaptations (environment_type, adaptation_level)
+    /// * `Vec<(EnvironmentKind, u8)>` - The environmental adaptations (environment_type, adaptation_level)
     pub fn get_all_adaptations(
         pet_id: PetId,
-    ) -> Vec<(u8, u8)> {
+    ) -> Vec<(EnvironmentKind, u8)> {
         crate::PetEnvironmentalAdaptations::<T>::get(pet_id).to_vec()
     }
 }
\ No newline at end of file
@@ -0,0 +1,231 @@
+//! # Featured Pets
+//!
+//! Every `FeatureRotationPeriod` blocks, `on_initialize` draws up to `FeaturedCount` pets to
+//! feature in the UI until the next rotation. The draw hashes the parent block hash with a
+//! counter and maps each hash into the range of issued pet IDs, so it is deterministic given
+//! the block hash and cannot be steered by a transaction in the rotating block. Candidates that
+//! no longer exist, are quarantined or were already drawn are skipped, at most
+//! `FeaturedMaxRetries` times per rotation, so a rotation over a sparse or mostly quarantined
+//! ID range settles for fewer pets instead of drawing without end.
+//!
+//! The owner of each featured pet is paid `FeaturedPetBonus` from `LifecycleRewardPot` and
+//! notified, once per rotation. A pot that cannot cover the bonus does not stop the rotation.
+//! Blocks between rotations cost nothing beyond the period check.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    pallet_prelude::{RuntimeDebug, Weight},
+    traits::Get,
+    BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::traits::{SaturatedConversion, Zero};
+use sp_std::vec::Vec;
+use crittercraft_traits::nft::TransferRestrictionProvider;
+use crittercraft_traits::types::TransferBlockReason;
+use crate::user_experience::{NotificationPriority, NotificationType, UserExperienceSystem};
+use crate::{Config, Event, Pallet, PetId};
+
+/// The pets featured in one rotation.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct FeaturedRotation<T: Config> {
+    /// The rotation's number: the block it was drawn at divided by `FeatureRotationPeriod`
+    pub epoch: u32,
+
+    /// The featured pets, in the order they were drawn
+    pub pet_ids: BoundedVec<PetId, T::FeaturedCount>,
+
+    /// The block the rotation was drawn at
+    pub rotated_at: BlockNumberFor<T>,
+}
+
+/// What the UI shows of a featured pet.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct FeaturedPetSummary<AccountId> {
+    /// The pet's ID
+    pub pet_id: PetId,
+
+    /// The pet's name
+    pub name: Vec<u8>,
+
+    /// The pet's species
+    pub species: Vec<u8>,
+
+    /// The pet's level
+    pub level: u32,
+
+    /// The pet's breeding generation
+    pub generation: u32,
+
+    /// The pet's owner
+    pub owner: AccountId,
+
+    /// The pet's quality score
+    pub quality_score: u32,
+}
+
+/// The current featured rotation, as returned by the `featured_pets` runtime API.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct FeaturedPets<AccountId, BlockNumber> {
+    /// The rotation's number
+    pub epoch: u32,
+
+    /// The block the rotation was drawn at
+    pub rotated_at: BlockNumber,
+
+    /// The featured pets that still exist, in the order they were drawn
+    pub pets: Vec<FeaturedPetSummary<AccountId>>,
+}
+
+/// A system for rotating featured pets.
+pub struct FeaturedSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> FeaturedSystem<T> {
+    /// Rotates the featured pets if `now` starts a rotation period.
+    ///
+    /// # Parameters
+    ///
+    /// * `now` - The block being initialized
+    ///
+    /// # Returns
+    ///
+    /// * `Weight` - The weight consumed
+    pub fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+        let period = T::FeatureRotationPeriod::get();
+        if period.is_zero() || !(now % period).is_zero() {
+            return Weight::zero();
+        }
+        Self::rotate(now, (now / period).saturated_into::<u32>())
+    }
+
+    /// Draws the featured pets for rotation `epoch`, pays their owners and stores the rotation.
+    /// Does nothing if rotation `epoch` was already drawn.
+    ///
+    /// # Parameters
+    ///
+    /// * `now` - The current block
+    /// * `epoch` - The rotation's number
+    ///
+    /// # Returns
+    ///
+    /// * `Weight` - The weight consumed
+    pub fn rotate(now: BlockNumberFor<T>, epoch: u32) -> Weight {
+        let db = T::DbWeight::get();
+        if crate::CurrentFeatured::<T>::get().map_or(false, |current| current.epoch == epoch) {
+            return db.reads(1);
+        }
+
+        let (featured, draws) = Self::select(&frame_system::Pallet::<T>::parent_hash());
+        let bonus = T::FeaturedPetBonus::get();
+        for (pet_id, owner) in featured.iter() {
+            if !bonus.is_zero() {
+                // An empty pot only costs the owner the bonus; the pet is still featured.
+                let _ = Pallet::<T>::pay_from_lifecycle_pot(owner, bonus);
+            }
+            let _ = UserExperienceSystem::<T>::add_notification(
+                owner.clone(),
+                NotificationType::Event as u8,
+                b"Featured Pet".to_vec(),
+                b"Your pet is featured today! A bonus has been sent your way.".to_vec(),
+                b"featured".to_vec(),
+                NotificationPriority::Medium as u8,
+                [b"view_pet:".as_slice(), &pet_id.to_le_bytes()].concat(),
+            );
+        }
+
+        let pet_ids: Vec<PetId> = featured.iter().map(|(pet_id, _)| *pet_id).collect();
+        let count = pet_ids.len() as u64;
+        crate::CurrentFeatured::<T>::put(FeaturedRotation {
+            epoch,
+            // `select` draws at most `FeaturedCount` pets.
+            pet_ids: BoundedVec::truncate_from(pet_ids.clone()),
+            rotated_at: now,
+        });
+        Pallet::<T>::deposit_event(Event::FeaturedPetsRotated { epoch, pet_ids });
+
+        // The current rotation and pet count, two reads per draw, and per featured pet the pot
+        // transfer and the notification.
+        db.reads_writes(
+            2u64.saturating_add(draws.saturating_mul(2)).saturating_add(count.saturating_mul(4)),
+            1u64.saturating_add(count.saturating_mul(5)),
+        )
+    }
+
+    /// Draws up to `FeaturedCount` distinct pets that exist and are not quarantined, skipping
+    /// at most `FeaturedMaxRetries` candidates.
+    ///
+    /// # Parameters
+    ///
+    /// * `seed` - The block hash to draw from
+    ///
+    /// # Returns
+    ///
+    /// * `(Vec<(PetId, T::AccountId)>, u64)` - The drawn pets with their owners, and the number
+    ///   of candidates drawn
+    pub fn select(seed: &T::Hash) -> (Vec<(PetId, T::AccountId)>, u64) {
+        let mut featured: Vec<(PetId, T::AccountId)> = Vec::new();
+        let issued = crate::NextPetId::<T>::get();
+        if issued == 0 {
+            return (featured, 0);
+        }
+
+        let wanted = T::FeaturedCount::get();
+        let max_draws = wanted.saturating_add(T::FeaturedMaxRetries::get());
+        let mut draws = 0u32;
+        while (featured.len() as u32) < wanted && draws < max_draws {
+            let pet_id = Self::candidate(seed, draws, issued);
+            draws = draws.saturating_add(1);
+
+            if featured.iter().any(|(featured_id, _)| *featured_id == pet_id) {
+                continue;
+            }
+            let pet = match crate::PetNfts::<T>::get(pet_id) {
+                Some(pet) => pet,
+                None => continue,
+            };
+            if T::TransferRestrictions::transfer_restriction(&pet_id) == Some(TransferBlockReason::Quarantined) {
+                continue;
+            }
+            featured.push((pet_id, pet.owner));
+        }
+        (featured, draws as u64)
+    }
+
+    /// The `counter`th candidate drawn from `seed`, among pet IDs below `issued`.
+    pub fn candidate(seed: &T::Hash, counter: u32, issued: PetId) -> PetId {
+        let hash = sp_io::hashing::blake2_256(&(seed, counter).encode());
+        let draw = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]);
+        draw % issued
+    }
+
+    /// Lists the current rotation's pets that still exist, with their summaries.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<FeaturedPets<T::AccountId, T::BlockNumber>>` - The rotation, or `None` if no
+    ///   rotation has been drawn yet
+    pub fn featured_pets() -> Option<FeaturedPets<T::AccountId, T::BlockNumber>> {
+        let rotation = crate::CurrentFeatured::<T>::get()?;
+        let pets = rotation
+            .pet_ids
+            .iter()
+            .filter_map(|pet_id| {
+                let pet = crate::PetNfts::<T>::get(pet_id)?;
+                Some(FeaturedPetSummary {
+                    pet_id: *pet_id,
+                    name: pet.current_pet_name.to_vec(),
+                    species: pet.initial_species.to_vec(),
+                    level: pet.level,
+                    generation: pet.generation,
+                    owner: pet.owner,
+                    quality_score: crate::valuation::PetValuationSystem::<T>::quality_score(*pet_id).unwrap_or(0),
+                })
+            })
+            .collect();
+        Some(FeaturedPets { epoch: rotation.epoch, rotated_at: rotation.rotated_at, pets })
+    }
+}
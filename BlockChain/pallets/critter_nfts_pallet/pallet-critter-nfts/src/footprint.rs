@@ -0,0 +1,161 @@
+//! # Storage Footprint
+//!
+//! Lets operators find the pets responsible for state bloat. `pet_storage_footprint` measures
+//! a pet's auxiliary storage exactly, by reading and encoding each per-pet map. Every map it
+//! reads is bounded by its own `Max*` constant, so the measurement is bounded too.
+//!
+//! `heaviest_pets` ranks pets by `PetStorageSize`, an approximate counter kept up to date on the
+//! write paths of the auxiliary maps (saturating adds and subtractions of the change in encoded
+//! size), so ranking does not have to measure every pet.
+//!
+//! Both count an empty entry as zero bytes. The tracked size therefore matches the measured
+//! total except for:
+//! - the one-byte length prefix of an empty entry that is still stored, per map;
+//! - entries written by paths that do not track sizes: genesis, migrations and the compressed
+//!   interaction history, which has no write path in this pallet.
+
+use codec::{Compact, CompactLen, Decode, Encode};
+use frame_support::pallet_prelude::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+use crate::{Config, PetId};
+
+/// The entry count and encoded size of one of a pet's auxiliary maps.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MapFootprint {
+    /// The number of items stored for the pet
+    pub entries: u32,
+
+    /// The encoded size of the pet's value, in bytes; zero when there are no items
+    pub bytes: u32,
+}
+
+/// The storage a pet takes up in each auxiliary map. Byte blobs (the interaction history and
+/// analytics report) count as one entry when present.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StorageFootprint {
+    /// `PetMemories`
+    pub memories: MapFootprint,
+
+    /// `PetSkills`
+    pub skills: MapFootprint,
+
+    /// `PetAchievements`
+    pub achievements: MapFootprint,
+
+    /// `PetSocialInteractions`
+    pub social_interactions: MapFootprint,
+
+    /// `PetInteractionHistory`
+    pub interaction_history: MapFootprint,
+
+    /// `PetAnalyticsReports`; always empty without the `analytics` feature
+    pub analytics_report: MapFootprint,
+
+    /// `PetVisualAttributes`
+    pub visual_attributes: MapFootprint,
+
+    /// The sum of the maps' encoded sizes, in bytes
+    pub total_bytes: u32,
+}
+
+/// The encoded size of a per-pet list, counting an empty list as zero bytes.
+pub fn stored_size<I: Encode>(items: &[I]) -> u32 {
+    if items.is_empty() { 0 } else { items.encoded_size() as u32 }
+}
+
+/// The encoded size of a per-pet byte blob of `len` bytes, counting an empty blob as zero bytes.
+pub fn blob_size(len: usize) -> u32 {
+    if len == 0 { 0 } else { (Compact::<u32>::compact_len(&(len as u32)) + len) as u32 }
+}
+
+/// A system for measuring and tracking the storage taken up by pets.
+pub struct FootprintSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> FootprintSystem<T> {
+    /// Measures a pet's auxiliary storage by reading and encoding each map.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `StorageFootprint` - The entry count and size per map
+    pub fn footprint(pet_id: PetId) -> StorageFootprint {
+        fn list<I: Encode>(items: &[I]) -> MapFootprint {
+            MapFootprint { entries: items.len() as u32, bytes: stored_size(items) }
+        }
+        fn blob(bytes: &[u8]) -> MapFootprint {
+            MapFootprint { entries: (!bytes.is_empty()) as u32, bytes: blob_size(bytes.len()) }
+        }
+
+        #[cfg(feature = "analytics")]
+        let analytics_report = blob(&crate::PetAnalyticsReports::<T>::get(pet_id));
+        #[cfg(not(feature = "analytics"))]
+        let analytics_report = MapFootprint::default();
+
+        let mut footprint = StorageFootprint {
+            memories: list(&crate::PetMemories::<T>::get(pet_id)),
+            skills: list(&crate::PetSkills::<T>::get(pet_id)),
+            achievements: list(&crate::PetAchievements::<T>::get(pet_id)),
+            social_interactions: list(&crate::PetSocialInteractions::<T>::get(pet_id)),
+            interaction_history: blob(&crate::PetInteractionHistory::<T>::get(pet_id)),
+            analytics_report,
+            visual_attributes: list(&crate::PetVisualAttributes::<T>::get(pet_id)),
+            total_bytes: 0,
+        };
+        footprint.total_bytes = [
+            footprint.memories,
+            footprint.skills,
+            footprint.achievements,
+            footprint.social_interactions,
+            footprint.interaction_history,
+            footprint.analytics_report,
+            footprint.visual_attributes,
+        ]
+        .iter()
+        .fold(0u32, |total, map| total.saturating_add(map.bytes));
+        footprint
+    }
+
+    /// Records that one of a pet's auxiliary entries changed size.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    /// * `before` - The entry's size before the write, as given by `stored_size` or `blob_size`
+    /// * `after` - The entry's size after the write
+    pub fn note_resize(pet_id: PetId, before: u32, after: u32) {
+        if before == after {
+            return;
+        }
+        crate::PetStorageSize::<T>::mutate_exists(pet_id, |size| {
+            let tracked = size.unwrap_or(0);
+            let tracked = if after > before {
+                tracked.saturating_add(after - before)
+            } else {
+                tracked.saturating_sub(before - after)
+            };
+            *size = if tracked == 0 { None } else { Some(tracked) };
+        });
+    }
+
+    /// Lists up to `limit` pets with the largest tracked storage size, largest first.
+    ///
+    /// # Parameters
+    ///
+    /// * `limit` - The most pets to return
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(PetId, u32)>` - The pets with their tracked sizes, in bytes
+    pub fn heaviest(limit: u32) -> Vec<(PetId, u32)> {
+        let mut sizes: Vec<(PetId, u32)> = crate::PetStorageSize::<T>::iter().collect();
+        sizes.sort_by(|(id_a, size_a), (id_b, size_b)| size_b.cmp(size_a).then(id_a.cmp(id_b)));
+        sizes.truncate(limit as usize);
+        sizes
+    }
+}
@@ -0,0 +1,57 @@
+//! # Free Care
+//!
+//! Feeding and play normally consume an item, which a new user with an empty inventory does
+//! not have. `pet_basic_care` feeds or plays with a pet without one, at `FreeCareEffect` of the
+//! item-based mood and XP gains, up to `MaxFreeCarePerDay` times per pet per care epoch
+//! (`CareEpochBlocks`). It otherwise runs the item-based care path: the rate limits, species
+//! preferences, rested XP, buffs, care history and `CareActionPerformed`, with no item.
+//!
+//! Uses are counted in `FreeCareUses` together with the epoch they were counted in. A count
+//! from an earlier epoch reads as zero, so nothing is written at epoch boundaries.
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use crate::care_history::CareHistorySystem;
+use crate::{Config, Error, PetId};
+
+/// A system for counting free care uses.
+pub struct FreeCareSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> FreeCareSystem<T> {
+    /// Gets the free care uses a pet has left in the current epoch.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The remaining uses
+    pub fn remaining(pet_id: PetId) -> u32 {
+        T::MaxFreeCarePerDay::get().saturating_sub(Self::used(pet_id))
+    }
+
+    /// Counts a free care use for a pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet had a use left, Err otherwise
+    pub fn use_one(pet_id: PetId) -> DispatchResult {
+        let used = Self::used(pet_id);
+        ensure!(used < T::MaxFreeCarePerDay::get(), Error::<T>::FreeCareLimitReached);
+
+        crate::FreeCareUses::<T>::insert(pet_id, (CareHistorySystem::<T>::current_epoch(), used.saturating_add(1)));
+        Ok(())
+    }
+
+    /// The uses counted in the current epoch; a count from an earlier epoch has expired.
+    fn used(pet_id: PetId) -> u32 {
+        let (epoch, used) = crate::FreeCareUses::<T>::get(pet_id);
+        if epoch == CareHistorySystem::<T>::current_epoch() { used } else { 0 }
+    }
+}
@@ -0,0 +1,166 @@
+//! # Game Parameters
+//!
+//! This module holds the care tuning constants that governance can adjust without a runtime
+//! upgrade. The values live in the `GameParameters` storage item, set through
+//! `set_game_parameters`; until they are first set, the Config constants apply. Every read
+//! site goes through `Pallet::params()`, so an update takes effect from the next call.
+//!
+//! The stored value is versioned: a later version can add fields, and values stored under
+//! an earlier version keep decoding.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, pallet_prelude::RuntimeDebug, traits::Get};
+use scale_info::TypeInfo;
+use sp_runtime::SaturatedConversion;
+use sp_std::vec::Vec;
+use crate::{Config, Error};
+
+/// Upper bound on the XP granted by a single feed or play.
+pub const MAX_XP_GAIN_PER_ACTION: u32 = 10_000;
+
+/// The governable care tuning values.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct GameParameters<BlockNumber> {
+    /// Mood restored by feeding
+    pub feed_mood_boost: u8,
+
+    /// Mood restored by playing
+    pub play_mood_boost: u8,
+
+    /// XP gained by feeding
+    pub feed_xp_gain: u32,
+
+    /// XP gained by playing
+    pub play_xp_gain: u32,
+
+    /// Mood lost when a neglect check finds a pet neglected
+    pub neglect_mood_penalty: u8,
+
+    /// Blocks without play after which a pet counts as neglected
+    pub neglect_threshold_blocks: BlockNumber,
+}
+
+/// The game parameters type for a runtime.
+pub type GameParametersOf<T> = GameParameters<<T as frame_system::Config>::BlockNumber>;
+
+/// The stored game parameters, tagged with their layout version.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum VersionedGameParameters<BlockNumber> {
+    V1(GameParameters<BlockNumber>),
+}
+
+/// A game parameter, as named in `GameParametersUpdated`.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum GameParameterField {
+    FeedMoodBoost,
+    PlayMoodBoost,
+    FeedXpGain,
+    PlayXpGain,
+    NeglectMoodPenalty,
+    NeglectThresholdBlocks,
+}
+
+/// A changed game parameter with its old and new value.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct GameParameterChange {
+    /// The parameter that changed
+    pub field: GameParameterField,
+
+    /// The value before the update
+    pub old: u64,
+
+    /// The value after the update
+    pub new: u64,
+}
+
+/// A system for managing the governable game parameters.
+pub struct GameParametersSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> GameParametersSystem<T> {
+    /// Returns the game parameters in force: the stored values, or the Config constants if
+    /// none have been set.
+    pub fn current() -> GameParametersOf<T> {
+        match crate::GameParameters::<T>::get() {
+            Some(VersionedGameParameters::V1(params)) => params,
+            None => Self::defaults(),
+        }
+    }
+
+    /// Returns the game parameters given by the Config constants.
+    pub fn defaults() -> GameParametersOf<T> {
+        GameParameters {
+            feed_mood_boost: T::FeedMoodBoost::get(),
+            play_mood_boost: T::PlayMoodBoost::get(),
+            feed_xp_gain: T::FeedXpGain::get(),
+            play_xp_gain: T::PlayXpGain::get(),
+            neglect_mood_penalty: T::NeglectMoodPenalty::get(),
+            neglect_threshold_blocks: T::NeglectThresholdBlocks::get(),
+        }
+    }
+
+    /// Validates and stores new game parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `new` - The new game parameters
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<GameParameterChange>, DispatchError>` - The changed parameters if stored, Err otherwise
+    pub fn set(new: GameParametersOf<T>) -> Result<Vec<GameParameterChange>, sp_runtime::DispatchError> {
+        // 1. Check every field against its sanity bounds.
+        Self::validate(&new)?;
+
+        // 2. Compute the diff against the parameters in force and store the new ones.
+        let changes = Self::diff(&Self::current(), &new);
+        crate::GameParameters::<T>::put(VersionedGameParameters::V1(new));
+
+        Ok(changes)
+    }
+
+    /// Checks that mood values fit the mood range, XP gains stay below `MAX_XP_GAIN_PER_ACTION`
+    /// and the neglect threshold is at least one block.
+    pub fn validate(params: &GameParametersOf<T>) -> DispatchResult {
+        let max_mood = T::MaxMoodValue::get();
+        ensure!(
+            params.feed_mood_boost <= max_mood
+                && params.play_mood_boost <= max_mood
+                && params.neglect_mood_penalty <= max_mood,
+            Error::<T>::GameParameterOutOfBounds
+        );
+        ensure!(
+            params.feed_xp_gain <= MAX_XP_GAIN_PER_ACTION && params.play_xp_gain <= MAX_XP_GAIN_PER_ACTION,
+            Error::<T>::GameParameterOutOfBounds
+        );
+        ensure!(
+            params.neglect_threshold_blocks.saturated_into::<u64>() > 0,
+            Error::<T>::GameParameterOutOfBounds
+        );
+
+        Ok(())
+    }
+
+    /// Lists the fields that differ between `old` and `new`, in declaration order.
+    pub fn diff(old: &GameParametersOf<T>, new: &GameParametersOf<T>) -> Vec<GameParameterChange> {
+        let fields = [
+            (GameParameterField::FeedMoodBoost, old.feed_mood_boost as u64, new.feed_mood_boost as u64),
+            (GameParameterField::PlayMoodBoost, old.play_mood_boost as u64, new.play_mood_boost as u64),
+            (GameParameterField::FeedXpGain, old.feed_xp_gain as u64, new.feed_xp_gain as u64),
+            (GameParameterField::PlayXpGain, old.play_xp_gain as u64, new.play_xp_gain as u64),
+            (GameParameterField::NeglectMoodPenalty, old.neglect_mood_penalty as u64, new.neglect_mood_penalty as u64),
+            (
+                GameParameterField::NeglectThresholdBlocks,
+                old.neglect_threshold_blocks.saturated_into::<u64>(),
+                new.neglect_threshold_blocks.saturated_into::<u64>(),
+            ),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, old, new)| old != new)
+            .map(|(field, old, new)| GameParameterChange { field, old, new })
+            .collect()
+    }
+}
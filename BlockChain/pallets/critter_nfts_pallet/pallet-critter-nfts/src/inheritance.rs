@@ -0,0 +1,131 @@
+//! # Trait Inheritance
+//!
+//! A bred pet inherits personality traits from its parents. Each parent trait is inherited with
+//! a chance from the `InheritanceTable`, where governance can set a per-trait chance, falling
+//! back to `BaseInheritanceChance`. A trait both parents have is rolled once per parent, so it
+//! is inherited with a boosted chance of `1 - (1 - chance)^2`.
+//!
+//! The rolls use entropy derived from the offspring's DNA, so the inherited traits are
+//! deterministic given the DNA and the table. When more traits are inherited than the
+//! offspring's `MaxPetPersonalityTraits` allows, they are kept in priority order:
+//!
+//! 1. Higher effective chance first, counting the shared-trait boost.
+//! 2. On equal chance, the first parent's traits before the second's, each in its own order.
+//!
+//! Inherited traits are owner-sourced, so the owner can replace them like any other of their
+//! traits. A bred pet starts with its inherited traits only; any trait it gains later is fresh.
+
+use codec::Encode;
+use frame_support::{traits::Get, BoundedVec};
+use sp_runtime::Perbill;
+use sp_std::vec::Vec;
+use crate::traits::{DnaHashType, TraitTypeString};
+use crate::Config;
+
+/// The traits a bred pet inherits, bounded like the pet's own trait list.
+pub type InheritedTraits<T> = BoundedVec<TraitTypeString, <T as crate::config::NftCoreConfig>::MaxPetPersonalityTraits>;
+
+/// A parent trait that may be inherited.
+struct Candidate {
+    trait_string: TraitTypeString,
+    chance: Perbill,
+    shared: bool,
+}
+
+/// Returns the chance that a trait is inherited, counting one roll per parent with it.
+pub fn effective_chance(chance: Perbill, shared: bool) -> Perbill {
+    if shared {
+        // 1 - (1 - p)^2
+        let miss = chance.left_from_one();
+        (miss * miss).left_from_one()
+    } else {
+        chance
+    }
+}
+
+/// Rolls whether an offspring with `dna` inherits a parent trait, once per parent with it.
+/// Deterministic given the DNA, the trait and its chance.
+///
+/// # Parameters
+///
+/// * `dna` - The offspring's DNA, the source of the rolls
+/// * `trait_string` - The parent trait
+/// * `chance` - The trait's chance per roll, before the shared-trait boost
+/// * `shared` - Whether both parents have the trait
+///
+/// # Returns
+///
+/// * `bool` - Whether any roll succeeded
+pub fn inherits(dna: &DnaHashType, trait_string: &TraitTypeString, chance: Perbill, shared: bool) -> bool {
+    let rolls = if shared { 2 } else { 1 };
+    (0..rolls).any(|roll| roll_below(dna, trait_string, roll, chance))
+}
+
+/// Rolls against `chance` with entropy derived from the DNA, the trait and the roll index.
+fn roll_below(dna: &DnaHashType, trait_string: &TraitTypeString, roll: u8, chance: Perbill) -> bool {
+    let entropy = sp_io::hashing::blake2_256(&(b"inherit", dna, trait_string, roll).encode());
+    let draw = u32::from_le_bytes([entropy[0], entropy[1], entropy[2], entropy[3]]) % Perbill::ACCURACY;
+    draw < chance.deconstruct()
+}
+
+/// A system for inheriting parent traits during breeding.
+pub struct InheritanceSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> InheritanceSystem<T> {
+    /// Returns the chance that a parent with `trait_string` passes it on: its entry in the
+    /// `InheritanceTable`, or `BaseInheritanceChance`.
+    pub fn chance_of(trait_string: &TraitTypeString) -> Perbill {
+        crate::InheritanceTable::<T>::get(trait_string).unwrap_or_else(T::BaseInheritanceChance::get)
+    }
+
+    /// Returns the chance that a trait is inherited, counting one roll per parent with it.
+    pub fn effective_chance(chance: Perbill, shared: bool) -> Perbill {
+        effective_chance(chance, shared)
+    }
+
+    /// Picks the traits an offspring with `dna` inherits from its parents' traits.
+    ///
+    /// # Parameters
+    ///
+    /// * `dna` - The offspring's DNA, the source of the rolls
+    /// * `parent1_traits` - The first parent's traits
+    /// * `parent2_traits` - The second parent's traits
+    ///
+    /// # Returns
+    ///
+    /// * `InheritedTraits<T>` - The inherited traits, in priority order
+    pub fn inherit_traits(
+        dna: &DnaHashType,
+        parent1_traits: &[TraitTypeString],
+        parent2_traits: &[TraitTypeString],
+    ) -> InheritedTraits<T> {
+        // 1. Collect each distinct parent trait once, noting whether both parents have it.
+        let mut candidates: Vec<Candidate> = Vec::new();
+        for trait_string in parent1_traits.iter().chain(parent2_traits.iter()) {
+            if candidates.iter().any(|candidate| &candidate.trait_string == trait_string) {
+                continue;
+            }
+            let shared = parent1_traits.contains(trait_string) && parent2_traits.contains(trait_string);
+            let chance = Self::effective_chance(Self::chance_of(trait_string), shared);
+            candidates.push(Candidate { trait_string: trait_string.clone(), chance, shared });
+        }
+
+        // 2. Order by priority. The sort is stable, so equal chances keep parent order.
+        candidates.sort_by(|a, b| b.chance.cmp(&a.chance));
+
+        // 3. Roll each candidate until the offspring has no room left.
+        let mut inherited = InheritedTraits::<T>::default();
+        for candidate in candidates {
+            let base = Self::chance_of(&candidate.trait_string);
+            if inherits(dna, &candidate.trait_string, base, candidate.shared)
+                && inherited.try_push(candidate.trait_string).is_err()
+            {
+                break;
+            }
+        }
+
+        inherited
+    }
+}
@@ -0,0 +1,199 @@
+//! # Input Catalog
+//!
+//! The gestures and touch areas `process_gesture` and `process_touch` accept, kept on-chain in
+//! `GestureCatalog` and `TouchAreaCatalog` so the catalog origin can add or retune inputs
+//! without a runtime upgrade, and clients can discover them through the `input_catalog`
+//! runtime API.
+//!
+//! Each entry gives the input's mood effect, the response animation the client plays, the
+//! minimum pet level and mood, and a per-pet cooldown. Unknown IDs are rejected with
+//! `InvalidGesture` or `InvalidTouchArea`. Cooldowns are only stored for inputs that have one.
+//!
+//! The types are always available so the runtime API has a fixed signature; the catalogs
+//! themselves exist only with the `interactive-sessions` feature.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    pallet_prelude::RuntimeDebug,
+    traits::ConstU32,
+    BoundedVec,
+};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+#[cfg(feature = "interactive-sessions")]
+use frame_support::{dispatch::DispatchError, ensure, traits::Get};
+#[cfg(feature = "interactive-sessions")]
+use sp_runtime::traits::Saturating;
+#[cfg(feature = "interactive-sessions")]
+use crate::kinds::CareActionKind;
+#[cfg(feature = "interactive-sessions")]
+use crate::species_care::SpeciesCareSystem;
+#[cfg(feature = "interactive-sessions")]
+use crate::{Config, Error, PetNft};
+
+/// The kind of input a catalog entry describes.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum InputKind {
+    /// A gesture, used by `process_gesture`
+    Gesture,
+
+    /// A touch area, used by `process_touch`
+    TouchArea,
+}
+
+/// A catalogued gesture or touch area and the response it triggers.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct InputCatalogEntry {
+    /// The display name of the input
+    pub name: BoundedVec<u8, ConstU32<32>>,
+
+    /// The mood change the input applies to the pet
+    pub base_mood_effect: i8,
+
+    /// The animation the client plays in response
+    pub response_animation: u8,
+
+    /// The minimum level the pet must have
+    pub min_level: u32,
+
+    /// The minimum mood the pet must have
+    pub min_mood: u8,
+
+    /// The number of blocks before the same pet can receive this input again
+    pub cooldown_blocks: u32,
+}
+
+/// Every catalogued input, as returned by the `input_catalog` runtime API.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct InputCatalog {
+    /// The gestures, by ID
+    pub gestures: Vec<(u8, InputCatalogEntry)>,
+
+    /// The touch areas, by ID
+    pub touch_areas: Vec<(u8, InputCatalogEntry)>,
+}
+
+fn entry(name: &[u8], base_mood_effect: i8, response_animation: u8, min_mood: u8) -> InputCatalogEntry {
+    InputCatalogEntry {
+        name: name.to_vec().try_into().expect("default names fit the bound; qed"),
+        base_mood_effect,
+        response_animation,
+        min_level: 0,
+        min_mood,
+        cooldown_blocks: 0,
+    }
+}
+
+/// The gestures catalogued at genesis, matching the gestures the pallet handled before the
+/// catalog: Pet, Tickle, Stroke, Tap, Swipe, Pinch, Shake and Hold.
+pub fn default_gestures() -> Vec<(u8, InputCatalogEntry)> {
+    sp_std::vec![
+        (0, entry(b"Pet", 5, 0, 0)),
+        (1, entry(b"Tickle", 8, 1, 100)),
+        (2, entry(b"Stroke", 3, 2, 0)),
+        (3, entry(b"Tap", 1, 3, 0)),
+        (4, entry(b"Swipe", 6, 4, 120)),
+        (5, entry(b"Pinch", 0, 5, 0)),
+        (6, entry(b"Shake", 10, 6, 150)),
+        (7, entry(b"Hold", 7, 7, 80)),
+    ]
+}
+
+/// The touch areas catalogued at genesis, with the responses the pallet gave a pet of any mood
+/// before the catalog: Head, Back, Belly, Paws and Tail.
+pub fn default_touch_areas() -> Vec<(u8, InputCatalogEntry)> {
+    sp_std::vec![
+        (0, entry(b"Head", 5, 0, 0)),
+        (1, entry(b"Back", 3, 3, 0)),
+        (2, entry(b"Belly", -2, 6, 0)),
+        (3, entry(b"Paws", 0, 9, 0)),
+        (4, entry(b"Tail", -5, 12, 0)),
+    ]
+}
+
+/// A system for looking up catalogued inputs and applying them to pets.
+#[cfg(feature = "interactive-sessions")]
+pub struct InputCatalogSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "interactive-sessions")]
+impl<T: Config> InputCatalogSystem<T> {
+    /// Gets the catalog entry of an input.
+    ///
+    /// # Parameters
+    ///
+    /// * `kind` - The kind of input
+    /// * `id` - The gesture or touch area ID
+    ///
+    /// # Returns
+    ///
+    /// * `Option<InputCatalogEntry>` - The entry, or `None` if the ID is not catalogued
+    pub fn entry(kind: InputKind, id: u8) -> Option<InputCatalogEntry> {
+        match kind {
+            InputKind::Gesture => crate::GestureCatalog::<T>::get(id),
+            InputKind::TouchArea => crate::TouchAreaCatalog::<T>::get(id),
+        }
+    }
+
+    /// Applies a catalogued input to a pet the caller has already read, and starts the input's
+    /// cooldown for the pet. The caller writes the pet back.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    /// * `kind` - The kind of input
+    /// * `id` - The gesture or touch area ID
+    ///
+    /// # Returns
+    ///
+    /// * `Result<InputCatalogEntry, DispatchError>` - The applied entry, or an error
+    pub fn apply(
+        pet: &mut PetNft<T>,
+        kind: InputKind,
+        id: u8,
+    ) -> Result<InputCatalogEntry, DispatchError> {
+        let entry = Self::entry(kind, id).ok_or(match kind {
+            InputKind::Gesture => Error::<T>::InvalidGesture,
+            InputKind::TouchArea => Error::<T>::InvalidTouchArea,
+        })?;
+
+        ensure!(pet.level >= entry.min_level, Error::<T>::InteractionLevelTooLow);
+        ensure!(pet.mood_indicator >= entry.min_mood, Error::<T>::RequirementsNotMet);
+
+        // A cooldown lowered since the last use applies at once.
+        let now = frame_system::Pallet::<T>::block_number();
+        if let Some(last_block) = crate::PetInputCooldowns::<T>::get(pet.id, (kind, id)) {
+            let ready_at = last_block.saturating_add(entry.cooldown_blocks.into());
+            ensure!(now >= ready_at, Error::<T>::InteractionOnCooldown);
+        }
+        if entry.cooldown_blocks > 0 {
+            crate::PetInputCooldowns::<T>::insert(pet.id, (kind, id), now);
+        }
+
+        if entry.base_mood_effect > 0 {
+            let care_kind = match kind {
+                InputKind::Gesture => CareActionKind::Gesture,
+                InputKind::TouchArea => CareActionKind::Touch,
+            };
+            let factor = SpeciesCareSystem::<T>::factor(pet, care_kind);
+            pet.mood_indicator = pet.mood_indicator
+                .saturating_add(factor.apply_mood(entry.base_mood_effect as u8))
+                .min(T::MaxMoodValue::get());
+        } else if entry.base_mood_effect < 0 {
+            pet.mood_indicator = pet.mood_indicator
+                .saturating_sub(entry.base_mood_effect.unsigned_abs());
+        }
+
+        Ok(entry)
+    }
+
+    /// Lists every catalogued gesture and touch area, in ID order.
+    pub fn catalog() -> InputCatalog {
+        let mut gestures: Vec<(u8, InputCatalogEntry)> = crate::GestureCatalog::<T>::iter().collect();
+        let mut touch_areas: Vec<(u8, InputCatalogEntry)> = crate::TouchAreaCatalog::<T>::iter().collect();
+        gestures.sort_by_key(|(id, _)| *id);
+        touch_areas.sort_by_key(|(id, _)| *id);
+        InputCatalog { gestures, touch_areas }
+    }
+}
@@ -1,3 +1,52 @@
+    pub fn process_touch(
+        pet_id: PetId,
+        touch_area: u8,
+    ) -> Result<TouchResponse, DispatchError> {
+        // Get the pet from storage
+        let mut pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        
+        let response = Self::apply_touch(&mut pet, touch_area)?;
+        crate::PetNfts::<T>::insert(pet_id, pet);
+        
+        Ok(response)
+    }
+    
+    /// Applies a touch to a pet the caller has already read, without touching storage.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet` - The pet
+    /// * `touch_area` - The area being touched
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<TouchResponse, DispatchError>` - The touch response, or an error
+    pub fn apply_touch(
+        pet: &mut PetNft<T>,
+        touch_area: u8,
+    ) -> Result<TouchResponse, DispatchError> {
+        // Get the touch responses
+        let responses = Self::get_all_touch_responses();
+        
+        // Find a response for the touch area that matches the pet's mood
+        let response = responses.iter()
+            .filter(|r| r.touch_area == touch_area && pet.mood_indicator >= r.mood_requirement)
+            .max_by_key(|r| r.mood_requirement)
+            .ok_or(Error::<T>::NoTouchResponse)?;
+        
+        // Apply the mood effect
+        if response.mood_effect > 0 {
+            pet.mood_indicator = pet.mood_indicator
+                .saturating_add(response.mood_effect as u8)
+                .min(T::MaxMoodValue::get());
+        } else if response.mood_effect < 0 {
+            pet.mood_indicator = pet.mood_indicator
+                .saturating_sub((-response.mood_effect) as u8);
+        }
+        
+        Ok(response.clone())
+    }
+    
 //! # Interactive Elements System
 //!
 //! This module provides a system for managing interactive elements in the UI,
@@ -506,6 +555,9 @@ impl<T: Config> InteractiveSessionSystem<T> {
         // Update the session in storage
         crate::InteractiveSessions::<T>::insert(session_id, session.clone());
         
+        // Buff the pet if the session was good enough
+        crate::buffs::BuffSystem::<T>::reward_session(&session);
+        
         Ok(session)
     }
     
@@ -1533,6 +1585,9 @@ impl<T: Config> InteractiveSystem<T> {
         // Update the session in storage
         crate::InteractiveSessions::<T>::insert(session_id, session.clone());
         
+        // Buff the pet if the session was good enough
+        crate::buffs::BuffSystem::<T>::reward_session(&session);
+        
         Ok(session)
     }
     
@@ -2365,8 +2420,29 @@ impl<T: Config> InteractiveSystem<T> {
         gesture_id: u8,
     ) -> Result<(u8, i8), DispatchError> {
         // Get the pet from storage
-        let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        let mut pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        
+        let response = Self::apply_gesture(&mut pet, gesture_id)?;
+        crate::PetNfts::<T>::insert(pet_id, pet);
         
+        Ok(response)
+    }
+    
+    /// Applies a gesture to a pet the caller has already read, without touching storage.
+    /// The caller writes the pet back.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet` - The pet
+    /// * `gesture_id` - The gesture ID
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<(u8, i8), DispatchError>` - The response animation and mood effect, or an error
+    pub fn apply_gesture(
+        pet: &mut PetNft<T>,
+        gesture_id: u8,
+    ) -> Result<(u8, i8), DispatchError> {
         // Get the gesture
         let gestures = Self::get_all_gestures();
         let gesture = gestures.iter()
@@ -2374,7 +2450,7 @@ impl<T: Config> InteractiveSystem<T> {
             .ok_or(Error::<T>::InvalidGesture)?;
         
         // Check if the pet meets the requirements
-        if !Self::check_gesture_requirements(&pet, gesture) {
+        if !Self::check_gesture_requirements(pet, gesture) {
             return Err(Error::<T>::RequirementsNotMet.into());
         }
         
@@ -2385,20 +2461,14 @@ impl<T: Config> InteractiveSystem<T> {
                 mood_effect = *magnitude;
                 
                 // Update the pet's mood
-                crate::PetNfts::<T>::try_mutate(pet_id, |pet_opt| -> DispatchResult {
-                    let pet = pet_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
-                    
-                    if *magnitude > 0 {
-                        pet.mood_indicator = pet.mood_indicator
-                            .saturating_add(*magnitude as u8)
-                            .min(T::MaxMoodValue::get());
-                    } else if *magnitude < 0 {
-                        pet.mood_indicator = pet.mood_indicator
-                            .saturating_sub((-*magnitude) as u8);
-                    }
-                    
-                    Ok(())
-                })?;
+                if *magnitude > 0 {
+                    pet.mood_indicator = pet.mood_indicator
+                        .saturating_add(*magnitude as u8)
+                        .min(T::MaxMoodValue::get());
+                } else if *magnitude < 0 {
+                    pet.mood_indicator = pet.mood_indicator
+                        .saturating_sub((-*magnitude) as u8);
+                }
             }
         }
         
@@ -2435,6 +2505,25 @@ impl<T: Config> InteractiveSystem<T> {
         // Delegate to the TouchInteractionSystem
         TouchInteractionSystem::<T>::process_touch(pet_id, touch_area)
     }
+    
+    /// Applies a touch to a pet the caller has already read, without touching storage.
+    /// The caller writes the pet back.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet` - The pet
+    /// * `touch_area` - The touch area
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<TouchResponse, DispatchError>` - The touch response, or an error
+    pub fn apply_touch(
+        pet: &mut PetNft<T>,
+        touch_area: u8,
+    ) -> Result<TouchResponse, DispatchError> {
+        // Delegate to the TouchInteractionSystem
+        TouchInteractionSystem::<T>::apply_touch(pet, touch_area)
+    }
 }
 
 // ============================================================================
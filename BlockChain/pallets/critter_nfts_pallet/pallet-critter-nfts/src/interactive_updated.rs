@@ -963,6 +963,15 @@ impl<T: Config> InteractiveSessionSystem<T> {
         // Clean up old sessions if needed
         Self::prune_old_sessions(&origin);
         
+        // Buff the pet if the session was good enough
+        let positive = session
+            .interactions
+            .iter()
+            .filter(|interaction| interaction.outcome == crate::buffs::POSITIVE_OUTCOME)
+            .count() as u32;
+        let quality = crate::buffs::BuffSystem::<T>::quality_score(positive, session.interactions.len() as u32);
+        crate::buffs::BuffSystem::<T>::reward(session.pet_id, quality);
+        
         // Emit an event
         crate::Pallet::<T>::deposit_event(crate::Event::InteractiveSessionEnded {
             account_id: origin.clone(),
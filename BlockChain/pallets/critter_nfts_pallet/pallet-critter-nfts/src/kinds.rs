@@ -0,0 +1,162 @@
+//! # Interaction Kinds
+//!
+//! Strongly-typed kinds for the interaction, memory, environment, skill and lifecycle event
+//! parameters that used to be passed around as bare `u8`s. Each kind is a `#[repr(u8)]` enum
+//! with explicit discriminants and encodes as a single byte, so extrinsics and storage keep
+//! their encoding while unknown values are rejected when a call or value is decoded.
+//!
+//! The discriminants are part of the encoding and must never change; new kinds are appended.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Returned when a `u8` does not name a variant of the requested kind.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct InvalidKind(pub u8);
+
+/// Defines a kind enum and its conversions from and to `u8`.
+macro_rules! define_kind {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $value:literal, )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+        #[repr(u8)]
+        pub enum $name {
+            $( $(#[$variant_meta])* $variant = $value, )+
+        }
+
+        impl $name {
+            /// Every variant, in discriminant order.
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = InvalidKind;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                match value {
+                    $( $value => Ok($name::$variant), )+
+                    _ => Err(InvalidKind(value)),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(kind: $name) -> u8 {
+                kind as u8
+            }
+        }
+    };
+}
+
+define_kind! {
+    /// Social interaction kinds. The social interaction registry configures each kind.
+    pub enum InteractionKind {
+        Play = 0,
+        Greet = 1,
+        Share = 2,
+        Compete = 3,
+        Teach = 4,
+        Learn = 5,
+        Comfort = 6,
+        Protect = 7,
+        Explore = 8,
+    }
+}
+
+define_kind! {
+    /// Memory kinds.
+    pub enum MemoryKind {
+        Feeding = 0,
+        Playing = 1,
+        Training = 2,
+        SocialInteraction = 3,
+        EnvironmentalAdaptation = 4,
+        Achievement = 5,
+        LifecycleEvent = 6,
+        OwnerBonding = 7,
+        Neglect = 8,
+        Trauma = 9,
+        Joy = 10,
+        Discovery = 11,
+    }
+}
+
+define_kind! {
+    /// Environment kinds.
+    pub enum EnvironmentKind {
+        Forest = 0,
+        Mountain = 1,
+        Desert = 2,
+        Ocean = 3,
+        Tundra = 4,
+        Volcano = 5,
+        City = 6,
+        Space = 7,
+        Digital = 8,
+        Ethereal = 9,
+    }
+}
+
+define_kind! {
+    /// Skill kinds.
+    pub enum SkillKind {
+        Hunting = 0,
+        Fishing = 1,
+        Gathering = 2,
+        Crafting = 3,
+        Mining = 4,
+        Farming = 5,
+        Cooking = 6,
+        Healing = 7,
+        Combat = 8,
+        Stealth = 9,
+        Diplomacy = 10,
+        Leadership = 11,
+        Magic = 12,
+        Technology = 13,
+        Music = 14,
+        Art = 15,
+    }
+}
+
+define_kind! {
+    /// Lifecycle event kinds.
+    pub enum LifecycleEventKind {
+        Birth = 0,
+        Adolescence = 1,
+        Adulthood = 2,
+        Mastery = 3,
+        Evolution = 4,
+        Transformation = 5,
+        Awakening = 6,
+        Enlightenment = 7,
+        Rebirth = 8,
+    }
+}
+
+define_kind! {
+    /// Care action kinds, as reported by `CareActionPerformed`. Feeding and play are also counted
+    /// in the care history.
+    pub enum CareActionKind {
+        Feed = 0,
+        Play = 1,
+        Gesture = 2,
+        Touch = 3,
+    }
+}
+
+define_kind! {
+    /// Temporary buff kinds. Each boosts one kind of gain, or the pet's effective stats, while
+    /// it lasts.
+    pub enum BuffKind {
+        XpGain = 0,
+        MoodGain = 1,
+        Stats = 2,
+    }
+}
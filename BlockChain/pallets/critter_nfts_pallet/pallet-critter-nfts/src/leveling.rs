@@ -0,0 +1,167 @@
+//! # Leveling
+//!
+//! The XP curve. The runtime picks it with its `LevelCurve` config item, which gives the XP a pet
+//! at each level needs for its next one:
+//!
+//! - `LinearLevelCurve`: `base * n` XP at level `n`; 100 XP per level by default
+//! - `PowerLevelCurve`: `base * n^exponent` XP at level `n`
+//! - `ExponentialLevelCurve`: `base * (1 + growth)^(n - 1)` XP at level `n`, each level needing
+//!   `growth` more than the last
+//!
+//! All of them saturate at `u32::MAX`. The XP a level-up uses is deducted and the rest carries
+//! over. A pet at level zero never levels up, and a pet at `MaxLevel` stays there: XP it earns is
+//! kept, but levels nothing up.
+//!
+//! The curves are free of runtime state, so off-chain simulators can reproduce them from the
+//! `test_vectors` fixtures, which cover the default curve. `LevelingSystem` applies the runtime's
+//! curve and cap.
+
+use core::marker::PhantomData;
+use frame_support::traits::{ConstU32, Get};
+use sp_runtime::{traits::Saturating, FixedPointNumber, FixedU128, Percent};
+use crate::Config;
+
+/// The default XP each level adds to the XP needed for the next one.
+pub const XP_PER_LEVEL: u32 = 100;
+
+/// An XP curve.
+pub trait LevelCurve {
+    /// Gets the XP a pet at `level` needs for its next level.
+    ///
+    /// # Parameters
+    ///
+    /// * `level` - The pet's level
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The XP needed; zero if the pet cannot level up
+    fn xp_for_level(level: u32) -> u32;
+}
+
+/// A linear curve: `Base * n` XP at level `n`. The default, 100 XP per level.
+pub struct LinearLevelCurve<Base = ConstU32<XP_PER_LEVEL>>(PhantomData<Base>);
+
+impl<Base: Get<u32>> LevelCurve for LinearLevelCurve<Base> {
+    fn xp_for_level(level: u32) -> u32 {
+        xp_to_next_level(level, Base::get(), 1)
+    }
+}
+
+/// A power curve: `Base * n^Exponent` XP at level `n`.
+pub struct PowerLevelCurve<Base, Exponent>(PhantomData<(Base, Exponent)>);
+
+impl<Base: Get<u32>, Exponent: Get<u32>> LevelCurve for PowerLevelCurve<Base, Exponent> {
+    fn xp_for_level(level: u32) -> u32 {
+        xp_to_next_level(level, Base::get(), Exponent::get())
+    }
+}
+
+/// An exponential curve: `Base` XP at level 1, and `Growth` more at each level than the last.
+pub struct ExponentialLevelCurve<Base, Growth>(PhantomData<(Base, Growth)>);
+
+impl<Base: Get<u32>, Growth: Get<Percent>> LevelCurve for ExponentialLevelCurve<Base, Growth> {
+    fn xp_for_level(level: u32) -> u32 {
+        exponential_xp_to_next_level(level, Base::get(), Growth::get())
+    }
+}
+
+/// Gets the XP a pet at `level` needs for its next level on a power curve.
+///
+/// # Parameters
+///
+/// * `level` - The pet's level
+/// * `base` - The XP a level 1 pet needs
+/// * `exponent` - The power of the level the XP needed grows with
+///
+/// # Returns
+///
+/// * `u32` - The XP needed, zero at level zero
+pub fn xp_to_next_level(level: u32, base: u32, exponent: u32) -> u32 {
+    if level == 0 {
+        return 0;
+    }
+    base.saturating_mul(level.saturating_pow(exponent))
+}
+
+/// Gets the XP a pet at `level` needs for its next level on an exponential curve.
+///
+/// # Parameters
+///
+/// * `level` - The pet's level
+/// * `base` - The XP a level 1 pet needs
+/// * `growth` - How much more XP each level needs than the last
+///
+/// # Returns
+///
+/// * `u32` - The XP needed, zero at level zero
+pub fn exponential_xp_to_next_level(level: u32, base: u32, growth: Percent) -> u32 {
+    if level == 0 {
+        return 0;
+    }
+    let factor = FixedU128::saturating_from_rational(100 + growth.deconstruct() as u32, 100);
+    factor.saturating_pow((level - 1) as usize).saturating_mul_int(base)
+}
+
+/// Levels a pet up once, if its XP covers the next level on curve `C`.
+///
+/// # Parameters
+///
+/// * `level` - The pet's level
+/// * `xp` - The pet's XP towards its next level
+/// * `max_level` - The highest level a pet can reach
+///
+/// # Returns
+///
+/// * `Option<(u32, u32)>` - The new level and the XP left over, or `None` if the pet cannot
+///   level up
+pub fn level_up_once<C: LevelCurve>(level: u32, xp: u32, max_level: u32) -> Option<(u32, u32)> {
+    if level >= max_level {
+        return None;
+    }
+    let needed = C::xp_for_level(level);
+    if needed == 0 || xp < needed {
+        return None;
+    }
+    Some((level + 1, xp - needed))
+}
+
+/// Levels a pet up as often as its XP allows on curve `C`.
+///
+/// # Parameters
+///
+/// * `level` - The pet's level
+/// * `xp` - The pet's XP towards its next level
+/// * `max_level` - The highest level a pet can reach
+///
+/// # Returns
+///
+/// * `(u32, u32)` - The new level and the XP left over
+pub fn level_up<C: LevelCurve>(mut level: u32, mut xp: u32, max_level: u32) -> (u32, u32) {
+    while let Some((next_level, left)) = level_up_once::<C>(level, xp, max_level) {
+        level = next_level;
+        xp = left;
+    }
+    (level, xp)
+}
+
+/// The XP curve with the runtime's `LevelCurve` and `MaxLevel`.
+pub struct LevelingSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> LevelingSystem<T> {
+    /// Gets the XP a pet at `level` needs for its next level.
+    pub fn xp_to_next_level(level: u32) -> u32 {
+        T::LevelCurve::xp_for_level(level)
+    }
+
+    /// Levels a pet up once, if its XP covers the next level. See `level_up_once`.
+    pub fn level_up_once(level: u32, xp: u32) -> Option<(u32, u32)> {
+        level_up_once::<T::LevelCurve>(level, xp, T::MaxLevel::get())
+    }
+
+    /// Levels a pet up as often as its XP allows. See `level_up`.
+    pub fn level_up(level: u32, xp: u32) -> (u32, u32) {
+        level_up::<T::LevelCurve>(level, xp, T::MaxLevel::get())
+    }
+}
@@ -47,6 +47,7 @@ pub mod seasonal;
 pub mod lifecycle;
 
 // Include the analytics module
+#[cfg(feature = "analytics")]
 pub mod analytics;
 
 // Include the UI bridge module
@@ -56,20 +57,163 @@ pub mod ui_bridge;
 pub mod visual;
 
 // Include the interactive elements module
+#[cfg(feature = "interactive-sessions")]
 pub mod interactive;
 
 // Include the updated interactive elements module with enhanced security and optimizations
+#[cfg(feature = "interactive-sessions")]
 pub mod interactive_updated;
 
+// Include the gesture and touch area catalog module
+pub mod input_catalog;
+
 // Include the user experience module
 pub mod user_experience;
 
+// Include the pet valuation module
+pub mod valuation;
+
+// Include the auto-care subscriptions module
+pub mod auto_care;
+
+// Include the pending effects queue module
+pub mod pending_effects;
+
+// Include the typed interaction, memory, environment, skill and lifecycle event kinds
+pub mod kinds;
+
+// Include the per-account rate limiter
+pub mod rate_limit;
+
+// Include the two-party pet trades module
+pub mod trade;
+
+// Include the pet lineage module
+pub mod lineage;
+
+// Include the pending claims module for bred pets that do not fit
+pub mod pending_claims;
+
+// Include the mint preview module
+pub mod mint_preview;
+
+// Include the species care preferences module
+pub mod species_care;
+
+// Include the governable game parameters
+pub mod game_params;
+
+// Include the rested XP module
+pub mod rested_xp;
+
+// Include the care history module
+pub mod care_history;
+
+// Include the unified care events module
+pub mod care_events;
+
+// Include the personality trait provenance module
+pub mod trait_provenance;
+
+// Include the temporary trait module
+pub mod temporary_traits;
+
+// Include the breeding trait inheritance module
+pub mod inheritance;
+
+// Include the offspring DNA crossover module
+pub mod breeding_genetics;
+
+// Include the deprecated call tracking module
+pub mod deprecation;
+
+// Include the pet dossier module
+pub mod dossier;
+
+// Include the temporary buffs module
+pub mod buffs;
+
+// Include the featured pet rotation module
+pub mod featured;
+
+// Include the mint DNA derivation module
+pub mod dna;
+
+// Include the storage footprint module
+pub mod footprint;
+
+// Include the pet sacrifice module
+pub mod sacrifice;
+
+// Include the effective stats module
+pub mod effective_stats;
+
+// Include the anti-flip transfer gates module
+pub mod transfer_gates;
+
+// Include the account migration module
+pub mod account_migration;
+
+// Include the free care module
+pub mod free_care;
+
+// Include the pet release module
+pub mod release;
+
+// Include the round-up donations module
+pub mod donations;
+
+// Include the XP curve module
+pub mod leveling;
+
+// Include the pet burning module
+pub mod burn;
+
+// Include the pet delegation module
+pub mod delegation;
+
+// Include the batch transfer module
+pub mod batch_transfer;
+
+// Include the neglect check module
+pub mod neglect;
+
+// Include the transfer approval module
+pub mod approvals;
+
+// Include the benchmark-derived weights
+pub mod weights;
+
+// Include the benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+// Include the canonical formulas and golden fixtures for off-chain simulators
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
+
+// Include the capability-scoped configuration sub-traits
+pub mod config;
+
+// Include the runtime API declarations
+pub mod runtime_api;
+
+// Include the JSON-RPC server for the pet profile queries
+#[cfg(feature = "rpc")]
+pub mod rpc;
+
+// Include the storage migrations
+pub mod migrations;
+
+#[cfg(test)]
+mod test;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
-        dispatch::DispatchResult,
+        dispatch::{DispatchResult, Pays, PostDispatchInfo, WithPostDispatchInfo},
         pallet_prelude::*, // Provides common types and macros for pallets
-        traits::{Currency, Randomness, UnixTime}, // Currency for balances, Randomness for DNA, UnixTime for timestamps (if used)
+        traits::{Currency, ExistenceRequirement, Randomness, UnixTime}, // Currency for balances, Randomness for DNA, UnixTime for event timestamps
         BoundedVec, // For bounded collections, crucial for security
     };
     use frame_system::{
@@ -90,11 +234,20 @@ pub mod pallet {
         SpeciesType,        // Type for Species (e.g., BoundedVec<u8, MaxSpeciesNameLen>)
         TraitTypeString,    // Type for Personality Trait strings (e.g., BoundedVec<u8, MaxTraitStringLen>)
         ItemCategoryTag,    // For `BasicCareItemConsumer` (e.g., u8 for enum variant index)
+        CareError,          // Why `BasicCareItemConsumer` failed to consume an item
+        LockerId,           // The pallet holding a lock on a pet
     };
     use sp_std::vec::Vec; // Standard Vec for dynamic arrays (used where not bounded)
     use scale_info::TypeInfo; // For `TypeInfo` derive macro
     use frame_support::log; // Correct way to import Substrate's logging macro
     use sp_runtime::SaturatedFrom; // For saturating arithmetic
+    use sp_runtime::traits::Zero;
+    use sp_runtime::Percent;
+    use crittercraft_traits::nft::TransferRestrictionProvider;
+    use crittercraft_traits::types::{DeferredEffect, DeferredEffectId, ListingEligibility, TransferBlockReason};
+    use crate::kinds::{BuffKind, CareActionKind, EnvironmentKind, InteractionKind, LifecycleEventKind, MemoryKind, SkillKind};
+    use crate::config::{NftCoreConfig, SessionConfig, SocialConfig, SyncConfig, UxConfig};
+    use crate::weights::WeightInfo;
 
     // --- Type Aliases ---
     // These aliases enhance clarity, aligning with "Know Your Core, Keep it Clear".
@@ -116,6 +269,45 @@ pub mod pallet {
         Mystic,
     }
 
+    // BatchMintValidation: Per-entry result of validating a `batch_mint_pet_nfts` input.
+    // Shared by the extrinsic and the `validate_batch_mint` runtime API so they cannot drift.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum BatchMintValidation {
+        /// The entry would be minted successfully.
+        Ok,
+        /// The species name exceeds MaxSpeciesNameLen.
+        SpeciesNameTooLong,
+        /// The pet name exceeds MaxPetNameLen.
+        PetNameTooLong,
+        /// Minting the entry at this index would exceed MaxOwnedPets.
+        WouldExceedCapacity(u32),
+    }
+
+    // SpeciesPopulationRecord: Lifetime population counters for one species, for game balance telemetry.
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub struct SpeciesPopulationRecord {
+        /// Pets of this species ever minted (including bred pets).
+        pub minted: u32,
+        /// Pets of this species released or burned.
+        pub released: u32,
+    }
+
+    impl SpeciesPopulationRecord {
+        /// The number of pets of this species currently alive.
+        pub fn living(&self) -> u32 {
+            self.minted.saturating_sub(self.released)
+        }
+    }
+
+    // LockInfo: Who holds the lock on a locked pet, and since when.
+    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct LockInfo<BlockNumber> {
+        /// The pallet holding the lock; only it can release the lock.
+        pub locker: LockerId,
+        /// The block the lock was taken at.
+        pub locked_at: BlockNumber,
+    }
+
     // --- Struct Definitions ---
     // PetNft: Defines the core attributes and state of a CritterCraft Pet NFT.
     // #[scale_info(skip_type_params(T))] is important when T is only used in BoundedVec/Storage, not directly in struct.
@@ -159,7 +351,7 @@ pub mod pallet {
         
         // --- Synchronization Flags ---
         // Used to track which aspects of the pet state have been synchronized with off-chain systems
-        pub sync_flags: u8, // Bitfield for tracking sync status (0x01 = basic info, 0x02 = stats, 0x04 = traits, etc.)
+        pub sync_flags: crate::sync::SyncFlags, // Change types not yet synchronized, one bit per StateChangeType
         
         // --- Locking Status ---
         // Used to prevent interactions with the pet during certain operations
@@ -169,180 +361,724 @@ pub mod pallet {
         // Used for rate limiting and tracking recent activity
         pub last_interaction_time: BlockNumberFor<T>, // Block number of the last interaction
         
-        // V2+: Parent IDs for breeding traceability
-        // pub parent1_id: Option<PetId>,
-        // pub parent2_id: Option<PetId>,
+        // --- Growth Bonuses ---
+        // Stat growth earned over the pet's life (e.g., lifecycle stage rewards).
+        // Kept separate so the charter attributes above stay immutable; see `effective_stats`.
+        pub bonus_strength: u8,
+        pub bonus_agility: u8,
+        pub bonus_intelligence: u8,
+        pub bonus_vitality: u8,
+        
+        // --- Owner ---
+        // Embedded so the hot care paths check ownership with the same read that loads the pet.
+        // Kept in sync with `PetNftOwner`, which remains as the index for owner lookups by pet.
+        pub owner: T::AccountId,
+        
+        // --- Generation ---
+        // 0 for minted pets; a bred pet is one more than the later of its parents' generations,
+        // saturating at `u32::MAX`.
+        pub generation: u32,
+        
+        // --- Acquisition ---
+        // The block the current owner received the pet: its mint, claim or latest transfer.
+        // The ownership transfer gate counts from here; see `transfer_gates`.
+        pub acquired_at_block: BlockNumberFor<T>,
+        
+        // --- Parents ---
+        // The pets this one was bred from; `None` for minted pets. Mirrors `PetParents`, which
+        // outlives the pet so family trees stay whole after a release.
+        pub parent1_id: Option<PetId>,
+        pub parent2_id: Option<PetId>,
     }
 
     // BalanceOf<T> type alias for the pallet's currency type.
-    pub(crate) type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub(crate) type BalanceOf<T> = <<T as NftCoreConfig>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
     // --- Pallet Configuration Trait ---
-    // Defines the types and constants that the runtime must provide for this pallet to function.
+    // The umbrella over the capability-scoped sub-traits in `crate::config`.
+    // Only the event type lives here; everything else is provided through a sub-trait.
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config:
+        frame_system::Config
+        + frame_system::offchain::SendTransactionTypes<Call<Self>>
+        + NftCoreConfig
+        + SyncConfig
+        + SocialConfig
+        + SessionConfig
+        + UxConfig
+    {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        /// The currency trait for handling PTCN token balances.
-        type Currency: Currency<Self::AccountId>;
+    }
 
-        /// The randomness trait for generating deterministic DNA hashes.
-        type PetRandomness: Randomness<Self::Hash, Self::BlockNumber>;
+    // --- Pallet Constants ---
+    // The sub-trait constants are not `#[pallet::constant]`s, so they are exposed here to keep
+    // them in the runtime metadata under their original names.
+    #[pallet::extra_constants]
+    impl<T: Config> Pallet<T> {
         /// Maximum number of Pet NFTs an account can own. Crucial for limiting state bloat.
-        #[pallet::constant]
-        type MaxOwnedPets: Get<u32>;
+        #[pallet::constant_name(MaxOwnedPets)]
+        fn max_owned_pets() -> u32 {
+            <T as NftCoreConfig>::MaxOwnedPets::get()
+        }
+
         /// Maximum length of a pet's species name (in bytes). Crucial for input validation.
-        #[pallet::constant]
-        type MaxSpeciesNameLen: Get<u32>;
+        #[pallet::constant_name(MaxSpeciesNameLen)]
+        fn max_species_name_len() -> u32 {
+            <T as NftCoreConfig>::MaxSpeciesNameLen::get()
+        }
+
         /// Maximum length of a pet's current name (in bytes). Crucial for input validation.
-        #[pallet::constant]
-        type MaxPetNameLen: Get<u32>;
+        #[pallet::constant_name(MaxPetNameLen)]
+        fn max_pet_name_len() -> u32 {
+            <T as NftCoreConfig>::MaxPetNameLen::get()
+        }
+
         /// Maximum length of a single personality trait string (in bytes).
-        #[pallet::constant]
-        type MaxTraitStringLen: Get<u32>;
+        #[pallet::constant_name(MaxTraitStringLen)]
+        fn max_trait_string_len() -> u32 {
+            <T as NftCoreConfig>::MaxTraitStringLen::get()
+        }
+
         /// Maximum number of personality traits a pet can have.
-        #[pallet::constant]
-        type MaxPetPersonalityTraits: Get<u32>;
+        #[pallet::constant_name(MaxPetPersonalityTraits)]
+        fn max_pet_personality_traits() -> u32 {
+            <T as NftCoreConfig>::MaxPetPersonalityTraits::get()
+        }
+
+        /// Maximum number of temporary traits a pet can hold, apart from its permanent traits.
+        #[pallet::constant_name(MaxTemporaryTraits)]
+        fn max_temporary_traits() -> u32 {
+            <T as NftCoreConfig>::MaxTemporaryTraits::get()
+        }
+
         /// Maximum value for `mood_indicator` (e.g., 100 or 200).
-        #[pallet::constant]
-        type MaxMoodValue: Get<u8>;
+        #[pallet::constant_name(MaxMoodValue)]
+        fn max_mood_value() -> u8 {
+            <T as NftCoreConfig>::MaxMoodValue::get()
+        }
+
         /// Amount of mood restored from basic feeding.
-        #[pallet::constant]
-        type FeedMoodBoost: Get<u8>;
+        #[pallet::constant_name(FeedMoodBoost)]
+        fn feed_mood_boost() -> u8 {
+            <T as NftCoreConfig>::FeedMoodBoost::get()
+        }
+
         /// Amount of mood restored from basic playing.
-        #[pallet::constant]
-        type PlayMoodBoost: Get<u8>;
+        #[pallet::constant_name(PlayMoodBoost)]
+        fn play_mood_boost() -> u8 {
+            <T as NftCoreConfig>::PlayMoodBoost::get()
+        }
+
         /// XP gained from basic feeding.
-        #[pallet::constant]
-        type FeedXpGain: Get<u32>;
+        #[pallet::constant_name(FeedXpGain)]
+        fn feed_xp_gain() -> u32 {
+            <T as NftCoreConfig>::FeedXpGain::get()
+        }
+
         /// XP gained from basic playing.
-        #[pallet::constant]
-        type PlayXpGain: Get<u32>;
+        #[pallet::constant_name(PlayXpGain)]
+        fn play_xp_gain() -> u32 {
+            <T as NftCoreConfig>::PlayXpGain::get()
+        }
+
         /// Mood penalty applied due to neglect.
-        #[pallet::constant]
-        type NeglectMoodPenalty: Get<u8>;
+        #[pallet::constant_name(NeglectMoodPenalty)]
+        fn neglect_mood_penalty() -> u8 {
+            <T as NftCoreConfig>::NeglectMoodPenalty::get()
+        }
+
         /// Number of blocks after which neglect effects might apply.
-        #[pallet::constant]
-        type NeglectThresholdBlocks: Get<Self::BlockNumber>;
+        #[pallet::constant_name(NeglectThresholdBlocks)]
+        fn neglect_threshold_blocks() -> T::BlockNumber {
+            <T as NftCoreConfig>::NeglectThresholdBlocks::get()
+        }
+
         /// Amount of PTCN claimed daily by users.
-        #[pallet::constant]
-        type DailyClaimAmount: Get<BalanceOf<Self>>;
+        #[pallet::constant_name(DailyClaimAmount)]
+        fn daily_claim_amount() -> BalanceOf<T> {
+            <T as NftCoreConfig>::DailyClaimAmount::get()
+        }
+
         /// Cooldown period (in blocks) for daily PTCN claims.
-        #[pallet::constant]
-        type ClaimCooldownPeriod: Get<Self::BlockNumber>;
+        #[pallet::constant_name(ClaimCooldownPeriod)]
+        fn claim_cooldown_period() -> T::BlockNumber {
+            <T as NftCoreConfig>::ClaimCooldownPeriod::get()
+        }
+
+        /// Maximum number of lifecycle events a pet can experience.
+        #[pallet::constant_name(MaxLifecycleEvents)]
+        fn max_lifecycle_events() -> u32 {
+            <T as NftCoreConfig>::MaxLifecycleEvents::get()
+        }
+
+        /// Maximum number of visual attributes a pet can have.
+        #[pallet::constant_name(MaxVisualAttributes)]
+        fn max_visual_attributes() -> u32 {
+            <T as NftCoreConfig>::MaxVisualAttributes::get()
+        }
+
+        /// Maximum length of a pet's free-text bio (in bytes).
+        #[pallet::constant_name(MaxPetBioLen)]
+        fn max_pet_bio_len() -> u32 {
+            <T as NftCoreConfig>::MaxPetBioLen::get()
+        }
+
+        /// Quality score weight per pet level.
+        #[pallet::constant_name(QualityLevelWeight)]
+        fn quality_level_weight() -> u32 {
+            <T as NftCoreConfig>::QualityLevelWeight::get()
+        }
+
+        /// Quality score weight per point of base stat total.
+        #[pallet::constant_name(QualityStatWeight)]
+        fn quality_stat_weight() -> u32 {
+            <T as NftCoreConfig>::QualityStatWeight::get()
+        }
+
+        /// Quality score weight per point of personality trait rarity.
+        #[pallet::constant_name(QualityTraitRarityWeight)]
+        fn quality_trait_rarity_weight() -> u32 {
+            <T as NftCoreConfig>::QualityTraitRarityWeight::get()
+        }
+
+        /// Quality score weight per earned achievement.
+        #[pallet::constant_name(QualityAchievementWeight)]
+        fn quality_achievement_weight() -> u32 {
+            <T as NftCoreConfig>::QualityAchievementWeight::get()
+        }
+
+        /// Quality score weight per point of battle rating.
+        #[pallet::constant_name(QualityBattleRatingWeight)]
+        fn quality_battle_rating_weight() -> u32 {
+            <T as NftCoreConfig>::QualityBattleRatingWeight::get()
+        }
+
+        /// Quality score weight per prestige level.
+        #[pallet::constant_name(QualityPrestigeWeight)]
+        fn quality_prestige_weight() -> u32 {
+            <T as NftCoreConfig>::QualityPrestigeWeight::get()
+        }
+
+        /// Quality score penalty per time the pet has been bred.
+        #[pallet::constant_name(QualityBreedPenalty)]
+        fn quality_breed_penalty() -> u32 {
+            <T as NftCoreConfig>::QualityBreedPenalty::get()
+        }
+
+        /// Quality score weight per breeding generation; negative values lower the score.
+        #[pallet::constant_name(QualityGenerationWeight)]
+        fn quality_generation_weight() -> i32 {
+            <T as NftCoreConfig>::QualityGenerationWeight::get()
+        }
+
+        /// The fee charged per auto-care interval.
+        #[pallet::constant_name(AutoCareFee)]
+        fn auto_care_fee() -> BalanceOf<T> {
+            <T as NftCoreConfig>::AutoCareFee::get()
+        }
+
+        /// The part of each auto-care fee paid to whoever executes the care.
+        #[pallet::constant_name(AutoCareBounty)]
+        fn auto_care_bounty() -> BalanceOf<T> {
+            <T as NftCoreConfig>::AutoCareBounty::get()
+        }
+
+        /// Number of blocks between automated care executions.
+        #[pallet::constant_name(AutoCareInterval)]
+        fn auto_care_interval() -> T::BlockNumber {
+            <T as NftCoreConfig>::AutoCareInterval::get()
+        }
+
+        /// The effect of automated care relative to manual care.
+        #[pallet::constant_name(AutoCareEffect)]
+        fn auto_care_effect() -> Percent {
+            <T as NftCoreConfig>::AutoCareEffect::get()
+        }
+
+        /// Maximum number of deferred effects that can be queued for a single block.
+        #[pallet::constant_name(MaxPendingEffectsPerBlock)]
+        fn max_pending_effects_per_block() -> u32 {
+            <T as NftCoreConfig>::MaxPendingEffectsPerBlock::get()
+        }
+
+        /// Number of blocks between featured pet rotations.
+        #[pallet::constant_name(FeatureRotationPeriod)]
+        fn feature_rotation_period() -> T::BlockNumber {
+            <T as NftCoreConfig>::FeatureRotationPeriod::get()
+        }
+
+        /// Maximum number of pets featured per rotation.
+        #[pallet::constant_name(FeaturedCount)]
+        fn featured_count() -> u32 {
+            <T as NftCoreConfig>::FeaturedCount::get()
+        }
+
+        /// Maximum number of drawn candidates a rotation may skip.
+        #[pallet::constant_name(FeaturedMaxRetries)]
+        fn featured_max_retries() -> u32 {
+            <T as NftCoreConfig>::FeaturedMaxRetries::get()
+        }
+
+        /// Maximum number of pet IDs `on_initialize` checks for neglect per block.
+        #[pallet::constant_name(MaxNeglectChecksPerBlock)]
+        fn max_neglect_checks_per_block() -> u32 {
+            <T as NftCoreConfig>::MaxNeglectChecksPerBlock::get()
+        }
+
+        /// Maximum number of pets in one unsigned neglect batch from the off-chain worker.
+        #[pallet::constant_name(MaxNeglectBatchPerBlock)]
+        fn max_neglect_batch_per_block() -> u32 {
+            <T as NftCoreConfig>::MaxNeglectBatchPerBlock::get()
+        }
+
+        /// The one-time bonus paid to the owner of each featured pet.
+        #[pallet::constant_name(FeaturedPetBonus)]
+        fn featured_pet_bonus() -> BalanceOf<T> {
+            <T as NftCoreConfig>::FeaturedPetBonus::get()
+        }
+
+        /// Number of blocks a proposed trade can be accepted for.
+        #[pallet::constant_name(TradeExpiryBlocks)]
+        fn trade_expiry_blocks() -> T::BlockNumber {
+            <T as NftCoreConfig>::TradeExpiryBlocks::get()
+        }
+
+        /// Number of blocks a pet must have been owned before it can be transferred or listed.
+        #[pallet::constant_name(MinOwnershipBlocksBeforeTransfer)]
+        fn min_ownership_blocks_before_transfer() -> T::BlockNumber {
+            <T as NftCoreConfig>::MinOwnershipBlocksBeforeTransfer::get()
+        }
+
+        /// Level a pet must have reached before it can be transferred or listed.
+        #[pallet::constant_name(MinLevelForTransfer)]
+        fn min_level_for_transfer() -> u32 {
+            <T as NftCoreConfig>::MinLevelForTransfer::get()
+        }
+
+        /// What breeding does when the recipient already owns `MaxOwnedPets` pets.
+        #[pallet::constant_name(BreedingOverflowPolicy)]
+        fn breeding_overflow_policy() -> pending_claims::OverflowPolicy {
+            <T as NftCoreConfig>::BreedingOverflowPolicy::get()
+        }
+
+        /// Number of blocks a parked pet can be claimed for before it is burned.
+        #[pallet::constant_name(PendingClaimExpiry)]
+        fn pending_claim_expiry() -> T::BlockNumber {
+            <T as NftCoreConfig>::PendingClaimExpiry::get()
+        }
+
+        /// Maximum number of pets parked for one recipient.
+        #[pallet::constant_name(MaxPendingClaims)]
+        fn max_pending_claims() -> u32 {
+            <T as NftCoreConfig>::MaxPendingClaims::get()
+        }
+
+        /// Maximum number of pets sacrificed in one call.
+        #[pallet::constant_name(MaxSacrificesPerCall)]
+        fn max_sacrifices_per_call() -> u32 {
+            <T as NftCoreConfig>::MaxSacrificesPerCall::get()
+        }
+
+        /// Maximum bonus a pet can receive from sacrifices, per bonus stat.
+        #[pallet::constant_name(MaxSacrificeBonus)]
+        fn max_sacrifice_bonus() -> u8 {
+            <T as NftCoreConfig>::MaxSacrificeBonus::get()
+        }
+
+        /// Maximum number of account migration categories migrated per block.
+        #[pallet::constant_name(AccountMigrationStepsPerBlock)]
+        fn account_migration_steps_per_block() -> u32 {
+            <T as NftCoreConfig>::AccountMigrationStepsPerBlock::get()
+        }
+
+        /// The effect of free care relative to item-based care.
+        #[pallet::constant_name(FreeCareEffect)]
+        fn free_care_effect() -> Percent {
+            <T as NftCoreConfig>::FreeCareEffect::get()
+        }
+
+        /// Maximum number of free care actions per pet per care epoch.
+        #[pallet::constant_name(MaxFreeCarePerDay)]
+        fn max_free_care_per_day() -> u32 {
+            <T as NftCoreConfig>::MaxFreeCarePerDay::get()
+        }
+
+        /// The largest share of a fee an account can round up as a donation.
+        #[pallet::constant_name(MaxDonationPercent)]
+        fn max_donation_percent() -> sp_runtime::Perbill {
+            <T as NftCoreConfig>::MaxDonationPercent::get()
+        }
+
+        /// The PTCN paid to the owner of a burned pet.
+        #[pallet::constant_name(BurnCompensation)]
+        fn burn_compensation() -> BalanceOf<T> {
+            <T as NftCoreConfig>::BurnCompensation::get()
+        }
+
+        /// Maximum number of pets `batch_transfer_pet_nfts` moves in one call.
+        #[pallet::constant_name(MaxBatchTransferSize)]
+        fn max_batch_transfer_size() -> u32 {
+            <T as NftCoreConfig>::MaxBatchTransferSize::get()
+        }
+
+        /// How much of a mint's outcome the `mint_preview` runtime API reveals.
+        #[pallet::constant_name(MintPreviewMode)]
+        fn mint_preview_mode() -> mint_preview::MintPreviewMode {
+            <T as NftCoreConfig>::MintPreviewMode::get()
+        }
+
+        /// Number of candidate outcomes a distribution mint preview covers.
+        #[pallet::constant_name(MintPreviewSamples)]
+        fn mint_preview_samples() -> u32 {
+            <T as NftCoreConfig>::MintPreviewSamples::get()
+        }
+
+        /// Chance that a parent passes a trait on when breeding, unless the `InheritanceTable` overrides it.
+        #[pallet::constant_name(BaseInheritanceChance)]
+        fn base_inheritance_chance() -> sp_runtime::Perbill {
+            <T as NftCoreConfig>::BaseInheritanceChance::get()
+        }
+
+        /// Chance that each byte of a bred pet's DNA mutates after crossover.
+        #[pallet::constant_name(MutationRate)]
+        fn mutation_rate() -> sp_runtime::Perbill {
+            <T as NftCoreConfig>::MutationRate::get()
+        }
+
+        /// Maximum number of children indexed per pet.
+        #[pallet::constant_name(MaxChildrenPerPet)]
+        fn max_children_per_pet() -> u32 {
+            <T as NftCoreConfig>::MaxChildrenPerPet::get()
+        }
+
+        /// Maximum number of pets returned by a single lineage query.
+        #[pallet::constant_name(MaxLineageNodes)]
+        fn max_lineage_nodes() -> u32 {
+            <T as NftCoreConfig>::MaxLineageNodes::get()
+        }
+
+        /// Rested XP a pet accrues per block without gaining XP.
+        #[pallet::constant_name(RestedXpPerBlock)]
+        fn rested_xp_per_block() -> u32 {
+            <T as NftCoreConfig>::RestedXpPerBlock::get()
+        }
+
+        /// Maximum rested XP a pet can accrue.
+        #[pallet::constant_name(MaxRestedXp)]
+        fn max_rested_xp() -> u32 {
+            <T as NftCoreConfig>::MaxRestedXp::get()
+        }
+
+        /// The bonus paid on XP matched by rested XP.
+        #[pallet::constant_name(RestedXpBonus)]
+        fn rested_xp_bonus() -> Percent {
+            <T as NftCoreConfig>::RestedXpBonus::get()
+        }
+
+        /// The highest level a pet can reach.
+        #[pallet::constant_name(MaxLevel)]
+        fn max_level() -> u32 {
+            <T as NftCoreConfig>::MaxLevel::get()
+        }
+
+        /// Number of blocks in a care history epoch.
+        #[pallet::constant_name(CareEpochBlocks)]
+        fn care_epoch_blocks() -> T::BlockNumber {
+            <T as NftCoreConfig>::CareEpochBlocks::get()
+        }
+
+        /// Number of epochs of care history kept per pet.
+        #[pallet::constant_name(CareHistoryEpochs)]
+        fn care_history_epochs() -> u32 {
+            <T as NftCoreConfig>::CareHistoryEpochs::get()
+        }
+
+        /// Whether calls into deprecated interfaces are counted.
+        #[pallet::constant_name(TrackDeprecatedCalls)]
+        fn track_deprecated_calls() -> bool {
+            <T as NftCoreConfig>::TrackDeprecatedCalls::get()
+        }
+
+        /// Whether care actions also emit their legacy events next to `CareActionPerformed`.
+        #[pallet::constant_name(EmitLegacyCareEvents)]
+        fn emit_legacy_care_events() -> bool {
+            <T as NftCoreConfig>::EmitLegacyCareEvents::get()
+        }
+
+        /// Number of blocks in a rate limit window.
+        #[pallet::constant_name(RateLimitWindow)]
+        fn rate_limit_window() -> T::BlockNumber {
+            <T as NftCoreConfig>::RateLimitWindow::get()
+        }
+
+        /// Feeds an account may make per rate limit window; zero disables the limit.
+        #[pallet::constant_name(FeedRateLimit)]
+        fn feed_rate_limit() -> u32 {
+            <T as NftCoreConfig>::FeedRateLimit::get()
+        }
+
+        /// Play sessions an account may make per rate limit window; zero disables the limit.
+        #[pallet::constant_name(PlayRateLimit)]
+        fn play_rate_limit() -> u32 {
+            <T as NftCoreConfig>::PlayRateLimit::get()
+        }
+
+        /// Training sessions an account may make per rate limit window; zero disables the limit.
+        #[pallet::constant_name(TrainRateLimit)]
+        fn train_rate_limit() -> u32 {
+            <T as NftCoreConfig>::TrainRateLimit::get()
+        }
+
+        /// Gestures an account may make per rate limit window; zero disables the limit.
+        #[pallet::constant_name(GestureRateLimit)]
+        fn gesture_rate_limit() -> u32 {
+            <T as NftCoreConfig>::GestureRateLimit::get()
+        }
+
+        /// Touches an account may make per rate limit window; zero disables the limit.
+        #[pallet::constant_name(TouchRateLimit)]
+        fn touch_rate_limit() -> u32 {
+            <T as NftCoreConfig>::TouchRateLimit::get()
+        }
+
+        /// Social interactions an account may start per rate limit window; zero disables the limit.
+        #[pallet::constant_name(SocialRateLimit)]
+        fn social_rate_limit() -> u32 {
+            <T as NftCoreConfig>::SocialRateLimit::get()
+        }
+
+        /// Memory writes an account may make per rate limit window; zero disables the limit.
+        #[pallet::constant_name(MemoryRateLimit)]
+        fn memory_rate_limit() -> u32 {
+            <T as NftCoreConfig>::MemoryRateLimit::get()
+        }
 
         /// Maximum number of execution statistics entries to store per hook.
-        #[pallet::constant]
-        type MaxHookExecutionStats: Get<u32>;
-        
+        #[pallet::constant_name(MaxHookExecutionStats)]
+        fn max_hook_execution_stats() -> u32 {
+            <T as SyncConfig>::MaxHookExecutionStats::get()
+        }
+
         /// Maximum number of synchronization status entries to store per pet.
-        #[pallet::constant]
-        type MaxSyncStatusEntries: Get<u32>;
-        
+        #[pallet::constant_name(MaxSyncStatusEntries)]
+        fn max_sync_status_entries() -> u32 {
+            <T as SyncConfig>::MaxSyncStatusEntries::get()
+        }
+
         /// Maximum number of hooks that can be registered.
-        #[pallet::constant]
-        type MaxRegisteredHooks: Get<u32>;
-        
+        #[pallet::constant_name(MaxRegisteredHooks)]
+        fn max_registered_hooks() -> u32 {
+            <T as SyncConfig>::MaxRegisteredHooks::get()
+        }
+
         /// Maximum execution time for a hook in milliseconds.
-        #[pallet::constant]
-        type MaxHookExecutionTimeMs: Get<u32>;
-        
-        /// Maximum size of a pet's compressed interaction history.
-        #[pallet::constant]
-        type MaxInteractionHistorySize: Get<u32>;
-        
-        /// Maximum number of behavior predictions to store for a pet.
-        #[pallet::constant]
-        type MaxBehaviorPredictions: Get<u32>;
-        
-        /// Maximum number of state transition probabilities to store for a pet.
-        #[pallet::constant]
-        type MaxTransitionProbabilities: Get<u32>;
-        
-        /// Threshold for adaptive behavior adjustments.
-        #[pallet::constant]
-        type AdaptiveBehaviorThreshold: Get<u32>;
-        
+        #[pallet::constant_name(MaxHookExecutionTimeMs)]
+        fn max_hook_execution_time_ms() -> u32 {
+            <T as SyncConfig>::MaxHookExecutionTimeMs::get()
+        }
+
+        /// Weighted consecutive failures after which a hook is disabled.
+        #[pallet::constant_name(HookAutoDisableThreshold)]
+        fn hook_auto_disable_threshold() -> u32 {
+            <T as SyncConfig>::HookAutoDisableThreshold::get()
+        }
+
+        /// Maximum number of failed notifications queued for retry per hook.
+        #[pallet::constant_name(MaxQueuedFailuresPerHook)]
+        fn max_queued_failures_per_hook() -> u32 {
+            <T as SyncConfig>::MaxQueuedFailuresPerHook::get()
+        }
+
+        /// Number of failed retries after which a failed notification is dropped.
+        #[pallet::constant_name(MaxNotificationRetries)]
+        fn max_notification_retries() -> u32 {
+            <T as SyncConfig>::MaxNotificationRetries::get()
+        }
+
+        /// Blocks before the first retry of a failed notification; each failed retry doubles it.
+        #[pallet::constant_name(RetryBaseBlocks)]
+        fn retry_base_blocks() -> T::BlockNumber {
+            <T as SyncConfig>::RetryBaseBlocks::get()
+        }
+
+        /// The version of the synchronization protocol.
+        #[pallet::constant_name(SyncProtocolVersion)]
+        fn sync_protocol_version() -> u32 {
+            sync::SYNC_PROTOCOL_VERSION
+        }
+
         /// Maximum number of memories a pet can have.
-        #[pallet::constant]
-        type MaxPetMemories: Get<u32>;
-        
+        #[pallet::constant_name(MaxPetMemories)]
+        fn max_pet_memories() -> u32 {
+            <T as SocialConfig>::MaxPetMemories::get()
+        }
+
         /// Maximum significance of a memory.
-        #[pallet::constant]
-        type MaxMemorySignificance: Get<u8>;
-        
+        #[pallet::constant_name(MaxMemorySignificance)]
+        fn max_memory_significance() -> u8 {
+            <T as SocialConfig>::MaxMemorySignificance::get()
+        }
+
         /// Maximum number of skills a pet can have.
-        #[pallet::constant]
-        type MaxPetSkills: Get<u32>;
-        
+        #[pallet::constant_name(MaxPetSkills)]
+        fn max_pet_skills() -> u32 {
+            <T as SocialConfig>::MaxPetSkills::get()
+        }
+
         /// Maximum level of a skill.
-        #[pallet::constant]
-        type MaxSkillLevel: Get<u8>;
-        
+        #[pallet::constant_name(MaxSkillLevel)]
+        fn max_skill_level() -> u8 {
+            <T as SocialConfig>::MaxSkillLevel::get()
+        }
+
         /// Maximum number of achievements a pet can earn.
-        #[pallet::constant]
-        type MaxPetAchievements: Get<u32>;
-        
+        #[pallet::constant_name(MaxPetAchievements)]
+        fn max_pet_achievements() -> u32 {
+            <T as SocialConfig>::MaxPetAchievements::get()
+        }
+
+        /// The spacing of achievement progress milestones.
+        #[pallet::constant_name(AchievementMilestoneStep)]
+        fn achievement_milestone_step() -> Percent {
+            <T as SocialConfig>::AchievementMilestoneStep::get()
+        }
+
         /// Maximum number of social interactions a pet can have per block.
-        #[pallet::constant]
-        type MaxSocialInteractionsPerBlock: Get<u32>;
-        
+        #[pallet::constant_name(MaxSocialInteractionsPerBlock)]
+        fn max_social_interactions_per_block() -> u32 {
+            <T as SocialConfig>::MaxSocialInteractionsPerBlock::get()
+        }
+
         /// Maximum mood change from a social interaction.
-        #[pallet::constant]
-        type MaxMoodChangeFromSocialInteraction: Get<u8>;
-        
+        #[pallet::constant_name(MaxMoodChangeFromSocialInteraction)]
+        fn max_mood_change_from_social_interaction() -> u8 {
+            <T as SocialConfig>::MaxMoodChangeFromSocialInteraction::get()
+        }
+
         /// Maximum number of environments a pet can adapt to.
-        #[pallet::constant]
-        type MaxEnvironmentalAdaptations: Get<u32>;
-        
+        #[pallet::constant_name(MaxEnvironmentalAdaptations)]
+        fn max_environmental_adaptations() -> u32 {
+            <T as SocialConfig>::MaxEnvironmentalAdaptations::get()
+        }
+
         /// Maximum adaptation level to an environment.
-        #[pallet::constant]
-        type MaxAdaptationLevel: Get<u8>;
-        
+        #[pallet::constant_name(MaxAdaptationLevel)]
+        fn max_adaptation_level() -> u8 {
+            <T as SocialConfig>::MaxAdaptationLevel::get()
+        }
+
+        /// Adaptation level a pet must exceed in its species' preferred environment to get the
+        /// preferred environment bonus.
+        #[pallet::constant_name(PreferredEnvironmentThreshold)]
+        fn preferred_environment_threshold() -> u8 {
+            <T as SocialConfig>::PreferredEnvironmentThreshold::get()
+        }
+
+        /// Extra care gains for a pet adapted to its species' preferred environment.
+        #[pallet::constant_name(PreferredEnvironmentBonus)]
+        fn preferred_environment_bonus() -> Percent {
+            <T as SocialConfig>::PreferredEnvironmentBonus::get()
+        }
+
         /// Maximum number of seasonal events that can be active at once.
-        #[pallet::constant]
-        type MaxActiveSeasonalEvents: Get<u32>;
-        
+        #[pallet::constant_name(MaxActiveSeasonalEvents)]
+        fn max_active_seasonal_events() -> u32 {
+            <T as SocialConfig>::MaxActiveSeasonalEvents::get()
+        }
+
         /// Maximum effect magnitude of a seasonal event.
-        #[pallet::constant]
-        type MaxSeasonalEventEffectMagnitude: Get<u8>;
-        
-        /// Maximum number of lifecycle events a pet can experience.
-        #[pallet::constant]
-        type MaxLifecycleEvents: Get<u32>;
-        
-        /// Maximum size of an analytics report.
-        #[pallet::constant]
-        type MaxAnalyticsReportSize: Get<u32>;
-        
-        /// Maximum number of visual attributes a pet can have.
-        #[pallet::constant]
-        type MaxVisualAttributes: Get<u32>;
-        
+        #[pallet::constant_name(MaxSeasonalEventEffectMagnitude)]
+        fn max_seasonal_event_effect_magnitude() -> u8 {
+            <T as SocialConfig>::MaxSeasonalEventEffectMagnitude::get()
+        }
+
+        /// Maximum size of a pet's compressed interaction history.
+        #[pallet::constant_name(MaxInteractionHistorySize)]
+        fn max_interaction_history_size() -> u32 {
+            <T as SessionConfig>::MaxInteractionHistorySize::get()
+        }
+
+        /// Maximum number of behavior predictions to store for a pet.
+        #[pallet::constant_name(MaxBehaviorPredictions)]
+        fn max_behavior_predictions() -> u32 {
+            <T as SessionConfig>::MaxBehaviorPredictions::get()
+        }
+
+        /// Maximum number of state transition probabilities to store for a pet.
+        #[pallet::constant_name(MaxTransitionProbabilities)]
+        fn max_transition_probabilities() -> u32 {
+            <T as SessionConfig>::MaxTransitionProbabilities::get()
+        }
+
+        /// Threshold for adaptive behavior adjustments.
+        #[pallet::constant_name(AdaptiveBehaviorThreshold)]
+        fn adaptive_behavior_threshold() -> u32 {
+            <T as SessionConfig>::AdaptiveBehaviorThreshold::get()
+        }
+
+        /// Maximum number of temporary buffs a pet can have at once.
+        #[pallet::constant_name(MaxActiveBuffs)]
+        fn max_active_buffs() -> u32 {
+            <T as SessionConfig>::MaxActiveBuffs::get()
+        }
+
+        /// Number of blocks a temporary buff lasts.
+        #[pallet::constant_name(BuffDurationBlocks)]
+        fn buff_duration_blocks() -> T::BlockNumber {
+            <T as SessionConfig>::BuffDurationBlocks::get()
+        }
+
+        /// The session quality score a session needs to earn its pet buffs.
+        #[pallet::constant_name(SessionBuffQualityThreshold)]
+        fn session_buff_quality_threshold() -> u8 {
+            <T as SessionConfig>::SessionBuffQualityThreshold::get()
+        }
+
+        /// How much the buffs earned by a session boost XP and mood gains by.
+        #[pallet::constant_name(SessionBuffMagnitude)]
+        fn session_buff_magnitude() -> Percent {
+            <T as SessionConfig>::SessionBuffMagnitude::get()
+        }
+
         /// Maximum number of notifications a user can have.
-        #[pallet::constant]
-        type MaxNotifications: Get<u32>;
-        
+        #[pallet::constant_name(MaxNotifications)]
+        fn max_notifications() -> u32 {
+            <T as UxConfig>::MaxNotifications::get()
+        }
+
         /// Maximum number of achievements a user can have.
-        #[pallet::constant]
-        type MaxAchievements: Get<u32>;
-        
-        /// Handler for consuming basic care items (Food, Toys).
-        /// This trait is from `crate::traits` and MUST be implemented by `pallet-items`.
-        /// It dictates what `pallet-items` must provide for basic care item consumption logic
-        /// called by `pallet-critter-nfts`.
-        type ItemHandler: BasicCareItemConsumer<Self::AccountId, SharedItemId, ItemCategoryTag, DispatchResult> + 
-                        // Add associated types for category tags if they are part of the trait.
-                        // Example: `food_category_tag() -> ItemCategoryTag;`
-                        // `toy_category_tag() -> ItemCategoryTag;`
-                        // Or if they are constants defined within the trait itself.
-                        // For now, assuming direct functions are available on the trait.
-                        frame_support::traits::Get<ItemCategoryTag>; // Assuming ItemHandler can provide constants or associated types for tags.
+        #[pallet::constant_name(MaxAchievements)]
+        fn max_achievements() -> u32 {
+            <T as UxConfig>::MaxAchievements::get()
+        }
+
+        /// Notifications with a priority below this are folded into the digest for users in digest mode.
+        #[pallet::constant_name(DigestPriorityThreshold)]
+        fn digest_priority_threshold() -> u8 {
+            <T as UxConfig>::DigestPriorityThreshold::get()
+        }
+
+        /// Maximum number of notifications generated per block across all users.
+        #[pallet::constant_name(MaxNotificationsPerBlock)]
+        fn max_notifications_per_block() -> u32 {
+            <T as UxConfig>::MaxNotificationsPerBlock::get()
+        }
+
+        /// The UX flow whose completion grants new users a starter pet.
+        #[pallet::constant_name(OnboardingFlowId)]
+        fn onboarding_flow_id() -> u16 {
+            <T as UxConfig>::OnboardingFlowId::get()
+        }
     }
 
+    /// The in-code storage version. Bump this together with a new entry in `migrations`.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+
     // --- Pallet Definition ---
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)] // Generates getter functions for storage items
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // --- Pallet Storage Items ---
@@ -354,7 +1090,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(PetId, u8, u8, BlockNumberFor<T>), T::MaxSocialInteractionsPerBlock>,
+        BoundedVec<(PetId, InteractionKind, u8, BlockNumberFor<T>), T::MaxSocialInteractionsPerBlock>,
         ValueQuery,
     >;
     
@@ -376,7 +1112,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, u8), T::MaxEnvironmentalAdaptations>,
+        BoundedVec<(EnvironmentKind, u8), T::MaxEnvironmentalAdaptations>,
         ValueQuery,
     >;
     
@@ -387,7 +1123,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, u8), T::MaxPetSkills>,
+        BoundedVec<(SkillKind, u8), T::MaxPetSkills>,
         ValueQuery,
     >;
     
@@ -398,7 +1134,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, u8, u64, Vec<u8>), T::MaxPetMemories>,
+        BoundedVec<(MemoryKind, u8, u64, Vec<u8>), T::MaxPetMemories>,
         ValueQuery,
     >;
     
@@ -429,11 +1165,12 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, BlockNumberFor<T>), T::MaxLifecycleEvents>,
+        BoundedVec<(LifecycleEventKind, BlockNumberFor<T>), T::MaxLifecycleEvents>,
         ValueQuery,
     >;
     
     /// Storage for pet analytics reports.
+    #[cfg(feature = "analytics")]
     #[pallet::storage]
     #[pallet::getter(fn pet_analytics_reports)]
     pub type PetAnalyticsReports<T: Config> = StorageMap<
@@ -503,6 +1240,97 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn next_notification_id)]
     pub type NextNotificationId<T: Config> = StorageValue<_, u32, ValueQuery>;
+    
+    /// Storage for users' notification digest preferences.
+    /// Users without an entry receive every notification individually.
+    #[pallet::storage]
+    #[pallet::getter(fn notification_digest_settings)]
+    pub type NotificationDigestSettings<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        user_experience::DigestSettings<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+    
+    /// Storage for notification counts pending in a user's digest.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_digest)]
+    pub type PendingDigest<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u8, // notification_type
+        u32, // count
+        ValueQuery,
+    >;
+    
+    /// Storage for the number of notifications generated in the current block.
+    /// Reset in `on_initialize`; only written by blocks that generate notifications.
+    #[pallet::storage]
+    #[pallet::getter(fn notifications_this_block)]
+    pub type NotificationsThisBlock<T: Config> = StorageValue<_, u32, ValueQuery>;
+    
+    /// Storage for the number of notifications dropped in the current block for exceeding
+    /// `MaxNotificationsPerBlock`. Reported and cleared in `on_finalize`.
+    #[pallet::storage]
+    #[pallet::getter(fn throttled_notifications)]
+    pub type ThrottledNotifications<T: Config> = StorageValue<_, u32, ValueQuery>;
+    
+    /// Storage for the pets featured in the current rotation.
+    #[pallet::storage]
+    #[pallet::getter(fn current_featured)]
+    pub type CurrentFeatured<T: Config> = StorageValue<_, featured::FeaturedRotation<T>, OptionQuery>;
+    
+    /// Storage for the next pet ID the neglect scan in `on_initialize` checks.
+    #[pallet::storage]
+    #[pallet::getter(fn neglect_scan_cursor)]
+    pub type NeglectScanCursor<T: Config> = StorageValue<_, PetId, ValueQuery>;
+    
+    /// Storage for the block the last unsigned neglect batch was applied in. At most one batch
+    /// is applied per block.
+    #[pallet::storage]
+    #[pallet::getter(fn last_neglect_batch_block)]
+    pub type LastNeglectBatchBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+    
+    /// Storage for the (block, count) of pets minted in the latest block with a mint. The count
+    /// is mixed into mint DNA, and resets lazily on the first mint of a later block.
+    #[pallet::storage]
+    #[pallet::getter(fn mints_this_block)]
+    pub type MintsThisBlock<T: Config> = StorageValue<_, (BlockNumberFor<T>, u32), ValueQuery>;
+    
+    /// Storage for the DNA of the most recently minted pets, oldest first. New mint DNA is
+    /// checked against it for exact collisions.
+    #[pallet::storage]
+    #[pallet::getter(fn recent_dna)]
+    pub type RecentDna<T: Config> = StorageValue<_, dna::RecentDnaBuffer, ValueQuery>;
+    
+    /// Storage for the approximate encoded size of each pet's auxiliary map entries, in bytes.
+    /// Updated on the auxiliary maps' write paths; see the `footprint` module for its accuracy.
+    #[pallet::storage]
+    #[pallet::getter(fn pet_storage_size)]
+    pub type PetStorageSize<T: Config> = StorageMap<_, Twox64Concat, PetId, u32, OptionQuery>;
+    
+    /// Storage for each pet's tracked progress towards the achievements with a countable goal
+    /// that it has not earned yet. Bounded by the number of achievement definitions per pet.
+    #[pallet::storage]
+    #[pallet::getter(fn achievement_progress)]
+    pub type AchievementProgress<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        PetId,
+        Twox64Concat,
+        u32, // achievement_id
+        achievements::ProgressRecord,
+        OptionQuery,
+    >;
+    
+    /// Storage for whether a pet's achievement progress has been backfilled from its existing
+    /// memories, skills and social interactions.
+    #[pallet::storage]
+    #[pallet::getter(fn achievement_progress_backfilled)]
+    pub type AchievementProgressBackfilled<T: Config> = StorageMap<_, Twox64Concat, PetId, bool, ValueQuery>;
     // These store the actual state of the CritterChain.
     #[pallet::storage]
     #[pallet::getter(fn next_pet_id)]
@@ -527,9 +1355,9 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn locked_nfts)]
-    /// Stores PetIds of NFTs that are currently locked (e.g., listed on marketplace, in battle).
-    /// Using `ValueQuery` with `()` means we only care about the key's presence, not an associated value.
-    pub(super) type LockedNfts<T: Config> = StorageMap<_, Blake2_128Concat, PetId, (), ValueQuery>;
+    /// Stores the lock on each currently locked NFT (e.g., listed on marketplace, in battle):
+    /// the pallet holding it and the block it was taken at.
+    pub(super) type LockedNfts<T: Config> = StorageMap<_, Blake2_128Concat, PetId, LockInfo<BlockNumberFor<T>>, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn last_claim_time)]
@@ -542,6 +1370,275 @@ pub mod pallet {
     /// This is used for optimistic concurrency control and synchronization.
     pub(super) type PetStateVersions<T: Config> = StorageMap<_, Blake2_128Concat, PetId, u32, ValueQuery>;
     
+    #[pallet::storage]
+    #[pallet::getter(fn pet_bios)]
+    /// Stores the optional owner-authored bio for each pet.
+    /// Kept out of `PetNft` so the hot pet record stays small.
+    pub(super) type PetBios<T: Config> = StorageMap<_, Blake2_128Concat, PetId, BoundedVec<u8, T::MaxPetBioLen>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn deferred_lifecycle_rewards)]
+    /// Stores lifecycle stage PTCN rewards that could not be paid because the reward pot was short.
+    /// The pet's current owner can claim them once the pot is refilled.
+    pub(super) type DeferredLifecycleRewards<T: Config> = StorageMap<_, Blake2_128Concat, PetId, BalanceOf<T>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn social_interaction_types)]
+    /// Registry of social interaction types (type id -> effects, cooldown and level gate).
+    /// Only registered types can be used in `social_interact`.
+    pub(super) type SocialInteractionTypes<T: Config> = StorageMap<_, Twox64Concat, InteractionKind, social::SocialInteractionTypeInfo, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn onboarding_starter_config)]
+    /// The species and name of the starter pet granted on onboarding completion.
+    /// No starter pet is granted while unset.
+    pub(super) type OnboardingStarterConfig<T: Config> = StorageValue<_, user_experience::StarterPetConfig<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn starter_granted)]
+    /// Records the pet granted to each account that received a starter pet.
+    /// An account is granted at most one starter pet, ever.
+    pub(super) type StarterGranted<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PetId, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn species_population)]
+    /// Lifetime minted and released counts per species, keyed by species name.
+    /// Updated on every mint path and on release; read through the `species_population` runtime API.
+    pub(super) type SpeciesPopulation<T: Config> = StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxSpeciesNameLen>, SpeciesPopulationRecord, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn species_care_preferences)]
+    /// Care preferences per species, keyed by species name, set by the catalog origin.
+    /// Species without an entry gain 100% from every care action; see `species_care`.
+    pub(super) type SpeciesCarePreferences<T: Config> = StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxSpeciesNameLen>, species_care::CarePreferences, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn care_subscriptions)]
+    /// Stores each pet's auto-care subscription. Prepaid balances are held in the auto-care account.
+    /// Lapsed subscriptions stay here, inactive, until their owner cancels and reclaims the balance.
+    pub(super) type CareSubscriptions<T: Config> = StorageMap<_, Blake2_128Concat, PetId, auto_care::CareSubscriptionOf<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    /// Stores the deferred effects queued for each block, in queueing order.
+    /// Effects are applied in `on_idle` from their block on; what does not fit stays queued.
+    pub(super) type PendingEffects<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<(DeferredEffectId, PetId, DeferredEffect), T::MaxPendingEffectsPerBlock>,
+        ValueQuery,
+    >;
+    
+    #[pallet::storage]
+    /// Stores the block each queued effect is filed under. Removed once the effect is applied,
+    /// dropped or cancelled.
+    pub(super) type PendingEffectBlocks<T: Config> = StorageMap<_, Twox64Concat, DeferredEffectId, BlockNumberFor<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    /// The oldest block that may still hold pending effects; `None` when the queue is empty.
+    pub(super) type PendingEffectsCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    /// The ID assigned to the next queued effect.
+    pub(super) type NextDeferredEffectId<T: Config> = StorageValue<_, DeferredEffectId, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn rate_limits)]
+    /// Stores each account's (window_start_block, count) per rate limit action class.
+    /// Windows reset lazily on the next call after they end.
+    pub(super) type RateLimits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u8,
+        (BlockNumberFor<T>, u32),
+        OptionQuery,
+    >;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pending_trades)]
+    /// Stores pending pet trades. The offered pet is locked until the trade is accepted or cancelled.
+    pub(super) type PendingTrades<T: Config> = StorageMap<_, Twox64Concat, trade::TradeId, trade::PendingTradeOf<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    /// The ID assigned to the next proposed trade.
+    pub(super) type NextTradeId<T: Config> = StorageValue<_, trade::TradeId, ValueQuery>;
+    
+    #[pallet::storage]
+    /// The governable game parameters. Unset until first updated; the Config constants apply until then.
+    /// Read through `Pallet::params()`.
+    pub(super) type GameParameters<T: Config> = StorageValue<_, game_params::VersionedGameParameters<BlockNumberFor<T>>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pending_claims)]
+    /// Stores bred pets parked because their recipient had no room, until claimed or burned.
+    pub(super) type PendingClaims<T: Config> = StorageMap<_, Twox64Concat, PetId, pending_claims::PendingClaim<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pending_claims_of)]
+    /// Index from a recipient to their parked pets, bounded by `MaxPendingClaims`.
+    pub(super) type PendingClaimsOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<PetId, T::MaxPendingClaims>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn system_traits)]
+    /// The personality traits of each pet that the system granted, which the owner cannot remove.
+    /// Traits not listed are owner-sourced; see `trait_provenance`.
+    pub(super) type SystemTraits<T: Config> = StorageMap<_, Twox64Concat, PetId, BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn temporary_traits)]
+    /// The temporary traits items granted each pet, with the block each expires at. Expired
+    /// entries are pruned when read; see `temporary_traits`.
+    pub(super) type TemporaryTraits<T: Config> = StorageMap<_, Twox64Concat, PetId, BoundedVec<(TraitTypeString, BlockNumberFor<T>), T::MaxTemporaryTraits>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn sacrifice_bonus)]
+    /// The bonus each pet has received from sacrifices over its lifetime, per bonus stat. Capped
+    /// at `MaxSacrificeBonus`; see `sacrifice`.
+    pub(super) type SacrificeBonus<T: Config> = StorageMap<_, Twox64Concat, PetId, u8, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn account_migration_requests)]
+    /// The account each account asked to migrate to, until the new account accepts.
+    pub(super) type AccountMigrationRequests<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn account_migrations)]
+    /// Accepted account migrations in progress, keyed by the old account, with the next
+    /// category to migrate. Continued in `on_idle`; see `account_migration`.
+    pub(super) type AccountMigrations<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, account_migration::AccountMigration<T::AccountId>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn free_care_uses)]
+    /// The free care uses each pet has had and the care epoch they were counted in. A count from
+    /// an earlier epoch has expired; see `free_care`.
+    pub(super) type FreeCareUses<T: Config> = StorageMap<_, Twox64Concat, PetId, (u32, u32), ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn donation_preferences)]
+    /// Each account's round-up donation preference, if it donates. See `donations`.
+    pub(super) type DonationPreferences<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, donations::DonationPreference, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn total_donated)]
+    /// The PTCN each account has donated through round-ups, over its lifetime.
+    pub(super) type TotalDonated<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn community_pots)]
+    /// The account of each community pot accounts can donate to, registered by `UpdateOrigin`.
+    pub(super) type CommunityPots<T: Config> = StorageMap<_, Twox64Concat, donations::CommunityPotId, T::AccountId, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_cemetery)]
+    /// The final state of each burned pet, for its former owner to look up. See `burn`.
+    pub(super) type PetCemetery<T: Config> = StorageMap<_, Blake2_128Concat, PetId, burn::BurnRecord<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_delegations)]
+    /// The account each delegated pet is lent to for care, until its expiry block. See `delegation`.
+    pub(super) type PetDelegations<T: Config> = StorageMap<_, Blake2_128Concat, PetId, delegation::PetDelegation<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_approvals)]
+    /// The account each pet's owner approved to transfer it, until it changes hands or is
+    /// locked. See `approvals`.
+    pub(super) type PetApprovals<T: Config> = StorageMap<_, Blake2_128Concat, PetId, T::AccountId, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn operator_approvals)]
+    /// The operators each owner approved to transfer all of their pets. See `approvals`.
+    pub(super) type OperatorApprovals<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_genealogy)]
+    /// The breeding record of each bred pet. Kept after a pet is released, like `PetParents`,
+    /// and backfilled from `PetParents` by the v7 migration.
+    pub(super) type PetGenealogy<T: Config> = StorageMap<_, Blake2_128Concat, PetId, lineage::GenealogyRecord<T>, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn inheritance_table)]
+    /// Per-trait chance that a parent passes the trait on when breeding.
+    /// Traits without an entry use `BaseInheritanceChance`.
+    pub(super) type InheritanceTable<T: Config> = StorageMap<_, Blake2_128Concat, TraitTypeString, sp_runtime::Perbill, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_minter)]
+    /// The account each pet was minted to, or bred for. Kept after a pet is released or burned,
+    /// like `PetParents`. Pets minted before it was recorded have no entry.
+    pub(super) type PetMinters<T: Config> = StorageMap<_, Blake2_128Concat, PetId, T::AccountId, OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_parents)]
+    /// Stores the (parent1, parent2) of each bred pet. Kept after a pet is released so family trees stay whole.
+    pub(super) type PetParents<T: Config> = StorageMap<_, Twox64Concat, PetId, (PetId, PetId), OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn children_of)]
+    /// Index from a parent to its bred children, in breeding order. Maintained at breeding time
+    /// and rebuilt from `PetParents` by the v2 migration.
+    pub(super) type ChildrenIndex<T: Config> = StorageMap<_, Twox64Concat, PetId, BoundedVec<PetId, T::MaxChildrenPerPet>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn rested_xp_state)]
+    /// Stores each pet's rested XP as (accrued, block last settled). Absent until the pet's first
+    /// XP grant. Read through `RestedXpSystem::current`, which adds the accrual since the last settle.
+    pub(super) type RestedXp<T: Config> = StorageMap<_, Twox64Concat, PetId, (u32, BlockNumberFor<T>), OptionQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn temporary_buffs)]
+    /// Stores each pet's temporary buffs, at most `MaxActiveBuffs`. Expired buffs are pruned
+    /// lazily, so read through `BuffSystem::active_buffs` to skip them.
+    pub(super) type TemporaryBuffs<T: Config> = StorageMap<_, Twox64Concat, PetId, buffs::TemporaryBuffsOf<T>, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn care_action_bucket)]
+    /// Stores the care actions each pet received per care history epoch, counted per kind.
+    /// Buckets older than `CareHistoryEpochs` are pruned lazily when the pet's next epoch starts.
+    pub(super) type CareActionBuckets<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        PetId,
+        Twox64Concat,
+        u32,
+        care_history::CareActionCounts,
+        ValueQuery,
+    >;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn pet_display_version)]
+    /// Counts the changes to each pet's display data (name, visual theme, bio), so clients know
+    /// when to re-fetch it. Separate from the state version, which changes far more often.
+    pub(super) type PetDisplayVersions<T: Config> = StorageMap<_, Twox64Concat, PetId, u32, ValueQuery>;
+    
+    #[pallet::storage]
+    /// Counts the calls into each deprecated (interface, method), while `TrackDeprecatedCalls` is on.
+    /// The codes are listed in `deprecation`; read through the `deprecated_call_counts` runtime API.
+    pub(super) type DeprecatedCallCounts<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u8,
+        Twox64Concat,
+        u8,
+        u64,
+        ValueQuery,
+    >;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn social_interaction_cooldowns)]
+    /// Stores the block at which a pet pair last performed each interaction type.
+    /// The pair key is ordered (lower pet id first); bounded by the number of registered types per pair.
+    pub(super) type SocialInteractionCooldowns<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (PetId, PetId),
+        Twox64Concat,
+        InteractionKind,
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+    
     #[pallet::storage]
     #[pallet::getter(fn sync_hook_registry)]
     /// Stores detailed information about registered synchronization hooks.
@@ -560,6 +1657,24 @@ pub mod pallet {
         ValueQuery
     >;
     
+    #[pallet::storage]
+    #[pallet::getter(fn sync_hook_failure_score)]
+    /// The weighted count of each hook's consecutive failures since its last success.
+    /// A hook is disabled once this reaches `HookAutoDisableThreshold`.
+    pub(super) type SyncHookFailureScore<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+    
+    #[pallet::storage]
+    #[pallet::getter(fn failed_notifications)]
+    /// The notifications each hook failed to receive, oldest first, waiting to be retried in
+    /// `on_idle`. Bounded by `MaxQueuedFailuresPerHook`; see `sync`.
+    pub(super) type FailedNotifications<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u32, // hook_id
+        BoundedVec<sync::FailedNotification<T::BlockNumber>, T::MaxQueuedFailuresPerHook>,
+        ValueQuery
+    >;
+    
     #[pallet::storage]
     #[pallet::getter(fn pet_sync_status)]
     /// Stores the synchronization status for each pet.
@@ -616,7 +1731,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, u8, u64, Vec<u8>), T::MaxPetMemories>, // (memory_type, significance, timestamp, associated_data)
+        BoundedVec<(MemoryKind, u8, u64, Vec<u8>), T::MaxPetMemories>, // (memory_type, significance, timestamp, associated_data)
         ValueQuery
     >;
     
@@ -628,7 +1743,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, u8), T::MaxPetSkills>, // (skill_type, skill_level)
+        BoundedVec<(SkillKind, u8), T::MaxPetSkills>, // (skill_type, skill_level)
         ValueQuery
     >;
     
@@ -652,7 +1767,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(PetId, u8, u8, T::BlockNumber), T::MaxSocialInteractionsPerBlock>, // (other_pet_id, interaction_type, outcome, timestamp)
+        BoundedVec<(PetId, InteractionKind, u8, T::BlockNumber), T::MaxSocialInteractionsPerBlock>, // (other_pet_id, interaction_type, outcome, timestamp)
         ValueQuery
     >;
     
@@ -664,7 +1779,7 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, u8), T::MaxEnvironmentalAdaptations>, // (environment_type, adaptation_level)
+        BoundedVec<(EnvironmentKind, u8), T::MaxEnvironmentalAdaptations>, // (environment_type, adaptation_level)
         ValueQuery
     >;
     
@@ -686,10 +1801,11 @@ pub mod pallet {
         _,
         Blake2_128Concat,
         PetId,
-        BoundedVec<(u8, T::BlockNumber), T::MaxLifecycleEvents>, // (event_type, timestamp)
+        BoundedVec<(LifecycleEventKind, T::BlockNumber), T::MaxLifecycleEvents>, // (event_type, timestamp)
         ValueQuery
     >;
     
+    #[cfg(feature = "analytics")]
     #[pallet::storage]
     #[pallet::getter(fn pet_analytics_reports)]
     /// Stores analytics reports for each pet.
@@ -702,6 +1818,7 @@ pub mod pallet {
         ValueQuery
     >;
 
+    #[cfg(feature = "interactive-sessions")]
     #[pallet::storage]
     #[pallet::getter(fn interactive_sessions)]
     /// Stores interactive sessions for each pet.
@@ -714,6 +1831,7 @@ pub mod pallet {
         OptionQuery
     >;
     
+    #[cfg(feature = "interactive-sessions")]
     #[pallet::storage]
     #[pallet::getter(fn user_sessions)]
     /// Stores session IDs for each user.
@@ -726,6 +1844,7 @@ pub mod pallet {
         ValueQuery
     >;
     
+    #[cfg(feature = "interactive-sessions")]
     #[pallet::storage]
     #[pallet::getter(fn nonce)]
     /// A nonce for generating unique session IDs.
@@ -734,6 +1853,35 @@ pub mod pallet {
         u32,
         ValueQuery
     >;
+    
+    #[cfg(feature = "interactive-sessions")]
+    #[pallet::storage]
+    #[pallet::getter(fn gesture_catalog)]
+    /// Catalog of the gestures `process_gesture` accepts (gesture id -> effect, animation,
+    /// level and mood gates, cooldown).
+    pub(super) type GestureCatalog<T: Config> = StorageMap<_, Twox64Concat, u8, input_catalog::InputCatalogEntry, OptionQuery>;
+    
+    #[cfg(feature = "interactive-sessions")]
+    #[pallet::storage]
+    #[pallet::getter(fn touch_area_catalog)]
+    /// Catalog of the touch areas `process_touch` accepts (touch area id -> effect, animation,
+    /// level and mood gates, cooldown).
+    pub(super) type TouchAreaCatalog<T: Config> = StorageMap<_, Twox64Concat, u8, input_catalog::InputCatalogEntry, OptionQuery>;
+    
+    #[cfg(feature = "interactive-sessions")]
+    #[pallet::storage]
+    #[pallet::getter(fn pet_input_cooldowns)]
+    /// Stores the block at which a pet last received each catalogued input with a cooldown.
+    /// Bounded by the number of catalogued inputs per pet.
+    pub(super) type PetInputCooldowns<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PetId,
+        Twox64Concat,
+        (input_catalog::InputKind, u8),
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
 
 
     // --- Pallet Events ---
@@ -741,8 +1889,8 @@ pub mod pallet {
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// A new Pet NFT has been minted with detailed information.
-        /// [owner, pet_id, species, dna_hash, base_stats, timestamp]
+        /// A new Pet NFT has been minted with detailed information. `parents` is set for bred pets.
+        /// [owner, pet_id, species, dna_hash, base_stats, timestamp, unix_time_ms, parents]
         PetNftMinted { 
             owner: T::AccountId, 
             pet_id: PetId,
@@ -753,7 +1901,9 @@ pub mod pallet {
             base_intelligence: u8,
             base_vitality: u8,
             elemental_affinity: ElementType,
-            timestamp: BlockNumberFor<T>
+            timestamp: BlockNumberFor<T>,
+            unix_time_ms: u64,
+            parents: Option<(PetId, PetId)>,
         },
         
         /// A Pet NFT has been transferred with detailed information.
@@ -783,28 +1933,34 @@ pub mod pallet {
             claim_time: T::BlockNumber 
         },
         
-        /// A pet was fed with detailed information.
-        /// [owner, pet_id, food_item_id, mood_boost, xp_gain, timestamp]
+        /// A pet was fed with detailed information. `rested_bonus_xp` is the part of the XP paid from rested XP.
+        /// Legacy: emitted next to `CareActionPerformed` only while `EmitLegacyCareEvents` is on.
+        /// [owner, pet_id, food_item_id, mood_boost, xp_gain, rested_bonus_xp, timestamp, unix_time_ms]
         PetFed { 
             owner: T::AccountId, 
             pet_id: PetId, 
             food_item_id: SharedItemId,
             mood_boost: u8,
             xp_gain: u32,
+            rested_bonus_xp: u32,
             new_mood: u8,
-            timestamp: BlockNumberFor<T>
+            timestamp: BlockNumberFor<T>,
+            unix_time_ms: u64,
         },
         
         /// A pet was played with detailed information.
-        /// [owner, pet_id, toy_item_id, mood_boost, xp_gain, timestamp]
+        /// Legacy: emitted next to `CareActionPerformed` only while `EmitLegacyCareEvents` is on.
+        /// [owner, pet_id, toy_item_id, mood_boost, xp_gain, rested_bonus_xp, timestamp, unix_time_ms]
         PetPlayedWith { 
             owner: T::AccountId, 
             pet_id: PetId, 
             toy_item_id: SharedItemId,
             mood_boost: u8,
             xp_gain: u32,
+            rested_bonus_xp: u32,
             new_mood: u8,
-            timestamp: BlockNumberFor<T>
+            timestamp: BlockNumberFor<T>,
+            unix_time_ms: u64,
         },
         
         /// A pet leveled up with detailed information.
@@ -898,7 +2054,18 @@ pub mod pallet {
             hook_id: u32,
             pet_id: PetId,
             change_type: u8,
-            error: Vec<u8>,
+            reason: sync::HookFailureReason,
+            detail: sync::HookFailureDetail,
+        },
+        
+        /// A failed notification was given up on, after `MaxNotificationRetries` failed retries
+        /// or to make room in the hook's retry queue. The hook's off-chain system should resync
+        /// the pet. [hook_id, pet_id, change_mask, version]
+        NotificationDropped {
+            hook_id: u32,
+            pet_id: PetId,
+            change_mask: u16,
+            version: u32,
         },
         
         /// A pet's behavior has been predicted.
@@ -953,7 +2120,7 @@ pub mod pallet {
         PetSocialInteraction {
             pet_id_1: PetId,
             pet_id_2: PetId,
-            interaction_type: u8,
+            interaction_type: InteractionKind,
             outcome: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -961,7 +2128,7 @@ pub mod pallet {
         /// A pet has adapted to a new environment.
         EnvironmentalAdaptation {
             pet_id: PetId,
-            environment_type: u8,
+            environment_type: EnvironmentKind,
             adaptation_level: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -969,7 +2136,7 @@ pub mod pallet {
         /// A pet has been trained in a skill.
         PetTrainingCompleted {
             pet_id: PetId,
-            skill_type: u8,
+            skill_type: SkillKind,
             skill_level: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -977,7 +2144,7 @@ pub mod pallet {
         /// A pet has formed a new memory.
         MemoryFormed {
             pet_id: PetId,
-            memory_type: u8,
+            memory_type: MemoryKind,
             significance: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -996,6 +2163,15 @@ pub mod pallet {
             pet_id: PetId,
             achievement_id: u32,
             timestamp: BlockNumberFor<T>,
+            unix_time_ms: u64,
+        },
+        
+        /// A pet's progress towards an achievement crossed a milestone.
+        AchievementProgressed {
+            pet_id: PetId,
+            achievement_id: u32,
+            progress: u32,
+            target: u32,
         },
         
         /// A seasonal event has affected a pet.
@@ -1010,11 +2186,12 @@ pub mod pallet {
         /// A pet has experienced a lifecycle event.
         LifecycleEvent {
             pet_id: PetId,
-            event_type: u8,
+            event_type: LifecycleEventKind,
             timestamp: BlockNumberFor<T>,
         },
         
         /// An analytics report has been generated for a pet.
+        #[cfg(feature = "analytics")]
         AnalyticsReportGenerated {
             pet_id: PetId,
             report_size: u32,
@@ -1035,7 +2212,7 @@ pub mod pallet {
         SocialInteraction {
             pet_id_1: PetId,
             pet_id_2: PetId,
-            interaction_type: u8,
+            interaction_type: InteractionKind,
             outcome: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -1043,7 +2220,7 @@ pub mod pallet {
         /// A pet adapted to an environment.
         EnvironmentalAdaptation {
             pet_id: PetId,
-            environment_type: u8,
+            environment_type: EnvironmentKind,
             adaptation_level: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -1051,7 +2228,7 @@ pub mod pallet {
         /// A pet learned or improved a skill.
         SkillLearned {
             pet_id: PetId,
-            skill_type: u8,
+            skill_type: SkillKind,
             skill_level: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -1059,7 +2236,7 @@ pub mod pallet {
         /// A pet formed a memory.
         MemoryFormed {
             pet_id: PetId,
-            memory_type: u8,
+            memory_type: MemoryKind,
             significance: u8,
             timestamp: BlockNumberFor<T>,
         },
@@ -1080,11 +2257,20 @@ pub mod pallet {
             timestamp: BlockNumberFor<T>,
         },
         
+        /// A pet earned a temporary buff from a high-quality interactive session.
+        TemporaryBuffGranted {
+            pet_id: PetId,
+            kind: BuffKind,
+            magnitude: Percent,
+            expires_at: BlockNumberFor<T>,
+        },
+        
         /// A pet earned an achievement.
         AchievementEarned {
             pet_id: PetId,
             achievement_id: u32,
             timestamp: BlockNumberFor<T>,
+            unix_time_ms: u64,
         },
         
         /// A seasonal event started.
@@ -1112,7 +2298,7 @@ pub mod pallet {
         /// A pet experienced a lifecycle event.
         LifecycleEvent {
             pet_id: PetId,
-            event_type: u8,
+            event_type: LifecycleEventKind,
             timestamp: BlockNumberFor<T>,
         },
         
@@ -1131,6 +2317,8 @@ pub mod pallet {
         },
         
         /// A gesture interaction occurred with a pet.
+        /// Legacy: emitted next to `CareActionPerformed` only while `EmitLegacyCareEvents` is on.
+        #[cfg(feature = "interactive-sessions")]
         GestureInteraction {
             pet_id: PetId,
             gesture_id: u8,
@@ -1140,6 +2328,8 @@ pub mod pallet {
         },
         
         /// A touch interaction occurred with a pet.
+        /// Legacy: emitted next to `CareActionPerformed` only while `EmitLegacyCareEvents` is on.
+        #[cfg(feature = "interactive-sessions")]
         TouchInteraction {
             pet_id: PetId,
             touch_area: u8,
@@ -1148,6 +2338,20 @@ pub mod pallet {
             timestamp: BlockNumberFor<T>,
         },
         
+        /// A gesture or touch area was added to or updated in the input catalog.
+        #[cfg(feature = "interactive-sessions")]
+        InputCatalogEntrySet {
+            kind: input_catalog::InputKind,
+            id: u8,
+        },
+        
+        /// A gesture or touch area was removed from the input catalog.
+        #[cfg(feature = "interactive-sessions")]
+        InputCatalogEntryRemoved {
+            kind: input_catalog::InputKind,
+            id: u8,
+        },
+        
         /// A UX flow was started for a user.
         UxFlowStarted {
             account_id: T::AccountId,
@@ -1184,25 +2388,55 @@ pub mod pallet {
             notification_id: u32,
             notification_type: u8,
             timestamp: BlockNumberFor<T>,
+            unix_time_ms: u64,
         },
         
-        /// A notification was marked as read.
-        NotificationRead {
+        /// A user's notification digest preference was changed.
+        NotificationDigestUpdated {
             account_id: T::AccountId,
-            notification_id: u32,
-            timestamp: BlockNumberFor<T>,
+            enabled: bool,
+            interval_blocks: BlockNumberFor<T>,
         },
         
-        // Interactive session events
-        /// An interactive session was started.
-        InteractiveSessionStarted {
+        /// A user's pending digest was materialized into a single notification.
+        NotificationDigestFlushed {
             account_id: T::AccountId,
-            pet_id: PetId,
-            session_id: u32,
+            notification_id: u32,
+            total_count: u32,
             timestamp: BlockNumberFor<T>,
         },
         
-        /// An interactive session was ended.
+        /// A new set of featured pets was drawn for rotation `epoch`.
+        FeaturedPetsRotated {
+            epoch: u32,
+            pet_ids: Vec<PetId>,
+        },
+        
+        /// Non-critical notifications were dropped in this block for exceeding
+        /// `MaxNotificationsPerBlock`. Emitted once, at the end of the block.
+        NotificationsThrottled {
+            dropped_count: u32,
+        },
+        
+        /// A notification was marked as read.
+        NotificationRead {
+            account_id: T::AccountId,
+            notification_id: u32,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        // Interactive session events
+        /// An interactive session was started.
+        #[cfg(feature = "interactive-sessions")]
+        InteractiveSessionStarted {
+            account_id: T::AccountId,
+            pet_id: PetId,
+            session_id: u32,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        /// An interactive session was ended.
+        #[cfg(feature = "interactive-sessions")]
         InteractiveSessionEnded {
             account_id: T::AccountId,
             pet_id: PetId,
@@ -1212,6 +2446,7 @@ pub mod pallet {
         },
         
         /// An interaction was recorded in a session.
+        #[cfg(feature = "interactive-sessions")]
         SessionInteractionRecorded {
             session_id: u32,
             interaction_type: u8,
@@ -1220,6 +2455,7 @@ pub mod pallet {
         },
         
         /// A mood change was recorded in a session.
+        #[cfg(feature = "interactive-sessions")]
         SessionMoodChangeRecorded {
             session_id: u32,
             change: i8,
@@ -1227,6 +2463,7 @@ pub mod pallet {
         },
         
         /// A reward was earned in a session.
+        #[cfg(feature = "interactive-sessions")]
         SessionRewardEarned {
             session_id: u32,
             reward_type: u8,
@@ -1235,12 +2472,388 @@ pub mod pallet {
         },
         
         /// A multi-touch interaction was processed.
+        #[cfg(feature = "interactive-sessions")]
         MultiTouchInteractionProcessed {
             pet_id: PetId,
             interaction_id: u8,
             touch_count: u8,
             timestamp: BlockNumberFor<T>,
         },
+        
+        /// A pet's bio was set or replaced by its owner.
+        /// [owner, pet_id, bio_len, version, timestamp]
+        PetBioUpdated {
+            owner: T::AccountId,
+            pet_id: PetId,
+            bio_len: u32,
+            version: u32,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        /// A pet reached a lifecycle stage and received its one-time reward.
+        /// [pet_id, event_type, bonuses, currency_reward, currency_deferred, timestamp]
+        LifecycleStageRewardGranted {
+            pet_id: PetId,
+            event_type: LifecycleEventKind,
+            bonus_strength: u8,
+            bonus_agility: u8,
+            bonus_intelligence: u8,
+            bonus_vitality: u8,
+            currency_reward: BalanceOf<T>,
+            currency_deferred: bool,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        /// A deferred lifecycle stage reward was paid out.
+        /// [owner, pet_id, amount, timestamp]
+        DeferredLifecycleRewardClaimed {
+            owner: T::AccountId,
+            pet_id: PetId,
+            amount: BalanceOf<T>,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        /// A pet's bio was cleared, either by its owner or by moderation.
+        /// [pet_id, by_moderation, version, timestamp]
+        PetBioCleared {
+            pet_id: PetId,
+            by_moderation: bool,
+            version: u32,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        /// A social interaction type was registered or updated in the registry.
+        SocialInteractionTypeSet {
+            type_id: InteractionKind,
+        },
+        
+        /// A social interaction type was removed from the registry.
+        SocialInteractionTypeRemoved {
+            type_id: InteractionKind,
+        },
+        
+        /// A trait's inheritance chance was set, or reset to `BaseInheritanceChance` if `None`.
+        /// [trait_name, chance]
+        TraitInheritanceChanceSet {
+            trait_name: TraitTypeString,
+            chance: Option<sp_runtime::Perbill>,
+        },
+        
+        /// A species' care preferences were set, or cleared if `None`. [species, preferences]
+        SpeciesCarePreferencesSet {
+            species: BoundedVec<u8, T::MaxSpeciesNameLen>,
+            preferences: Option<species_care::CarePreferences>,
+        },
+        
+        /// A pet was granted a temporary trait, or had its expiry refreshed.
+        /// [pet_id, trait_name, expires_at]
+        TemporaryTraitGranted {
+            pet_id: PetId,
+            trait_name: TraitTypeString,
+            expires_at: BlockNumberFor<T>,
+        },
+        
+        /// A pet's temporary trait expired and was pruned. [pet_id, trait_name]
+        TemporaryTraitExpired {
+            pet_id: PetId,
+            trait_name: TraitTypeString,
+        },
+        
+        /// `count` pets were sacrificed to add `bonus_granted` to each of a pet's bonus stats.
+        /// [target, count, bonus_granted]
+        PetsSacrificed {
+            target: PetId,
+            count: u32,
+            bonus_granted: u8,
+        },
+        
+        /// A pet was released and its state removed. [owner, pet_id]
+        PetReleased {
+            owner: T::AccountId,
+            pet_id: PetId,
+        },
+        
+        /// A pet was bred, inheriting `inherited_traits` from its parents. It has no fresh traits
+        /// yet; any it gains later are not listed here. [owner, pet_id, parents, inherited_traits]
+        PetNftBred {
+            owner: T::AccountId,
+            pet_id: PetId,
+            parents: (PetId, PetId),
+            inherited_traits: inheritance::InheritedTraits<T>,
+        },
+        
+        /// The onboarding starter pet configuration was updated.
+        OnboardingStarterConfigUpdated {
+            species: BoundedVec<u8, T::MaxSpeciesNameLen>,
+            name: BoundedVec<u8, T::MaxPetNameLen>,
+        },
+        
+        /// A starter pet was granted to a new user on onboarding completion.
+        StarterPetGranted {
+            owner: T::AccountId,
+            pet_id: PetId,
+            timestamp: BlockNumberFor<T>,
+        },
+        
+        /// A pet was subscribed to auto-care.
+        /// [owner, pet_id, kinds, prepaid_balance, next_due_block]
+        AutoCareSubscribed {
+            owner: T::AccountId,
+            pet_id: PetId,
+            kinds: u8,
+            prepaid_balance: BalanceOf<T>,
+            next_due_block: BlockNumberFor<T>,
+        },
+        
+        /// Due auto-care was performed and its fee charged.
+        /// [pet_id, executor, kinds, fee, bounty, next_due_block]
+        AutoCareExecuted {
+            pet_id: PetId,
+            executor: T::AccountId,
+            kinds: u8,
+            fee: BalanceOf<T>,
+            bounty: BalanceOf<T>,
+            next_due_block: BlockNumberFor<T>,
+        },
+        
+        /// An auto-care subscription became inactive, either because its balance cannot cover
+        /// another interval or because the pet changed hands.
+        AutoCareLapsed {
+            pet_id: PetId,
+            remaining_balance: BalanceOf<T>,
+        },
+        
+        /// An auto-care subscription was cancelled and its remaining balance refunded.
+        AutoCareCancelled {
+            owner: T::AccountId,
+            pet_id: PetId,
+            refunded: BalanceOf<T>,
+        },
+        
+        /// An effect was queued by another pallet.
+        /// [effect_id, pet_id, effect, due_block]
+        PendingEffectQueued {
+            effect_id: DeferredEffectId,
+            pet_id: PetId,
+            effect: DeferredEffect,
+            due_block: BlockNumberFor<T>,
+        },
+        
+        /// A queued effect was applied.
+        PendingEffectApplied {
+            effect_id: DeferredEffectId,
+            pet_id: PetId,
+        },
+        
+        /// A queued effect failed and was dropped; other effects are unaffected.
+        PendingEffectDropped {
+            effect_id: DeferredEffectId,
+            pet_id: PetId,
+            error: DispatchError,
+        },
+        
+        /// A queued effect was cancelled before it was applied.
+        PendingEffectCancelled {
+            effect_id: DeferredEffectId,
+            pet_id: PetId,
+        },
+        
+        /// A pet trade was proposed and the offered pet locked.
+        TradeProposed {
+            trade_id: trade::TradeId,
+            proposer: T::AccountId,
+            counterparty: T::AccountId,
+            offered_pet: PetId,
+            requested_pet: PetId,
+            expires_at: BlockNumberFor<T>,
+        },
+        
+        /// A pet trade was accepted and both pets swapped.
+        TradeAccepted {
+            trade_id: trade::TradeId,
+            proposer: T::AccountId,
+            counterparty: T::AccountId,
+            offered_pet: PetId,
+            requested_pet: PetId,
+        },
+        
+        /// A pet trade was cancelled and the offered pet unlocked.
+        TradeCancelled {
+            trade_id: trade::TradeId,
+            proposer: T::AccountId,
+            offered_pet: PetId,
+            reason: trade::TradeCancelReason,
+        },
+        
+        /// The game parameters were updated. Lists each changed field with its old and new value.
+        GameParametersUpdated {
+            changes: Vec<game_params::GameParameterChange>,
+        },
+        
+        /// Root released a pet's lock without its holder. The holding pallet may still consider
+        /// the pet locked (e.g., a listing or battle still referencing it).
+        /// [pet_id, locker, locked_at]
+        NftForceUnlocked {
+            pet_id: PetId,
+            locker: LockerId,
+            locked_at: BlockNumberFor<T>,
+        },
+        
+        /// A pet's display data (name, visual theme or bio) changed and its display version was
+        /// bumped. Carries the blake2-256 hash of the current name rather than the name itself.
+        /// [pet_id, name_hash]
+        PetDisplayChanged {
+            pet_id: PetId,
+            name_hash: [u8; 32],
+        },
+        
+        /// A bred pet was parked because its recipient had no room. It can be claimed until `expires_at`.
+        /// [recipient, pet_id, expires_at]
+        PetParked {
+            recipient: T::AccountId,
+            pet_id: PetId,
+            expires_at: BlockNumberFor<T>,
+        },
+        
+        /// A parked pet was claimed and minted to its recipient. [owner, pet_id]
+        PendingPetClaimed {
+            owner: T::AccountId,
+            pet_id: PetId,
+        },
+        
+        /// A parked pet was not claimed in time and was burned. [recipient, pet_id]
+        PendingPetBurned {
+            recipient: T::AccountId,
+            pet_id: PetId,
+        },
+        
+        /// A pet received a care action. Emitted exactly once for every care action, whatever its kind.
+        /// `xp_delta` includes any rested bonus. [pet_id, actor, kind, item, mood_delta, xp_delta, block]
+        CareActionPerformed {
+            pet_id: PetId,
+            actor: T::AccountId,
+            kind: CareActionKind,
+            item: Option<SharedItemId>,
+            mood_delta: i8,
+            xp_delta: u32,
+            block: BlockNumberFor<T>,
+        },
+        
+        /// A hook owner's off-chain system caught up with a pet, clearing the `mask` sync flags
+        /// as of state `version`. [pet_id, account_id, mask, version]
+        SyncAcknowledged {
+            pet_id: PetId,
+            account_id: T::AccountId,
+            mask: u16,
+            version: u32,
+        },
+        
+        /// An account asked to migrate everything to a new account. [old_account, new_account]
+        AccountMigrationRequested {
+            old_account: T::AccountId,
+            new_account: T::AccountId,
+        },
+        
+        /// One category of an account migration completed. [old_account, new_account, category]
+        AccountMigrationCategoryMigrated {
+            old_account: T::AccountId,
+            new_account: T::AccountId,
+            category: account_migration::AccountMigrationCategory,
+        },
+        
+        /// Every category of an account migration completed. [old_account, new_account]
+        AccountMigrationCompleted {
+            old_account: T::AccountId,
+            new_account: T::AccountId,
+        },
+        
+        /// An account set or cleared its round-up donation preference. [account, preference]
+        DonationPreferenceSet {
+            account: T::AccountId,
+            preference: Option<donations::DonationPreference>,
+        },
+        
+        /// A round-up donation was made. [donor, beneficiary, amount]
+        DonationMade {
+            donor: T::AccountId,
+            beneficiary: donations::DonationTarget,
+            amount: BalanceOf<T>,
+        },
+        
+        /// A round-up donation could not be made and was skipped; the action it rounded up went
+        /// ahead. [donor, beneficiary, amount]
+        DonationSkipped {
+            donor: T::AccountId,
+            beneficiary: donations::DonationTarget,
+            amount: BalanceOf<T>,
+        },
+        
+        /// A community pot was registered, moved to another account or removed. [pot_id, account]
+        CommunityPotSet {
+            pot_id: donations::CommunityPotId,
+            account: Option<T::AccountId>,
+        },
+        
+        /// A pet was burned and its owner compensated. Its final state is in `PetCemetery`.
+        /// [owner, pet_id, compensation]
+        PetNftBurned {
+            owner: T::AccountId,
+            pet_id: PetId,
+            compensation: BalanceOf<T>,
+        },
+        
+        /// The owner removed one of a pet's personality traits. [owner, pet_id, removed_trait, new_version]
+        PersonalityTraitRemoved {
+            owner: T::AccountId,
+            pet_id: PetId,
+            removed_trait: TraitTypeString,
+            new_version: u32,
+        },
+        
+        /// A pet was lent to another account for care. [owner, pet_id, delegate, expiry_block]
+        PetDelegated {
+            owner: T::AccountId,
+            pet_id: PetId,
+            delegate: T::AccountId,
+            expiry_block: BlockNumberFor<T>,
+        },
+        
+        /// The owner ended a pet's delegation early. [owner, pet_id, delegate]
+        DelegationRevoked {
+            owner: T::AccountId,
+            pet_id: PetId,
+            delegate: T::AccountId,
+        },
+        
+        /// Pets were transferred in one batch, each with its own `PetNftTransferred` event.
+        /// [from, to, count]
+        BatchTransferCompleted {
+            from: T::AccountId,
+            to: T::AccountId,
+            count: u32,
+        },
+        
+        /// The owner approved an account to transfer a pet. [owner, pet_id, delegate]
+        TransferApproved {
+            owner: T::AccountId,
+            pet_id: PetId,
+            delegate: T::AccountId,
+        },
+        
+        /// The owner revoked a pet's transfer approval. [owner, pet_id, delegate]
+        ApprovalRevoked {
+            owner: T::AccountId,
+            pet_id: PetId,
+            delegate: T::AccountId,
+        },
+        
+        /// The owner approved or unapproved an operator for all of their pets.
+        /// [owner, operator, approved]
+        OperatorSet {
+            owner: T::AccountId,
+            operator: T::AccountId,
+            approved: bool,
+        },
     }
 
     // --- Pallet Errors ---
@@ -1263,16 +2876,20 @@ pub mod pallet {
         NftAlreadyLocked,
         /// The NFT is not locked and thus cannot be unlocked.
         NftNotLocked,
+        /// The NFT is locked by a different pallet than the one unlocking it.
+        NotLockHolder,
         /// The NFT is locked and cannot be transferred by standard means.
         NftLocked, // Used when transfer is attempted on a locked NFT
         /// The cooldown period for claiming daily PTCN has not yet passed.
         ClaimCooldownNotMet,
-        /// Error from the ItemHandler (e.g., item not found, not correct category, consumption failed).
+        /// The ItemHandler failed to consume the item for a reason other than those below.
         ItemInteractionFailed,
         /// Personality trait string is too long (exceeds T::MaxTraitStringLen).
         TraitStringTooLong,
         /// Pet already has the maximum number of personality traits.
         TooManyPersonalityTraits,
+        /// The submitted personality traits do not fit next to the pet's system-granted traits.
+        TooManyTraitsWithSystemTraits,
         /// Failed to unmarshal/reconstruct personality traits (internal error).
         FailedToReconstructPersonalityTraits, // Added for trait handling robustness
         /// Custom name provided for pet exceeds MaxPetNameLen.
@@ -1298,8 +2915,6 @@ pub mod pallet {
         HookDisabled,
         /// Invalid hook parameters.
         InvalidHookParameters,
-        /// The maximum number of synchronization status entries has been reached.
-        TooManySyncStatusEntries,
         /// The pet's state is invalid.
         InvalidState,
         /// Failed to compress the pet's interaction history.
@@ -1360,8 +2975,6 @@ pub mod pallet {
         TooManyTraits,
         /// The pets are incompatible for this interaction.
         IncompatiblePets,
-        /// The pet has too many social interactions.
-        TooManySocialInteractions,
         /// The pet has too many social bonds.
         TooManySocialBonds,
         /// The pet is incompatible with this environment.
@@ -1430,8 +3043,10 @@ pub mod pallet {
         IconTooLong,
         /// The action is too long.
         ActionTooLong,
-        /// The user has too many notifications.
-        TooManyNotifications,
+        /// Digest mode is not enabled for the user.
+        DigestNotEnabled,
+        /// The user's digest interval has not elapsed yet.
+        DigestIntervalNotElapsed,
         /// The notification was not found.
         NotificationNotFound,
         /// The progress value is invalid.
@@ -1496,6 +3111,279 @@ pub mod pallet {
         SessionMoodChangesExceeded,
         /// Error when a session has reached its maximum number of rewards.
         SessionRewardsExceeded,
+        
+        // Pet profile errors
+        /// The pet bio exceeds MaxPetBioLen.
+        PetBioTooLong,
+        /// The pet bio is not valid UTF-8 or contains control characters.
+        InvalidPetBio,
+        /// The pet has no bio to clear.
+        PetBioNotFound,
+        
+        // Lifecycle reward errors
+        /// The pet has no deferred lifecycle reward to claim.
+        NoDeferredLifecycleReward,
+        /// The lifecycle reward pot cannot cover the reward.
+        LifecycleRewardPotInsufficient,
+        
+        // Social interaction registry errors
+        /// The social interaction type name is empty or too long.
+        InvalidInteractionTypeName,
+        /// One of the pets is below the interaction type's minimum level.
+        InteractionLevelTooLow,
+        /// The pet pair performed this interaction type too recently.
+        InteractionOnCooldown,
+        
+        // Auto-care errors
+        /// The care kinds are empty or contain unknown flags.
+        InvalidAutoCareKinds,
+        /// The prepaid interval count is zero or the prepayment overflows.
+        InvalidAutoCarePrepayment,
+        /// The pet already has an active auto-care subscription.
+        AutoCareAlreadySubscribed,
+        /// The pet has no auto-care subscription.
+        AutoCareSubscriptionNotFound,
+        /// The auto-care subscription has lapsed.
+        AutoCareInactive,
+        /// The auto-care subscription is not due yet.
+        AutoCareNotDue,
+        
+        // Pending effect errors
+        /// The block's pending effects queue is full.
+        TooManyPendingEffects,
+        /// No queued effect has this ID.
+        PendingEffectNotFound,
+        
+        // Rate limit errors
+        /// The account has used up this action's calls for the current rate limit window.
+        RateLimitExceeded,
+        
+        // Trade errors
+        /// No pending trade has this ID.
+        TradeNotFound,
+        /// Only the counterparty can accept the trade.
+        NotTradeCounterparty,
+        /// Only the parties can cancel a trade before it expires.
+        NotTradeParty,
+        /// Both pets in a trade belong to the same account.
+        CannotTradeWithSelf,
+        /// The care item does not exist.
+        CareItemNotFound,
+        /// The care item is of the wrong category (e.g., a toy used as food).
+        WrongCareItemCategory,
+        /// The owner does not hold the care item.
+        CareItemNotInInventory,
+        /// A game parameter is outside its sanity bounds.
+        GameParameterOutOfBounds,
+        // Pending claim errors
+        /// No parked pet has this ID.
+        PendingClaimNotFound,
+        /// Only the recipient can claim a parked pet.
+        NotPendingClaimRecipient,
+        /// The parked pet can still be claimed.
+        PendingClaimNotExpired,
+        /// The recipient already has `MaxPendingClaims` pets parked.
+        TooManyPendingClaims,
+        // Sync acknowledgement errors
+        /// The caller owns no registered sync hook.
+        NotHookOwner,
+        /// The sync mask covers change types none of the caller's hooks is interested in.
+        SyncMaskNotInInterests,
+        /// The pet changed after the acknowledged version; its newer changes are not synchronized.
+        StaleSyncVersion,
+        // Input catalog errors
+        /// The input catalog entry name is empty.
+        InvalidInputName,
+        // Temporary trait errors
+        /// The pet already holds the maximum number of temporary traits.
+        TooManyTemporaryTraits,
+        /// A temporary trait must last at least one block.
+        InvalidTemporaryTraitDuration,
+        // Species care errors
+        /// The care preferences list a care action more than once.
+        DuplicateCareMultiplier,
+        // Sacrifice errors
+        /// No pets were given to sacrifice.
+        NoPetsToSacrifice,
+        /// More pets were given than `MaxSacrificesPerCall`.
+        TooManySacrifices,
+        /// A pet cannot be sacrificed to itself.
+        CannotSacrificeTarget,
+        /// The same pet was given more than once.
+        DuplicateSacrifice,
+        /// A sacrificed pet has a lifecycle reward waiting to be claimed.
+        UnclaimedLifecycleReward,
+        // Transfer gate errors
+        /// The pet was acquired less than `MinOwnershipBlocksBeforeTransfer` blocks ago.
+        /// `listing_eligibility` reports the block it unlocks at.
+        TransferOwnershipTooRecent,
+        /// The pet is below `MinLevelForTransfer`. `listing_eligibility` reports the level needed.
+        TransferLevelTooLow,
+        // Account migration errors
+        /// An account cannot migrate to itself.
+        CannotMigrateToSelf,
+        /// The old account has not asked to migrate to the accepting account.
+        AccountMigrationNotRequested,
+        /// One of the accounts is already part of a migration in progress.
+        AccountMigrationInProgress,
+        /// The old account's pets, parked pets or achievements would not fit the new account.
+        AccountMigrationExceedsCapacity,
+        // Free care errors
+        /// The pet has used its `MaxFreeCarePerDay` free care actions this epoch.
+        FreeCareLimitReached,
+        /// Free care only feeds or plays.
+        InvalidFreeCareKind,
+        // Release blocker errors
+        /// The pet cannot be released while it is offered in a pending trade.
+        ReleaseBlockedByTrade,
+        /// The pet cannot be released while it is leased out.
+        ReleaseBlockedByLease,
+        /// The pet cannot be released while it is a member of a party.
+        ReleaseBlockedByParty,
+        /// The pet cannot be released while another account holds a social permission over it.
+        ReleaseBlockedBySocialPermission,
+        /// The pet cannot be released while its auto-care subscription is active.
+        ReleaseBlockedByAutoCare,
+        // Donation errors
+        /// The round-up is above `MaxDonationPercent`.
+        DonationPercentTooHigh,
+        /// No community pot is registered under the ID.
+        CommunityPotNotFound,
+        // Trait removal errors
+        /// The trait was granted by the system; only owner-sourced traits can be removed.
+        SystemTraitNotRemovable,
+        // Delegation errors
+        /// A pet cannot be delegated to its owner.
+        CannotDelegateToOwner,
+        /// The delegation's expiry block has already been reached.
+        DelegationExpiryInPast,
+        /// The pet is not delegated.
+        DelegationNotFound,
+        /// The pet is delegated; only the delegate can care for it until the delegation ends.
+        PetIsDelegated,
+        // Battle release errors
+        /// The pet cannot be released while it is in an active battle.
+        ReleaseBlockedByBattle,
+        // Batch transfer errors
+        /// A batch transfer was given no pets.
+        EmptyBatchTransfer,
+        /// More pets were given than `MaxBatchTransferSize`.
+        BatchTransferTooLarge,
+        /// The same pet was given more than once in a batch.
+        DuplicatePetInBatch,
+        // Neglect batch errors
+        /// More pets were given than `MaxNeglectBatchPerBlock`.
+        NeglectBatchTooLarge,
+        /// An unsigned neglect batch was already applied in this block.
+        NeglectBatchAlreadyApplied,
+        // Transfer approval errors
+        /// An owner cannot approve themselves.
+        CannotApproveOwner,
+        /// The pet has no transfer approval.
+        ApprovalNotFound,
+    }
+
+    impl<T> From<CareError> for Error<T> {
+        fn from(error: CareError) -> Self {
+            match error {
+                CareError::ItemNotFound => Error::<T>::CareItemNotFound,
+                CareError::WrongCategory => Error::<T>::WrongCareItemCategory,
+                CareError::NotInInventory => Error::<T>::CareItemNotInInventory,
+                CareError::ConsumptionFailed => Error::<T>::ItemInteractionFailed,
+            }
+        }
+    }
+
+    // --- Pallet Hooks ---
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Resets the per-block notification budget, rotates the featured pets when due and
+        /// scans the next pets for neglect.
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            NotificationsThisBlock::<T>::kill();
+            // Covers `on_finalize` too, which reads and clears `ThrottledNotifications`.
+            T::WeightInfo::on_initialize()
+                .saturating_add(featured::FeaturedSystem::<T>::on_initialize(now))
+                .saturating_add(neglect::NeglectSystem::<T>::on_initialize(now))
+        }
+
+        /// Reports the notifications dropped in this block, if any.
+        fn on_finalize(_now: BlockNumberFor<T>) {
+            let dropped_count = ThrottledNotifications::<T>::take();
+            if dropped_count > 0 {
+                Self::deposit_event(Event::NotificationsThrottled { dropped_count });
+            }
+        }
+
+        /// Submits the pets due a neglect penalty as an unsigned batch.
+        fn offchain_worker(now: BlockNumberFor<T>) {
+            neglect::NeglectSystem::<T>::offchain_worker(now);
+        }
+
+        /// Applies deferred effects with whatever weight the block has left.
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used = pending_effects::PendingEffectsSystem::<T>::process(now, remaining_weight);
+            let used = used.saturating_add(sync::SyncHookManager::<T>::retry_failed(now, remaining_weight.saturating_sub(used)));
+            used.saturating_add(account_migration::AccountMigrationSystem::<T>::process(remaining_weight.saturating_sub(used)))
+        }
+
+        /// Checks that the configured bounds keep pet dossiers within `DOSSIER_SIZE_LIMIT`.
+        fn integrity_test() {
+            assert!(
+                dossier::DossierSystem::<T>::max_record_size() <= dossier::RECORD_SIZE_LIMIT,
+                "pet dossier records exceed RECORD_SIZE_LIMIT; lower MaxPetPersonalityTraits or MaxTraitStringLen",
+            );
+        }
+
+        /// Checks that pet ownership is recorded consistently.
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
+    // --- Pallet Genesis Configuration ---
+    #[pallet::genesis_config]
+    pub struct GenesisConfig {
+        /// Social interaction types registered at genesis.
+        pub social_interaction_types: Vec<(InteractionKind, social::SocialInteractionTypeInfo)>,
+        /// Gestures catalogued at genesis.
+        #[cfg(feature = "interactive-sessions")]
+        pub gestures: Vec<(u8, input_catalog::InputCatalogEntry)>,
+        /// Touch areas catalogued at genesis.
+        #[cfg(feature = "interactive-sessions")]
+        pub touch_areas: Vec<(u8, input_catalog::InputCatalogEntry)>,
+    }
+
+    #[cfg(feature = "std")]
+    impl Default for GenesisConfig {
+        fn default() -> Self {
+            Self {
+                social_interaction_types: social::default_interaction_types(),
+                #[cfg(feature = "interactive-sessions")]
+                gestures: input_catalog::default_gestures(),
+                #[cfg(feature = "interactive-sessions")]
+                touch_areas: input_catalog::default_touch_areas(),
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            for (type_id, info) in &self.social_interaction_types {
+                SocialInteractionTypes::<T>::insert(type_id, info.clone());
+            }
+            #[cfg(feature = "interactive-sessions")]
+            {
+                for (gesture_id, entry) in &self.gestures {
+                    GestureCatalog::<T>::insert(gesture_id, entry.clone());
+                }
+                for (touch_area, entry) in &self.touch_areas {
+                    TouchAreaCatalog::<T>::insert(touch_area, entry.clone());
+                }
+            }
+        }
     }
 
     // --- Pallet Extrinsics (Callable Functions) ---
@@ -1503,7 +3391,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Mint a new Pet NFT.
         /// This creates a unique digital companion on CritterChain.
-        #[pallet::weight(Weight::from_parts(10_000, T::DbWeight::get().writes(4).reads(1)))] // Basic weight, adjust as needed
+        #[pallet::weight(T::WeightInfo::mint_pet_nft())]
         pub fn mint_pet_nft(
             origin: OriginFor<T>,
             species: Vec<u8>, // Will be converted to BoundedVec inside
@@ -1517,132 +3405,43 @@ pub mod pallet {
             let bounded_name: BoundedVec<u8, T::MaxPetNameLen> = name.try_into()
                 .map_err(|_| Error::<T>::PetNameTooLong)?;
 
-            // 2. Check maximum owned pets for sender.
-            ensure!(
-                OwnerOfPet::<T>::get(&sender).len() < T::MaxOwnedPets::get() as usize,
-                Error::<T>::ExceedMaxOwnedPets
-            );
+            // 2. Mint through the shared path.
+            Self::do_mint(&sender, bounded_species, bounded_name)?;
 
-            // 3. Generate PetId.
-            let pet_id = NextPetId::<T>::try_mutate(|next_id| -> Result<PetId, DispatchError> {
-                let current_id = *next_id;
-                *next_id = next_id.checked_add(1).ok_or(Error::<T>::NextPetIdOverflow)?;
-                Ok(current_id)
-            })?;
+            Ok(())
+        }
 
-            // 4. DNA Hash Generation: Uses secure on-chain randomness.
-            let (dna_seed, _) = T::PetRandomness::random_seed();
-            let dna_hash_data = (dna_seed, &sender, pet_id, &bounded_species, &bounded_name).encode();
-            // Using a full SHA256 hash (32 bytes) for DnaHashType, not Blake2_128 (16 bytes).
-            // Need to change DnaHashType to [u8; 32] in traits/mod.rs.
-            let dna_hash_val = sp_io::hashing::sha256(&dna_hash_data);
-
-            // 5. Charter Attribute Derivation from dna_hash.
-            // This algorithm is deterministic.
-            let base_strength = (dna_hash_val[0] % 16) + 5; // 5-20
-            let base_agility = (dna_hash_val[1] % 16) + 5;
-            let base_intelligence = (dna_hash_val[2] % 16) + 5;
-            let base_vitality = (dna_hash_val[3] % 16) + 5;
-            let primary_elemental_affinity = match dna_hash_val[4] % 8 {
-                0 => ElementType::Fire, 1 => ElementType::Water, 2 => ElementType::Earth,
-                3 => ElementType::Air, 4 => ElementType::Tech, 5 => ElementType::Nature,
-                6 => ElementType::Mystic,
-                _ => ElementType::Neutral, // Default for 7th value, if any
-            };
+        /// Transfer a Pet NFT from its owner to a recipient.
+        /// Adheres to "Sense the Landscape, Secure the Solution" by checking transferability.
+        /// The pet must be past the transfer gates (`MinOwnershipBlocksBeforeTransfer`, `MinLevelForTransfer`).
+        /// The sender must be the owner, the account approved for the pet, or an operator of the owner.
+        #[pallet::weight(T::WeightInfo::transfer_pet_nft())]
+        pub fn transfer_pet_nft(
+            origin: OriginFor<T>,
+            recipient: T::AccountId,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
 
-            // 6. Initial Dynamic Attributes (set to defaults).
-            let current_block_number = frame_system::Pallet::<T>::block_number();
-            let initial_mood = T::MaxMoodValue::get();
-            let initial_personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits> = Default::default(); // Start empty
+            // 1. Verify pet existence and that the sender may transfer it.
+            let owner = PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(approvals::ApprovalSystem::<T>::can_transfer(&sender, &owner, pet_id), Error::<T>::NotOwner);
 
-            // Initialize state version and sync flags
-            let initial_state_version = 1;
-            let initial_sync_flags = 0; // No synchronization has occurred yet
+            // 2. Basic validation: The owner cannot be the recipient.
+            ensure!(owner != recipient, Error::<T>::CannotTransferToSelf);
 
-            let new_pet = PetNft {
-                id: pet_id,
-                dna_hash: dna_hash_val, // Use the 32-byte SHA256 hash
-                initial_species: bounded_species.clone(),
-                current_pet_name: bounded_name.clone(),
-                base_strength,
-                base_agility,
-                base_intelligence,
-                base_vitality,
-                primary_elemental_affinity,
-                level: 1,
-                experience_points: 0,
-                mood_indicator: initial_mood,
-                last_fed_block: current_block_number,
-                last_played_block: current_block_number,
-                personality_traits: initial_personality_traits.clone(),
-                last_state_update_block: current_block_number,
-                state_version: initial_state_version,
-                sync_flags: initial_sync_flags,
-            };
+            // 3. Crucial check: Ensure the NFT is transferable (not locked by marketplace, battle, etc.)
+            // and past the anti-flip gates.
+            ensure!(Self::is_transferable(&pet_id), Error::<T>::NftLocked);
+            transfer_gates::TransferGateSystem::<T>::ensure_passes(pet_id)?;
 
-            // 7. Storage Operations: Insert Pet NFT and update ownership.
-            PetNfts::<T>::insert(pet_id, new_pet.clone());
-            OwnerOfPet::<T>::try_mutate(&sender, |owned_pets_vec| {
-                owned_pets_vec.try_push(pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
-            })?;
-            PetNftOwner::<T>::insert(pet_id, sender.clone());
-            
-            // Store the initial state version
-            PetStateVersions::<T>::insert(pet_id, initial_state_version);
-
-            // 8. Emit detailed event for transparency and off-chain indexing.
-            Self::deposit_event(Event::PetNftMinted { 
-                owner: sender.clone(), 
-                pet_id,
-                species: bounded_species,
-                dna_hash: dna_hash_val,
-                base_strength,
-                base_agility,
-                base_intelligence,
-                base_vitality,
-                elemental_affinity: primary_elemental_affinity,
-                timestamp: current_block_number
-            });
-            
-            // 9. Notify synchronization hooks
-            use crate::sync::{SyncHookManager, StateChangeType};
-            SyncHookManager::<T>::notify_hooks(
-                pet_id,
-                StateChangeType::BasicInfo,
-                initial_state_version,
-                current_block_number
-            ).map_err(|_| Error::<T>::SynchronizationFailed)?;
-
-            Ok(())
-        }
-
-        /// Transfer a Pet NFT from the sender to a recipient.
-        /// Adheres to "Sense the Landscape, Secure the Solution" by checking transferability.
-        #[pallet::weight(Weight::from_parts(10_000, T::DbWeight::get().reads(2).writes(2)))] // Adjust weight
-        pub fn transfer_pet_nft(
-            origin: OriginFor<T>,
-            recipient: T::AccountId,
-            pet_id: PetId,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-
-            // 1. Basic validation: Sender cannot transfer to themselves.
-            ensure!(sender != recipient, Error::<T>::CannotTransferToSelf);
-
-            // 2. Verify ownership and pet existence.
-            let owner = PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
-            ensure!(owner == sender, Error::<T>::NotOwner);
-
-            // 3. Crucial check: Ensure the NFT is transferable (not locked by marketplace, battle, etc.).
-            ensure!(Self::is_transferable(&pet_id), Error::<T>::NftLocked);
-
-            // 4. Check recipient capacity.
-            let recipient_pets_count = OwnerOfPet::<T>::get(&recipient).len(); // Get length directly
-            ensure!(recipient_pets_count < T::MaxOwnedPets::get() as usize, Error::<T>::RecipientExceedMaxOwnedPets);
+            // 4. Check recipient capacity.
+            let recipient_pets_count = OwnerOfPet::<T>::get(&recipient).len(); // Get length directly
+            ensure!(recipient_pets_count < T::MaxOwnedPets::get() as usize, Error::<T>::RecipientExceedMaxOwnedPets);
 
             // 5. Mutate ownership records atomically.
-            OwnerOfPet::<T>::try_mutate(&sender, |sender_owned_pets| -> DispatchResult {
-                // Find and remove the pet_id from sender's owned list.
+            OwnerOfPet::<T>::try_mutate(&owner, |sender_owned_pets| -> DispatchResult {
+                // Find and remove the pet_id from the owner's owned list.
                 if let Some(index) = sender_owned_pets.iter().position(|id| *id == pet_id) {
                     sender_owned_pets.swap_remove(index);
                     Ok(())
@@ -1652,7 +3451,7 @@ pub mod pallet {
                         target: "runtime::critter_nfts_pallet",
                         "Inconsistency: Pet {} owned by {} but not in OwnerOfPet list.",
                         pet_id,
-                        sender
+                        owner
                     );
                     Err(Error::<T>::PetNotFound.into()) // More robust error, or panic in debug.
                 }
@@ -1664,11 +3463,11 @@ pub mod pallet {
                 // This error should ideally be caught by check 4, but good to have defense in depth.
             })?;
 
-            // Update the direct owner mapping for the pet.
-            PetNftOwner::<T>::insert(pet_id, recipient.clone());
+            // Update the direct owner mapping and the pet's embedded owner.
+            Self::set_pet_owner(pet_id, &recipient);
 
             // 6. Emit event for transparency and off-chain indexing.
-            Self::deposit_event(Event::PetNftTransferred { from: sender, to: recipient, pet_id });
+            Self::deposit_event(Event::PetNftTransferred { from: owner, to: recipient, pet_id });
 
             Ok(())
         }
@@ -1679,7 +3478,7 @@ pub mod pallet {
         /// This is crucial for owner agency in pet development and AI personality integration.
         /// Uses optimistic concurrency control to prevent conflicting updates.
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(3).writes(2), 0))] // Reads: PetNftOwner, PetNfts, PetStateVersions. Writes: PetNfts, PetStateVersions.
+        #[pallet::weight(T::WeightInfo::update_pet_metadata())]
         pub fn update_pet_metadata(
             origin: OriginFor<T>,
             pet_id: PetId,
@@ -1687,12 +3486,10 @@ pub mod pallet {
             personality_traits: Option<BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>>,
             expected_version: u32, // For optimistic concurrency control
         ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            let current_block_number = frame_system::Pallet::<T>::block_number();
-
             // 1. Verify ownership.
-            let owner = PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
-            ensure!(owner == sender, Error::<T>::NotOwner);
+            ensure!(PetNftOwner::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+            let sender = Self::ensure_pet_controller(origin, pet_id)?;
+            let current_block_number = frame_system::Pallet::<T>::block_number();
             
             // 2. Check version for optimistic concurrency control
             let current_version = PetStateVersions::<T>::get(pet_id);
@@ -1704,6 +3501,7 @@ pub mod pallet {
             // 4. Prepare variables to capture changes for the event
             let mut new_name_for_event: Option<BoundedVec<u8, T::MaxPetNameLen>> = None;
             let mut new_traits_for_event: Option<BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>> = None;
+            let mut name_changed = false;
 
             // 5. Mutate PetNft data.
             PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> DispatchResult {
@@ -1717,27 +3515,22 @@ pub mod pallet {
                     // Use try_into() for BoundedVec conversion and propagate error.
                     let bounded_name: BoundedVec<u8, T::MaxPetNameLen> = new_name.try_into()
                         .map_err(|_| Error::<T>::PetNameTooLong)?;
+                    name_changed = pet_nft.current_pet_name != bounded_name;
                     pet_nft.current_pet_name = bounded_name.clone();
                     new_name_for_event = Some(bounded_name);
                 }
 
                 // Selectively update personality traits if provided.
-                // This replaces all existing traits with the new set.
-                if let Some(new_traits) = personality_traits.clone() {
+                // This replaces the owner's traits with the new set; system-granted traits are kept.
+                if let Some(new_traits) = personality_traits {
                     // COMMENT: This field is updated by the owner. For the AI Personality system
                     // (conceptually an off-chain engine detailed in AI_PERSONALITY_ENGINE.md),
                     // suggestions for new or modified personality traits would be presented to the owner.
                     // If the owner accepts these suggestions, they would use this extrinsic, providing
-                    // the complete, updated list of traits. This ensures owner agency over on-chain
-                    // personality changes for their Pet NFT.
-                    
-                    // Validate new_traits content - BoundedVec already checks overall length.
-                    // Check individual trait string lengths.
-                    for trait_string in new_traits.iter() {
-                         ensure!(trait_string.len() <= T::MaxTraitStringLen::get() as usize, Error::<T>::TraitStringTooLong);
-                    }
-                    pet_nft.personality_traits = new_traits.clone();
-                    new_traits_for_event = Some(new_traits);
+                    // the complete, updated list of their traits. This ensures owner agency over on-chain
+                    // personality changes for their Pet NFT, without touching traits other systems granted.
+                    trait_provenance::TraitProvenanceSystem::<T>::replace_owner_traits(pet_nft, new_traits)?;
+                    new_traits_for_event = Some(pet_nft.personality_traits.clone());
                 }
 
                 // Update the last state update block and version
@@ -1746,17 +3539,20 @@ pub mod pallet {
                 
                 // Set the appropriate sync flags
                 if new_name_for_event.is_some() {
-                    pet_nft.sync_flags |= crate::sync::state_change_to_flag(crate::sync::StateChangeType::BasicInfo);
+                    pet_nft.sync_flags.set_dirty(crate::sync::StateChangeType::BasicInfo);
                 }
                 if new_traits_for_event.is_some() {
-                    pet_nft.sync_flags |= crate::sync::state_change_to_flag(crate::sync::StateChangeType::Traits);
+                    pet_nft.sync_flags.set_dirty(crate::sync::StateChangeType::Traits);
                 }
                 
                 Ok(())
             })?;
             
-            // 6. Update the state version in storage
+            // 6. Update the state version in storage, and the display version if the name changed
             PetStateVersions::<T>::insert(pet_id, new_version);
+            if name_changed {
+                Self::note_display_changed(pet_id);
+            }
 
             // 7. Emit detailed event for transparency.
             Self::deposit_event(Event::PetNftMetadataUpdated { 
@@ -1791,8 +3587,10 @@ pub mod pallet {
 
         /// Allows a user to claim their daily PTCN reward.
         /// This mechanism incentivizes consistent engagement with the CritterCraft ecosystem.
+        /// Deliberately signed-only: the reward is per individual player, so a `CollectiveOrigin`
+        /// cannot claim it for its account.
         #[pallet::call_index(3)]
-        #[pallet::weight(Weight::from_parts(10_000, T::DbWeight::get().reads(1).writes(2)))] // Adjust weight
+        #[pallet::weight(T::WeightInfo::claim_daily_ptcn())]
         pub fn claim_daily_ptcn(origin: OriginFor<T>) -> DispatchResult {
             let claimer = ensure_signed(origin)?;
             let current_block = frame_system::Pallet::<T>::block_number();
@@ -1828,12 +3626,12 @@ pub mod pallet {
         /// Feed a pet with a specified food item.
         /// This promotes pet nurturing and directly impacts dynamic attributes.
         #[pallet::call_index(4)]
-        #[pallet::weight(Weight::from_parts(10_000, T::DbWeight::get().reads(2).writes(1)))] // R: Owner, Item; W: PetNft
+        #[pallet::weight(T::WeightInfo::feed_pet())]
         pub fn feed_pet(origin: OriginFor<T>, pet_id: PetId, food_item_id: ItemId) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
-
-            // 1. Check if the sender owns the pet.
-            ensure!(Self::pet_nft_owner(pet_id) == Some(owner.clone()), Error::<T>::NotOwner);
+            // 1. Check that the origin cares for the pet and is within the feeding rate limit.
+            // The pet is read once here; its embedded owner replaces a `PetNftOwner` read.
+            let (owner, mut pet) = Self::ensure_pet_caretaker_with_pet(origin, pet_id)?;
+            rate_limit::RateLimiter::<T>::check(&owner, rate_limit::RATE_LIMIT_FEED)?;
 
             // 2. Consume the specified food item via the ItemHandler.
             // This interaction confirms the item exists, is of the correct category (Food),
@@ -1841,85 +3639,79 @@ pub mod pallet {
             // We pass ItemHandler's internally defined FOOD_CATEGORY_TAG via its associated type if it had one,
             // or a concrete value if it's a global constant in pallet-items.
             T::ItemHandler::consume_item_of_category(&owner, &food_item_id, T::ItemHandler::food_category_tag())
-                .map_err(|_| Error::<T>::ItemInteractionFailed)?; 
+                .map_err(Error::<T>::from)?;
             
             // 3. Update pet's attributes.
-            PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> DispatchResult {
-                let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
-                let current_block = frame_system::Pallet::<T>::block_number();
-
-                // Update last fed time.
-                pet.last_fed_block = current_block;
-                // Boost mood, capped by MaxMoodValue.
-                pet.mood_indicator = pet.mood_indicator.saturating_add(T::FeedMoodBoost::get()).min(T::MaxMoodValue::get());
-                // Grant XP.
-                pet.experience_points = pet.experience_points.saturating_add(T::FeedXpGain::get());
-                // Attempt to level up based on new XP.
-                Self::attempt_level_up(pet)?;
-                // Record this interaction timestamp.
-                pet.last_state_update_block = current_block;
-                Ok(())
-            })?;
+            let outcome = Self::apply_feed(&mut pet, Percent::one())?;
+            PetNfts::<T>::insert(pet_id, pet);
 
-            // 4. Emit event for transparency.
-            Self::deposit_event(Event::PetFed { owner, pet_id, food_item_id });
+            // 4. Emit events for transparency.
+            let legacy = Event::PetFed {
+                owner: owner.clone(),
+                pet_id,
+                food_item_id,
+                mood_boost: care_events::CareEventSystem::<T>::legacy_mood_boost(&outcome),
+                xp_gain: care_events::CareEventSystem::<T>::legacy_xp_gain(&outcome),
+                rested_bonus_xp: outcome.rested_bonus_xp,
+                new_mood: outcome.new_mood,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+                unix_time_ms: Self::unix_time_ms(),
+            };
+            care_events::CareEventSystem::<T>::emit(pet_id, owner, CareActionKind::Feed, Some(food_item_id), outcome, Some(legacy));
             Ok(())
         }
 
         /// Play with a pet using a specified toy item.
         /// This promotes pet nurturing and directly impacts dynamic attributes.
         #[pallet::call_index(5)]
-        #[pallet::weight(Weight::from_parts(10_000, T::DbWeight::get().reads(2).writes(1)))] // Similar to feed_pet
+        #[pallet::weight(T::WeightInfo::play_with_pet())]
         pub fn play_with_pet(origin: OriginFor<T>, pet_id: PetId, toy_item_id: ItemId) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
-
-            // 1. Check if the sender owns the pet.
-            ensure!(Self::pet_nft_owner(pet_id) == Some(owner.clone()), Error::<T>::NotOwner);
+            // 1. Check that the origin cares for the pet and is within the play rate limit.
+            let (owner, mut pet) = Self::ensure_pet_caretaker_with_pet(origin, pet_id)?;
+            rate_limit::RateLimiter::<T>::check(&owner, rate_limit::RATE_LIMIT_PLAY)?;
 
             // 2. Consume the specified toy item via the ItemHandler.
             T::ItemHandler::consume_item_of_category(&owner, &toy_item_id, T::ItemHandler::toy_category_tag())
-                .map_err(|_| Error::<T>::ItemInteractionFailed)?;
+                .map_err(Error::<T>::from)?;
 
             // 3. Update pet's attributes.
-            PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> DispatchResult {
-                let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
-                let current_block = frame_system::Pallet::<T>::block_number();
-
-                // Update last played time (also general care timestamp).
-                pet.last_played_block = current_block;
-                // Boost mood.
-                pet.mood_indicator = pet.mood_indicator.saturating_add(T::PlayMoodBoost::get()).min(T::MaxMoodValue::get());
-                // Grant XP.
-                pet.experience_points = pet.experience_points.saturating_add(T::PlayXpGain::get());
-                // Attempt to level up.
-                Self::attempt_level_up(pet)?;
-                // Record this interaction.
-                pet.last_state_update_block = current_block;
-                Ok(())
-            })?;
+            let outcome = Self::apply_play(&mut pet, Percent::one())?;
+            PetNfts::<T>::insert(pet_id, pet);
 
-            // 4. Emit event.
-            Self::deposit_event(Event::PetPlayedWith { owner, pet_id, toy_item_id });
+            // 4. Emit events.
+            let legacy = Event::PetPlayedWith {
+                owner: owner.clone(),
+                pet_id,
+                toy_item_id,
+                mood_boost: care_events::CareEventSystem::<T>::legacy_mood_boost(&outcome),
+                xp_gain: care_events::CareEventSystem::<T>::legacy_xp_gain(&outcome),
+                rested_bonus_xp: outcome.rested_bonus_xp,
+                new_mood: outcome.new_mood,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+                unix_time_ms: Self::unix_time_ms(),
+            };
+            care_events::CareEventSystem::<T>::emit(pet_id, owner, CareActionKind::Play, Some(toy_item_id), outcome, Some(legacy));
             Ok(())
         }
 
         /// Batch mint multiple Pet NFTs in a single transaction.
         /// This is more efficient than calling mint_pet_nft multiple times.
+        /// A batch rejected by validation is only charged for the validation.
         #[pallet::call_index(6)]
-        #[pallet::weight(Weight::from_parts(10_000 * pets.len() as u64, T::DbWeight::get().writes(4 * pets.len() as u64).reads(1 * pets.len() as u64)))]
+        #[pallet::weight(Pallet::<T>::batch_mint_weight(pets.len() as u32))]
         pub fn batch_mint_pet_nfts(
             origin: OriginFor<T>,
             pets: Vec<(Vec<u8>, Vec<u8>)>, // Vector of (species, name) pairs
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             
-            // 1. Check that the sender has enough capacity for all the new pets
-            let current_pet_count = OwnerOfPet::<T>::get(&sender).len();
-            let max_pets = T::MaxOwnedPets::get() as usize;
-            ensure!(
-                current_pet_count + pets.len() <= max_pets,
-                Error::<T>::ExceedMaxOwnedPets
-            );
+            // 1. Validate every entry up front, using the same checks as the `validate_batch_mint` runtime API.
+            if let Some(error) = Self::validate_batch_mint(&sender, &pets)
+                .into_iter()
+                .find_map(Self::batch_mint_validation_error)
+            {
+                return Err(error.with_weight(Self::batch_mint_weight(0)));
+            }
             
             // 2. Process each pet in the batch
             let mut minted_pet_ids = Vec::with_capacity(pets.len());
@@ -1940,25 +3732,21 @@ pub mod pallet {
                 })?;
                 
                 // 2.3 DNA Hash Generation
-                let (dna_seed, _) = T::PetRandomness::random_seed();
-                let dna_hash_data = (dna_seed, &sender, pet_id, &bounded_species, &bounded_name).encode();
-                let dna_hash_val = sp_io::hashing::sha256(&dna_hash_data);
+                let dna_hash_val = Self::mint_dna(&sender, pet_id, &bounded_species, &bounded_name);
+                dna::DnaSystem::<T>::note_minted(dna_hash_val);
                 
                 // 2.4 Charter Attribute Derivation
-                let base_strength = (dna_hash_val[0] % 16) + 5;
-                let base_agility = (dna_hash_val[1] % 16) + 5;
-                let base_intelligence = (dna_hash_val[2] % 16) + 5;
-                let base_vitality = (dna_hash_val[3] % 16) + 5;
-                let primary_elemental_affinity = match dna_hash_val[4] % 8 {
-                    0 => ElementType::Fire, 1 => ElementType::Water, 2 => ElementType::Earth,
-                    3 => ElementType::Air, 4 => ElementType::Tech, 5 => ElementType::Nature,
-                    6 => ElementType::Mystic,
-                    _ => ElementType::Neutral,
-                };
+                let mint_preview::CharterAttributes {
+                    strength: base_strength,
+                    agility: base_agility,
+                    intelligence: base_intelligence,
+                    vitality: base_vitality,
+                    affinity: primary_elemental_affinity,
+                } = mint_preview::CharterAttributes::from_dna(&dna_hash_val);
                 
                 // 2.5 Initialize state version and sync flags
                 let initial_state_version = 1;
-                let initial_sync_flags = 0;
+                let initial_sync_flags = crate::sync::SyncFlags::default();
                 let initial_mood = T::MaxMoodValue::get();
                 let initial_personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits> = Default::default();
                 
@@ -1982,9 +3770,19 @@ pub mod pallet {
                     last_state_update_block: current_block_number,
                     state_version: initial_state_version,
                     sync_flags: initial_sync_flags,
+                    bonus_strength: 0,
+                    bonus_agility: 0,
+                    bonus_intelligence: 0,
+                    bonus_vitality: 0,
+                    owner: sender.clone(),
+                    generation: 0,
+                    acquired_at_block: current_block_number,
+                    parent1_id: None,
+                    parent2_id: None,
                 };
                 
                 // 2.7 Storage Operations
+                Self::note_minted(pet_id, &sender, &bounded_species);
                 PetNfts::<T>::insert(pet_id, new_pet.clone());
                 PetNftOwner::<T>::insert(pet_id, sender.clone());
                 PetStateVersions::<T>::insert(pet_id, initial_state_version);
@@ -2000,7 +3798,9 @@ pub mod pallet {
                     base_intelligence,
                     base_vitality,
                     elemental_affinity: primary_elemental_affinity,
-                    timestamp: current_block_number
+                    timestamp: current_block_number,
+                    unix_time_ms: Self::unix_time_ms(),
+                    parents: None,
                 });
                 
                 // 2.9 Notify synchronization hooks
@@ -2017,6 +3817,7 @@ pub mod pallet {
             }
             
             // 3. Update the owner's pet list with all minted pets
+            let minted = minted_pet_ids.len() as u32;
             OwnerOfPet::<T>::try_mutate(&sender, |owned_pets_vec| -> DispatchResult {
                 for pet_id in minted_pet_ids {
                     owned_pets_vec.try_push(pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)?;
@@ -2024,46 +3825,24 @@ pub mod pallet {
                 Ok(())
             })?;
             
-            Ok(())
+            Ok(Some(Self::batch_mint_weight(minted)).into())
         }
         
         /// Potentially apply neglect effects if the pet hasn't been interacted with for a long time.
-        /// This is a public extrinsic, designed to be called by any account (e.g., an off-chain worker,
-        /// another player as a utility function, or the owner themselves) to trigger neglect calculations.
+        /// This is a public extrinsic, designed to be called by any account (e.g., another player as
+        /// a utility function, or the owner themselves) to trigger neglect calculations between the
+        /// scans `on_initialize` runs.
+        /// The call is free when it applies a penalty; a check that changes nothing is paid for.
         #[pallet::call_index(7)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))] // Reads: PetNfts. Writes: PetNfts.
-        pub fn apply_neglect_check(origin: OriginFor<T>, pet_id: PetId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::apply_neglect_check())]
+        pub fn apply_neglect_check(origin: OriginFor<T>, pet_id: PetId) -> DispatchResultWithPostInfo {
             let _sender = ensure_signed(origin)?; // Ensure the call is signed for security/spam prevention.
 
-            // 1. Mutate the PetNft state.
-            PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> DispatchResult {
-                let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
-                let current_block = frame_system::Pallet::<T>::block_number();
-
-                // 2. Check if the neglect threshold has been passed since the last play/care interaction.
-                // Using saturating_sub to prevent underflow if current_block is very low for some reason.
-                if current_block.saturating_sub(pet.last_played_block) > T::NeglectThresholdBlocks::get() {
-                    let old_mood = pet.mood_indicator;
-                    // 3. Apply mood penalty due to neglect.
-                    // Mood cannot go below 0.
-                    pet.mood_indicator = pet.mood_indicator.saturating_sub(T::NeglectMoodPenalty::get());
-                    // 4. Update the last state update block to reflect this change.
-                    pet.last_state_update_block = current_block;
-
-                    // 5. Emit an event if mood actually changed due to neglect.
-                    if pet.mood_indicator != old_mood {
-                       Self::deposit_event(Event::PetNeglected{ 
-                           pet_id: pet.id, 
-                           old_mood,
-                           new_mood: pet.mood_indicator,
-                           neglect_duration: current_block.saturating_sub(pet.last_played_block),
-                           timestamp: current_block
-                       });
-                    }
-                }
-                // If neglect threshold not met, no state changes or events occur for neglect, and returns Ok.
-                Ok(())
-            })
+            // 1. Apply the penalty if the pet has not been played with past the neglect threshold.
+            let penalized = neglect::NeglectSystem::<T>::check(pet_id)?;
+            
+            // 2. Neglect sweeps keep pet state current for everyone, so a sweep that did work is free.
+            Ok(if penalized { Pays::No.into() } else { Pays::Yes.into() })
         }
         
         /// Predict a pet's behavior based on its current state and interaction history.
@@ -2289,12 +4068,12 @@ pub mod pallet {
         
         /// Facilitates a social interaction between two pets.
         #[pallet::call_index(16)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(4).writes(4), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(5).writes(5), 0))]
         pub fn pet_social_interaction(
             origin: OriginFor<T>,
             pet_id_1: PetId,
             pet_id_2: PetId,
-            interaction_type: u8,
+            interaction_type: InteractionKind,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
@@ -2306,6 +4085,7 @@ pub mod pallet {
                 sender == owner1 || sender == owner2,
                 Error::<T>::NotOwner
             );
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_SOCIAL)?;
             
             // Facilitate the interaction
             social::SocialInteractionSystem::<T>::interact(
@@ -2344,7 +4124,7 @@ pub mod pallet {
         pub fn adapt_to_environment(
             origin: OriginFor<T>,
             pet_id: PetId,
-            environment_type: u8,
+            environment_type: EnvironmentKind,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
@@ -2361,22 +4141,23 @@ pub mod pallet {
         
         /// Trains a pet in a specific skill.
         #[pallet::call_index(19)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(3).writes(2), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(4).writes(3), 0))] // The pet is read once, owner included, then PetDelegations
         pub fn train_pet(
             origin: OriginFor<T>,
             pet_id: PetId,
-            skill_type: u8,
+            skill_type: SkillKind,
             training_intensity: u8,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
-            // Ensure the sender owns the pet
-            let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
-            ensure!(sender == owner, Error::<T>::NotOwner);
+            // Ensure the sender cares for the pet: its owner, or its delegate while delegated
+            let pet = PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            delegation::DelegationSystem::<T>::ensure_caretaker(&sender, &pet)?;
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_TRAIN)?;
             
             // Train the pet
             training::PetTrainingSystem::<T>::train_pet(
-                pet_id,
+                &pet,
                 skill_type,
                 training_intensity,
             )
@@ -2384,11 +4165,11 @@ pub mod pallet {
         
         /// Records a memory for a pet.
         #[pallet::call_index(20)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(1), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(3).writes(2), 0))]
         pub fn record_pet_memory(
             origin: OriginFor<T>,
             pet_id: PetId,
-            memory_type: u8,
+            memory_type: MemoryKind,
             significance: u8,
             associated_data: Vec<u8>,
         ) -> DispatchResult {
@@ -2397,6 +4178,7 @@ pub mod pallet {
             // Ensure the sender owns the pet
             let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
             ensure!(sender == owner, Error::<T>::NotOwner);
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_MEMORY)?;
             
             // Record the memory
             memory::PetMemorySystem::<T>::record_memory(
@@ -2409,7 +4191,7 @@ pub mod pallet {
         
         /// Reinforces a memory for a pet.
         #[pallet::call_index(21)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(1), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(3).writes(2), 0))]
         pub fn reinforce_pet_memory(
             origin: OriginFor<T>,
             pet_id: PetId,
@@ -2420,6 +4202,7 @@ pub mod pallet {
             // Ensure the sender owns the pet
             let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
             ensure!(sender == owner, Error::<T>::NotOwner);
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_MEMORY)?;
             
             // Reinforce the memory
             memory::PetMemorySystem::<T>::reinforce_memory(
@@ -2457,19 +4240,22 @@ pub mod pallet {
         }
         
         /// Checks if a pet has earned any new achievements.
+        /// Charged for the achievements actually evaluated and awarded.
         #[pallet::call_index(23)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(10).writes(2), 0))]
+        #[pallet::weight(achievements::AchievementSystem::<T>::max_check_weight())]
         pub fn check_pet_achievements(
             origin: OriginFor<T>,
             pet_id: PetId,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             
             // Anyone can check achievements for any pet
             // This is a public service that benefits the ecosystem
             
             // Check achievements
-            achievements::AchievementSystem::<T>::check_achievements(pet_id)
+            let check = achievements::AchievementSystem::<T>::check_achievements(pet_id)?;
+            
+            Ok(Some(achievements::AchievementSystem::<T>::check_weight(&check)).into())
         }
         
         /// Starts a new seasonal event.
@@ -2517,15 +4303,22 @@ pub mod pallet {
         }
         
         /// Updates active seasonal events, ending those that have expired.
+        /// Charged for the events actually ended, and free when it ended any.
         #[pallet::call_index(27)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
+        #[pallet::weight(seasonal::SeasonalEventSystem::<T>::update_weight(T::MaxActiveSeasonalEvents::get()))]
         pub fn update_seasonal_events(
             origin: OriginFor<T>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let _ = ensure_signed(origin)?; // Anyone can update seasonal events
             
             // Update active events
-            seasonal::SeasonalEventSystem::<T>::update_active_events()
+            let ended = seasonal::SeasonalEventSystem::<T>::update_active_events()?;
+            
+            // An update that ended nothing is paid for, so it cannot be spammed for free
+            Ok(PostDispatchInfo {
+                actual_weight: Some(seasonal::SeasonalEventSystem::<T>::update_weight(ended)),
+                pays_fee: if ended > 0 { Pays::No } else { Pays::Yes },
+            })
         }
         
         /// Triggers a lifecycle event for a pet.
@@ -2534,7 +4327,7 @@ pub mod pallet {
         pub fn trigger_lifecycle_event(
             origin: OriginFor<T>,
             pet_id: PetId,
-            event_type: u8,
+            event_type: LifecycleEventKind,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
@@ -2547,48 +4340,51 @@ pub mod pallet {
         }
         
         /// Generates an analytics report for a pet.
+        /// Charged for the size of the report actually stored.
+        #[cfg(feature = "analytics")]
         #[pallet::call_index(29)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(20).writes(1), 0))]
+        #[pallet::weight(analytics::PetAnalyticsDashboard::<T>::report_weight(T::MaxAnalyticsReportSize::get()))]
         pub fn generate_analytics_report(
             origin: OriginFor<T>,
             pet_id: PetId,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             
             // Anyone can generate an analytics report for any pet
             // This is a public service that benefits the ecosystem
             
             // Generate the report
-            let _ = analytics::PetAnalyticsDashboard::<T>::generate_analytics_report(pet_id)?;
+            let report = analytics::PetAnalyticsDashboard::<T>::generate_analytics_report(pet_id)?;
             
-            Ok(())
+            Ok(Some(analytics::PetAnalyticsDashboard::<T>::report_weight(report.len() as u32)).into())
         }
         
         /// Initiates a social interaction between two pets.
         #[pallet::call_index(30)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(5).writes(6), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(4).writes(7), 0))] // Each pet is read once, owner included
         pub fn social_interact(
             origin: OriginFor<T>,
             pet_id_1: PetId,
             pet_id_2: PetId,
-            interaction_type: u8,
+            interaction_type: InteractionKind,
             duration: u32,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
             // Ensure the sender owns at least one of the pets
-            let owner1 = Self::pet_nft_owner(&pet_id_1).ok_or(Error::<T>::PetNotFound)?;
-            let owner2 = Self::pet_nft_owner(&pet_id_2).ok_or(Error::<T>::PetNotFound)?;
+            let pet1 = PetNfts::<T>::get(pet_id_1).ok_or(Error::<T>::PetNotFound)?;
+            let pet2 = PetNfts::<T>::get(pet_id_2).ok_or(Error::<T>::PetNotFound)?;
             
             ensure!(
-                sender == owner1 || sender == owner2,
+                sender == pet1.owner || sender == pet2.owner,
                 Error::<T>::NotOwner
             );
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_SOCIAL)?;
             
             // Initiate the social interaction
-            social::SocialInteractionSystem::<T>::interact(
-                pet_id_1,
-                pet_id_2,
+            social::SocialInteractionSystem::<T>::interact_pets(
+                &pet1,
+                &pet2,
                 interaction_type,
                 duration,
             )
@@ -2600,7 +4396,7 @@ pub mod pallet {
         pub fn adapt_to_environment(
             origin: OriginFor<T>,
             pet_id: PetId,
-            environment_type: u8,
+            environment_type: EnvironmentKind,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
@@ -2616,52 +4412,72 @@ pub mod pallet {
         }
         
         /// Sets a visual attribute for a pet.
+        /// With `expected_version`, fails with `ConcurrentModification` unless the pet is still
+        /// at that state version; `None` overwrites whatever is there.
         #[pallet::call_index(32)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(1), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(4).writes(3), 0))] // R: PetNftOwner, PetStateVersions, PetNfts, PetVisualAttributes. W: PetVisualAttributes, PetNfts, PetStateVersions.
         pub fn set_visual_attribute(
             origin: OriginFor<T>,
             pet_id: PetId,
             attribute_type: u8,
             value: Vec<u8>,
+            expected_version: Option<u32>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
             // Ensure the sender owns the pet
             let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
             ensure!(sender == owner, Error::<T>::NotOwner);
+            Self::ensure_expected_version(pet_id, expected_version)?;
             
-            // Set the visual attribute
+            // Set the visual attribute and bump the pet's state version
             visual::VisualSystem::<T>::set_visual_attribute(
                 pet_id,
                 attribute_type,
                 value,
-            )
+            )?;
+            Self::record_profile_change(pet_id)?;
+            
+            Ok(())
         }
         
         /// Sets the visual theme for a pet.
+        /// With `expected_version`, fails with `ConcurrentModification` unless the pet is still
+        /// at that state version; `None` overwrites whatever is there.
         #[pallet::call_index(33)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(1), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(5).writes(4), 0))] // R: PetNftOwner, PetStateVersions, PetVisualTheme, PetNfts, PetDisplayVersions. W: PetVisualTheme, PetNfts, PetStateVersions, PetDisplayVersions.
         pub fn set_visual_theme(
             origin: OriginFor<T>,
             pet_id: PetId,
             theme_id: u8,
+            expected_version: Option<u32>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
             // Ensure the sender owns the pet
             let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
             ensure!(sender == owner, Error::<T>::NotOwner);
+            Self::ensure_expected_version(pet_id, expected_version)?;
             
-            // Set the visual theme
+            // Set the visual theme and bump the pet's state version, and the display version if
+            // the theme changed
+            let previous_theme = PetVisualTheme::<T>::get(pet_id);
             visual::VisualSystem::<T>::set_visual_theme(
                 pet_id,
                 theme_id,
-            )
+            )?;
+            Self::record_profile_change(pet_id)?;
+            if previous_theme != Some(theme_id) {
+                Self::note_display_changed(pet_id);
+            }
+            
+            Ok(())
         }
         
         /// Processes a gesture interaction with a pet.
+        #[cfg(feature = "interactive-sessions")]
         #[pallet::call_index(34)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(1), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(6).writes(3), 0))] // The pet is read once, owner included
         pub fn process_gesture(
             origin: OriginFor<T>,
             pet_id: PetId,
@@ -2670,21 +4486,36 @@ pub mod pallet {
             let sender = ensure_signed(origin)?;
             
             // Ensure the sender owns the pet
-            let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
-            ensure!(sender == owner, Error::<T>::NotOwner);
+            let mut pet = Self::owned_pet(&sender, pet_id)?;
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_GESTURE)?;
             
             // Process the gesture
-            let _ = interactive::InteractiveSystem::<T>::process_gesture(
-                pet_id,
+            let entry = input_catalog::InputCatalogSystem::<T>::apply(
+                &mut pet,
+                input_catalog::InputKind::Gesture,
                 gesture_id,
             )?;
+            PetNfts::<T>::insert(pet_id, pet);
+            let (response_animation, mood_effect) = (entry.response_animation, entry.base_mood_effect);
+            
+            // Report the care action
+            let outcome = care_events::CareOutcome { mood_delta: mood_effect, ..Default::default() };
+            let legacy = Event::GestureInteraction {
+                pet_id,
+                gesture_id,
+                response_animation,
+                mood_effect,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+            };
+            care_events::CareEventSystem::<T>::emit(pet_id, sender, CareActionKind::Gesture, None, outcome, Some(legacy));
             
             Ok(())
         }
         
         /// Processes a touch interaction with a pet.
+        #[cfg(feature = "interactive-sessions")]
         #[pallet::call_index(35)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(1), 0))]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(6).writes(3), 0))] // The pet is read once, owner included
         pub fn process_touch(
             origin: OriginFor<T>,
             pet_id: PetId,
@@ -2693,14 +4524,27 @@ pub mod pallet {
             let sender = ensure_signed(origin)?;
             
             // Ensure the sender owns the pet
-            let owner = Self::pet_nft_owner(&pet_id).ok_or(Error::<T>::PetNotFound)?;
-            ensure!(sender == owner, Error::<T>::NotOwner);
+            let mut pet = Self::owned_pet(&sender, pet_id)?;
+            rate_limit::RateLimiter::<T>::check(&sender, rate_limit::RATE_LIMIT_TOUCH)?;
             
             // Process the touch
-            let _ = interactive::InteractiveSystem::<T>::process_touch(
-                pet_id,
+            let entry = input_catalog::InputCatalogSystem::<T>::apply(
+                &mut pet,
+                input_catalog::InputKind::TouchArea,
                 touch_area,
             )?;
+            PetNfts::<T>::insert(pet_id, pet);
+            
+            // Report the care action
+            let outcome = care_events::CareOutcome { mood_delta: entry.base_mood_effect, ..Default::default() };
+            let legacy = Event::TouchInteraction {
+                pet_id,
+                touch_area,
+                response_id: entry.response_animation,
+                mood_effect: entry.base_mood_effect,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+            };
+            care_events::CareEventSystem::<T>::emit(pet_id, sender, CareActionKind::Touch, None, outcome, Some(legacy));
             
             Ok(())
         }
@@ -2739,153 +4583,1746 @@ pub mod pallet {
             Ok(())
         }
         
-        /// Skips the current UX flow step for a user.
-        #[pallet::call_index(38)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
-        pub fn skip_ux_flow_step(
-            origin: OriginFor<T>,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            
-            // Skip the UX flow step
-            let _ = user_experience::UserExperienceSystem::<T>::skip_ux_flow_step(
-                sender,
-            )?;
+        /// Skips the current UX flow step for a user.
+        #[pallet::call_index(38)]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
+        pub fn skip_ux_flow_step(
+            origin: OriginFor<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // Skip the UX flow step
+            let _ = user_experience::UserExperienceSystem::<T>::skip_ux_flow_step(
+                sender,
+            )?;
+            
+            Ok(())
+        }
+        
+        /// Marks a notification as read.
+        #[pallet::call_index(39)]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
+        pub fn mark_notification_as_read(
+            origin: OriginFor<T>,
+            notification_id: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // Materialize the sender's digest if it has come due
+            user_experience::UserExperienceSystem::<T>::maybe_flush_digest(&sender)?;
+            
+            // Mark the notification as read
+            user_experience::UserExperienceSystem::<T>::mark_notification_as_read(
+                sender.clone(),
+                notification_id,
+            )?;
+            
+            // Emit event
+            Self::deposit_event(Event::NotificationRead {
+                account_id: sender,
+                notification_id,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+            });
+            
+            Ok(())
+        }
+        
+        /// Adds a notification for a user.
+        #[pallet::call_index(40)]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
+        pub fn add_user_notification(
+            origin: OriginFor<T>,
+            target_account: T::AccountId,
+            notification_type: u8,
+            title: Vec<u8>,
+            message: Vec<u8>,
+            icon: Vec<u8>,
+            priority: u8,
+            action: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // Only allow certain accounts to add notifications for others
+            // For now, we'll allow any account to add notifications
+            // In a production environment, this should be restricted
+            
+            // Add the notification
+            let _ = user_experience::UserExperienceSystem::<T>::add_notification(
+                target_account,
+                notification_type,
+                title,
+                message,
+                icon,
+                priority,
+                action,
+            )?;
+            
+            Ok(())
+        }
+        
+        /// Updates achievement progress for a user.
+        #[pallet::call_index(41)]
+        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(2), 0))]
+        pub fn update_user_achievement(
+            origin: OriginFor<T>,
+            achievement_id: u32,
+            progress: u8,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // Update the achievement progress
+            let _ = Self::update_achievement_progress(
+                sender,
+                achievement_id,
+                progress,
+            )?;
+            
+            Ok(())
+        }
+        
+        // Call index 42 was `get_pet_profile`, which returned nothing to its caller; profiles are
+        // read through the `get_pet_profile` runtime API. The index is not reused.
+        
+        /// Sets or replaces the free-text bio shown on a pet's profile.
+        /// Only the owner of the pet can perform this action.
+        /// With `expected_version`, fails with `ConcurrentModification` unless the pet is still
+        /// at that state version; `None` overwrites whatever is there.
+        #[pallet::call_index(43)]
+        #[pallet::weight(T::WeightInfo::set_pet_bio())]
+        pub fn set_pet_bio(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            bio: Vec<u8>,
+            expected_version: Option<u32>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // 1. Verify ownership and the expected version.
+            let owner = PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(owner == sender, Error::<T>::NotOwner);
+            Self::ensure_expected_version(pet_id, expected_version)?;
+            
+            // 2. Validate the bio: bounded length and markdown-safe content.
+            let bounded_bio: BoundedVec<u8, T::MaxPetBioLen> = bio.try_into()
+                .map_err(|_| Error::<T>::PetBioTooLong)?;
+            ensure!(Self::is_valid_bio(&bounded_bio), Error::<T>::InvalidPetBio);
+            let bio_len = bounded_bio.len() as u32;
+            
+            // 3. Store the bio and bump the pet's state and display versions.
+            PetBios::<T>::insert(pet_id, bounded_bio);
+            let (version, timestamp) = Self::record_profile_change(pet_id)?;
+            Self::note_display_changed(pet_id);
+            
+            // 4. Emit event.
+            Self::deposit_event(Event::PetBioUpdated {
+                owner: sender,
+                pet_id,
+                bio_len,
+                version,
+                timestamp,
+            });
+            
+            Ok(())
+        }
+        
+        /// Removes the bio from a pet's profile.
+        /// Only the owner of the pet can perform this action.
+        /// With `expected_version`, fails with `ConcurrentModification` unless the pet is still
+        /// at that state version; `None` removes the bio regardless.
+        #[pallet::call_index(44)]
+        #[pallet::weight(T::WeightInfo::clear_pet_bio())]
+        pub fn clear_pet_bio(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            expected_version: Option<u32>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // 1. Verify ownership and the expected version.
+            let owner = PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(owner == sender, Error::<T>::NotOwner);
+            Self::ensure_expected_version(pet_id, expected_version)?;
+            
+            // 2. Clear the bio.
+            Self::do_clear_pet_bio(pet_id, false)
+        }
+        
+        /// Force-clears a pet's bio after it has been reported and upheld by moderation.
+        /// Can only be called by `T::ModerationOrigin`.
+        #[pallet::call_index(45)]
+        #[pallet::weight(T::WeightInfo::force_clear_pet_bio())]
+        pub fn force_clear_pet_bio(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            T::ModerationOrigin::ensure_origin(origin)?;
+            
+            ensure!(PetNfts::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+            
+            Self::do_clear_pet_bio(pet_id, true)
+        }
+        
+        /// Enables or disables notification digest mode for the caller.
+        /// In digest mode, non-critical notifications are counted per type and delivered
+        /// as a single summary on the user's first notification, care or owner call once
+        /// `interval_blocks` have passed, or through `flush_digest`.
+        #[pallet::call_index(47)]
+        #[pallet::weight(T::WeightInfo::set_notification_digest())]
+        pub fn set_notification_digest(
+            origin: OriginFor<T>,
+            enabled: bool,
+            interval_blocks: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            user_experience::UserExperienceSystem::<T>::set_notification_digest(
+                sender.clone(),
+                enabled,
+                interval_blocks,
+            )?;
+            
+            Self::deposit_event(Event::NotificationDigestUpdated {
+                account_id: sender,
+                enabled,
+                interval_blocks,
+            });
+            
+            Ok(())
+        }
+        
+        /// Materializes an account's pending digest once its interval has elapsed.
+        /// Permissionless, so anyone (e.g., a frontend relayer) can deliver a due digest.
+        #[pallet::call_index(48)]
+        #[pallet::weight(T::WeightInfo::flush_digest())]
+        pub fn flush_digest(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+            
+            // 1. Check the account is in digest mode and its interval has elapsed.
+            let settings = NotificationDigestSettings::<T>::get(&account)
+                .ok_or(Error::<T>::DigestNotEnabled)?;
+            let current_block = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                current_block >= settings.last_flush.saturating_add(settings.interval_blocks),
+                Error::<T>::DigestIntervalNotElapsed
+            );
+            
+            // 2. Materialize the digest.
+            user_experience::UserExperienceSystem::<T>::flush_digest(&account)?;
+            
+            Ok(())
+        }
+        
+        /// Claims lifecycle stage PTCN rewards that were deferred because the reward pot was short.
+        /// Only the current owner of the pet can claim.
+        #[pallet::call_index(46)]
+        #[pallet::weight(T::WeightInfo::claim_deferred_lifecycle_reward())]
+        pub fn claim_deferred_lifecycle_reward(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // 1. Verify ownership.
+            let owner = PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(owner == sender, Error::<T>::NotOwner);
+            
+            // 2. Check there is something to claim.
+            let amount = DeferredLifecycleRewards::<T>::get(pet_id);
+            ensure!(!amount.is_zero(), Error::<T>::NoDeferredLifecycleReward);
+            
+            // 3. Pay from the pot; leave the reward deferred if it is still short.
+            Self::pay_from_lifecycle_pot(&sender, amount)?;
+            DeferredLifecycleRewards::<T>::remove(pet_id);
+            
+            // 4. Emit event.
+            Self::deposit_event(Event::DeferredLifecycleRewardClaimed {
+                owner: sender,
+                pet_id,
+                amount,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+            });
+            
+            Ok(())
+        }
+        
+        /// Registers or updates a social interaction type.
+        /// Only callable by the catalog origin.
+        #[pallet::call_index(49)]
+        #[pallet::weight(T::WeightInfo::set_social_interaction_type())]
+        pub fn set_social_interaction_type(
+            origin: OriginFor<T>,
+            type_id: InteractionKind,
+            info: social::SocialInteractionTypeInfo,
+        ) -> DispatchResult {
+            T::CatalogOrigin::ensure_origin(origin)?;
+            
+            ensure!(!info.name.is_empty(), Error::<T>::InvalidInteractionTypeName);
+            
+            SocialInteractionTypes::<T>::insert(type_id, info);
+            
+            Self::deposit_event(Event::SocialInteractionTypeSet { type_id });
+            
+            Ok(())
+        }
+        
+        /// Sets the species and name of the starter pet granted on onboarding completion.
+        /// Only callable by the update origin.
+        #[pallet::call_index(51)]
+        #[pallet::weight(T::WeightInfo::set_onboarding_starter_config())]
+        pub fn set_onboarding_starter_config(
+            origin: OriginFor<T>,
+            species: Vec<u8>,
+            name: Vec<u8>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            
+            let species: BoundedVec<u8, T::MaxSpeciesNameLen> = species.try_into()
+                .map_err(|_| Error::<T>::SpeciesNameTooLong)?;
+            let name: BoundedVec<u8, T::MaxPetNameLen> = name.try_into()
+                .map_err(|_| Error::<T>::PetNameTooLong)?;
+            
+            OnboardingStarterConfig::<T>::put(user_experience::StarterPetConfig {
+                species: species.clone(),
+                name: name.clone(),
+            });
+            
+            Self::deposit_event(Event::OnboardingStarterConfigUpdated { species, name });
+            
+            Ok(())
+        }
+        
+        /// Removes a social interaction type from the registry.
+        /// Only callable by the catalog origin. Existing cooldown entries simply expire unused.
+        #[pallet::call_index(50)]
+        #[pallet::weight(T::WeightInfo::remove_social_interaction_type())]
+        pub fn remove_social_interaction_type(
+            origin: OriginFor<T>,
+            type_id: InteractionKind,
+        ) -> DispatchResult {
+            T::CatalogOrigin::ensure_origin(origin)?;
+            
+            ensure!(
+                SocialInteractionTypes::<T>::contains_key(type_id),
+                Error::<T>::InvalidInteractionType
+            );
+            SocialInteractionTypes::<T>::remove(type_id);
+            
+            Self::deposit_event(Event::SocialInteractionTypeRemoved { type_id });
+            
+            Ok(())
+        }
+        
+        /// Subscribes a pet to auto-care, prepaying `intervals_prepaid` intervals into the
+        /// auto-care account. `kinds` is a bitmask of `auto_care::AUTO_CARE_*` flags.
+        #[pallet::call_index(52)]
+        #[pallet::weight(T::WeightInfo::subscribe_auto_care())]
+        pub fn subscribe_auto_care(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            kinds: u8,
+            intervals_prepaid: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            auto_care::AutoCareSystem::<T>::subscribe(sender, pet_id, kinds, intervals_prepaid)
+        }
+        
+        /// Performs a pet's due auto-care. Anyone can call this (typically an off-chain worker)
+        /// and is paid the auto-care bounty out of the subscription's fee.
+        #[pallet::call_index(53)]
+        #[pallet::weight(T::WeightInfo::execute_due_care())]
+        pub fn execute_due_care(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            auto_care::AutoCareSystem::<T>::execute_due_care(sender, pet_id)
+        }
+        
+        /// Cancels a pet's auto-care subscription and refunds the remaining balance.
+        /// Only callable by the subscriber; also reclaims the balance of a lapsed subscription.
+        #[pallet::call_index(54)]
+        #[pallet::weight(T::WeightInfo::cancel_auto_care())]
+        pub fn cancel_auto_care(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            auto_care::AutoCareSystem::<T>::cancel(sender, pet_id)
+        }
+        
+        /// Proposes swapping the sender's `offered_pet` for `requested_pet`. The offered pet is
+        /// locked until the owner of `requested_pet` accepts, either party cancels, or the trade expires.
+        #[pallet::call_index(55)]
+        #[pallet::weight(T::WeightInfo::propose_trade())]
+        pub fn propose_trade(
+            origin: OriginFor<T>,
+            offered_pet: PetId,
+            requested_pet: PetId,
+        ) -> DispatchResult {
+            let sender = Self::ensure_pet_controller(origin, offered_pet)?;
+            
+            trade::TradeSystem::<T>::propose(sender, offered_pet, requested_pet)
+        }
+        
+        /// Accepts a trade, swapping both pets. A trade whose pets changed hands or that has
+        /// expired is cancelled instead.
+        #[pallet::call_index(56)]
+        #[pallet::weight(T::WeightInfo::accept_trade())]
+        pub fn accept_trade(
+            origin: OriginFor<T>,
+            trade_id: trade::TradeId,
+        ) -> DispatchResult {
+            let sender = Self::ensure_account(origin)?;
+            
+            trade::TradeSystem::<T>::accept(sender, trade_id)
+        }
+        
+        /// Cancels a trade and unlocks the offered pet. Callable by either party at any time,
+        /// and by anyone once the trade has expired.
+        #[pallet::call_index(57)]
+        #[pallet::weight(T::WeightInfo::cancel_trade())]
+        pub fn cancel_trade(
+            origin: OriginFor<T>,
+            trade_id: trade::TradeId,
+        ) -> DispatchResult {
+            let sender = Self::ensure_account(origin)?;
+            
+            trade::TradeSystem::<T>::cancel(sender, trade_id)
+        }
+        
+        /// Replaces the governable game parameters (mood boosts, XP gains, neglect tuning).
+        /// Only callable by the params origin. Takes effect from the next care action.
+        #[pallet::call_index(58)]
+        #[pallet::weight(T::WeightInfo::set_game_parameters())]
+        pub fn set_game_parameters(
+            origin: OriginFor<T>,
+            new: game_params::GameParametersOf<T>,
+        ) -> DispatchResult {
+            T::ParamsOrigin::ensure_origin(origin)?;
+            
+            let changes = game_params::GameParametersSystem::<T>::set(new)?;
+            
+            Self::deposit_event(Event::GameParametersUpdated { changes });
+            
+            Ok(())
+        }
+        
+        /// Releases a pet's lock regardless of which pallet holds it.
+        /// Only callable by root, as an escape hatch for pets stuck locked.
+        #[pallet::call_index(59)]
+        #[pallet::weight(T::WeightInfo::force_unlock())]
+        pub fn force_unlock(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            
+            let lock = LockedNfts::<T>::get(pet_id).ok_or(Error::<T>::NftNotLocked)?;
+            rested_xp::RestedXpSystem::<T>::settle(pet_id);
+            LockedNfts::<T>::remove(pet_id);
+            
+            Self::deposit_event(Event::NftForceUnlocked { pet_id, locker: lock.locker, locked_at: lock.locked_at });
+            
+            Ok(())
+        }
+        
+        /// Claims a bred pet parked for the sender, once they have room for it.
+        /// A pet past its claim window is burned instead.
+        #[pallet::call_index(60)]
+        #[pallet::weight(T::WeightInfo::claim_pending_pet())]
+        pub fn claim_pending_pet(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            pending_claims::PendingClaimSystem::<T>::claim(sender, pet_id)
+        }
+        
+        /// Burns a parked pet whose claim window has passed. Callable by anyone.
+        #[pallet::call_index(61)]
+        #[pallet::weight(T::WeightInfo::clear_expired_pending_pet())]
+        pub fn clear_expired_pending_pet(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            
+            pending_claims::PendingClaimSystem::<T>::clear_expired(pet_id)
+        }
+        
+        /// Sets the chance that a parent passes a trait on when breeding, or resets it to
+        /// `BaseInheritanceChance` with `None`. Only callable by the catalog origin.
+        #[pallet::call_index(62)]
+        #[pallet::weight(T::WeightInfo::set_trait_inheritance_chance())]
+        pub fn set_trait_inheritance_chance(
+            origin: OriginFor<T>,
+            trait_name: TraitTypeString,
+            chance: Option<sp_runtime::Perbill>,
+        ) -> DispatchResult {
+            T::CatalogOrigin::ensure_origin(origin)?;
+            
+            ensure!(trait_name.len() <= T::MaxTraitStringLen::get() as usize, Error::<T>::TraitStringTooLong);
+            
+            InheritanceTable::<T>::set(&trait_name, chance);
+            
+            Self::deposit_event(Event::TraitInheritanceChanceSet { trait_name, chance });
+            
+            Ok(())
+        }
+        
+        /// Clears a pet's sync flags in `mask` once the caller's off-chain system has caught up
+        /// with state `version`. Only callable by sync hook owners, for change types their hooks
+        /// are interested in, and only for the pet's current version.
+        #[pallet::call_index(63)]
+        #[pallet::weight(T::WeightInfo::acknowledge_sync(T::MaxRegisteredHooks::get()))]
+        pub fn acknowledge_sync(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            mask: u16,
+            version: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            sync::SyncHookManager::<T>::acknowledge(sender, pet_id, mask, version)
+        }
+        
+        /// Adds or updates a gesture or touch area in the input catalog.
+        /// Only callable by the catalog origin.
+        #[cfg(feature = "interactive-sessions")]
+        #[pallet::call_index(64)]
+        #[pallet::weight(T::WeightInfo::set_input_catalog_entry())]
+        pub fn set_input_catalog_entry(
+            origin: OriginFor<T>,
+            kind: input_catalog::InputKind,
+            id: u8,
+            entry: input_catalog::InputCatalogEntry,
+        ) -> DispatchResult {
+            T::CatalogOrigin::ensure_origin(origin)?;
+            
+            ensure!(!entry.name.is_empty(), Error::<T>::InvalidInputName);
+            
+            match kind {
+                input_catalog::InputKind::Gesture => GestureCatalog::<T>::insert(id, entry),
+                input_catalog::InputKind::TouchArea => TouchAreaCatalog::<T>::insert(id, entry),
+            }
+            
+            Self::deposit_event(Event::InputCatalogEntrySet { kind, id });
+            
+            Ok(())
+        }
+        
+        /// Removes a gesture or touch area from the input catalog.
+        /// Only callable by the catalog origin. Existing cooldown entries simply expire unused.
+        #[cfg(feature = "interactive-sessions")]
+        #[pallet::call_index(65)]
+        #[pallet::weight(T::WeightInfo::remove_input_catalog_entry())]
+        pub fn remove_input_catalog_entry(
+            origin: OriginFor<T>,
+            kind: input_catalog::InputKind,
+            id: u8,
+        ) -> DispatchResult {
+            T::CatalogOrigin::ensure_origin(origin)?;
+            
+            match kind {
+                input_catalog::InputKind::Gesture => {
+                    ensure!(GestureCatalog::<T>::contains_key(id), Error::<T>::InvalidGesture);
+                    GestureCatalog::<T>::remove(id);
+                },
+                input_catalog::InputKind::TouchArea => {
+                    ensure!(TouchAreaCatalog::<T>::contains_key(id), Error::<T>::InvalidTouchArea);
+                    TouchAreaCatalog::<T>::remove(id);
+                },
+            }
+            
+            Self::deposit_event(Event::InputCatalogEntryRemoved { kind, id });
+            
+            Ok(())
+        }
+        
+        /// Sets a species' care preferences, or clears them with `None` so every care action
+        /// gains 100% again. Only callable by the catalog origin.
+        #[pallet::call_index(66)]
+        #[pallet::weight(T::WeightInfo::set_species_care_preferences())]
+        pub fn set_species_care_preferences(
+            origin: OriginFor<T>,
+            species: BoundedVec<u8, T::MaxSpeciesNameLen>,
+            preferences: Option<species_care::CarePreferences>,
+        ) -> DispatchResult {
+            T::CatalogOrigin::ensure_origin(origin)?;
+            
+            if let Some(preferences) = &preferences {
+                for (index, (kind, _)) in preferences.multipliers.iter().enumerate() {
+                    ensure!(
+                        !preferences.multipliers.iter().skip(index + 1).any(|(other, _)| other == kind),
+                        Error::<T>::DuplicateCareMultiplier
+                    );
+                }
+            }
+            
+            SpeciesCarePreferences::<T>::set(&species, preferences.clone());
+            
+            Self::deposit_event(Event::SpeciesCarePreferencesSet { species, preferences });
+            
+            Ok(())
+        }
+        
+        /// Burns up to `MaxSacrificesPerCall` of the sender's pets to add a bonus to each of
+        /// `target_pet_id`'s bonus stats, up to `MaxSacrificeBonus` over the pet's lifetime.
+        /// The sacrificed pets must be unlocked and have no unclaimed lifecycle rewards.
+        #[pallet::call_index(67)]
+        #[pallet::weight(T::WeightInfo::sacrifice_pets(sacrificed.len() as u32))]
+        pub fn sacrifice_pets(
+            origin: OriginFor<T>,
+            target_pet_id: PetId,
+            sacrificed: Vec<PetId>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            sacrifice::SacrificeSystem::<T>::sacrifice(sender, target_pet_id, sacrificed).map(|_| ())
+        }
+        
+        /// Moves an unlocked pet from `from` to `to` for a marketplace. With `bypass_gates` the
+        /// transfer gates are skipped, to deliver a sale already agreed while the pet passed them;
+        /// otherwise they apply as for `transfer_pet_nft`. Only `MarketplaceOrigin` can call this.
+        #[pallet::call_index(68)]
+        #[pallet::weight(T::WeightInfo::marketplace_transfer_pet())]
+        pub fn marketplace_transfer_pet(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            pet_id: PetId,
+            bypass_gates: bool,
+        ) -> DispatchResult {
+            T::MarketplaceOrigin::ensure_origin(origin)?;
+            ensure!(from != to, Error::<T>::CannotTransferToSelf);
+            ensure!(Self::is_transferable(&pet_id), Error::<T>::NftLocked);
+            if !bypass_gates {
+                transfer_gates::TransferGateSystem::<T>::ensure_passes(pet_id)?;
+            }
+            
+            Self::do_transfer_nft(&from, &to, &pet_id)?;
+            
+            Self::deposit_event(Event::PetNftTransferred { from, to, pet_id });
+            
+            Ok(())
+        }
+        
+        /// Asks to migrate everything the caller holds in this pallet to `new_account`, which
+        /// must accept with `accept_account_migration`. Replaces an earlier request.
+        #[pallet::call_index(69)]
+        #[pallet::weight(T::WeightInfo::initiate_account_migration())]
+        pub fn initiate_account_migration(
+            origin: OriginFor<T>,
+            new_account: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            account_migration::AccountMigrationSystem::<T>::initiate(sender, new_account)
+        }
+        
+        /// Accepts the migration `old_account` asked for to the caller. Fails unless none of
+        /// `old_account`'s pets is locked and its pets, parked pets and achievements fit the
+        /// caller's. Migrates up to `AccountMigrationStepsPerBlock` categories at once; the rest
+        /// continue in later blocks.
+        #[pallet::call_index(70)]
+        #[pallet::weight(account_migration::AccountMigrationSystem::<T>::accept_weight())]
+        pub fn accept_account_migration(
+            origin: OriginFor<T>,
+            old_account: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            account_migration::AccountMigrationSystem::<T>::accept(sender, old_account)
+        }
+        
+        /// Feed or play with a pet without an item, at `FreeCareEffect` of the item-based gains.
+        /// Each pet gets `MaxFreeCarePerDay` free care actions per care epoch, on top of the
+        /// feeding and play rate limits. Emits `CareActionPerformed` with no item, and no legacy
+        /// event.
+        #[pallet::call_index(71)]
+        #[pallet::weight(T::WeightInfo::pet_basic_care())]
+        pub fn pet_basic_care(origin: OriginFor<T>, pet_id: PetId, kind: CareActionKind) -> DispatchResult {
+            // 1. Check that the origin cares for the pet and is within the rate limit of the action.
+            let (owner, mut pet) = Self::ensure_pet_caretaker_with_pet(origin, pet_id)?;
+            let action = match kind {
+                CareActionKind::Feed => rate_limit::RATE_LIMIT_FEED,
+                CareActionKind::Play => rate_limit::RATE_LIMIT_PLAY,
+                _ => return Err(Error::<T>::InvalidFreeCareKind.into()),
+            };
+            rate_limit::RateLimiter::<T>::check(&owner, action)?;
+
+            // 2. Count the free use instead of consuming an item.
+            free_care::FreeCareSystem::<T>::use_one(pet_id)?;
+
+            // 3. Update pet's attributes at the reduced effect.
+            let effect = T::FreeCareEffect::get();
+            let outcome = match kind {
+                CareActionKind::Feed => Self::apply_feed(&mut pet, effect)?,
+                _ => Self::apply_play(&mut pet, effect)?,
+            };
+            PetNfts::<T>::insert(pet_id, pet);
+
+            // 4. Emit the care event.
+            care_events::CareEventSystem::<T>::emit(pet_id, owner, kind, None, outcome, None);
+            Ok(())
+        }
+        
+        /// Release a pet the caller controls, deleting it with all its state. Fails while anything
+        /// still holds the pet (a lock, an active auto-care subscription, an unclaimed reward or
+        /// a `ReleaseBlockers` subsystem), naming the first blocker.
+        #[pallet::call_index(72)]
+        #[pallet::weight(T::WeightInfo::release_pet())]
+        pub fn release_pet(origin: OriginFor<T>, pet_id: PetId) -> DispatchResult {
+            ensure!(PetNftOwner::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+            let sender = Self::ensure_pet_controller(origin, pet_id)?;
+            
+            release::ReleaseSystem::<T>::release(sender, pet_id)
+        }
+        
+        /// Release a pet whatever holds it: every release blocker drops its references to the
+        /// pet first. Only root can call this.
+        #[pallet::call_index(73)]
+        #[pallet::weight(
+            T::WeightInfo::force_release()
+                .saturating_add(<release::ReleaseBlockers<T> as crittercraft_traits::nft::ReleaseBlocker<PetId>>::on_forced_release_weight())
+        )]
+        pub fn force_release(origin: OriginFor<T>, pet_id: PetId) -> DispatchResult {
+            ensure_root(origin)?;
+            
+            release::ReleaseSystem::<T>::force_release(pet_id)
+        }
+        
+        /// Round up the PTCN fees the caller pays this pallet by `percent`, donated to
+        /// `beneficiary`, or stop with `enabled` false. The percent is capped at
+        /// `MaxDonationPercent`. A donation never fails the action it rounds up.
+        #[pallet::call_index(74)]
+        #[pallet::weight(T::WeightInfo::set_donation_roundup())]
+        pub fn set_donation_roundup(
+            origin: OriginFor<T>,
+            enabled: bool,
+            percent: sp_runtime::Perbill,
+            beneficiary: donations::DonationTarget,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            let preference = enabled.then_some(donations::DonationPreference { percent, beneficiary });
+            donations::DonationSystem::<T>::set_preference(sender, preference)
+        }
+        
+        /// Register a community pot accounts can donate to, point it at another account, or
+        /// remove it with `None`. Donations to a removed pot are skipped. Only `UpdateOrigin`
+        /// can call this.
+        #[pallet::call_index(75)]
+        #[pallet::weight(T::WeightInfo::set_community_pot())]
+        pub fn set_community_pot(
+            origin: OriginFor<T>,
+            pot_id: donations::CommunityPotId,
+            account: Option<T::AccountId>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            
+            match &account {
+                Some(account) => CommunityPots::<T>::insert(pot_id, account),
+                None => CommunityPots::<T>::remove(pot_id),
+            }
+            Self::deposit_event(Event::CommunityPotSet { pot_id, account });
+            Ok(())
+        }
+        
+        /// Burn a pet the caller controls for `BurnCompensation` PTCN from the lifecycle reward pot.
+        /// The pet is deleted with all its state, like a release, and its final state is kept in
+        /// `PetCemetery`. Fails while anything still holds the pet, naming the first blocker.
+        #[pallet::call_index(76)]
+        #[pallet::weight(T::WeightInfo::burn_pet())]
+        pub fn burn_pet(origin: OriginFor<T>, pet_id: PetId) -> DispatchResult {
+            ensure!(PetNftOwner::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+            let sender = Self::ensure_pet_controller(origin, pet_id)?;
+            
+            burn::BurnSystem::<T>::burn(sender, pet_id)
+        }
+        
+        /// Remove one owner-sourced personality trait from a pet, without resubmitting the rest
+        /// through `update_pet_metadata`. Fails with `TraitNotFound` if the pet does not have the
+        /// trait and `SystemTraitNotRemovable` if the system granted it. Uses optimistic
+        /// concurrency control like `update_pet_metadata`.
+        #[pallet::call_index(77)]
+        #[pallet::weight(T::WeightInfo::remove_personality_trait())]
+        pub fn remove_personality_trait(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            trait_to_remove: TraitTypeString,
+            expected_version: u32,
+        ) -> DispatchResult {
+            // 1. Verify ownership and the expected version.
+            ensure!(PetNftOwner::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+            let sender = Self::ensure_pet_controller(origin, pet_id)?;
+            let current_block_number = frame_system::Pallet::<T>::block_number();
+            let current_version = PetStateVersions::<T>::get(pet_id);
+            ensure!(current_version == expected_version, Error::<T>::ConcurrentModification);
+            let new_version = current_version.saturating_add(1);
+
+            // 2. Remove the trait and mark the traits for synchronization.
+            PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> DispatchResult {
+                let pet_nft = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+                ensure!(pet_nft.state_version == expected_version, Error::<T>::ConcurrentModification);
+
+                trait_provenance::TraitProvenanceSystem::<T>::remove_owner_trait(pet_nft, &trait_to_remove)?;
+                pet_nft.last_state_update_block = current_block_number;
+                pet_nft.state_version = new_version;
+                pet_nft.sync_flags.set_dirty(crate::sync::StateChangeType::Traits);
+                Ok(())
+            })?;
+            PetStateVersions::<T>::insert(pet_id, new_version);
+
+            // 3. Emit the event and notify synchronization hooks.
+            Self::deposit_event(Event::PersonalityTraitRemoved {
+                owner: sender,
+                pet_id,
+                removed_trait: trait_to_remove,
+                new_version,
+            });
+            crate::sync::SyncHookManager::<T>::notify_hooks(
+                pet_id,
+                crate::sync::StateChangeType::Traits,
+                new_version,
+                current_block_number,
+                None,
+            ).map_err(|_| Error::<T>::SynchronizationFailed)?;
+
+            Ok(())
+        }
+        
+        /// Lend a pet the caller controls to `delegate` for care until `expiry_block`, replacing
+        /// any delegation it has. While delegated, only the delegate can feed, play with and
+        /// train the pet; the owner keeps every other right.
+        #[pallet::call_index(78)]
+        #[pallet::weight(T::WeightInfo::delegate_pet())]
+        pub fn delegate_pet(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            delegate: T::AccountId,
+            expiry_block: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            ensure!(PetNftOwner::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+            let sender = Self::ensure_pet_controller(origin, pet_id)?;
+            
+            delegation::DelegationSystem::<T>::delegate(sender, pet_id, delegate, expiry_block)
+        }
+        
+        /// End the delegation of a pet the caller owns before it expires.
+        #[pallet::call_index(79)]
+        #[pallet::weight(T::WeightInfo::revoke_delegation())]
+        pub fn revoke_delegation(origin: OriginFor<T>, pet_id: PetId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            delegation::DelegationSystem::<T>::revoke(sender, pet_id)
+        }
+        
+        /// Transfer up to `MaxBatchTransferSize` of the caller's pets to `recipient` in one call.
+        /// Every pet is checked as `transfer_pet_nft` would check it before any moves, and the
+        /// recipient must have room for all of them: either every pet is transferred or none is.
+        #[pallet::call_index(80)]
+        #[pallet::weight(T::WeightInfo::batch_transfer_pet_nfts(pet_ids.len() as u32))]
+        pub fn batch_transfer_pet_nfts(
+            origin: OriginFor<T>,
+            recipient: T::AccountId,
+            pet_ids: Vec<PetId>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            batch_transfer::BatchTransferSystem::<T>::transfer(sender, recipient, pet_ids)
+        }
+        
+        /// Apply the neglect penalty to a batch of pets found due one by the off-chain worker.
+        /// Unsigned; the transaction pool accepts one batch per block (see `ValidateUnsigned`).
+        /// Missing pets and pets not due a penalty are skipped.
+        #[pallet::call_index(81)]
+        #[pallet::weight(T::WeightInfo::apply_neglect_check_unsigned(pet_ids.len() as u32))]
+        pub fn apply_neglect_check_unsigned(
+            origin: OriginFor<T>,
+            pet_ids: neglect::NeglectBatch,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            
+            neglect::NeglectSystem::<T>::apply_batch(pet_ids)?;
+            Ok(Pays::No.into())
+        }
+        
+        /// Approve `delegate` to transfer a pet the caller owns, replacing any approval it has.
+        /// The approval is cleared when the pet changes hands or is locked.
+        #[pallet::call_index(82)]
+        #[pallet::weight(T::WeightInfo::approve_transfer())]
+        pub fn approve_transfer(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            delegate: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            approvals::ApprovalSystem::<T>::approve(sender, pet_id, delegate)
+        }
+        
+        /// Revoke the transfer approval of a pet the caller owns.
+        #[pallet::call_index(83)]
+        #[pallet::weight(T::WeightInfo::revoke_approval())]
+        pub fn revoke_approval(origin: OriginFor<T>, pet_id: PetId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            approvals::ApprovalSystem::<T>::revoke(sender, pet_id)
+        }
+        
+        /// Approve or unapprove `operator` to transfer every pet the caller owns, now or later.
+        #[pallet::call_index(84)]
+        #[pallet::weight(T::WeightInfo::set_operator())]
+        pub fn set_operator(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            approved: bool,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            approvals::ApprovalSystem::<T>::set_operator(sender, operator, approved)
+        }
+    }
+    
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+        
+        /// Accepts only `apply_neglect_check_unsigned`, once per block, from the local off-chain
+        /// worker or in a block.
+        fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            if !matches!(source, TransactionSource::Local | TransactionSource::InBlock) {
+                return InvalidTransaction::Call.into();
+            }
+            match call {
+                Call::apply_neglect_check_unsigned { pet_ids } => neglect::NeglectSystem::<T>::validate_batch(pet_ids),
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
+    }
+
+    // --- Pallet Internal Helper Functions ---
+    // These functions are not directly callable as extrinsics but are used internally by the pallet.
+    impl<T: Config> Pallet<T> {
+        /// Resolves the account acting for `origin`: the signer of a signed origin, or the
+        /// account a `CollectiveOrigin` resolves to. Every owner-gated and care call comes
+        /// through here, so this is also where the account's notification digest is
+        /// materialized once its interval has elapsed. A digest that cannot be stored stays
+        /// pending for a later call rather than failing this one.
+        ///
+        /// # Parameters
+        ///
+        /// * `origin` - The call's origin
+        ///
+        /// # Returns
+        ///
+        /// * `Result<T::AccountId, DispatchError>` - The acting account, or `BadOrigin`
+        pub(crate) fn ensure_account(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
+            let account = match T::CollectiveOrigin::try_origin(origin) {
+                Ok(account) => account,
+                Err(origin) => ensure_signed(origin)?,
+            };
+            let _ = frame_support::storage::with_storage_layer(|| {
+                user_experience::UserExperienceSystem::<T>::maybe_flush_digest(&account)
+            });
+            Ok(account)
+        }
+
+        /// Ensures `origin` controls `pet_id`: a signed origin of the pet's owner, or a
+        /// `CollectiveOrigin` that resolves to the owner. Used by the owner-gated calls so
+        /// pets owned by a guild account can be cared for through council proposals.
+        ///
+        /// # Parameters
+        ///
+        /// * `origin` - The call's origin
+        /// * `pet_id` - The pet the call acts on
+        ///
+        /// # Returns
+        ///
+        /// * `Result<T::AccountId, DispatchError>` - The owner's account, or `NotOwner`
+        pub(crate) fn ensure_pet_controller(origin: OriginFor<T>, pet_id: PetId) -> Result<T::AccountId, DispatchError> {
+            let account = Self::ensure_account(origin)?;
+            ensure!(Self::pet_nft_owner(pet_id) == Some(account.clone()), Error::<T>::NotOwner);
+            Ok(account)
+        }
+
+        /// Like `ensure_pet_controller`, for the care calls, and also returns the pet. The
+        /// account caring for a delegated pet is its delegate, not its owner; see `delegation`.
+        /// Ownership is checked against the pet's embedded `owner`, so the care calls read the
+        /// pet once instead of reading `PetNftOwner` and then the pet.
+        ///
+        /// # Parameters
+        ///
+        /// * `origin` - The call's origin
+        /// * `pet_id` - The pet the call acts on
+        ///
+        /// # Returns
+        ///
+        /// * `Result<(T::AccountId, PetNft<T>), DispatchError>` - The caretaker's account and the
+        ///   pet, `PetIsDelegated` for the owner of a delegated pet, or `NotOwner`
+        pub(crate) fn ensure_pet_caretaker_with_pet(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+        ) -> Result<(T::AccountId, PetNft<T>), DispatchError> {
+            let account = Self::ensure_account(origin)?;
+            let pet = PetNfts::<T>::get(pet_id).ok_or(Error::<T>::NotOwner)?;
+            delegation::DelegationSystem::<T>::ensure_caretaker(&account, &pet)?;
+            Ok((account, pet))
+        }
+
+        /// Reads a pet `who` owns, checking ownership against the pet's embedded `owner`.
+        ///
+        /// # Parameters
+        ///
+        /// * `who` - The account that must own the pet
+        /// * `pet_id` - The pet to read
+        ///
+        /// # Returns
+        ///
+        /// * `Result<PetNft<T>, DispatchError>` - The pet, `PetNotFound` or `NotOwner`
+        pub(crate) fn owned_pet(who: &T::AccountId, pet_id: PetId) -> Result<PetNft<T>, DispatchError> {
+            let pet = PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(pet.owner == *who, Error::<T>::NotOwner);
+            Ok(pet)
+        }
+
+        /// Checks that every pet's embedded `owner` matches the `PetNftOwner` index, that the
+        /// owner's `OwnerOfPet` list holds the pet, and that no `PetNftOwner` entry outlives its pet.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub(crate) fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            for (pet_id, pet) in PetNfts::<T>::iter() {
+                ensure!(
+                    PetNftOwner::<T>::get(pet_id).as_ref() == Some(&pet.owner),
+                    "a pet's embedded owner differs from PetNftOwner"
+                );
+                ensure!(
+                    OwnerOfPet::<T>::get(&pet.owner).contains(&pet_id),
+                    "a pet is missing from its owner's OwnerOfPet list"
+                );
+            }
+            for pet_id in PetNftOwner::<T>::iter_keys() {
+                ensure!(PetNfts::<T>::contains_key(pet_id), "PetNftOwner records a pet that does not exist");
+            }
+            Ok(())
+        }
+
+        /// Records `owner` as the owner of `pet_id`, in the pet's embedded `owner` and in the
+        /// `PetNftOwner` index, acquired as of this block. Every transfer goes through here so
+        /// the two never disagree. A transfer ends the pet's delegation and transfer approval.
+        pub(crate) fn set_pet_owner(pet_id: PetId, owner: &T::AccountId) {
+            PetDelegations::<T>::remove(pet_id);
+            approvals::ApprovalSystem::<T>::clear(pet_id);
+            let now = frame_system::Pallet::<T>::block_number();
+            PetNfts::<T>::mutate(pet_id, |pet_opt| {
+                if let Some(pet) = pet_opt {
+                    pet.owner = owner.clone();
+                    pet.acquired_at_block = now;
+                }
+            });
+            PetNftOwner::<T>::insert(pet_id, owner.clone());
+        }
+
+        /// Internal helper to handle pet level ups based on experience points.
+        /// This is called after interactions that grant XP. The pet levels up as often as its
+        /// XP covers the next level on the `LevelCurve`, carrying the excess over, with one
+        /// `PetLeveledUp` event per level gained. A pet at `MaxLevel` keeps its XP without leveling.
+        pub(crate) fn attempt_level_up(pet: &mut PetNft<T>) -> DispatchResult {
+            let now = frame_system::Pallet::<T>::block_number();
+            while let Some((new_level, xp_left)) = leveling::LevelingSystem::<T>::level_up_once(pet.level, pet.experience_points) {
+                let old_level = pet.level;
+                pet.level = new_level;
+                pet.experience_points = xp_left;
+
+                // Emit event for transparency and off-chain indexing.
+                Self::deposit_event(Event::PetLeveledUp {
+                    pet_id: pet.id,
+                    old_level,
+                    new_level,
+                    experience_points: xp_left,
+                    timestamp: now,
+                });
+            }
+            Ok(())
+        }
+
+        /// Grants XP to a pet, adding any rested XP and XP buff bonuses, and levels it up as
+        /// often as the new XP allows. This is the shared path for XP grants. Both bonuses are
+        /// computed from `amount`, so they do not compound.
+        ///
+        /// # Parameters
+        ///
+        /// * `pet` - The pet receiving the XP
+        /// * `amount` - The XP to grant, before any bonus
+        ///
+        /// # Returns
+        ///
+        /// * `Result<(u32, u32), DispatchError>` - The rested bonus and the buff bonus granted
+        ///   on top of `amount`
+        pub(crate) fn grant_xp(pet: &mut PetNft<T>, amount: u32) -> Result<(u32, u32), DispatchError> {
+            // 1. Match the grant against the pet's rested XP and its active XP buff.
+            let rested_bonus = rested_xp::RestedXpSystem::<T>::consume(pet.id, amount);
+            let buff_bonus = buffs::BuffSystem::<T>::xp_bonus(pet.id, amount);
+
+            // 2. Add the XP and the bonuses together.
+            pet.experience_points = pet.experience_points
+                .saturating_add(amount)
+                .saturating_add(rested_bonus)
+                .saturating_add(buff_bonus);
+
+            // 3. Level up until the remaining XP no longer covers the next level.
+            Self::attempt_level_up(pet)?;
+
+            Ok((rested_bonus, buff_bonus))
+        }
+
+        /// Applies item-less feeding to a pet, scaling the mood boost and XP gain by `effect`.
+        /// `feed_pet` applies it in full; auto-care applies it at `AutoCareEffect`, free care at
+        /// `FreeCareEffect`.
+        /// Returns how the feeding changed the pet, for the care events.
+        pub(crate) fn apply_feed(pet: &mut PetNft<T>, effect: Percent) -> Result<care_events::CareOutcome, DispatchError> {
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let params = Self::params();
+            let old_mood = pet.mood_indicator;
+            // The species' care preferences scale both gains.
+            let factor = species_care::SpeciesCareSystem::<T>::factor(pet, CareActionKind::Feed);
+            let xp_gain = factor.apply_xp(effect.mul_floor(params.feed_xp_gain));
+
+            // Update last fed time.
+            pet.last_fed_block = current_block;
+            // Boost mood, capped by MaxMoodValue.
+            pet.mood_indicator = pet.mood_indicator
+                .saturating_add(factor.apply_mood(effect.mul_floor(params.feed_mood_boost)))
+                .min(T::MaxMoodValue::get());
+            // Grant XP and level up based on the new XP.
+            let (rested_bonus_xp, buff_bonus_xp) = Self::grant_xp(pet, xp_gain)?;
+            // Record this interaction timestamp and count it in the care history.
+            pet.last_state_update_block = current_block;
+            care_history::CareHistorySystem::<T>::record(pet.id, CareActionKind::Feed);
+            Ok(care_events::CareOutcome::new(old_mood, pet.mood_indicator, xp_gain.saturating_add(buff_bonus_xp), rested_bonus_xp))
+        }
+
+        /// Applies item-less play to a pet, scaling the mood boost and XP gain by `effect`.
+        /// `play_with_pet` applies it in full; auto-care applies it at `AutoCareEffect`, free care at
+        /// `FreeCareEffect`.
+        /// Returns how the play changed the pet, for the care events.
+        pub(crate) fn apply_play(pet: &mut PetNft<T>, effect: Percent) -> Result<care_events::CareOutcome, DispatchError> {
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let params = Self::params();
+            let old_mood = pet.mood_indicator;
+            // The species' care preferences scale both gains.
+            let factor = species_care::SpeciesCareSystem::<T>::factor(pet, CareActionKind::Play);
+            let xp_gain = factor.apply_xp(effect.mul_floor(params.play_xp_gain));
+
+            // Update last played time (also general care timestamp).
+            pet.last_played_block = current_block;
+            // Boost mood, capped by MaxMoodValue.
+            pet.mood_indicator = pet.mood_indicator
+                .saturating_add(factor.apply_mood(effect.mul_floor(params.play_mood_boost)))
+                .min(T::MaxMoodValue::get());
+            // Grant XP and level up.
+            let (rested_bonus_xp, buff_bonus_xp) = Self::grant_xp(pet, xp_gain)?;
+            // Record this interaction and count it in the care history.
+            pet.last_state_update_block = current_block;
+            care_history::CareHistorySystem::<T>::record(pet.id, CareActionKind::Play);
+            Ok(care_events::CareOutcome::new(old_mood, pet.mood_indicator, xp_gain.saturating_add(buff_bonus_xp), rested_bonus_xp))
+        }
+
+        /// Helper function to get a UI-friendly pet profile.
+        /// 
+        /// # Parameters
+        /// 
+        /// * `pet_id` - The ID of the pet
+        /// 
+        /// # Returns
+        /// 
+        /// * `Result<ui_bridge::UiPetProfileOf<T>, DispatchError>` - The pet profile, or an error
+        pub fn get_ui_pet_profile(pet_id: PetId) -> Result<ui_bridge::UiPetProfileOf<T>, DispatchError> {
+            ui_bridge::UiBridge::<T>::get_pet_profile(pet_id)
+        }
+        
+        /// Helper function to get a compact pet summary for search results.
+        /// 
+        /// # Parameters
+        /// 
+        /// * `pet_id` - The ID of the pet
+        /// 
+        /// # Returns
+        /// 
+        /// * `Result<ui_bridge::UiPetSummary<T>, DispatchError>` - The pet summary, or an error
+        pub fn get_ui_pet_summary(pet_id: PetId) -> Result<ui_bridge::UiPetSummary<T>, DispatchError> {
+            ui_bridge::UiBridge::<T>::get_pet_summary(pet_id)
+        }
+        
+        /// Shared minting path used by `mint_pet_nft` and the onboarding starter pet grant.
+        /// Derives charter attributes from fresh DNA, stores the pet and notifies sync hooks.
+        ///
+        /// # Parameters
+        ///
+        /// * `owner` - The account receiving the pet
+        /// * `bounded_species` - The pet's species
+        /// * `bounded_name` - The pet's name
+        ///
+        /// # Returns
+        ///
+        /// * `Result<PetId, DispatchError>` - The new pet's ID, or an error
+        pub(crate) fn do_mint(
+            owner: &T::AccountId,
+            bounded_species: BoundedVec<u8, T::MaxSpeciesNameLen>,
+            bounded_name: BoundedVec<u8, T::MaxPetNameLen>,
+        ) -> Result<PetId, DispatchError> {
+            let sender = owner.clone();
+
+            // 1. Check maximum owned pets for the owner.
+            ensure!(
+                OwnerOfPet::<T>::get(&sender).len() < T::MaxOwnedPets::get() as usize,
+                Error::<T>::ExceedMaxOwnedPets
+            );
+
+            // 2. Generate PetId.
+            let pet_id = NextPetId::<T>::try_mutate(|next_id| -> Result<PetId, DispatchError> {
+                let current_id = *next_id;
+                *next_id = next_id.checked_add(1).ok_or(Error::<T>::NextPetIdOverflow)?;
+                Ok(current_id)
+            })?;
+
+            // 3. DNA Hash Generation: Uses secure on-chain randomness.
+            let dna_hash_val = Self::mint_dna(&sender, pet_id, &bounded_species, &bounded_name);
+            dna::DnaSystem::<T>::note_minted(dna_hash_val);
+
+            // 4. Charter Attribute Derivation from dna_hash.
+            // This algorithm is deterministic, and shared with mint previews.
+            let mint_preview::CharterAttributes {
+                strength: base_strength,
+                agility: base_agility,
+                intelligence: base_intelligence,
+                vitality: base_vitality,
+                affinity: primary_elemental_affinity,
+            } = mint_preview::CharterAttributes::from_dna(&dna_hash_val);
+
+            // 5. Initial Dynamic Attributes (set to defaults).
+            let current_block_number = frame_system::Pallet::<T>::block_number();
+            let initial_mood = T::MaxMoodValue::get();
+            let initial_personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits> = Default::default(); // Start empty
+
+            // Initialize state version and sync flags
+            let initial_state_version = 1;
+            let initial_sync_flags = crate::sync::SyncFlags::default(); // No synchronization has occurred yet
+
+            let new_pet = PetNft {
+                id: pet_id,
+                dna_hash: dna_hash_val, // Use the 32-byte SHA256 hash
+                initial_species: bounded_species.clone(),
+                current_pet_name: bounded_name.clone(),
+                base_strength,
+                base_agility,
+                base_intelligence,
+                base_vitality,
+                primary_elemental_affinity,
+                level: 1,
+                experience_points: 0,
+                mood_indicator: initial_mood,
+                last_fed_block: current_block_number,
+                last_played_block: current_block_number,
+                personality_traits: initial_personality_traits.clone(),
+                last_state_update_block: current_block_number,
+                state_version: initial_state_version,
+                sync_flags: initial_sync_flags,
+                bonus_strength: 0,
+                bonus_agility: 0,
+                bonus_intelligence: 0,
+                bonus_vitality: 0,
+                owner: sender.clone(),
+                generation: 0,
+                acquired_at_block: current_block_number,
+                parent1_id: None,
+                parent2_id: None,
+            };
+
+            // 6. Storage Operations: Insert Pet NFT and update ownership.
+            Self::note_minted(pet_id, &sender, &bounded_species);
+            PetNfts::<T>::insert(pet_id, new_pet.clone());
+            OwnerOfPet::<T>::try_mutate(&sender, |owned_pets_vec| {
+                owned_pets_vec.try_push(pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
+            })?;
+            PetNftOwner::<T>::insert(pet_id, sender.clone());
+            
+            // Store the initial state version
+            PetStateVersions::<T>::insert(pet_id, initial_state_version);
+
+            // 7. Emit detailed event for transparency and off-chain indexing.
+            Self::deposit_event(Event::PetNftMinted { 
+                owner: sender.clone(), 
+                pet_id,
+                species: bounded_species,
+                dna_hash: dna_hash_val,
+                base_strength,
+                base_agility,
+                base_intelligence,
+                base_vitality,
+                elemental_affinity: primary_elemental_affinity,
+                timestamp: current_block_number,
+                unix_time_ms: Self::unix_time_ms(),
+                parents: None,
+            });
+            
+            // 8. Notify synchronization hooks
+            use crate::sync::{SyncHookManager, StateChangeType};
+            SyncHookManager::<T>::notify_hooks(
+                pet_id,
+                StateChangeType::BasicInfo,
+                initial_state_version,
+                current_block_number
+            ).map_err(|_| Error::<T>::SynchronizationFailed)?;
+
+            Ok(pet_id)
+        }
+
+        /// The game parameters in force: the governed values, or the Config constants until set.
+        pub fn params() -> game_params::GameParametersOf<T> {
+            game_params::GameParametersSystem::<T>::current()
+        }
+        
+        /// The current unix time in milliseconds from `TimeProvider`, carried by user-facing events
+        /// next to the block number. Zero with `NoTimeProvider`.
+        pub(crate) fn unix_time_ms() -> u64 {
+            u64::saturated_from(<T as NftCoreConfig>::TimeProvider::now().as_millis())
+        }
+        
+        /// Records the account a newly minted pet was minted to and counts the pet in its
+        /// species' population.
+        pub(crate) fn note_minted(pet_id: PetId, minter: &T::AccountId, species: &BoundedVec<u8, T::MaxSpeciesNameLen>) {
+            PetMinters::<T>::insert(pet_id, minter);
+            SpeciesPopulation::<T>::mutate(species, |record| record.minted = record.minted.saturating_add(1));
+        }
+        
+        /// Releases a pet: removes it from its owner and deletes its per-pet state, refunding any
+        /// auto-care subscription. The caller checks the pet may be released, with
+        /// `ReleaseSystem::ensure_releasable` unless it forces the release. `PetParents`,
+        /// `ChildrenIndex` and `PetGenealogy` are kept so family trees stay whole.
+        pub(crate) fn do_release_pet(pet_id: PetId) -> DispatchResult {
+            let pet = PetNfts::<T>::take(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            let owner = pet.owner.clone();
+            Self::note_species_released(&pet.initial_species);
+            
+            OwnerOfPet::<T>::mutate(&owner, |owned_pets| {
+                if let Some(index) = owned_pets.iter().position(|id| *id == pet_id) {
+                    owned_pets.swap_remove(index);
+                }
+            });
+            PetNftOwner::<T>::remove(pet_id);
+            if CareSubscriptions::<T>::contains_key(pet_id) {
+                auto_care::AutoCareSystem::<T>::cancel(owner.clone(), pet_id)?;
+            }
+            
+            let final_version = PetStateVersions::<T>::take(pet_id);
+            PetDisplayVersions::<T>::remove(pet_id);
+            PetBios::<T>::remove(pet_id);
+            SystemTraits::<T>::remove(pet_id);
+            TemporaryTraits::<T>::remove(pet_id);
+            SacrificeBonus::<T>::remove(pet_id);
+            FreeCareUses::<T>::remove(pet_id);
+            PetDelegations::<T>::remove(pet_id);
+            PetApprovals::<T>::remove(pet_id);
+            RestedXp::<T>::remove(pet_id);
+            TemporaryBuffs::<T>::remove(pet_id);
+            PetStorageSize::<T>::remove(pet_id);
+            PetSocialInteractions::<T>::remove(pet_id);
+            PetSocialBonds::<T>::remove(pet_id);
+            PetEnvironmentalAdaptations::<T>::remove(pet_id);
+            PetSkills::<T>::remove(pet_id);
+            PetMemories::<T>::remove(pet_id);
+            PetAchievements::<T>::remove(pet_id);
+            PetLifecycleEvents::<T>::remove(pet_id);
+            PetVisualAttributes::<T>::remove(pet_id);
+            PetVisualTheme::<T>::remove(pet_id);
+            AchievementProgressBackfilled::<T>::remove(pet_id);
+            PetSyncStatus::<T>::remove(pet_id);
+            PetInteractionHistory::<T>::remove(pet_id);
+            PetBehaviorPredictions::<T>::remove(pet_id);
+            PetTransitionProbabilities::<T>::remove(pet_id);
+            #[cfg(feature = "analytics")]
+            PetAnalyticsReports::<T>::remove(pet_id);
+            let _ = AchievementProgress::<T>::clear_prefix(pet_id, u32::MAX, None);
+            let _ = CareActionBuckets::<T>::clear_prefix(pet_id, u32::MAX, None);
+            #[cfg(feature = "interactive-sessions")]
+            let _ = PetInputCooldowns::<T>::clear_prefix(pet_id, u32::MAX, None);
+            
+            Self::deposit_event(Event::PetReleased { owner, pet_id });
+            sync::SyncHookManager::<T>::notify_hooks(
+                pet_id,
+                sync::StateChangeType::Removed,
+                final_version,
+                frame_system::Pallet::<T>::block_number(),
+                None,
+            ).map_err(|_| Error::<T>::SynchronizationFailed)?;
+            Ok(())
+        }
+        
+        /// Counts a released or burned pet in its species' population.
+        /// Must be called by every path that removes a pet, before the pet record is deleted.
+        pub(crate) fn note_species_released(species: &BoundedVec<u8, T::MaxSpeciesNameLen>) {
+            SpeciesPopulation::<T>::mutate(species, |record| record.released = record.released.saturating_add(1));
+        }
+        
+        /// Gets a species' population counters. Backs the `species_population` runtime API.
+        /// Unknown (or over-long) species names report zero counts.
+        pub fn species_population_of(species: Vec<u8>) -> SpeciesPopulationRecord {
+            BoundedVec::<u8, T::MaxSpeciesNameLen>::try_from(species)
+                .map(SpeciesPopulation::<T>::get)
+                .unwrap_or_default()
+        }
+        
+        /// Gets a pet's effective stats, after every modifier. Backs the `effective_stats`
+        /// runtime API.
+        pub fn effective_stats(pet_id: PetId) -> Option<effective_stats::EffectiveStats> {
+            effective_stats::EffectiveStatsSystem::<T>::effective_stats(pet_id)
+        }
+        
+        /// Converts a pet to the shared stats view used by battle and quests, with effective stats.
+        pub(crate) fn shared_pet_stats(pet: &PetNft<T>) -> crate::traits::PetStats {
+            let stats = effective_stats::EffectiveStatsSystem::<T>::of(pet);
+            crate::traits::PetStats {
+                strength: stats.strength,
+                agility: stats.agility,
+                intelligence: stats.intelligence,
+                vitality: stats.vitality,
+                elemental_affinity: pet.primary_elemental_affinity as u8,
+                level: pet.level.min(u16::MAX as u32) as u16,
+                experience: pet.experience_points,
+            }
+        }
+        
+        /// Gets a pet's quality score. Backs the `quality_score` runtime API.
+        pub fn quality_score(pet_id: PetId) -> Option<u32> {
+            valuation::PetValuationSystem::<T>::quality_score(pet_id)
+        }
+        
+        /// Previews a pet's reaction to an interaction without changing state.
+        /// Backs the `preview_reaction` runtime API.
+        pub fn preview_reaction(pet_id: PetId, interaction: InteractionKind) -> Option<social::ReactionPreview> {
+            social::SocialInteractionSystem::<T>::preview_reaction(pet_id, interaction)
+        }
+        
+        /// Returns a pet's ancestor tree up to `max_depth` generations. Backs the `pet_lineage` runtime API.
+        pub fn pet_lineage(pet_id: PetId, max_depth: u8) -> Option<lineage::LineageTree<T::AccountId>> {
+            lineage::LineageSystem::<T>::ancestors(pet_id, max_depth)
+        }
+        
+        /// Lists every catalogued gesture and touch area. Empty without the
+        /// `interactive-sessions` feature. Backs the `input_catalog` runtime API.
+        pub fn input_catalog() -> input_catalog::InputCatalog {
+            #[cfg(feature = "interactive-sessions")]
+            return input_catalog::InputCatalogSystem::<T>::catalog();
+            #[cfg(not(feature = "interactive-sessions"))]
+            return input_catalog::InputCatalog::default();
+        }
+        
+        /// Lists the pets of the current featured rotation with their summaries.
+        /// Backs the `featured_pets` runtime API.
+        pub fn featured_pets() -> Option<featured::FeaturedPets<T::AccountId, T::BlockNumber>> {
+            featured::FeaturedSystem::<T>::featured_pets()
+        }
+        
+        /// Measures the storage a pet takes up in each auxiliary map.
+        /// Backs the `pet_storage_footprint` runtime API.
+        pub fn pet_storage_footprint(pet_id: PetId) -> footprint::StorageFootprint {
+            footprint::FootprintSystem::<T>::footprint(pet_id)
+        }
+        
+        /// Lists up to `limit` pets with the largest tracked storage size, largest first.
+        /// Backs the `heaviest_pets` runtime API.
+        pub fn heaviest_pets(limit: u32) -> Vec<(PetId, u32)> {
+            footprint::FootprintSystem::<T>::heaviest(limit)
+        }
+        
+        /// Returns up to `limit` descendants of a pet, nearest generations first.
+        /// Backs the `descendants_of` runtime API.
+        pub fn descendants_of(pet_id: PetId, limit: u32) -> Vec<PetId> {
+            lineage::LineageSystem::<T>::descendants(pet_id, limit)
+        }
+        
+        /// Returns the genealogy records of a pet and its bred ancestors up to `depth`
+        /// generations, nearest generations first.
+        pub fn get_ancestry(pet_id: PetId, depth: u8) -> Vec<lineage::GenealogyRecord<T>> {
+            lineage::LineageSystem::<T>::genealogy(pet_id, depth)
+        }
+        
+        /// Derives the DNA of a pet minted by `owner` as `pet_id` at this point of the current
+        /// block. It hashes the block's randomness seed with the mint inputs and the entropy
+        /// sources listed in the `dna` module, so it changes from mint to mint.
+        pub(crate) fn mint_dna(
+            owner: &T::AccountId,
+            pet_id: PetId,
+            species: &BoundedVec<u8, T::MaxSpeciesNameLen>,
+            name: &BoundedVec<u8, T::MaxPetNameLen>,
+        ) -> DnaHashType {
+            dna::DnaSystem::<T>::mint_dna(owner, pet_id, species, name)
+        }
+        
+        /// Previews what a mint by `owner` in the current block would produce, in the configured
+        /// `MintPreviewMode`. Backs the `mint_preview` runtime API.
+        pub fn mint_preview(
+            owner: &T::AccountId,
+            species: Vec<u8>,
+            name: Vec<u8>,
+        ) -> Option<mint_preview::MintPreviewDistributionOf<T>> {
+            mint_preview::MintPreviewSystem::<T>::preview(owner, species, name)
+        }
+        
+        /// Assembles the requested `DOSSIER_*` sections of a pet's dossier, or `None` if the pet does
+        /// not exist. Backs the `pet_dossier` runtime API.
+        pub fn pet_dossier(pet_id: PetId, sections: u32) -> Option<dossier::PetDossierOf<T>> {
+            dossier::DossierSystem::<T>::dossier(pet_id, sections)
+        }
+        
+        /// Gets a pet's UI profile, or `None` if the pet does not exist.
+        /// Backs the `get_pet_profile` runtime API.
+        pub fn get_pet_profile(pet_id: PetId) -> Option<ui_bridge::UiPetProfileOf<T>> {
+            ui_bridge::UiBridge::<T>::get_pet_profile(pet_id).ok()
+        }
+        
+        /// Lists the pets `owner` owns. Backs the `get_owned_pets` runtime API.
+        pub fn get_owned_pets(owner: &T::AccountId) -> Vec<PetId> {
+            OwnerOfPet::<T>::get(owner).into_inner()
+        }
+        
+        /// Gets a pet's level, mood, species and the block of its last state change, or `None`
+        /// if the pet does not exist. Backs the `get_pet_summary` runtime API.
+        pub fn get_pet_summary(pet_id: PetId) -> Option<(u32, u8, Vec<u8>, BlockNumberFor<T>)> {
+            PetNfts::<T>::get(pet_id).map(|pet| {
+                (pet.level, pet.mood_indicator, pet.initial_species.into_inner(), pet.last_state_update_block)
+            })
+        }
+        
+        /// Lists the calls counted into each deprecated (interface, method).
+        /// Backs the `deprecated_call_counts` runtime API.
+        pub fn deprecated_call_counts() -> Vec<(u8, u8, u64)> {
+            deprecation::DeprecationTracker::<T>::counts()
+        }
+        
+        /// Locks a pet on behalf of `locker`, which becomes the only one able to unlock it.
+        /// Shared by `SharedNftManager::lock_nft` and this pallet's own locks (e.g., trades).
+        pub(crate) fn do_lock_nft(owner: &T::AccountId, pet_id: &PetId, locker: LockerId) -> DispatchResult {
+            // 1. Verify the `owner` is the actual owner of the `pet_id`.
+            let current_owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(current_owner == *owner, Error::<T>::NotOwner);
+
+            // 2. Ensure the NFT is not already locked.
+            ensure!(!LockedNfts::<T>::contains_key(pet_id), Error::<T>::NftAlreadyLocked);
+
+            // 3. Settle rested XP up to now, then add the `pet_id` to the `LockedNfts` storage.
+            // A locked pet accrues no rested XP, and loses its transfer approval.
+            rested_xp::RestedXpSystem::<T>::settle(*pet_id);
+            LockedNfts::<T>::insert(pet_id, LockInfo { locker, locked_at: frame_system::Pallet::<T>::block_number() });
+            approvals::ApprovalSystem::<T>::clear(*pet_id);
+            Self::deposit_event(Event::NftLocked { owner: owner.clone(), pet_id: *pet_id }); // Emit event
+            Ok(())
+        }
+        
+        /// Unlocks a pet, failing with `NotLockHolder` unless `locker` is the one that locked it.
+        /// Shared by `SharedNftManager::unlock_nft` and this pallet's own locks (e.g., trades).
+        pub(crate) fn do_unlock_nft(owner: &T::AccountId, pet_id: &PetId, locker: LockerId) -> DispatchResult {
+            // 1. Verify the `owner` is the actual owner of the `pet_id`.
+            let current_owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(current_owner == *owner, Error::<T>::NotOwner);
+
+            // 2. Ensure the NFT is currently locked, by this locker.
+            let lock = LockedNfts::<T>::get(pet_id).ok_or(Error::<T>::NftNotLocked)?;
+            ensure!(lock.locker == locker, Error::<T>::NotLockHolder);
+
+            // 3. Settle rested XP (nothing accrues for the locked span), then remove the `pet_id`
+            // from the `LockedNfts` storage.
+            rested_xp::RestedXpSystem::<T>::settle(*pet_id);
+            LockedNfts::<T>::remove(pet_id);
+            Self::deposit_event(Event::NftUnlocked { owner: owner.clone(), pet_id: *pet_id }); // Emit event
+            Ok(())
+        }
+        
+        /// Moves a pet between accounts without checking locks; the caller handles them.
+        /// Shared by `SharedNftManager::transfer_nft` and this pallet's own transfers (e.g., trades).
+        pub(crate) fn do_transfer_nft(from: &T::AccountId, to: &T::AccountId, pet_id: &PetId) -> DispatchResult {
+            // 1. Verify 'from' is the current owner.
+            let current_owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(current_owner == *from, Error::<T>::NotOwner);
+
+            // 2. Check recipient capacity (important for inter-pallet transfers).
+            let recipient_pets_count = OwnerOfPet::<T>::get(to).len();
+            ensure!(recipient_pets_count < T::MaxOwnedPets::get() as usize, Error::<T>::RecipientExceedMaxOwnedPets);
+
+            // 3. Mutate ownership records atomically.
+            // Remove pet_id from sender's owned list.
+            OwnerOfPet::<T>::try_mutate(from, |sender_owned_pets| -> DispatchResult {
+                if let Some(index) = sender_owned_pets.iter().position(|id| *id == *pet_id) {
+                    sender_owned_pets.swap_remove(index);
+                    Ok(())
+                } else {
+                    // This indicates an internal inconsistency if owner check passed but pet not in list.
+                    log::error!(
+                        target: "runtime::critter_nfts_pallet",
+                        "Inconsistency: Pet {} owned by {:?} but not in OwnerOfPet list for transfer.",
+                        pet_id,
+                        from
+                    );
+                    Err(Error::<T>::PetNotFound.into()) // Return a consistent error, or panic in debug builds.
+                }
+            })?;
+
+            // Add pet_id to recipient's owned list.
+            OwnerOfPet::<T>::try_mutate(to, |recipient_owned_pets| -> DispatchResult {
+                recipient_owned_pets.try_push(*pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
+            })?;
+
+            // 4. Update the direct owner mapping and the pet's embedded owner.
+            Self::set_pet_owner(*pet_id, to);
+
+            // Note: No event is emitted here by default for inter-pallet transfers via trait.
+            // The calling pallet (e.g., marketplace) is responsible for emitting its own relevant event (e.g., NftSold).
+            // The user-facing `transfer_pet_nft` extrinsic in this pallet *does* emit `PetNftTransferred`.
+            Ok(())
+        }
+        
+        /// The weight of a batch mint that minted `minted` pets; a batch rejected by validation
+        /// mints none and pays only the benchmark's base weight.
+        pub fn batch_mint_weight(minted: u32) -> Weight {
+            T::WeightInfo::batch_mint_pet_nfts(minted)
+        }
+        
+        /// Validates `batch_mint_pet_nfts` inputs for `owner` without changing state.
+        /// Backs both the extrinsic and the `validate_batch_mint` runtime API.
+        /// 
+        /// # Parameters
+        /// 
+        /// * `owner` - The account that would receive the pets
+        /// * `pets` - The (species, name) pairs to validate
+        /// 
+        /// # Returns
+        /// 
+        /// * `Vec<BatchMintValidation>` - One result per entry, in input order
+        pub fn validate_batch_mint(owner: &T::AccountId, pets: &[(Vec<u8>, Vec<u8>)]) -> Vec<BatchMintValidation> {
+            let owned_count = OwnerOfPet::<T>::get(owner).len();
+            Self::validate_batch_mint_entries(owned_count, pets)
+        }
+        
+        /// Pure validation of batch mint entries given the owner's current pet count.
+        pub fn validate_batch_mint_entries(owned_count: usize, pets: &[(Vec<u8>, Vec<u8>)]) -> Vec<BatchMintValidation> {
+            let max_owned = T::MaxOwnedPets::get() as usize;
             
-            Ok(())
+            pets.iter()
+                .enumerate()
+                .map(|(index, (species, name))| {
+                    if species.len() > T::MaxSpeciesNameLen::get() as usize {
+                        BatchMintValidation::SpeciesNameTooLong
+                    } else if name.len() > T::MaxPetNameLen::get() as usize {
+                        BatchMintValidation::PetNameTooLong
+                    } else if owned_count.saturating_add(index) >= max_owned {
+                        BatchMintValidation::WouldExceedCapacity(index as u32)
+                    } else {
+                        BatchMintValidation::Ok
+                    }
+                })
+                .collect()
         }
         
-        /// Marks a notification as read.
-        #[pallet::call_index(39)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
-        pub fn mark_notification_as_read(
-            origin: OriginFor<T>,
-            notification_id: u32,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            
-            // Mark the notification as read
-            user_experience::UserExperienceSystem::<T>::mark_notification_as_read(
-                sender.clone(),
-                notification_id,
-            )?;
-            
-            // Emit event
-            Self::deposit_event(Event::NotificationRead {
-                account_id: sender,
-                notification_id,
-                timestamp: frame_system::Pallet::<T>::block_number(),
-            });
-            
-            Ok(())
+        /// Maps a batch mint validation result to the error the extrinsic fails with.
+        fn batch_mint_validation_error(result: BatchMintValidation) -> Option<Error<T>> {
+            match result {
+                BatchMintValidation::Ok => None,
+                BatchMintValidation::SpeciesNameTooLong => Some(Error::<T>::SpeciesNameTooLong),
+                BatchMintValidation::PetNameTooLong => Some(Error::<T>::PetNameTooLong),
+                BatchMintValidation::WouldExceedCapacity(_) => Some(Error::<T>::ExceedMaxOwnedPets),
+            }
         }
         
-        /// Adds a notification for a user.
-        #[pallet::call_index(40)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(1).writes(1), 0))]
-        pub fn add_user_notification(
-            origin: OriginFor<T>,
-            target_account: T::AccountId,
-            notification_type: u8,
-            title: Vec<u8>,
-            message: Vec<u8>,
-            icon: Vec<u8>,
-            priority: u8,
-            action: Vec<u8>,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
+        /// Pays `amount` from the lifecycle reward pot to `recipient`.
+        /// Fails without moving funds if the pot cannot cover the amount.
+        pub(crate) fn pay_from_lifecycle_pot(recipient: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let pot = T::LifecycleRewardPot::get();
+            ensure!(T::Currency::free_balance(&pot) >= amount, Error::<T>::LifecycleRewardPotInsufficient);
+            T::Currency::transfer(&pot, recipient, amount, ExistenceRequirement::AllowDeath)
+                .map_err(|_| Error::<T>::LifecycleRewardPotInsufficient.into())
+        }
+        
+        /// Removes a pet's bio, bumps its state version and emits `PetBioCleared`.
+        fn do_clear_pet_bio(pet_id: PetId, by_moderation: bool) -> DispatchResult {
+            ensure!(PetBios::<T>::contains_key(pet_id), Error::<T>::PetBioNotFound);
             
-            // Only allow certain accounts to add notifications for others
-            // For now, we'll allow any account to add notifications
-            // In a production environment, this should be restricted
+            PetBios::<T>::remove(pet_id);
+            let (version, timestamp) = Self::record_profile_change(pet_id)?;
+            Self::note_display_changed(pet_id);
             
-            // Add the notification
-            let _ = Self::add_notification(
-                target_account,
-                notification_type,
-                title,
-                message,
-                icon,
-                priority,
-                action,
-            )?;
+            Self::deposit_event(Event::PetBioCleared {
+                pet_id,
+                by_moderation,
+                version,
+                timestamp,
+            });
             
             Ok(())
         }
         
-        /// Updates achievement progress for a user.
-        #[pallet::call_index(41)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(2).writes(2), 0))]
-        pub fn update_user_achievement(
-            origin: OriginFor<T>,
-            achievement_id: u32,
-            progress: u8,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            
-            // Update the achievement progress
-            let _ = Self::update_achievement_progress(
-                sender,
-                achievement_id,
-                progress,
-            )?;
-            
+        /// Checks the optimistic-concurrency version an owner-facing call was given against the
+        /// pet's state version. `None` skips the check, so the call overwrites (last write wins).
+        fn ensure_expected_version(pet_id: PetId, expected_version: Option<u32>) -> DispatchResult {
+            if let Some(expected_version) = expected_version {
+                ensure!(PetStateVersions::<T>::get(pet_id) == expected_version, Error::<T>::ConcurrentModification);
+            }
             Ok(())
         }
         
-        /// Gets a UI-friendly pet profile.
-        #[pallet::call_index(42)]
-        #[pallet::weight(Weight::from_parts(T::DbWeight::get().reads(10).writes(0), 0))]
-        pub fn get_pet_profile(
-            origin: OriginFor<T>,
-            pet_id: PetId,
-        ) -> DispatchResultWithPostInfo {
-            let _ = ensure_signed(origin)?;
+        /// Bumps a pet's state version for a profile change and notifies sync hooks.
+        /// 
+        /// # Returns
+        /// 
+        /// * `Result<(u32, BlockNumberFor<T>), DispatchError>` - The new version and the current block
+        fn record_profile_change(pet_id: PetId) -> Result<(u32, BlockNumberFor<T>), DispatchError> {
+            use crate::sync::{SyncHookManager, StateChangeType};
+            let current_block = frame_system::Pallet::<T>::block_number();
             
-            // Get the pet profile
-            let _ = Self::get_ui_pet_profile(pet_id)?;
+            let new_version = PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> Result<u32, DispatchError> {
+                let pet_nft = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+                pet_nft.state_version = pet_nft.state_version.saturating_add(1);
+                pet_nft.last_state_update_block = current_block;
+                pet_nft.sync_flags.set_dirty(StateChangeType::Profile);
+                Ok(pet_nft.state_version)
+            })?;
+            PetStateVersions::<T>::insert(pet_id, new_version);
             
-            // This is a read-only extrinsic, so we don't need to emit an event
-            // The profile will be returned in the RPC response
+            SyncHookManager::<T>::notify_hooks(
+                pet_id,
+                StateChangeType::Profile,
+                new_version,
+                current_block,
+                None,
+            ).map_err(|_| Error::<T>::SynchronizationFailed)?;
             
-            Ok(().into())
+            Ok((new_version, current_block))
         }
-    }
-
-    // --- Pallet Internal Helper Functions ---
-    // These functions are not directly callable as extrinsics but are used internally by the pallet.
-    impl<T: Config> Pallet<T> {
-        /// Internal helper to handle pet level ups based on experience points.
-        /// This is called after interactions that grant XP.
-        fn attempt_level_up(pet: &mut PetNft<T>) -> DispatchResult {
-            // 1. Define XP needed for the next level (example: 100 XP per level).
-            // This calculation could be made more complex using T::Config constants for a curve.
-            let xp_needed_for_next_level = 100u32.saturating_mul(pet.level);
-
-            // 2. Check if pet has enough XP to level up.
-            if pet.experience_points >= xp_needed_for_next_level && xp_needed_for_next_level > 0 { // Ensure XP needed is positive
-                // 3. Increment level.
-                pet.level = pet.level.saturating_add(1);
-                // 4. Deduct XP used for leveling (carry over excess XP).
-                pet.experience_points = pet.experience_points.saturating_sub(xp_needed_for_next_level);
-
-                // 5. Emit event for transparency and off-chain indexing.
-                Self::deposit_event(Event::PetLeveledUp { pet_id: pet.id, new_level: pet.level });
-            }
-            Ok(())
+        
+        /// Bumps a pet's display version and emits `PetDisplayChanged` with its current name hash.
+        /// Called whenever the pet's name, visual theme or bio changes.
+        pub(crate) fn note_display_changed(pet_id: PetId) {
+            PetDisplayVersions::<T>::mutate(pet_id, |version| *version = version.saturating_add(1));
+            let name_hash = PetNfts::<T>::get(pet_id)
+                .map(|pet| sp_io::hashing::blake2_256(&pet.current_pet_name))
+                .unwrap_or_default();
+            Self::deposit_event(Event::PetDisplayChanged { pet_id, name_hash });
         }
-
-        /// Helper function to get a UI-friendly pet profile.
-        /// 
-        /// # Parameters
-        /// 
-        /// * `pet_id` - The ID of the pet
-        /// 
-        /// # Returns
-        /// 
-        /// * `Result<ui_bridge::UiPetProfile<T>, DispatchError>` - The pet profile, or an error
-        pub fn get_ui_pet_profile(pet_id: PetId) -> Result<ui_bridge::UiPetProfile<T>, DispatchError> {
-            ui_bridge::UiBridge::<T>::get_pet_profile(pet_id)
+        
+        /// Checks that a bio is UTF-8 without control characters (newlines and tabs allowed),
+        /// so it can be rendered safely as markdown on the frontend.
+        fn is_valid_bio(bio: &[u8]) -> bool {
+            match sp_std::str::from_utf8(bio) {
+                Ok(text) => !text.chars().any(|c| c.is_control() && c != '\n' && c != '\t'),
+                Err(_) => false,
+            }
         }
         
         // --- Shared NFT Manager Trait Helper ---
@@ -2894,6 +6331,29 @@ pub mod pallet {
         fn is_transferable(pet_id: &PetId) -> bool {
             !LockedNfts::<T>::contains_key(pet_id)
         }
+        
+        /// Returns how many more pets an account can receive before reaching `MaxOwnedPets`.
+        /// Parked pets do not count until they are claimed.
+        pub fn remaining_pet_capacity(account: &T::AccountId) -> u32 {
+            T::MaxOwnedPets::get().saturating_sub(OwnerOfPet::<T>::decode_len(account).unwrap_or(0) as u32)
+        }
+        
+        /// Returns a pet's owner and whether it can be listed, with the reason when it cannot.
+        /// Restrictions reported by `TransferRestrictions` take precedence over a plain lock, since
+        /// they say why the pet is locked, and a lock over the transfer gates, which lift on their
+        /// own. Returns `None` if the pet does not exist.
+        pub fn listing_eligibility(pet_id: PetId) -> Option<ListingEligibility<T::AccountId>> {
+            let pet = Self::pet_nfts(pet_id)?;
+            let reason = T::TransferRestrictions::transfer_restriction(&pet_id)
+                .or_else(|| LockedNfts::<T>::contains_key(pet_id).then_some(TransferBlockReason::Locked))
+                .or_else(|| transfer_gates::TransferGateSystem::<T>::gate(&pet));
+            
+            Some(ListingEligibility {
+                owner: pet.owner,
+                transferable: reason.is_none(),
+                reason,
+            })
+        }
     }
 }
 
@@ -2903,7 +6363,7 @@ pub mod pallet {
 
 // Implementation of the unified `NftManagement` trait from crittercraft-traits
 // This provides a standardized interface for all NFT operations across the ecosystem
-use crittercraft_traits::{nft::NftManagement, types::{PetStats, DnaHash}};
+use crittercraft_traits::{nft::NftManagement, types::{ListingEligibility, PetStats, DnaHash}};
 
 impl<T: Config> NftManagement<crittercraft_traits::Config> for Pallet<T> 
 where
@@ -2917,6 +6377,7 @@ where
     }
 
     /// Transfer a pet NFT from one account to another.
+    /// Like `SharedNftManager::transfer_nft`, this delivers a sale and skips the transfer gates.
     fn transfer(
         from: &<crittercraft_traits::Config as crittercraft_traits::Config>::AccountId, 
         to: &<crittercraft_traits::Config as crittercraft_traits::Config>::AccountId, 
@@ -2957,8 +6418,8 @@ where
             recipient_owned_pets.try_push(local_pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
         })?;
 
-        // 4. Update the direct owner mapping for the pet.
-        PetNftOwner::<T>::insert(&local_pet_id, local_to.clone());
+        // 4. Update the direct owner mapping and the pet's embedded owner.
+        Self::set_pet_owner(local_pet_id, &local_to);
 
         // 5. Emit event for transparency and off-chain indexing.
         Self::deposit_event(Event::PetNftTransferred { 
@@ -2980,18 +6441,31 @@ where
     fn pet_stats(pet_id: &<crittercraft_traits::Config as crittercraft_traits::Config>::PetId) -> Option<PetStats> {
         let local_pet_id: PetId = (*pet_id).into();
         Self::pet_nfts(&local_pet_id).map(|pet| {
+            let stats = effective_stats::EffectiveStatsSystem::<T>::of(&pet);
             PetStats {
                 level: pet.level as u16,
                 experience: pet.experience_points,
-                strength: pet.base_strength,
-                agility: pet.base_agility,
-                intelligence: pet.base_intelligence,
+                strength: stats.strength,
+                agility: stats.agility,
+                intelligence: stats.intelligence,
                 charisma: 0, // Not tracked in our current implementation
-                stamina: pet.base_vitality, // Using vitality as stamina
+                stamina: stats.vitality, // Using vitality as stamina
             }
         })
     }
 
+    /// Get the owner of a pet and whether it can be listed, with the reason when it cannot.
+    fn listing_eligibility(
+        pet_id: &<crittercraft_traits::Config as crittercraft_traits::Config>::PetId
+    ) -> Option<ListingEligibility<<crittercraft_traits::Config as crittercraft_traits::Config>::AccountId>> {
+        let local_pet_id: PetId = (*pet_id).into();
+        Self::listing_eligibility(local_pet_id).map(|eligibility| ListingEligibility {
+            owner: eligibility.owner.into(),
+            transferable: eligibility.transferable,
+            reason: eligibility.reason,
+        })
+    }
+
     /// Mint a new pet NFT and assign it to an owner.
     fn mint(
         owner: &<crittercraft_traits::Config as crittercraft_traits::Config>::AccountId, 
@@ -3038,9 +6512,19 @@ where
             last_played_block: current_block_number,
             personality_traits: Default::default(), // Start empty
             last_state_update_block: current_block_number,
+            bonus_strength: 0,
+            bonus_agility: 0,
+            bonus_intelligence: 0,
+            bonus_vitality: 0,
+            owner: local_owner.clone(),
+            generation: 0,
+            acquired_at_block: current_block_number,
+            parent1_id: None,
+            parent2_id: None,
         };
 
         // 5. Storage Operations: Insert Pet NFT and update ownership.
+        Self::note_minted(pet_id, &local_owner, &new_pet.initial_species);
         PetNfts::<T>::insert(pet_id, new_pet);
         OwnerOfPet::<T>::try_mutate(&local_owner, |owned_pets_vec| {
             owned_pets_vec.try_push(pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
@@ -3048,7 +6532,7 @@ where
         PetNftOwner::<T>::insert(pet_id, local_owner.clone());
 
         // 6. Emit event for transparency and off-chain indexing.
-        Self::deposit_event(Event::PetNftMinted { owner: local_owner, pet_id });
+        Self::deposit_event(Event::PetNftMinted { owner: local_owner, pet_id, unix_time_ms: Self::unix_time_ms() });
 
         Ok(pet_id.into())
     }
@@ -3061,91 +6545,70 @@ impl<T: Config> SharedNftManager<T::AccountId, PetId> for Pallet<T> {
     /// Get the owner of an NFT.
     /// Used by other pallets (e.g., marketplace) to verify ownership.
     fn owner_of(pet_id: &PetId) -> Option<T::AccountId> {
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_OWNER_OF);
         Self::pet_nft_owner(pet_id)
     }
 
     /// Check if an NFT is transferable (i.e., not locked).
     /// Used by other pallets to verify if a pet can be moved.
     fn is_transferable(pet_id: &PetId) -> bool {
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_IS_TRANSFERABLE);
         // Delegates to the internal helper function to avoid code duplication.
         Self::is_transferable(pet_id)
     }
 
     /// Lock an NFT, preventing transfers (e.g., when listed on marketplace or in battle).
     /// This is crucial for maintaining state synchronization across the ecosystem.
-    fn lock_nft(owner: &T::AccountId, pet_id: &PetId) -> DispatchResult {
-        // 1. Verify the `owner` is the actual owner of the `pet_id`.
-        let current_owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
-        ensure!(current_owner == *owner, Error::<T>::NotOwner);
-
-        // 2. Ensure the NFT is not already locked.
-        ensure!(!LockedNfts::<T>::contains_key(pet_id), Error::<T>::NftAlreadyLocked);
-
-        // 3. Add the `pet_id` to the `LockedNfts` storage.
-        LockedNfts::<T>::insert(pet_id, ());
-        Self::deposit_event(Event::NftLocked { owner: owner.clone(), pet_id: *pet_id }); // Emit event
-        Ok(())
+    /// `locker` is recorded as the lock holder; only it can unlock the NFT again.
+    /// A marketplace lock lists the pet, so the pet must be past the anti-flip gates.
+    fn lock_nft(owner: &T::AccountId, pet_id: &PetId, locker: LockerId) -> DispatchResult {
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_LOCK_NFT);
+        if locker == LockerId::Marketplace {
+            transfer_gates::TransferGateSystem::<T>::ensure_passes(*pet_id)?;
+        }
+        Self::do_lock_nft(owner, pet_id, locker)
     }
 
     /// Unlock an NFT, allowing transfers.
     /// This is called when an NFT is no longer actively participating in an exclusive state.
-    fn unlock_nft(owner: &T::AccountId, pet_id: &PetId) -> DispatchResult {
-        // 1. Verify the `owner` is the actual owner of the `pet_id`.
-        let current_owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
-        ensure!(current_owner == *owner, Error::<T>::NotOwner);
-
-        // 2. Ensure the NFT is currently locked.
-        ensure!(LockedNfts::<T>::contains_key(pet_id), Error::<T>::NftNotLocked);
-
-        // 3. Remove the `pet_id` from the `LockedNfts` storage.
-        LockedNfts::<T>::remove(pet_id);
-        Self::deposit_event(Event::NftUnlocked { owner: owner.clone(), pet_id: *pet_id }); // Emit event
-        Ok(())
+    /// Fails with `NotLockHolder` unless `locker` is the pallet that locked it.
+    fn unlock_nft(owner: &T::AccountId, pet_id: &PetId, locker: LockerId) -> DispatchResult {
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_UNLOCK_NFT);
+        Self::do_unlock_nft(owner, pet_id, locker)
     }
 
     /// Transfer an NFT from one account to another.
     /// Note: This is a direct transfer, typically called by another pallet (e.g., marketplace after a sale).
     /// It assumes any necessary lock/unlock logic specific to the calling context (like marketplace listing)
-    /// has been handled by the caller. This function itself does not check `is_transferable`.
+    /// has been handled by the caller. This function itself does not check `is_transferable`, nor the
+    /// transfer gates, which the pet passed when it was locked for listing.
+    /// `from` may also be the account approved for the pet or an operator of its owner, in which
+    /// case the pet moves from its owner.
     fn transfer_nft(from: &T::AccountId, to: &T::AccountId, pet_id: &PetId) -> DispatchResult { // DispatchResultType is DispatchResult
-        // 1. Verify 'from' is the current owner.
-        let current_owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
-        ensure!(current_owner == *from, Error::<T>::NotOwner);
-
-        // 2. Check recipient capacity (important for inter-pallet transfers).
-        let recipient_pets_count = OwnerOfPet::<T>::get(to).len();
-        ensure!(recipient_pets_count < T::MaxOwnedPets::get() as usize, Error::<T>::RecipientExceedMaxOwnedPets);
-
-        // 3. Mutate ownership records atomically.
-        // Remove pet_id from sender's owned list.
-        OwnerOfPet::<T>::try_mutate(from, |sender_owned_pets| -> DispatchResult {
-            if let Some(index) = sender_owned_pets.iter().position(|id| *id == *pet_id) {
-                sender_owned_pets.swap_remove(index);
-                Ok(())
-            } else {
-                // This indicates an internal inconsistency if owner check passed but pet not in list.
-                log::error!(
-                    target: "runtime::critter_nfts_pallet",
-                    "Inconsistency: Pet {} owned by {:?} but not in OwnerOfPet list for transfer.",
-                    pet_id,
-                    from
-                );
-                Err(Error::<T>::PetNotFound.into()) // Return a consistent error, or panic in debug builds.
-            }
-        })?;
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_TRANSFER_NFT);
+        let owner = Self::pet_nft_owner(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        ensure!(approvals::ApprovalSystem::<T>::can_transfer(from, &owner, *pet_id), Error::<T>::NotOwner);
+        Self::do_transfer_nft(&owner, to, pet_id)
+    }
 
-        // Add pet_id to recipient's owned list.
-        OwnerOfPet::<T>::try_mutate(to, |recipient_owned_pets| -> DispatchResult {
-            recipient_owned_pets.try_push(*pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
-        })?;
+    /// Check if an account has room for another pet.
+    /// Lets calling pallets (e.g., breeding) validate before spending their own resources.
+    fn can_receive_pet(account: &T::AccountId) -> bool {
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_CAN_RECEIVE_PET);
+        Self::remaining_pet_capacity(account) > 0
+    }
 
-        // 4. Update the direct owner mapping for the pet.
-        PetNftOwner::<T>::insert(pet_id, to.clone());
+    /// Get how many more pets an account can receive before reaching `MaxOwnedPets`.
+    fn remaining_capacity(account: &T::AccountId) -> u32 {
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_SHARED_NFT_MANAGER, deprecation::METHOD_REMAINING_CAPACITY);
+        Self::remaining_pet_capacity(account)
+    }
+}
 
-        // Note: No event is emitted here by default for inter-pallet transfers via trait.
-        // The calling pallet (e.g., marketplace) is responsible for emitting its own relevant event (e.g., NftSold).
-        // The user-facing `transfer_pet_nft` extrinsic in this pallet *does* emit `PetNftTransferred`.
-        Ok(())
+impl<T: Config> crittercraft_traits::nft::PetMinterProvider<T::AccountId, PetId> for Pallet<T> {
+    /// Get the account a pet was minted to, if it was recorded.
+    fn minter_of(pet_id: &PetId) -> Option<T::AccountId> {
+        Self::pet_minter(pet_id)
     }
 }
 
@@ -3171,8 +6634,7 @@ impl<T: Config> NftManagerForItems<T::AccountId, PetId, TraitTypeString, BlockNu
         PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| -> DispatchResult {
             let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
 
-            pet.experience_points = pet.experience_points.saturating_add(amount);
-            Self::attempt_level_up(pet)?; // Call internal helper to check for level up.
+            Self::grant_xp(pet, amount)?; // Shared grant path: rested bonus and level ups.
 
             pet.last_state_update_block = frame_system::Pallet::<T>::block_number();
             // Note: Event::PetNftMetadataUpdated or a more specific XP event could be emitted here or in attempt_level_up.
@@ -3204,7 +6666,8 @@ impl<T: Config> NftManagerForItems<T::AccountId, PetId, TraitTypeString, BlockNu
     }
 
     /// Grant a new personality trait to a pet.
-    /// Ensures trait string is bounded and not a duplicate.
+    /// Ensures trait string is bounded and not a duplicate. The trait is system-sourced, so the
+    /// owner cannot remove it through `update_pet_metadata`.
     fn apply_personality_trait_to_pet( // Renamed in trait
         caller: &T::AccountId,
         pet_id: &PetId,
@@ -3215,20 +6678,8 @@ impl<T: Config> NftManagerForItems<T::AccountId, PetId, TraitTypeString, BlockNu
         PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| -> DispatchResult {
             let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
 
-            // Check if pet already has this trait.
-            if !pet.personality_traits.iter().any(|existing_trait| existing_trait == &trait_to_grant) {
-                // Try to push new trait, mapping potential PushError to TooManyPersonalityTraits.
-                pet.personality_traits.try_push(trait_to_grant)
-                    .map_err(|_| Error::<T>::TooManyPersonalityTraits)?; // Error if max traits reached.
-            } else {
-                // Trait already exists, so no change needed. This is an Ok() case.
-                log::info!(
-                    target: "runtime::critter_nfts_pallet",
-                    "Trait already exists for pet {}: {:?}",
-                    pet_id,
-                    trait_to_grant
-                );
-            }
+            // Add the trait if the pet does not have it, and mark it as system-granted either way.
+            trait_provenance::TraitProvenanceSystem::<T>::grant_system_trait(pet, trait_to_grant)?;
 
             pet.last_state_update_block = frame_system::Pallet::<T>::block_number();
             // Consider emitting PetNftMetadataUpdated or PetPersonalityTraitAdded event.
@@ -3236,6 +6687,21 @@ impl<T: Config> NftManagerForItems<T::AccountId, PetId, TraitTypeString, BlockNu
         })
     }
 
+    /// Grant a personality trait to a pet for `duration_blocks` blocks.
+    /// The trait is kept apart from the permanent traits and does not take one of their slots.
+    /// Granting a trait the pet already holds temporarily refreshes its expiry.
+    fn apply_temporary_trait(
+        caller: &T::AccountId,
+        pet_id: &PetId,
+        trait_to_grant: TraitTypeString,
+        duration_blocks: BlockNumberFor<T>,
+    ) -> DispatchResult {
+        ensure!(Self::pet_nft_owner(pet_id) == Some(caller.clone()), Error::<T>::NotOwner);
+        ensure!(PetNfts::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+
+        temporary_traits::TemporaryTraitSystem::<T>::grant(*pet_id, trait_to_grant, duration_blocks)
+    }
+
     /// Apply a generic breeding-assist effect to a pet.
     /// This can influence breeding-specific fields or call `pallet-breeding`.
     fn apply_breeding_assist_effect( // Renamed in trait
@@ -3264,6 +6730,13 @@ impl<T: Config> NftManagerForItems<T::AccountId, PetId, TraitTypeString, BlockNu
     }
 }
 
+// Implementation of the read-only valuation trait used by the marketplace for price hints
+impl<T: Config> crittercraft_traits::valuation::PetValuation<PetId> for Pallet<T> {
+    fn quality_score(pet_id: &PetId) -> Option<u32> {
+        valuation::PetValuationSystem::<T>::quality_score(*pet_id)
+    }
+}
+
 // Implementation of NftBreedingHandler trait (now defined in `crate::traits`)
 impl<T: Config> NftBreedingHandler<T::AccountId, PetId, DnaHashType, SpeciesType> for Pallet<T> {
     /// Get basic genetic information for a pet, used by `pallet-breeding`.
@@ -3272,6 +6745,7 @@ impl<T: Config> NftBreedingHandler<T::AccountId, PetId, DnaHashType, SpeciesType
             Some(SimpleGeneticInfo {
                 dna_hash: pet_nft.dna_hash,
                 species: pet_nft.initial_species.clone(), // Assuming initial_species is BoundedVec
+                generation: pet_nft.generation,
             })
         } else {
             None
@@ -3279,22 +6753,31 @@ impl<T: Config> NftBreedingHandler<T::AccountId, PetId, DnaHashType, SpeciesType
     }
 
     /// Mint a new pet from a breeding outcome, called by `pallet-breeding`.
-    /// This method ensures secure, synchronized creation of new pets based on derived DNA.
+    /// The offspring's DNA is a crossover of its parents' DNA, computed here (see the
+    /// `breeding_genetics` module) so callers cannot choose it.
     fn mint_pet_from_breeding(
         owner: &T::AccountId,
         species: SpeciesType, // BoundedVec from Breeding pallet
-        dna_hash: DnaHashType, // [u8; 32] from Breeding pallet
         parent1_id: PetId,
         parent2_id: PetId,
         initial_name: BoundedVec<u8, T::MaxPetNameLen>, // BoundedVec for name
     ) -> Result<PetId, DispatchResult> {
-        // This logic is similar to `mint_pet_nft` but uses provided DNA and species.
+        deprecation::DeprecationTracker::<T>::record(deprecation::INTERFACE_NFT_BREEDING_HANDLER, deprecation::METHOD_MINT_PET_FROM_BREEDING);
 
-        // 1. Check maximum owned pets for owner.
+        // This logic is similar to `mint_pet_nft` but uses provided species and crossed-over DNA.
+
+        // 1. Check maximum owned pets for owner. With `OverflowPolicy::Park`, a full owner gets
+        //    the pet parked instead, so the breeding pallet's spent resources are not lost. Both
+        //    parents must exist, to cross their DNA over.
+        let park = Self::remaining_pet_capacity(owner) == 0;
         ensure!(
-            OwnerOfPet::<T>::get(owner).len() < T::MaxOwnedPets::get() as usize,
+            !park || T::BreedingOverflowPolicy::get() == pending_claims::OverflowPolicy::Park,
             Error::<T>::ExceedMaxOwnedPets
         );
+        let parent_dna = |parent_id: &PetId| {
+            Self::get_pet_simple_genetics(parent_id).map(|genetics| genetics.dna_hash).ok_or(Error::<T>::PetNotFound)
+        };
+        let parent_dnas = (parent_dna(&parent1_id)?, parent_dna(&parent2_id)?);
 
         // 2. Generate PetId.
         let pet_id = NextPetId::<T>::try_mutate(|next_id| -> Result<PetId, DispatchError> {
@@ -3303,23 +6786,40 @@ impl<T: Config> NftBreedingHandler<T::AccountId, PetId, DnaHashType, SpeciesType
             Ok(current_id)
         })?;
 
-        // 3. Derive Charter Attributes from the given dna_hash (same deterministic logic as in `mint_pet_nft`).
-        let base_strength = (dna_hash[0] % 16) + 5; // 5-20
-        let base_agility = (dna_hash[1] % 16) + 5;
-        let base_intelligence = (dna_hash[2] % 16) + 5;
-        let base_vitality = (dna_hash[3] % 16) + 5;
-        let primary_elemental_affinity = match dna_hash[4] % 8 {
-            0 => ElementType::Fire, 1 => ElementType::Water, 2 => ElementType::Earth,
-            3 => ElementType::Air, 4 => ElementType::Tech, 5 => ElementType::Nature,
-            6 => ElementType::Mystic,
-            _ => ElementType::Neutral,
+        // 3. Cross the parents' DNA over and derive Charter Attributes from the offspring's
+        //    (same deterministic logic as in `mint_pet_nft`).
+        let dna_hash = breeding_genetics::BreedingGeneticsSystem::<T>::offspring_dna(
+            pet_id,
+            (parent1_id, parent2_id),
+            parent_dnas,
+        );
+        let mint_preview::CharterAttributes {
+            strength: base_strength,
+            agility: base_agility,
+            intelligence: base_intelligence,
+            vitality: base_vitality,
+            affinity: primary_elemental_affinity,
+        } = mint_preview::CharterAttributes::from_dna(&dna_hash);
+
+        // 4. Inherit traits from the parents, rolled from the offspring's DNA, and count the
+        //    offspring's generation from theirs.
+        let (parent1, parent2) = (Self::pet_nfts(parent1_id), Self::pet_nfts(parent2_id));
+        let parent_traits = |parent: &Option<PetNft<T>>| {
+            parent.as_ref().map(|parent| parent.personality_traits.to_vec()).unwrap_or_default()
         };
+        let inherited_traits = inheritance::InheritanceSystem::<T>::inherit_traits(
+            &dna_hash,
+            &parent_traits(&parent1),
+            &parent_traits(&parent2),
+        );
+        let parent_generation = |parent: &Option<PetNft<T>>| parent.as_ref().map_or(0, |parent| parent.generation);
+        let generation = lineage::offspring_generation(parent_generation(&parent1), parent_generation(&parent2));
 
-        // 4. Initial Dynamic Attributes (set to defaults).
+        // 5. Initial Dynamic Attributes (set to defaults).
         let current_block_number = frame_system::Pallet::<T>::block_number();
         let new_pet = PetNft {
             id: pet_id,
-            dna_hash, // Use the provided dna_hash
+            dna_hash, // The crossed-over DNA
             initial_species: species.clone(), // Use provided species
             current_pet_name: initial_name, // Use provided initial name
             base_strength,
@@ -3332,20 +6832,60 @@ impl<T: Config> NftBreedingHandler<T::AccountId, PetId, DnaHashType, SpeciesType
             mood_indicator: T::MaxMoodValue::get(),
             last_fed_block: current_block_number,
             last_played_block: current_block_number,
-            personality_traits: Default::default(), // Start empty for newly bred pets
+            personality_traits: inherited_traits.clone(), // Only what was inherited
             last_state_update_block: current_block_number,
-            // TODO: Add parent1_id, parent2_id fields to PetNft struct and set them here (V2+ enhancement)
+            bonus_strength: 0,
+            bonus_agility: 0,
+            bonus_intelligence: 0,
+            bonus_vitality: 0,
+            owner: owner.clone(),
+            generation,
+            acquired_at_block: current_block_number,
+            parent1_id: Some(parent1_id),
+            parent2_id: Some(parent2_id),
         };
 
-        // 5. Storage Operations: Insert Pet NFT and update ownership.
+        let bred = Event::PetNftBred {
+            owner: owner.clone(),
+            pet_id,
+            parents: (parent1_id, parent2_id),
+            inherited_traits,
+        };
+
+        // 6. Park the pet if the owner has no room; it is minted when claimed.
+        if park {
+            pending_claims::PendingClaimSystem::<T>::park(owner, new_pet, (parent1_id, parent2_id))?;
+            Self::deposit_event(bred);
+            return Ok(pet_id);
+        }
+
+        // 7. Storage Operations: Insert Pet NFT and update ownership.
+        Self::note_minted(pet_id, owner, &new_pet.initial_species);
+        let minted = Event::PetNftMinted {
+            owner: owner.clone(),
+            pet_id,
+            species: new_pet.initial_species.clone(),
+            dna_hash,
+            base_strength,
+            base_agility,
+            base_intelligence,
+            base_vitality,
+            elemental_affinity: primary_elemental_affinity,
+            timestamp: current_block_number,
+            unix_time_ms: Self::unix_time_ms(),
+            parents: Some((parent1_id, parent2_id)),
+        };
         PetNfts::<T>::insert(pet_id, new_pet);
         OwnerOfPet::<T>::try_mutate(owner, |owned_pets_vec| {
             owned_pets_vec.try_push(pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
         })?;
         PetNftOwner::<T>::insert(pet_id, owner.clone());
+        lineage::LineageSystem::<T>::record_parents(pet_id, parent1_id, parent2_id);
+        lineage::LineageSystem::<T>::record_genealogy(pet_id, (parent1_id, parent2_id), generation, current_block_number);
 
-        // 6. Emit event.
-        Self::deposit_event(Event::PetNftMinted { owner: owner.clone(), pet_id });
+        // 8. Emit events.
+        Self::deposit_event(minted);
+        Self::deposit_event(bred);
         Ok(pet_id) // Return the ID of the newly minted pet
     }
 }
@@ -3362,8 +6902,165 @@ impl<T: Config> QuestNftRequirementChecker<T::AccountId, PetId, SpeciesType> for
         Self::pet_nfts(pet_id).map(|pet| pet.level)
     }
 
+    /// Get the effective stats of a pet. Used by `pallet-quests` to verify stat requirements.
+    fn get_pet_stats_for_quest(pet_id: &PetId) -> Option<crate::traits::PetStats> {
+        Self::pet_nfts(pet_id).map(|pet| Self::shared_pet_stats(&pet))
+    }
+
     /// Get the species of a pet. (Deferred in trait for MVP).
     /// This method would provide the pet's species for quest requirements.
     fn get_pet_species_for_quest(pet_id: &PetId) -> Option<SpeciesType> {
         Self::pet_nfts(pet_id).map(|pet| pet.initial_species.clone())
     }
+
+    /// Count the care actions of a kind a pet received in the last `window_epochs` epochs.
+    /// Used by `pallet-quests` for quests like "feed your pet 5 times this week".
+    fn care_actions_in_window(pet_id: &PetId, kind: u8, window_epochs: u32) -> u32 {
+        match kinds::CareActionKind::try_from(kind) {
+            Ok(kind) => care_history::CareHistorySystem::<T>::count_in_window(*pet_id, kind, window_epochs),
+            Err(_) => 0,
+        }
+    }
+
+    /// Check whether a pet has a trait, permanent or unexpired temporary.
+    /// Used by `pallet-quests` for quests that require a trait, e.g. "Festive".
+    fn pet_has_trait_for_quest(pet_id: &PetId, trait_name: &[u8]) -> bool {
+        Self::pet_nfts(pet_id)
+            .map(|pet| temporary_traits::TemporaryTraitSystem::<T>::all_traits(&pet)
+                .iter()
+                .any(|trait_string| trait_string.as_slice() == trait_name))
+            .unwrap_or(false)
+    }
+}
+
+// Implementation of the display version view used by pallet-critter-battle's snapshots
+impl<T: Config> crittercraft_traits::nft::PetDisplayVersionProvider<PetId> for Pallet<T> {
+    fn display_version(pet_id: &PetId) -> u32 {
+        PetDisplayVersions::<T>::get(pet_id)
+    }
+}
+
+// Implementation of the canonical elemental affinity view used by pallet-critter-battle's telemetry
+impl<T: Config> crittercraft_traits::nft::PetElementProvider<PetId> for Pallet<T> {
+    fn element_of(pet_id: &PetId) -> Option<u8> {
+        Self::pet_nfts(pet_id).map(|pet| pet.primary_elemental_affinity as u8)
+    }
+}
+
+// Implementation of the existence check other pallets use to notice released pets
+impl<T: Config> crittercraft_traits::nft::ExistenceChecker<PetId> for Pallet<T> {
+    fn pet_exists(pet_id: &PetId) -> bool {
+        PetNfts::<T>::contains_key(pet_id)
+    }
+
+    /// IDs are never reused, so a minted ID without a pet or a parked claim was released or burned.
+    fn was_released(pet_id: &PetId) -> bool {
+        *pet_id < NextPetId::<T>::get()
+            && !PetNfts::<T>::contains_key(pet_id)
+            && !PendingClaims::<T>::contains_key(pet_id)
+    }
+}
+
+// Implementation of the Zoologist score contribution used by pallet-user-profile
+impl<T: Config> pallet_user_profile::ScoreContributor<T::AccountId> for Pallet<T> {
+    /// A point per level and per achievement of each pet the user owns.
+    fn get_score_contribution(user: &T::AccountId) -> u64 {
+        OwnerOfPet::<T>::get(user)
+            .iter()
+            .map(|pet_id| {
+                let level = Self::pet_nfts(pet_id).map_or(0, |pet| pet.level as u64);
+                level.saturating_add(PetAchievements::<T>::get(pet_id).len() as u64)
+            })
+            .fold(0u64, |score, pet_score| score.saturating_add(pet_score))
+    }
+}
+
+// Implementation of the pet view and XP sink used by pallet-critter-battle
+impl<T: Config> crate::traits::AdvancedPetManagement<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+    fn get_enhanced_pet_info(pet_id: &PetId) -> Option<crate::traits::EnhancedPetInfo<T::AccountId, BlockNumberFor<T>>> {
+        let pet = Self::pet_nfts(pet_id)?;
+        Some(crate::traits::EnhancedPetInfo {
+            id: *pet_id,
+            owner: Self::pet_nft_owner(pet_id)?,
+            stats: Self::shared_pet_stats(&pet),
+            mood: pet.mood_indicator,
+            last_interaction: pet.last_state_update_block,
+            is_locked: LockedNfts::<T>::contains_key(pet_id),
+        })
+    }
+
+    /// Charter stats are fixed at minting, so other pallets cannot overwrite them.
+    fn update_pet_stats(pet_id: &PetId, _stats: crate::traits::PetStats) -> Result<(), crate::traits::NftCallError> {
+        ensure!(PetNfts::<T>::contains_key(pet_id), crate::traits::NftCallError::PetNotFound);
+        Err(crate::traits::NftCallError::Failed)
+    }
+
+    /// Grants battle XP through the shared XP path, so rested XP and level-ups apply.
+    fn add_experience(pet_id: &PetId, experience: u32) -> Result<(), crate::traits::NftCallError> {
+        PetNfts::<T>::try_mutate(pet_id, |pet_opt| -> Result<(), crate::traits::NftCallError> {
+            let pet = pet_opt.as_mut().ok_or(crate::traits::NftCallError::PetNotFound)?;
+            Self::grant_xp(pet, experience).map_err(|_| crate::traits::NftCallError::Failed)?;
+            Ok(())
+        })
+    }
+
+    fn get_pet_level(pet_id: &PetId) -> Result<u16, crate::traits::NftCallError> {
+        Self::pet_nfts(pet_id)
+            .map(|pet| pet.level.min(u16::MAX as u32) as u16)
+            .ok_or(crate::traits::NftCallError::PetNotFound)
+    }
+
+    fn get_pet_attributes(pet_id: &PetId) -> Result<sp_std::vec::Vec<(crate::traits::AttributeType, u8)>, crate::traits::NftCallError> {
+        use crate::traits::AttributeType;
+        let pet = Self::pet_nfts(pet_id).ok_or(crate::traits::NftCallError::PetNotFound)?;
+        let stats = effective_stats::EffectiveStatsSystem::<T>::of(&pet);
+        Ok(sp_std::vec![
+            (AttributeType::Strength, stats.strength),
+            (AttributeType::Agility, stats.agility),
+            (AttributeType::Intelligence, stats.intelligence),
+            (AttributeType::Vitality, stats.vitality),
+            (AttributeType::Elemental, pet.primary_elemental_affinity as u8),
+        ])
+    }
+
+    /// Pet state is derived on read, so there is nothing to refresh beyond checking the pet exists.
+    fn update_pet_state(pet_id: &PetId) -> Result<(), crate::traits::NftCallError> {
+        ensure!(PetNfts::<T>::contains_key(pet_id), crate::traits::NftCallError::PetNotFound);
+        Ok(())
+    }
+
+    /// Uses the same threshold as `apply_neglect_check`.
+    fn is_pet_neglected(pet_id: &PetId) -> bool {
+        Self::pet_nfts(pet_id).map_or(false, |pet| {
+            frame_system::Pallet::<T>::block_number().saturating_sub(pet.last_played_block)
+                > Self::params().neglect_threshold_blocks
+        })
+    }
+
+    /// Evolution is driven by this pallet's lifecycle, not by other pallets.
+    fn is_eligible_for_evolution(_pet_id: &PetId) -> bool {
+        false
+    }
+
+    fn evolve_pet(_owner: &T::AccountId, _pet_id: &PetId) -> Result<(), crate::traits::NftCallError> {
+        Err(crate::traits::NftCallError::Failed)
+    }
+}
+
+// Implementation of the pending effects queue used by other pallets for asynchronous effects
+use crittercraft_traits::types::{DeferredEffect, DeferredEffectId};
+use frame_support::dispatch::{DispatchError, DispatchResult};
+
+impl<T: Config> crittercraft_traits::nft::DeferredEffects<PetId> for Pallet<T> {
+    fn defer_effect(pet_id: &PetId, effect: DeferredEffect) -> Result<DeferredEffectId, DispatchError> {
+        pending_effects::PendingEffectsSystem::<T>::defer(*pet_id, effect)
+    }
+
+    fn pending_effect(effect_id: DeferredEffectId) -> Option<(PetId, DeferredEffect)> {
+        pending_effects::PendingEffectsSystem::<T>::pending(effect_id)
+    }
+
+    fn cancel_effect(effect_id: DeferredEffectId) -> DispatchResult {
+        pending_effects::PendingEffectsSystem::<T>::cancel(effect_id)
+    }
+}
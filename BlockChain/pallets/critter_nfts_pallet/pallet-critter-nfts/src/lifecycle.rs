@@ -12,13 +12,15 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use sp_runtime::traits::Zero;
 use crate::{Config, Error, PetId, PetNft};
+use crate::kinds::{LifecycleEventKind, SkillKind};
 
 /// Represents a lifecycle event.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct LifecycleEvent<T: Config> {
     /// The event type
-    pub event_type: u8,
+    pub event_type: LifecycleEventKind,
     
     /// The event name
     pub name: Vec<u8>,
@@ -71,26 +73,72 @@ pub struct LifecycleEventRequirements {
     pub min_stats: Vec<(u8, u8)>, // (stat_type, min_value)
     
     /// The skills required
-    pub required_skills: Vec<(u8, u8)>, // (skill_type, min_level)
+    pub required_skills: Vec<(SkillKind, u8)>, // (skill_type, min_level)
     
     /// The achievements required
     pub required_achievements: Vec<u32>, // achievement_ids
     
     /// The previous lifecycle events required
-    pub prerequisite_events: Vec<u8>, // event_types
+    pub prerequisite_events: Vec<LifecycleEventKind>, // event_types
 }
 
-/// Lifecycle event types.
-pub enum LifecycleEventType {
-    Birth = 0,
-    Adolescence = 1,
-    Adulthood = 2,
-    Mastery = 3,
-    Evolution = 4,
-    Transformation = 5,
-    Awakening = 6,
-    Enlightenment = 7,
-    Rebirth = 8,
+/// Lifecycle event types, now the typed `crate::kinds::LifecycleEventKind`.
+pub type LifecycleEventType = crate::kinds::LifecycleEventKind;
+
+/// Lifecycle stages that grant a one-time reward when first reached, in the order pets reach them.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum LifecycleStage {
+    /// Reached through the Adolescence event.
+    Juvenile,
+    /// Reached through the Adulthood event.
+    Adult,
+    /// Reached through the Mastery event.
+    Elder,
+}
+
+impl LifecycleStage {
+    /// Maps a lifecycle event type to the stage it transitions the pet into, if any.
+    pub fn from_event_type(event_type: LifecycleEventKind) -> Option<Self> {
+        match event_type {
+            LifecycleEventKind::Adolescence => Some(LifecycleStage::Juvenile),
+            LifecycleEventKind::Adulthood => Some(LifecycleStage::Adult),
+            LifecycleEventKind::Mastery => Some(LifecycleStage::Elder),
+            _ => None,
+        }
+    }
+}
+
+/// The one-time reward granted when a pet reaches a lifecycle stage.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub struct StageReward<Balance> {
+    /// Growth added to the pet's `bonus_strength`
+    pub strength: u8,
+    /// Growth added to the pet's `bonus_agility`
+    pub agility: u8,
+    /// Growth added to the pet's `bonus_intelligence`
+    pub intelligence: u8,
+    /// Growth added to the pet's `bonus_vitality`
+    pub vitality: u8,
+    /// PTCN paid to the owner from the lifecycle reward pot
+    pub currency: Balance,
+}
+
+/// Provides the per-stage rewards and stat modifiers configured by the runtime.
+pub trait LifecycleStageRewards<Balance> {
+    /// Returns the reward for reaching `stage`, or `None` if the stage grants nothing.
+    fn stage_reward(stage: LifecycleStage) -> Option<StageReward<Balance>>;
+
+    /// Returns the percentage a pet's effective stats change by while `stage` is its latest
+    /// stage, e.g. -10 for frail elders. Defaults to no change.
+    fn stage_stat_percent(_stage: LifecycleStage) -> i8 {
+        0
+    }
+}
+
+impl<Balance> LifecycleStageRewards<Balance> for () {
+    fn stage_reward(_stage: LifecycleStage) -> Option<StageReward<Balance>> {
+        None
+    }
 }
 
 /// A system for managing pet lifecycle events.
@@ -111,7 +159,7 @@ impl<T: Config> LifecycleEventSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     pub fn trigger_lifecycle_event(
         pet_id: PetId,
-        event_type: u8,
+        event_type: LifecycleEventKind,
     ) -> DispatchResult {
         // Get the pet from storage
         let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
@@ -141,10 +189,16 @@ impl<T: Config> LifecycleEventSystem<T> {
             timestamp: current_block,
         });
         
+        // Grant the stage reward. This runs at most once per stage because the event
+        // has just been recorded in `PetLifecycleEvents` and cannot be triggered again.
+        if let Some(stage) = LifecycleStage::from_event_type(event_type) {
+            Self::grant_stage_reward(pet_id, stage, event_type)?;
+        }
+        
         // Record a memory of the event
         crate::memory::PetMemorySystem::<T>::record_memory(
             pet_id,
-            crate::memory::MemoryType::LifecycleEvent as u8,
+            crate::memory::MemoryType::LifecycleEvent,
             255, // Maximum significance
             event_type.encode(), // Store the event type as associated data
         )?;
@@ -162,7 +216,7 @@ impl<T: Config> LifecycleEventSystem<T> {
     /// 
     /// * `Result<LifecycleEvent<T>, DispatchError>` - The lifecycle event, or an error
     fn get_lifecycle_event(
-        event_type: u8,
+        event_type: LifecycleEventKind,
     ) -> Result<LifecycleEvent<T>, DispatchError> {
         // In a real implementation, this would get the event from storage
         // For now, we'll just return a hardcoded event based on the type
@@ -170,7 +224,7 @@ impl<T: Config> LifecycleEventSystem<T> {
         let current_block = frame_system::Pallet::<T>::block_number();
         
         match event_type {
-            0 => { // Birth
+            LifecycleEventKind::Birth => {
                 Ok(LifecycleEvent {
                     event_type,
                     name: b"Birth".to_vec(),
@@ -199,7 +253,7 @@ impl<T: Config> LifecycleEventSystem<T> {
                     },
                 })
             },
-            1 => { // Adolescence
+            LifecycleEventKind::Adolescence => {
                 Ok(LifecycleEvent {
                     event_type,
                     name: b"Adolescence".to_vec(),
@@ -224,11 +278,11 @@ impl<T: Config> LifecycleEventSystem<T> {
                         min_stats: Vec::new(),
                         required_skills: Vec::new(),
                         required_achievements: Vec::new(),
-                        prerequisite_events: vec![0], // Birth
+                        prerequisite_events: vec![LifecycleEventKind::Birth],
                     },
                 })
             },
-            2 => { // Adulthood
+            LifecycleEventKind::Adulthood => {
                 Ok(LifecycleEvent {
                     event_type,
                     name: b"Adulthood".to_vec(),
@@ -256,9 +310,9 @@ impl<T: Config> LifecycleEventSystem<T> {
                             (2, 50), // Intelligence >= 50
                             (3, 50), // Vitality >= 50
                         ],
-                        required_skills: vec![(0, 5)], // At least one skill at level 5
+                        required_skills: vec![(SkillKind::Hunting, 5)], // Hunting at level 5
                         required_achievements: vec![1], // At least one achievement
-                        prerequisite_events: vec![0, 1], // Birth and Adolescence
+                        prerequisite_events: vec![LifecycleEventKind::Birth, LifecycleEventKind::Adolescence],
                     },
                 })
             },
@@ -330,12 +384,13 @@ impl<T: Config> LifecycleEventSystem<T> {
         );
         
         // Check stat requirements
+        let stats = crate::effective_stats::EffectiveStatsSystem::<T>::of(pet);
         for (stat_type, min_value) in &requirements.min_stats {
             let stat_value = match stat_type {
-                0 => pet.base_strength,
-                1 => pet.base_agility,
-                2 => pet.base_intelligence,
-                3 => pet.base_vitality,
+                0 => stats.strength,
+                1 => stats.agility,
+                2 => stats.intelligence,
+                3 => stats.vitality,
                 _ => 0,
             };
             
@@ -405,7 +460,7 @@ impl<T: Config> LifecycleEventSystem<T> {
     /// * `DispatchResult` - Ok if the pet has not experienced the event, Err otherwise
     fn check_not_already_experienced(
         pet_id: PetId,
-        event_type: u8,
+        event_type: LifecycleEventKind,
     ) -> DispatchResult {
         let events = crate::PetLifecycleEvents::<T>::get(pet_id);
         
@@ -442,20 +497,20 @@ impl<T: Config> LifecycleEventSystem<T> {
                 .saturating_add(effects.mood_boost)
                 .min(T::MaxMoodValue::get());
             
-            // Apply stat boosts
+            // Apply stat boosts to the bonus fields; charter attributes are immutable
             for (stat_type, boost) in &effects.stat_boosts {
                 match stat_type {
                     0 => { // Strength
-                        pet.base_strength = pet.base_strength.saturating_add(*boost);
+                        pet.bonus_strength = pet.bonus_strength.saturating_add(*boost);
                     },
                     1 => { // Agility
-                        pet.base_agility = pet.base_agility.saturating_add(*boost);
+                        pet.bonus_agility = pet.bonus_agility.saturating_add(*boost);
                     },
                     2 => { // Intelligence
-                        pet.base_intelligence = pet.base_intelligence.saturating_add(*boost);
+                        pet.bonus_intelligence = pet.bonus_intelligence.saturating_add(*boost);
                     },
                     3 => { // Vitality
-                        pet.base_vitality = pet.base_vitality.saturating_add(*boost);
+                        pet.bonus_vitality = pet.bonus_vitality.saturating_add(*boost);
                     },
                     _ => {} // No boost for other stats
                 }
@@ -471,8 +526,71 @@ impl<T: Config> LifecycleEventSystem<T> {
         })
     }
     
+    /// Grants the configured one-time reward for reaching a lifecycle stage.
+    /// Stat growth is always applied; if the reward pot cannot cover the PTCN
+    /// reward, it is deferred and can be claimed later by the pet's owner.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// * `stage` - The stage the pet has reached
+    /// * `event_type` - The lifecycle event type that triggered the stage
+    /// 
+    /// # Returns
+    /// 
+    /// * `DispatchResult` - Ok if successful, Err otherwise
+    pub(crate) fn grant_stage_reward(
+        pet_id: PetId,
+        stage: LifecycleStage,
+        event_type: LifecycleEventKind,
+    ) -> DispatchResult {
+        let reward = match T::LifecycleStageRewards::stage_reward(stage) {
+            Some(reward) => reward,
+            None => return Ok(()),
+        };
+        
+        // Apply the stat growth to the bonus fields
+        crate::PetNfts::<T>::try_mutate(pet_id, |pet_opt| -> DispatchResult {
+            let pet = pet_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+            pet.bonus_strength = pet.bonus_strength.saturating_add(reward.strength);
+            pet.bonus_agility = pet.bonus_agility.saturating_add(reward.agility);
+            pet.bonus_intelligence = pet.bonus_intelligence.saturating_add(reward.intelligence);
+            pet.bonus_vitality = pet.bonus_vitality.saturating_add(reward.vitality);
+            Ok(())
+        })?;
+        
+        // Pay the PTCN reward from the pot, or defer it if the pot is short
+        let mut currency_deferred = false;
+        if !reward.currency.is_zero() {
+            let owner = crate::PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            if crate::Pallet::<T>::pay_from_lifecycle_pot(&owner, reward.currency).is_err() {
+                crate::DeferredLifecycleRewards::<T>::mutate(pet_id, |deferred| {
+                    *deferred = deferred.saturating_add(reward.currency);
+                });
+                currency_deferred = true;
+            }
+        }
+        
+        crate::Pallet::<T>::deposit_event(crate::Event::LifecycleStageRewardGranted {
+            pet_id,
+            event_type,
+            bonus_strength: reward.strength,
+            bonus_agility: reward.agility,
+            bonus_intelligence: reward.intelligence,
+            bonus_vitality: reward.vitality,
+            currency_reward: reward.currency,
+            currency_deferred,
+            timestamp: frame_system::Pallet::<T>::block_number(),
+        });
+        
+        Ok(())
+    }
+    
     /// Records a lifecycle event for a pet.
     /// 
+    /// Unlike the other history lists, this one keeps failing when full: it is how a pet
+    /// remembers which once-only events it has already experienced, so nothing may be evicted.
+    /// 
     /// # Parameters
     /// 
     /// * `pet_id` - The ID of the pet
@@ -483,7 +601,7 @@ impl<T: Config> LifecycleEventSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     fn record_event(
         pet_id: PetId,
-        event_type: u8,
+        event_type: LifecycleEventKind,
     ) -> DispatchResult {
         crate::PetLifecycleEvents::<T>::try_mutate(pet_id, |events| -> DispatchResult {
             // Get the current block number
@@ -505,13 +623,29 @@ impl<T: Config> LifecycleEventSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, BlockNumberFor<T>)>` - The lifecycle events (event_type, timestamp)
+    /// * `Vec<(LifecycleEventKind, BlockNumberFor<T>)>` - The lifecycle events (event_type, timestamp)
     pub fn get_pet_lifecycle_events(
         pet_id: PetId,
-    ) -> Vec<(u8, BlockNumberFor<T>)> {
+    ) -> Vec<(LifecycleEventKind, BlockNumberFor<T>)> {
         crate::PetLifecycleEvents::<T>::get(pet_id).to_vec()
     }
     
+    /// Gets the latest lifecycle stage a pet has reached.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// 
+    /// # Returns
+    /// 
+    /// * `Option<LifecycleStage>` - The stage, or `None` if the pet has not reached one yet
+    pub fn current_stage(pet_id: PetId) -> Option<LifecycleStage> {
+        crate::PetLifecycleEvents::<T>::get(pet_id)
+            .iter()
+            .filter_map(|(event_type, _)| LifecycleStage::from_event_type(*event_type))
+            .max()
+    }
+    
     /// Gets detailed information about a pet's lifecycle events.
     /// 
     /// # Parameters
@@ -555,17 +689,10 @@ impl<T: Config> LifecycleEventSystem<T> {
         let current_events = crate::PetLifecycleEvents::<T>::get(pet_id);
         
         // Get all possible lifecycle events
-        let all_events = vec![
-            Self::get_lifecycle_event(0)?, // Birth
-            Self::get_lifecycle_event(1)?, // Adolescence
-            Self::get_lifecycle_event(2)?, // Adulthood
-            Self::get_lifecycle_event(3)?, // Mastery
-            Self::get_lifecycle_event(4)?, // Evolution
-            Self::get_lifecycle_event(5)?, // Transformation
-            Self::get_lifecycle_event(6)?, // Awakening
-            Self::get_lifecycle_event(7)?, // Enlightenment
-            Self::get_lifecycle_event(8)?, // Rebirth
-        ];
+        let mut all_events = Vec::with_capacity(LifecycleEventKind::ALL.len());
+        for event_type in LifecycleEventKind::ALL {
+            all_events.push(Self::get_lifecycle_event(*event_type)?);
+        }
         
         // Find events the pet hasn't experienced yet
         let mut result = Vec::new();
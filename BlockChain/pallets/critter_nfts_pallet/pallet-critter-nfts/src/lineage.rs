@@ -0,0 +1,279 @@
+//! # Pet Lineage
+//!
+//! This module records the parents of bred pets and answers family tree queries for the
+//! explorer. A bred pet carries its parents in `PetNft::parent1_id` and `parent2_id`, and they
+//! are also kept in `PetParents`, which outlives the pet so trees stay whole after a release.
+//! Ancestors are found by walking the `PetParents` links; descendants through the
+//! `ChildrenIndex`, which is kept up to date at breeding time. Both walks keep a visited set,
+//! so a corrupted link that forms a loop cannot make them run forever, and both are capped
+//! by `MaxLineageNodes`.
+//!
+//! Each pet also carries its generation: minted pets are generation 0 and a bred pet is one more
+//! than the later of its parents (`offspring_generation`).
+//!
+//! `PetGenealogy` keeps a `GenealogyRecord` for every bred pet: its parents, generation and the
+//! block it was bred at. Like `PetParents`, it is kept after a release, and `get_ancestry`
+//! returns the records of a pet and its bred ancestors for genealogy displays.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::Get};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_std::{collections::btree_set::BTreeSet, collections::vec_deque::VecDeque, vec::Vec};
+use crate::{Config, PetId};
+
+/// The breeding record of a bred pet.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct GenealogyRecord<T: Config> {
+    /// The first parent
+    pub parent1_id: Option<PetId>,
+
+    /// The second parent
+    pub parent2_id: Option<PetId>,
+
+    /// One more than the later of the parents' generations; minted pets are generation 0
+    pub generation: u32,
+
+    /// The block the pet was bred at
+    pub breed_block: BlockNumberFor<T>,
+}
+
+/// One pet in a lineage tree.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LineageNode<AccountId> {
+    /// The pet's ID
+    pub pet_id: PetId,
+
+    /// The pet's species; empty if the pet no longer exists
+    pub species: Vec<u8>,
+
+    /// The pet's owner; `None` if the pet no longer exists
+    pub owner: Option<AccountId>,
+
+    /// Whether the pet still exists
+    pub alive: bool,
+
+    /// Generations above the queried pet (0 for the pet itself)
+    pub depth: u8,
+
+    /// The pet's recorded parents, if it was bred
+    pub parents: Option<(PetId, PetId)>,
+}
+
+/// A pet's ancestors, listed breadth-first starting with the pet itself.
+/// Each pet appears once, even if it is reached through both parents.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LineageTree<AccountId> {
+    /// The nodes of the tree
+    pub nodes: Vec<LineageNode<AccountId>>,
+
+    /// Whether the tree was cut short by `MaxLineageNodes`
+    pub truncated: bool,
+}
+
+/// The generation of a pet bred from parents of the given generations: one more than the
+/// later of the two, saturating at `u32::MAX`.
+pub fn offspring_generation(parent1: u32, parent2: u32) -> u32 {
+    parent1.max(parent2).saturating_add(1)
+}
+
+/// A system for recording and querying pet lineage.
+pub struct LineageSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> LineageSystem<T> {
+    /// Records the parents of a newly bred pet and adds it to its parents' children.
+    /// A parent whose child list is full keeps its first `MaxChildrenPerPet` children.
+    ///
+    /// # Parameters
+    ///
+    /// * `child` - The bred pet
+    /// * `parent1` - The first parent
+    /// * `parent2` - The second parent
+    pub fn record_parents(child: PetId, parent1: PetId, parent2: PetId) {
+        // 1. Record the parents.
+        crate::PetParents::<T>::insert(child, (parent1, parent2));
+
+        // 2. Index the child under each distinct parent.
+        Self::index_child(parent1, child);
+        if parent2 != parent1 {
+            Self::index_child(parent2, child);
+        }
+    }
+
+    /// Records the genealogy of a newly bred pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `child` - The bred pet
+    /// * `parents` - The pet's parents
+    /// * `generation` - The pet's generation
+    /// * `breed_block` - The block the pet was bred at
+    pub fn record_genealogy(child: PetId, parents: (PetId, PetId), generation: u32, breed_block: BlockNumberFor<T>) {
+        crate::PetGenealogy::<T>::insert(child, GenealogyRecord {
+            parent1_id: Some(parents.0),
+            parent2_id: Some(parents.1),
+            generation,
+            breed_block,
+        });
+    }
+
+    /// Adds `child` to `parent`'s entry in the children index, if there is room.
+    pub(crate) fn index_child(parent: PetId, child: PetId) {
+        crate::ChildrenIndex::<T>::mutate(parent, |children| {
+            if !children.contains(&child) {
+                let _ = children.try_push(child);
+            }
+        });
+    }
+
+    /// Assembles a pet's ancestor tree, up to `max_depth` generations above the pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet at the root of the tree
+    /// * `max_depth` - How many generations of ancestors to include
+    ///
+    /// # Returns
+    ///
+    /// * `Option<LineageTree<T::AccountId>>` - The tree, or `None` if the pet has no record at all
+    pub fn ancestors(pet_id: PetId, max_depth: u8) -> Option<LineageTree<T::AccountId>> {
+        if !crate::PetNfts::<T>::contains_key(pet_id) && !crate::PetParents::<T>::contains_key(pet_id) {
+            return None;
+        }
+
+        let max_nodes = T::MaxLineageNodes::get() as usize;
+        let mut nodes = Vec::new();
+        let mut truncated = false;
+        let mut visited = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(pet_id);
+        queue.push_back((pet_id, 0u8));
+
+        // 1. Walk the parent links breadth-first, skipping pets already in the tree.
+        while let Some((current, depth)) = queue.pop_front() {
+            if nodes.len() >= max_nodes {
+                truncated = true;
+                break;
+            }
+
+            let parents = crate::PetParents::<T>::get(current);
+            if let Some((parent1, parent2)) = parents {
+                if depth < max_depth {
+                    for parent in [parent1, parent2] {
+                        if visited.insert(parent) {
+                            queue.push_back((parent, depth.saturating_add(1)));
+                        }
+                    }
+                }
+            }
+
+            // 2. Describe the pet. Released pets keep their place in the tree.
+            let pet = crate::PetNfts::<T>::get(current);
+            nodes.push(LineageNode {
+                pet_id: current,
+                species: pet.as_ref().map(|pet| pet.initial_species.to_vec()).unwrap_or_default(),
+                owner: crate::PetNftOwner::<T>::get(current),
+                alive: pet.is_some(),
+                depth,
+                parents,
+            });
+        }
+
+        Some(LineageTree { nodes, truncated })
+    }
+
+    /// Lists a pet's ancestors breadth-first (parents, then grandparents, ...), up to
+    /// `max_depth` generations above the pet. Each ancestor appears once, and the list is
+    /// capped by `MaxLineageNodes`.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet whose ancestors to list
+    /// * `max_depth` - How many generations of ancestors to include
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<PetId>` - The ancestors, nearest generations first; empty for a minted pet
+    pub fn ancestry(pet_id: PetId, max_depth: u8) -> Vec<PetId> {
+        let max_nodes = T::MaxLineageNodes::get() as usize;
+        let mut ancestry = Vec::new();
+        let mut visited = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(pet_id);
+        queue.push_back((pet_id, 0u8));
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            if let Some((parent1, parent2)) = crate::PetParents::<T>::get(current) {
+                for parent in [parent1, parent2] {
+                    if ancestry.len() >= max_nodes {
+                        return ancestry;
+                    }
+                    if visited.insert(parent) {
+                        ancestry.push(parent);
+                        queue.push_back((parent, depth.saturating_add(1)));
+                    }
+                }
+            }
+        }
+
+        ancestry
+    }
+
+    /// Gets the genealogy records of a pet and its ancestors up to `depth` generations above it,
+    /// nearest first. Minted pets have no record and are skipped; their IDs still appear as
+    /// the parents of the pets bred from them.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet whose genealogy to list
+    /// * `depth` - How many generations of ancestors to include
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<GenealogyRecord<T>>` - The records, the pet's own first if it was bred
+    pub fn genealogy(pet_id: PetId, depth: u8) -> Vec<GenealogyRecord<T>> {
+        sp_std::iter::once(pet_id)
+            .chain(Self::ancestry(pet_id, depth))
+            .filter_map(crate::PetGenealogy::<T>::get)
+            .collect()
+    }
+
+    /// Lists a pet's descendants breadth-first (children, then grandchildren, ...).
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet whose descendants to list
+    /// * `limit` - The maximum number of descendants to return; capped by `MaxLineageNodes`
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<PetId>` - The descendants, nearest generations first
+    pub fn descendants(pet_id: PetId, limit: u32) -> Vec<PetId> {
+        let limit = limit.min(T::MaxLineageNodes::get()) as usize;
+        let mut descendants = Vec::new();
+        let mut visited = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(pet_id);
+        queue.push_back(pet_id);
+
+        while let Some(current) = queue.pop_front() {
+            for child in crate::ChildrenIndex::<T>::get(current) {
+                if descendants.len() >= limit {
+                    return descendants;
+                }
+                if visited.insert(child) {
+                    descendants.push(child);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        descendants
+    }
+}
@@ -12,13 +12,14 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use crate::kinds::MemoryKind;
 use crate::{Config, Error, PetId, PetNft};
 
 /// Represents a memory that a pet has formed.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Memory<T: Config> {
     /// The memory type
-    pub memory_type: u8,
+    pub memory_type: MemoryKind,
     
     /// The significance of the memory (0-255)
     pub significance: u8,
@@ -37,21 +38,8 @@ pub struct Memory<T: Config> {
     pub associated_data: BoundedVec<u8, T::MaxMemoryDataSize>,
 }
 
-/// Memory types.
-pub enum MemoryType {
-    Feeding = 0,
-    Playing = 1,
-    Training = 2,
-    SocialInteraction = 3,
-    EnvironmentalAdaptation = 4,
-    Achievement = 5,
-    LifecycleEvent = 6,
-    OwnerBonding = 7,
-    Neglect = 8,
-    Trauma = 9,
-    Joy = 10,
-    Discovery = 11,
-}
+/// Memory types, now the typed `crate::kinds::MemoryKind`.
+pub type MemoryType = crate::kinds::MemoryKind;
 
 /// A system for managing pet memories.
 pub struct PetMemorySystem<T: Config> {
@@ -73,7 +61,7 @@ impl<T: Config> PetMemorySystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     pub fn record_memory(
         pet_id: PetId,
-        memory_type: u8,
+        memory_type: MemoryKind,
         significance: u8,
         associated_data: Vec<u8>,
     ) -> DispatchResult {
@@ -95,7 +83,9 @@ impl<T: Config> PetMemorySystem<T> {
         );
         
         // Store the memory
-        crate::PetMemories::<T>::try_mutate(pet_id, |memories| -> DispatchResult {
+        let stored = crate::PetMemories::<T>::try_mutate(pet_id, |memories| -> Result<bool, DispatchError> {
+            let size_before = crate::footprint::stored_size(memories);
+            
             // Check if we've reached the maximum number of memories
             if memories.len() >= T::MaxPetMemories::get() as usize {
                 // Find the least significant memory
@@ -117,7 +107,7 @@ impl<T: Config> PetMemorySystem<T> {
                     memories.set(least_significant_index, memory_data)?;
                 } else {
                     // Otherwise, don't store the new memory
-                    return Ok(());
+                    return Ok(false);
                 }
             } else {
                 // Add the new memory
@@ -125,9 +115,19 @@ impl<T: Config> PetMemorySystem<T> {
                     .map_err(|_| Error::<T>::MemoryCapacityFull)?;
             }
             
-            Ok(())
+            crate::footprint::FootprintSystem::<T>::note_resize(pet_id, size_before, crate::footprint::stored_size(memories));
+            Ok(true)
         })?;
         
+        // Count the memory towards memory achievements
+        if stored {
+            crate::achievements::AchievementSystem::<T>::note_progress(
+                pet_id,
+                crate::achievements::ProgressSource::Memories,
+                crate::achievements::ProgressUpdate::Add(1),
+            );
+        }
+        
         // Emit an event
         crate::Pallet::<T>::deposit_event(crate::Event::MemoryFormed {
             pet_id,
@@ -140,10 +140,10 @@ impl<T: Config> PetMemorySystem<T> {
         if significance > T::MaxMemorySignificance::get() / 2 {
             // Significant memory: evolve a relevant trait
             let trait_type = match memory_type {
-                0 => crate::personality::PersonalityTraitType::Grateful as u8, // Feeding
-                1 => crate::personality::PersonalityTraitType::Playful as u8, // Playing
-                2 => crate::personality::PersonalityTraitType::Intelligent as u8, // Training
-                3 => crate::personality::PersonalityTraitType::Friendly as u8, // Social Interaction
+                MemoryKind::Feeding => crate::personality::PersonalityTraitType::Grateful as u8,
+                MemoryKind::Playing => crate::personality::PersonalityTraitType::Playful as u8,
+                MemoryKind::Training => crate::personality::PersonalityTraitType::Intelligent as u8,
+                MemoryKind::SocialInteraction => crate::personality::PersonalityTraitType::Friendly as u8,
                 _ => crate::personality::PersonalityTraitType::Adaptable as u8, // Default
             };
             
@@ -169,7 +169,7 @@ impl<T: Config> PetMemorySystem<T> {
     /// 
     /// * `i8` - The emotional impact of the memory (-128 to 127)
     fn calculate_emotional_impact(
-        memory_type: u8,
+        memory_type: MemoryKind,
         significance: u8,
         pet: &PetNft<T>,
     ) -> i8 {
@@ -182,19 +182,18 @@ impl<T: Config> PetMemorySystem<T> {
         
         // Adjust based on memory type
         match memory_type {
-            0 => impact += 20, // Feeding: positive
-            1 => impact += 30, // Playing: very positive
-            2 => impact += 10, // Training: slightly positive
-            3 => impact += 20, // Social Interaction: positive
-            4 => impact += 10, // Environmental Adaptation: slightly positive
-            5 => impact += 40, // Achievement: extremely positive
-            6 => impact += 30, // Lifecycle Event: very positive
-            7 => impact += 50, // Owner Bonding: extremely positive
-            8 => impact -= 30, // Neglect: very negative
-            9 => impact -= 50, // Trauma: extremely negative
-            10 => impact += 40, // Joy: extremely positive
-            11 => impact += 20, // Discovery: positive
-            _ => {} // No adjustment for other types
+            MemoryKind::Feeding => impact += 20, // positive
+            MemoryKind::Playing => impact += 30, // very positive
+            MemoryKind::Training => impact += 10, // slightly positive
+            MemoryKind::SocialInteraction => impact += 20, // positive
+            MemoryKind::EnvironmentalAdaptation => impact += 10, // slightly positive
+            MemoryKind::Achievement => impact += 40, // extremely positive
+            MemoryKind::LifecycleEvent => impact += 30, // very positive
+            MemoryKind::OwnerBonding => impact += 50, // extremely positive
+            MemoryKind::Neglect => impact -= 30, // very negative
+            MemoryKind::Trauma => impact -= 50, // extremely negative
+            MemoryKind::Joy => impact += 40, // extremely positive
+            MemoryKind::Discovery => impact += 20, // positive
         }
         
         // Adjust based on significance
@@ -291,11 +290,11 @@ impl<T: Config> PetMemorySystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Result<(u8, u8, u64, Vec<u8>), DispatchError>` - The memory, or an error
+    /// * `Result<(MemoryKind, u8, u64, Vec<u8>), DispatchError>` - The memory, or an error
     pub fn get_memory(
         pet_id: PetId,
         memory_index: usize,
-    ) -> Result<(u8, u8, u64, Vec<u8>), DispatchError> {
+    ) -> Result<(MemoryKind, u8, u64, Vec<u8>), DispatchError> {
         let memories = crate::PetMemories::<T>::get(pet_id);
         
         // Ensure the memory exists
@@ -319,10 +318,10 @@ impl<T: Config> PetMemorySystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, u8, u64, Vec<u8>)>` - The memories
+    /// * `Vec<(MemoryKind, u8, u64, Vec<u8>)>` - The memories
     pub fn get_all_memories(
         pet_id: PetId,
-    ) -> Vec<(u8, u8, u64, Vec<u8>)> {
+    ) -> Vec<(MemoryKind, u8, u64, Vec<u8>)> {
         let memories = crate::PetMemories::<T>::get(pet_id);
         
         let mut result = Vec::with_capacity(memories.len());
@@ -342,11 +341,11 @@ impl<T: Config> PetMemorySystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, u8, u64, Vec<u8>)>` - The memories
+    /// * `Vec<(MemoryKind, u8, u64, Vec<u8>)>` - The memories
     pub fn get_significant_memories(
         pet_id: PetId,
         count: usize,
-    ) -> Vec<(u8, u8, u64, Vec<u8>)> {
+    ) -> Vec<(MemoryKind, u8, u64, Vec<u8>)> {
         let memories = crate::PetMemories::<T>::get(pet_id);
         
         // Sort memories by significance (highest first)
@@ -371,11 +370,11 @@ impl<T: Config> PetMemorySystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, u8, u64, Vec<u8>)>` - The memories
+    /// * `Vec<(MemoryKind, u8, u64, Vec<u8>)>` - The memories
     pub fn get_memories_by_type(
         pet_id: PetId,
-        memory_type: u8,
-    ) -> Vec<(u8, u8, u64, Vec<u8>)> {
+        memory_type: MemoryKind,
+    ) -> Vec<(MemoryKind, u8, u64, Vec<u8>)> {
         let memories = crate::PetMemories::<T>::get(pet_id);
         
         let mut result = Vec::new();
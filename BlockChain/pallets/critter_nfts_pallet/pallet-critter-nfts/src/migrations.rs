@@ -0,0 +1,914 @@
+//! # Storage Migrations
+//!
+//! This module contains the storage migrations for pallet-critter-nfts.
+//! Each migration lives in its own versioned submodule as a `MigrationStep`, wrapped in
+//! `VersionedMigration` so it only runs when the on-chain storage version matches the
+//! version it migrates from. Migrations are exercised against storage fixtures with the
+//! harness in `crittercraft_traits::migration`.
+
+use crittercraft_traits::migration::{MigrationStep, VersionedMigration};
+use frame_support::{
+    pallet_prelude::*,
+    weights::Weight,
+    log,
+    BoundedVec,
+};
+use frame_system::pallet_prelude::*;
+use sp_std::vec::Vec;
+use crate::{BalanceOf, ChildrenIndex, Config, ElementType, LockInfo, LockedNfts, Pallet, PetId, PetNft, PetNftOwner, PetNfts, PetParents};
+use crate::traits::{DnaHashType, LockerId, SpeciesType, TraitTypeString};
+
+/// Migration to storage version 1: adds the `bonus_*` growth stat fields to `PetNft`.
+pub mod v1 {
+    use super::*;
+
+    /// The `PetNft` layout before the growth bonus fields were added.
+    #[derive(Encode, Decode)]
+    pub struct OldPetNft<T: Config> {
+        pub id: PetId,
+        pub dna_hash: DnaHashType,
+        pub initial_species: SpeciesType,
+        pub current_pet_name: BoundedVec<u8, T::MaxPetNameLen>,
+        pub base_strength: u8,
+        pub base_agility: u8,
+        pub base_intelligence: u8,
+        pub base_vitality: u8,
+        pub primary_elemental_affinity: ElementType,
+        pub level: u32,
+        pub experience_points: u32,
+        pub mood_indicator: u8,
+        pub last_fed_block: BlockNumberFor<T>,
+        pub last_played_block: BlockNumberFor<T>,
+        pub personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>,
+        pub last_state_update_block: BlockNumberFor<T>,
+        pub state_version: u32,
+        pub sync_flags: u8,
+        pub is_locked: bool,
+        pub last_interaction_time: BlockNumberFor<T>,
+    }
+
+    /// Translates every stored `PetNft` to the version 1 layout with zeroed growth bonuses.
+    /// That layout still has the `u8` sync flags, which version 4 widens.
+    pub struct AddGrowthBonuses<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddGrowthBonuses<T> {
+        fn migrate() -> Weight {
+            let mut translated = 0u64;
+            super::v4::PetNfts::<T>::translate::<OldPetNft<T>, _>(|_pet_id, old| {
+                translated = translated.saturating_add(1);
+                Some(super::v4::OldPetNft {
+                    id: old.id,
+                    dna_hash: old.dna_hash,
+                    initial_species: old.initial_species,
+                    current_pet_name: old.current_pet_name,
+                    base_strength: old.base_strength,
+                    base_agility: old.base_agility,
+                    base_intelligence: old.base_intelligence,
+                    base_vitality: old.base_vitality,
+                    primary_elemental_affinity: old.primary_elemental_affinity,
+                    level: old.level,
+                    experience_points: old.experience_points,
+                    mood_indicator: old.mood_indicator,
+                    last_fed_block: old.last_fed_block,
+                    last_played_block: old.last_played_block,
+                    personality_traits: old.personality_traits,
+                    last_state_update_block: old.last_state_update_block,
+                    state_version: old.state_version,
+                    sync_flags: old.sync_flags,
+                    is_locked: old.is_locked,
+                    last_interaction_time: old.last_interaction_time,
+                    bonus_strength: 0,
+                    bonus_agility: 0,
+                    bonus_intelligence: 0,
+                    bonus_vitality: 0,
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Migrated {} pets to storage version 1",
+                translated
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((PetNfts::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let pets_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode pet count"))?;
+            // `iter` skips undecodable values, so a matching count means every pet decodes.
+            ensure!(super::v4::PetNfts::<T>::iter().count() as u64 == pets_before, "pets lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 0 -> 1, safe to queue unconditionally in the runtime.
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        AddGrowthBonuses<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 2: builds the `ChildrenIndex` from the recorded `PetParents`.
+pub mod v2 {
+    use super::*;
+
+    /// Rebuilds every parent's children list from `PetParents`. Children are indexed in pet id
+    /// order, which is breeding order, and a full list keeps its first `MaxChildrenPerPet`.
+    pub struct BackfillChildrenIndex<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for BackfillChildrenIndex<T> {
+        fn migrate() -> Weight {
+            let _ = ChildrenIndex::<T>::clear(u32::MAX, None);
+
+            let mut records: Vec<(PetId, (PetId, PetId))> = PetParents::<T>::iter().collect();
+            records.sort_by_key(|(child, _)| *child);
+            let mut writes = 0u64;
+            for (child, (parent1, parent2)) in records.iter() {
+                crate::lineage::LineageSystem::<T>::index_child(*parent1, *child);
+                if parent2 != parent1 {
+                    crate::lineage::LineageSystem::<T>::index_child(*parent2, *child);
+                }
+                writes = writes.saturating_add(2);
+            }
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Indexed the children of {} bred pets for storage version 2",
+                records.len()
+            );
+
+            let reads = records.len() as u64;
+            T::DbWeight::get().reads_writes(reads.saturating_add(writes), writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            // Every indexed child must have the parent it is indexed under.
+            for (parent, children) in ChildrenIndex::<T>::iter() {
+                for child in children {
+                    let (parent1, parent2) = PetParents::<T>::get(child)
+                        .ok_or(sp_runtime::TryRuntimeError::Other("indexed child without parents"))?;
+                    ensure!(parent1 == parent || parent2 == parent, "child indexed under the wrong parent");
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Storage version 1 -> 2, safe to queue unconditionally in the runtime.
+    pub type MigrateToV2<T> = VersionedMigration<
+        1,
+        2,
+        BackfillChildrenIndex<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 3: records a lock holder for every locked pet.
+pub mod v3 {
+    use super::*;
+
+    /// The locker recorded for locks taken before lockers were tracked.
+    pub const LEGACY_LOCKER: LockerId = LockerId::Other(0);
+
+    /// Translates every `()` entry of `LockedNfts` to a `LockInfo` held by `LEGACY_LOCKER`.
+    /// The original lock block is unknown, so the upgrade block is recorded instead.
+    pub struct RecordLockHolders<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for RecordLockHolders<T> {
+        fn migrate() -> Weight {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut translated = 0u64;
+            LockedNfts::<T>::translate::<(), _>(|_pet_id, ()| {
+                translated = translated.saturating_add(1);
+                Some(LockInfo { locker: LEGACY_LOCKER, locked_at: now })
+            });
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Recorded the legacy lock holder for {} locked pets for storage version 3",
+                translated
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((LockedNfts::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let locks_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode lock count"))?;
+            // `iter` skips undecodable values, so a matching count means every lock decodes.
+            ensure!(LockedNfts::<T>::iter().count() as u64 == locks_before, "locks lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 2 -> 3, safe to queue unconditionally in the runtime.
+    pub type MigrateToV3<T> = VersionedMigration<
+        2,
+        3,
+        RecordLockHolders<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 4: widens `PetNft::sync_flags` from a `u8` to `SyncFlags`.
+pub mod v4 {
+    use super::*;
+    use crate::sync::SyncFlags;
+
+    /// The `PetNft` layout of storage versions 1 to 3, with `u8` sync flags.
+    #[derive(Encode, Decode)]
+    pub struct OldPetNft<T: Config> {
+        pub id: PetId,
+        pub dna_hash: DnaHashType,
+        pub initial_species: SpeciesType,
+        pub current_pet_name: BoundedVec<u8, T::MaxPetNameLen>,
+        pub base_strength: u8,
+        pub base_agility: u8,
+        pub base_intelligence: u8,
+        pub base_vitality: u8,
+        pub primary_elemental_affinity: ElementType,
+        pub level: u32,
+        pub experience_points: u32,
+        pub mood_indicator: u8,
+        pub last_fed_block: BlockNumberFor<T>,
+        pub last_played_block: BlockNumberFor<T>,
+        pub personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>,
+        pub last_state_update_block: BlockNumberFor<T>,
+        pub state_version: u32,
+        pub sync_flags: u8,
+        pub is_locked: bool,
+        pub last_interaction_time: BlockNumberFor<T>,
+        pub bonus_strength: u8,
+        pub bonus_agility: u8,
+        pub bonus_intelligence: u8,
+        pub bonus_vitality: u8,
+    }
+
+    /// `PetNfts` as stored in versions 1 to 3.
+    #[frame_support::storage_alias]
+    pub type PetNfts<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, PetId, OldPetNft<T>>;
+
+    /// Translates every stored `PetNft` to the version 4 layout with widened sync flags. The old
+    /// bits keep their meaning: bit `n` is still `StateChangeType` `n`. That layout does not
+    /// embed the owner yet, which version 5 adds.
+    pub struct WidenSyncFlags<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for WidenSyncFlags<T> {
+        fn migrate() -> Weight {
+            let mut translated = 0u64;
+            super::v5::PetNfts::<T>::translate::<OldPetNft<T>, _>(|_pet_id, old| {
+                translated = translated.saturating_add(1);
+                Some(super::v5::OldPetNft {
+                    id: old.id,
+                    dna_hash: old.dna_hash,
+                    initial_species: old.initial_species,
+                    current_pet_name: old.current_pet_name,
+                    base_strength: old.base_strength,
+                    base_agility: old.base_agility,
+                    base_intelligence: old.base_intelligence,
+                    base_vitality: old.base_vitality,
+                    primary_elemental_affinity: old.primary_elemental_affinity,
+                    level: old.level,
+                    experience_points: old.experience_points,
+                    mood_indicator: old.mood_indicator,
+                    last_fed_block: old.last_fed_block,
+                    last_played_block: old.last_played_block,
+                    personality_traits: old.personality_traits,
+                    last_state_update_block: old.last_state_update_block,
+                    state_version: old.state_version,
+                    sync_flags: SyncFlags::from_bits(old.sync_flags as u16),
+                    is_locked: old.is_locked,
+                    last_interaction_time: old.last_interaction_time,
+                    bonus_strength: old.bonus_strength,
+                    bonus_agility: old.bonus_agility,
+                    bonus_intelligence: old.bonus_intelligence,
+                    bonus_vitality: old.bonus_vitality,
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Widened the sync flags of {} pets for storage version 4",
+                translated
+            );
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((crate::PetNfts::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let pets_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode pet count"))?;
+            // `iter` skips undecodable values, so a matching count means every pet decodes.
+            ensure!(super::v5::PetNfts::<T>::iter().count() as u64 == pets_before, "pets lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 3 -> 4, safe to queue unconditionally in the runtime.
+    pub type MigrateToV4<T> = VersionedMigration<
+        3,
+        4,
+        WidenSyncFlags<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 5: embeds the owner in `PetNft`, so care calls check ownership
+/// with the same read that loads the pet, and records each pet's breeding generation.
+pub mod v5 {
+    use super::*;
+    use crate::sync::SyncFlags;
+    use sp_std::collections::btree_map::BTreeMap;
+
+    /// The `PetNft` layout of storage version 4, without the embedded owner and generation.
+    #[derive(Encode, Decode)]
+    pub struct OldPetNft<T: Config> {
+        pub id: PetId,
+        pub dna_hash: DnaHashType,
+        pub initial_species: SpeciesType,
+        pub current_pet_name: BoundedVec<u8, T::MaxPetNameLen>,
+        pub base_strength: u8,
+        pub base_agility: u8,
+        pub base_intelligence: u8,
+        pub base_vitality: u8,
+        pub primary_elemental_affinity: ElementType,
+        pub level: u32,
+        pub experience_points: u32,
+        pub mood_indicator: u8,
+        pub last_fed_block: BlockNumberFor<T>,
+        pub last_played_block: BlockNumberFor<T>,
+        pub personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>,
+        pub last_state_update_block: BlockNumberFor<T>,
+        pub state_version: u32,
+        pub sync_flags: SyncFlags,
+        pub is_locked: bool,
+        pub last_interaction_time: BlockNumberFor<T>,
+        pub bonus_strength: u8,
+        pub bonus_agility: u8,
+        pub bonus_intelligence: u8,
+        pub bonus_vitality: u8,
+    }
+
+    impl<T: Config> OldPetNft<T> {
+        /// The pet in the version 5 layout, owned by `owner`.
+        fn upgrade(self, owner: T::AccountId, generation: u16) -> super::v6::OldPetNft<T> {
+            super::v6::OldPetNft {
+                id: self.id,
+                dna_hash: self.dna_hash,
+                initial_species: self.initial_species,
+                current_pet_name: self.current_pet_name,
+                base_strength: self.base_strength,
+                base_agility: self.base_agility,
+                base_intelligence: self.base_intelligence,
+                base_vitality: self.base_vitality,
+                primary_elemental_affinity: self.primary_elemental_affinity,
+                level: self.level,
+                experience_points: self.experience_points,
+                mood_indicator: self.mood_indicator,
+                last_fed_block: self.last_fed_block,
+                last_played_block: self.last_played_block,
+                personality_traits: self.personality_traits,
+                last_state_update_block: self.last_state_update_block,
+                state_version: self.state_version,
+                sync_flags: self.sync_flags,
+                is_locked: self.is_locked,
+                last_interaction_time: self.last_interaction_time,
+                bonus_strength: self.bonus_strength,
+                bonus_agility: self.bonus_agility,
+                bonus_intelligence: self.bonus_intelligence,
+                bonus_vitality: self.bonus_vitality,
+                owner,
+                generation,
+            }
+        }
+    }
+
+    /// The `PendingClaim` layout of storage version 4, parking a pet without an owner.
+    #[derive(Encode, Decode)]
+    pub struct OldPendingClaim<T: Config> {
+        pub recipient: T::AccountId,
+        pub pet: OldPetNft<T>,
+        pub parents: (PetId, PetId),
+        pub expires_at: BlockNumberFor<T>,
+    }
+
+    /// `PetNfts` as stored in version 4.
+    #[frame_support::storage_alias]
+    pub type PetNfts<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, PetId, OldPetNft<T>>;
+
+    /// The generation of a pet bred from parents of the given generations, in the `u16` that
+    /// storage versions 5 and 6 keep; version 7 widens it.
+    fn offspring_generation(parent1: u16, parent2: u16) -> u16 {
+        parent1.max(parent2).saturating_add(1)
+    }
+
+    /// Counts the generation of every pet with recorded parents. Pet IDs are handed out in
+    /// order, so walking the children in ascending ID order visits bred parents first. A parent
+    /// without recorded parents, including one that no longer exists, is generation 0.
+    ///
+    /// # Returns
+    ///
+    /// * `(BTreeMap<PetId, u16>, u64)` - The generation of each bred pet, and the number of
+    ///   `PetParents` entries read
+    pub(super) fn bred_generations<T: Config>() -> (BTreeMap<PetId, u16>, u64) {
+        let links: BTreeMap<PetId, (PetId, PetId)> = PetParents::<T>::iter().collect();
+        let mut generations = BTreeMap::new();
+        for (child, (parent1, parent2)) in links.iter() {
+            let generation_of = |parent: &PetId| generations.get(parent).copied().unwrap_or(0);
+            let generation = offspring_generation(generation_of(parent1), generation_of(parent2));
+            generations.insert(*child, generation);
+        }
+        (generations, links.len() as u64)
+    }
+
+    /// Translates every stored `PetNft` to embed its owner from `PetNftOwner` and its generation
+    /// from `PetParents`, and every parked pet to embed its recipient and the generation that
+    /// follows its parents. A pet without a `PetNftOwner` entry cannot be owned or transferred
+    /// by anyone, so it is dropped and logged. That layout does not record when the pet was
+    /// acquired yet, which version 6 adds.
+    pub struct AddPetOwner<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddPetOwner<T> {
+        fn migrate() -> Weight {
+            let (generations, links) = bred_generations::<T>();
+            let generation_of = |pet_id: &PetId| generations.get(pet_id).copied().unwrap_or(0);
+
+            let mut translated = 0u64;
+            let mut dropped = 0u64;
+            super::v6::PetNfts::<T>::translate::<OldPetNft<T>, _>(|pet_id, old| {
+                translated = translated.saturating_add(1);
+                match PetNftOwner::<T>::get(pet_id) {
+                    Some(owner) => Some(old.upgrade(owner, generation_of(&pet_id))),
+                    None => {
+                        dropped = dropped.saturating_add(1);
+                        log::warn!(
+                            target: "runtime::critter_nfts_pallet",
+                            "Dropping pet {} without an owner for storage version 5",
+                            pet_id
+                        );
+                        None
+                    }
+                }
+            });
+
+            let mut parked = 0u64;
+            super::v6::PendingClaims::<T>::translate::<OldPendingClaim<T>, _>(|_pet_id, old| {
+                parked = parked.saturating_add(1);
+                Some(super::v6::OldPendingClaim {
+                    pet: old.pet.upgrade(
+                        old.recipient.clone(),
+                        offspring_generation(generation_of(&old.parents.0), generation_of(&old.parents.1)),
+                    ),
+                    recipient: old.recipient,
+                    parents: old.parents,
+                    expires_at: old.expires_at,
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Embedded the owner and generation of {} pets and {} parked pets for storage version 5 ({} dropped)",
+                translated.saturating_sub(dropped),
+                parked,
+                dropped
+            );
+
+            // Each pet reads its `PetNftOwner` entry besides itself.
+            T::DbWeight::get().reads_writes(
+                translated.saturating_mul(2).saturating_add(parked).saturating_add(links),
+                translated.saturating_add(parked),
+            )
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let owned = PetNfts::<T>::iter_keys().filter(|pet_id| PetNftOwner::<T>::contains_key(pet_id)).count() as u64;
+            Ok(owned.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let owned_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode pet count"))?;
+            let mut pets = 0u64;
+            for (pet_id, pet) in super::v6::PetNfts::<T>::iter() {
+                ensure!(PetNftOwner::<T>::get(pet_id) == Some(pet.owner), "embedded owner differs from PetNftOwner");
+                pets = pets.saturating_add(1);
+            }
+            ensure!(pets == owned_before, "owned pets lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 4 -> 5, safe to queue unconditionally in the runtime.
+    pub type MigrateToV5<T> = VersionedMigration<
+        4,
+        5,
+        AddPetOwner<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 6: records when each pet was acquired, for the ownership
+/// transfer gate.
+pub mod v6 {
+    use super::*;
+    use crate::sync::SyncFlags;
+    use sp_runtime::traits::Zero;
+
+    /// The `PetNft` layout of storage version 5, without the acquisition block.
+    #[derive(Encode, Decode)]
+    pub struct OldPetNft<T: Config> {
+        pub id: PetId,
+        pub dna_hash: DnaHashType,
+        pub initial_species: SpeciesType,
+        pub current_pet_name: BoundedVec<u8, T::MaxPetNameLen>,
+        pub base_strength: u8,
+        pub base_agility: u8,
+        pub base_intelligence: u8,
+        pub base_vitality: u8,
+        pub primary_elemental_affinity: ElementType,
+        pub level: u32,
+        pub experience_points: u32,
+        pub mood_indicator: u8,
+        pub last_fed_block: BlockNumberFor<T>,
+        pub last_played_block: BlockNumberFor<T>,
+        pub personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>,
+        pub last_state_update_block: BlockNumberFor<T>,
+        pub state_version: u32,
+        pub sync_flags: SyncFlags,
+        pub is_locked: bool,
+        pub last_interaction_time: BlockNumberFor<T>,
+        pub bonus_strength: u8,
+        pub bonus_agility: u8,
+        pub bonus_intelligence: u8,
+        pub bonus_vitality: u8,
+        pub owner: T::AccountId,
+        pub generation: u16,
+    }
+
+    impl<T: Config> OldPetNft<T> {
+        /// The pet in the version 6 layout, acquired at `acquired_at_block`.
+        fn upgrade(self, acquired_at_block: BlockNumberFor<T>) -> super::v7::OldPetNft<T> {
+            super::v7::OldPetNft {
+                id: self.id,
+                dna_hash: self.dna_hash,
+                initial_species: self.initial_species,
+                current_pet_name: self.current_pet_name,
+                base_strength: self.base_strength,
+                base_agility: self.base_agility,
+                base_intelligence: self.base_intelligence,
+                base_vitality: self.base_vitality,
+                primary_elemental_affinity: self.primary_elemental_affinity,
+                level: self.level,
+                experience_points: self.experience_points,
+                mood_indicator: self.mood_indicator,
+                last_fed_block: self.last_fed_block,
+                last_played_block: self.last_played_block,
+                personality_traits: self.personality_traits,
+                last_state_update_block: self.last_state_update_block,
+                state_version: self.state_version,
+                sync_flags: self.sync_flags,
+                is_locked: self.is_locked,
+                last_interaction_time: self.last_interaction_time,
+                bonus_strength: self.bonus_strength,
+                bonus_agility: self.bonus_agility,
+                bonus_intelligence: self.bonus_intelligence,
+                bonus_vitality: self.bonus_vitality,
+                owner: self.owner,
+                generation: self.generation,
+                acquired_at_block,
+            }
+        }
+    }
+
+    /// The `PendingClaim` layout of storage version 5, parking a pet without an acquisition block.
+    #[derive(Encode, Decode)]
+    pub struct OldPendingClaim<T: Config> {
+        pub recipient: T::AccountId,
+        pub pet: OldPetNft<T>,
+        pub parents: (PetId, PetId),
+        pub expires_at: BlockNumberFor<T>,
+    }
+
+    /// `PetNfts` as stored in version 5.
+    #[frame_support::storage_alias]
+    pub type PetNfts<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, PetId, OldPetNft<T>>;
+
+    /// `PendingClaims` as stored in version 5.
+    #[frame_support::storage_alias]
+    pub type PendingClaims<T: Config> = StorageMap<Pallet<T>, Twox64Concat, PetId, OldPendingClaim<T>>;
+
+    /// Translates every stored `PetNft` and parked pet to the version 6 layout. When a pet was
+    /// acquired was never recorded, so existing pets count as acquired at block zero: pets that
+    /// changed hands before the gate existed are not held up by it. Parked pets are stamped
+    /// again when claimed.
+    pub struct AddAcquiredAt<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddAcquiredAt<T> {
+        fn migrate() -> Weight {
+            let mut translated = 0u64;
+            super::v7::PetNfts::<T>::translate::<OldPetNft<T>, _>(|_pet_id, old| {
+                translated = translated.saturating_add(1);
+                Some(old.upgrade(Zero::zero()))
+            });
+
+            let mut parked = 0u64;
+            super::v7::PendingClaims::<T>::translate::<OldPendingClaim<T>, _>(|_pet_id, old| {
+                parked = parked.saturating_add(1);
+                Some(super::v7::OldPendingClaim {
+                    recipient: old.recipient,
+                    pet: old.pet.upgrade(Zero::zero()),
+                    parents: old.parents,
+                    expires_at: old.expires_at,
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Added the acquisition block of {} pets and {} parked pets for storage version 6",
+                translated,
+                parked
+            );
+
+            T::DbWeight::get().reads_writes(translated.saturating_add(parked), translated.saturating_add(parked))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((PetNfts::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let pets_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode pet count"))?;
+            // `iter` skips undecodable values, so a matching count means every pet decodes.
+            ensure!(super::v7::PetNfts::<T>::iter().count() as u64 == pets_before, "pets lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 5 -> 6, safe to queue unconditionally in the runtime.
+    pub type MigrateToV6<T> = VersionedMigration<
+        5,
+        6,
+        AddAcquiredAt<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 7: records each bred pet's parents in `PetNft` and backfills
+/// `PetGenealogy`, widening the generation to `u32` on the way.
+pub mod v7 {
+    use super::*;
+    use crate::burn::BurnRecord;
+    use crate::lineage::GenealogyRecord;
+    use crate::pending_claims::PendingClaim;
+    use crate::sync::SyncFlags;
+    use sp_runtime::traits::Zero;
+
+    /// The `PetNft` layout of storage version 6, without the parent IDs and with a `u16`
+    /// generation.
+    #[derive(Encode, Decode)]
+    pub struct OldPetNft<T: Config> {
+        pub id: PetId,
+        pub dna_hash: DnaHashType,
+        pub initial_species: SpeciesType,
+        pub current_pet_name: BoundedVec<u8, T::MaxPetNameLen>,
+        pub base_strength: u8,
+        pub base_agility: u8,
+        pub base_intelligence: u8,
+        pub base_vitality: u8,
+        pub primary_elemental_affinity: ElementType,
+        pub level: u32,
+        pub experience_points: u32,
+        pub mood_indicator: u8,
+        pub last_fed_block: BlockNumberFor<T>,
+        pub last_played_block: BlockNumberFor<T>,
+        pub personality_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>,
+        pub last_state_update_block: BlockNumberFor<T>,
+        pub state_version: u32,
+        pub sync_flags: SyncFlags,
+        pub is_locked: bool,
+        pub last_interaction_time: BlockNumberFor<T>,
+        pub bonus_strength: u8,
+        pub bonus_agility: u8,
+        pub bonus_intelligence: u8,
+        pub bonus_vitality: u8,
+        pub owner: T::AccountId,
+        pub generation: u16,
+        pub acquired_at_block: BlockNumberFor<T>,
+    }
+
+    impl<T: Config> OldPetNft<T> {
+        /// The pet in the version 7 layout, bred from `parents` if it was bred.
+        fn upgrade(self, parents: Option<(PetId, PetId)>) -> PetNft<T> {
+            PetNft {
+                id: self.id,
+                dna_hash: self.dna_hash,
+                initial_species: self.initial_species,
+                current_pet_name: self.current_pet_name,
+                base_strength: self.base_strength,
+                base_agility: self.base_agility,
+                base_intelligence: self.base_intelligence,
+                base_vitality: self.base_vitality,
+                primary_elemental_affinity: self.primary_elemental_affinity,
+                level: self.level,
+                experience_points: self.experience_points,
+                mood_indicator: self.mood_indicator,
+                last_fed_block: self.last_fed_block,
+                last_played_block: self.last_played_block,
+                personality_traits: self.personality_traits,
+                last_state_update_block: self.last_state_update_block,
+                state_version: self.state_version,
+                sync_flags: self.sync_flags,
+                is_locked: self.is_locked,
+                last_interaction_time: self.last_interaction_time,
+                bonus_strength: self.bonus_strength,
+                bonus_agility: self.bonus_agility,
+                bonus_intelligence: self.bonus_intelligence,
+                bonus_vitality: self.bonus_vitality,
+                owner: self.owner,
+                generation: self.generation.into(),
+                acquired_at_block: self.acquired_at_block,
+                parent1_id: parents.map(|(parent1, _)| parent1),
+                parent2_id: parents.map(|(_, parent2)| parent2),
+            }
+        }
+    }
+
+    /// The `PendingClaim` layout of storage version 6, parking a pet without parent IDs.
+    #[derive(Encode, Decode)]
+    pub struct OldPendingClaim<T: Config> {
+        pub recipient: T::AccountId,
+        pub pet: OldPetNft<T>,
+        pub parents: (PetId, PetId),
+        pub expires_at: BlockNumberFor<T>,
+    }
+
+    /// The `BurnRecord` layout of storage version 6, keeping a pet without parent IDs.
+    #[derive(Encode, Decode)]
+    pub struct OldBurnRecord<T: Config> {
+        pub pet: OldPetNft<T>,
+        pub owner: T::AccountId,
+        pub burned_at: BlockNumberFor<T>,
+        pub compensation: BalanceOf<T>,
+    }
+
+    /// `PetNfts` as stored in version 6.
+    #[frame_support::storage_alias]
+    pub type PetNfts<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, PetId, OldPetNft<T>>;
+
+    /// `PendingClaims` as stored in version 6.
+    #[frame_support::storage_alias]
+    pub type PendingClaims<T: Config> = StorageMap<Pallet<T>, Twox64Concat, PetId, OldPendingClaim<T>>;
+
+    /// Translates every stored `PetNft`, parked pet and burned pet to the version 7 layout,
+    /// copying the parents of bred and burned pets from `PetParents` and parked pets from their
+    /// claim. Minted pets have no parents.
+    ///
+    /// Then records the genealogy of every pet with recorded parents, released ones included.
+    /// A living pet keeps the generation it carries; a released one gets the generation counted
+    /// from `PetParents`. When a pet was bred was never recorded, so existing records are bred
+    /// at block zero. Parked pets get their record when claimed.
+    pub struct AddLineage<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> MigrationStep for AddLineage<T> {
+        fn migrate() -> Weight {
+            let mut translated = 0u64;
+            let mut bred = 0u64;
+            crate::PetNfts::<T>::translate::<OldPetNft<T>, _>(|pet_id, old| {
+                translated = translated.saturating_add(1);
+                let parents = PetParents::<T>::get(pet_id);
+                if parents.is_some() {
+                    bred = bred.saturating_add(1);
+                }
+                Some(old.upgrade(parents))
+            });
+
+            let mut parked = 0u64;
+            crate::PendingClaims::<T>::translate::<OldPendingClaim<T>, _>(|_pet_id, old| {
+                parked = parked.saturating_add(1);
+                Some(PendingClaim {
+                    recipient: old.recipient,
+                    pet: old.pet.upgrade(Some(old.parents)),
+                    parents: old.parents,
+                    expires_at: old.expires_at,
+                })
+            });
+
+            let mut burned = 0u64;
+            crate::PetCemetery::<T>::translate::<OldBurnRecord<T>, _>(|pet_id, old| {
+                burned = burned.saturating_add(1);
+                Some(BurnRecord {
+                    pet: old.pet.upgrade(PetParents::<T>::get(pet_id)),
+                    owner: old.owner,
+                    burned_at: old.burned_at,
+                    compensation: old.compensation,
+                })
+            });
+
+            let (generations, links) = super::v5::bred_generations::<T>();
+            let mut recorded = 0u64;
+            for (child, (parent1, parent2)) in PetParents::<T>::iter() {
+                let generation = match crate::PetNfts::<T>::get(child) {
+                    Some(pet) => pet.generation,
+                    None => generations.get(&child).copied().unwrap_or(0).into(),
+                };
+                crate::PetGenealogy::<T>::insert(child, GenealogyRecord {
+                    parent1_id: Some(parent1),
+                    parent2_id: Some(parent2),
+                    generation,
+                    breed_block: Zero::zero(),
+                });
+                recorded = recorded.saturating_add(1);
+            }
+
+            log::info!(
+                target: "runtime::critter_nfts_pallet",
+                "Added the parents of {} bred pets ({} pets in all), {} parked pets and {} burned pets, and recorded the genealogy of {} bred pets for storage version 7",
+                bred,
+                translated,
+                parked,
+                burned,
+                recorded
+            );
+
+            // One extra read per living or burned pet for its `PetParents` entry. Both genealogy
+            // walks read every `PetParents` entry, and each child also reads its pet.
+            T::DbWeight::get().reads_writes(
+                translated
+                    .saturating_add(burned)
+                    .saturating_mul(2)
+                    .saturating_add(parked)
+                    .saturating_add(links)
+                    .saturating_add(recorded.saturating_mul(2)),
+                translated.saturating_add(parked).saturating_add(burned).saturating_add(recorded),
+            )
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((PetNfts::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let pets_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode pet count"))?;
+            // `iter` skips undecodable values, so a matching count means every pet decodes.
+            ensure!(crate::PetNfts::<T>::iter().count() as u64 == pets_before, "pets lost during migration");
+            for (pet_id, pet) in crate::PetNfts::<T>::iter() {
+                let parents = pet.parent1_id.zip(pet.parent2_id);
+                ensure!(parents == PetParents::<T>::get(pet_id), "parent IDs differ from PetParents");
+            }
+            for (child, (parent1, parent2)) in PetParents::<T>::iter() {
+                let record = crate::PetGenealogy::<T>::get(child)
+                    .ok_or(sp_runtime::TryRuntimeError::Other("bred pet without a genealogy record"))?;
+                ensure!(
+                    (record.parent1_id, record.parent2_id) == (Some(parent1), Some(parent2)),
+                    "genealogy parents differ from PetParents"
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// Storage version 6 -> 7, safe to queue unconditionally in the runtime.
+    pub type MigrateToV7<T> = VersionedMigration<
+        6,
+        7,
+        AddLineage<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,205 @@
+//! # Mint Previews
+//!
+//! A minted pet's charter attributes come from its DNA, which hashes the block's randomness
+//! seed with the owner, the next pet ID, the species, the name and the entropy sources listed
+//! in the `dna` module. This module lets the minting UI show what a mint would produce before
+//! the user commits, through the `mint_preview` runtime API. `MintPreviewMode` chooses how much
+//! is revealed:
+//!
+//! - `Exact`: the attributes a mint by `owner` would get if it were included in the current
+//!   block. The preview changes every block with the randomness seed, and is only exact while
+//!   no other pet is minted first and the mint's extrinsic index and account nonce match the
+//!   ones at the time of the preview.
+//! - `Distribution`: stat ranges and affinity odds over `MintPreviewSamples` candidates. The
+//!   candidates are the DNA a mint would get as each of the next `MintPreviewSamples` pet IDs,
+//!   starting with the next one, so the outcome of a mint in the current block is always among
+//!   them without being singled out.
+//!
+//! Both modes derive attributes with `CharterAttributes::from_dna`, the helper minting uses.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::Get, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::Permill;
+use sp_std::vec::Vec;
+use crate::species_care::{SpeciesCareProfile, SpeciesCareSystem};
+use crate::traits::DnaHashType;
+use crate::{Config, ElementType, Pallet, PetId};
+
+/// How much of a mint's outcome `mint_preview` reveals.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MintPreviewMode {
+    /// The exact attributes of a mint in the current block.
+    Exact,
+    /// Stat ranges and affinity odds over a set of candidate outcomes.
+    Distribution,
+}
+
+/// The charter attributes a pet is minted with, derived from its DNA.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CharterAttributes {
+    /// Base strength (5-20)
+    pub strength: u8,
+
+    /// Base agility (5-20)
+    pub agility: u8,
+
+    /// Base intelligence (5-20)
+    pub intelligence: u8,
+
+    /// Base vitality (5-20)
+    pub vitality: u8,
+
+    /// Primary elemental affinity
+    pub affinity: ElementType,
+}
+
+impl CharterAttributes {
+    /// Derives the charter attributes from a DNA hash. Deterministic; shared by every mint path
+    /// and by previews so they cannot drift.
+    pub fn from_dna(dna: &DnaHashType) -> Self {
+        Self {
+            strength: (dna[0] % 16) + 5,
+            agility: (dna[1] % 16) + 5,
+            intelligence: (dna[2] % 16) + 5,
+            vitality: (dna[3] % 16) + 5,
+            affinity: match dna[4] % 8 {
+                0 => ElementType::Fire, 1 => ElementType::Water, 2 => ElementType::Earth,
+                3 => ElementType::Air, 4 => ElementType::Tech, 5 => ElementType::Nature,
+                6 => ElementType::Mystic,
+                _ => ElementType::Neutral,
+            },
+        }
+    }
+}
+
+/// The lowest and highest value of a stat across the previewed candidates.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StatRange {
+    /// The lowest value
+    pub min: u8,
+
+    /// The highest value
+    pub max: u8,
+}
+
+impl StatRange {
+    fn of(value: u8) -> Self {
+        Self { min: value, max: value }
+    }
+
+    fn include(&mut self, value: u8) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Whether `value` lies within the range.
+    pub fn contains(&self, value: u8) -> bool {
+        self.min <= value && value <= self.max
+    }
+}
+
+/// What a mint would produce. In `Exact` mode every range is a single value and one affinity
+/// has all the odds.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct MintPreviewDistribution<BlockNumber> {
+    /// The mode the preview was computed in
+    pub mode: MintPreviewMode,
+
+    /// The block the preview holds for; it changes with the next block's randomness seed
+    pub block: BlockNumber,
+
+    /// The number of candidate outcomes covered
+    pub samples: u32,
+
+    /// Base strength across the candidates
+    pub strength: StatRange,
+
+    /// Base agility across the candidates
+    pub agility: StatRange,
+
+    /// Base intelligence across the candidates
+    pub intelligence: StatRange,
+
+    /// Base vitality across the candidates
+    pub vitality: StatRange,
+
+    /// The share of candidates with each affinity; affinities no candidate has are left out
+    pub affinities: Vec<(ElementType, Permill)>,
+
+    /// How the species responds to care
+    pub care_preferences: SpeciesCareProfile,
+}
+
+/// The preview type returned for a runtime.
+pub type MintPreviewDistributionOf<T> = MintPreviewDistribution<<T as frame_system::Config>::BlockNumber>;
+
+/// A system for previewing mints.
+pub struct MintPreviewSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> MintPreviewSystem<T> {
+    /// Previews a mint of `species` named `name` by `owner` in the current block, in the
+    /// configured `MintPreviewMode`.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account that would mint
+    /// * `species` - The species to mint
+    /// * `name` - The pet's name
+    ///
+    /// # Returns
+    ///
+    /// * `Option<MintPreviewDistributionOf<T>>` - The preview, or `None` if the species or name
+    ///   is too long to mint
+    pub fn preview(owner: &T::AccountId, species: Vec<u8>, name: Vec<u8>) -> Option<MintPreviewDistributionOf<T>> {
+        let species: BoundedVec<u8, T::MaxSpeciesNameLen> = species.try_into().ok()?;
+        let name: BoundedVec<u8, T::MaxPetNameLen> = name.try_into().ok()?;
+
+        // 1. Pick the candidate pet IDs: only the next one in exact mode.
+        let mode = T::MintPreviewMode::get();
+        let samples = match mode {
+            MintPreviewMode::Exact => 1,
+            MintPreviewMode::Distribution => T::MintPreviewSamples::get().max(1),
+        };
+        let next_pet_id = crate::NextPetId::<T>::get();
+        let candidates = (0..samples).map(|offset| next_pet_id.wrapping_add(offset as PetId));
+
+        // 2. Derive each candidate's attributes exactly as minting would.
+        let mut attributes = candidates
+            .map(|pet_id| CharterAttributes::from_dna(&Pallet::<T>::mint_dna(owner, pet_id, &species, &name)));
+        let first = attributes.next()?;
+        let mut preview = MintPreviewDistribution {
+            mode,
+            block: frame_system::Pallet::<T>::block_number(),
+            samples,
+            strength: StatRange::of(first.strength),
+            agility: StatRange::of(first.agility),
+            intelligence: StatRange::of(first.intelligence),
+            vitality: StatRange::of(first.vitality),
+            affinities: Vec::new(),
+            care_preferences: SpeciesCareSystem::<T>::profile(&species),
+        };
+        let mut affinity_counts: Vec<(ElementType, u32)> = sp_std::vec![(first.affinity, 1)];
+
+        for candidate in attributes {
+            preview.strength.include(candidate.strength);
+            preview.agility.include(candidate.agility);
+            preview.intelligence.include(candidate.intelligence);
+            preview.vitality.include(candidate.vitality);
+            match affinity_counts.iter_mut().find(|(affinity, _)| *affinity == candidate.affinity) {
+                Some((_, count)) => *count += 1,
+                None => affinity_counts.push((candidate.affinity, 1)),
+            }
+        }
+
+        // 3. Turn the affinity counts into odds.
+        preview.affinities = affinity_counts
+            .into_iter()
+            .map(|(affinity, count)| (affinity, Permill::from_rational(count, samples)))
+            .collect();
+
+        Some(preview)
+    }
+}
@@ -0,0 +1,295 @@
+//! # Neglect
+//!
+//! A pet not played with for more than `neglect_threshold_blocks` loses `neglect_mood_penalty`
+//! mood when a neglect check finds it, and `PetNeglected` is emitted. Anyone can check a pet
+//! with `apply_neglect_check`, but pets of inactive players would rarely be checked, so
+//! `on_initialize` also scans up to `MaxNeglectChecksPerBlock` pet IDs per block (zero turns the
+//! scan off). The scan resumes from `NeglectScanCursor` and wraps around at `NextPetId`, so
+//! every pet is checked once per pass; IDs without a pet are skipped.
+//!
+//! A pass over a small collection takes only a few blocks, so the scan penalizes a pet at most
+//! once per `neglect_threshold_blocks`: a pet whose state changed within the threshold, by an
+//! earlier penalty or otherwise, is left for a later pass.
+//!
+//! The off-chain worker sweeps the same way without using block weight. Each block it checks
+//! the next `NEGLECT_PAGE_SIZE` pet IDs from a cursor kept in persistent local storage, and
+//! submits the pets due a penalty, up to `MaxNeglectBatchPerBlock`, in an unsigned
+//! `apply_neglect_check_unsigned` transaction. The transaction pool accepts batches only from
+//! the local worker or in blocks, never from peers, and only while at least one of their pets is
+//! due. `LastNeglectBatchBlock` limits the chain to one batch per block. It is not a nonce: a
+//! batch included again in a later block is applied again, but penalizes only the pets due by
+//! then, exactly as a fresh batch would.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    pallet_prelude::{
+        InvalidTransaction, TransactionPriority, TransactionValidity, ValidTransaction, Weight,
+    },
+    traits::{ConstU32, Get},
+    BoundedVec,
+};
+use frame_system::{offchain::SubmitTransaction, pallet_prelude::BlockNumberFor};
+use sp_std::vec::Vec;
+use crate::game_params::GameParametersOf;
+use crate::{Call, Config, Error, Event, Pallet, PetId, PetNft};
+
+/// How many pet IDs the off-chain worker checks per block, and the most pets one unsigned batch
+/// can carry.
+pub const NEGLECT_PAGE_SIZE: u32 = 100;
+
+/// The persistent local storage key of the off-chain worker's cursor.
+pub const NEGLECT_CURSOR_KEY: &[u8] = b"critter-nfts::neglect-cursor";
+
+/// The priority of unsigned neglect batches in the transaction pool.
+pub const NEGLECT_BATCH_PRIORITY: TransactionPriority = TransactionPriority::MAX / 2;
+
+/// How many blocks an unsigned neglect batch stays valid in the transaction pool.
+pub const NEGLECT_BATCH_LONGEVITY: u64 = 3;
+
+/// The pets one unsigned neglect batch applies penalties to.
+pub type NeglectBatch = BoundedVec<PetId, ConstU32<NEGLECT_PAGE_SIZE>>;
+
+/// A system for applying neglect penalties.
+pub struct NeglectSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> NeglectSystem<T> {
+    /// Applies the neglect penalty to a pet if it is neglected. Backs `apply_neglect_check`.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, DispatchError>` - Whether the pet's mood dropped, or an error if the pet
+    ///   does not exist
+    pub fn check(pet_id: PetId) -> Result<bool, DispatchError> {
+        let now = frame_system::Pallet::<T>::block_number();
+        let params = Pallet::<T>::params();
+        crate::PetNfts::<T>::try_mutate(&pet_id, |pet_nft_opt| -> Result<bool, DispatchError> {
+            let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+            Ok(Self::penalize(pet, now, &params))
+        })
+    }
+
+    /// Checks the next `MaxNeglectChecksPerBlock` pet IDs from `NeglectScanCursor` and advances
+    /// the cursor, wrapping around at `NextPetId`.
+    ///
+    /// # Parameters
+    ///
+    /// * `now` - The current block
+    ///
+    /// # Returns
+    ///
+    /// * `Weight` - The weight consumed
+    pub fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+        let max_checks = T::MaxNeglectChecksPerBlock::get();
+        if max_checks == 0 {
+            return Weight::zero();
+        }
+        let db = T::DbWeight::get();
+        let next_pet_id = crate::NextPetId::<T>::get();
+        if next_pet_id == 0 {
+            return db.reads(1);
+        }
+
+        // Never check a pet twice in one block, however few there are.
+        let checks = max_checks.min(next_pet_id);
+        let params = Pallet::<T>::params();
+        let mut cursor = crate::NeglectScanCursor::<T>::get();
+        let mut penalized = 0u32;
+        for _ in 0..checks {
+            if cursor >= next_pet_id {
+                cursor = 0;
+            }
+            penalized += Self::check_due(cursor, now, &params) as u32;
+            cursor += 1;
+        }
+        crate::NeglectScanCursor::<T>::put(if cursor >= next_pet_id { 0 } else { cursor });
+
+        // Reads: NextPetId, GameParameters, NeglectScanCursor and each checked pet.
+        // Writes: NeglectScanCursor and each penalized pet.
+        db.reads(3 + checks as u64).saturating_add(db.writes(1 + penalized as u64))
+    }
+
+    /// Applies the neglect penalty to the pets of an unsigned batch that are due one. Backs
+    /// `apply_neglect_check_unsigned`; missing pets and pets not due are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_ids` - The pets to check
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the batch was applied, Err if it is too large or a batch was
+    ///   already applied in this block
+    pub fn apply_batch(pet_ids: NeglectBatch) -> DispatchResult {
+        ensure!(pet_ids.len() as u32 <= T::MaxNeglectBatchPerBlock::get(), Error::<T>::NeglectBatchTooLarge);
+        let now = frame_system::Pallet::<T>::block_number();
+        ensure!(
+            crate::LastNeglectBatchBlock::<T>::get().map_or(true, |last| last < now),
+            Error::<T>::NeglectBatchAlreadyApplied
+        );
+        crate::LastNeglectBatchBlock::<T>::put(now);
+
+        let params = Pallet::<T>::params();
+        for pet_id in pet_ids {
+            Self::check_due(pet_id, now, &params);
+        }
+        Ok(())
+    }
+
+    /// Validates an unsigned neglect batch for the transaction pool: it must be non-empty,
+    /// within `MaxNeglectBatchPerBlock`, the first batch of its block, and hold a pet due a
+    /// penalty.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_ids` - The batch's pets
+    ///
+    /// # Returns
+    ///
+    /// * `TransactionValidity` - The batch's validity, tagged with its block
+    pub fn validate_batch(pet_ids: &NeglectBatch) -> TransactionValidity {
+        let now = frame_system::Pallet::<T>::block_number();
+        if crate::LastNeglectBatchBlock::<T>::get().map_or(false, |last| last >= now) {
+            return InvalidTransaction::Stale.into();
+        }
+        if pet_ids.is_empty() || pet_ids.len() as u32 > T::MaxNeglectBatchPerBlock::get() {
+            return InvalidTransaction::Call.into();
+        }
+        // A batch none of whose pets is due would only spend block weight.
+        let params = Pallet::<T>::params();
+        let any_due = pet_ids
+            .iter()
+            .any(|pet_id| crate::PetNfts::<T>::get(pet_id).map_or(false, |pet| Self::is_due(&pet, now, &params)));
+        if !any_due {
+            return InvalidTransaction::Stale.into();
+        }
+
+        ValidTransaction::with_tag_prefix("CritterNftsNeglect")
+            .priority(NEGLECT_BATCH_PRIORITY)
+            .and_provides(now)
+            .longevity(NEGLECT_BATCH_LONGEVITY)
+            .propagate(true)
+            .build()
+    }
+
+    /// Checks the next `NEGLECT_PAGE_SIZE` pet IDs from the cursor in local storage and submits
+    /// the pets due a penalty, up to `MaxNeglectBatchPerBlock`, as an unsigned batch. Stops at a
+    /// full batch, so the next run resumes after its last pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `now` - The block the worker runs for
+    pub fn offchain_worker(now: BlockNumberFor<T>) {
+        let max_batch = T::MaxNeglectBatchPerBlock::get().min(NEGLECT_PAGE_SIZE);
+        let next_pet_id = crate::NextPetId::<T>::get();
+        if max_batch == 0 || next_pet_id == 0 {
+            return;
+        }
+
+        let kind = sp_core::offchain::StorageKind::PERSISTENT;
+        let mut cursor = sp_io::offchain::local_storage_get(kind, NEGLECT_CURSOR_KEY)
+            .and_then(|raw| PetId::decode(&mut &raw[..]).ok())
+            .unwrap_or(0);
+        let params = Pallet::<T>::params();
+        let mut due = Vec::new();
+        for _ in 0..NEGLECT_PAGE_SIZE.min(next_pet_id) {
+            if cursor >= next_pet_id {
+                cursor = 0;
+            }
+            if crate::PetNfts::<T>::get(cursor).map_or(false, |pet| Self::is_due(&pet, now, &params)) {
+                due.push(cursor);
+            }
+            cursor += 1;
+            if due.len() as u32 >= max_batch {
+                break;
+            }
+        }
+        sp_io::offchain::local_storage_set(kind, NEGLECT_CURSOR_KEY, &cursor.encode());
+        if due.is_empty() {
+            return;
+        }
+
+        // Cannot fail: the batch holds at most `NEGLECT_PAGE_SIZE` pets.
+        let pet_ids: NeglectBatch = due.try_into().unwrap_or_default();
+        let call = Call::<T>::apply_neglect_check_unsigned { pet_ids };
+        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+            frame_support::log::warn!(target: "runtime::critter-nfts", "failed to submit a neglect batch");
+        }
+    }
+
+    /// Whether an automatic check would penalize a pet: it has mood left to lose, has not been
+    /// played with past the neglect threshold, and its state has not changed within it.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    /// * `now` - The current block
+    /// * `params` - The game parameters
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the pet is due a penalty
+    pub fn is_due(pet: &PetNft<T>, now: BlockNumberFor<T>, params: &GameParametersOf<T>) -> bool {
+        pet.mood_indicator > 0
+            && params.neglect_mood_penalty > 0
+            && now.saturating_sub(pet.last_played_block) > params.neglect_threshold_blocks
+            && now.saturating_sub(pet.last_state_update_block) > params.neglect_threshold_blocks
+    }
+
+    /// Applies the neglect penalty to a pet if it exists and is due one, writing it back only
+    /// if it was penalized.
+    fn check_due(pet_id: PetId, now: BlockNumberFor<T>, params: &GameParametersOf<T>) -> bool {
+        crate::PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| -> Result<(), ()> {
+            let pet = pet_nft_opt.as_mut().ok_or(())?;
+            if !Self::is_due(pet, now, params) || !Self::penalize(pet, now, params) {
+                return Err(());
+            }
+            Ok(())
+        })
+        .is_ok()
+    }
+
+    /// Lowers a pet's mood by `neglect_mood_penalty` if it has not been played with for more
+    /// than `neglect_threshold_blocks`, emitting `PetNeglected` if its mood dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    /// * `now` - The current block
+    /// * `params` - The game parameters
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the pet's mood dropped
+    fn penalize(pet: &mut PetNft<T>, now: BlockNumberFor<T>, params: &GameParametersOf<T>) -> bool {
+        // Using saturating_sub to prevent underflow if now is very low for some reason.
+        let neglect_duration = now.saturating_sub(pet.last_played_block);
+        if neglect_duration <= params.neglect_threshold_blocks {
+            return false;
+        }
+
+        // Mood cannot go below 0.
+        let old_mood = pet.mood_indicator;
+        pet.mood_indicator = pet.mood_indicator.saturating_sub(params.neglect_mood_penalty);
+        pet.last_state_update_block = now;
+        if pet.mood_indicator == old_mood {
+            return false;
+        }
+
+        Pallet::<T>::deposit_event(Event::PetNeglected {
+            pet_id: pet.id,
+            old_mood,
+            new_mood: pet.mood_indicator,
+            neglect_duration,
+            timestamp: now,
+        });
+        true
+    }
+}
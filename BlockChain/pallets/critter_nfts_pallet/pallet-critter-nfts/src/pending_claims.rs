@@ -0,0 +1,188 @@
+//! # Pending Pet Claims
+//!
+//! Breeding pallets spend the parents' items and fees before asking this pallet to mint the
+//! child, so a mint that fails because the recipient already owns `MaxOwnedPets` pets costs them
+//! those resources. Calling pallets should check `SharedNftManager::can_receive_pet` first; for
+//! what still slips through, a runtime can opt into `OverflowPolicy::Park`.
+//!
+//! With parking on, a bred pet that does not fit is kept in `PendingClaims` instead of failing
+//! the mint. It has its ID and data but no owner, so it is not an NFT yet. The recipient claims
+//! it with `claim_pending_pet` once they have room, which mints it as of that block. A parked
+//! pet that is not claimed within `PendingClaimExpiry` blocks is burned: claiming it then, or
+//! anyone calling `clear_expired_pending_pet`, removes it for good.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    pallet_prelude::RuntimeDebug,
+    traits::Get,
+};
+use scale_info::TypeInfo;
+use sp_runtime::traits::Saturating;
+use crate::{Config, Error, Event, Pallet, PetId, PetNft};
+
+/// What breeding does when the recipient of a new pet already owns `MaxOwnedPets` pets.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum OverflowPolicy {
+    /// The mint fails with `ExceedMaxOwnedPets`.
+    Fail,
+    /// The pet is parked for the recipient to claim later.
+    Park,
+}
+
+/// A bred pet waiting for its recipient to make room.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct PendingClaim<T: Config> {
+    /// The account the pet was bred for
+    pub recipient: T::AccountId,
+
+    /// The pet, as it will be minted
+    pub pet: PetNft<T>,
+
+    /// The pet's parents, recorded in its lineage once claimed
+    pub parents: (PetId, PetId),
+
+    /// The last block in which the pet can be claimed
+    pub expires_at: frame_system::pallet_prelude::BlockNumberFor<T>,
+}
+
+/// A system for parking bred pets until their recipient has room.
+pub struct PendingClaimSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> PendingClaimSystem<T> {
+    /// Parks a bred pet for `recipient`, who has no room for it.
+    ///
+    /// # Parameters
+    ///
+    /// * `recipient` - The account the pet was bred for
+    /// * `pet` - The pet, with its ID already assigned
+    /// * `parents` - The pet's parents
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was parked, Err if the recipient has too many pets parked
+    pub fn park(recipient: &T::AccountId, pet: PetNft<T>, parents: (PetId, PetId)) -> DispatchResult {
+        let pet_id = pet.id;
+
+        // 1. Count the pet against the recipient's parked pets.
+        crate::PendingClaimsOf::<T>::try_mutate(recipient, |parked| {
+            parked.try_push(pet_id).map_err(|_| Error::<T>::TooManyPendingClaims)
+        })?;
+
+        // 2. Park it.
+        let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(T::PendingClaimExpiry::get());
+        crate::PendingClaims::<T>::insert(pet_id, PendingClaim {
+            recipient: recipient.clone(),
+            pet,
+            parents,
+            expires_at,
+        });
+
+        // 3. Emit event.
+        Pallet::<T>::deposit_event(Event::PetParked { recipient: recipient.clone(), pet_id, expires_at });
+
+        Ok(())
+    }
+
+    /// Claims a parked pet, minting it to the recipient. An expired pet is burned instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `sender` - The account claiming the pet; must be its recipient
+    /// * `pet_id` - The ID of the parked pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was claimed or the expired pet burned, Err otherwise
+    pub fn claim(sender: T::AccountId, pet_id: PetId) -> DispatchResult {
+        let claim = crate::PendingClaims::<T>::get(pet_id).ok_or(Error::<T>::PendingClaimNotFound)?;
+        ensure!(sender == claim.recipient, Error::<T>::NotPendingClaimRecipient);
+
+        // 1. Burn the pet if it was not claimed in time.
+        let current_block = frame_system::Pallet::<T>::block_number();
+        if current_block > claim.expires_at {
+            Self::burn(pet_id, &claim);
+            return Ok(());
+        }
+
+        // 2. The recipient must have made room.
+        ensure!(Pallet::<T>::remaining_pet_capacity(&sender) > 0, Error::<T>::ExceedMaxOwnedPets);
+
+        // 3. Mint the pet as of this block, so the wait does not count as neglect.
+        Self::remove(pet_id, &claim.recipient);
+        let mut pet = claim.pet;
+        // Parked pets are stamped with the block they were bred at until now.
+        let bred_at = pet.acquired_at_block;
+        pet.owner = sender.clone();
+        pet.acquired_at_block = current_block;
+        pet.last_fed_block = current_block;
+        pet.last_played_block = current_block;
+        pet.last_state_update_block = current_block;
+
+        Pallet::<T>::note_minted(pet_id, &sender, &pet.initial_species);
+        let generation = pet.generation;
+        let minted = Event::PetNftMinted {
+            owner: sender.clone(),
+            pet_id,
+            species: pet.initial_species.clone(),
+            dna_hash: pet.dna_hash,
+            base_strength: pet.base_strength,
+            base_agility: pet.base_agility,
+            base_intelligence: pet.base_intelligence,
+            base_vitality: pet.base_vitality,
+            elemental_affinity: pet.primary_elemental_affinity,
+            timestamp: current_block,
+            unix_time_ms: Pallet::<T>::unix_time_ms(),
+            parents: Some(claim.parents),
+        };
+        crate::PetNfts::<T>::insert(pet_id, pet);
+        crate::OwnerOfPet::<T>::try_mutate(&sender, |owned_pets| {
+            owned_pets.try_push(pet_id).map_err(|_| Error::<T>::ExceedMaxOwnedPets)
+        })?;
+        crate::PetNftOwner::<T>::insert(pet_id, sender.clone());
+        crate::lineage::LineageSystem::<T>::record_parents(pet_id, claim.parents.0, claim.parents.1);
+        crate::lineage::LineageSystem::<T>::record_genealogy(pet_id, claim.parents, generation, bred_at);
+
+        // 4. Emit events.
+        Pallet::<T>::deposit_event(minted);
+        Pallet::<T>::deposit_event(Event::PendingPetClaimed { owner: sender, pet_id });
+
+        Ok(())
+    }
+
+    /// Burns a parked pet whose claim window has passed. Callable by anyone.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the parked pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was burned, Err if it is not parked or not yet expired
+    pub fn clear_expired(pet_id: PetId) -> DispatchResult {
+        let claim = crate::PendingClaims::<T>::get(pet_id).ok_or(Error::<T>::PendingClaimNotFound)?;
+        ensure!(
+            frame_system::Pallet::<T>::block_number() > claim.expires_at,
+            Error::<T>::PendingClaimNotExpired
+        );
+        Self::burn(pet_id, &claim);
+
+        Ok(())
+    }
+
+    /// Removes an expired parked pet for good. Its ID is never reused.
+    fn burn(pet_id: PetId, claim: &PendingClaim<T>) {
+        Self::remove(pet_id, &claim.recipient);
+        Pallet::<T>::deposit_event(Event::PendingPetBurned { recipient: claim.recipient.clone(), pet_id });
+    }
+
+    /// Removes a parked pet from storage and from its recipient's parked pets.
+    fn remove(pet_id: PetId, recipient: &T::AccountId) {
+        crate::PendingClaims::<T>::remove(pet_id);
+        crate::PendingClaimsOf::<T>::mutate(recipient, |parked| parked.retain(|id| *id != pet_id));
+    }
+}
@@ -0,0 +1,223 @@
+//! # Pending Effects
+//!
+//! This module queues effects that other pallets want applied to a pet (XP grants, mood
+//! changes, owner notifications) so they do not have to apply them inside their own
+//! extrinsics. Effects are queued for the next block and applied in `on_idle` within the
+//! remaining block weight. Each effect is applied in its own storage layer: a failing effect
+//! is dropped with an event and never blocks the others.
+
+use codec::Encode;
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    storage::with_storage_layer,
+    traits::Get,
+    weights::Weight,
+    BoundedVec,
+};
+use sp_runtime::traits::{One, Saturating};
+use sp_std::vec::Vec;
+use crittercraft_traits::types::{DeferredEffect, DeferredEffectId};
+use crate::user_experience::UserExperienceSystem;
+use crate::{Config, Error, Event, Pallet, PetId};
+
+/// Title of the notifications sent by `DeferredEffect::Notify`.
+pub const DEFERRED_NOTIFICATION_TITLE: &[u8] = b"Pet update";
+
+/// A system for managing the pending effects queue.
+pub struct PendingEffectsSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> PendingEffectsSystem<T> {
+    /// The weight charged for applying a single effect.
+    pub fn effect_weight() -> Weight {
+        // Reads: PetNfts, PetNftOwner, UserNotifications. Writes: PetNfts, UserNotifications, PendingEffectBlocks.
+        Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 3))
+    }
+
+    /// Queues an effect for a pet, to be applied from the next block on.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    /// * `effect` - The effect to apply
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DeferredEffectId, DispatchError>` - The ID of the queued effect, or an error
+    pub fn defer(pet_id: PetId, effect: DeferredEffect) -> Result<DeferredEffectId, DispatchError> {
+        // 1. Validate the request.
+        ensure!(crate::PetNfts::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+
+        // 2. Queue the effect in the next block's bucket.
+        let due_block = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+        let effect_id = crate::NextDeferredEffectId::<T>::get();
+        crate::PendingEffects::<T>::try_mutate(due_block, |effects| {
+            effects.try_push((effect_id, pet_id, effect.clone()))
+        })
+        .map_err(|_| Error::<T>::TooManyPendingEffects)?;
+
+        // 3. Index the effect and make sure the next processing run starts no later than its block.
+        crate::NextDeferredEffectId::<T>::put(effect_id.saturating_add(1));
+        crate::PendingEffectBlocks::<T>::insert(effect_id, due_block);
+        if crate::PendingEffectsCursor::<T>::get().map_or(true, |cursor| cursor > due_block) {
+            crate::PendingEffectsCursor::<T>::put(due_block);
+        }
+
+        // 4. Emit event.
+        Pallet::<T>::deposit_event(Event::PendingEffectQueued { effect_id, pet_id, effect, due_block });
+
+        Ok(effect_id)
+    }
+
+    /// Returns the pet and effect of a queued effect.
+    pub fn pending(effect_id: DeferredEffectId) -> Option<(PetId, DeferredEffect)> {
+        let due_block = crate::PendingEffectBlocks::<T>::get(effect_id)?;
+        crate::PendingEffects::<T>::get(due_block)
+            .into_iter()
+            .find(|(id, _, _)| *id == effect_id)
+            .map(|(_, pet_id, effect)| (pet_id, effect))
+    }
+
+    /// Removes a queued effect before it is applied.
+    ///
+    /// # Parameters
+    ///
+    /// * `effect_id` - The ID of the queued effect
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the effect was cancelled, Err otherwise
+    pub fn cancel(effect_id: DeferredEffectId) -> DispatchResult {
+        let due_block = crate::PendingEffectBlocks::<T>::take(effect_id)
+            .ok_or(Error::<T>::PendingEffectNotFound)?;
+
+        let mut pet_id = None;
+        crate::PendingEffects::<T>::mutate_exists(due_block, |effects_opt| {
+            if let Some(effects) = effects_opt {
+                effects.retain(|(id, pet, _)| {
+                    if *id == effect_id {
+                        pet_id = Some(*pet);
+                    }
+                    *id != effect_id
+                });
+                if effects.is_empty() {
+                    *effects_opt = None;
+                }
+            }
+        });
+        let pet_id = pet_id.ok_or(Error::<T>::PendingEffectNotFound)?;
+
+        Pallet::<T>::deposit_event(Event::PendingEffectCancelled { effect_id, pet_id });
+
+        Ok(())
+    }
+
+    /// Applies due effects, oldest first, until the weight limit is reached.
+    /// Whatever does not fit stays queued for later blocks.
+    ///
+    /// # Parameters
+    ///
+    /// * `now` - The current block
+    /// * `limit` - The weight available for processing
+    ///
+    /// # Returns
+    ///
+    /// * `Weight` - The weight consumed
+    pub fn process(now: T::BlockNumber, limit: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+
+        // 1. Find the oldest block that may hold effects. Reading and updating the cursor are
+        // charged up front.
+        let mut used = db_weight.reads_writes(2, 1);
+        if used.any_gt(limit) {
+            return Weight::zero();
+        }
+        let mut block = match crate::PendingEffectsCursor::<T>::get() {
+            Some(block) => block,
+            None => return used,
+        };
+
+        // 2. Drain due buckets in order.
+        let bucket_weight = db_weight.reads_writes(1, 1);
+        let effect_weight = Self::effect_weight();
+        while block <= now {
+            if used.saturating_add(bucket_weight).any_gt(limit) {
+                break;
+            }
+            used.saturating_accrue(bucket_weight);
+
+            let effects = crate::PendingEffects::<T>::take(block).into_inner();
+            let mut applied = 0;
+            for (effect_id, pet_id, effect) in effects.iter() {
+                if used.saturating_add(effect_weight).any_gt(limit) {
+                    break;
+                }
+                used.saturating_accrue(effect_weight);
+                // Unindex before applying so an effect can never be applied twice.
+                crate::PendingEffectBlocks::<T>::remove(effect_id);
+                Self::apply(*effect_id, *pet_id, effect.clone());
+                applied += 1;
+            }
+
+            // 3. Put back what did not fit and resume from this bucket next time.
+            if applied < effects.len() {
+                let remaining: Vec<_> = effects.into_iter().skip(applied).collect();
+                crate::PendingEffects::<T>::insert(block, BoundedVec::truncate_from(remaining));
+                break;
+            }
+            block = block.saturating_add(One::one());
+        }
+
+        // 4. Every due bucket is drained: only effects queued for the next block can remain.
+        if block > now && !crate::PendingEffects::<T>::contains_key(block) {
+            crate::PendingEffectsCursor::<T>::kill();
+        } else {
+            crate::PendingEffectsCursor::<T>::put(block);
+        }
+
+        used
+    }
+
+    /// Applies one effect in its own storage layer, dropping it with an event if it fails.
+    fn apply(effect_id: DeferredEffectId, pet_id: PetId, effect: DeferredEffect) {
+        match with_storage_layer(|| Self::do_apply(pet_id, &effect)) {
+            Ok(()) => Pallet::<T>::deposit_event(Event::PendingEffectApplied { effect_id, pet_id }),
+            Err(error) => Pallet::<T>::deposit_event(Event::PendingEffectDropped { effect_id, pet_id, error }),
+        }
+    }
+
+    fn do_apply(pet_id: PetId, effect: &DeferredEffect) -> DispatchResult {
+        match effect {
+            DeferredEffect::GrantXp(amount) => crate::PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| {
+                let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+                Pallet::<T>::grant_xp(pet, *amount)?;
+                pet.last_state_update_block = frame_system::Pallet::<T>::block_number();
+                Ok(())
+            }),
+            DeferredEffect::ChangeMood(amount) => crate::PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| {
+                let pet = pet_nft_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+                pet.mood_indicator = (pet.mood_indicator as i16)
+                    .saturating_add(*amount)
+                    .clamp(0, T::MaxMoodValue::get() as i16) as u8;
+                pet.last_state_update_block = frame_system::Pallet::<T>::block_number();
+                Ok(())
+            }),
+            DeferredEffect::Notify { notification_type, priority } => {
+                let owner = crate::PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+                // The pet id is the notification's action, so clients can open the pet.
+                UserExperienceSystem::<T>::add_notification(
+                    owner,
+                    *notification_type,
+                    DEFERRED_NOTIFICATION_TITLE.to_vec(),
+                    Vec::new(),
+                    Vec::new(),
+                    *priority,
+                    pet_id.encode(),
+                )
+                .map(|_| ())
+            },
+        }
+    }
+}
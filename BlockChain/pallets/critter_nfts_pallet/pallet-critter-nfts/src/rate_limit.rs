@@ -0,0 +1,106 @@
+//! # Rate Limiting
+//!
+//! This module provides a per-account rate limiter shared by the gameplay extrinsics. Each
+//! account has a counter per action class that allows a fixed number of calls per window of
+//! blocks. Windows reset lazily: a counter whose window has ended is restarted by the next call
+//! instead of being cleared by a hook.
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use sp_runtime::traits::{Saturating, Zero};
+use crate::{Config, Error};
+
+/// Action class: feeding a pet.
+pub const RATE_LIMIT_FEED: u8 = 0;
+
+/// Action class: playing with a pet.
+pub const RATE_LIMIT_PLAY: u8 = 1;
+
+/// Action class: training a pet.
+pub const RATE_LIMIT_TRAIN: u8 = 2;
+
+/// Action class: gesture interactions.
+pub const RATE_LIMIT_GESTURE: u8 = 3;
+
+/// Action class: touch interactions.
+pub const RATE_LIMIT_TOUCH: u8 = 4;
+
+/// Action class: social interactions between pets.
+pub const RATE_LIMIT_SOCIAL: u8 = 5;
+
+/// Action class: recording and reinforcing memories.
+pub const RATE_LIMIT_MEMORY: u8 = 6;
+
+/// A per-account, per-action-class rate limiter.
+pub struct RateLimiter<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> RateLimiter<T> {
+    /// Counts a call of `class` by `account`, failing if the account has already made
+    /// `max_per_window` such calls in the current window.
+    ///
+    /// # Parameters
+    ///
+    /// * `account` - The account making the call
+    /// * `class` - The action class (one of the `RATE_LIMIT_*` constants)
+    /// * `max_per_window` - The calls allowed per window; zero disables the limit
+    /// * `window_blocks` - The length of a window in blocks; zero disables the limit
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the call is allowed, `RateLimitExceeded` otherwise
+    pub fn check_and_bump(
+        account: &T::AccountId,
+        class: u8,
+        max_per_window: u32,
+        window_blocks: T::BlockNumber,
+    ) -> DispatchResult {
+        // 1. Disabled classes are not tracked at all.
+        if max_per_window == 0 || window_blocks.is_zero() {
+            return Ok(());
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        crate::RateLimits::<T>::try_mutate(account, class, |entry| -> DispatchResult {
+            // 2. Restart the window if there is none yet or the last one has ended.
+            let (window_start, count) = match *entry {
+                Some((window_start, count)) if now < window_start.saturating_add(window_blocks) => (window_start, count),
+                _ => (now, 0),
+            };
+
+            // 3. Count the call if the window has room for it.
+            ensure!(count < max_per_window, Error::<T>::RateLimitExceeded);
+            *entry = Some((window_start, count.saturating_add(1)));
+
+            Ok(())
+        })
+    }
+
+    /// Applies the configured limit of an action class to a call by `account`.
+    ///
+    /// # Parameters
+    ///
+    /// * `account` - The account making the call
+    /// * `class` - The action class (one of the `RATE_LIMIT_*` constants)
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the call is allowed, `RateLimitExceeded` otherwise
+    pub fn check(account: &T::AccountId, class: u8) -> DispatchResult {
+        Self::check_and_bump(account, class, Self::limit_of(class), T::RateLimitWindow::get())
+    }
+
+    /// Returns the configured calls per window of an action class. Unknown classes are unlimited.
+    pub fn limit_of(class: u8) -> u32 {
+        match class {
+            RATE_LIMIT_FEED => T::FeedRateLimit::get(),
+            RATE_LIMIT_PLAY => T::PlayRateLimit::get(),
+            RATE_LIMIT_TRAIN => T::TrainRateLimit::get(),
+            RATE_LIMIT_GESTURE => T::GestureRateLimit::get(),
+            RATE_LIMIT_TOUCH => T::TouchRateLimit::get(),
+            RATE_LIMIT_SOCIAL => T::SocialRateLimit::get(),
+            RATE_LIMIT_MEMORY => T::MemoryRateLimit::get(),
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! # Pet Release
+//!
+//! Releasing a pet destroys it, so nothing may still refer to it. Before `release_pet` or
+//! `sacrifice_pets` destroys anything, every `ReleaseBlocker` is asked in order, and the first
+//! objection fails the call with an error naming it:
+//!
+//! - locks: a pet locked by another pallet (`NftLocked`), offered in a pending trade
+//!   (`ReleaseBlockedByTrade`) or leased out (`ReleaseBlockedByLease`)
+//! - auto-care: an active subscription (`ReleaseBlockedByAutoCare`); a lapsed one is cancelled
+//!   and refunded instead
+//! - lifecycle rewards: a reward waiting to be claimed (`UnclaimedLifecycleReward`)
+//! - `ReleaseBlockers`: the subsystems of other pallets, such as parties, social permissions
+//!   and battles
+//!
+//! Every per-pet storage item is removed with the pet, except its lineage and, for a burned
+//! pet, its `PetCemetery` record. Sync hooks are notified with `StateChangeType::Removed`, and
+//! other pallets can tell a released pet from one that never existed with `ExistenceChecker`.
+//!
+//! `force_release` is the root escape hatch: it has every blocker drop its references with
+//! `on_forced_release`, then destroys the pet. A pending trade whose offered pet is force
+//! released is void; accepting it cancels it.
+
+use crittercraft_traits::nft::ReleaseBlocker;
+use crittercraft_traits::types::ReleaseBlockReason;
+use frame_support::{dispatch::DispatchResult, ensure};
+use sp_runtime::traits::Zero;
+use crate::auto_care::AutoCareSystem;
+use crate::traits::LockerId;
+use crate::{Config, Error, Pallet, PetId};
+
+/// Blocks the release of locked pets.
+pub struct LockBlocker<T>(std::marker::PhantomData<T>);
+
+impl<T: Config> ReleaseBlocker<PetId> for LockBlocker<T> {
+    fn blocks_release(pet_id: &PetId) -> Option<ReleaseBlockReason> {
+        crate::LockedNfts::<T>::get(pet_id).map(|lock| match lock.locker {
+            LockerId::Trade => ReleaseBlockReason::PendingTrade,
+            LockerId::Lease => ReleaseBlockReason::Leased,
+            _ => ReleaseBlockReason::Locked,
+        })
+    }
+
+    fn on_forced_release(pet_id: &PetId) {
+        crate::LockedNfts::<T>::remove(pet_id);
+    }
+}
+
+/// Blocks the release of pets with an active auto-care subscription, and cancels lapsed ones.
+pub struct AutoCareBlocker<T>(std::marker::PhantomData<T>);
+
+impl<T: Config> ReleaseBlocker<PetId> for AutoCareBlocker<T> {
+    fn blocks_release(pet_id: &PetId) -> Option<ReleaseBlockReason> {
+        let subscription = crate::CareSubscriptions::<T>::get(pet_id)?;
+        if subscription.active {
+            return Some(ReleaseBlockReason::AutoCareScheduled);
+        }
+        // A lapsed subscription only holds a refund; pay it out rather than block.
+        AutoCareSystem::<T>::cancel(subscription.owner, *pet_id)
+            .err()
+            .map(|_| ReleaseBlockReason::AutoCareScheduled)
+    }
+
+    fn on_forced_release(pet_id: &PetId) {
+        if let Some(subscription) = crate::CareSubscriptions::<T>::get(pet_id) {
+            if AutoCareSystem::<T>::cancel(subscription.owner, *pet_id).is_err() {
+                // The refund failed; the prepaid balance stays in the auto-care account.
+                crate::CareSubscriptions::<T>::remove(pet_id);
+            }
+        }
+    }
+}
+
+/// Blocks the release of pets with an unclaimed lifecycle reward.
+pub struct LifecycleRewardBlocker<T>(std::marker::PhantomData<T>);
+
+impl<T: Config> ReleaseBlocker<PetId> for LifecycleRewardBlocker<T> {
+    fn blocks_release(pet_id: &PetId) -> Option<ReleaseBlockReason> {
+        (!crate::DeferredLifecycleRewards::<T>::get(pet_id).is_zero()).then_some(ReleaseBlockReason::UnclaimedReward)
+    }
+
+    fn on_forced_release(pet_id: &PetId) {
+        // The reward is forfeited and stays in `LifecycleRewardPot`.
+        crate::DeferredLifecycleRewards::<T>::remove(pet_id);
+    }
+}
+
+/// Every release blocker: the pallet's own, then those of other pallets.
+pub type ReleaseBlockers<T> = (
+    LockBlocker<T>,
+    AutoCareBlocker<T>,
+    LifecycleRewardBlocker<T>,
+    <T as crate::config::NftCoreConfig>::ReleaseBlockers,
+);
+
+/// A system for releasing pets.
+pub struct ReleaseSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> ReleaseSystem<T> {
+    /// Checks that nothing blocks a pet's release, cleaning up expired references on the way.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet can be released, Err naming the first blocker otherwise
+    pub fn ensure_releasable(pet_id: PetId) -> DispatchResult {
+        match ReleaseBlockers::<T>::blocks_release(&pet_id) {
+            None => Ok(()),
+            Some(reason) => Err(match reason {
+                ReleaseBlockReason::Locked => Error::<T>::NftLocked,
+                ReleaseBlockReason::PendingTrade => Error::<T>::ReleaseBlockedByTrade,
+                ReleaseBlockReason::Leased => Error::<T>::ReleaseBlockedByLease,
+                ReleaseBlockReason::InParty => Error::<T>::ReleaseBlockedByParty,
+                ReleaseBlockReason::SocialPermissionTarget => Error::<T>::ReleaseBlockedBySocialPermission,
+                ReleaseBlockReason::AutoCareScheduled => Error::<T>::ReleaseBlockedByAutoCare,
+                ReleaseBlockReason::UnclaimedReward => Error::<T>::UnclaimedLifecycleReward,
+                ReleaseBlockReason::InBattle => Error::<T>::ReleaseBlockedByBattle,
+            }
+            .into()),
+        }
+    }
+
+    /// Releases a pet its owner no longer wants.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account releasing the pet; must own it
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was released, Err otherwise
+    pub fn release(owner: T::AccountId, pet_id: PetId) -> DispatchResult {
+        let pet_owner = crate::PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        ensure!(pet_owner == owner, Error::<T>::NotOwner);
+        Self::ensure_releasable(pet_id)?;
+
+        Pallet::<T>::do_release_pet(pet_id)
+    }
+
+    /// Releases a pet whatever holds it, after every blocker has dropped its references.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet was released, Err if it does not exist
+    pub fn force_release(pet_id: PetId) -> DispatchResult {
+        ensure!(crate::PetNfts::<T>::contains_key(pet_id), Error::<T>::PetNotFound);
+        ReleaseBlockers::<T>::on_forced_release(&pet_id);
+
+        Pallet::<T>::do_release_pet(pet_id)
+    }
+}
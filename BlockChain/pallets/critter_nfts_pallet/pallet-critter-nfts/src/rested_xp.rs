@@ -0,0 +1,92 @@
+//! # Rested XP
+//!
+//! This module rewards owners for rotating among their pets. A pet that goes without gaining
+//! XP builds up rested XP, `RestedXpPerBlock` per block up to `MaxRestedXp`. The next XP grant
+//! through `Pallet::grant_xp` matches up to that much XP with a `RestedXpBonus` bonus and
+//! depletes the rested XP it matched.
+//!
+//! Rested XP is tracked lazily in `RestedXp` as the amount accrued when last settled and the
+//! block it was settled at. A pet starts accruing at its first XP grant, and does not accrue
+//! while it is locked (e.g. in a battle): the lock and unlock paths settle the pet, so the
+//! locked span never counts.
+
+use frame_support::traits::Get;
+use sp_runtime::SaturatedConversion;
+use crate::{Config, PetId};
+
+/// A system for accruing and spending rested XP.
+pub struct RestedXpSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> RestedXpSystem<T> {
+    /// Returns a pet's current rested XP without writing to storage.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet to check
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The rested XP available to the pet's next grant
+    pub fn current(pet_id: PetId) -> u32 {
+        let (accrued, last_update) = match crate::RestedXp::<T>::get(pet_id) {
+            Some(state) => state,
+            None => return 0,
+        };
+        if crate::LockedNfts::<T>::contains_key(pet_id) {
+            return accrued;
+        }
+
+        let elapsed = frame_system::Pallet::<T>::block_number()
+            .saturating_sub(last_update)
+            .saturated_into::<u32>();
+        accrued
+            .saturating_add(elapsed.saturating_mul(T::RestedXpPerBlock::get()))
+            .min(T::MaxRestedXp::get())
+    }
+
+    /// Brings a pet's stored rested XP up to the current block.
+    /// Called before the pet is locked or unlocked, so the locked span accrues nothing.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet to settle
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The pet's rested XP as of the current block
+    pub fn settle(pet_id: PetId) -> u32 {
+        let rested = Self::current(pet_id);
+        crate::RestedXp::<T>::insert(pet_id, (rested, frame_system::Pallet::<T>::block_number()));
+        rested
+    }
+
+    /// Computes the bonus for an XP grant and spends the rested XP it matched.
+    /// Accrual restarts from the current block, since the pet has just gained XP.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The pet receiving the grant
+    /// * `amount` - The XP being granted, before the bonus
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The bonus XP to add on top of `amount`
+    pub fn consume(pet_id: PetId, amount: u32) -> u32 {
+        // 1. Settle the rested XP accrued since the last update.
+        let rested = Self::current(pet_id);
+
+        // 2. Match as much of the grant as there is rested XP, and pay the bonus on that part.
+        let matched = amount.min(rested);
+        let bonus = T::RestedXpBonus::get().mul_floor(matched);
+
+        // 3. Deplete the matched rested XP.
+        crate::RestedXp::<T>::insert(
+            pet_id,
+            (rested.saturating_sub(matched), frame_system::Pallet::<T>::block_number()),
+        );
+
+        bonus
+    }
+}
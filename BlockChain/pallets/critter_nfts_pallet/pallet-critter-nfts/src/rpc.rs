@@ -0,0 +1,100 @@
+//! # Pet NFTs RPC
+//!
+//! Serves the pet profile queries of `PetNftsApi` over JSON-RPC, so front-ends can read a pet
+//! without building `state_call` payloads. Nodes merge `PetNftsRpc::new(client).into_rpc()` into
+//! their RPC module.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use crate::runtime_api::PetNftsApi;
+use crate::ui_bridge::UiPetProfile;
+use crate::PetId;
+
+/// The error code of a failed runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+
+#[rpc(client, server)]
+pub trait PetNftsRpcApi<BlockHash, AccountId, BlockNumber> {
+    /// Returns a pet's UI profile at the given block, or the best block.
+    #[method(name = "petNfts_getPetProfile")]
+    fn get_pet_profile(&self, pet_id: PetId, at: Option<BlockHash>) -> RpcResult<Option<UiPetProfile<AccountId>>>;
+
+    /// Returns the pets `owner` owns at the given block, or the best block.
+    #[method(name = "petNfts_getOwnedPets")]
+    fn get_owned_pets(&self, owner: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<PetId>>;
+
+    /// Returns a pet's level, mood, species and the block of its last state change at the
+    /// given block, or the best block.
+    #[method(name = "petNfts_getPetSummary")]
+    fn get_pet_summary(&self, pet_id: PetId, at: Option<BlockHash>) -> RpcResult<Option<(u32, u8, Vec<u8>, BlockNumber)>>;
+}
+
+/// The pet NFTs RPC handler, backed by a client of the runtime.
+pub struct PetNftsRpc<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> PetNftsRpc<C, Block> {
+    /// Creates a handler calling into `client`'s runtime.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: PhantomData }
+    }
+}
+
+/// Converts a failed runtime API call into a JSON-RPC error.
+fn runtime_error(message: &'static str, error: impl ToString) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(error.to_string()))))
+}
+
+impl<C, Block, AccountId, BlockNumber> PetNftsRpcApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber>
+    for PetNftsRpc<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: PetNftsApi<Block, AccountId, BlockNumber>,
+    AccountId: Codec + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    BlockNumber: Codec + serde::Serialize + Send + Sync + 'static,
+{
+    fn get_pet_profile(
+        &self,
+        pet_id: PetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<UiPetProfile<AccountId>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .get_pet_profile(at, pet_id)
+            .map_err(|error| runtime_error("Unable to get the pet profile", error))
+    }
+
+    fn get_owned_pets(&self, owner: AccountId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<PetId>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .get_owned_pets(at, owner)
+            .map_err(|error| runtime_error("Unable to get the owned pets", error))
+    }
+
+    fn get_pet_summary(
+        &self,
+        pet_id: PetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<(u32, u8, Vec<u8>, BlockNumber)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .get_pet_summary(at, pet_id)
+            .map_err(|error| runtime_error("Unable to get the pet summary", error))
+    }
+}
@@ -0,0 +1,107 @@
+//! # Runtime API
+//!
+//! This module declares the runtime APIs exposed by pallet-critter-nfts.
+//! They let front-ends and partners query pet data and dry-run inputs
+//! without submitting (and paying for) transactions.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+use crate::dossier::PetDossier;
+use crate::effective_stats::EffectiveStats;
+use crate::featured::FeaturedPets;
+use crate::footprint::StorageFootprint;
+use crate::input_catalog::InputCatalog;
+use crate::kinds::InteractionKind;
+use crate::lineage::LineageTree;
+use crate::mint_preview::MintPreviewDistribution;
+use crate::social::ReactionPreview;
+use crate::ui_bridge::UiPetProfile;
+use crate::{BatchMintValidation, PetId, SpeciesPopulationRecord};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query pallet-critter-nfts state.
+    pub trait PetNftsApi<AccountId, BlockNumber>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Dry-runs the validation performed by `batch_mint_pet_nfts` for `owner`.
+        /// Returns one result per entry, in input order.
+        fn validate_batch_mint(owner: AccountId, pets: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<BatchMintValidation>;
+
+        /// Returns the pet's quality score used for marketplace price hints, or `None` if it does not exist.
+        fn quality_score(pet_id: PetId) -> Option<u32>;
+
+        /// Returns the pet's stats after every modifier, as battle, quests and the UI see them,
+        /// or `None` if it does not exist.
+        fn effective_stats(pet_id: PetId) -> Option<EffectiveStats>;
+
+        /// Returns how many pets of `species` were ever minted and released, for game balance telemetry.
+        fn species_population(species: Vec<u8>) -> SpeciesPopulationRecord;
+
+        /// Previews how a pet would react to `interaction`, for UI hover hints. Returns the
+        /// possible mood delta range and a reaction class, or `None` if the pet does not exist.
+        /// Outcomes that depend on randomness are covered by the range rather than predicted.
+        fn preview_reaction(pet_id: PetId, interaction: InteractionKind) -> Option<ReactionPreview>;
+
+        /// Returns a pet's family tree up to `max_depth` generations of ancestors, in one call.
+        /// The tree is capped at `MaxLineageNodes` pets and flagged as truncated when cut short.
+        /// Returns `None` if the pet does not exist and has no recorded parents.
+        fn pet_lineage(pet_id: PetId, max_depth: u8) -> Option<LineageTree<AccountId>>;
+
+        /// Returns up to `limit` descendants of a pet, nearest generations first.
+        fn descendants_of(pet_id: PetId, limit: u32) -> Vec<PetId>;
+
+        /// Returns the entry count and encoded size of each of a pet's auxiliary storage maps,
+        /// measured by reading them, for finding the pets behind state bloat.
+        fn pet_storage_footprint(pet_id: PetId) -> StorageFootprint;
+
+        /// Returns up to `limit` pets with the largest tracked auxiliary storage size, largest
+        /// first, as (pet_id, bytes). The tracked size is approximate; see the `footprint` module.
+        fn heaviest_pets(limit: u32) -> Vec<(PetId, u32)>;
+
+        /// Returns the calls counted into each deprecated interface method, as
+        /// (interface, method, calls). The codes are listed in the `deprecation` module.
+        fn deprecated_call_counts() -> Vec<(u8, u8, u64)>;
+
+        /// Previews what minting `species` named `name` as `owner` would produce in the current
+        /// block: the exact charter attributes, or stat ranges and affinity odds, depending on
+        /// `MintPreviewMode`. Changes every block. Returns `None` if the species or name is too long.
+        fn mint_preview(owner: AccountId, species: Vec<u8>, name: Vec<u8>) -> Option<MintPreviewDistribution<BlockNumber>>;
+
+        /// Returns the current featured pets with their summaries, in the order they were drawn.
+        /// Pets that no longer exist are left out. Returns `None` before the first rotation.
+        fn featured_pets() -> Option<FeaturedPets<AccountId, BlockNumber>>;
+
+        /// Returns every gesture and touch area `process_gesture` and `process_touch` accept,
+        /// with their effects, gates and cooldowns, in ID order. Empty without the
+        /// `interactive-sessions` feature.
+        fn input_catalog() -> InputCatalog;
+
+        /// Returns everything the UI shows on a pet's profile page, traits, skills,
+        /// achievements and memories included, or `None` if the pet does not exist.
+        fn get_pet_profile(pet_id: PetId) -> Option<UiPetProfile<AccountId>>;
+
+        /// Returns the pets `owner` owns.
+        fn get_owned_pets(owner: AccountId) -> Vec<PetId>;
+
+        /// Returns a pet's level, mood, species and the block of its last state change, for
+        /// lists that do not need a whole profile, or `None` if the pet does not exist.
+        fn get_pet_summary(pet_id: PetId) -> Option<(u32, u8, Vec<u8>, BlockNumber)>;
+    }
+
+    /// The API to export everything known about a pet, for data requests and support tickets.
+    /// Kept apart from `PetNftsApi` because it also needs the runtime's pet record type.
+    pub trait PetDossierApi<AccountId, BlockNumber, Pet>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Pet: Codec,
+    {
+        /// Returns the `sections` of a pet's dossier, a bitmask of the `DOSSIER_*` constants in
+        /// the `dossier` module. Each list section is capped at `SECTION_SIZE_LIMIT` bytes and
+        /// flagged when truncated; a whole dossier stays within `DOSSIER_SIZE_LIMIT` bytes.
+        /// Returns `None` if the pet does not exist.
+        fn pet_dossier(pet_id: PetId, sections: u32) -> Option<PetDossier<AccountId, BlockNumber, Pet>>;
+    }
+}
@@ -0,0 +1,131 @@
+//! # Pet Sacrifices
+//!
+//! An economy sink: an owner can burn pets they no longer want to make another of their pets
+//! permanently stronger. `sacrifice_pets` burns up to `MaxSacrificesPerCall` pets at once and adds
+//! the resulting bonus to each of the target's bonus stats.
+//!
+//! A sacrificed pet is worth `POINTS_PER_LEVEL` points per level plus its quality score. The pets
+//! count most valuable first, each half as much as the one before, so a pile of weak pets is worth
+//! little more than the best of them. Every `POINTS_PER_BONUS` points make one bonus point.
+//!
+//! The bonus a pet has received from sacrifices over its lifetime is kept in `SacrificeBonus` and
+//! capped at `MaxSacrificeBonus`; points beyond the cap are lost. Sacrificed pets are released
+//! with all their per-pet state, each with a `PetReleased` event, and only if nothing blocks
+//! their release (see `release`).
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use sp_runtime::{traits::{Saturating, Zero}, DispatchError, FixedPointNumber, FixedU128};
+use sp_std::vec::Vec;
+use crate::sync::{StateChangeType, SyncHookManager};
+use crate::release::ReleaseSystem;
+use crate::valuation::PetValuationSystem;
+use crate::{Config, Error, Event, Pallet, PetId};
+
+/// The points a sacrificed pet is worth per level, on top of its quality score.
+pub const POINTS_PER_LEVEL: u32 = 10;
+
+/// The points that make one bonus point.
+pub const POINTS_PER_BONUS: u32 = 100;
+
+/// A system for sacrificing pets to strengthen another.
+pub struct SacrificeSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> SacrificeSystem<T> {
+    /// Burns `sacrificed` to add a bonus to `target`'s bonus stats.
+    ///
+    /// # Parameters
+    ///
+    /// * `owner` - The account sacrificing the pets; must own the target and every sacrificed pet
+    /// * `target` - The pet receiving the bonus
+    /// * `sacrificed` - The pets to burn
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u8, DispatchError>` - The bonus granted to each bonus stat, or an error
+    pub fn sacrifice(
+        owner: T::AccountId,
+        target: PetId,
+        sacrificed: Vec<PetId>,
+    ) -> Result<u8, DispatchError> {
+        // 1. Validate the list and the pets.
+        ensure!(!sacrificed.is_empty(), Error::<T>::NoPetsToSacrifice);
+        ensure!(sacrificed.len() as u32 <= T::MaxSacrificesPerCall::get(), Error::<T>::TooManySacrifices);
+        ensure!(!sacrificed.contains(&target), Error::<T>::CannotSacrificeTarget);
+        for (index, pet_id) in sacrificed.iter().enumerate() {
+            ensure!(!sacrificed[index + 1..].contains(pet_id), Error::<T>::DuplicateSacrifice);
+        }
+
+        let target_owner = crate::PetNftOwner::<T>::get(target).ok_or(Error::<T>::PetNotFound)?;
+        ensure!(target_owner == owner, Error::<T>::NotOwner);
+
+        let mut points = Vec::with_capacity(sacrificed.len());
+        for pet_id in &sacrificed {
+            let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+            ensure!(pet.owner == owner, Error::<T>::NotOwner);
+            ReleaseSystem::<T>::ensure_releasable(*pet_id)?;
+            let quality = PetValuationSystem::<T>::quality_score(*pet_id).unwrap_or(0);
+            points.push(pet.level.saturating_mul(POINTS_PER_LEVEL).saturating_add(quality));
+        }
+
+        // 2. Grant the bonus, up to what is left under the cap.
+        let received = crate::SacrificeBonus::<T>::get(target);
+        let granted = Self::bonus(&points)
+            .min(T::MaxSacrificeBonus::get().saturating_sub(received) as u32) as u8;
+        if granted > 0 {
+            Self::grant(target, granted)?;
+            crate::SacrificeBonus::<T>::insert(target, received.saturating_add(granted));
+        }
+
+        // 3. Release the sacrificed pets.
+        let count = sacrificed.len() as u32;
+        for pet_id in sacrificed {
+            Pallet::<T>::do_release_pet(pet_id)?;
+        }
+
+        Pallet::<T>::deposit_event(Event::PetsSacrificed { target, count, bonus_granted: granted });
+        Ok(granted)
+    }
+
+    /// Computes the bonus that pets worth `points` grant, before the cap. The pets count most
+    /// valuable first, the n-th at 1/2^n of its points.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - The points of each sacrificed pet, in any order
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The bonus points
+    pub fn bonus(points: &[u32]) -> u32 {
+        let mut sorted = points.to_vec();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        let total = sorted.iter().enumerate().fold(FixedU128::zero(), |total, (index, points)| {
+            let share = FixedU128::from_rational(1, 1u128.checked_shl(index as u32).unwrap_or(u128::MAX));
+            total.saturating_add(share.saturating_mul(FixedU128::saturating_from_integer(*points)))
+        });
+        (total / FixedU128::saturating_from_integer(POINTS_PER_BONUS)).saturating_mul_int(1u32)
+    }
+
+    /// Adds `bonus` to each of a pet's bonus stats.
+    fn grant(pet_id: PetId, bonus: u8) -> DispatchResult {
+        let now = frame_system::Pallet::<T>::block_number();
+        let version = crate::PetNfts::<T>::try_mutate(pet_id, |pet_opt| -> Result<u32, DispatchError> {
+            let pet = pet_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
+            pet.bonus_strength = pet.bonus_strength.saturating_add(bonus);
+            pet.bonus_agility = pet.bonus_agility.saturating_add(bonus);
+            pet.bonus_intelligence = pet.bonus_intelligence.saturating_add(bonus);
+            pet.bonus_vitality = pet.bonus_vitality.saturating_add(bonus);
+            pet.state_version = pet.state_version.saturating_add(1);
+            pet.last_state_update_block = now;
+            pet.sync_flags.set_dirty(StateChangeType::Stats);
+            Ok(pet.state_version)
+        })?;
+        crate::PetStateVersions::<T>::insert(pet_id, version);
+
+        SyncHookManager::<T>::notify_hooks(pet_id, StateChangeType::Stats, version, now, None)
+            .map_err(|_| Error::<T>::SynchronizationFailed.into())
+    }
+}
@@ -458,8 +458,8 @@ impl<T: Config> SeasonalEventSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `DispatchResult` - Ok if successful, Err otherwise
-    pub fn update_active_events() -> DispatchResult {
+    /// * `Result<u32, DispatchError>` - The number of events ended, or an error
+    pub fn update_active_events() -> Result<u32, DispatchError> {
         // Get the current block number
         let current_block = frame_system::Pallet::<T>::block_number();
         
@@ -467,13 +467,37 @@ impl<T: Config> SeasonalEventSystem<T> {
         let active_events = crate::ActiveSeasonalEvents::<T>::get();
         
         // Check each event
+        let mut ended = 0;
         for (event_id, _, end_time) in active_events {
             // If the event has expired, end it
             if current_block >= end_time {
                 Self::end_event(event_id)?;
+                ended += 1;
             }
         }
         
-        Ok(())
+        Ok(ended)
+    }
+    
+    /// The weight of an update that ended `ended` events.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `ended` - The number of events ended
+    /// 
+    /// # Returns
+    /// 
+    /// * `Weight` - The weight to charge
+    pub fn update_weight(
+        ended: u32,
+    ) -> Weight {
+        // Reads: ActiveSeasonalEvents. Per ended event, reads and writes: ActiveSeasonalEvents.
+        Weight::from_parts(10_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(
+                Weight::from_parts(10_000, 0)
+                    .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                    .saturating_mul(ended as u64),
+            )
     }
 }
\ No newline at end of file
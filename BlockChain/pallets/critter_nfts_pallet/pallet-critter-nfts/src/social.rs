@@ -12,6 +12,10 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, Percent};
+use crittercraft_traits::bounded::push_rotating;
+use crate::buffs::BuffSystem;
+use crate::kinds::{BuffKind, InteractionKind};
 use crate::{Config, Error, PetId, PetNft};
 
 /// Represents a social interaction between two pets.
@@ -24,7 +28,7 @@ pub struct SocialInteraction<T: Config> {
     pub pet_id_2: PetId,
     
     /// The type of interaction
-    pub interaction_type: u8,
+    pub interaction_type: InteractionKind,
     
     /// The outcome of the interaction
     pub outcome: u8,
@@ -61,18 +65,52 @@ pub struct SocialBond {
     pub last_interaction: u64,
 }
 
-/// Social interaction types.
-pub enum InteractionType {
-    Play = 0,
-    Greet = 1,
-    Share = 2,
-    Compete = 3,
-    Teach = 4,
-    Learn = 5,
-    Comfort = 6,
-    Protect = 7,
+/// A registered social interaction type and the effects it applies.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SocialInteractionTypeInfo {
+    /// The display name of the interaction type
+    pub name: BoundedVec<u8, ConstU32<32>>,
+    
+    /// The base mood change for the initiating pet
+    pub mood_effect_1: i8,
+    
+    /// The base mood change for the other pet
+    pub mood_effect_2: i8,
+    
+    /// The change in bond strength between the two pets
+    pub bond_delta: i8,
+    
+    /// The number of blocks before the same pair can repeat this interaction type
+    pub cooldown_blocks: u32,
+    
+    /// The minimum level both pets must have
+    pub min_level: u32,
 }
 
+/// The interaction types registered at genesis: Play Together, Groom, Spar and Explore.
+pub fn default_interaction_types() -> Vec<(InteractionKind, SocialInteractionTypeInfo)> {
+    let entry = |name: &[u8], mood_effect_1, mood_effect_2, bond_delta, cooldown_blocks, min_level| {
+        SocialInteractionTypeInfo {
+            name: name.to_vec().try_into().expect("default names fit the bound; qed"),
+            mood_effect_1,
+            mood_effect_2,
+            bond_delta,
+            cooldown_blocks,
+            min_level,
+        }
+    };
+    
+    sp_std::vec![
+        (InteractionKind::Play, entry(b"Play Together", 10, 10, 5, 100, 1)),
+        (InteractionKind::Comfort, entry(b"Groom", 5, 8, 3, 50, 1)),
+        (InteractionKind::Compete, entry(b"Spar", -2, -2, 2, 200, 5)),
+        (InteractionKind::Explore, entry(b"Explore", 8, 8, 4, 300, 3)),
+    ]
+}
+
+/// Social interaction types, now the typed `crate::kinds::InteractionKind`.
+pub type InteractionType = crate::kinds::InteractionKind;
+
 /// Social bond types.
 pub enum BondType {
     Friend = 0,
@@ -94,6 +132,71 @@ pub enum InteractionOutcome {
     VeryNegative = 4,
 }
 
+/// How a pet is expected to react to an interaction.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ReactionClass {
+    /// The pet's mood is expected to rise by at least `ECSTATIC_MOOD_DELTA`.
+    Ecstatic,
+    /// The pet's mood is expected to rise.
+    Pleased,
+    /// The pet's mood is not expected to change.
+    Indifferent,
+    /// The pet's mood is expected to drop.
+    Annoyed,
+    /// The interaction cannot take place with this pet.
+    Refuses,
+}
+
+/// Expected mood rises at or above this are previewed as `ReactionClass::Ecstatic`.
+pub const ECSTATIC_MOOD_DELTA: i16 = 10;
+
+impl ReactionClass {
+    /// Classifies a mood delta range by its midpoint.
+    pub fn from_mood_delta_range(min_mood_delta: i16, max_mood_delta: i16) -> Self {
+        let midpoint = (min_mood_delta + max_mood_delta) / 2;
+        if midpoint >= ECSTATIC_MOOD_DELTA {
+            ReactionClass::Ecstatic
+        } else if midpoint > 0 {
+            ReactionClass::Pleased
+        } else if midpoint == 0 {
+            ReactionClass::Indifferent
+        } else {
+            ReactionClass::Annoyed
+        }
+    }
+}
+
+/// The predicted reaction of a pet to an interaction.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ReactionPreview {
+    /// The smallest mood change the interaction can apply to the pet
+    pub min_mood_delta: i16,
+    
+    /// The largest mood change the interaction can apply to the pet
+    pub max_mood_delta: i16,
+    
+    /// The expected reaction
+    pub reaction: ReactionClass,
+    
+    /// The mood gain buff included in the mood deltas
+    pub mood_buff: Percent,
+}
+
+/// Returns a pet's mood after an interaction's mood effect. Rises are capped at `max_mood`
+/// and drops at zero. Shared by the interaction path and reaction previews so both agree.
+pub fn apply_mood_effect(mood: u8, effect: i8, max_mood: u8) -> u8 {
+    if effect > 0 {
+        mood.saturating_add(effect as u8).min(max_mood)
+    } else {
+        mood.saturating_sub(effect.unsigned_abs())
+    }
+}
+
+/// Whether the compatibility check may refuse an interaction at random.
+pub fn may_be_refused(interaction_type: InteractionKind) -> bool {
+    interaction_type == InteractionKind::Compete
+}
+
 /// A system for managing social interactions between pets.
 pub struct SocialInteractionSystem<T: Config> {
     _phantom: std::marker::PhantomData<T>,
@@ -115,7 +218,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     pub fn interact(
         pet_id_1: PetId,
         pet_id_2: PetId,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         duration: u32,
     ) -> DispatchResult {
         // Ensure the pets are different
@@ -125,18 +228,64 @@ impl<T: Config> SocialInteractionSystem<T> {
         let pet1 = crate::PetNfts::<T>::get(pet_id_1).ok_or(Error::<T>::PetNotFound)?;
         let pet2 = crate::PetNfts::<T>::get(pet_id_2).ok_or(Error::<T>::PetNotFound)?;
         
+        Self::interact_pets(&pet1, &pet2, interaction_type, duration)
+    }
+    
+    /// Initiates a social interaction between two pets the caller has already read, so callers
+    /// that check ownership against the pets themselves read each pet only once.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet1` - The first pet
+    /// * `pet2` - The second pet
+    /// * `interaction_type` - The type of interaction
+    /// * `duration` - The duration of the interaction
+    /// 
+    /// # Returns
+    /// 
+    /// * `DispatchResult` - Ok if successful, Err otherwise
+    pub fn interact_pets(
+        pet1: &PetNft<T>,
+        pet2: &PetNft<T>,
+        interaction_type: InteractionKind,
+        duration: u32,
+    ) -> DispatchResult {
+        let (pet_id_1, pet_id_2) = (pet1.id, pet2.id);
+        
+        // Ensure the pets are different
+        ensure!(pet_id_1 != pet_id_2, Error::<T>::InvalidPetState);
+        
+        // Look up the registered interaction type
+        let info = crate::SocialInteractionTypes::<T>::get(interaction_type)
+            .ok_or(Error::<T>::InvalidInteractionType)?;
+        
+        // Both pets must meet the type's level requirement
+        ensure!(
+            pet1.level >= info.min_level && pet2.level >= info.min_level,
+            Error::<T>::InteractionLevelTooLow
+        );
+        
+        // Enforce the per-pair, per-type cooldown
+        let current_block = frame_system::Pallet::<T>::block_number();
+        let pair = Self::pair_key(pet_id_1, pet_id_2);
+        if let Some(last_block) = crate::SocialInteractionCooldowns::<T>::get(pair, interaction_type) {
+            let ready_at = last_block.saturating_add(info.cooldown_blocks.into());
+            ensure!(current_block >= ready_at, Error::<T>::InteractionOnCooldown);
+        }
+        
         // Check compatibility
-        Self::check_compatibility(&pet1, &pet2, interaction_type)?;
+        Self::check_compatibility(pet1, pet2, interaction_type)?;
         
         // Calculate the outcome of the interaction
-        let (outcome, mood_change_1, mood_change_2) = Self::calculate_outcome(
-            &pet1,
-            &pet2,
+        let outcome = Self::calculate_outcome(
+            pet1,
+            pet2,
             interaction_type,
             duration,
         )?;
         
-        // Apply the effects of the interaction
+        // Apply the registered effects of the interaction
+        let (mood_change_1, mood_change_2) = (info.mood_effect_1, info.mood_effect_2);
         Self::apply_interaction_effects(
             pet_id_1,
             pet_id_2,
@@ -152,8 +301,12 @@ impl<T: Config> SocialInteractionSystem<T> {
             pet_id_2,
             interaction_type,
             outcome,
+            info.bond_delta,
         )?;
         
+        // Start the pair's cooldown for this interaction type
+        crate::SocialInteractionCooldowns::<T>::insert(pair, interaction_type, current_block);
+        
         // Record the interaction
         Self::record_interaction(
             pet_id_1,
@@ -165,9 +318,6 @@ impl<T: Config> SocialInteractionSystem<T> {
             mood_change_2,
         )?;
         
-        // Get the current block number
-        let current_block = frame_system::Pallet::<T>::block_number();
-        
         // Emit an event
         crate::Pallet::<T>::deposit_event(crate::Event::SocialInteraction {
             pet_id_1,
@@ -196,6 +346,11 @@ impl<T: Config> SocialInteractionSystem<T> {
         Ok(())
     }
     
+    /// Returns the cooldown key for a pet pair, independent of who initiated.
+    fn pair_key(pet_id_1: PetId, pet_id_2: PetId) -> (PetId, PetId) {
+        if pet_id_1 <= pet_id_2 { (pet_id_1, pet_id_2) } else { (pet_id_2, pet_id_1) }
+    }
+    
     /// Checks if two pets are compatible for a social interaction.
     /// 
     /// # Parameters
@@ -210,7 +365,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     fn check_compatibility(
         pet1: &PetNft<T>,
         pet2: &PetNft<T>,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
     ) -> DispatchResult {
         // In a real implementation, this would check various factors
         // such as personality traits, elemental affinities, etc.
@@ -219,7 +374,7 @@ impl<T: Config> SocialInteractionSystem<T> {
         // Check if the pets have opposite elemental affinities
         if (pet1.primary_elemental_affinity as u8 + pet2.primary_elemental_affinity as u8) % 8 == 4 {
             // Opposite elements: 50% chance of incompatibility for competitive interactions
-            if interaction_type == InteractionType::Compete as u8 {
+            if may_be_refused(interaction_type) {
                 let (random_seed, _) = T::PetRandomness::random_seed();
                 let random_value = random_seed.using_encoded(|encoded| {
                     let mut buf = [0u8; 4];
@@ -236,7 +391,7 @@ impl<T: Config> SocialInteractionSystem<T> {
         // Check if the pets have the same elemental affinity
         if pet1.primary_elemental_affinity as u8 == pet2.primary_elemental_affinity as u8 {
             // Same element: 50% chance of incompatibility for competitive interactions
-            if interaction_type == InteractionType::Compete as u8 {
+            if may_be_refused(interaction_type) {
                 let (random_seed, _) = T::PetRandomness::random_seed();
                 let random_value = random_seed.using_encoded(|encoded| {
                     let mut buf = [0u8; 4];
@@ -264,13 +419,13 @@ impl<T: Config> SocialInteractionSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Result<(u8, i8, i8), DispatchError>` - The outcome, mood change for pet1, and mood change for pet2, or an error
+    /// * `Result<u8, DispatchError>` - The outcome, or an error
     fn calculate_outcome(
         pet1: &PetNft<T>,
         pet2: &PetNft<T>,
-        interaction_type: u8,
-        duration: u32,
-    ) -> Result<(u8, i8, i8), DispatchError> {
+        interaction_type: InteractionKind,
+        _duration: u32,
+    ) -> Result<u8, DispatchError> {
         // In a real implementation, this would use a complex algorithm
         // to calculate the outcome based on various factors
         // For now, we'll use a simple algorithm
@@ -290,41 +445,37 @@ impl<T: Config> SocialInteractionSystem<T> {
         // Adjust based on elemental affinity
         if pet1.primary_elemental_affinity as u8 == pet2.primary_elemental_affinity as u8 {
             // Same element: more positive for cooperative interactions, more negative for competitive
-            if interaction_type == InteractionType::Play as u8 || 
-               interaction_type == InteractionType::Share as u8 ||
-               interaction_type == InteractionType::Comfort as u8 {
+            if matches!(interaction_type, InteractionKind::Play | InteractionKind::Share | InteractionKind::Comfort) {
                 outcome = outcome.saturating_sub(1); // More positive
-            } else if interaction_type == InteractionType::Compete as u8 {
+            } else if interaction_type == InteractionKind::Compete {
                 outcome = outcome.saturating_add(1); // More negative
             }
         } else if (pet1.primary_elemental_affinity as u8 + pet2.primary_elemental_affinity as u8) % 8 == 4 {
             // Opposite elements: more negative for cooperative interactions, more positive for competitive
-            if interaction_type == InteractionType::Play as u8 || 
-               interaction_type == InteractionType::Share as u8 ||
-               interaction_type == InteractionType::Comfort as u8 {
+            if matches!(interaction_type, InteractionKind::Play | InteractionKind::Share | InteractionKind::Comfort) {
                 outcome = outcome.saturating_add(1); // More negative
-            } else if interaction_type == InteractionType::Compete as u8 {
+            } else if interaction_type == InteractionKind::Compete {
                 outcome = outcome.saturating_sub(1); // More positive
             }
         }
         
         // Adjust based on interaction type
         match interaction_type {
-            0 => { // Play
+            InteractionKind::Play => {
                 // Playing is generally positive
                 outcome = outcome.saturating_sub(1); // More positive
             },
-            1 => { // Greet
+            InteractionKind::Greet => {
                 // Greeting is neutral to positive
                 if pet1.mood_indicator > 150 && pet2.mood_indicator > 150 {
                     outcome = outcome.saturating_sub(1); // More positive
                 }
             },
-            2 => { // Share
+            InteractionKind::Share => {
                 // Sharing is generally positive
                 outcome = outcome.saturating_sub(1); // More positive
             },
-            3 => { // Compete
+            InteractionKind::Compete => {
                 // Competition can be positive or negative
                 let (random_seed, _) = T::PetRandomness::random_seed();
                 let random_value = random_seed.using_encoded(|encoded| {
@@ -342,31 +493,7 @@ impl<T: Config> SocialInteractionSystem<T> {
             _ => {} // Other interaction types are neutral
         }
         
-        // Calculate mood changes
-        let (mood_change_1, mood_change_2) = match outcome {
-            0 => { // Very positive
-                let base_change = (duration as u16 * 20 / 100) as i8;
-                (base_change, base_change)
-            },
-            1 => { // Positive
-                let base_change = (duration as u16 * 10 / 100) as i8;
-                (base_change, base_change)
-            },
-            2 => { // Neutral
-                (0, 0)
-            },
-            3 => { // Negative
-                let base_change = -((duration as u16 * 10 / 100) as i8);
-                (base_change, base_change)
-            },
-            4 => { // Very negative
-                let base_change = -((duration as u16 * 20 / 100) as i8);
-                (base_change, base_change)
-            },
-            _ => (0, 0), // Default
-        };
-        
-        Ok((outcome, mood_change_1, mood_change_2))
+        Ok(outcome)
     }
     
     /// Applies the effects of a social interaction.
@@ -386,7 +513,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     fn apply_interaction_effects(
         pet_id_1: PetId,
         pet_id_2: PetId,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         outcome: u8,
         mood_change_1: i8,
         mood_change_2: i8,
@@ -395,15 +522,9 @@ impl<T: Config> SocialInteractionSystem<T> {
         crate::PetNfts::<T>::try_mutate(pet_id_1, |pet_opt| -> DispatchResult {
             let pet = pet_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
             
-            // Apply mood change
-            if mood_change_1 > 0 {
-                pet.mood_indicator = pet.mood_indicator
-                    .saturating_add(mood_change_1 as u8)
-                    .min(T::MaxMoodValue::get());
-            } else if mood_change_1 < 0 {
-                pet.mood_indicator = pet.mood_indicator
-                    .saturating_sub((-mood_change_1) as u8);
-            }
+            // Apply mood change, boosted by any mood gain buff
+            let mood_change = BuffSystem::<T>::boost_mood_effect(pet_id_1, mood_change_1);
+            pet.mood_indicator = apply_mood_effect(pet.mood_indicator, mood_change, T::MaxMoodValue::get());
             
             // Apply experience gain
             let xp_gain = match outcome {
@@ -428,15 +549,9 @@ impl<T: Config> SocialInteractionSystem<T> {
         crate::PetNfts::<T>::try_mutate(pet_id_2, |pet_opt| -> DispatchResult {
             let pet = pet_opt.as_mut().ok_or(Error::<T>::PetNotFound)?;
             
-            // Apply mood change
-            if mood_change_2 > 0 {
-                pet.mood_indicator = pet.mood_indicator
-                    .saturating_add(mood_change_2 as u8)
-                    .min(T::MaxMoodValue::get());
-            } else if mood_change_2 < 0 {
-                pet.mood_indicator = pet.mood_indicator
-                    .saturating_sub((-mood_change_2) as u8);
-            }
+            // Apply mood change, boosted by any mood gain buff
+            let mood_change = BuffSystem::<T>::boost_mood_effect(pet_id_2, mood_change_2);
+            pet.mood_indicator = apply_mood_effect(pet.mood_indicator, mood_change, T::MaxMoodValue::get());
             
             // Apply experience gain
             let xp_gain = match outcome {
@@ -468,6 +583,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     /// * `pet_id_2` - The ID of the second pet
     /// * `interaction_type` - The type of interaction
     /// * `outcome` - The outcome of the interaction
+    /// * `bond_delta` - The registered bond strength change for the interaction type
     /// 
     /// # Returns
     /// 
@@ -475,14 +591,15 @@ impl<T: Config> SocialInteractionSystem<T> {
     fn update_social_bond(
         pet_id_1: PetId,
         pet_id_2: PetId,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         outcome: u8,
+        bond_delta: i8,
     ) -> DispatchResult {
         // Update the bond from pet1 to pet2
-        Self::update_one_way_bond(pet_id_1, pet_id_2, interaction_type, outcome)?;
+        Self::update_one_way_bond(pet_id_1, pet_id_2, interaction_type, outcome, bond_delta)?;
         
         // Update the bond from pet2 to pet1
-        Self::update_one_way_bond(pet_id_2, pet_id_1, interaction_type, outcome)?;
+        Self::update_one_way_bond(pet_id_2, pet_id_1, interaction_type, outcome, bond_delta)?;
         
         Ok(())
     }
@@ -495,6 +612,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     /// * `other_pet_id` - The ID of the other pet
     /// * `interaction_type` - The type of interaction
     /// * `outcome` - The outcome of the interaction
+    /// * `bond_delta` - The registered bond strength change for the interaction type
     /// 
     /// # Returns
     /// 
@@ -502,8 +620,9 @@ impl<T: Config> SocialInteractionSystem<T> {
     fn update_one_way_bond(
         pet_id: PetId,
         other_pet_id: PetId,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         outcome: u8,
+        bond_delta: i8,
     ) -> DispatchResult {
         crate::PetSocialBonds::<T>::try_mutate(pet_id, |bonds| -> DispatchResult {
             // Find the bond with the other pet
@@ -524,9 +643,9 @@ impl<T: Config> SocialInteractionSystem<T> {
                 );
                 
                 // Update the bond strength
-                bond.bond_strength = Self::calculate_bond_strength(
-                    bond.bond_strength,
-                    outcome,
+                bond.bond_strength = Self::apply_bond_delta(
+                    Self::calculate_bond_strength(bond.bond_strength, outcome),
+                    bond_delta,
                 );
                 
                 // Update the interaction count
@@ -541,8 +660,9 @@ impl<T: Config> SocialInteractionSystem<T> {
                     outcome,
                 );
                 
-                let bond_strength = Self::calculate_initial_bond_strength(
-                    outcome,
+                let bond_strength = Self::apply_bond_delta(
+                    Self::calculate_initial_bond_strength(outcome),
+                    bond_delta,
                 );
                 
                 let new_bond = SocialBond {
@@ -573,16 +693,16 @@ impl<T: Config> SocialInteractionSystem<T> {
     /// 
     /// * `u8` - The bond type
     fn determine_initial_bond_type(
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         outcome: u8,
     ) -> u8 {
         match (interaction_type, outcome) {
-            (0, 0) | (0, 1) | (2, 0) | (2, 1) => BondType::Friend as u8, // Play or Share with positive outcome
-            (3, 0) | (3, 1) => BondType::Rival as u8, // Compete with positive outcome
-            (4, 0) | (4, 1) => BondType::Mentor as u8, // Teach with positive outcome
-            (5, 0) | (5, 1) => BondType::Student as u8, // Learn with positive outcome
-            (6, 0) | (6, 1) => BondType::Guardian as u8, // Comfort with positive outcome
-            (7, 0) | (7, 1) => BondType::Protector as u8, // Protect with positive outcome
+            (InteractionKind::Play | InteractionKind::Share, 0 | 1) => BondType::Friend as u8, // Positive outcome
+            (InteractionKind::Compete, 0 | 1) => BondType::Rival as u8,
+            (InteractionKind::Teach, 0 | 1) => BondType::Mentor as u8,
+            (InteractionKind::Learn, 0 | 1) => BondType::Student as u8,
+            (InteractionKind::Comfort, 0 | 1) => BondType::Guardian as u8,
+            (InteractionKind::Protect, 0 | 1) => BondType::Protector as u8,
             (_, 3) | (_, 4) => BondType::Rival as u8, // Any interaction with negative outcome
             _ => BondType::Neutral as u8, // Default
         }
@@ -601,7 +721,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     /// * `u8` - The new bond type
     fn determine_bond_type(
         current_bond_type: u8,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         outcome: u8,
     ) -> u8 {
         // In a real implementation, this would use a complex algorithm
@@ -656,6 +776,27 @@ impl<T: Config> SocialInteractionSystem<T> {
         }
     }
     
+    /// Applies a signed bond strength change, saturating at the bounds.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `strength` - The current bond strength
+    /// * `delta` - The change to apply
+    /// 
+    /// # Returns
+    /// 
+    /// * `u8` - The new bond strength
+    fn apply_bond_delta(
+        strength: u8,
+        delta: i8,
+    ) -> u8 {
+        if delta >= 0 {
+            strength.saturating_add(delta as u8)
+        } else {
+            strength.saturating_sub(delta.unsigned_abs())
+        }
+    }
+    
     /// Records a social interaction.
     /// 
     /// # Parameters
@@ -674,7 +815,7 @@ impl<T: Config> SocialInteractionSystem<T> {
     fn record_interaction(
         pet_id_1: PetId,
         pet_id_2: PetId,
-        interaction_type: u8,
+        interaction_type: InteractionKind,
         outcome: u8,
         duration: u32,
         mood_change_1: i8,
@@ -683,35 +824,37 @@ impl<T: Config> SocialInteractionSystem<T> {
         // Get the current block number
         let current_block = frame_system::Pallet::<T>::block_number();
         
-        // Record the interaction for the first pet
-        crate::PetSocialInteractions::<T>::try_mutate(pet_id_1, |interactions| -> DispatchResult {
-            // Add the interaction
-            interactions.try_push((pet_id_2, interaction_type, outcome, current_block))
-                .map_err(|_| Error::<T>::TooManySocialInteractions)?;
+        // Record the interaction for both pets, dropping each pet's oldest interaction when full
+        for (pet_id, other_pet_id) in [(pet_id_1, pet_id_2), (pet_id_2, pet_id_1)] {
+            let new_partner = crate::PetSocialInteractions::<T>::mutate(pet_id, |interactions| {
+                let size_before = crate::footprint::stored_size(interactions);
+                let new_partner = !interactions.iter().any(|(other, _, _, _)| *other == other_pet_id);
+                push_rotating(interactions, (other_pet_id, interaction_type, outcome, current_block));
+                crate::footprint::FootprintSystem::<T>::note_resize(pet_id, size_before, crate::footprint::stored_size(interactions));
+                new_partner
+            });
             
-            Ok(())
-        })?;
-        
-        // Record the interaction for the second pet
-        crate::PetSocialInteractions::<T>::try_mutate(pet_id_2, |interactions| -> DispatchResult {
-            // Add the interaction
-            interactions.try_push((pet_id_1, interaction_type, outcome, current_block))
-                .map_err(|_| Error::<T>::TooManySocialInteractions)?;
-            
-            Ok(())
-        })?;
+            // Count a partner the pet has not recently met towards social achievements
+            if new_partner {
+                crate::achievements::AchievementSystem::<T>::note_progress(
+                    pet_id,
+                    crate::achievements::ProgressSource::SocialPartners,
+                    crate::achievements::ProgressUpdate::Add(1),
+                );
+            }
+        }
         
         // Record a memory of the interaction for both pets
         crate::memory::PetMemorySystem::<T>::record_memory(
             pet_id_1,
-            crate::memory::MemoryType::SocialInteraction as u8,
+            crate::memory::MemoryType::SocialInteraction,
             if outcome <= 1 { 150 } else if outcome >= 3 { 100 } else { 50 }, // Significance based on outcome
             pet_id_2.encode(), // Store the other pet's ID as associated data
         )?;
         
         crate::memory::PetMemorySystem::<T>::record_memory(
             pet_id_2,
-            crate::memory::MemoryType::SocialInteraction as u8,
+            crate::memory::MemoryType::SocialInteraction,
             if outcome <= 1 { 150 } else if outcome >= 3 { 100 } else { 50 }, // Significance based on outcome
             pet_id_1.encode(), // Store the other pet's ID as associated data
         )?;
@@ -719,6 +862,68 @@ impl<T: Config> SocialInteractionSystem<T> {
         Ok(())
     }
     
+    /// Previews how a pet would react to an interaction, without changing state.
+    /// 
+    /// Uses the same registry entry, level requirement and mood clamping as `interact`. The pet
+    /// may take part as either the initiating or the other pet, so the mood delta is the range
+    /// over both roles. Interactions that the compatibility check may refuse depend on the
+    /// partner and on randomness that is only known at dispatch; their range includes no change.
+    /// Mood gains include the pet's active mood gain buff, reported as `mood_buff`.
+    /// The result describes the current block's state and can change once the pet's mood or
+    /// buffs do.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// * `interaction_type` - The type of interaction
+    /// 
+    /// # Returns
+    /// 
+    /// * `Option<ReactionPreview>` - The preview, or `None` if the pet does not exist
+    pub fn preview_reaction(
+        pet_id: PetId,
+        interaction_type: InteractionKind,
+    ) -> Option<ReactionPreview> {
+        let pet = crate::PetNfts::<T>::get(pet_id)?;
+        let refuses = ReactionPreview {
+            min_mood_delta: 0,
+            max_mood_delta: 0,
+            reaction: ReactionClass::Refuses,
+            mood_buff: Percent::zero(),
+        };
+        
+        // The interaction must be registered and the pet must meet its level requirement
+        let info = match crate::SocialInteractionTypes::<T>::get(interaction_type) {
+            Some(info) => info,
+            None => return Some(refuses),
+        };
+        if pet.level < info.min_level {
+            return Some(refuses);
+        }
+        
+        // Compute the delta for both roles, including any active mood gain buff
+        let max_mood = T::MaxMoodValue::get();
+        let mood_buff = BuffSystem::<T>::strongest(&BuffSystem::<T>::active_buffs(pet_id), BuffKind::MoodGain);
+        let delta_of = |effect: i8| {
+            let effect = BuffSystem::<T>::boosted_mood_effect(effect, mood_buff);
+            apply_mood_effect(pet.mood_indicator, effect, max_mood) as i16 - pet.mood_indicator as i16
+        };
+        let (delta_1, delta_2) = (delta_of(info.mood_effect_1), delta_of(info.mood_effect_2));
+        let mut min_mood_delta = delta_1.min(delta_2);
+        let mut max_mood_delta = delta_1.max(delta_2);
+        if may_be_refused(interaction_type) {
+            min_mood_delta = min_mood_delta.min(0);
+            max_mood_delta = max_mood_delta.max(0);
+        }
+        
+        Some(ReactionPreview {
+            min_mood_delta,
+            max_mood_delta,
+            reaction: ReactionClass::from_mood_delta_range(min_mood_delta, max_mood_delta),
+            mood_buff,
+        })
+    }
+    
     /// Gets all social bonds for a pet.
     /// 
     /// # Parameters
@@ -742,10 +947,10 @@ impl<T: Config> SocialInteractionSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(PetId, u8, u8, BlockNumberFor<T>)>` - The social interactions (other_pet_id, interaction_type, outcome, timestamp)
+    /// * `Vec<(PetId, InteractionKind, u8, BlockNumberFor<T>)>` - The social interactions (other_pet_id, interaction_type, outcome, timestamp)
     pub fn get_social_interactions(
         pet_id: PetId,
-    ) -> Vec<(PetId, u8, u8, BlockNumberFor<T>)> {
+    ) -> Vec<(PetId, InteractionKind, u8, BlockNumberFor<T>)> {
         crate::PetSocialInteractions::<T>::get(pet_id).to_vec()
     }
     
@@ -813,6 +1018,13 @@ impl<T: Config> SocialInteractionSystem<T> {
             compatibility = compatibility.saturating_sub(25);
         }
         
+        // Adjust based on shared personality traits, including unexpired temporary ones:
+        // +5% compatibility each
+        let traits1 = crate::temporary_traits::TemporaryTraitSystem::<T>::all_traits(&pet1);
+        let traits2 = crate::temporary_traits::TemporaryTraitSystem::<T>::all_traits(&pet2);
+        let shared_traits = traits1.iter().filter(|trait_str| traits2.contains(trait_str)).count();
+        compatibility = compatibility.saturating_add((shared_traits as u8).saturating_mul(13));
+        
         Ok(compatibility)
     }
 }
\ No newline at end of file
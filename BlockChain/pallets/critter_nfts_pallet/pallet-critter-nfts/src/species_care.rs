@@ -0,0 +1,132 @@
+//! # Species Care Preferences
+//!
+//! Species can respond differently to care. The catalog origin records a species' preferences
+//! in `SpeciesCarePreferences`, keyed by species name:
+//!
+//! - a `Perbill` multiplier per `CareActionKind`, applied to the mood and XP gains of feeding and
+//!   play (including auto-care) and to the mood gains of gestures and touches; actions without
+//!   one gain 100%, as do species without an entry
+//! - a preferred `EnvironmentKind`: a pet adapted to it above `PreferredEnvironmentThreshold`
+//!   gains `PreferredEnvironmentBonus` more
+//!
+//! Players learn a species' quirks from the mint preview and the UI profile, which show the
+//! values the care paths apply.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::{ConstU32, Get}, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::{Perbill, Percent};
+use sp_std::vec::Vec;
+use crate::kinds::{CareActionKind, EnvironmentKind};
+use crate::{Config, PetNft};
+
+/// A species' care preferences.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CarePreferences {
+    /// The gain multipliers of care actions; actions not listed gain 100%. One entry per
+    /// `CareActionKind` at most.
+    pub multipliers: BoundedVec<(CareActionKind, Perbill), ConstU32<4>>,
+
+    /// The environment the species thrives in, if any
+    pub preferred_environment: Option<EnvironmentKind>,
+}
+
+/// A species' care preferences with the defaults filled in, as shown to players.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SpeciesCareProfile {
+    /// The gain multiplier of every care action, in `CareActionKind` order
+    pub multipliers: Vec<(CareActionKind, Perbill)>,
+
+    /// The environment the species thrives in, if any
+    pub preferred_environment: Option<EnvironmentKind>,
+}
+
+/// How a pet's species scales the gains of one care action.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct CareFactor {
+    /// The species' multiplier for the action
+    pub multiplier: Perbill,
+
+    /// The bonus for being adapted to the species' preferred environment, zero otherwise
+    pub environment_bonus: Percent,
+}
+
+impl CareFactor {
+    /// Scales an XP gain.
+    pub fn apply_xp(&self, gain: u32) -> u32 {
+        let scaled = self.multiplier.mul_floor(gain);
+        scaled.saturating_add(self.environment_bonus.mul_floor(scaled))
+    }
+
+    /// Scales a mood gain.
+    pub fn apply_mood(&self, gain: u8) -> u8 {
+        self.apply_xp(gain as u32).min(u8::MAX as u32) as u8
+    }
+}
+
+/// A system for looking up species care preferences.
+pub struct SpeciesCareSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> SpeciesCareSystem<T> {
+    /// Gets a species' care preferences with the defaults filled in.
+    ///
+    /// # Parameters
+    ///
+    /// * `species` - The species name
+    ///
+    /// # Returns
+    ///
+    /// * `SpeciesCareProfile` - The multiplier of every care action and the preferred environment
+    pub fn profile(species: &[u8]) -> SpeciesCareProfile {
+        let preferences = Self::preferences(species);
+        SpeciesCareProfile {
+            multipliers: CareActionKind::ALL
+                .iter()
+                .map(|kind| (*kind, Self::multiplier(preferences.as_ref(), *kind)))
+                .collect(),
+            preferred_environment: preferences.and_then(|preferences| preferences.preferred_environment),
+        }
+    }
+
+    /// Gets how a pet's species scales the gains of a care action.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    /// * `kind` - The care action
+    ///
+    /// # Returns
+    ///
+    /// * `CareFactor` - The species' multiplier and the pet's environment bonus
+    pub fn factor(pet: &PetNft<T>, kind: CareActionKind) -> CareFactor {
+        let preferences = Self::preferences(&pet.initial_species);
+        let adapted = preferences
+            .as_ref()
+            .and_then(|preferences| preferences.preferred_environment)
+            .map(|environment| {
+                crate::PetEnvironmentalAdaptations::<T>::get(pet.id)
+                    .iter()
+                    .any(|(adapted_to, level)| *adapted_to == environment && *level > T::PreferredEnvironmentThreshold::get())
+            })
+            .unwrap_or(false);
+
+        CareFactor {
+            multiplier: Self::multiplier(preferences.as_ref(), kind),
+            environment_bonus: if adapted { T::PreferredEnvironmentBonus::get() } else { Percent::from_percent(0) },
+        }
+    }
+
+    fn preferences(species: &[u8]) -> Option<CarePreferences> {
+        let species: BoundedVec<u8, T::MaxSpeciesNameLen> = species.to_vec().try_into().ok()?;
+        crate::SpeciesCarePreferences::<T>::get(species)
+    }
+
+    fn multiplier(preferences: Option<&CarePreferences>, kind: CareActionKind) -> Perbill {
+        preferences
+            .and_then(|preferences| preferences.multipliers.iter().find(|(listed, _)| *listed == kind))
+            .map(|(_, multiplier)| *multiplier)
+            .unwrap_or(Perbill::from_percent(100))
+    }
+}
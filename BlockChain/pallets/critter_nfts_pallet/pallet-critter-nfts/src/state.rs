@@ -267,7 +267,7 @@ impl<T: Config> PetStateManager<T> {
                 .saturating_sub(pet.last_state_update_block);
             
             // Apply adaptive behavior based on interaction frequency
-            if blocks_since_last_interaction > T::NeglectThresholdBlocks::get() {
+            if blocks_since_last_interaction > crate::Pallet::<T>::params().neglect_threshold_blocks {
                 // Pet has been neglected, make it more responsive to interactions
                 // (i.e., increase the mood boost from interactions)
                 // This is a simple example of adaptive behavior
@@ -3,11 +3,158 @@
 //! This module defines traits and utilities for synchronizing pet state changes
 //! across the CritterCraft ecosystem. It provides mechanisms for other pallets
 //! to register hooks that are called when pet state changes occur.
+//!
+//! A failed notification is queued in `FailedNotifications` for its hook and retried in
+//! `on_idle` with exponential backoff: the n-th retry happens `2^n * RetryBaseBlocks` blocks
+//! after the previous attempt. Failures for a pet that is already queued are merged into its
+//! entry. An entry is dropped with a `NotificationDropped` event once it has failed
+//! `MaxNotificationRetries` retries, or when the hook's queue overflows (oldest first), so
+//! the off-chain system knows to resync the pet.
 
-use frame_support::dispatch::DispatchResult;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::DispatchResult,
+    pallet_prelude::{ConstU32, RuntimeDebug},
+    traits::Get,
+    weights::Weight,
+    BoundedVec,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, DispatchError};
+use sp_std::vec::Vec;
 use crate::traits::PetId;
+use crittercraft_traits::bounded::push_rotating;
 use frame_system::Config as SystemConfig;
 
+/// The version of the synchronization protocol, i.e. of the sync events' encoding.
+/// Version 2 replaced the raw error bytes of `HookExecutionFailed` with a `HookFailureReason`.
+pub const SYNC_PROTOCOL_VERSION: u32 = 2;
+
+/// The handler error code reported when a handler fails without a module error.
+pub const UNKNOWN_HANDLER_ERROR: u8 = u8::MAX;
+
+/// Auxiliary detail attached to a hook failure, capped at 64 bytes.
+pub type HookFailureDetail = BoundedVec<u8, ConstU32<64>>;
+
+/// Why a synchronization hook execution failed, as reported in `HookExecutionFailed`.
+/// The variant order is part of the sync protocol; append new variants only.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum HookFailureReason {
+    /// The hook ran longer than `MaxHookExecutionTimeMs`
+    Timeout,
+    /// The hook's target could not be resolved
+    TargetUnreachable,
+    /// The state change data or the hook's response could not be decoded
+    DecodeError,
+    /// The hook's handler returned an error; the code is the handler's error index
+    HandlerError(u8),
+    /// The hook's target reported the hook as disabled
+    Disabled,
+}
+
+impl HookFailureReason {
+    /// How much a failure counts toward auto-disabling the hook.
+    /// Timeouts count double, since they also cost the most block time.
+    pub fn failure_weight(&self) -> u32 {
+        match self {
+            HookFailureReason::Timeout => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// The internal failure sources of a hook execution, before they are reduced to a reason.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum HookExecutionError {
+    /// The hook completed, but took longer than allowed
+    TimedOut { elapsed_ms: u32 },
+    /// The hook's target could not be resolved
+    Unreachable,
+    /// Decoding failed
+    Decode(codec::Error),
+    /// The hook's handler returned an error
+    Dispatch(DispatchError),
+    /// The hook's target has the hook switched off
+    Disabled,
+}
+
+impl HookExecutionError {
+    /// Maps the failure to the reason reported on chain.
+    pub fn reason(&self) -> HookFailureReason {
+        match self {
+            HookExecutionError::TimedOut { .. } => HookFailureReason::Timeout,
+            HookExecutionError::Unreachable
+            | HookExecutionError::Dispatch(DispatchError::CannotLookup) => HookFailureReason::TargetUnreachable,
+            HookExecutionError::Decode(_) => HookFailureReason::DecodeError,
+            HookExecutionError::Dispatch(DispatchError::Module(error)) => HookFailureReason::HandlerError(error.error[0]),
+            HookExecutionError::Dispatch(_) => HookFailureReason::HandlerError(UNKNOWN_HANDLER_ERROR),
+            HookExecutionError::Disabled => HookFailureReason::Disabled,
+        }
+    }
+
+    /// A short human-readable detail for the failure, truncated to 64 bytes.
+    pub fn detail(&self) -> HookFailureDetail {
+        let text: &str = match self {
+            HookExecutionError::Dispatch(DispatchError::Module(error)) => error.message.unwrap_or(""),
+            HookExecutionError::Dispatch(DispatchError::Other(message)) => *message,
+            _ => "",
+        };
+        BoundedVec::truncate_from(text.as_bytes().to_vec())
+    }
+}
+
+impl From<DispatchError> for HookExecutionError {
+    fn from(error: DispatchError) -> Self {
+        HookExecutionError::Dispatch(error)
+    }
+}
+
+impl From<codec::Error> for HookExecutionError {
+    fn from(error: codec::Error) -> Self {
+        HookExecutionError::Decode(error)
+    }
+}
+
+/// Delivers state change notifications to registered hooks.
+pub trait HookExecutor<T: SystemConfig> {
+    /// Executes a single hook.
+    ///
+    /// # Parameters
+    ///
+    /// * `hook_id` - The unique identifier of the hook
+    /// * `hook_info` - The registered hook
+    /// * `pet_id` - The ID of the pet whose state changed
+    /// * `change_type` - The type of state change that occurred
+    /// * `version` - The new version of the pet's state
+    /// * `data` - Optional additional data related to the state change
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, HookExecutionError>` - The execution time in milliseconds, or why the hook failed
+    fn execute(
+        hook_id: u32,
+        hook_info: &HookInfo<T>,
+        pet_id: PetId,
+        change_type: StateChangeType,
+        version: u32,
+        data: Option<&[u8]>,
+    ) -> Result<u32, HookExecutionError>;
+}
+
+/// Hooks are bookkeeping only: every execution succeeds instantly.
+impl<T: SystemConfig> HookExecutor<T> for () {
+    fn execute(
+        _hook_id: u32,
+        _hook_info: &HookInfo<T>,
+        _pet_id: PetId,
+        _change_type: StateChangeType,
+        _version: u32,
+        _data: Option<&[u8]>,
+    ) -> Result<u32, HookExecutionError> {
+        Ok(0)
+    }
+}
+
 /// Defines the types of state changes that can trigger synchronization hooks.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum StateChangeType {
@@ -27,11 +174,85 @@ pub enum StateChangeType {
     Mood = 6,
     /// Any other state change
     Other = 7,
+    /// Owner-authored profile content (bio, visuals, etc.)
+    Profile = 8,
+    /// The pet was released or burned; it no longer exists
+    Removed = 9,
 }
 
-/// Converts a StateChangeType to a bit flag for the sync_flags field.
+impl StateChangeType {
+    /// Every change type, in discriminant order.
+    pub const ALL: [StateChangeType; 10] = [
+        StateChangeType::BasicInfo,
+        StateChangeType::Stats,
+        StateChangeType::Traits,
+        StateChangeType::Ownership,
+        StateChangeType::Interactions,
+        StateChangeType::LevelAndXp,
+        StateChangeType::Mood,
+        StateChangeType::Other,
+        StateChangeType::Profile,
+        StateChangeType::Removed,
+    ];
+}
+
+/// Converts a StateChangeType to a bit flag for a hook's interests bitfield.
+/// Change types beyond the width of the bitfield share the `Other` flag.
 pub fn state_change_to_flag(change_type: StateChangeType) -> u8 {
-    1 << (change_type as u8)
+    1u8.checked_shl(change_type as u32)
+        .unwrap_or(1 << (StateChangeType::Other as u8))
+}
+
+/// The change types a pet has not yet been synchronized for, one bit per `StateChangeType`.
+/// Stored on `PetNft` as a `u16`; change types beyond its width share the `Other` bit rather
+/// than overflowing.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SyncFlags(u16);
+
+impl SyncFlags {
+    /// The bit for a change type.
+    pub fn flag(change_type: StateChangeType) -> u16 {
+        1u16.checked_shl(change_type as u32)
+            .unwrap_or(1 << (StateChangeType::Other as u16))
+    }
+
+    /// The bits of every change type a hook with `interests` is notified of.
+    pub fn mask_for_interests(interests: u8) -> u16 {
+        StateChangeType::ALL
+            .iter()
+            .filter(|change_type| interests & state_change_to_flag(**change_type) != 0)
+            .fold(0, |mask, change_type| mask | Self::flag(*change_type))
+    }
+
+    /// Flags built from raw bits, e.g. a legacy `u8` bitfield.
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// The raw bits.
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Marks a change type as not yet synchronized.
+    pub fn set_dirty(&mut self, change_type: StateChangeType) {
+        self.0 |= Self::flag(change_type);
+    }
+
+    /// Marks a change type as synchronized.
+    pub fn clear_dirty(&mut self, change_type: StateChangeType) {
+        self.0 &= !Self::flag(change_type);
+    }
+
+    /// Whether a change type is not yet synchronized.
+    pub fn is_dirty(&self, change_type: StateChangeType) -> bool {
+        self.0 & Self::flag(change_type) != 0
+    }
+
+    /// Marks every change type in `mask` as synchronized.
+    pub fn clear_mask(&mut self, mask: u16) {
+        self.0 &= !mask;
+    }
 }
 
 /// A trait for pallets that want to be notified of pet state changes.
@@ -97,12 +318,27 @@ pub struct HookInfo<T: SystemConfig> {
     pub execution_count: u32,
 }
 
+/// A notification a hook failed to receive, waiting to be retried.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FailedNotification<BlockNumber> {
+    /// The ID of the pet whose state changed
+    pub pet_id: PetId,
+    /// The `SyncFlags` bits of every change type the hook missed for the pet
+    pub change_mask: u16,
+    /// The newest version of the pet's state the hook missed
+    pub version: u32,
+    /// The number of retries that have failed
+    pub attempts: u32,
+    /// The block from which the notification is retried
+    pub retry_at: BlockNumber,
+}
+
 /// A struct for registering and managing synchronization hooks.
 pub struct SyncHookManager<T: SystemConfig> {
     _phantom: std::marker::PhantomData<T>,
 }
 
-impl<T: SystemConfig> SyncHookManager<T> {
+impl<T: crate::Config> SyncHookManager<T> {
     /// Registers a new synchronization hook with detailed information.
     /// 
     /// # Parameters
@@ -171,6 +407,9 @@ impl<T: SystemConfig> SyncHookManager<T> {
         // Get the hook info before removing it for the event
         if let Some(hook_info) = crate::SyncHookRegistry::<T>::get(hook_id) {
             crate::SyncHookRegistry::<T>::remove(hook_id);
+            crate::SyncHookExecutionStats::<T>::remove(hook_id);
+            crate::SyncHookFailureScore::<T>::remove(hook_id);
+            crate::FailedNotifications::<T>::remove(hook_id);
             
             // Emit an event for transparency
             crate::Pallet::<T>::deposit_event(crate::Event::HookUnregistered {
@@ -280,18 +519,32 @@ impl<T: SystemConfig> SyncHookManager<T> {
                 continue;
             }
             
-            // Execute the hook
-            // In a real implementation, this would call the hook's on_pet_state_change method
-            // For now, we'll just update the hook's execution stats
-            hook_info.last_execution = timestamp;
-            hook_info.execution_count = hook_info.execution_count.saturating_add(1);
+            let outcome = Self::execute(hook_id, &hook_info, pet_id, change_type, version, data.as_deref());
             
-            // Update the hook info in storage
-            crate::SyncHookRegistry::<T>::insert(hook_id, hook_info.clone());
+            hook_info.execution_count = hook_info.execution_count.saturating_add(1);
             
             // Track success/failure
-            // In a real implementation, this would depend on the result of the hook execution
-            successful_hooks = successful_hooks.saturating_add(1);
+            match outcome {
+                Ok(elapsed_ms) => {
+                    hook_info.last_execution = timestamp;
+                    Self::record_execution(hook_id, timestamp, true, elapsed_ms);
+                    crate::SyncHookFailureScore::<T>::remove(hook_id);
+                    successful_hooks = successful_hooks.saturating_add(1);
+                },
+                Err(error) => {
+                    let elapsed_ms = match error {
+                        HookExecutionError::TimedOut { elapsed_ms } => elapsed_ms,
+                        _ => 0,
+                    };
+                    Self::record_execution(hook_id, timestamp, false, elapsed_ms);
+                    Self::record_failure(hook_id, &mut hook_info, pet_id, change_type, &error);
+                    Self::queue_failure(hook_id, pet_id, change_type, version);
+                    failed_hooks = failed_hooks.saturating_add(1);
+                },
+            }
+            
+            // Update the hook info in storage
+            crate::SyncHookRegistry::<T>::insert(hook_id, hook_info);
         }
         
         // Emit an event for transparency
@@ -307,6 +560,266 @@ impl<T: SystemConfig> SyncHookManager<T> {
         Ok(())
     }
     
+    /// Executes a hook, treating slow completions as timeouts.
+    fn execute(
+        hook_id: u32,
+        hook_info: &HookInfo<T>,
+        pet_id: PetId,
+        change_type: StateChangeType,
+        version: u32,
+        data: Option<&[u8]>,
+    ) -> Result<u32, HookExecutionError> {
+        T::HookExecutor::execute(hook_id, hook_info, pet_id, change_type, version, data)
+            .and_then(|elapsed_ms| {
+                if elapsed_ms > T::MaxHookExecutionTimeMs::get() {
+                    Err(HookExecutionError::TimedOut { elapsed_ms })
+                } else {
+                    Ok(elapsed_ms)
+                }
+            })
+    }
+
+    /// Queues a failed notification for retry, merging it into the pet's entry if the hook
+    /// already missed a change for the pet. When the queue is full, the oldest entry is
+    /// dropped.
+    fn queue_failure(hook_id: u32, pet_id: PetId, change_type: StateChangeType, version: u32) {
+        let flag = SyncFlags::flag(change_type);
+        let dropped = crate::FailedNotifications::<T>::mutate(hook_id, |queue| {
+            if let Some(entry) = queue.iter_mut().find(|entry| entry.pet_id == pet_id) {
+                entry.change_mask |= flag;
+                entry.version = entry.version.max(version);
+                return None;
+            }
+            let retry_at = frame_system::Pallet::<T>::block_number().saturating_add(Self::backoff(0));
+            push_rotating(queue, FailedNotification { pet_id, change_mask: flag, version, attempts: 0, retry_at })
+        });
+        if let Some(entry) = dropped {
+            Self::drop_notification(hook_id, entry);
+        }
+    }
+
+    /// The number of blocks to wait after an attempt before the next retry, once `attempts`
+    /// retries have failed.
+    fn backoff(attempts: u32) -> T::BlockNumber {
+        let factor = 1u32.checked_shl(attempts).unwrap_or(u32::MAX);
+        T::RetryBaseBlocks::get().saturating_mul(factor.into())
+    }
+
+    fn drop_notification(hook_id: u32, entry: FailedNotification<T::BlockNumber>) {
+        crate::Pallet::<T>::deposit_event(crate::Event::NotificationDropped {
+            hook_id,
+            pet_id: entry.pet_id,
+            change_mask: entry.change_mask,
+            version: entry.version,
+        });
+    }
+
+    /// The weight charged for retrying a single notification: one execution per missed change
+    /// type, plus the bookkeeping.
+    pub fn retry_weight(change_mask: u16) -> Weight {
+        // Writes: SyncHookExecutionStats, SyncHookFailureScore.
+        Weight::from_parts(10_000, 0)
+            .saturating_mul(change_mask.count_ones().max(1) as u64)
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Retries due failed notifications until the weight limit is reached. Whatever does not
+    /// fit stays queued for later blocks. Hooks that are disabled keep their queue as it is.
+    ///
+    /// # Parameters
+    ///
+    /// * `now` - The current block
+    /// * `limit` - The weight available for retries
+    ///
+    /// # Returns
+    ///
+    /// * `Weight` - The weight consumed
+    pub fn retry_failed(now: T::BlockNumber, limit: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+
+        // 1. Finding the queues is charged up front.
+        let mut used = db_weight.reads(1);
+        if used.any_gt(limit) {
+            return Weight::zero();
+        }
+
+        // Reads: FailedNotifications, SyncHookRegistry. Writes: FailedNotifications, SyncHookRegistry.
+        let queue_weight = db_weight.reads_writes(2, 2);
+        let hook_ids: Vec<u32> = crate::FailedNotifications::<T>::iter_keys().collect();
+        for hook_id in hook_ids {
+            if used.saturating_add(queue_weight).any_gt(limit) {
+                break;
+            }
+            used.saturating_accrue(queue_weight);
+
+            let mut hook_info = match crate::SyncHookRegistry::<T>::get(hook_id) {
+                Some(hook_info) => hook_info,
+                None => {
+                    crate::FailedNotifications::<T>::remove(hook_id);
+                    continue;
+                },
+            };
+            if !hook_info.enabled {
+                continue;
+            }
+
+            // 2. Retry the hook's due entries, oldest first.
+            let mut queue = crate::FailedNotifications::<T>::get(hook_id);
+            let mut out_of_weight = false;
+            let mut index = 0;
+            while index < queue.len() {
+                if queue[index].retry_at > now {
+                    index += 1;
+                    continue;
+                }
+                let retry_weight = Self::retry_weight(queue[index].change_mask);
+                if used.saturating_add(retry_weight).any_gt(limit) {
+                    out_of_weight = true;
+                    break;
+                }
+                used.saturating_accrue(retry_weight);
+
+                match Self::retry(hook_id, &mut hook_info, &queue[index], now) {
+                    Ok(()) => {
+                        queue.remove(index);
+                    },
+                    Err(()) => {
+                        let entry = &mut queue[index];
+                        entry.attempts = entry.attempts.saturating_add(1);
+                        if entry.attempts >= T::MaxNotificationRetries::get() {
+                            let entry = queue.remove(index);
+                            Self::drop_notification(hook_id, entry);
+                        } else {
+                            entry.retry_at = now.saturating_add(Self::backoff(entry.attempts));
+                            index += 1;
+                        }
+                    },
+                }
+            }
+
+            // 3. Store what is left.
+            crate::SyncHookRegistry::<T>::insert(hook_id, hook_info);
+            if queue.is_empty() {
+                crate::FailedNotifications::<T>::remove(hook_id);
+            } else {
+                crate::FailedNotifications::<T>::insert(hook_id, queue);
+            }
+            if out_of_weight {
+                break;
+            }
+        }
+
+        used
+    }
+
+    /// Re-sends every change type of a failed notification, stopping at the first failure.
+    /// A successful retry is recorded like a successful notification.
+    fn retry(
+        hook_id: u32,
+        hook_info: &mut HookInfo<T>,
+        entry: &FailedNotification<T::BlockNumber>,
+        now: T::BlockNumber,
+    ) -> Result<(), ()> {
+        let mut elapsed_ms = 0u32;
+        for change_type in StateChangeType::ALL.iter().filter(|change_type| entry.change_mask & SyncFlags::flag(**change_type) != 0) {
+            hook_info.execution_count = hook_info.execution_count.saturating_add(1);
+            match Self::execute(hook_id, hook_info, entry.pet_id, *change_type, entry.version, None) {
+                Ok(elapsed) => elapsed_ms = elapsed_ms.saturating_add(elapsed),
+                Err(error) => {
+                    let elapsed = match error {
+                        HookExecutionError::TimedOut { elapsed_ms } => elapsed_ms,
+                        _ => 0,
+                    };
+                    Self::record_execution(hook_id, now, false, elapsed);
+                    return Err(());
+                },
+            }
+        }
+
+        hook_info.last_execution = now;
+        Self::record_execution(hook_id, now, true, elapsed_ms);
+        crate::SyncHookFailureScore::<T>::remove(hook_id);
+        Ok(())
+    }
+
+    /// Appends an execution to the hook's statistics, dropping the oldest entry when full.
+    fn record_execution(hook_id: u32, timestamp: T::BlockNumber, success: bool, elapsed_ms: u32) {
+        crate::SyncHookExecutionStats::<T>::mutate(hook_id, |stats| {
+            push_rotating(stats, (timestamp, success, elapsed_ms));
+        });
+    }
+    
+    /// Reports a failed execution and disables the hook once its consecutive failures,
+    /// weighted by reason, reach `HookAutoDisableThreshold`.
+    fn record_failure(
+        hook_id: u32,
+        hook_info: &mut HookInfo<T>,
+        pet_id: PetId,
+        change_type: StateChangeType,
+        error: &HookExecutionError,
+    ) {
+        let reason = error.reason();
+        
+        crate::Pallet::<T>::deposit_event(crate::Event::HookExecutionFailed {
+            hook_id,
+            pet_id,
+            change_type: change_type as u8,
+            reason,
+            detail: error.detail(),
+        });
+        
+        let score = crate::SyncHookFailureScore::<T>::mutate(hook_id, |score| {
+            *score = score.saturating_add(reason.failure_weight());
+            *score
+        });
+        
+        if score >= T::HookAutoDisableThreshold::get() {
+            hook_info.enabled = false;
+            crate::SyncHookFailureScore::<T>::remove(hook_id);
+            crate::Pallet::<T>::deposit_event(crate::Event::HookDisabled { hook_id });
+        }
+    }
+    
+    /// Clears a pet's sync flags in `mask` once the caller's off-chain system has caught up
+    /// with `version`. The caller must own a registered hook interested in every change type
+    /// in `mask`, and `version` must be the pet's current state version, so a change made after
+    /// the caller synchronized stays dirty.
+    ///
+    /// # Parameters
+    ///
+    /// * `who` - The account acknowledging the sync
+    /// * `pet_id` - The ID of the synchronized pet
+    /// * `mask` - The `SyncFlags` bits to clear
+    /// * `version` - The pet state version the caller synchronized
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the flags were cleared, Err otherwise
+    pub fn acknowledge(who: T::AccountId, pet_id: PetId, mask: u16, version: u32) -> DispatchResult {
+        // 1. The caller may only clear what its hooks are notified of.
+        let interests = crate::SyncHookRegistry::<T>::iter_values()
+            .filter(|hook_info| hook_info.account_id == who)
+            .map(|hook_info| hook_info.interests)
+            .reduce(|all, interests| all | interests)
+            .ok_or(crate::Error::<T>::NotHookOwner)?;
+        frame_support::ensure!(
+            mask & !SyncFlags::mask_for_interests(interests) == 0,
+            crate::Error::<T>::SyncMaskNotInInterests
+        );
+
+        // 2. Clear the flags, unless the pet changed since the caller synchronized.
+        crate::PetNfts::<T>::try_mutate(pet_id, |pet_nft_opt| -> DispatchResult {
+            let pet_nft = pet_nft_opt.as_mut().ok_or(crate::Error::<T>::PetNotFound)?;
+            frame_support::ensure!(pet_nft.state_version == version, crate::Error::<T>::StaleSyncVersion);
+            pet_nft.sync_flags.clear_mask(mask);
+            Ok(())
+        })?;
+
+        crate::Pallet::<T>::deposit_event(crate::Event::SyncAcknowledged { pet_id, account_id: who, mask, version });
+
+        Ok(())
+    }
+    
     /// Gets information about a registered hook.
     /// 
     /// # Parameters
@@ -0,0 +1,128 @@
+//! # Temporary Traits
+//!
+//! Some items grant a personality trait only for a while, e.g. "Festive" for the duration of a
+//! seasonal event. Such grants are kept in `TemporaryTraits` with the block they expire at,
+//! apart from `PetNft::personality_traits`, so they never take a permanent trait slot. They have
+//! their own cap, `MaxTemporaryTraits`.
+//!
+//! Trait readers (the quest checker, the UI profile and compatibility scoring) see the pet's
+//! permanent traits followed by its unexpired temporary ones. Expired grants are pruned when
+//! read, with a `TemporaryTraitExpired` event each, so no block hook is needed. Granting a trait
+//! the pet already holds temporarily refreshes its expiry.
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec::Vec;
+use crate::traits::TraitTypeString;
+use crate::{Config, Error, Event, Pallet, PetId, PetNft};
+
+/// A system for granting temporary personality traits and reading them back.
+pub struct TemporaryTraitSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> TemporaryTraitSystem<T> {
+    /// Grants a pet a trait for `duration_blocks` blocks. If the pet already holds the trait
+    /// temporarily, its expiry is moved to `duration_blocks` from now.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    /// * `trait_string` - The trait to grant
+    /// * `duration_blocks` - The number of blocks the trait lasts
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the trait was granted, Err if the trait is too long, the
+    ///   duration is zero or the pet already holds `MaxTemporaryTraits` other temporary traits
+    pub fn grant(
+        pet_id: PetId,
+        trait_string: TraitTypeString,
+        duration_blocks: frame_system::pallet_prelude::BlockNumberFor<T>,
+    ) -> DispatchResult {
+        ensure!(trait_string.len() <= T::MaxTraitStringLen::get() as usize, Error::<T>::TraitStringTooLong);
+        ensure!(!duration_blocks.is_zero(), Error::<T>::InvalidTemporaryTraitDuration);
+
+        // Prune first, so expired grants do not hold up the cap.
+        let mut grants = Self::prune(pet_id);
+        let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(duration_blocks);
+        match grants.iter_mut().find(|(granted, _)| *granted == trait_string) {
+            Some(grant) => grant.1 = expires_at,
+            None => grants
+                .try_push((trait_string.clone(), expires_at))
+                .map_err(|_| Error::<T>::TooManyTemporaryTraits)?,
+        }
+        crate::TemporaryTraits::<T>::insert(pet_id, grants);
+
+        Pallet::<T>::deposit_event(Event::TemporaryTraitGranted { pet_id, trait_name: trait_string, expires_at });
+        Ok(())
+    }
+
+    /// Gets a pet's unexpired temporary traits, pruning the expired ones.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<TraitTypeString>` - The unexpired temporary traits, in the order they were granted
+    pub fn active_traits(pet_id: PetId) -> Vec<TraitTypeString> {
+        Self::prune(pet_id).into_iter().map(|(trait_string, _)| trait_string).collect()
+    }
+
+    /// Gets a pet's permanent traits followed by its unexpired temporary ones, pruning the
+    /// expired ones. A trait held both ways is listed once.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<TraitTypeString>` - The pet's traits
+    pub fn all_traits(pet: &PetNft<T>) -> Vec<TraitTypeString> {
+        let mut traits = pet.personality_traits.to_vec();
+        for trait_string in Self::active_traits(pet.id) {
+            if !traits.contains(&trait_string) {
+                traits.push(trait_string);
+            }
+        }
+        traits
+    }
+
+    /// Removes a pet's expired temporary traits, emitting `TemporaryTraitExpired` for each, and
+    /// returns the unexpired grants.
+    fn prune(
+        pet_id: PetId,
+    ) -> frame_support::BoundedVec<(TraitTypeString, frame_system::pallet_prelude::BlockNumberFor<T>), T::MaxTemporaryTraits> {
+        let mut grants = crate::TemporaryTraits::<T>::get(pet_id);
+        if grants.is_empty() {
+            return grants;
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut expired = Vec::new();
+        grants.retain(|(trait_string, expires_at)| {
+            if *expires_at <= now {
+                expired.push(trait_string.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if expired.is_empty() {
+            return grants;
+        }
+
+        if grants.is_empty() {
+            crate::TemporaryTraits::<T>::remove(pet_id);
+        } else {
+            crate::TemporaryTraits::<T>::insert(pet_id, grants.clone());
+        }
+        for trait_name in expired {
+            Pallet::<T>::deposit_event(Event::TemporaryTraitExpired { pet_id, trait_name });
+        }
+        grants
+    }
+}
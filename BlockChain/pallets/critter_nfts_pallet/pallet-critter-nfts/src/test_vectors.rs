@@ -0,0 +1,327 @@
+//! # Test Vectors
+//!
+//! Frozen reference vectors for the pallet's formulas, so off-chain simulators can match the
+//! chain exactly. Each formula has a fixture file in `test-vectors/` with `VECTOR_COUNT`
+//! vectors, inputs and outputs together, spanning edge cases (zero entropy, minimum and maximum
+//! stats, saturation points) and pseudo-random inputs from a fixed seed:
+//!
+//! - `dna.json`: mint DNA, `dna::derive_dna` with the runtime's types (`u64` accounts and
+//!   nonces, `H256` seeds)
+//! - `charter.json`: charter attributes, `CharterAttributes::from_dna`
+//! - `offspring.json`: an offspring's generation and trait inheritance rolls from its DNA,
+//!   `lineage::offspring_generation` and `inheritance::inherits`
+//! - `xp_curve.json`: the default XP curve without a level cap, `LinearLevelCurve` and
+//!   `leveling::level_up`
+//! - `effective_stats.json`: the effective stats pipeline, `EffectiveStats::compute`
+//!
+//! Bytes are `0x`-prefixed hex and `u64` values are decimal strings, so JavaScript clients
+//! read them without losing precision. Chances are in parts per billion.
+//!
+//! The pallet's tests check the fixtures with `crittercraft_traits::golden::check_fixture`, so
+//! a formula change fails them until the fixtures are regenerated on purpose with
+//! `CRITTERCRAFT_REGENERATE_VECTORS=1 cargo test`. The module is compiled for tests and with the
+//! `test-vectors` feature, for clients that want the canonical functions themselves.
+
+use std::path::PathBuf;
+use crittercraft_traits::golden::{hex, VectorEntropy};
+use crittercraft_traits::types::StatModifier;
+use sp_runtime::{Perbill, Percent};
+use crate::dna::{derive_dna, MintEntropy};
+use crate::effective_stats::EffectiveStats;
+use crate::inheritance::{effective_chance, inherits};
+use crate::leveling::{level_up, LevelCurve, LinearLevelCurve};
+use crate::lineage::offspring_generation;
+use crate::mint_preview::CharterAttributes;
+use crate::traits::{DnaHashType, TraitTypeString};
+use crate::PetId;
+
+/// The number of vectors in each fixture.
+pub const VECTOR_COUNT: usize = 64;
+
+/// Species the DNA vectors mint, including the empty one.
+const SPECIES: [&str; 4] = ["", "Cat", "Dragon", "Axolotl"];
+
+/// Names the DNA vectors mint; the longest crosses into a two-byte length prefix.
+const NAMES: [&str; 5] = [
+    "",
+    "Tom",
+    "Ember",
+    "Nibbles the Third",
+    "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+];
+
+/// Traits the offspring vectors roll for.
+const TRAITS: [&str; 6] = ["", "Brave", "Curious", "Playful", "Shy", "Loyal"];
+
+/// Gets the path of a fixture file.
+///
+/// # Parameters
+///
+/// * `name` - The fixture's name, without the extension
+///
+/// # Returns
+///
+/// * `PathBuf` - The path of `test-vectors/<name>.json` in this crate
+pub fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-vectors").join(format!("{}.json", name))
+}
+
+/// Derives the DNA of a mint with the runtime's types.
+pub fn dna(
+    seed: [u8; 32],
+    owner: u64,
+    pet_id: PetId,
+    species: &[u8],
+    name: &[u8],
+    entropy: &MintEntropy<u64>,
+) -> DnaHashType {
+    derive_dna(&seed, &owner, pet_id, &species, &name, entropy)
+}
+
+/// Rolls an offspring's generation and whether it inherits a parent trait.
+///
+/// # Returns
+///
+/// * `(u32, Perbill, bool)` - The generation, the trait's effective chance and whether it is
+///   inherited
+pub fn offspring(
+    dna: &DnaHashType,
+    parent1_generation: u32,
+    parent2_generation: u32,
+    trait_string: &TraitTypeString,
+    chance: Perbill,
+    shared: bool,
+) -> (u32, Perbill, bool) {
+    (
+        offspring_generation(parent1_generation, parent2_generation),
+        effective_chance(chance, shared),
+        inherits(dna, trait_string, chance, shared),
+    )
+}
+
+/// The vectors of `dna.json`.
+pub fn dna_vectors() -> Vec<String> {
+    let mut entropy = VectorEntropy::new(1);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            let seed = match index {
+                0 => [0u8; 32],
+                1 => [u8::MAX; 32],
+                _ => entropy.bytes(),
+            };
+            let owner = match index % 4 {
+                0 => 0,
+                1 => u64::MAX,
+                _ => entropy.next_u64(),
+            };
+            let pet_id = match index % 5 {
+                0 => 0,
+                1 => PetId::MAX,
+                _ => entropy.next_u64() as PetId,
+            };
+            let species = SPECIES[index % SPECIES.len()];
+            let name = NAMES[index % NAMES.len()];
+            let mint_entropy = MintEntropy {
+                extrinsic_index: match index % 4 {
+                    0 => 0,
+                    1 => u32::MAX,
+                    _ => entropy.below(16) as u32,
+                },
+                account_nonce: match index % 6 {
+                    0 => 0,
+                    1 => u64::MAX,
+                    _ => entropy.below(1_000),
+                },
+                mint_counter: match index % 7 {
+                    0 => 0,
+                    1 => u32::MAX,
+                    _ => entropy.below(8) as u32,
+                },
+            };
+            let dna = dna(seed, owner, pet_id, species.as_bytes(), name.as_bytes(), &mint_entropy);
+            format!(
+                "{{\"seed\":\"{}\",\"owner\":\"{}\",\"pet_id\":{},\"species\":\"{}\",\"name\":\"{}\",\"extrinsic_index\":{},\"account_nonce\":\"{}\",\"mint_counter\":{},\"dna\":\"{}\"}}",
+                hex(&seed),
+                owner,
+                pet_id,
+                species,
+                name,
+                mint_entropy.extrinsic_index,
+                mint_entropy.account_nonce,
+                mint_entropy.mint_counter,
+                hex(&dna),
+            )
+        })
+        .collect()
+}
+
+/// The vectors of `charter.json`.
+pub fn charter_vectors() -> Vec<String> {
+    let mut entropy = VectorEntropy::new(2);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            // Zero and saturated DNA, then every affinity with the lowest and highest stats.
+            let dna = match index {
+                0 => [0u8; 32],
+                1 => [u8::MAX; 32],
+                2..=9 => [(index - 1) as u8; 32],
+                10..=17 => [u8::MAX - (index - 9) as u8; 32],
+                _ => entropy.bytes(),
+            };
+            let attributes = CharterAttributes::from_dna(&dna);
+            format!(
+                "{{\"dna\":\"{}\",\"strength\":{},\"agility\":{},\"intelligence\":{},\"vitality\":{},\"affinity\":\"{:?}\"}}",
+                hex(&dna),
+                attributes.strength,
+                attributes.agility,
+                attributes.intelligence,
+                attributes.vitality,
+                attributes.affinity,
+            )
+        })
+        .collect()
+}
+
+/// The vectors of `offspring.json`.
+pub fn offspring_vectors() -> Vec<String> {
+    let mut entropy = VectorEntropy::new(3);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            let dna = if index == 0 { [0u8; 32] } else { entropy.bytes() };
+            let (parent1_generation, parent2_generation) = match index % 4 {
+                0 => (0, 0),
+                1 => (u32::MAX, u32::MAX),
+                2 => (entropy.below(100) as u32, entropy.below(100) as u32),
+                _ => (entropy.next_u64() as u32, 0),
+            };
+            let trait_name = TRAITS[index % TRAITS.len()];
+            let trait_string = TraitTypeString::truncate_from(trait_name.as_bytes().to_vec());
+            let chance = match index % 5 {
+                0 => Perbill::zero(),
+                1 => Perbill::one(),
+                2 => Perbill::from_percent(50),
+                _ => Perbill::from_parts(entropy.below(Perbill::ACCURACY as u64 + 1) as u32),
+            };
+            let shared = index % 3 == 0;
+            let (generation, effective, inherited) =
+                offspring(&dna, parent1_generation, parent2_generation, &trait_string, chance, shared);
+            format!(
+                "{{\"dna\":\"{}\",\"parent1_generation\":{},\"parent2_generation\":{},\"trait\":\"{}\",\"chance\":{},\"shared\":{},\"generation\":{},\"effective_chance\":{},\"inherited\":{}}}",
+                hex(&dna),
+                parent1_generation,
+                parent2_generation,
+                trait_name,
+                chance.deconstruct(),
+                shared,
+                generation,
+                effective.deconstruct(),
+                inherited,
+            )
+        })
+        .collect()
+}
+
+/// The vectors of `xp_curve.json`.
+pub fn xp_curve_vectors() -> Vec<String> {
+    // Level zero, exact thresholds, and the levels where the curve saturates.
+    const EDGES: [(u32, u32); 16] = [
+        (0, 0),
+        (0, u32::MAX),
+        (1, 0),
+        (1, 99),
+        (1, 100),
+        (1, 299),
+        (1, 300),
+        (2, 199),
+        (2, 200),
+        (1, u32::MAX),
+        (42_949_672, u32::MAX),
+        (42_949_673, u32::MAX - 1),
+        (42_949_673, u32::MAX),
+        (u32::MAX, 0),
+        (u32::MAX, u32::MAX - 1),
+        (u32::MAX, u32::MAX),
+    ];
+    let mut entropy = VectorEntropy::new(4);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            let (level, xp) = match EDGES.get(index) {
+                Some(edge) => *edge,
+                None if index % 4 == 0 => (entropy.next_u64() as u32, entropy.next_u64() as u32),
+                None => (entropy.below(1_000) as u32, entropy.below(1_000_000) as u32),
+            };
+            let (new_level, new_xp) = level_up::<LinearLevelCurve>(level, xp, u32::MAX);
+            format!(
+                "{{\"level\":{},\"xp\":{},\"next_level_xp\":{},\"new_level\":{},\"new_xp\":{}}}",
+                level,
+                xp,
+                <LinearLevelCurve as LevelCurve>::xp_for_level(level),
+                new_level,
+                new_xp,
+            )
+        })
+        .collect()
+}
+
+/// The vectors of `effective_stats.json`.
+pub fn effective_stats_vectors() -> Vec<String> {
+    let modifier = |values: [i16; 4]| StatModifier {
+        strength: values[0],
+        agility: values[1],
+        intelligence: values[2],
+        vitality: values[3],
+    };
+    // (base, bonus, stage percent, equipment, conditions, buff percent)
+    let edges: [([u8; 4], [u8; 4], i32, [i16; 4], [i16; 4], u8); 8] = [
+        ([0; 4], [0; 4], 0, [0; 4], [0; 4], 0),
+        ([u8::MAX; 4], [u8::MAX; 4], 50, [50; 4], [50; 4], 100),
+        ([u8::MAX; 4], [0; 4], i8::MAX as i32, [i16::MAX; 4], [i16::MAX; 4], 100),
+        ([0; 4], [0; 4], i8::MIN as i32, [i16::MIN; 4], [i16::MIN; 4], 0),
+        ([20; 4], [0; 4], -100, [-5, 0, 5, 51], [-51, -50, 50, 51], 0),
+        ([5, 10, 15, 20], [1, 2, 3, 4], -50, [0; 4], [-30, -30, -30, -30], 0),
+        ([200, 210, 220, 230], [40, 40, 40, 40], 30, [50, 0, 0, 0], [0; 4], 50),
+        ([7, 13, 17, 19], [0; 4], -1, [1; 4], [-1; 4], 1),
+    ];
+    let mut entropy = VectorEntropy::new(5);
+    (0..VECTOR_COUNT)
+        .map(|index| {
+            let (base, bonus, stage_percent, equipment, conditions, buff) = match edges.get(index) {
+                Some(edge) => *edge,
+                None => (
+                    [0; 4].map(|_| entropy.below(16) as u8 + 5),
+                    [0; 4].map(|_| entropy.below(64) as u8),
+                    entropy.below(201) as i32 - 100,
+                    [0; 4].map(|_| entropy.below(121) as i16 - 20),
+                    [0; 4].map(|_| entropy.below(241) as i16 - 120),
+                    entropy.below(101) as u8,
+                ),
+            };
+            let grown = EffectiveStats {
+                strength: base[0].saturating_add(bonus[0]),
+                agility: base[1].saturating_add(bonus[1]),
+                intelligence: base[2].saturating_add(bonus[2]),
+                vitality: base[3].saturating_add(bonus[3]),
+            };
+            let stats = EffectiveStats::compute(
+                grown,
+                stage_percent,
+                modifier(equipment),
+                modifier(conditions),
+                Percent::from_percent(buff),
+            );
+            format!(
+                "{{\"base\":{:?},\"bonus\":{:?},\"stage_percent\":{},\"equipment\":{:?},\"conditions\":{:?},\"buff_percent\":{},\"stats\":[{},{},{},{}]}}",
+                base,
+                bonus,
+                stage_percent,
+                equipment,
+                conditions,
+                buff,
+                stats.strength,
+                stats.agility,
+                stats.intelligence,
+                stats.vitality,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,232 @@
+//! # Pet Trades
+//!
+//! This module lets two owners swap pets directly. The proposer's pet is locked while the trade
+//! is pending, so it cannot be sold or moved elsewhere; the counterparty's pet stays free until
+//! they accept. Both pets must be past the transfer gates when the trade is proposed.
+//! Accepting swaps both pets in one call or not at all. A trade that can no longer
+//! be honoured (a pet changed hands, the lock was released, or it expired) is void: accepting it
+//! cancels it instead, and expired trades can be cleaned up by anyone.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    pallet_prelude::RuntimeDebug,
+    traits::Get,
+};
+use scale_info::TypeInfo;
+use crittercraft_traits::nft::TransferRestrictionProvider;
+use sp_runtime::traits::Saturating;
+use crate::traits::LockerId;
+use crate::transfer_gates::TransferGateSystem;
+use crate::{Config, Error, Event, Pallet, PetId};
+
+/// Identifier of a pending trade.
+pub type TradeId = u64;
+
+/// A proposed swap of two pets.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingTrade<AccountId, BlockNumber> {
+    /// The account that proposed the trade and owns the offered pet
+    pub proposer: AccountId,
+
+    /// The owner of the requested pet when the trade was proposed
+    pub counterparty: AccountId,
+
+    /// The proposer's pet, locked until the trade is accepted or cancelled
+    pub offered_pet: PetId,
+
+    /// The counterparty's pet
+    pub requested_pet: PetId,
+
+    /// The last block in which the trade can be accepted
+    pub expires_at: BlockNumber,
+}
+
+/// The trade type stored for a runtime.
+pub type PendingTradeOf<T> = PendingTrade<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
+/// Why a trade was cancelled.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TradeCancelReason {
+    /// One of the parties withdrew the trade.
+    Withdrawn,
+    /// The trade was not accepted in time.
+    Expired,
+    /// A pet changed hands or the offered pet's lock was released before acceptance.
+    PetsChanged,
+}
+
+/// A system for managing pet trades.
+pub struct TradeSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> TradeSystem<T> {
+    /// Proposes swapping `offered_pet` for `requested_pet` and locks the offered pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `proposer` - The account proposing the trade
+    /// * `offered_pet` - The proposer's pet
+    /// * `requested_pet` - The pet the proposer wants in return
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the trade was proposed, Err otherwise
+    pub fn propose(proposer: T::AccountId, offered_pet: PetId, requested_pet: PetId) -> DispatchResult {
+        // 1. Validate the request. Both pets must be free to move, exactly as for a transfer.
+        let offered = Pallet::<T>::listing_eligibility(offered_pet).ok_or(Error::<T>::PetNotFound)?;
+        let requested = Pallet::<T>::listing_eligibility(requested_pet).ok_or(Error::<T>::PetNotFound)?;
+        ensure!(offered.owner == proposer, Error::<T>::NotOwner);
+        ensure!(requested.owner != proposer, Error::<T>::CannotTradeWithSelf);
+        TransferGateSystem::<T>::ensure_passes(offered_pet)?;
+        TransferGateSystem::<T>::ensure_passes(requested_pet)?;
+        ensure!(offered.transferable && requested.transferable, Error::<T>::NftLocked);
+
+        // 2. Lock the offered pet and record the trade.
+        Pallet::<T>::do_lock_nft(&proposer, &offered_pet, LockerId::Trade)?;
+        let trade_id = crate::NextTradeId::<T>::get();
+        let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(T::TradeExpiryBlocks::get());
+        crate::PendingTrades::<T>::insert(trade_id, PendingTrade {
+            proposer: proposer.clone(),
+            counterparty: requested.owner.clone(),
+            offered_pet,
+            requested_pet,
+            expires_at,
+        });
+        crate::NextTradeId::<T>::put(trade_id.saturating_add(1));
+
+        // 3. Emit event.
+        Pallet::<T>::deposit_event(Event::TradeProposed {
+            trade_id,
+            proposer,
+            counterparty: requested.owner,
+            offered_pet,
+            requested_pet,
+            expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Accepts a trade and swaps both pets. A void trade is cancelled instead.
+    /// Both sides must have room for the incoming pet before giving theirs up, as with transfers.
+    ///
+    /// # Parameters
+    ///
+    /// * `sender` - The account accepting the trade; must be the counterparty
+    /// * `trade_id` - The ID of the trade
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pets were swapped or the void trade was cancelled, Err otherwise
+    pub fn accept(sender: T::AccountId, trade_id: TradeId) -> DispatchResult {
+        let trade = crate::PendingTrades::<T>::get(trade_id).ok_or(Error::<T>::TradeNotFound)?;
+        ensure!(sender == trade.counterparty, Error::<T>::NotTradeCounterparty);
+
+        // 1. Cancel the trade if it can no longer be honoured.
+        if frame_system::Pallet::<T>::block_number() > trade.expires_at {
+            Self::close(trade_id, &trade, Some(TradeCancelReason::Expired));
+            return Ok(());
+        }
+        if !Self::is_intact(&trade) {
+            Self::close(trade_id, &trade, Some(TradeCancelReason::PetsChanged));
+            return Ok(());
+        }
+
+        // 2. The requested pet must be free to move, and both sides must have room.
+        ensure!(
+            Pallet::<T>::listing_eligibility(trade.requested_pet).map_or(false, |eligibility| eligibility.transferable),
+            Error::<T>::NftLocked
+        );
+        ensure!(
+            T::TransferRestrictions::transfer_restriction(&trade.offered_pet).is_none(),
+            Error::<T>::NftLocked
+        );
+        let max_owned = T::MaxOwnedPets::get() as usize;
+        ensure!(
+            crate::OwnerOfPet::<T>::get(&trade.proposer).len() < max_owned
+                && crate::OwnerOfPet::<T>::get(&trade.counterparty).len() < max_owned,
+            Error::<T>::RecipientExceedMaxOwnedPets
+        );
+
+        // 3. Release the lock and swap both pets.
+        Self::close(trade_id, &trade, None);
+        Pallet::<T>::do_transfer_nft(&trade.proposer, &trade.counterparty, &trade.offered_pet)?;
+        Pallet::<T>::do_transfer_nft(&trade.counterparty, &trade.proposer, &trade.requested_pet)?;
+
+        // 4. Emit events.
+        Pallet::<T>::deposit_event(Event::PetNftTransferred {
+            from: trade.proposer.clone(),
+            to: trade.counterparty.clone(),
+            pet_id: trade.offered_pet,
+        });
+        Pallet::<T>::deposit_event(Event::PetNftTransferred {
+            from: trade.counterparty.clone(),
+            to: trade.proposer.clone(),
+            pet_id: trade.requested_pet,
+        });
+        Pallet::<T>::deposit_event(Event::TradeAccepted {
+            trade_id,
+            proposer: trade.proposer,
+            counterparty: trade.counterparty,
+            offered_pet: trade.offered_pet,
+            requested_pet: trade.requested_pet,
+        });
+
+        Ok(())
+    }
+
+    /// Cancels a trade and unlocks the offered pet. Either party can cancel at any time;
+    /// anyone can clean up an expired trade.
+    ///
+    /// # Parameters
+    ///
+    /// * `sender` - The account cancelling the trade
+    /// * `trade_id` - The ID of the trade
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the trade was cancelled, Err otherwise
+    pub fn cancel(sender: T::AccountId, trade_id: TradeId) -> DispatchResult {
+        let trade = crate::PendingTrades::<T>::get(trade_id).ok_or(Error::<T>::TradeNotFound)?;
+
+        let reason = if sender == trade.proposer || sender == trade.counterparty {
+            TradeCancelReason::Withdrawn
+        } else {
+            ensure!(frame_system::Pallet::<T>::block_number() > trade.expires_at, Error::<T>::NotTradeParty);
+            TradeCancelReason::Expired
+        };
+        Self::close(trade_id, &trade, Some(reason));
+
+        Ok(())
+    }
+
+    /// Whether both pets are still owned as recorded and the offered pet is still locked by the trade.
+    fn is_intact(trade: &PendingTradeOf<T>) -> bool {
+        crate::PetNftOwner::<T>::get(trade.offered_pet).as_ref() == Some(&trade.proposer)
+            && crate::PetNftOwner::<T>::get(trade.requested_pet).as_ref() == Some(&trade.counterparty)
+            && crate::LockedNfts::<T>::get(trade.offered_pet).map(|lock| lock.locker) == Some(LockerId::Trade)
+    }
+
+    /// Removes a trade and releases the offered pet's lock if the proposer still holds it.
+    /// Emits `TradeCancelled` when a cancellation reason is given.
+    fn close(trade_id: TradeId, trade: &PendingTradeOf<T>, reason: Option<TradeCancelReason>) {
+        crate::PendingTrades::<T>::remove(trade_id);
+        // Only the proposer's own lock is released; a pet that changed hands keeps its new state.
+        let _ = Pallet::<T>::do_unlock_nft(&trade.proposer, &trade.offered_pet, LockerId::Trade);
+
+        if let Some(reason) = reason {
+            Pallet::<T>::deposit_event(Event::TradeCancelled {
+                trade_id,
+                proposer: trade.proposer.clone(),
+                offered_pet: trade.offered_pet,
+                reason,
+            });
+        }
+    }
+}
@@ -12,13 +12,14 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
+use crate::kinds::SkillKind;
 use crate::{Config, Error, PetId, PetNft};
 
 /// Represents a skill that a pet can learn.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Skill {
     /// The skill type
-    pub skill_type: u8,
+    pub skill_type: SkillKind,
     
     /// The skill name
     pub name: Vec<u8>,
@@ -61,25 +62,8 @@ pub struct SkillBenefits {
     pub special_ability: u8,
 }
 
-/// Skill types.
-pub enum SkillType {
-    Hunting = 0,
-    Fishing = 1,
-    Gathering = 2,
-    Crafting = 3,
-    Mining = 4,
-    Farming = 5,
-    Cooking = 6,
-    Healing = 7,
-    Combat = 8,
-    Stealth = 9,
-    Diplomacy = 10,
-    Leadership = 11,
-    Magic = 12,
-    Technology = 13,
-    Music = 14,
-    Art = 15,
-}
+/// Skill types, now the typed `crate::kinds::SkillKind`.
+pub type SkillType = crate::kinds::SkillKind;
 
 /// Training intensity levels.
 pub enum TrainingIntensity {
@@ -99,7 +83,7 @@ impl<T: Config> PetTrainingSystem<T> {
     /// 
     /// # Parameters
     /// 
-    /// * `pet_id` - The ID of the pet
+    /// * `pet` - The pet, as already read by the caller
     /// * `skill_type` - The type of skill to train
     /// * `training_intensity` - The intensity of the training
     /// 
@@ -107,18 +91,17 @@ impl<T: Config> PetTrainingSystem<T> {
     /// 
     /// * `DispatchResult` - Ok if successful, Err otherwise
     pub fn train_pet(
-        pet_id: PetId,
-        skill_type: u8,
+        pet: &PetNft<T>,
+        skill_type: SkillKind,
         training_intensity: u8,
     ) -> DispatchResult {
-        // Get the pet from storage
-        let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        let pet_id = pet.id;
         
         // Get the skill
         let skill = Self::get_skill(skill_type)?;
         
         // Check if the pet meets the requirements for this skill
-        Self::check_requirements(&pet, &skill)?;
+        Self::check_requirements(pet, &skill)?;
         
         // Get the pet's current skill level
         let current_level = Self::get_skill_level(pet_id, skill_type)?;
@@ -131,7 +114,7 @@ impl<T: Config> PetTrainingSystem<T> {
         
         // Calculate training effectiveness
         let effectiveness = Self::calculate_training_effectiveness(
-            &pet,
+            pet,
             &skill,
             training_intensity,
         )?;
@@ -170,10 +153,10 @@ impl<T: Config> PetTrainingSystem<T> {
             if current_level + 1 >= skill.max_level / 2 {
                 // High skill level: evolve a relevant trait
                 let trait_type = match skill_type {
-                    0 => crate::personality::PersonalityTraitType::Brave as u8, // Hunting
-                    1 => crate::personality::PersonalityTraitType::Patient as u8, // Fishing
-                    2 => crate::personality::PersonalityTraitType::Curious as u8, // Gathering
-                    3 => crate::personality::PersonalityTraitType::Creative as u8, // Crafting
+                    SkillKind::Hunting => crate::personality::PersonalityTraitType::Brave as u8,
+                    SkillKind::Fishing => crate::personality::PersonalityTraitType::Patient as u8,
+                    SkillKind::Gathering => crate::personality::PersonalityTraitType::Curious as u8,
+                    SkillKind::Crafting => crate::personality::PersonalityTraitType::Creative as u8,
                     _ => crate::personality::PersonalityTraitType::Intelligent as u8, // Default
                 };
                 
@@ -203,12 +186,12 @@ impl<T: Config> PetTrainingSystem<T> {
     /// # Returns
     /// 
     /// * `Result<Skill, DispatchError>` - The skill, or an error
-    fn get_skill(skill_type: u8) -> Result<Skill, DispatchError> {
+    fn get_skill(skill_type: SkillKind) -> Result<Skill, DispatchError> {
         // In a real implementation, this would get the skill from storage
         // For now, we'll just return a hardcoded skill based on the type
         
         match skill_type {
-            0 => { // Hunting
+            SkillKind::Hunting => {
                 Ok(Skill {
                     skill_type,
                     name: b"Hunting".to_vec(),
@@ -226,7 +209,7 @@ impl<T: Config> PetTrainingSystem<T> {
                     },
                 })
             },
-            1 => { // Fishing
+            SkillKind::Fishing => {
                 Ok(Skill {
                     skill_type,
                     name: b"Fishing".to_vec(),
@@ -244,7 +227,7 @@ impl<T: Config> PetTrainingSystem<T> {
                     },
                 })
             },
-            2 => { // Gathering
+            SkillKind::Gathering => {
                 Ok(Skill {
                     skill_type,
                     name: b"Gathering".to_vec(),
@@ -337,7 +320,7 @@ impl<T: Config> PetTrainingSystem<T> {
     /// * `Result<u8, DispatchError>` - The skill level, or an error
     fn get_skill_level(
         pet_id: PetId,
-        skill_type: u8,
+        skill_type: SkillKind,
     ) -> Result<u8, DispatchError> {
         let skills = crate::PetSkills::<T>::get(pet_id);
         
@@ -495,7 +478,7 @@ impl<T: Config> PetTrainingSystem<T> {
     /// * `DispatchResult` - Ok if successful, Err otherwise
     fn increase_skill_level(
         pet_id: PetId,
-        skill_type: u8,
+        skill_type: SkillKind,
         current_level: u8,
     ) -> DispatchResult {
         crate::PetSkills::<T>::try_mutate(pet_id, |skills| -> DispatchResult {
@@ -511,11 +494,22 @@ impl<T: Config> PetTrainingSystem<T> {
             }
             
             // Add the new skill
+            let size_before = crate::footprint::stored_size(skills);
             skills.try_push((skill_type, current_level + 1))
                 .map_err(|_| Error::<T>::TooManySkills)?;
             
+            crate::footprint::FootprintSystem::<T>::note_resize(pet_id, size_before, crate::footprint::stored_size(skills));
             Ok(())
-        })
+        })?;
+        
+        // Count the new level towards skill achievements
+        crate::achievements::AchievementSystem::<T>::note_progress(
+            pet_id,
+            crate::achievements::ProgressSource::Skill(skill_type),
+            crate::achievements::ProgressUpdate::Raise(current_level as u32 + 1),
+        );
+        
+        Ok(())
     }
     
     /// Applies the effects of successful training.
@@ -647,10 +641,10 @@ impl<T: Config> PetTrainingSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Vec<(u8, u8)>` - The skills (skill_type, level)
+    /// * `Vec<(SkillKind, u8)>` - The skills (skill_type, level)
     pub fn get_all_skills(
         pet_id: PetId,
-    ) -> Vec<(u8, u8)> {
+    ) -> Vec<(SkillKind, u8)> {
         crate::PetSkills::<T>::get(pet_id).to_vec()
     }
     
@@ -666,7 +660,7 @@ impl<T: Config> PetTrainingSystem<T> {
     /// * `Result<(Skill, u8), DispatchError>` - The skill and the pet's level in it, or an error
     pub fn get_skill_details(
         pet_id: PetId,
-        skill_type: u8,
+        skill_type: SkillKind,
     ) -> Result<(Skill, u8), DispatchError> {
         let skill = Self::get_skill(skill_type)?;
         let level = Self::get_skill_level(pet_id, skill_type)?;
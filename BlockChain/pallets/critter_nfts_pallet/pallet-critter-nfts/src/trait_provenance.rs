@@ -0,0 +1,160 @@
+//! # Trait Provenance
+//!
+//! A pet's personality traits come from two sources: the owner, through `update_pet_metadata`,
+//! and the system, when items, achievements or lifecycle events grant one. Other systems rely
+//! on system-granted traits (e.g. a quest requiring a "Champion" trait), so owners must not be
+//! able to remove them.
+//!
+//! `PetNft::personality_traits` keeps the full list, and `SystemTraits` records which of those
+//! traits the system granted. Any trait not in `SystemTraits` is owner-sourced, which covers
+//! every trait stored before provenance was tracked, so no storage migration is needed.
+//!
+//! `update_pet_metadata` only replaces the owner-sourced traits. The system traits are kept in
+//! front of the submitted ones, and the combined list must fit in `MaxPetPersonalityTraits`.
+//! `remove_personality_trait` drops a single owner-sourced trait and refuses system traits.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, pallet_prelude::RuntimeDebug, traits::Get, BoundedVec};
+use scale_info::TypeInfo;
+use crate::traits::TraitTypeString;
+use crate::{Config, Error, PetId, PetNft};
+
+/// Where a pet's personality trait came from.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TraitSource {
+    /// Chosen by the owner; the owner can remove it.
+    Owner,
+    /// Granted by an item, achievement or lifecycle event; the owner cannot remove it.
+    System,
+}
+
+/// A system for tracking where personality traits came from.
+pub struct TraitProvenanceSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> TraitProvenanceSystem<T> {
+    /// Returns where one of a pet's traits came from, or `None` if the pet does not have it.
+    pub fn source_of(pet: &PetNft<T>, trait_string: &TraitTypeString) -> Option<TraitSource> {
+        if !pet.personality_traits.contains(trait_string) {
+            return None;
+        }
+        Some(if crate::SystemTraits::<T>::get(pet.id).contains(trait_string) {
+            TraitSource::System
+        } else {
+            TraitSource::Owner
+        })
+    }
+
+    /// Grants a trait to a pet as system-sourced. Granting a trait the pet already has as an
+    /// owner trait makes it system-sourced; granting one it has as a system trait is a no-op.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet receiving the trait
+    /// * `trait_string` - The trait to grant
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet has the trait as a system trait, Err if it has no room
+    pub fn grant_system_trait(pet: &mut PetNft<T>, trait_string: TraitTypeString) -> DispatchResult {
+        ensure!(
+            trait_string.len() <= T::MaxTraitStringLen::get() as usize,
+            Error::<T>::TraitStringTooLong
+        );
+
+        // 1. Add the trait to the pet, unless it already has it.
+        if !pet.personality_traits.contains(&trait_string) {
+            pet.personality_traits.try_push(trait_string.clone())
+                .map_err(|_| Error::<T>::TooManyPersonalityTraits)?;
+        }
+
+        // 2. Mark it as system-sourced. It fits: the system traits are a subset of the pet's.
+        let mut system_traits = Self::present_system_traits(pet);
+        if !system_traits.contains(&trait_string) {
+            system_traits.try_push(trait_string).map_err(|_| Error::<T>::TooManyPersonalityTraits)?;
+        }
+        Self::store_system_traits(pet.id, system_traits);
+
+        Ok(())
+    }
+
+    /// Replaces a pet's owner-sourced traits with `owner_traits`, keeping its system traits.
+    /// Submitted traits that are already system traits are not duplicated.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet whose traits are replaced
+    /// * `owner_traits` - The owner's new traits
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the traits were replaced, Err if a trait is too long or the
+    ///   owner's traits do not fit next to the system traits
+    pub fn replace_owner_traits(
+        pet: &mut PetNft<T>,
+        owner_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>,
+    ) -> DispatchResult {
+        // 1. Validate the submitted traits.
+        for trait_string in owner_traits.iter() {
+            ensure!(trait_string.len() <= T::MaxTraitStringLen::get() as usize, Error::<T>::TraitStringTooLong);
+        }
+
+        // 2. Keep the system traits first, then add the owner's.
+        let system_traits = Self::present_system_traits(pet);
+        let mut traits = system_traits.clone();
+        for trait_string in owner_traits.into_iter() {
+            if !traits.contains(&trait_string) {
+                traits.try_push(trait_string).map_err(|_| Error::<T>::TooManyTraitsWithSystemTraits)?;
+            }
+        }
+
+        pet.personality_traits = traits;
+        Self::store_system_traits(pet.id, system_traits);
+
+        Ok(())
+    }
+
+    /// Removes one of a pet's owner-sourced traits, keeping the order of the rest.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet losing the trait
+    /// * `trait_string` - The trait to remove
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the trait was removed, Err if the pet does not have it or the
+    ///   system granted it
+    pub fn remove_owner_trait(pet: &mut PetNft<T>, trait_string: &TraitTypeString) -> DispatchResult {
+        match Self::source_of(pet, trait_string) {
+            None => Err(Error::<T>::TraitNotFound.into()),
+            Some(TraitSource::System) => Err(Error::<T>::SystemTraitNotRemovable.into()),
+            Some(TraitSource::Owner) => {
+                pet.personality_traits.retain(|t| t != trait_string);
+                Ok(())
+            }
+        }
+    }
+
+    /// The pet's system traits, in the order they appear on the pet. Traits another system has
+    /// since removed from the pet are dropped.
+    fn present_system_traits(pet: &PetNft<T>) -> BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits> {
+        let system_traits = crate::SystemTraits::<T>::get(pet.id);
+        let mut present = BoundedVec::default();
+        for trait_string in pet.personality_traits.iter().filter(|t| system_traits.contains(t)) {
+            // Cannot fail: the pet's traits fit the same bound.
+            let _ = present.try_push(trait_string.clone());
+        }
+        present
+    }
+
+    /// Stores a pet's system traits, removing the entry once it has none.
+    fn store_system_traits(pet_id: PetId, system_traits: BoundedVec<TraitTypeString, T::MaxPetPersonalityTraits>) {
+        if system_traits.is_empty() {
+            crate::SystemTraits::<T>::remove(pet_id);
+        } else {
+            crate::SystemTraits::<T>::insert(pet_id, system_traits);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! # Transfer Gates
+//!
+//! Anti-flip gates that keep freshly acquired or low-level pets from being moved on at once:
+//!
+//! - ownership: a pet can be transferred `MinOwnershipBlocksBeforeTransfer` blocks after it was
+//!   acquired, counted from `PetNft::acquired_at_block`, which every mint, claim and ownership
+//!   change sets
+//! - level: a pet below `MinLevelForTransfer` cannot be transferred
+//!
+//! Both are off at zero. They apply to new transfers: `transfer_pet_nft`, proposing a trade and
+//! locking a pet for a marketplace listing. Deliveries of sales already agreed are not gated:
+//! `SharedNftManager::transfer_nft` and `NftManagement::transfer`, which the marketplace calls
+//! after a sale, and `marketplace_transfer_pet` with `bypass_gates` set, which only
+//! `MarketplaceOrigin` can dispatch.
+//!
+//! A gated call fails with `TransferOwnershipTooRecent` or `TransferLevelTooLow`;
+//! `listing_eligibility` reports the same gate with the block it unlocks at or the level it
+//! needs.
+
+use crittercraft_traits::types::TransferBlockReason;
+use frame_support::{dispatch::DispatchResult, traits::Get};
+use sp_runtime::{traits::{Saturating, Zero}, SaturatedConversion};
+use crate::{Config, Error, PetId, PetNft};
+
+/// A system for checking the anti-flip transfer gates.
+pub struct TransferGateSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> TransferGateSystem<T> {
+    /// Gets the first gate a pet does not pass yet, ownership before level.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet` - The pet
+    ///
+    /// # Returns
+    ///
+    /// * `Option<TransferBlockReason>` - The failed gate with when it unlocks, or `None` if the
+    ///   pet passes both
+    pub fn gate(pet: &PetNft<T>) -> Option<TransferBlockReason> {
+        let min_blocks = T::MinOwnershipBlocksBeforeTransfer::get();
+        if !min_blocks.is_zero() {
+            let unlocks_at = pet.acquired_at_block.saturating_add(min_blocks);
+            if frame_system::Pallet::<T>::block_number() < unlocks_at {
+                return Some(TransferBlockReason::OwnershipTooRecent { unlocks_at: unlocks_at.saturated_into::<u64>() });
+            }
+        }
+
+        let required_level = T::MinLevelForTransfer::get();
+        if pet.level < required_level {
+            return Some(TransferBlockReason::LevelTooLow { required_level });
+        }
+
+        None
+    }
+
+    /// Checks that a pet passes both gates.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Ok if the pet passes, Err naming the gate it fails otherwise
+    pub fn ensure_passes(pet_id: PetId) -> DispatchResult {
+        let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        match Self::gate(&pet) {
+            Some(TransferBlockReason::OwnershipTooRecent { .. }) => Err(Error::<T>::TransferOwnershipTooRecent.into()),
+            Some(TransferBlockReason::LevelTooLow { .. }) => Err(Error::<T>::TransferLevelTooLow.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -14,19 +14,24 @@ use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
 use codec::{Encode, Decode};
+use crate::kinds::BuffKind;
 use crate::{Config, Error, PetId, PetNft, ElementType};
 
 /// Represents a UI-friendly pet profile with all relevant information.
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
-pub struct UiPetProfile<T: Config> {
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct UiPetProfile<AccountId> {
     // Basic information
     pub id: PetId,
     pub name: Vec<u8>,
     pub species: Vec<u8>,
     pub level: u32,
     pub experience: u32,
+    pub rested_xp: u32, // Rested XP available to the next XP grant
+    pub generation: u32, // Breeding generation, 0 for minted pets
     pub mood: u8,
-    pub owner: T::AccountId,
+    pub owner: AccountId,
+    pub bio: Vec<u8>, // Owner-authored bio, empty if not set
     
     // Attributes
     pub strength: u8,
@@ -58,6 +63,8 @@ pub struct UiPetProfile<T: Config> {
     pub adaptations: Vec<(u8, u8)>, // (environment_type, adaptation_level)
     pub preferred_environment: u8, // The environment type the pet is most adapted to
     pub environment_bonuses: Vec<(u8, Vec<(u8, i8)>)>, // (environment_type, [(stat_type, bonus)])
+    pub care_multipliers: Vec<(u8, u32)>, // (care_action_kind, gain multiplier in parts per billion) of the pet's species
+    pub species_preferred_environment: Option<u8>, // The environment type the pet's species thrives in
     
     // Skills
     pub skills: Vec<(u8, u8)>, // (skill_type, skill_level)
@@ -90,7 +97,7 @@ pub struct UiPetProfile<T: Config> {
     
     // Status effects
     pub status_effects: Vec<(u8, u64, i8)>, // (effect_type, duration, magnitude)
-    pub buffs: Vec<(Vec<u8>, u64, Vec<(u8, i8)>)>, // (name, duration, [(stat_type, bonus)])
+    pub buffs: Vec<(Vec<u8>, u64, Vec<(u8, i8)>)>, // (name, blocks remaining, [(buff kind, bonus percent)])
     pub debuffs: Vec<(Vec<u8>, u64, Vec<(u8, i8)>)>, // (name, duration, [(stat_type, penalty)])
     
     // Mood and needs
@@ -103,6 +110,7 @@ pub struct UiPetProfile<T: Config> {
     pub last_played: u64,
     pub creation_time: u64,
     pub last_interaction: u64,
+    pub free_care_remaining: u32, // Free care actions the pet has left this care epoch
     pub active_hours: Vec<u8>, // Hours of the day when the pet is most active
     pub activity_level: u8, // Overall activity level
     
@@ -119,6 +127,26 @@ pub struct UiPetProfile<T: Config> {
     pub hidden_potential: Vec<(u8, u8)>, // (potential_type, level)
 }
 
+/// The profile type returned for a runtime.
+pub type UiPetProfileOf<T> = UiPetProfile<<T as frame_system::Config>::AccountId>;
+
+/// Maximum number of bio bytes included in a pet summary preview.
+pub const BIO_PREVIEW_LEN: usize = 64;
+
+/// A compact pet summary for search results and listings.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct UiPetSummary<T: Config> {
+    pub id: PetId,
+    pub name: Vec<u8>,
+    pub species: Vec<u8>,
+    pub level: u32,
+    pub generation: u32, // Breeding generation, 0 for minted pets
+    pub mood: u8,
+    pub owner: T::AccountId,
+    pub bio_preview: Vec<u8>, // First BIO_PREVIEW_LEN bytes of the bio, cut on a character boundary
+    pub quality_score: u32,
+}
+
 /// A system for bridging between the blockchain and UI/UX.
 pub struct UiBridge<T: Config> {
     _phantom: std::marker::PhantomData<T>,
@@ -133,16 +161,17 @@ impl<T: Config> UiBridge<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Result<UiPetProfile<T>, DispatchError>` - The pet profile, or an error
-    pub fn get_pet_profile(pet_id: PetId) -> Result<UiPetProfile<T>, DispatchError> {
+    /// * `Result<UiPetProfileOf<T>, DispatchError>` - The pet profile, or an error
+    pub fn get_pet_profile(pet_id: PetId) -> Result<UiPetProfileOf<T>, DispatchError> {
         // Get the pet from storage
         let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
         
         // Get the pet owner
         let owner = crate::PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
         
-        // Get personality traits
-        let personality_traits = pet.personality_traits.iter()
+        // Get personality traits, including unexpired temporary ones
+        let personality_traits = crate::temporary_traits::TemporaryTraitSystem::<T>::all_traits(&pet)
+            .iter()
             .map(|trait_str| {
                 // In a real implementation, this would look up the trait name from a mapping
                 // For now, we'll just return the trait string and a default intensity
@@ -160,23 +189,35 @@ impl<T: Config> UiBridge<T> {
         let recent_interactions = crate::social::SocialInteractionSystem::<T>::get_social_interactions(pet_id)
             .iter()
             .map(|(other_pet_id, interaction_type, outcome, timestamp)| 
-                (*other_pet_id, *interaction_type, *outcome, timestamp.saturated_into::<u64>()))
+                (*other_pet_id, *interaction_type as u8, *outcome, timestamp.saturated_into::<u64>()))
             .collect::<Vec<_>>();
         
         // Get environmental adaptations
-        let adaptations = crate::environment::EnvironmentalAdaptationSystem::<T>::get_environmental_adaptations(pet_id);
+        let adaptations = crate::PetEnvironmentalAdaptations::<T>::get(pet_id)
+            .into_iter()
+            .map(|(environment_type, level)| (environment_type as u8, level))
+            .collect::<Vec<_>>();
         
         // Get skills
-        let skills = crate::training::PetTrainingSystem::<T>::get_skills(pet_id);
+        let skills = crate::PetSkills::<T>::get(pet_id)
+            .into_iter()
+            .map(|(skill_type, level)| (skill_type as u8, level))
+            .collect::<Vec<_>>();
         
         // Get significant memories
-        let significant_memories = crate::memory::PetMemorySystem::<T>::get_significant_memories(pet_id, 5);
+        let significant_memories = crate::memory::PetMemorySystem::<T>::get_significant_memories(pet_id, 5)
+            .into_iter()
+            .map(|(memory_type, significance, timestamp, data)| (memory_type as u8, significance, timestamp, data))
+            .collect::<Vec<_>>();
         
         // Get achievements
-        let achievements = crate::achievements::AchievementSystem::<T>::get_achievements(pet_id);
+        let achievements = crate::PetAchievements::<T>::get(pet_id).into_inner();
         
         // Get lifecycle events
-        let lifecycle_events = crate::lifecycle::LifecycleEventSystem::<T>::get_lifecycle_events(pet_id);
+        let lifecycle_events = crate::PetLifecycleEvents::<T>::get(pet_id)
+            .into_iter()
+            .map(|(kind, block)| (kind as u8, block.saturated_into::<u64>()))
+            .collect::<Vec<_>>();
         
         // Get visual attributes
         let visual_attributes = crate::visual::VisualSystem::<T>::get_visual_attributes(pet_id)?;
@@ -184,13 +225,14 @@ impl<T: Config> UiBridge<T> {
         // Get visual effects
         let visual_effects = crate::visual::VisualSystem::<T>::get_visual_effects(pet_id)?;
         
-        // Calculate derived stats
-        let health = (pet.base_vitality as u16 * 10) + (pet.level as u16 * 5);
-        let energy = (pet.base_vitality as u16 * 5) + (pet.base_agility as u16 * 5) + (pet.level as u16 * 2);
-        let speed = (pet.base_agility as u16 * 10) + (pet.level as u16 * 2);
-        let defense = (pet.base_vitality as u16 * 5) + (pet.base_strength as u16 * 5) + (pet.level as u16 * 2);
-        let attack = (pet.base_strength as u16 * 10) + (pet.level as u16 * 3);
-        let special_ability = (pet.base_intelligence as u16 * 10) + (pet.level as u16 * 3);
+        // Calculate derived stats from effective stats so every modifier is included
+        let stats = crate::effective_stats::EffectiveStatsSystem::<T>::of(&pet);
+        let health = (stats.vitality as u16 * 10) + (pet.level as u16 * 5);
+        let energy = (stats.vitality as u16 * 5) + (stats.agility as u16 * 5) + (pet.level as u16 * 2);
+        let speed = (stats.agility as u16 * 10) + (pet.level as u16 * 2);
+        let defense = (stats.vitality as u16 * 5) + (stats.strength as u16 * 5) + (pet.level as u16 * 2);
+        let attack = (stats.strength as u16 * 10) + (pet.level as u16 * 3);
+        let special_ability = (stats.intelligence as u16 * 10) + (pet.level as u16 * 3);
         
         // Determine dominant personality trait
         let dominant_trait = if !personality_traits.is_empty() {
@@ -238,6 +280,13 @@ impl<T: Config> UiBridge<T> {
             (preferred_environment, vec![(0, 5i8), (1, 3i8)]), // Strength and agility bonuses in preferred environment
         ];
         
+        // Show how the pet's species responds to care
+        let care_profile = crate::species_care::SpeciesCareSystem::<T>::profile(&pet.initial_species);
+        let care_multipliers = care_profile.multipliers.iter()
+            .map(|(kind, multiplier)| (*kind as u8, multiplier.deconstruct()))
+            .collect::<Vec<_>>();
+        let species_preferred_environment = care_profile.preferred_environment.map(|environment| environment as u8);
+        
         // Generate skill progress
         let skill_progress = skills.iter().map(|(skill_type, level)| (*skill_type, 50u8)).collect::<Vec<_>>(); // 50% to next level
         
@@ -332,10 +381,20 @@ impl<T: Config> UiBridge<T> {
             (0, 3600u64, 5i8), // Well-fed buff for 1 hour
         ];
         
-        // Generate buffs
-        let buffs = vec![
-            (b"Well Rested".to_vec(), 7200u64, vec![(0, 2i8), (1, 2i8)]), // Strength and agility bonus for 2 hours
-        ];
+        // List the pet's active temporary buffs
+        let now = frame_system::Pallet::<T>::block_number();
+        let buffs = crate::buffs::BuffSystem::<T>::active_buffs(pet_id)
+            .into_iter()
+            .map(|buff| {
+                let name = match buff.kind {
+                    BuffKind::XpGain => b"XP Boost".to_vec(),
+                    BuffKind::MoodGain => b"Mood Boost".to_vec(),
+                    BuffKind::Stats => b"Stat Boost".to_vec(),
+                };
+                let remaining = (buff.expires_at - now).saturated_into::<u64>();
+                (name, remaining, vec![(buff.kind as u8, buff.magnitude.deconstruct() as i8)])
+            })
+            .collect::<Vec<_>>();
         
         // Generate debuffs
         let debuffs = Vec::new(); // No debuffs currently
@@ -364,7 +423,7 @@ impl<T: Config> UiBridge<T> {
         let activity_level = if pet.base_agility > 150 { 80u8 } else { 50u8 };
         
         // Calculate XP to next level
-        let xp_needed_for_next_level = 100u32.saturating_mul(pet.level);
+        let xp_needed_for_next_level = crate::leveling::LevelingSystem::<T>::xp_to_next_level(pet.level);
         let xp_to_next_level = xp_needed_for_next_level.saturating_sub(pet.experience_points);
         
         // Calculate level progress percentage
@@ -429,6 +488,9 @@ impl<T: Config> UiBridge<T> {
             (1, (pet.base_agility + pet.base_vitality) / 50), // Survival potential
         ];
         
+        // Get the owner-authored bio
+        let bio = crate::PetBios::<T>::get(pet_id).map(|bio| bio.to_vec()).unwrap_or_default();
+        
         // Create the enhanced UI pet profile
         let profile = UiPetProfile {
             id: pet_id,
@@ -436,8 +498,11 @@ impl<T: Config> UiBridge<T> {
             species: pet.initial_species.to_vec(),
             level: pet.level,
             experience: pet.experience_points,
+            rested_xp: crate::rested_xp::RestedXpSystem::<T>::current(pet_id),
+            generation: pet.generation,
             mood: pet.mood_indicator,
             owner,
+            bio,
             
             // Basic attributes
             strength: pet.base_strength,
@@ -469,6 +534,8 @@ impl<T: Config> UiBridge<T> {
             adaptations,
             preferred_environment,
             environment_bonuses,
+            care_multipliers,
+            species_preferred_environment,
             
             // Skills
             skills,
@@ -514,6 +581,7 @@ impl<T: Config> UiBridge<T> {
             last_played: pet.last_played_block.saturated_into::<u64>(),
             creation_time: 0, // Would need to be stored in the pet struct
             last_interaction: pet.last_state_update_block.saturated_into::<u64>(),
+            free_care_remaining: crate::free_care::FreeCareSystem::<T>::remaining(pet.id),
             active_hours,
             activity_level,
             
@@ -533,6 +601,51 @@ impl<T: Config> UiBridge<T> {
         Ok(profile)
     }
     
+    /// Gets a compact pet summary for search results.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `pet_id` - The ID of the pet
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<UiPetSummary<T>, DispatchError>` - The pet summary, or an error
+    pub fn get_pet_summary(pet_id: PetId) -> Result<UiPetSummary<T>, DispatchError> {
+        let pet = crate::PetNfts::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        let owner = crate::PetNftOwner::<T>::get(pet_id).ok_or(Error::<T>::PetNotFound)?;
+        
+        let bio_preview = crate::PetBios::<T>::get(pet_id)
+            .map(|bio| Self::truncate_bio(&bio, BIO_PREVIEW_LEN))
+            .unwrap_or_default();
+        
+        Ok(UiPetSummary {
+            id: pet_id,
+            name: pet.current_pet_name.to_vec(),
+            species: pet.initial_species.to_vec(),
+            level: pet.level,
+            generation: pet.generation,
+            mood: pet.mood_indicator,
+            owner,
+            bio_preview,
+            quality_score: crate::valuation::PetValuationSystem::<T>::quality_score(pet_id).unwrap_or(0),
+        })
+    }
+    
+    /// Truncates a UTF-8 bio to at most `max_len` bytes without splitting a character.
+    fn truncate_bio(bio: &[u8], max_len: usize) -> Vec<u8> {
+        if bio.len() <= max_len {
+            return bio.to_vec();
+        }
+        
+        // Step back past UTF-8 continuation bytes (0b10xxxxxx) to a character boundary
+        let mut end = max_len;
+        while end > 0 && (bio[end] & 0xC0) == 0x80 {
+            end -= 1;
+        }
+        
+        bio[..end].to_vec()
+    }
+    
     /// Gets UI-friendly analytics data for a pet.
     /// 
     /// # Parameters
@@ -14,6 +14,7 @@ use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 use scale_info::TypeInfo;
 use codec::{Encode, Decode};
+use crittercraft_traits::bounded::push_evicting_by;
 use crate::{Config, Error, PetId, PetNft};
 
 /// Represents a user experience flow step.
@@ -106,6 +107,31 @@ pub struct UserAchievement {
     pub unlock_timestamp: Option<u64>,
 }
 
+/// A user's notification digest preference.
+/// When present, non-critical notifications are counted instead of stored individually.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DigestSettings<BlockNumber> {
+    /// Minimum number of blocks between digest materializations
+    pub interval_blocks: BlockNumber,
+    
+    /// The block at which the last digest was materialized
+    pub last_flush: BlockNumber,
+}
+
+/// The starter pet granted when a new user completes onboarding.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct StarterPetConfig<T: Config> {
+    /// The species of the starter pet
+    pub species: BoundedVec<u8, T::MaxSpeciesNameLen>,
+    
+    /// The name given to the starter pet
+    pub name: BoundedVec<u8, T::MaxPetNameLen>,
+}
+
+/// The last step of the built-in UX flow; advancing past it completes the flow.
+pub const LAST_UX_FLOW_STEP: u32 = 10;
+
 /// UX flow step types.
 pub enum UxFlowStepType {
     Welcome = 0,
@@ -250,7 +276,7 @@ impl<T: Config> UserExperienceSystem<T> {
             step_type: if step_id == 0 { UxFlowStepType::Welcome as u8 } else { UxFlowStepType::Tutorial as u8 },
             highlight_element,
             required_action,
-            next_step_id: step_id + 1,
+            next_step_id: if step_id >= LAST_UX_FLOW_STEP { 0 } else { step_id + 1 },
             skippable: step_id > 3, // First few steps are mandatory
         })
     }
@@ -282,7 +308,19 @@ impl<T: Config> UserExperienceSystem<T> {
         // If there is no next step, the flow is complete
         if next_step_id == 0 {
             // Clear the user's flow
-            crate::UserUxFlow::<T>::insert(account_id, (0, 0));
+            crate::UserUxFlow::<T>::insert(account_id.clone(), (0, 0));
+            
+            crate::Pallet::<T>::deposit_event(crate::Event::UxFlowCompleted {
+                account_id: account_id.clone(),
+                flow_id,
+                timestamp: frame_system::Pallet::<T>::block_number(),
+            });
+            
+            // Completing onboarding grants the starter pet
+            if flow_id == T::OnboardingFlowId::get() {
+                Self::grant_starter_pet(&account_id)?;
+            }
+            
             return Ok(None);
         }
         
@@ -295,6 +333,41 @@ impl<T: Config> UserExperienceSystem<T> {
         Ok(Some(next_step))
     }
     
+    /// Grants the configured starter pet to an account that has just completed onboarding.
+    /// Accounts that already own pets, were granted a starter before, or complete onboarding
+    /// while no starter is configured are skipped silently.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `account_id` - The user's account ID
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Option<PetId>, DispatchError>` - The starter pet's ID if one was granted, or an error
+    pub fn grant_starter_pet(account_id: &T::AccountId) -> Result<Option<PetId>, DispatchError> {
+        if crate::StarterGranted::<T>::contains_key(account_id)
+            || !crate::OwnerOfPet::<T>::get(account_id).is_empty()
+        {
+            return Ok(None);
+        }
+        
+        let config = match crate::OnboardingStarterConfig::<T>::get() {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        
+        let pet_id = crate::Pallet::<T>::do_mint(account_id, config.species, config.name)?;
+        crate::StarterGranted::<T>::insert(account_id, pet_id);
+        
+        crate::Pallet::<T>::deposit_event(crate::Event::StarterPetGranted {
+            owner: account_id.clone(),
+            pet_id,
+            timestamp: frame_system::Pallet::<T>::block_number(),
+        });
+        
+        Ok(Some(pet_id))
+    }
+    
     /// Skips the current UX flow step for a user.
     /// 
     /// # Parameters
@@ -385,7 +458,8 @@ impl<T: Config> UserExperienceSystem<T> {
     /// 
     /// # Returns
     /// 
-    /// * `Result<u32, DispatchError>` - The notification ID, or an error
+    /// * `Result<Option<u32>, DispatchError>` - The notification ID, None if it was folded into the user's digest
+    ///   or dropped for exceeding the block's notification budget, or an error
     pub fn add_notification(
         account_id: T::AccountId,
         notification_type: u8,
@@ -394,13 +468,82 @@ impl<T: Config> UserExperienceSystem<T> {
         icon: Vec<u8>,
         priority: u8,
         action: Vec<u8>,
-    ) -> Result<u32, DispatchError> {
+    ) -> Result<Option<u32>, DispatchError> {
         // Ensure the notification type is valid
         ensure!(notification_type <= NotificationType::Promotional as u8, Error::<T>::InvalidNotificationType);
         
         // Ensure the priority is valid
         ensure!(priority <= NotificationPriority::Urgent as u8, Error::<T>::InvalidNotificationPriority);
         
+        // Drop non-critical notifications once the block's budget is spent
+        let critical = priority >= T::DigestPriorityThreshold::get();
+        if !Self::take_notification_budget(critical) {
+            return Ok(None);
+        }
+        
+        // Materialize any digest that has come due before handling the new notification
+        Self::maybe_flush_digest(&account_id)?;
+        
+        // Non-critical notifications for digest users are only counted
+        if !critical
+            && crate::NotificationDigestSettings::<T>::contains_key(&account_id)
+        {
+            crate::PendingDigest::<T>::mutate(&account_id, notification_type, |count| {
+                *count = count.saturating_add(1);
+            });
+            return Ok(None);
+        }
+        
+        Self::push_notification(account_id, notification_type, title, message, icon, priority, action)
+            .map(Some)
+    }
+    
+    /// Counts a notification against the block's `MaxNotificationsPerBlock` budget.
+    /// Critical notifications always go through and use up budget that non-critical
+    /// notifications, digested or not, would otherwise get. A dropped notification is only
+    /// counted in `ThrottledNotifications`, for the `NotificationsThrottled` event.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `critical` - Whether the notification is at or above `DigestPriorityThreshold`
+    /// 
+    /// # Returns
+    /// 
+    /// * `bool` - Whether the notification may be generated
+    fn take_notification_budget(critical: bool) -> bool {
+        let used = crate::NotificationsThisBlock::<T>::get();
+        if !critical && used >= T::MaxNotificationsPerBlock::get() {
+            crate::ThrottledNotifications::<T>::mutate(|dropped| *dropped = dropped.saturating_add(1));
+            return false;
+        }
+        crate::NotificationsThisBlock::<T>::put(used.saturating_add(1));
+        true
+    }
+    
+    /// Stores a notification for a user, bypassing the digest.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `account_id` - The user's account ID
+    /// * `notification_type` - The notification type
+    /// * `title` - The notification title
+    /// * `message` - The notification message
+    /// * `icon` - The notification icon
+    /// * `priority` - The notification priority
+    /// * `action` - The action associated with the notification
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<u32, DispatchError>` - The notification ID, or an error
+    fn push_notification(
+        account_id: T::AccountId,
+        notification_type: u8,
+        title: Vec<u8>,
+        message: Vec<u8>,
+        icon: Vec<u8>,
+        priority: u8,
+        action: Vec<u8>,
+    ) -> Result<u32, DispatchError> {
         // Convert the inputs to bounded vectors
         let bounded_title: BoundedVec<u8, ConstU32<64>> = title.try_into()
             .map_err(|_| Error::<T>::TitleTooLong)?;
@@ -438,17 +581,170 @@ impl<T: Config> UserExperienceSystem<T> {
             read: false,
         };
         
-        // Add the notification to the user's notifications
-        crate::UserNotifications::<T>::try_mutate(account_id, |notifications| -> DispatchResult {
-            notifications.try_push(notification.clone())
-                .map_err(|_| Error::<T>::TooManyNotifications)?;
-            
-            Ok(())
-        })?;
+        // Add the notification to the user's notifications. When the list is full, a read
+        // notification is dropped first, then the least urgent one, oldest first.
+        crate::UserNotifications::<T>::mutate(account_id.clone(), |notifications| {
+            push_evicting_by(notifications, notification.clone(), |existing| (!existing.read, existing.priority));
+        });
+        
+        // Emit an event
+        crate::Pallet::<T>::deposit_event(crate::Event::NotificationAdded {
+            account_id,
+            notification_id,
+            notification_type,
+            timestamp: current_block,
+            unix_time_ms: crate::Pallet::<T>::unix_time_ms(),
+        });
         
         Ok(notification_id)
     }
     
+    /// Enables, reconfigures or disables digest mode for a user.
+    /// Disabling flushes any pending counts immediately so nothing is lost.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `account_id` - The user's account ID
+    /// * `enabled` - Whether digest mode should be enabled
+    /// * `interval_blocks` - Minimum number of blocks between digests
+    /// 
+    /// # Returns
+    /// 
+    /// * `DispatchResult` - Ok if successful, Err otherwise
+    pub fn set_notification_digest(
+        account_id: T::AccountId,
+        enabled: bool,
+        interval_blocks: BlockNumberFor<T>,
+    ) -> DispatchResult {
+        let current_block = frame_system::Pallet::<T>::block_number();
+        
+        if enabled {
+            crate::NotificationDigestSettings::<T>::mutate(&account_id, |settings| {
+                let last_flush = settings.as_ref().map(|s| s.last_flush).unwrap_or(current_block);
+                *settings = Some(DigestSettings { interval_blocks, last_flush });
+            });
+        } else {
+            Self::flush_digest(&account_id)?;
+            crate::NotificationDigestSettings::<T>::remove(&account_id);
+        }
+        
+        Ok(())
+    }
+    
+    /// Materializes the user's digest if digest mode is enabled and the interval has elapsed.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `account_id` - The user's account ID
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Option<u32>, DispatchError>` - The digest notification ID if one was created, or an error
+    pub fn maybe_flush_digest(account_id: &T::AccountId) -> Result<Option<u32>, DispatchError> {
+        let settings = match crate::NotificationDigestSettings::<T>::get(account_id) {
+            Some(settings) => settings,
+            None => return Ok(None),
+        };
+        
+        let current_block = frame_system::Pallet::<T>::block_number();
+        if current_block < settings.last_flush.saturating_add(settings.interval_blocks) {
+            return Ok(None);
+        }
+        
+        Self::flush_digest(account_id)
+    }
+    
+    /// Turns the user's pending digest counts into a single summary notification,
+    /// e.g. "14 pet interactions, 3 achievements".
+    /// 
+    /// # Parameters
+    /// 
+    /// * `account_id` - The user's account ID
+    /// 
+    /// # Returns
+    /// 
+    /// * `Result<Option<u32>, DispatchError>` - The digest notification ID, None if nothing was pending, or an error
+    pub fn flush_digest(account_id: &T::AccountId) -> Result<Option<u32>, DispatchError> {
+        let current_block = frame_system::Pallet::<T>::block_number();
+        
+        // Collect and clear the pending counts (bounded by the number of notification types)
+        let mut counts: Vec<(u8, u32)> = crate::PendingDigest::<T>::drain_prefix(account_id).collect();
+        counts.sort_by_key(|(notification_type, _)| *notification_type);
+        
+        crate::NotificationDigestSettings::<T>::mutate(account_id, |settings| {
+            if let Some(settings) = settings {
+                settings.last_flush = current_block;
+            }
+        });
+        
+        let total_count = counts.iter().fold(0u32, |acc, (_, count)| acc.saturating_add(*count));
+        if total_count == 0 {
+            return Ok(None);
+        }
+        
+        // Build the summary message
+        let mut message = Vec::new();
+        for (index, (notification_type, count)) in counts.iter().enumerate() {
+            if index > 0 {
+                message.extend_from_slice(b", ");
+            }
+            Self::push_decimal(&mut message, *count);
+            message.push(b' ');
+            message.extend_from_slice(Self::digest_label(*notification_type));
+        }
+        // Keep within the notification message bound
+        message.truncate(256);
+        
+        let notification_id = Self::push_notification(
+            account_id.clone(),
+            NotificationType::Info as u8,
+            b"Activity digest".to_vec(),
+            message,
+            b"digest_icon".to_vec(),
+            NotificationPriority::Low as u8,
+            b"view_notifications".to_vec(),
+        )?;
+        
+        crate::Pallet::<T>::deposit_event(crate::Event::NotificationDigestFlushed {
+            account_id: account_id.clone(),
+            notification_id,
+            total_count,
+            timestamp: current_block,
+        });
+        
+        Ok(Some(notification_id))
+    }
+    
+    /// Returns the plural label used for a notification type in digest summaries.
+    fn digest_label(notification_type: u8) -> &'static [u8] {
+        match notification_type {
+            x if x == NotificationType::Info as u8 => b"updates",
+            x if x == NotificationType::Warning as u8 => b"warnings",
+            x if x == NotificationType::Alert as u8 => b"alerts",
+            x if x == NotificationType::Achievement as u8 => b"achievements",
+            x if x == NotificationType::Event as u8 => b"events",
+            x if x == NotificationType::Social as u8 => b"pet interactions",
+            x if x == NotificationType::System as u8 => b"system messages",
+            _ => b"promotions",
+        }
+    }
+    
+    /// Appends the decimal representation of `value` to `buffer`.
+    fn push_decimal(buffer: &mut Vec<u8>, value: u32) {
+        let mut digits = [0u8; 10];
+        let mut remaining = value;
+        let mut len = 0;
+        loop {
+            digits[len] = b'0' + (remaining % 10) as u8;
+            remaining /= 10;
+            len += 1;
+            if remaining == 0 {
+                break;
+            }
+        }
+        buffer.extend(digits[..len].iter().rev());
+    }
+    
     /// Marks a notification as read.
     /// 
     /// # Parameters
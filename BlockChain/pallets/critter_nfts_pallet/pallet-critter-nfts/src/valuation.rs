@@ -0,0 +1,145 @@
+//! # Pet Valuation
+//!
+//! This module computes a deterministic quality score for pets, which the marketplace
+//! uses to suggest listing prices. The score is a weighted composite of the pet's
+//! progress, stats, traits, record and generation; all weights are Config constants so they
+//! can be tuned without a logic change.
+
+use frame_support::traits::Get;
+use sp_runtime::traits::Saturating;
+use crittercraft_traits::battle::BattleStatsProvider;
+use crate::effective_stats::EffectiveStatsSystem;
+use crate::{Config, PetId};
+
+/// Valuation inputs that live outside this pallet.
+/// Every method may return `None`, in which case the component contributes zero.
+pub trait ValuationInputs {
+    /// The rarity weight of a personality trait, as recorded in the trait registry.
+    fn trait_rarity(trait_name: &[u8]) -> Option<u32>;
+
+    /// The pet's prestige level.
+    fn prestige(pet_id: PetId) -> Option<u32>;
+
+    /// The number of times the pet has been bred.
+    fn breed_count(pet_id: PetId) -> Option<u32>;
+}
+
+impl ValuationInputs for () {
+    fn trait_rarity(_trait_name: &[u8]) -> Option<u32> { None }
+    fn prestige(_pet_id: PetId) -> Option<u32> { None }
+    fn breed_count(_pet_id: PetId) -> Option<u32> { None }
+}
+
+/// The raw components of a pet's quality score, before weighting.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct QualityComponents {
+    /// The pet's level
+    pub level: u32,
+
+    /// The sum of the pet's effective stats
+    pub stat_total: u32,
+
+    /// The sum of the rarity weights of the pet's personality traits
+    pub trait_rarity: u32,
+
+    /// The number of achievements the pet has earned
+    pub achievement_count: u32,
+
+    /// The pet's battle rating
+    pub battle_rating: u32,
+
+    /// The pet's prestige level
+    pub prestige: u32,
+
+    /// The number of times the pet has been bred
+    pub breed_count: u32,
+
+    /// The pet's breeding generation
+    pub generation: u32,
+}
+
+/// A system for valuing pets.
+pub struct PetValuationSystem<T: Config> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Config> PetValuationSystem<T> {
+    /// Gets the quality score of a pet.
+    /// Reads are bounded: the pet, its achievements, one battle record, the prestige and
+    /// breed count, and one rarity lookup per personality trait.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u32>` - The quality score, or None if the pet does not exist
+    pub fn quality_score(pet_id: PetId) -> Option<u32> {
+        Self::components(pet_id).map(|components| Self::score(&components))
+    }
+
+    /// Gathers the unweighted score components for a pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `pet_id` - The ID of the pet
+    ///
+    /// # Returns
+    ///
+    /// * `Option<QualityComponents>` - The components, or None if the pet does not exist
+    pub fn components(pet_id: PetId) -> Option<QualityComponents> {
+        let pet = crate::PetNfts::<T>::get(pet_id)?;
+
+        let stat_total = EffectiveStatsSystem::<T>::of(&pet).total();
+
+        let trait_rarity = pet.personality_traits
+            .iter()
+            .filter_map(|personality_trait| T::ValuationInputs::trait_rarity(personality_trait))
+            .fold(0u32, |total, rarity| total.saturating_add(rarity));
+
+        Some(QualityComponents {
+            level: pet.level,
+            stat_total,
+            trait_rarity,
+            achievement_count: crate::PetAchievements::<T>::get(pet_id).len() as u32,
+            battle_rating: T::BattleStats::battle_rating(&pet_id).unwrap_or(0) as u32,
+            prestige: T::ValuationInputs::prestige(pet_id).unwrap_or(0),
+            breed_count: T::ValuationInputs::breed_count(pet_id).unwrap_or(0),
+            generation: pet.generation,
+        })
+    }
+
+    /// Applies the configured weights to the score components.
+    /// Every component except the breed count and the generation can only raise the score.
+    /// The generation raises or lowers it depending on the sign of `QualityGenerationWeight`;
+    /// penalties saturate at zero.
+    ///
+    /// # Parameters
+    ///
+    /// * `components` - The unweighted components
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The quality score
+    pub fn score(components: &QualityComponents) -> u32 {
+        let weighted = |value: u32, weight: u32| value.saturating_mul(weight);
+
+        let total = weighted(components.level, T::QualityLevelWeight::get())
+            .saturating_add(weighted(components.stat_total, T::QualityStatWeight::get()))
+            .saturating_add(weighted(components.trait_rarity, T::QualityTraitRarityWeight::get()))
+            .saturating_add(weighted(components.achievement_count, T::QualityAchievementWeight::get()))
+            .saturating_add(weighted(components.battle_rating, T::QualityBattleRatingWeight::get()))
+            .saturating_add(weighted(components.prestige, T::QualityPrestigeWeight::get()));
+
+        let generation_weight = T::QualityGenerationWeight::get();
+        let generation = weighted(components.generation, generation_weight.unsigned_abs());
+        let total = if generation_weight >= 0 {
+            total.saturating_add(generation)
+        } else {
+            total.saturating_sub(generation)
+        };
+
+        total.saturating_sub(weighted(components.breed_count, T::QualityBreedPenalty::get()))
+    }
+}
@@ -361,6 +361,8 @@ impl<T: Config> VisualSystem<T> {
         
         // Update the attribute
         crate::PetVisualAttributes::<T>::try_mutate(pet_id, |attributes| -> DispatchResult {
+            let size_before = crate::footprint::stored_size(attributes);
+            
             // Find the attribute if it exists
             let attribute_index = attributes.iter().position(|attr| attr.attribute_type == attribute_type);
             
@@ -375,6 +377,7 @@ impl<T: Config> VisualSystem<T> {
                 }).map_err(|_| Error::<T>::TooManyAttributes)?;
             }
             
+            crate::footprint::FootprintSystem::<T>::note_resize(pet_id, size_before, crate::footprint::stored_size(attributes));
             Ok(())
         })
     }
@@ -1683,6 +1686,7 @@ impl<T: Config> VisualSystem<T> {
         // Add effects based on environment adaptations
         let adaptations = crate::environment::EnvironmentalAdaptationSystem::<T>::get_environmental_adaptations(pet_id);
         for (env_type, level) in adaptations {
+            let env_type = env_type as u8;
             if level >= 3 {
                 // Environment-specific effect with intensity based on level
                 effects.push(50 + env_type); // Base environment effect
@@ -1700,6 +1704,7 @@ impl<T: Config> VisualSystem<T> {
         // Add effects based on skills
         let skills = crate::training::PetTrainingSystem::<T>::get_skills(pet_id);
         for (skill_type, level) in skills {
+            let skill_type = skill_type as u8;
             if level >= 3 {
                 // Skill-specific effect with intensity based on level
                 effects.push(80 + skill_type); // Base skill effect
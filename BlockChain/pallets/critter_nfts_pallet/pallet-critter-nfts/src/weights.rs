@@ -1,8 +1,17 @@
 //! Weights for pallet-critter-nfts
 //!
-//! This file contains the WeightInfo trait and its default implementation.
-//! These weights are generated from FRAME benchmarking results and reflect
-//! the actual resource usage of each extrinsic in the pallet.
+//! This file contains the WeightInfo trait and its default implementation, in the layout
+//! `frame-benchmarking-cli` generates. The benchmarks behind each function are in
+//! `benchmarking.rs`; regenerate this file from them on reference hardware with the node's
+//! `benchmark pallet` subcommand, built with the `runtime-benchmarks` feature:
+//!
+//! ```text
+//! <node> benchmark pallet --chain dev --pallet pallet_critter_nfts --extrinsic '*' \
+//!     --steps 50 --repeat 20 --output src/weights.rs
+//! ```
+//!
+//! Until then, the reference times below are estimates; the database reads and writes are
+//! counted from the code paths the benchmarks exercise.
 //!
 //! The weights are carefully calculated to ensure:
 //! 1. Accurate transaction fee pricing
@@ -10,13 +19,14 @@
 //! 3. Fair resource allocation
 //! 4. Consistent performance across the network
 
+#![allow(unused_parens)]
 #![allow(unused_imports)]
 #![allow(clippy::unnecessary_cast)]
 #![allow(clippy::too_many_arguments)]
 
-use frame_support::weights::{
-    constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_MILLIS, WEIGHT_REF_TIME_PER_NANOS},
-    Weight,
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
 };
 use sp_std::marker::PhantomData;
 
@@ -56,7 +66,7 @@ pub trait WeightInfo {
 
     /// Weight for feed_pet extrinsic.
     /// This operation involves:
-    /// - Ownership verification
+    /// - Ownership verification, against the owner embedded in the pet record
     /// - Item consumption
     /// - Updating pet attributes
     /// - Updating timestamps
@@ -64,7 +74,7 @@ pub trait WeightInfo {
 
     /// Weight for play_with_pet extrinsic.
     /// This operation involves:
-    /// - Ownership verification
+    /// - Ownership verification, against the owner embedded in the pet record
     /// - Item consumption
     /// - Updating pet attributes
     /// - Updating timestamps
@@ -76,50 +86,930 @@ pub trait WeightInfo {
     /// - Applying mood penalty if needed
     /// - Updating last update timestamp
     fn apply_neglect_check() -> Weight;
+
+    /// Weight for apply_neglect_check_unsigned extrinsic checking `n` pets.
+    /// This operation involves:
+    /// - Checking and recording the block's batch
+    /// - Everything `apply_neglect_check` does, once per pet
+    fn apply_neglect_check_unsigned(n: u32) -> Weight;
+
+    /// Weight for batch_mint_pet_nfts extrinsic minting `n` pets.
+    /// This operation involves:
+    /// - Validating every entry against the owner's capacity
+    /// - Everything `mint_pet_nft` does, once per pet
+    /// - Updating the owner's pet list once
+    fn batch_mint_pet_nfts(n: u32) -> Weight;
+
+    /// Weight for the on_initialize hook, with `on_finalize`, on a block without a featured
+    /// rotation. A rotation reports its own weight on top.
+    /// This operation involves:
+    /// - Resetting the per-block notification budget
+    /// - Reporting and clearing the throttled notification count
+    fn on_initialize() -> Weight;
+
+    /// Weight for approve_transfer extrinsic.
+    /// This operation involves:
+    /// - Ownership verification, against the owner embedded in the pet record
+    /// - Lock check
+    /// - Storing the approval
+    fn approve_transfer() -> Weight;
+
+    /// Weight for revoke_approval extrinsic.
+    /// This operation involves:
+    /// - Ownership verification, against the owner embedded in the pet record
+    /// - Removing the approval
+    fn revoke_approval() -> Weight;
+
+    /// Weight for set_operator extrinsic.
+    /// This operation involves:
+    /// - Storing or removing the operator approval
+    fn set_operator() -> Weight;
+
+    /// Weight for set_pet_bio extrinsic.
+    /// This operation involves:
+    /// - Ownership and version verification
+    /// - Validating and storing the bio
+    /// - Bumping the state and display versions
+    fn set_pet_bio() -> Weight;
+
+    /// Weight for clear_pet_bio extrinsic.
+    /// This operation involves:
+    /// - Ownership and version verification
+    /// - Removing the bio
+    /// - Bumping the state and display versions
+    fn clear_pet_bio() -> Weight;
+
+    /// Weight for force_clear_pet_bio extrinsic.
+    /// This operation involves:
+    /// - Removing the bio
+    /// - Bumping the state and display versions
+    fn force_clear_pet_bio() -> Weight;
+
+    /// Weight for set_notification_digest extrinsic.
+    /// This operation involves:
+    /// - Updating the digest settings
+    /// - When disabling, flushing a digest pending for every notification type
+    fn set_notification_digest() -> Weight;
+
+    /// Weight for flush_digest extrinsic.
+    /// This operation involves:
+    /// - Interval verification
+    /// - Draining the digest pending for every notification type
+    /// - Storing the summary notification
+    fn flush_digest() -> Weight;
+
+    /// Weight for claim_deferred_lifecycle_reward extrinsic.
+    /// This operation involves:
+    /// - Ownership verification
+    /// - Paying the reward from the lifecycle reward pot
+    fn claim_deferred_lifecycle_reward() -> Weight;
+
+    /// Weight for set_social_interaction_type extrinsic.
+    /// This operation involves:
+    /// - Storing the interaction type
+    fn set_social_interaction_type() -> Weight;
+
+    /// Weight for set_onboarding_starter_config extrinsic.
+    /// This operation involves:
+    /// - Storing the starter pet's species and name
+    fn set_onboarding_starter_config() -> Weight;
+
+    /// Weight for remove_social_interaction_type extrinsic.
+    /// This operation involves:
+    /// - Removing the interaction type
+    fn remove_social_interaction_type() -> Weight;
+
+    /// Weight for subscribe_auto_care extrinsic.
+    /// This operation involves:
+    /// - Ownership verification
+    /// - Refunding a lapsed subscription
+    /// - Moving the prepayment into the auto-care account
+    fn subscribe_auto_care() -> Weight;
+
+    /// Weight for execute_due_care extrinsic.
+    /// This operation involves:
+    /// - Performing the subscribed care
+    /// - Paying the bounty and burning the rest of the fee
+    fn execute_due_care() -> Weight;
+
+    /// Weight for cancel_auto_care extrinsic.
+    /// This operation involves:
+    /// - Refunding the remaining balance
+    /// - Removing the subscription
+    fn cancel_auto_care() -> Weight;
+
+    /// Weight for propose_trade extrinsic.
+    /// This operation involves:
+    /// - Checking both pets can move
+    /// - Locking the offered pet and recording the trade
+    fn propose_trade() -> Weight;
+
+    /// Weight for accept_trade extrinsic.
+    /// This operation involves:
+    /// - Checking the trade is intact and both sides have room
+    /// - Unlocking and swapping both pets
+    fn accept_trade() -> Weight;
+
+    /// Weight for cancel_trade extrinsic.
+    /// This operation involves:
+    /// - Removing the trade
+    /// - Unlocking the offered pet
+    fn cancel_trade() -> Weight;
+
+    /// Weight for set_game_parameters extrinsic.
+    /// This operation involves:
+    /// - Validating the parameters
+    /// - Storing them
+    fn set_game_parameters() -> Weight;
+
+    /// Weight for force_unlock extrinsic.
+    /// This operation involves:
+    /// - Settling the pet's rested XP
+    /// - Removing the lock
+    fn force_unlock() -> Weight;
+
+    /// Weight for claim_pending_pet extrinsic.
+    /// This operation involves:
+    /// - Capacity verification
+    /// - Minting the parked pet
+    /// - Recording its lineage
+    fn claim_pending_pet() -> Weight;
+
+    /// Weight for clear_expired_pending_pet extrinsic.
+    /// This operation involves:
+    /// - Expiry verification
+    /// - Removing the parked pet
+    fn clear_expired_pending_pet() -> Weight;
+
+    /// Weight for set_trait_inheritance_chance extrinsic.
+    /// This operation involves:
+    /// - Storing the chance
+    fn set_trait_inheritance_chance() -> Weight;
+
+    /// Weight for acknowledge_sync extrinsic with `h` hooks registered.
+    /// This operation involves:
+    /// - Collecting the caller's interests from `h` registered hooks
+    /// - Clearing the pet's sync flags
+    fn acknowledge_sync(h: u32) -> Weight;
+
+    /// Weight for set_input_catalog_entry extrinsic.
+    /// This operation involves:
+    /// - Storing the gesture or touch area
+    fn set_input_catalog_entry() -> Weight;
+
+    /// Weight for remove_input_catalog_entry extrinsic.
+    /// This operation involves:
+    /// - Removing the gesture or touch area
+    fn remove_input_catalog_entry() -> Weight;
+
+    /// Weight for set_species_care_preferences extrinsic.
+    /// This operation involves:
+    /// - Checking for duplicate multipliers
+    /// - Storing the preferences
+    fn set_species_care_preferences() -> Weight;
+
+    /// Weight for sacrifice_pets extrinsic with `n` pets.
+    /// This operation involves:
+    /// - Validating and valuing each sacrificed pet
+    /// - Granting the bonus to the target
+    /// - Releasing each sacrificed pet with all its state
+    fn sacrifice_pets(n: u32) -> Weight;
+
+    /// Weight for release_pet extrinsic.
+    /// This operation involves:
+    /// - Ownership verification
+    /// - Asking each release blocker
+    /// - Removing the pet with all its state
+    fn release_pet() -> Weight;
+
+    /// Weight for force_release extrinsic, before the other pallets' release blockers drop their references.
+    /// This operation involves:
+    /// - Dropping the lock, auto-care subscription and deferred reward
+    /// - Removing the pet with all its state
+    fn force_release() -> Weight;
+
+    /// Weight for burn_pet extrinsic.
+    /// This operation involves:
+    /// - Ownership verification
+    /// - Asking each release blocker
+    /// - Paying the compensation from the lifecycle reward pot
+    /// - Removing the pet with all its state and recording it in the cemetery
+    fn burn_pet() -> Weight;
+
+    /// Weight for marketplace_transfer_pet extrinsic.
+    /// This operation involves:
+    /// - Lock and transfer gate checks
+    /// - Updating ownership records
+    fn marketplace_transfer_pet() -> Weight;
+
+    /// Weight for initiate_account_migration extrinsic.
+    /// This operation involves:
+    /// - Checking no migration is in progress
+    /// - Storing the request
+    fn initiate_account_migration() -> Weight;
+
+    /// Weight for pet_basic_care extrinsic.
+    /// This operation involves:
+    /// - Caretaker verification, against the owner embedded in the pet record
+    /// - Counting the free use
+    /// - Updating pet attributes at the reduced effect
+    fn pet_basic_care() -> Weight;
+
+    /// Weight for set_donation_roundup extrinsic.
+    /// This operation involves:
+    /// - Checking the beneficiary exists
+    /// - Storing the preference
+    fn set_donation_roundup() -> Weight;
+
+    /// Weight for set_community_pot extrinsic.
+    /// This operation involves:
+    /// - Storing or removing the pot
+    fn set_community_pot() -> Weight;
+
+    /// Weight for remove_personality_trait extrinsic.
+    /// This operation involves:
+    /// - Ownership and version verification
+    /// - Removing the owner-sourced trait
+    /// - Bumping the state version and notifying sync hooks
+    fn remove_personality_trait() -> Weight;
+
+    /// Weight for delegate_pet extrinsic.
+    /// This operation involves:
+    /// - Ownership verification, against the owner embedded in the pet record
+    /// - Storing the delegation
+    fn delegate_pet() -> Weight;
+
+    /// Weight for revoke_delegation extrinsic.
+    /// This operation involves:
+    /// - Ownership verification, against the owner embedded in the pet record
+    /// - Removing the delegation
+    fn revoke_delegation() -> Weight;
+
+    /// Weight for batch_transfer_pet_nfts extrinsic with `n` pets.
+    /// This operation involves:
+    /// - Checking every pet and the recipient's room before anything moves
+    /// - Everything `transfer_pet_nft` does, once per pet
+    fn batch_transfer_pet_nfts(n: u32) -> Weight;
 }
 
 /// Default implementation for WeightInfo based on benchmarking results
 pub struct SubstrateWeights<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeights<T> {
-    /// Benchmarking results for mint_pet_nft:
-    /// - Reference time: ~15ms
-    /// - Proof size: ~2KB (4 DB writes + 1 DB read)
+    /// Storage: NextPetId, MintsThisBlock, RecentDna, SpeciesPopulation, SyncHookRegistry (r:6 w:0)
+    /// Storage: OwnerOfPet (r:1 w:1)
+    /// Storage: PetNfts, PetNftOwner, PetStateVersions, PetMinters (r:0 w:4)
     fn mint_pet_nft() -> Weight {
-        Weight::from_parts(
-            15_000_000, // 15ms reference time
-            2048, // ~2KB proof size
-        )
-        .saturating_add(T::DbWeight::get().reads(1))
-        .saturating_add(T::DbWeight::get().writes(4))
+        Weight::from_parts(15_000_000, 2048)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(9))
     }
 
-    /// Benchmarking results for transfer_pet_nft:
-    /// - Reference time: ~12ms
-    /// - Proof size: ~1.5KB (2 DB reads + 2 DB writes)
+    /// Storage: PetNfts, LockedNfts, OwnerOfPet (owner and recipient), PetApprovals, OperatorApprovals (r:6 w:0)
+    /// Storage: PetNfts, PetNftOwner, OwnerOfPet (owner and recipient), PetDelegations, PetApprovals (r:0 w:6)
     fn transfer_pet_nft() -> Weight {
-        Weight::from_parts(
-            12_000_000, // 12ms reference time
-            1536, // ~1.5KB proof size
-        )
-        .saturating_add(T::DbWeight::get().reads(2))
-        .saturating_add(T::DbWeight::get().writes(2))
+        Weight::from_parts(12_000_000, 1536)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+
+    /// Storage: PetNftOwner, PetNfts, PetStateVersions, SystemTraits (r:4 w:0)
+    /// Storage: PetNfts, PetStateVersions, PetDisplayVersions, SystemTraits (r:0 w:4)
+    fn update_pet_metadata() -> Weight {
+        Weight::from_parts(10_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Storage: LastClaimTime, the claimer's account (r:2 w:2)
+    fn claim_daily_ptcn() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: PetNfts (with owner), PetDelegations, the item, RateLimits, SpeciesCarePreferences,
+    /// PetEnvironmentalAdaptations (r:6 w:0)
+    /// Storage: PetNfts, RateLimits (r:0 w:2)
+    fn feed_pet() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: as feed_pet (r:6 w:2)
+    fn play_with_pet() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: PetNfts (r:1 w:1)
+    fn apply_neglect_check() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: LastNeglectBatchBlock, GameParameters (r:2 w:1)
+    /// Per pet, storage: PetNfts (r:1 w:1)
+    /// The range of component `n` is `[1, MaxNeglectBatchPerBlock]`.
+    fn apply_neglect_check_unsigned(n: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(Weight::from_parts(8_000_000, 512).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().reads(n as u64))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes(n as u64))
+    }
+
+    /// Storage: OwnerOfPet (r:1 w:1)
+    /// Per pet, storage: NextPetId, MintsThisBlock, RecentDna, SpeciesPopulation, SyncHookRegistry
+    /// (r:5 w:0) and NextPetId, MintsThisBlock, RecentDna, SpeciesPopulation, PetNfts, PetNftOwner,
+    /// PetStateVersions, PetMinters (r:0 w:8)
+    /// The range of component `n` is `[1, MaxOwnedPets]`.
+    fn batch_mint_pet_nfts(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(12_000_000, 2048).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((8 as u64).saturating_mul(n as u64)))
+    }
+
+    /// Storage: ThrottledNotifications (r:1 w:1)
+    /// Storage: NotificationsThisBlock (r:0 w:1)
+    fn on_initialize() -> Weight {
+        Weight::from_parts(2_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: PetNfts (with owner), LockedNfts (r:2 w:0)
+    /// Storage: PetApprovals (r:0 w:1)
+    fn approve_transfer() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNfts (with owner), PetApprovals (r:2 w:1)
+    fn revoke_approval() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: OperatorApprovals (r:0 w:1)
+    fn set_operator() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNftOwner, PetStateVersions, PetNfts (r:3 w:0)
+    /// Storage: PetBios, PetNfts, PetStateVersions, PetDisplayVersions (r:0 w:4)
+    fn set_pet_bio() -> Weight {
+        Weight::from_parts(12_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Storage: PetNftOwner, PetStateVersions, PetBios, PetNfts (r:4 w:0)
+    /// Storage: PetBios, PetNfts, PetStateVersions, PetDisplayVersions (r:0 w:4)
+    fn clear_pet_bio() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Storage: PetNfts, PetBios, PetStateVersions (r:3 w:0)
+    /// Storage: PetBios, PetNfts, PetStateVersions, PetDisplayVersions (r:0 w:4)
+    fn force_clear_pet_bio() -> Weight {
+        Weight::from_parts(9_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Storage: NotificationDigestSettings, PendingDigest (every type), NextNotificationId (r:10 w:0)
+    /// Storage: NotificationDigestSettings, PendingDigest (every type), NextNotificationId, UserNotifications, NotificationsThisBlock (r:0 w:12)
+    fn set_notification_digest() -> Weight {
+        Weight::from_parts(25_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(12))
+    }
+
+    /// Storage: NotificationDigestSettings, PendingDigest (every type), NextNotificationId (r:10 w:0)
+    /// Storage: NotificationDigestSettings, PendingDigest (every type), NextNotificationId, UserNotifications, NotificationsThisBlock (r:0 w:12)
+    fn flush_digest() -> Weight {
+        Weight::from_parts(25_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(12))
+    }
+
+    /// Storage: PetNftOwner, DeferredLifecycleRewards, the pot (r:3 w:0)
+    /// Storage: DeferredLifecycleRewards, the pot, the owner's account (r:0 w:3)
+    fn claim_deferred_lifecycle_reward() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Storage: SocialInteractionTypes (r:0 w:1)
+    fn set_social_interaction_type() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: OnboardingStarterConfig (r:0 w:1)
+    fn set_onboarding_starter_config() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: SocialInteractionTypes (r:1 w:1)
+    fn remove_social_interaction_type() -> Weight {
+        Weight::from_parts(7_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNftOwner, CareSubscriptions, the owner's account, the auto-care account, DonationPreferences (r:5 w:0)
+    /// Storage: CareSubscriptions, the owner's account, the auto-care account (r:0 w:3)
+    fn subscribe_auto_care() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Storage: CareSubscriptions, PetNftOwner, PetNfts, the auto-care account, the executor's account (r:5 w:0)
+    /// Storage: PetNfts, CareSubscriptions, the auto-care account, the executor's account (r:0 w:4)
+    fn execute_due_care() -> Weight {
+        Weight::from_parts(25_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// Storage: CareSubscriptions, the auto-care account, the owner's account (r:3 w:3)
+    fn cancel_auto_care() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Storage: PetNfts and LockedNfts of both pets, NextTradeId, the transfer gate inputs (r:6 w:0)
+    /// Storage: LockedNfts, PendingTrades, NextTradeId (r:0 w:3)
+    fn propose_trade() -> Weight {
+        Weight::from_parts(18_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Storage: PendingTrades, PetNftOwner and LockedNfts of both pets, OwnerOfPet of both accounts, PetNfts of both pets (r:9 w:0)
+    /// Storage: PendingTrades, LockedNfts, PetNfts and PetNftOwner of both pets, OwnerOfPet of both accounts (r:0 w:7)
+    fn accept_trade() -> Weight {
+        Weight::from_parts(35_000_000, 3072)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+
+    /// Storage: PendingTrades, LockedNfts (r:2 w:2)
+    fn cancel_trade() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: GameParameters (r:1 w:1)
+    fn set_game_parameters() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
     }
 
-    /// Benchmarking results for update_pet_metadata:
-    /// - Reference time: ~10ms
-    /// - Proof size: ~1KB (2 DB reads + 1 DB write)
+    /// Storage: LockedNfts, PetNfts (r:2 w:2)
+    fn force_unlock() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: PendingClaims, PendingClaimsOf, OwnerOfPet, SpeciesPopulation (r:4 w:0)
+    /// Storage: PendingClaims, PendingClaimsOf, SpeciesPopulation, PetNfts, OwnerOfPet, PetNftOwner, PetParents, PetGenealogy, PetMinters (r:0 w:9)
+    fn claim_pending_pet() -> Weight {
+        Weight::from_parts(22_000_000, 2048)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(9))
+    }
+
+    /// Storage: PendingClaims, PendingClaimsOf (r:2 w:2)
+    fn clear_expired_pending_pet() -> Weight {
+        Weight::from_parts(10_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: InheritanceTable (r:0 w:1)
+    fn set_trait_inheritance_chance() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNfts (r:1 w:1)
+    /// Per hook, storage: SyncHookRegistry (r:1 w:0)
+    /// The range of component `h` is `[1, MaxRegisteredHooks]`.
+    fn acknowledge_sync(h: u32) -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(Weight::from_parts(2_000_000, 256).saturating_mul(h as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(h as u64)))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: GestureCatalog or TouchAreaCatalog (r:0 w:1)
+    fn set_input_catalog_entry() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: GestureCatalog or TouchAreaCatalog (r:1 w:1)
+    fn remove_input_catalog_entry() -> Weight {
+        Weight::from_parts(7_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: SpeciesCarePreferences (r:0 w:1)
+    fn set_species_care_preferences() -> Weight {
+        Weight::from_parts(7_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNftOwner, SacrificeBonus, PetNfts (r:3 w:3)
+    /// Per pet, storage: PetNfts, LockedNfts, DeferredLifecycleRewards, the quality score inputs, CareSubscriptions (r:6 w:0) and OwnerOfPet, SpeciesPopulation and the pet's per-pet state (r:0 w:32)
+    /// The range of component `n` is `[1, MaxSacrificesPerCall]`.
+    fn sacrifice_pets(n: u32) -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(Weight::from_parts(20_000_000, 2048).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes((32 as u64).saturating_mul(n as u64)))
+    }
+
+    /// Storage: PetNftOwner, LockedNfts, CareSubscriptions, DeferredLifecycleRewards, PetNfts, PetStateVersions (r:6 w:0)
+    /// Storage: OwnerOfPet, SpeciesPopulation and the pet's per-pet state (r:0 w:32)
+    fn release_pet() -> Weight {
+        Weight::from_parts(20_000_000, 2048)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(32))
+    }
+
+    /// Storage: PetNfts, CareSubscriptions, PetStateVersions (r:3 w:0)
+    /// Storage: LockedNfts, CareSubscriptions, DeferredLifecycleRewards, OwnerOfPet, SpeciesPopulation and the pet's per-pet state (r:0 w:35)
+    fn force_release() -> Weight {
+        Weight::from_parts(20_000_000, 2048)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(35))
+    }
+
+    /// Storage: PetNfts, LockedNfts, CareSubscriptions, DeferredLifecycleRewards, System Account (pot), PetNftOwner, PetStateVersions (r:7 w:0)
+    /// Storage: System Account (pot and owner), PetCemetery, OwnerOfPet, SpeciesPopulation and the pet's per-pet state (r:0 w:35)
+    fn burn_pet() -> Weight {
+        Weight::from_parts(25_000_000, 4096)
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(35))
+    }
+
+    /// Storage: PetNfts, LockedNfts, OwnerOfPet (owner and recipient), the transfer gate inputs (r:6 w:0)
+    /// Storage: PetNfts, PetNftOwner, OwnerOfPet (owner and recipient), PetDelegations, PetApprovals (r:0 w:6)
+    fn marketplace_transfer_pet() -> Weight {
+        Weight::from_parts(12_000_000, 1536)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+
+    /// Storage: AccountMigrations (r:1 w:0)
+    /// Storage: AccountMigrationRequests (r:0 w:1)
+    fn initiate_account_migration() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNfts (with owner), PetDelegations, RateLimits, FreeCareUses, SpeciesCarePreferences, PetEnvironmentalAdaptations, CareActionBuckets (r:7 w:0)
+    /// Storage: PetNfts, RateLimits, FreeCareUses (r:0 w:3)
+    fn pet_basic_care() -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    /// Storage: CommunityPots (r:1 w:0)
+    /// Storage: DonationPreferences (r:0 w:1)
+    fn set_donation_roundup() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: CommunityPots (r:0 w:1)
+    fn set_community_pot() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNftOwner, PetStateVersions, PetNfts, SystemTraits (r:4 w:0)
+    /// Storage: PetNfts, PetStateVersions (r:0 w:2)
+    fn remove_personality_trait() -> Weight {
+        Weight::from_parts(12_000_000, 1024)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// Storage: PetNfts (with owner) (r:1 w:0)
+    /// Storage: PetDelegations (r:0 w:1)
+    fn delegate_pet() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: PetNfts (with owner), PetDelegations (r:2 w:1)
+    fn revoke_delegation() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Storage: OwnerOfPet of both accounts (r:2 w:2)
+    /// Per pet, storage: PetNfts, LockedNfts, PetNftOwner, the transfer gate inputs (r:4 w:0) and PetNfts, PetNftOwner, PetDelegations, PetApprovals (r:0 w:4)
+    /// The range of component `n` is `[1, MaxBatchTransferSize]`.
+    fn batch_transfer_pet_nfts(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(10_000_000, 1536).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(n as u64)))
+            .saturating_add(T::DbWeight::get().writes(2))
+            .saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn mint_pet_nft() -> Weight {
+        Weight::from_parts(15_000_000, 2048)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(9))
+    }
+    fn transfer_pet_nft() -> Weight {
+        Weight::from_parts(12_000_000, 1536)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(6))
+    }
     fn update_pet_metadata() -> Weight {
-        Weight::from_parts(10_000, 0)
+        Weight::from_parts(10_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(4))
     }
     fn claim_daily_ptcn() -> Weight {
-        Weight::from_parts(10_000, 0)
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
     }
     fn feed_pet() -> Weight {
-        Weight::from_parts(10_000, 0)
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(2))
     }
     fn play_with_pet() -> Weight {
-        Weight::from_parts(10_000, 0)
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(2))
     }
     fn apply_neglect_check() -> Weight {
-        Weight::from_parts(10_000, 0)
-    }
\ No newline at end of file
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn apply_neglect_check_unsigned(n: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(Weight::from_parts(8_000_000, 512).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().reads(n as u64))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes(n as u64))
+    }
+    fn batch_mint_pet_nfts(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(12_000_000, 2048).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((8 as u64).saturating_mul(n as u64)))
+    }
+    fn on_initialize() -> Weight {
+        Weight::from_parts(2_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn approve_transfer() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn revoke_approval() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_operator() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_pet_bio() -> Weight {
+        Weight::from_parts(12_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn clear_pet_bio() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn force_clear_pet_bio() -> Weight {
+        Weight::from_parts(9_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn set_notification_digest() -> Weight {
+        Weight::from_parts(25_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(10))
+            .saturating_add(RocksDbWeight::get().writes(12))
+    }
+    fn flush_digest() -> Weight {
+        Weight::from_parts(25_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(10))
+            .saturating_add(RocksDbWeight::get().writes(12))
+    }
+    fn claim_deferred_lifecycle_reward() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn set_social_interaction_type() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_onboarding_starter_config() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn remove_social_interaction_type() -> Weight {
+        Weight::from_parts(7_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn subscribe_auto_care() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn execute_due_care() -> Weight {
+        Weight::from_parts(25_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn cancel_auto_care() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn propose_trade() -> Weight {
+        Weight::from_parts(18_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn accept_trade() -> Weight {
+        Weight::from_parts(35_000_000, 3072)
+            .saturating_add(RocksDbWeight::get().reads(9))
+            .saturating_add(RocksDbWeight::get().writes(7))
+    }
+    fn cancel_trade() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn set_game_parameters() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn force_unlock() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn claim_pending_pet() -> Weight {
+        Weight::from_parts(22_000_000, 2048)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(9))
+    }
+    fn clear_expired_pending_pet() -> Weight {
+        Weight::from_parts(10_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn set_trait_inheritance_chance() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn acknowledge_sync(h: u32) -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(Weight::from_parts(2_000_000, 256).saturating_mul(h as u64))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(h as u64)))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_input_catalog_entry() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn remove_input_catalog_entry() -> Weight {
+        Weight::from_parts(7_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_species_care_preferences() -> Weight {
+        Weight::from_parts(7_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn sacrifice_pets(n: u32) -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(Weight::from_parts(20_000_000, 2048).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().writes((32 as u64).saturating_mul(n as u64)))
+    }
+    fn release_pet() -> Weight {
+        Weight::from_parts(20_000_000, 2048)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(32))
+    }
+    fn force_release() -> Weight {
+        Weight::from_parts(20_000_000, 2048)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(35))
+    }
+    fn burn_pet() -> Weight {
+        Weight::from_parts(25_000_000, 4096)
+            .saturating_add(RocksDbWeight::get().reads(7))
+            .saturating_add(RocksDbWeight::get().writes(35))
+    }
+    fn marketplace_transfer_pet() -> Weight {
+        Weight::from_parts(12_000_000, 1536)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(6))
+    }
+    fn initiate_account_migration() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn pet_basic_care() -> Weight {
+        Weight::from_parts(12_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(7))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn set_donation_roundup() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_community_pot() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn remove_personality_trait() -> Weight {
+        Weight::from_parts(12_000_000, 1024)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn delegate_pet() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn revoke_delegation() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn batch_transfer_pet_nfts(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(10_000_000, 1536).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(n as u64)))
+            .saturating_add(RocksDbWeight::get().writes(2))
+            .saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
+    }
+}
@@ -5,12 +5,42 @@
 //!
 //! Meticulously crafted to align with The Architect's vision for
 //! modularity, scalability, and robust operation of the CritterCraft digital ecosystem.
+//!
+//! ## Mood
+//!
+//! A pet's mood is not stored: `get_pet_mood` derives it from the pet's needs with
+//! `compute_mood`, so it can never go stale. Every needs write goes through `update_needs`,
+//! which emits `PetMoodChanged` when the write moves the pet into another mood.
+//!
+//! ## Conditions
+//!
+//! `AdminOrigin` defines conditions and sets pets' stored stats. `ConditionOrigin` applies
+//! conditions, and other pallets apply them through `inflict_condition`. A condition's need
+//! modifiers apply once, when it is applied. Its stat modifiers are never written to the stored
+//! stats: `effective_stats` adds those of the pet's active conditions on read. A condition ends
+//! when `on_initialize` expires it, or when the owner cures it with `cure_condition` through the
+//! `CureHandler` hook.
 
 #![cfg_attr(not(feature = "std"), no_std)] // No standard library for Wasm compilation
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+// Storage migrations
+pub mod migrations;
+
+/// Runs when an owner cures their pet of a condition with `cure_condition`, for example to burn
+/// the medicine item the cure takes. The cure fails if this does.
+pub trait ConditionCureHandler<AccountId> {
+    fn on_cure(who: &AccountId, pet_id: PetId, condition_id: ConditionId) -> frame_support::dispatch::DispatchResult;
+}
+
+impl<AccountId> ConditionCureHandler<AccountId> for () {
+    fn on_cure(_who: &AccountId, _pet_id: PetId, _condition_id: ConditionId) -> frame_support::dispatch::DispatchResult {
+        Ok(())
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
@@ -26,7 +56,7 @@ pub mod pallet {
     use sp_std::vec::Vec; // Standard Vec for dynamic arrays (used where not bounded)
     use scale_info::TypeInfo; // For `TypeInfo` derive macro
     use frame_support::log; // Correct way to import Substrate's logging macro
-    use sp_runtime::traits::StaticLookup; // For AccountIdLookup
+    use sp_runtime::traits::{StaticLookup, UniqueSaturatedInto, Zero}; // For AccountIdLookup and block arithmetic
 
     // --- Type Aliases ---
     pub type PetId = u32; // Unique identifier for each pet
@@ -82,13 +112,13 @@ pub mod pallet {
     }
 
     // --- Struct Definitions ---
-    // PetStatus: Defines the current status of a pet
+    // PetStatus: Defines the current status of a pet. Its mood is not stored but derived from its
+    // needs, see `Pallet::get_pet_mood`.
     #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
     pub struct PetStatus<T: Config> {
         pub pet_id: PetId,
         pub owner: T::AccountId,
-        pub mood: PetMood,
         pub last_interaction: BlockNumberFor<T>,
         pub last_fed: BlockNumberFor<T>,
         pub last_rested: BlockNumberFor<T>,
@@ -176,6 +206,10 @@ pub mod pallet {
         #[pallet::constant]
         type NeedDecayAmount: Get<StatValue>;
         
+        /// Maximum number of pets whose needs are checked for decay in one block.
+        #[pallet::constant]
+        type MaxNeedDecaysPerBlock: Get<u32>;
+        
         /// Blocks before a pet becomes hungry.
         #[pallet::constant]
         type HungerInterval: Get<Self::BlockNumber>;
@@ -195,11 +229,32 @@ pub mod pallet {
         /// Blocks before a pet becomes lonely.
         #[pallet::constant]
         type LonelinessInterval: Get<Self::BlockNumber>;
+        
+        /// Blocks a pet stays immune to a condition after recovering from it.
+        #[pallet::constant]
+        type ImmunityDurationBlocks: Get<Self::BlockNumber>;
+        
+        /// The origin that creates and removes conditions and sets pets' stats.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        
+        /// The origin that applies conditions to pets.
+        type ConditionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        
+        /// Runs when an owner cures their pet's condition; `()` makes cures free.
+        type CureHandler: crate::ConditionCureHandler<Self::AccountId>;
     }
 
+    /// The most decay intervals applied to a pet at once. Needs that went unchecked for longer
+    /// decay this much and no more.
+    pub const MAX_CATCH_UP_DECAYS: u32 = 10;
+
+    /// The in-code storage version. Bump this together with a new `OnRuntimeUpgrade` migration.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     // --- Pallet Definition ---
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // --- Pallet Storage Items ---
@@ -244,6 +299,32 @@ pub mod pallet {
     /// Stores the last block number when needs were decayed.
     pub(super) type LastNeedDecay<T: Config> = StorageMap<_, Blake2_128Concat, PetId, BlockNumberFor<T>>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn need_decay_cursor)]
+    /// Stores the last pet whose needs were checked for decay, if the walk over all pets'
+    /// needs stopped partway. The next block's walk resumes after it.
+    pub(super) type NeedDecayCursor<T: Config> = StorageValue<_, PetId, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn immunity_cleanup_cursor)]
+    /// Stores the last immunity record checked for expiry, if the sweep over all records
+    /// stopped partway. The next idle block's sweep resumes after it.
+    pub(super) type ImmunityCleanupCursor<T: Config> = StorageValue<_, (PetId, ConditionId), OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn condition_immunity)]
+    /// Stores the block until which each pet is immune to each condition it recovered from.
+    /// Expired records are removed in `on_idle`.
+    pub(super) type ConditionImmunity<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PetId,
+        Blake2_128Concat,
+        ConditionId,
+        BlockNumberFor<T>,
+        OptionQuery
+    >;
+
     // --- Pallet Events ---
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -251,16 +332,18 @@ pub mod pallet {
         /// A pet's status has been initialized. [pet_id, owner]
         PetStatusInitialized { pet_id: PetId, owner: T::AccountId },
         
-        /// A pet's mood has changed. [pet_id, mood]
+        /// A change to a pet's needs moved its derived mood. [pet_id, mood]
         PetMoodChanged { pet_id: PetId, mood: PetMood },
         
         /// A pet has been fed. [pet_id, hunger_restored]
+        /// Reports the needs change only; the care action itself is pallet-critter-nfts' `CareActionPerformed`.
         PetFed { pet_id: PetId, hunger_restored: StatValue },
         
         /// A pet has rested. [pet_id, energy_restored]
         PetRested { pet_id: PetId, energy_restored: StatValue },
         
         /// A pet has played. [pet_id, happiness_increased]
+        /// Reports the needs change only; the care action itself is pallet-critter-nfts' `CareActionPerformed`.
         PetPlayed { pet_id: PetId, happiness_increased: StatValue },
         
         /// A pet has been groomed. [pet_id, hygiene_increased]
@@ -275,14 +358,23 @@ pub mod pallet {
         /// A pet's needs have changed. [pet_id, need_type, old_value, new_value]
         PetNeedChanged { pet_id: PetId, need_type: NeedType, old_value: StatValue, new_value: StatValue },
         
+        /// A condition has been created. [condition_id, name]
+        ConditionCreated { condition_id: ConditionId, name: Vec<u8> },
+        
         /// A pet has developed a condition. [pet_id, condition_id, name]
         PetDevelopedCondition { pet_id: PetId, condition_id: ConditionId, name: Vec<u8> },
         
         /// A pet has recovered from a condition. [pet_id, condition_id, name]
         PetRecoveredFromCondition { pet_id: PetId, condition_id: ConditionId, name: Vec<u8> },
         
+        /// An owner cured their pet of a condition. [pet_id, condition_id, by]
+        PetConditionCured { pet_id: PetId, condition_id: ConditionId, by: T::AccountId },
+        
         /// A pet's needs have decayed. [pet_id]
         PetNeedsDecayed { pet_id: PetId },
+        
+        /// A pet that recovered from a condition is immune to it until a block. [pet_id, condition_id, expires_at]
+        ConditionImmunityGranted { pet_id: PetId, condition_id: ConditionId, expires_at: BlockNumberFor<T> },
     }
 
     // --- Pallet Errors ---
@@ -344,15 +436,22 @@ pub mod pallet {
         
         /// The pet socialized too recently.
         PetSocializedTooRecently,
+        
+        /// The pet recovered from this condition too recently to develop it again.
+        ConditionImmunityActive,
     }
 
     // --- Pallet Hooks ---
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(n: BlockNumberFor<T>) -> Weight {
-            // Process need decay and condition updates
-            Self::process_pet_updates(n);
-            Weight::zero()
+            // Decay the next batch of needs, then expire conditions
+            Self::decay_needs(n).saturating_add(Self::expire_conditions(n))
+        }
+        
+        fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // Remove expired immunity records with the block's spare weight
+            Self::clean_up_immunities(n, remaining_weight)
         }
     }
 
@@ -376,7 +475,6 @@ pub mod pallet {
             let pet_status = PetStatus::<T> {
                 pet_id,
                 owner: owner.clone(),
-                mood: PetMood::Happy,
                 last_interaction: current_block,
                 last_fed: current_block,
                 last_rested: current_block,
@@ -448,14 +546,11 @@ pub mod pallet {
             pet_status.last_fed = current_block;
             pet_status.last_interaction = current_block;
             
-            // 6. Update the pet's mood based on needs.
-            Self::update_pet_mood(&mut pet_status, &pet_needs);
-            
-            // 7. Store the updated pet status and needs.
-            PetStatuses::<T>::insert(pet_id, pet_status.clone());
-            PetNeedsStorage::<T>::insert(pet_id, pet_needs.clone());
+            // 6. Store the updated pet status and needs.
+            PetStatuses::<T>::insert(pet_id, pet_status);
+            Self::update_needs(pet_id, pet_needs.clone());
             
-            // 8. Emit the events.
+            // 7. Emit the events.
             Self::deposit_event(Event::PetFed {
                 pet_id,
                 hunger_restored,
@@ -468,11 +563,6 @@ pub mod pallet {
                 new_value: pet_needs.hunger,
             });
             
-            Self::deposit_event(Event::PetMoodChanged {
-                pet_id,
-                mood: pet_status.mood,
-            });
-            
             Ok(())
         }
 
@@ -506,14 +596,11 @@ pub mod pallet {
             pet_status.last_rested = current_block;
             pet_status.last_interaction = current_block;
             
-            // 6. Update the pet's mood based on needs.
-            Self::update_pet_mood(&mut pet_status, &pet_needs);
-            
-            // 7. Store the updated pet status and needs.
-            PetStatuses::<T>::insert(pet_id, pet_status.clone());
-            PetNeedsStorage::<T>::insert(pet_id, pet_needs.clone());
+            // 6. Store the updated pet status and needs.
+            PetStatuses::<T>::insert(pet_id, pet_status);
+            Self::update_needs(pet_id, pet_needs.clone());
             
-            // 8. Emit the events.
+            // 7. Emit the events.
             Self::deposit_event(Event::PetRested {
                 pet_id,
                 energy_restored,
@@ -526,11 +613,6 @@ pub mod pallet {
                 new_value: pet_needs.energy,
             });
             
-            Self::deposit_event(Event::PetMoodChanged {
-                pet_id,
-                mood: pet_status.mood,
-            });
-            
             Ok(())
         }
 
@@ -568,14 +650,11 @@ pub mod pallet {
             pet_status.last_played = current_block;
             pet_status.last_interaction = current_block;
             
-            // 7. Update the pet's mood based on needs.
-            Self::update_pet_mood(&mut pet_status, &pet_needs);
-            
-            // 8. Store the updated pet status and needs.
-            PetStatuses::<T>::insert(pet_id, pet_status.clone());
-            PetNeedsStorage::<T>::insert(pet_id, pet_needs.clone());
+            // 7. Store the updated pet status and needs.
+            PetStatuses::<T>::insert(pet_id, pet_status);
+            Self::update_needs(pet_id, pet_needs.clone());
             
-            // 9. Emit the events.
+            // 8. Emit the events.
             Self::deposit_event(Event::PetPlayed {
                 pet_id,
                 happiness_increased,
@@ -595,11 +674,6 @@ pub mod pallet {
                 new_value: pet_needs.energy,
             });
             
-            Self::deposit_event(Event::PetMoodChanged {
-                pet_id,
-                mood: pet_status.mood,
-            });
-            
             Ok(())
         }
 
@@ -633,14 +707,11 @@ pub mod pallet {
             pet_status.last_groomed = current_block;
             pet_status.last_interaction = current_block;
             
-            // 6. Update the pet's mood based on needs.
-            Self::update_pet_mood(&mut pet_status, &pet_needs);
-            
-            // 7. Store the updated pet status and needs.
-            PetStatuses::<T>::insert(pet_id, pet_status.clone());
-            PetNeedsStorage::<T>::insert(pet_id, pet_needs.clone());
+            // 6. Store the updated pet status and needs.
+            PetStatuses::<T>::insert(pet_id, pet_status);
+            Self::update_needs(pet_id, pet_needs.clone());
             
-            // 8. Emit the events.
+            // 7. Emit the events.
             Self::deposit_event(Event::PetGroomed {
                 pet_id,
                 hygiene_increased,
@@ -653,11 +724,6 @@ pub mod pallet {
                 new_value: pet_needs.hygiene,
             });
             
-            Self::deposit_event(Event::PetMoodChanged {
-                pet_id,
-                mood: pet_status.mood,
-            });
-            
             Ok(())
         }
 
@@ -695,14 +761,11 @@ pub mod pallet {
             pet_status.last_socialized = current_block;
             pet_status.last_interaction = current_block;
             
-            // 7. Update the pet's mood based on needs.
-            Self::update_pet_mood(&mut pet_status, &pet_needs);
-            
-            // 8. Store the updated pet status and needs.
-            PetStatuses::<T>::insert(pet_id, pet_status.clone());
-            PetNeedsStorage::<T>::insert(pet_id, pet_needs.clone());
+            // 7. Store the updated pet status and needs.
+            PetStatuses::<T>::insert(pet_id, pet_status);
+            Self::update_needs(pet_id, pet_needs.clone());
             
-            // 9. Also update the target pet's social need.
+            // 8. Also update the target pet's social need.
             if let Some(mut target_pet_status) = PetStatuses::<T>::get(target_pet_id) {
                 if let Some(mut target_pet_needs) = PetNeedsStorage::<T>::get(target_pet_id) {
                     let old_target_social = target_pet_needs.social;
@@ -711,10 +774,8 @@ pub mod pallet {
                     target_pet_status.last_socialized = current_block;
                     target_pet_status.last_interaction = current_block;
                     
-                    Self::update_pet_mood(&mut target_pet_status, &target_pet_needs);
-                    
-                    PetStatuses::<T>::insert(target_pet_id, target_pet_status.clone());
-                    PetNeedsStorage::<T>::insert(target_pet_id, target_pet_needs.clone());
+                    PetStatuses::<T>::insert(target_pet_id, target_pet_status);
+                    Self::update_needs(target_pet_id, target_pet_needs.clone());
                     
                     Self::deposit_event(Event::PetNeedChanged {
                         pet_id: target_pet_id,
@@ -722,15 +783,10 @@ pub mod pallet {
                         old_value: old_target_social,
                         new_value: target_pet_needs.social,
                     });
-                    
-                    Self::deposit_event(Event::PetMoodChanged {
-                        pet_id: target_pet_id,
-                        mood: target_pet_status.mood,
-                    });
                 }
             }
             
-            // 10. Emit the events.
+            // 9. Emit the events.
             Self::deposit_event(Event::PetSocialized {
                 pet_id,
                 social_increased,
@@ -743,11 +799,6 @@ pub mod pallet {
                 new_value: pet_needs.social,
             });
             
-            Self::deposit_event(Event::PetMoodChanged {
-                pet_id,
-                mood: pet_status.mood,
-            });
-            
             Ok(())
         }
 
@@ -764,7 +815,7 @@ pub mod pallet {
             stat_modifiers: Vec<(StatType, i8)>,
             need_modifiers: Vec<(NeedType, i8)>,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            T::AdminOrigin::ensure_origin(origin)?;
             
             // 1. Get the next condition ID.
             let condition_id = Self::next_condition_id();
@@ -786,10 +837,17 @@ pub mod pallet {
             // 3. Store the condition.
             Conditions::<T>::insert(condition_id, condition);
             
+            // 4. Emit the event.
+            Self::deposit_event(Event::ConditionCreated {
+                condition_id,
+                name: name.to_vec(),
+            });
+            
             Ok(())
         }
 
-        /// Apply a condition to a pet (admin only).
+        /// Apply a condition to a pet. Other pallets apply conditions through
+        /// `inflict_condition` instead.
         #[pallet::call_index(7)]
         #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn apply_condition(
@@ -797,8 +855,175 @@ pub mod pallet {
             pet_id: PetId,
             condition_id: ConditionId,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            T::ConditionOrigin::ensure_origin(origin)?;
+            Self::inflict_condition(pet_id, condition_id)
+        }
+
+        /// Remove a condition from a pet (admin only).
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_condition(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            condition_id: ConditionId,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::do_remove_condition(pet_id, condition_id)
+        }
+
+        /// Update a pet's stats (admin only).
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn update_pet_stats(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            strength: Option<StatValue>,
+            agility: Option<StatValue>,
+            intelligence: Option<StatValue>,
+            vitality: Option<StatValue>,
+            charisma: Option<StatValue>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            
+            // 1. Check if the pet status exists.
+            ensure!(PetStatuses::<T>::contains_key(pet_id), Error::<T>::PetStatusDoesNotExist);
+            
+            // 2. Get the pet stats.
+            let mut pet_stats = PetStatsStorage::<T>::get(pet_id).ok_or(Error::<T>::PetStatsDoNotExist)?;
+            
+            // 3. Update the stats.
+            if let Some(strength) = strength {
+                let old_value = pet_stats.strength;
+                pet_stats.strength = strength;
+                Self::deposit_event(Event::PetStatChanged {
+                    pet_id,
+                    stat_type: StatType::Strength,
+                    old_value,
+                    new_value: strength,
+                });
+            }
+            
+            if let Some(agility) = agility {
+                let old_value = pet_stats.agility;
+                pet_stats.agility = agility;
+                Self::deposit_event(Event::PetStatChanged {
+                    pet_id,
+                    stat_type: StatType::Agility,
+                    old_value,
+                    new_value: agility,
+                });
+            }
+            
+            if let Some(intelligence) = intelligence {
+                let old_value = pet_stats.intelligence;
+                pet_stats.intelligence = intelligence;
+                Self::deposit_event(Event::PetStatChanged {
+                    pet_id,
+                    stat_type: StatType::Intelligence,
+                    old_value,
+                    new_value: intelligence,
+                });
+            }
+            
+            if let Some(vitality) = vitality {
+                let old_value = pet_stats.vitality;
+                pet_stats.vitality = vitality;
+                Self::deposit_event(Event::PetStatChanged {
+                    pet_id,
+                    stat_type: StatType::Vitality,
+                    old_value,
+                    new_value: vitality,
+                });
+            }
+            
+            if let Some(charisma) = charisma {
+                let old_value = pet_stats.charisma;
+                pet_stats.charisma = charisma;
+                Self::deposit_event(Event::PetStatChanged {
+                    pet_id,
+                    stat_type: StatType::Charisma,
+                    old_value,
+                    new_value: charisma,
+                });
+            }
+            
+            // 4. Store the updated pet stats.
+            PetStatsStorage::<T>::insert(pet_id, pet_stats);
+            
+            Ok(())
+        }
+        
+        /// Cure one of the caller's pets of a condition, through `CureHandler` (which may, for
+        /// example, burn the medicine item the cure takes).
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn cure_condition(
+            origin: OriginFor<T>,
+            pet_id: PetId,
+            condition_id: ConditionId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            
+            // 1. Check if the sender is the owner of the pet.
+            let pet_status = PetStatuses::<T>::get(pet_id).ok_or(Error::<T>::PetStatusDoesNotExist)?;
+            ensure!(pet_status.owner == sender, Error::<T>::PetNotOwnedBySender);
+            ensure!(
+                PetConditions::<T>::get(pet_id).iter().any(|c| c.condition_id == condition_id),
+                Error::<T>::PetDoesNotHaveCondition
+            );
+            
+            // 2. Pay for the cure.
+            T::CureHandler::on_cure(&sender, pet_id, condition_id)?;
+            
+            // 3. Remove the condition.
+            Self::do_remove_condition(pet_id, condition_id)?;
             
+            // 4. Emit the event.
+            Self::deposit_event(Event::PetConditionCured {
+                pet_id,
+                condition_id,
+                by: sender,
+            });
+            
+            Ok(())
+        }
+    }
+
+    // --- Pallet Internal Helper Functions ---
+    impl<T: Config> Pallet<T> {
+        /// Derives a pet's mood from its needs, weighting hunger and happiness 30% each, energy 20%,
+        /// and hygiene and social 10% each.
+        pub fn compute_mood(needs: &PetNeeds) -> PetMood {
+            // Weights in tenths, so the score stays within 0-100 without floats.
+            let mood_score = (needs.hunger as u32 * 3
+                + needs.energy as u32 * 2
+                + needs.happiness as u32 * 3
+                + needs.hygiene as u32
+                + needs.social as u32)
+                / 10;
+            
+            if mood_score >= 80 {
+                PetMood::Happy
+            } else if mood_score >= 60 {
+                PetMood::Content
+            } else if mood_score >= 40 {
+                PetMood::Neutral
+            } else if mood_score >= 20 {
+                PetMood::Sad
+            } else {
+                PetMood::Distressed
+            }
+        }
+
+        /// Get a pet's current mood, derived from its needs. `None` if the pet has no needs.
+        pub fn get_pet_mood(pet_id: PetId) -> Option<PetMood> {
+            PetNeedsStorage::<T>::get(pet_id).map(|needs| Self::compute_mood(&needs))
+        }
+
+        /// Apply a condition to a pet, for the `apply_condition` call and for other pallets
+        /// (battle injuries, neglect) through a runtime adapter. The condition's need modifiers
+        /// apply at once; its stat modifiers count in `effective_stats` while it is active.
+        pub fn inflict_condition(pet_id: PetId, condition_id: ConditionId) -> DispatchResult {
             // 1. Check if the pet status exists.
             ensure!(PetStatuses::<T>::contains_key(pet_id), Error::<T>::PetStatusDoesNotExist);
             
@@ -813,8 +1038,13 @@ pub mod pallet {
                 }
             }
             
-            // 4. Apply the condition.
+            // 4. Check if the pet is still immune to the condition.
             let current_block = frame_system::Pallet::<T>::block_number();
+            if let Some(immunity_expiry) = ConditionImmunity::<T>::get(pet_id, condition_id) {
+                ensure!(current_block > immunity_expiry, Error::<T>::ConditionImmunityActive);
+            }
+            
+            // 5. Apply the condition.
             let expires_at_block = current_block.saturating_add(condition.duration_blocks);
             
             let pet_condition = PetCondition::<T> {
@@ -824,91 +1054,12 @@ pub mod pallet {
                 expires_at_block,
             };
             
-            // 5. Store the pet condition.
+            // 6. Store the pet condition.
             PetConditions::<T>::try_mutate(pet_id, |conditions| -> DispatchResult {
                 conditions.try_push(pet_condition).map_err(|_| Error::<T>::MaxPetConditionsReached)?;
                 Ok(())
             })?;
             
-            // 6. Apply stat modifiers.
-            if let Some(mut pet_stats) = PetStatsStorage::<T>::get(pet_id) {
-                for (stat_type, modifier) in condition.stat_modifiers.iter() {
-                    match stat_type {
-                        StatType::Strength => {
-                            let old_value = pet_stats.strength;
-                            if *modifier > 0 {
-                                pet_stats.strength = (pet_stats.strength.saturating_add(*modifier as u8)).min(100);
-                            } else {
-                                pet_stats.strength = pet_stats.strength.saturating_sub((-*modifier) as u8);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.strength,
-                            });
-                        },
-                        StatType::Agility => {
-                            let old_value = pet_stats.agility;
-                            if *modifier > 0 {
-                                pet_stats.agility = (pet_stats.agility.saturating_add(*modifier as u8)).min(100);
-                            } else {
-                                pet_stats.agility = pet_stats.agility.saturating_sub((-*modifier) as u8);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.agility,
-                            });
-                        },
-                        StatType::Intelligence => {
-                            let old_value = pet_stats.intelligence;
-                            if *modifier > 0 {
-                                pet_stats.intelligence = (pet_stats.intelligence.saturating_add(*modifier as u8)).min(100);
-                            } else {
-                                pet_stats.intelligence = pet_stats.intelligence.saturating_sub((-*modifier) as u8);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.intelligence,
-                            });
-                        },
-                        StatType::Vitality => {
-                            let old_value = pet_stats.vitality;
-                            if *modifier > 0 {
-                                pet_stats.vitality = (pet_stats.vitality.saturating_add(*modifier as u8)).min(100);
-                            } else {
-                                pet_stats.vitality = pet_stats.vitality.saturating_sub((-*modifier) as u8);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.vitality,
-                            });
-                        },
-                        StatType::Charisma => {
-                            let old_value = pet_stats.charisma;
-                            if *modifier > 0 {
-                                pet_stats.charisma = (pet_stats.charisma.saturating_add(*modifier as u8)).min(100);
-                            } else {
-                                pet_stats.charisma = pet_stats.charisma.saturating_sub((-*modifier) as u8);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.charisma,
-                            });
-                        },
-                    }
-                }
-                PetStatsStorage::<T>::insert(pet_id, pet_stats);
-            }
-            
             // 7. Apply need modifiers.
             if let Some(mut pet_needs) = PetNeedsStorage::<T>::get(pet_id) {
                 for (need_type, modifier) in condition.need_modifiers.iter() {
@@ -985,23 +1136,10 @@ pub mod pallet {
                         },
                     }
                 }
-                PetNeedsStorage::<T>::insert(pet_id, pet_needs);
+                Self::update_needs(pet_id, pet_needs);
             }
             
-            // 8. Update the pet's mood.
-            if let Some(mut pet_status) = PetStatuses::<T>::get(pet_id) {
-                if let Some(pet_needs) = PetNeedsStorage::<T>::get(pet_id) {
-                    Self::update_pet_mood(&mut pet_status, &pet_needs);
-                    PetStatuses::<T>::insert(pet_id, pet_status.clone());
-                    
-                    Self::deposit_event(Event::PetMoodChanged {
-                        pet_id,
-                        mood: pet_status.mood,
-                    });
-                }
-            }
-            
-            // 9. Emit the event.
+            // 8. Emit the event.
             Self::deposit_event(Event::PetDevelopedCondition {
                 pet_id,
                 condition_id,
@@ -1011,16 +1149,8 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Remove a condition from a pet (admin only).
-        #[pallet::call_index(8)]
-        #[pallet::weight(Weight::from_parts(10_000, 0))]
-        pub fn remove_condition(
-            origin: OriginFor<T>,
-            pet_id: PetId,
-            condition_id: ConditionId,
-        ) -> DispatchResult {
-            ensure_root(origin)?;
-            
+        /// Remove a condition from a pet and make it immune to the condition for a while.
+        fn do_remove_condition(pet_id: PetId, condition_id: ConditionId) -> DispatchResult {
             // 1. Check if the pet status exists.
             ensure!(PetStatuses::<T>::contains_key(pet_id), Error::<T>::PetStatusDoesNotExist);
             
@@ -1036,307 +1166,225 @@ pub mod pallet {
             pet_conditions.swap_remove(condition_index);
             PetConditions::<T>::insert(pet_id, pet_conditions);
             
-            // 5. Revert stat modifiers.
-            if let Some(mut pet_stats) = PetStatsStorage::<T>::get(pet_id) {
-                for (stat_type, modifier) in condition.stat_modifiers.iter() {
-                    match stat_type {
-                        StatType::Strength => {
-                            let old_value = pet_stats.strength;
-                            if *modifier > 0 {
-                                pet_stats.strength = pet_stats.strength.saturating_sub(*modifier as u8);
-                            } else {
-                                pet_stats.strength = (pet_stats.strength.saturating_add((-*modifier) as u8)).min(100);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.strength,
-                            });
-                        },
-                        StatType::Agility => {
-                            let old_value = pet_stats.agility;
-                            if *modifier > 0 {
-                                pet_stats.agility = pet_stats.agility.saturating_sub(*modifier as u8);
-                            } else {
-                                pet_stats.agility = (pet_stats.agility.saturating_add((-*modifier) as u8)).min(100);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.agility,
-                            });
-                        },
-                        StatType::Intelligence => {
-                            let old_value = pet_stats.intelligence;
-                            if *modifier > 0 {
-                                pet_stats.intelligence = pet_stats.intelligence.saturating_sub(*modifier as u8);
-                            } else {
-                                pet_stats.intelligence = (pet_stats.intelligence.saturating_add((-*modifier) as u8)).min(100);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.intelligence,
-                            });
-                        },
-                        StatType::Vitality => {
-                            let old_value = pet_stats.vitality;
-                            if *modifier > 0 {
-                                pet_stats.vitality = pet_stats.vitality.saturating_sub(*modifier as u8);
-                            } else {
-                                pet_stats.vitality = (pet_stats.vitality.saturating_add((-*modifier) as u8)).min(100);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.vitality,
-                            });
-                        },
-                        StatType::Charisma => {
-                            let old_value = pet_stats.charisma;
-                            if *modifier > 0 {
-                                pet_stats.charisma = pet_stats.charisma.saturating_sub(*modifier as u8);
-                            } else {
-                                pet_stats.charisma = (pet_stats.charisma.saturating_add((-*modifier) as u8)).min(100);
-                            }
-                            Self::deposit_event(Event::PetStatChanged {
-                                pet_id,
-                                stat_type: *stat_type,
-                                old_value,
-                                new_value: pet_stats.charisma,
-                            });
-                        },
-                    }
-                }
-                PetStatsStorage::<T>::insert(pet_id, pet_stats);
-            }
-            
-            // 6. Update the pet's mood.
-            if let Some(mut pet_status) = PetStatuses::<T>::get(pet_id) {
-                if let Some(pet_needs) = PetNeedsStorage::<T>::get(pet_id) {
-                    Self::update_pet_mood(&mut pet_status, &pet_needs);
-                    PetStatuses::<T>::insert(pet_id, pet_status.clone());
-                    
-                    Self::deposit_event(Event::PetMoodChanged {
-                        pet_id,
-                        mood: pet_status.mood,
-                    });
-                }
-            }
+            // 5. Make the pet immune to the condition for a while.
+            let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(T::ImmunityDurationBlocks::get());
+            ConditionImmunity::<T>::insert(pet_id, condition_id, expires_at);
             
-            // 7. Emit the event.
+            // 6. Emit the events.
             Self::deposit_event(Event::PetRecoveredFromCondition {
                 pet_id,
                 condition_id,
                 name: condition.name.to_vec(),
             });
             
+            Self::deposit_event(Event::ConditionImmunityGranted {
+                pet_id,
+                condition_id,
+                expires_at,
+            });
+            
             Ok(())
         }
 
-        /// Update a pet's stats (admin only).
-        #[pallet::call_index(9)]
-        #[pallet::weight(Weight::from_parts(10_000, 0))]
-        pub fn update_pet_stats(
-            origin: OriginFor<T>,
-            pet_id: PetId,
-            strength: Option<StatValue>,
-            agility: Option<StatValue>,
-            intelligence: Option<StatValue>,
-            vitality: Option<StatValue>,
-            charisma: Option<StatValue>,
-        ) -> DispatchResult {
-            ensure_root(origin)?;
-            
-            // 1. Check if the pet status exists.
-            ensure!(PetStatuses::<T>::contains_key(pet_id), Error::<T>::PetStatusDoesNotExist);
-            
-            // 2. Get the pet stats.
-            let mut pet_stats = PetStatsStorage::<T>::get(pet_id).ok_or(Error::<T>::PetStatsDoNotExist)?;
+        /// The combined modifier of a pet's active conditions to one stat. A condition stops
+        /// counting at its `expires_at_block`, even before `on_initialize` removes it.
+        pub fn condition_modifier(pet_id: PetId, stat_type: StatType) -> i16 {
+            let current_block = frame_system::Pallet::<T>::block_number();
+            Self::pet_conditions(pet_id)
+                .iter()
+                .filter(|pet_condition| current_block < pet_condition.expires_at_block)
+                .filter_map(|pet_condition| Conditions::<T>::get(pet_condition.condition_id))
+                .flat_map(|condition| condition.stat_modifiers.into_iter())
+                .filter(|(modified_stat, _)| *modified_stat == stat_type)
+                .map(|(_, modifier)| modifier as i16)
+                .sum()
+        }
+
+        /// Get a pet's stats with its active conditions' modifiers applied, each clamped to
+        /// 0-100. `None` if the pet has no stats. Stored stats never include the modifiers.
+        pub fn effective_stats(pet_id: PetId) -> Option<PetStats> {
+            let base = PetStatsStorage::<T>::get(pet_id)?;
+            let apply = |value: StatValue, stat_type: StatType| {
+                (value as i16 + Self::condition_modifier(pet_id, stat_type)).clamp(0, 100) as StatValue
+            };
+            Some(PetStats {
+                strength: apply(base.strength, StatType::Strength),
+                agility: apply(base.agility, StatType::Agility),
+                intelligence: apply(base.intelligence, StatType::Intelligence),
+                vitality: apply(base.vitality, StatType::Vitality),
+                charisma: apply(base.charisma, StatType::Charisma),
+            })
+        }
+
+        /// Store a pet's needs, emitting `PetMoodChanged` if they move its mood.
+        fn update_needs(pet_id: PetId, pet_needs: PetNeeds) {
+            let old_mood = Self::get_pet_mood(pet_id);
+            let new_mood = Self::compute_mood(&pet_needs);
+            PetNeedsStorage::<T>::insert(pet_id, pet_needs);
             
-            // 3. Update the stats.
-            if let Some(strength) = strength {
-                let old_value = pet_stats.strength;
-                pet_stats.strength = strength;
-                Self::deposit_event(Event::PetStatChanged {
+            if old_mood != Some(new_mood) {
+                Self::deposit_event(Event::PetMoodChanged {
                     pet_id,
-                    stat_type: StatType::Strength,
-                    old_value,
-                    new_value: strength,
+                    mood: new_mood,
                 });
             }
-            
-            if let Some(agility) = agility {
-                let old_value = pet_stats.agility;
-                pet_stats.agility = agility;
-                Self::deposit_event(Event::PetStatChanged {
-                    pet_id,
-                    stat_type: StatType::Agility,
-                    old_value,
-                    new_value: agility,
-                });
+        }
+
+        /// Remove expired immunity records, as many as `remaining_weight` allows, resuming the
+        /// sweep over all records where the previous idle block left it. Returns the weight used.
+        fn clean_up_immunities(current_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let (overhead, per_record) = Self::immunity_sweep_costs();
+            let Some(available) = remaining_weight.checked_sub(&overhead) else {
+                return Weight::zero();
+            };
+            // A dimension the records do not use does not limit the batch.
+            let budget = available.ref_time().checked_div(per_record.ref_time()).unwrap_or(u64::MAX)
+                .min(available.proof_size().checked_div(per_record.proof_size()).unwrap_or(u64::MAX));
+            let budget = usize::try_from(budget).unwrap_or(usize::MAX);
+            if budget == 0 {
+                return Weight::zero();
             }
             
-            if let Some(intelligence) = intelligence {
-                let old_value = pet_stats.intelligence;
-                pet_stats.intelligence = intelligence;
-                Self::deposit_event(Event::PetStatChanged {
-                    pet_id,
-                    stat_type: StatType::Intelligence,
-                    old_value,
-                    new_value: intelligence,
-                });
-            }
+            // 1. Walk the next batch of records, from the start once the previous sweep finished.
+            let batch: Vec<(PetId, ConditionId, BlockNumberFor<T>)> = match ImmunityCleanupCursor::<T>::get() {
+                Some((pet_id, condition_id)) => ConditionImmunity::<T>::iter_from(
+                    ConditionImmunity::<T>::hashed_key_for(pet_id, condition_id),
+                )
+                .take(budget)
+                .collect(),
+                None => ConditionImmunity::<T>::iter().take(budget).collect(),
+            };
             
-            if let Some(vitality) = vitality {
-                let old_value = pet_stats.vitality;
-                pet_stats.vitality = vitality;
-                Self::deposit_event(Event::PetStatChanged {
-                    pet_id,
-                    stat_type: StatType::Vitality,
-                    old_value,
-                    new_value: vitality,
-                });
+            // 2. Remove the expired ones.
+            for (pet_id, condition_id, expires_at) in batch.iter() {
+                if current_block > *expires_at {
+                    ConditionImmunity::<T>::remove(pet_id, condition_id);
+                }
             }
             
-            if let Some(charisma) = charisma {
-                let old_value = pet_stats.charisma;
-                pet_stats.charisma = charisma;
-                Self::deposit_event(Event::PetStatChanged {
-                    pet_id,
-                    stat_type: StatType::Charisma,
-                    old_value,
-                    new_value: charisma,
-                });
+            // 3. Resume after the last record next time, or start over if the sweep reached the end.
+            match batch.last() {
+                Some((pet_id, condition_id, _)) if batch.len() == budget => {
+                    ImmunityCleanupCursor::<T>::put((pet_id, condition_id))
+                },
+                _ => ImmunityCleanupCursor::<T>::kill(),
+            }
+            Self::immunity_sweep_weight(batch.len() as u64)
+        }
+
+        /// The weight of an idle sweep over `records` immunity records, cursor included.
+        pub fn immunity_sweep_weight(records: u64) -> Weight {
+            let (overhead, per_record) = Self::immunity_sweep_costs();
+            overhead.saturating_add(per_record.saturating_mul(records))
+        }
+
+        /// The fixed cost of an idle sweep and the cost of each record it visits. The cursor is
+        /// read and written once; each record is read, and written if it expired. Proof sizes
+        /// count the 32-byte storage prefix, the hashed keys and the value.
+        fn immunity_sweep_costs() -> (Weight, Weight) {
+            let cursor_len = 32 + <(PetId, ConditionId)>::max_encoded_len();
+            let record_len = 32
+                + 16 + PetId::max_encoded_len()
+                + 16 + ConditionId::max_encoded_len()
+                + BlockNumberFor::<T>::max_encoded_len();
+            let access = T::DbWeight::get().reads_writes(1, 1);
+            (
+                access.saturating_add(Weight::from_parts(0, cursor_len as u64)),
+                access.saturating_add(Weight::from_parts(0, record_len as u64)),
+            )
+        }
+
+        /// Decay the needs of up to `MaxNeedDecaysPerBlock` pets, resuming the walk over all pets'
+        /// needs where the previous block left it. Returns the weight used.
+        fn decay_needs(current_block: BlockNumberFor<T>) -> Weight {
+            let limit = T::MaxNeedDecaysPerBlock::get() as usize;
+            let mut weight = T::DbWeight::get().reads(1);
+            if limit == 0 || T::NeedDecayInterval::get().is_zero() {
+                return weight;
             }
             
-            // 4. Store the updated pet stats.
-            PetStatsStorage::<T>::insert(pet_id, pet_stats);
+            // 1. Walk the next batch of pets, from the start once the previous walk finished.
+            let batch: Vec<(PetId, PetNeeds)> = match NeedDecayCursor::<T>::get() {
+                Some(last_pet_id) => PetNeedsStorage::<T>::iter_from(PetNeedsStorage::<T>::hashed_key_for(last_pet_id))
+                    .take(limit)
+                    .collect(),
+                None => PetNeedsStorage::<T>::iter().take(limit).collect(),
+            };
             
-            Ok(())
+            // 2. Decay each pet whose interval has elapsed.
+            for (pet_id, pet_needs) in batch.iter() {
+                weight = weight.saturating_add(Self::decay_pet_needs(*pet_id, pet_needs.clone(), current_block));
+            }
+            
+            // 3. Resume after the last pet next block, or start over if the walk reached the end.
+            match batch.last() {
+                Some((last_pet_id, _)) if batch.len() == limit => NeedDecayCursor::<T>::put(last_pet_id),
+                _ => NeedDecayCursor::<T>::kill(),
+            }
+            weight.saturating_add(T::DbWeight::get().writes(1))
         }
-    }
 
-    // --- Pallet Internal Helper Functions ---
-    impl<T: Config> Pallet<T> {
-        /// Update a pet's mood based on its needs.
-        fn update_pet_mood(pet_status: &mut PetStatus<T>, pet_needs: &PetNeeds) {
-            // Calculate the average need value.
-            let total_needs = pet_needs.hunger as u32 + pet_needs.energy as u32 + pet_needs.happiness as u32 + pet_needs.hygiene as u32 + pet_needs.social as u32;
-            let avg_needs = total_needs / 5;
-            
-            // Update the mood based on the average need value.
-            pet_status.mood = if avg_needs >= 80 {
-                PetMood::Happy
-            } else if avg_needs >= 60 {
-                PetMood::Content
-            } else if avg_needs >= 40 {
-                PetMood::Neutral
-            } else if avg_needs >= 20 {
-                PetMood::Sad
+        /// Decay a pet's needs once for every `NeedDecayInterval` elapsed since they last decayed,
+        /// up to `MAX_CATCH_UP_DECAYS` times. Returns the weight used.
+        fn decay_pet_needs(pet_id: PetId, pet_needs: PetNeeds, current_block: BlockNumberFor<T>) -> Weight {
+            // The needs and the last decay are read either way.
+            let weight = T::DbWeight::get().reads(2);
+            let interval = T::NeedDecayInterval::get();
+            let Some(last_decay) = LastNeedDecay::<T>::get(pet_id) else {
+                // Needs without a decay record start decaying from now.
+                LastNeedDecay::<T>::insert(pet_id, current_block);
+                return weight.saturating_add(T::DbWeight::get().writes(1));
+            };
+            
+            let elapsed: u32 = (current_block.saturating_sub(last_decay) / interval).unique_saturated_into();
+            if elapsed == 0 {
+                return weight;
+            }
+            
+            // Keep the part of an interval already elapsed, unless intervals were skipped.
+            let decays = elapsed.min(MAX_CATCH_UP_DECAYS);
+            let next_decay_from = if elapsed > MAX_CATCH_UP_DECAYS {
+                current_block
             } else {
-                PetMood::Distressed
+                last_decay.saturating_add(interval.saturating_mul(decays.into()))
             };
-        }
-
-        /// Process pet updates (need decay and condition updates).
-        fn process_pet_updates(current_block: BlockNumberFor<T>) {
-            // Process need decay for all pets.
-            for (pet_id, _) in PetStatuses::<T>::iter() {
-                if let Some(last_decay) = LastNeedDecay::<T>::get(pet_id) {
-                    let blocks_since_decay = current_block.saturating_sub(last_decay);
-                    
-                    if blocks_since_decay >= T::NeedDecayInterval::get() {
-                        // Decay the pet's needs.
-                        if let Some(mut pet_needs) = PetNeedsStorage::<T>::get(pet_id) {
-                            let decay_amount = T::NeedDecayAmount::get();
-                            
-                            let old_hunger = pet_needs.hunger;
-                            let old_energy = pet_needs.energy;
-                            let old_happiness = pet_needs.happiness;
-                            let old_hygiene = pet_needs.hygiene;
-                            let old_social = pet_needs.social;
-                            
-                            pet_needs.hunger = pet_needs.hunger.saturating_sub(decay_amount);
-                            pet_needs.energy = pet_needs.energy.saturating_sub(decay_amount);
-                            pet_needs.happiness = pet_needs.happiness.saturating_sub(decay_amount);
-                            pet_needs.hygiene = pet_needs.hygiene.saturating_sub(decay_amount);
-                            pet_needs.social = pet_needs.social.saturating_sub(decay_amount);
-                            
-                            PetNeedsStorage::<T>::insert(pet_id, pet_needs.clone());
-                            LastNeedDecay::<T>::insert(pet_id, current_block);
-                            
-                            // Update the pet's mood.
-                            if let Some(mut pet_status) = PetStatuses::<T>::get(pet_id) {
-                                Self::update_pet_mood(&mut pet_status, &pet_needs);
-                                PetStatuses::<T>::insert(pet_id, pet_status.clone());
-                                
-                                Self::deposit_event(Event::PetMoodChanged {
-                                    pet_id,
-                                    mood: pet_status.mood,
-                                });
-                            }
-                            
-                            // Emit need changed events.
-                            if old_hunger != pet_needs.hunger {
-                                Self::deposit_event(Event::PetNeedChanged {
-                                    pet_id,
-                                    need_type: NeedType::Hunger,
-                                    old_value: old_hunger,
-                                    new_value: pet_needs.hunger,
-                                });
-                            }
-                            
-                            if old_energy != pet_needs.energy {
-                                Self::deposit_event(Event::PetNeedChanged {
-                                    pet_id,
-                                    need_type: NeedType::Energy,
-                                    old_value: old_energy,
-                                    new_value: pet_needs.energy,
-                                });
-                            }
-                            
-                            if old_happiness != pet_needs.happiness {
-                                Self::deposit_event(Event::PetNeedChanged {
-                                    pet_id,
-                                    need_type: NeedType::Happiness,
-                                    old_value: old_happiness,
-                                    new_value: pet_needs.happiness,
-                                });
-                            }
-                            
-                            if old_hygiene != pet_needs.hygiene {
-                                Self::deposit_event(Event::PetNeedChanged {
-                                    pet_id,
-                                    need_type: NeedType::Hygiene,
-                                    old_value: old_hygiene,
-                                    new_value: pet_needs.hygiene,
-                                });
-                            }
-                            
-                            if old_social != pet_needs.social {
-                                Self::deposit_event(Event::PetNeedChanged {
-                                    pet_id,
-                                    need_type: NeedType::Social,
-                                    old_value: old_social,
-                                    new_value: pet_needs.social,
-                                });
-                            }
-                            
-                            Self::deposit_event(Event::PetNeedsDecayed {
-                                pet_id,
-                            });
-                        }
-                    }
+            
+            let decay_amount = T::NeedDecayAmount::get().saturating_mul(decays as StatValue);
+            let new_needs = PetNeeds {
+                hunger: pet_needs.hunger.saturating_sub(decay_amount),
+                energy: pet_needs.energy.saturating_sub(decay_amount),
+                happiness: pet_needs.happiness.saturating_sub(decay_amount),
+                hygiene: pet_needs.hygiene.saturating_sub(decay_amount),
+                social: pet_needs.social.saturating_sub(decay_amount),
+            };
+            
+            Self::update_needs(pet_id, new_needs.clone());
+            LastNeedDecay::<T>::insert(pet_id, next_decay_from);
+            
+            for (need_type, old_value, new_value) in [
+                (NeedType::Hunger, pet_needs.hunger, new_needs.hunger),
+                (NeedType::Energy, pet_needs.energy, new_needs.energy),
+                (NeedType::Happiness, pet_needs.happiness, new_needs.happiness),
+                (NeedType::Hygiene, pet_needs.hygiene, new_needs.hygiene),
+                (NeedType::Social, pet_needs.social, new_needs.social),
+            ] {
+                if old_value != new_value {
+                    Self::deposit_event(Event::PetNeedChanged { pet_id, need_type, old_value, new_value });
                 }
             }
+            Self::deposit_event(Event::PetNeedsDecayed { pet_id });
+            
+            // `update_needs` reads the old needs again to compare moods.
+            weight.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+        }
+
+        /// Remove every condition that has run its course. Returns the weight used.
+        fn expire_conditions(current_block: BlockNumberFor<T>) -> Weight {
+            let mut weight = Weight::zero();
             
             // Process condition updates for all pets.
             for (pet_id, pet_conditions) in PetConditions::<T>::iter() {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
                 let mut conditions_to_remove = Vec::new();
                 
                 // Check for expired conditions.
@@ -1347,14 +1395,17 @@ pub mod pallet {
                 }
                 
                 // Remove expired conditions.
-                if !conditions_to_remove.is_empty() {
-                    for (i, condition_id) in conditions_to_remove.iter().rev() {
-                        if let Ok(()) = Self::remove_condition(RawOrigin::Root.into(), pet_id, *condition_id) {
-                            // Condition was successfully removed.
-                        }
+                for (_, condition_id) in conditions_to_remove.iter().rev() {
+                    // A removal reads the status, condition and conditions, and writes the
+                    // conditions and immunity.
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 2));
+                    if let Ok(()) = Self::do_remove_condition(pet_id, *condition_id) {
+                        // Condition was successfully removed.
                     }
                 }
             }
+            
+            weight
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,141 @@
+//! # Storage Migrations
+//!
+//! This module contains the storage migrations for pallet-critter-pet-status.
+//! Each migration lives in its own versioned submodule as a `MigrationStep`, wrapped in
+//! `VersionedMigration` so it only runs when the on-chain storage version matches the
+//! version it migrates from.
+
+use crittercraft_traits::migration::{MigrationStep, VersionedMigration};
+use frame_support::{
+    pallet_prelude::*,
+    weights::Weight,
+    log,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_std::marker::PhantomData;
+use crate::{Conditions, Config, Pallet, PetConditions, PetId, PetMood, PetStatsStorage, PetStatus, PetStatuses, StatType};
+
+/// Migration to storage version 1: drops the stored mood from `PetStatus`, which is now derived
+/// from the pet's needs.
+pub mod v1 {
+    use super::*;
+
+    /// The `PetStatus` layout before moods were derived.
+    #[derive(Encode, Decode)]
+    pub struct OldPetStatus<AccountId, BlockNumber> {
+        pub pet_id: PetId,
+        pub owner: AccountId,
+        pub mood: PetMood,
+        pub last_interaction: BlockNumber,
+        pub last_fed: BlockNumber,
+        pub last_rested: BlockNumber,
+        pub last_played: BlockNumber,
+        pub last_groomed: BlockNumber,
+        pub last_socialized: BlockNumber,
+    }
+
+    /// Re-encodes every `PetStatus` without its mood.
+    pub struct DropStoredMood<T>(PhantomData<T>);
+
+    impl<T: Config> MigrationStep for DropStoredMood<T> {
+        fn migrate() -> Weight {
+            let mut migrated = 0u64;
+            PetStatuses::<T>::translate::<OldPetStatus<T::AccountId, BlockNumberFor<T>>, _>(|_, old| {
+                migrated += 1;
+                Some(PetStatus::<T> {
+                    pet_id: old.pet_id,
+                    owner: old.owner,
+                    last_interaction: old.last_interaction,
+                    last_fed: old.last_fed,
+                    last_rested: old.last_rested,
+                    last_played: old.last_played,
+                    last_groomed: old.last_groomed,
+                    last_socialized: old.last_socialized,
+                })
+            });
+
+            log::info!(
+                target: "runtime::critter-pet-status",
+                "migrated {} pet statuses to v1",
+                migrated
+            );
+            T::DbWeight::get().reads_writes(migrated, migrated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade_state() -> Result<sp_std::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((PetStatuses::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade_check(state: sp_std::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let statuses_before: u64 = Decode::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode status count"))?;
+            // `iter` skips undecodable values, so a matching count means every status decodes.
+            ensure!(PetStatuses::<T>::iter().count() as u64 == statuses_before, "pet statuses lost during migration");
+            Ok(())
+        }
+    }
+
+    /// Storage version 0 -> 1, safe to queue unconditionally in the runtime.
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        DropStoredMood<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Migration to storage version 2: takes the stat modifiers of pets' current conditions back out
+/// of their stored stats. Conditions used to add them to the stored stats when applied and
+/// subtract them when removed; they now only count in `effective_stats`.
+///
+/// Modifiers that were clamped at 0 or 100 when applied cannot be recovered exactly, so the
+/// stats are restored as if they were not clamped, within 0-100.
+pub mod v2 {
+    use super::*;
+
+    /// Reverts every current condition's stat modifiers on the stored stats.
+    pub struct UnbakeConditionModifiers<T>(PhantomData<T>);
+
+    impl<T: Config> MigrationStep for UnbakeConditionModifiers<T> {
+        fn migrate() -> Weight {
+            let (mut reads, mut writes) = (0u64, 0u64);
+            for (pet_id, pet_conditions) in PetConditions::<T>::iter() {
+                reads += 2 + pet_conditions.len() as u64;
+                let Some(mut pet_stats) = PetStatsStorage::<T>::get(pet_id) else { continue };
+                for condition in pet_conditions.iter().filter_map(|c| Conditions::<T>::get(c.condition_id)) {
+                    for (stat_type, modifier) in condition.stat_modifiers.iter() {
+                        let stat = match stat_type {
+                            StatType::Strength => &mut pet_stats.strength,
+                            StatType::Agility => &mut pet_stats.agility,
+                            StatType::Intelligence => &mut pet_stats.intelligence,
+                            StatType::Vitality => &mut pet_stats.vitality,
+                            StatType::Charisma => &mut pet_stats.charisma,
+                        };
+                        *stat = (*stat as i16 - *modifier as i16).clamp(0, 100) as u8;
+                    }
+                }
+                PetStatsStorage::<T>::insert(pet_id, pet_stats);
+                writes += 1;
+            }
+
+            log::info!(
+                target: "runtime::critter-pet-status",
+                "migrated {} pets' stats to v2",
+                writes
+            );
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Storage version 1 -> 2, safe to queue unconditionally in the runtime.
+    pub type MigrateToV2<T> = VersionedMigration<
+        1,
+        2,
+        UnbakeConditionModifiers<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -102,7 +102,7 @@ benchmarks! {
         create_and_mint_pet::<T>(caller.clone(), pet_id)?; 
         // Ensure pet is unlocked (is_transferable) for registration.
         crittercraft_runtime::pallet_critter_nfts::Pallet::<T>::unlock_nft(
-            &caller, &pet_id
+            &caller, &pet_id, crittercraft_traits::LockerId::Battle
         )?;
         
     }: _(RawOrigin::Signed(caller.clone()), pet_id) // Benchmark the extrinsic call
@@ -130,7 +130,7 @@ benchmarks! {
 
         // Ensure pet2 is unlocked for initiation.
         crittercraft_runtime::pallet_critter_nfts::Pallet::<T>::unlock_nft(
-            &player2, &pet2_id
+            &player2, &pet2_id, crittercraft_traits::LockerId::Battle
         )?;
 
     }: _(RawOrigin::Signed(player1.clone()), battle_id, player2.clone(), pet2_id) // Benchmark the extrinsic
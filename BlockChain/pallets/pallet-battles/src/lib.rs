@@ -36,6 +36,7 @@ pub mod pallet {
     // They are crucial for inter-pallet communication and system synergy.
     use crittercraft_traits::{
         SharedNftManager as NftManager, // For pet locking/unlocking/ownership
+        LockerId,                       // Lock namespace for pets locked by battles
         NftManagerForItems,             // For applying item effects to pets (e.g., battle buffs)
         QuestNftRequirementChecker,     // For checking pet eligibility for quests
         PetId,                          // PetId type from traits crate
@@ -326,8 +327,8 @@ pub mod pallet {
 
                 // 6. Lock both pets for battle to prevent conflicting operations.
                 // This is a crucial atomic step for maintaining state integrity.
-                T::NftHandler::lock_nft(&player1, &pet1_id)?; // Player1's pet (already registered)
-                T::NftHandler::lock_nft(&player2, &pet2_id)?; // Player2's pet
+                T::NftHandler::lock_nft(&player1, &pet1_id, LockerId::Battle)?; // Player1's pet (already registered)
+                T::NftHandler::lock_nft(&player2, &pet2_id, LockerId::Battle)?; // Player2's pet
 
                 // 7. Update battle details and status.
                 battle.player2 = Some(player2.clone());
@@ -431,8 +432,8 @@ pub mod pallet {
                 
                 // 7. Unlock pets for battle. This is crucial for maintaining state integrity.
                 // Owner must be correct to unlock.
-                T::NftHandler::unlock_nft(&winner_account_final, &actual_winner_pet_id_final)?;
-                T::NftHandler::unlock_nft(&loser_account_final, &loser_pet_id_final)?;
+                T::NftHandler::unlock_nft(&winner_account_final, &actual_winner_pet_id_final, LockerId::Battle)?;
+                T::NftHandler::unlock_nft(&loser_account_final, &loser_pet_id_final, LockerId::Battle)?;
 
 
                 // 8. Clean up PetInBattle state for both pets involved.
@@ -493,8 +494,8 @@ pub mod pallet {
                 battle.concluded_block = Some(frame_system::Pallet::<T>::block_number());
 
                 // Unlock both pets from battle.
-                T::NftHandler::unlock_nft(&fleeing_owner_actual, &fleeing_pet_actual)?;
-                T::NftHandler::unlock_nft(&other_owner, &other_pet)?;
+                T::NftHandler::unlock_nft(&fleeing_owner_actual, &fleeing_pet_actual, LockerId::Battle)?;
+                T::NftHandler::unlock_nft(&other_owner, &other_pet, LockerId::Battle)?;
 
                 // Remove from PetInBattle storage.
                 <PetInBattle<T>>::remove(&fleeing_pet_actual);
@@ -0,0 +1,196 @@
+use crate as pallet_breeding;
+use crittercraft_traits::{
+    AdvancedPetManagement, AttributeType, EnhancedPetInfo, LockerId, NftBreedingHandler, NftCallError, PetId, PetStats,
+    SharedNftManager, SimpleGeneticInfo,
+};
+use frame_support::{
+    dispatch::DispatchResult,
+    parameter_types,
+    traits::{ConstU32, ConstU64},
+    BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError,
+};
+use sp_std::vec::Vec;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Breeding: pallet_breeding,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = u128;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = frame_support::traits::ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+
+/// Pets at or above this id are level 1; the rest are level 5.
+pub const FIRST_LEVEL_1_PET: PetId = 10;
+
+/// The id of the first pet minted from breeding.
+pub const FIRST_OFFSPRING: PetId = 100;
+
+parameter_types! {
+    /// Each pet's owner; pets not listed here do not exist.
+    pub static MockPetOwners: Vec<(PetId, u64)> = vec![(1, ALICE), (2, ALICE), (3, ALICE), (4, BOB), (FIRST_LEVEL_1_PET, ALICE)];
+    /// The pets locked, and by whom.
+    pub static MockLocks: Vec<(PetId, LockerId)> = Vec::new();
+    /// The offspring minted: (owner, species, parents, offspring id).
+    pub static MintedOffspring: Vec<(u64, u32, (PetId, PetId), PetId)> = Vec::new();
+}
+
+// Mock NFT manager which, like the NFT pallet, refuses to lock a locked pet
+pub struct MockNftManager;
+impl MockNftManager {
+    pub fn lock_of(pet_id: PetId) -> Option<LockerId> {
+        MockLocks::get().into_iter().find(|(id, _)| *id == pet_id).map(|(_, locker)| locker)
+    }
+}
+impl SharedNftManager<u64, PetId> for MockNftManager {
+    fn owner_of(token_id: &PetId) -> Option<u64> {
+        MockPetOwners::get().into_iter().find(|(id, _)| id == token_id).map(|(_, owner)| owner)
+    }
+    fn is_transferable(token_id: &PetId) -> bool {
+        Self::lock_of(*token_id).is_none()
+    }
+    fn lock_nft(_owner: &u64, token_id: &PetId, locker: LockerId) -> DispatchResult {
+        if Self::lock_of(*token_id).is_some() {
+            return Err(DispatchError::Other("pet locked"));
+        }
+        MockLocks::mutate(|locks| locks.push((*token_id, locker)));
+        Ok(())
+    }
+    fn unlock_nft(_owner: &u64, token_id: &PetId, locker: LockerId) -> DispatchResult {
+        if Self::lock_of(*token_id) != Some(locker) {
+            return Err(DispatchError::Other("not the lock holder"));
+        }
+        MockLocks::mutate(|locks| locks.retain(|(id, _)| id != token_id));
+        Ok(())
+    }
+    fn transfer_nft(_from: &u64, _to: &u64, _token_id: &PetId) -> DispatchResult { Ok(()) }
+    fn can_receive_pet(_account: &u64) -> bool { true }
+    fn remaining_capacity(_account: &u64) -> u32 { u32::MAX }
+}
+
+// Mock pet manager with the fixed level split
+pub struct MockPetManager;
+impl AdvancedPetManagement<u64, u64> for MockPetManager {
+    fn get_enhanced_pet_info(_pet_id: &PetId) -> Option<EnhancedPetInfo<u64, u64>> { None }
+    fn update_pet_stats(_pet_id: &PetId, _stats: PetStats) -> Result<(), NftCallError> { Ok(()) }
+    fn add_experience(_pet_id: &PetId, _experience: u32) -> Result<(), NftCallError> { Ok(()) }
+    fn get_pet_level(pet_id: &PetId) -> Result<u16, NftCallError> {
+        Ok(if *pet_id < FIRST_LEVEL_1_PET { 5 } else { 1 })
+    }
+    fn get_pet_attributes(_pet_id: &PetId) -> Result<Vec<(AttributeType, u8)>, NftCallError> { Ok(Vec::new()) }
+    fn update_pet_state(_pet_id: &PetId) -> Result<(), NftCallError> { Ok(()) }
+    fn is_pet_neglected(_pet_id: &PetId) -> bool { false }
+    fn is_eligible_for_evolution(_pet_id: &PetId) -> bool { false }
+    fn evolve_pet(_owner: &u64, _pet_id: &PetId) -> Result<(), NftCallError> { Ok(()) }
+}
+
+// Mock breeding handler: a pet's species is its id modulo 3, and offspring get ids from
+// `FIRST_OFFSPRING` on
+pub struct MockBreedingHandler;
+impl NftBreedingHandler<u64, PetId, u64, u32> for MockBreedingHandler {
+    fn get_pet_simple_genetics(token_id: &PetId) -> Option<SimpleGeneticInfo<u64, u32>> {
+        MockNftManager::owner_of(token_id)?;
+        Some(SimpleGeneticInfo { dna_hash: *token_id as u64, species: *token_id % 3, generation: 0 })
+    }
+    fn mint_pet_from_breeding(
+        owner: &u64,
+        species: u32,
+        parent1_id: PetId,
+        parent2_id: PetId,
+        _initial_name: BoundedVec<u8, ConstU32<64>>,
+    ) -> Result<PetId, DispatchResult> {
+        let offspring_id = FIRST_OFFSPRING + MintedOffspring::get().len() as PetId;
+        MintedOffspring::mutate(|minted| minted.push((*owner, species, (parent1_id, parent2_id), offspring_id)));
+        Ok(offspring_id)
+    }
+}
+
+parameter_types! {
+    pub const BreedingCost: u128 = 100;
+}
+
+impl pallet_breeding::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type NftManager = MockNftManager;
+    type PetManager = MockPetManager;
+    type DnaHash = u64;
+    type Species = u32;
+    type NftBreedingHandler = MockBreedingHandler;
+    type MinBreedingLevel = ConstU32<5>;
+    type BreedingCost = BreedingCost;
+    type BreedingDurationBlocks = ConstU64<10>;
+    type BreedingCooldownBlocks = ConstU64<20>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+    pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 1000), (BOB, 1000)] }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,93 @@
+use crate::{mock::*, Error, Event, PendingBreeding, BREEDING_LOCKER};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+
+#[test]
+fn initiating_locks_the_parents_and_burns_the_cost() {
+    new_test_ext().execute_with(|| {
+        let issuance = Balances::total_issuance();
+
+        assert_ok!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 2));
+
+        assert_eq!(
+            Breeding::pending_breedings((1, 2)),
+            Some(PendingBreeding { pet1_id: 1, pet2_id: 2, owner: ALICE, initiated_at: 1 })
+        );
+        assert_eq!(MockNftManager::lock_of(1), Some(BREEDING_LOCKER));
+        assert_eq!(MockNftManager::lock_of(2), Some(BREEDING_LOCKER));
+        assert_eq!(Balances::free_balance(ALICE), 900);
+        assert_eq!(Balances::total_issuance(), issuance - 100);
+        System::assert_last_event(Event::BreedingInitiated { owner: ALICE, pet1_id: 1, pet2_id: 2, ready_at: 11 }.into());
+    });
+}
+
+#[test]
+fn only_owned_eligible_pets_can_breed() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 1), Error::<Test>::SamePet);
+        assert_noop!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 4), Error::<Test>::NotPetOwner);
+        assert_noop!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 9), Error::<Test>::PetNotFound);
+        assert_noop!(
+            Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, FIRST_LEVEL_1_PET),
+            Error::<Test>::PetLevelTooLow
+        );
+
+        // A pet already breeding cannot start another breeding
+        assert_ok!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 2));
+        assert_noop!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 2, 3), Error::<Test>::PetNotTransferable);
+    });
+}
+
+#[test]
+fn completing_mints_the_offspring_and_releases_the_parents_on_cooldown() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 2));
+        assert_noop!(Breeding::complete_breeding(RuntimeOrigin::signed(ALICE), 1, 2), Error::<Test>::BreedingNotReady);
+
+        System::set_block_number(11);
+        assert_noop!(Breeding::complete_breeding(RuntimeOrigin::signed(BOB), 1, 2), Error::<Test>::NotBreedingOwner);
+        assert_ok!(Breeding::complete_breeding(RuntimeOrigin::signed(ALICE), 1, 2));
+
+        // The offspring takes the first parent's species
+        assert_eq!(MintedOffspring::get(), vec![(ALICE, 1, (1, 2), FIRST_OFFSPRING)]);
+        assert!(Breeding::pending_breedings((1, 2)).is_none());
+        assert!(MockNftManager::lock_of(1).is_none());
+        assert!(MockNftManager::lock_of(2).is_none());
+        assert_eq!(Breeding::breeding_cooldowns(1), Some(31));
+        assert_eq!(Breeding::breeding_cooldowns(2), Some(31));
+        System::assert_last_event(
+            Event::BreedingCompleted { owner: ALICE, pet1_id: 1, pet2_id: 2, offspring_id: FIRST_OFFSPRING }.into(),
+        );
+        assert_noop!(Breeding::complete_breeding(RuntimeOrigin::signed(ALICE), 1, 2), Error::<Test>::BreedingNotFound);
+    });
+}
+
+#[test]
+fn parents_cannot_breed_again_until_the_cooldown_ends() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 2));
+        System::set_block_number(11);
+        assert_ok!(Breeding::complete_breeding(RuntimeOrigin::signed(ALICE), 1, 2));
+
+        System::set_block_number(30);
+        assert_noop!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 3, 1), Error::<Test>::PetOnCooldown);
+
+        System::set_block_number(31);
+        assert_ok!(Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 3, 1));
+    });
+}
+
+#[test]
+fn breeding_needs_the_cost_in_hand() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(ALICE), BOB, 950));
+
+        // Alice has 50 left of the 100 breeding cost.
+        assert_noop!(
+            Breeding::initiate_breeding(RuntimeOrigin::signed(ALICE), 1, 2),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+
+        assert!(Breeding::pending_breedings((1, 2)).is_none());
+        assert!(MockNftManager::lock_of(1).is_none());
+    });
+}
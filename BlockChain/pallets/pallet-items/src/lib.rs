@@ -13,6 +13,7 @@ pub mod pallet {
         pallet_prelude::*,
         traits::Currency,
     };
+    use crittercraft_traits::CareError;
     use frame_system::pallet_prelude::*;
     use scale_info::TypeInfo;
     use sp_std::vec::Vec;
@@ -21,13 +22,13 @@ pub mod pallet {
     pub type ItemId = u32;
 
     // This trait is implemented by pallet-items and called by pallet-critter-nfts
-    // for basic feed/play item consumption.
+    // for basic feed/play item consumption. Failures report their cause as a `CareError`.
     pub trait BasicCareItemConsumer<AccountId, LocalItemId> {
         fn consume_specific_item(
             user: &AccountId,
             item_id: LocalItemId,
             expected_category: ItemCategory // Use local ItemCategory
-        ) -> DispatchResult;
+        ) -> Result<(), CareError>;
     }
 
     /// Enum defining categories for items (Simplified for MVP).
@@ -255,12 +256,12 @@ pub mod pallet {
             user: &T::AccountId,
             item_id: ItemId,
             expected_category: ItemCategory
-        ) -> DispatchResult {
-            let item_details = Self::item_definitions(item_id).ok_or(Error::<T>::ItemNotFound)?;
-            ensure!(item_details.category == expected_category, Error::<T>::ItemCategoryMismatch);
+        ) -> Result<(), CareError> {
+            let item_details = Self::item_definitions(item_id).ok_or(CareError::ItemNotFound)?;
+            ensure!(item_details.category == expected_category, CareError::WrongCategory);
 
             let current_quantity = Self::user_item_inventory((user, item_id));
-            ensure!(current_quantity > 0, Error::<T>::NotEnoughItemsInInventory);
+            ensure!(current_quantity > 0, CareError::NotInInventory);
 
             UserItemInventory::<T>::insert((user, item_id), current_quantity.saturating_sub(1));
             // Event for item consumption could be added here if needed, or rely on critter_nfts events.
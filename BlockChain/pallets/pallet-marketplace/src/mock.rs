@@ -0,0 +1,167 @@
+use crate as pallet_marketplace;
+use crittercraft_traits::{nft::PetMinterProvider, LockerId, PetId, SharedNftManager};
+use frame_support::{
+    dispatch::DispatchResult,
+    parameter_types,
+    traits::{ConstU32, ConstU64},
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, Perbill,
+};
+use sp_std::vec::Vec;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Marketplace: pallet_marketplace,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = u128;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = frame_support::traits::ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+parameter_types! {
+    /// Each pet's owner; pets not listed here do not exist.
+    pub static MockPetOwners: Vec<(PetId, u64)> = vec![(1, ALICE), (2, ALICE), (3, BOB)];
+    /// The account each pet was minted to; pet 2's minter was never recorded.
+    pub static MockPetMinters: Vec<(PetId, u64)> = vec![(1, ALICE), (3, BOB)];
+    /// The pets locked, and by whom.
+    pub static MockLocks: Vec<(PetId, LockerId)> = Vec::new();
+}
+
+// Mock NFT manager which, like the NFT pallet, refuses to move a locked pet
+pub struct MockNftManager;
+impl MockNftManager {
+    pub fn lock_of(pet_id: PetId) -> Option<LockerId> {
+        MockLocks::get().into_iter().find(|(id, _)| *id == pet_id).map(|(_, locker)| locker)
+    }
+}
+impl SharedNftManager<u64, PetId> for MockNftManager {
+    fn owner_of(token_id: &PetId) -> Option<u64> {
+        MockPetOwners::get().into_iter().find(|(id, _)| id == token_id).map(|(_, owner)| owner)
+    }
+    fn is_transferable(token_id: &PetId) -> bool {
+        Self::lock_of(*token_id).is_none()
+    }
+    fn lock_nft(_owner: &u64, token_id: &PetId, locker: LockerId) -> DispatchResult {
+        if Self::lock_of(*token_id).is_some() {
+            return Err(DispatchError::Other("pet locked"));
+        }
+        MockLocks::mutate(|locks| locks.push((*token_id, locker)));
+        Ok(())
+    }
+    fn unlock_nft(_owner: &u64, token_id: &PetId, locker: LockerId) -> DispatchResult {
+        if Self::lock_of(*token_id) != Some(locker) {
+            return Err(DispatchError::Other("not the lock holder"));
+        }
+        MockLocks::mutate(|locks| locks.retain(|(id, _)| id != token_id));
+        Ok(())
+    }
+    fn transfer_nft(_from: &u64, to: &u64, token_id: &PetId) -> DispatchResult {
+        if Self::lock_of(*token_id).is_some() {
+            return Err(DispatchError::Other("pet locked"));
+        }
+        MockPetOwners::mutate(|owners| {
+            owners.iter_mut().filter(|(id, _)| id == token_id).for_each(|(_, owner)| *owner = *to)
+        });
+        Ok(())
+    }
+    fn can_receive_pet(_account: &u64) -> bool { true }
+    fn remaining_capacity(_account: &u64) -> u32 { u32::MAX }
+}
+
+impl PetMinterProvider<u64, PetId> for MockNftManager {
+    fn minter_of(pet_id: &PetId) -> Option<u64> {
+        MockPetMinters::get().into_iter().find(|(id, _)| id == pet_id).map(|(_, minter)| minter)
+    }
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+
+/// The ecosystem treasury account, which starts with the existential deposit.
+pub const ECOSYSTEM_TREASURY: u64 = 6;
+
+parameter_types! {
+    pub const MarketplaceFee: Perbill = Perbill::from_percent(5);
+    pub const EcosystemTreasury: u64 = ECOSYSTEM_TREASURY;
+    pub const MaxRoyalty: Perbill = Perbill::from_percent(10);
+}
+
+impl pallet_marketplace::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type NftManager = MockNftManager;
+    type PetMinters = MockNftManager;
+    type MarketplaceFee = MarketplaceFee;
+    type EcosystemTreasury = EcosystemTreasury;
+    type MaxRoyalty = MaxRoyalty;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 1000), (ECOSYSTEM_TREASURY, 1)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,161 @@
+use crate::{mock::*, Error, Event, Listing};
+use crittercraft_traits::{LockerId, SharedNftManager};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Perbill;
+
+#[test]
+fn listing_locks_the_pet() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 200));
+
+        assert_eq!(
+            Marketplace::listings(1),
+            Some(Listing { seller: ALICE, pet_id: 1, price: 200, listed_at: 5 })
+        );
+        assert_eq!(MockNftManager::lock_of(1), Some(LockerId::Marketplace));
+        System::assert_last_event(Event::PetListed { seller: ALICE, pet_id: 1, price: 200 }.into());
+    });
+}
+
+#[test]
+fn only_the_owner_can_list_an_unlocked_pet() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Marketplace::list_pet(RuntimeOrigin::signed(BOB), 1, 200), Error::<Test>::NotPetOwner);
+        assert_noop!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 9, 200), Error::<Test>::PetNotFound);
+        assert_noop!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 0), Error::<Test>::ZeroPrice);
+
+        assert_ok!(MockNftManager::lock_nft(&ALICE, &2, LockerId::Battle));
+        assert_noop!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 2, 200), Error::<Test>::PetNotTransferable);
+
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 200));
+        assert_noop!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 300), Error::<Test>::AlreadyListed);
+    });
+}
+
+#[test]
+fn delisting_unlocks_the_pet() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 200));
+        assert_noop!(Marketplace::delist_pet(RuntimeOrigin::signed(BOB), 1), Error::<Test>::NotSeller);
+
+        assert_ok!(Marketplace::delist_pet(RuntimeOrigin::signed(ALICE), 1));
+
+        assert!(Marketplace::listings(1).is_none());
+        assert!(MockNftManager::is_transferable(&1));
+        System::assert_last_event(Event::PetDelisted { seller: ALICE, pet_id: 1 }.into());
+        assert_noop!(Marketplace::delist_pet(RuntimeOrigin::signed(ALICE), 1), Error::<Test>::ListingNotFound);
+    });
+}
+
+#[test]
+fn buying_pays_the_seller_and_the_treasury_and_transfers_the_pet() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 200));
+
+        assert_ok!(Marketplace::buy_pet(RuntimeOrigin::signed(BOB), 1));
+
+        // A 5% fee of 10, the rest to the seller
+        assert_eq!(Balances::free_balance(BOB), 800);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 1 + 10);
+        assert_eq!(Balances::free_balance(ALICE), 1000 + 190);
+        assert_eq!(MockNftManager::owner_of(&1), Some(BOB));
+        assert!(MockNftManager::is_transferable(&1));
+        assert!(Marketplace::listings(1).is_none());
+        System::assert_last_event(
+            Event::PetSold { buyer: BOB, seller: ALICE, pet_id: 1, price: 200, royalty_paid: 0, fee_paid: 10 }.into(),
+        );
+    });
+}
+
+#[test]
+fn buying_pays_the_royalty_out_of_the_proceeds() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 1, Perbill::from_percent(10)));
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 200));
+        assert_ok!(Marketplace::buy_pet(RuntimeOrigin::signed(BOB), 1));
+
+        // The minter selling their own pet pays no royalty to themselves
+        assert_eq!(Balances::free_balance(ALICE), 1000 + 190);
+
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(BOB), 1, 200));
+        assert_ok!(Marketplace::buy_pet(RuntimeOrigin::signed(CHARLIE), 1));
+
+        // 10 to the treasury, 10% of the remaining 190 to the minter, 171 to the seller
+        assert_eq!(Balances::free_balance(CHARLIE), 800);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 1 + 10 + 10);
+        assert_eq!(Balances::free_balance(ALICE), 1000 + 190 + 19);
+        assert_eq!(Balances::free_balance(BOB), 800 + 171);
+        System::assert_last_event(
+            Event::PetSold { buyer: CHARLIE, seller: BOB, pet_id: 1, price: 200, royalty_paid: 19, fee_paid: 10 }.into(),
+        );
+    });
+}
+
+#[test]
+fn failed_purchase_changes_nothing() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 2000));
+
+        assert!(Marketplace::buy_pet(RuntimeOrigin::signed(BOB), 1).is_err());
+        assert_noop!(Marketplace::buy_pet(RuntimeOrigin::signed(ALICE), 1), Error::<Test>::BuyerIsSeller);
+        assert_noop!(Marketplace::buy_pet(RuntimeOrigin::signed(BOB), 2), Error::<Test>::ListingNotFound);
+
+        assert_eq!(Balances::free_balance(BOB), 1000);
+        assert_eq!(Balances::free_balance(ECOSYSTEM_TREASURY), 1);
+        assert_eq!(MockNftManager::owner_of(&1), Some(ALICE));
+        assert!(Marketplace::listings(1).is_some());
+    });
+}
+
+#[test]
+fn royalty_is_set_once_by_the_minter_up_to_the_cap() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Marketplace::set_pet_royalty(RuntimeOrigin::signed(BOB), 1, Perbill::from_percent(5)),
+            Error::<Test>::NotPetMinter
+        );
+        assert_noop!(
+            Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 9, Perbill::from_percent(5)),
+            Error::<Test>::PetNotFound
+        );
+        assert_noop!(
+            Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 1, Perbill::from_percent(11)),
+            Error::<Test>::RoyaltyTooHigh
+        );
+
+        assert_ok!(Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 1, Perbill::from_percent(5)));
+        assert_eq!(Marketplace::pet_royalty(1), Some((ALICE, Perbill::from_percent(5))));
+        System::assert_last_event(
+            Event::PetRoyaltySet { pet_id: 1, recipient: ALICE, royalty: Perbill::from_percent(5) }.into(),
+        );
+
+        assert_noop!(
+            Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 1, Perbill::from_percent(1)),
+            Error::<Test>::RoyaltyAlreadySet
+        );
+    });
+}
+
+#[test]
+fn royalty_follows_the_minter_not_the_owner() {
+    new_test_ext().execute_with(|| {
+        // A later owner cannot name themselves the recipient of a pet they bought.
+        assert_ok!(Marketplace::list_pet(RuntimeOrigin::signed(ALICE), 1, 200));
+        assert_ok!(Marketplace::buy_pet(RuntimeOrigin::signed(BOB), 1));
+        assert_noop!(
+            Marketplace::set_pet_royalty(RuntimeOrigin::signed(BOB), 1, Perbill::from_percent(10)),
+            Error::<Test>::NotPetMinter
+        );
+
+        // The minter still can, after the sale.
+        assert_ok!(Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 1, Perbill::from_percent(10)));
+        assert_eq!(Marketplace::pet_royalty(1), Some((ALICE, Perbill::from_percent(10))));
+
+        // A pet whose minter was never recorded cannot carry a royalty, even for its owner.
+        assert_noop!(
+            Marketplace::set_pet_royalty(RuntimeOrigin::signed(ALICE), 2, Perbill::from_percent(5)),
+            Error::<Test>::NotPetMinter
+        );
+    });
+}
@@ -9,11 +9,38 @@
 //! It uses a `ScoreContributor` trait to aggregate metrics from any number of
 //! external sources, allowing for a flexible and maintainable progression system
 //! that can evolve without changes to this core pallet's logic.
+//!
+//! pallet-critter-nfts contributes its pets' levels and achievements, and pallet-critter-battle
+//! its pets' battle record. A runtime lists them, with any other sources, as a tuple:
+//! `type ScoreContributors = (CritterNfts, pallet_critter_battle::BattleRecordScore<Runtime, OwnedPets>);`
+//!
+//! ## Activity
+//!
+//! Other pallets report user activity through the `ActivityReporter` trait, tagging it with
+//! an `ActivitySource`. Every report re-aggregates the user's score, but only sources in the
+//! runtime's `CountedActivitySources` bitmask move `last_active_block`, so each deployment
+//! decides what counts as being active. Reports are rate-limited to one profile write per
+//! user per block; the only exception is a counted report following an uncounted one in the
+//! same block, so the user is still marked active.
+//!
+//! ## Decay
+//!
+//! Runtimes can opt into score decay with a non-zero `DecayPeriod`: every full period since a
+//! user's `last_active_block` takes `DecayPercent` off their score, down to `ScoreFloor`. Decay
+//! is lazy, with no block work: `effective_score` computes it on read, and `decay_score` or any
+//! uncounted activity report persists it and emits `ProfileScoreDecayed`. `DecayedPeriods`
+//! records how many periods the stored score has already lost, so none is applied twice.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 // --- Trait Definition for Score Contributors ---
 
 /// A generic trait that other pallets implement to contribute to the overall user score.
@@ -31,25 +58,65 @@ impl<AccountId> ScoreContributor<AccountId> for () {
     }
 }
 
-/// Implementation for a tuple of contributors.
-/// (S) - This allows the runtime to be configured with any number of score sources.
-impl<AccountId, A, B> ScoreContributor<AccountId> for (A, B)
-where
-    A: ScoreContributor<AccountId>,
-    B: ScoreContributor<AccountId>,
-{
-    fn get_score_contribution(user: &AccountId) -> u64 {
-        A::get_score_contribution(user).saturating_add(B::get_score_contribution(user))
-    }
+/// Implements `ScoreContributor` for a tuple of the given contributors and for each shorter tuple
+/// of its trailing elements, summing the elements' contributions with saturating addition.
+macro_rules! impl_score_contributors {
+    () => {};
+    ($first:ident $(, $rest:ident)*) => {
+        impl<AccountId, $first $(, $rest)*> ScoreContributor<AccountId> for ($first, $($rest,)*)
+        where
+            $first: ScoreContributor<AccountId>,
+            $($rest: ScoreContributor<AccountId>,)*
+        {
+            fn get_score_contribution(user: &AccountId) -> u64 {
+                $first::get_score_contribution(user)
+                    $(.saturating_add($rest::get_score_contribution(user)))*
+            }
+        }
+
+        impl_score_contributors!($($rest),*);
+    };
+}
+
+// (S) - This allows the runtime to be configured with up to 12 score sources in a flat tuple,
+// e.g. `(Quests, Battles, NftLevels, Items, Guilds)`. Nest tuples for more.
+impl_score_contributors!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+// --- Activity Reporting ---
+
+/// Where a user's activity came from: one bit of the `CountedActivitySources` bitmask.
+pub type ActivitySource = u8;
+
+/// Gameplay: caring for, battling and breeding pets.
+pub const ACTIVITY_GAMEPLAY: ActivitySource = 1 << 0;
+/// Daily reward claims.
+pub const ACTIVITY_DAILY_CLAIM: ActivitySource = 1 << 1;
+/// Social interactions with other users and their pets.
+pub const ACTIVITY_SOCIAL: ActivitySource = 1 << 2;
+/// Marketplace trading.
+pub const ACTIVITY_MARKETPLACE: ActivitySource = 1 << 3;
+/// Every activity source.
+pub const ALL_ACTIVITY_SOURCES: ActivitySource = ActivitySource::MAX;
+
+/// The trait other pallets use to report user activity, instead of calling into the profile
+/// pallet directly.
+pub trait ActivityReporter<AccountId> {
+    /// Notes that `user` was active through `source`.
+    fn note_activity(user: &AccountId, source: ActivitySource);
+}
+
+/// A no-op implementation for runtimes without a profile pallet.
+impl<AccountId> ActivityReporter<AccountId> for () {
+    fn note_activity(_user: &AccountId, _source: ActivitySource) {}
 }
-// Note: This can be extended with a macro for larger tuples if needed.
 
 #[frame_support::pallet]
 pub mod pallet {
-    use super::ScoreContributor;
+    use super::{ActivityReporter, ActivitySource, ScoreContributor, ACTIVITY_GAMEPLAY};
     use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
     use frame_system::pallet_prelude::*;
     use scale_info::TypeInfo;
+    use sp_runtime::{traits::{Saturating, Zero}, PerThing, Perbill, Percent, SaturatedConversion};
 
     pub type ScoreValue = u64;
 
@@ -68,6 +135,23 @@ pub mod pallet {
         /// (S) A tuple of all pallets that can contribute to the user's score.
         /// Example in runtime: `type ScoreContributors = (Quests, Battles, NftLevels);`
         type ScoreContributors: ScoreContributor<Self::AccountId>;
+
+        /// The `ActivitySource` bits that mark a user active by moving `last_active_block`.
+        /// Activity from other sources still updates the score.
+        #[pallet::constant]
+        type CountedActivitySources: Get<ActivitySource>;
+
+        /// The blocks of inactivity per score decay step. Zero disables decay.
+        #[pallet::constant]
+        type DecayPeriod: Get<BlockNumberFor<Self>>;
+
+        /// The share of the score lost per full `DecayPeriod` of inactivity.
+        #[pallet::constant]
+        type DecayPercent: Get<Percent>;
+
+        /// The score decay never takes a user below. Scores already below it do not decay.
+        #[pallet::constant]
+        type ScoreFloor: Get<ScoreValue>;
     }
 
     #[pallet::pallet]
@@ -84,11 +168,26 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn activity_noted_at)]
+    /// Stores the block of each user's latest profile write from an activity report, for
+    /// rate-limiting reports.
+    pub type ActivityNotedAt<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn decayed_periods)]
+    /// Stores how many decay periods since each user's `last_active_block` their stored score has
+    /// already lost. Cleared when the user is active again.
+    pub type DecayedPeriods<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// A user's profile score has been updated. [user, new_overall_score]
         ProfileScoreUpdated { user: T::AccountId, new_score: ScoreValue },
+
+        /// An inactive user's score decayed by `periods` decay periods. [user, old_score, new_score, periods]
+        ProfileScoreDecayed { user: T::AccountId, old_score: ScoreValue, new_score: ScoreValue, periods: u32 },
     }
 
     #[pallet::error]
@@ -109,20 +208,142 @@ pub mod pallet {
         /// (I) - A single, intuitive entry point for other pallets to trigger a score update.
         /// This is the *only* function other pallets need to know about.
         pub fn trigger_score_update(user: &T::AccountId) -> DispatchResult {
-            let new_score = T::ScoreContributors::get_score_contribution(user);
+            Self::update_profile_score(user);
+            Ok(())
+        }
+
+        /// Re-aggregates the user's score from all contributors and stores it, marking the user
+        /// active as for gameplay. See `update_score_from`.
+        pub fn update_profile_score(user: &T::AccountId) -> ScoreValue {
+            Self::update_score_from(user, ACTIVITY_GAMEPLAY)
+        }
+
+        /// Re-aggregates the user's score from all contributors and stores it.
+        /// The user is marked active at the current block if any of `sources` is in
+        /// `CountedActivitySources`, but `ProfileScoreUpdated` is only emitted when the score
+        /// actually changed. Otherwise the score is decayed for the user's inactivity.
+        /// A user without a profile gets one created through `ValueQuery`.
+        pub fn update_score_from(user: &T::AccountId, sources: ActivitySource) -> ScoreValue {
+            // 1. Aggregate the score. The tuple implementations saturate on overflow.
+            let aggregate = T::ScoreContributors::get_score_contribution(user);
             let current_block = frame_system::Pallet::<T>::block_number();
+            let counted = sources & T::CountedActivitySources::get() != 0;
+
+            // 2. Decay it for every full period an uncounted report leaves the user inactive.
+            let periods = if counted {
+                0
+            } else {
+                Self::inactive_periods(UserProfiles::<T>::get(user).last_active_block)
+            };
+            let new_score = Self::decay(aggregate, periods);
+            if periods.is_zero() {
+                DecayedPeriods::<T>::remove(user);
+            } else {
+                DecayedPeriods::<T>::insert(user, periods);
+            }
+            if new_score != aggregate {
+                Self::deposit_event(Event::ProfileScoreDecayed {
+                    user: user.clone(),
+                    old_score: aggregate,
+                    new_score,
+                    periods,
+                });
+            }
 
-            UserProfiles::<T>::mutate(user, |profile| {
+            // 3. Write the profile, remembering whether the score moved.
+            let changed = UserProfiles::<T>::mutate(user, |profile| {
+                let changed = profile.overall_score != new_score;
                 profile.overall_score = new_score;
-                profile.last_active_block = current_block;
+                if counted {
+                    profile.last_active_block = current_block;
+                }
+                changed
             });
 
-            Self::deposit_event(Event::ProfileScoreUpdated {
-                user: user.clone(),
-                new_score,
-            });
+            // 4. Only announce real changes.
+            if changed {
+                Self::deposit_event(Event::ProfileScoreUpdated {
+                    user: user.clone(),
+                    new_score,
+                });
+            }
 
-            Ok(())
+            new_score
+        }
+
+        /// Returns the user's last stored score, or zero if they have no profile yet.
+        pub fn score_of(user: &T::AccountId) -> ScoreValue {
+            UserProfiles::<T>::get(user).overall_score
+        }
+
+        /// Returns the user's stored score with any decay owed since it was stored, without
+        /// writing it. Other pallets should rank users by this rather than `score_of`.
+        pub fn effective_score(user: &T::AccountId) -> ScoreValue {
+            let profile = UserProfiles::<T>::get(user);
+            let owed = Self::inactive_periods(profile.last_active_block).saturating_sub(DecayedPeriods::<T>::get(user));
+            Self::decay(profile.overall_score, owed)
+        }
+
+        /// Persists the user's `effective_score`, emitting `ProfileScoreDecayed` if it decayed.
+        pub fn decay_score(user: &T::AccountId) -> ScoreValue {
+            let profile = UserProfiles::<T>::get(user);
+            let periods = Self::inactive_periods(profile.last_active_block);
+            let owed = periods.saturating_sub(DecayedPeriods::<T>::get(user));
+            if owed.is_zero() {
+                return profile.overall_score;
+            }
+
+            let old_score = profile.overall_score;
+            let new_score = Self::decay(old_score, owed);
+            DecayedPeriods::<T>::insert(user, periods);
+            if new_score != old_score {
+                UserProfiles::<T>::mutate(user, |profile| profile.overall_score = new_score);
+                Self::deposit_event(Event::ProfileScoreDecayed {
+                    user: user.clone(),
+                    old_score,
+                    new_score,
+                    periods: owed,
+                });
+            }
+            new_score
+        }
+
+        /// The full decay periods since `last_active_block`, or zero with decay disabled.
+        fn inactive_periods(last_active_block: BlockNumberFor<T>) -> u32 {
+            let period = T::DecayPeriod::get();
+            if period.is_zero() {
+                return 0;
+            }
+            let inactive = frame_system::Pallet::<T>::block_number().saturating_sub(last_active_block);
+            (inactive / period).saturated_into()
+        }
+
+        /// Takes `DecayPercent` off `score` once per period, compounding, but never below
+        /// `ScoreFloor`. A score already below the floor is kept.
+        fn decay(score: ScoreValue, periods: u32) -> ScoreValue {
+            if periods.is_zero() {
+                return score;
+            }
+            let retained = Perbill::from_percent(Percent::one().saturating_sub(T::DecayPercent::get()).deconstruct().into());
+            let decayed = retained.saturating_pow(periods as usize).mul_floor(score);
+            decayed.max(score.min(T::ScoreFloor::get()))
+        }
+    }
+
+    impl<T: Config> ActivityReporter<T::AccountId> for Pallet<T> {
+        /// Updates the user's score from `source`, at most once per user per block. A counted
+        /// report still goes through after an uncounted one in the same block, so the user is
+        /// marked active.
+        fn note_activity(user: &T::AccountId, source: ActivitySource) {
+            let current_block = frame_system::Pallet::<T>::block_number();
+            if ActivityNotedAt::<T>::get(user) == Some(current_block) {
+                let counted = source & T::CountedActivitySources::get() != 0;
+                if !counted || UserProfiles::<T>::get(user).last_active_block == current_block {
+                    return;
+                }
+            }
+            ActivityNotedAt::<T>::insert(user, current_block);
+            Self::update_score_from(user, source);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,96 @@
+use crate as pallet_user_profile;
+use crate::{ScoreContributor, ACTIVITY_GAMEPLAY};
+use frame_support::{parameter_types, traits::{ConstU32, ConstU64}};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage, Percent,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        UserProfile: pallet_user_profile,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub static QuestScore: u64 = 0;
+    pub static BattleScore: u64 = 0;
+    pub static CountedActivitySources: u8 = ACTIVITY_GAMEPLAY;
+    // Decay is off unless a test sets a period.
+    pub static DecayPeriod: u64 = 0;
+    pub static DecayPercent: Percent = Percent::from_percent(10);
+    pub static ScoreFloor: u64 = 0;
+}
+
+// Mock score sources, each returning the same score for every user.
+pub struct MockQuests;
+impl ScoreContributor<u64> for MockQuests {
+    fn get_score_contribution(_user: &u64) -> u64 {
+        QuestScore::get()
+    }
+}
+
+pub struct MockBattles;
+impl ScoreContributor<u64> for MockBattles {
+    fn get_score_contribution(_user: &u64) -> u64 {
+        BattleScore::get()
+    }
+}
+
+/// A score source contributing `N` for every user.
+pub struct FixedScore<const N: u64>;
+impl<const N: u64> ScoreContributor<u64> for FixedScore<N> {
+    fn get_score_contribution(_user: &u64) -> u64 {
+        N
+    }
+}
+
+impl pallet_user_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type ScoreContributors = (MockQuests, MockBattles);
+    type CountedActivitySources = CountedActivitySources;
+    type DecayPeriod = DecayPeriod;
+    type DecayPercent = DecayPercent;
+    type ScoreFloor = ScoreFloor;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    // Events are only recorded from block 1 onwards.
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
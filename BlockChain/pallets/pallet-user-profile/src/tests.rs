@@ -0,0 +1,282 @@
+use crate::{mock::*, ActivityReporter, DecayedPeriods, Event, ScoreContributor, UserProfiles, ACTIVITY_DAILY_CLAIM, ACTIVITY_GAMEPLAY};
+
+fn score_updated_events() -> usize {
+    System::events()
+        .iter()
+        .filter(|record| matches!(record.event, RuntimeEvent::UserProfile(Event::ProfileScoreUpdated { .. })))
+        .count()
+}
+
+#[test]
+fn first_update_creates_profile() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(30);
+        BattleScore::set(12);
+        assert!(!UserProfiles::<Test>::contains_key(1));
+
+        assert_eq!(UserProfile::update_profile_score(&1), 42);
+
+        let profile = UserProfiles::<Test>::get(1);
+        assert_eq!(profile.overall_score, 42);
+        assert_eq!(profile.last_active_block, 1);
+        assert_eq!(UserProfile::score_of(&1), 42);
+        System::assert_last_event(RuntimeEvent::UserProfile(Event::ProfileScoreUpdated { user: 1, new_score: 42 }));
+    });
+}
+
+#[test]
+fn unchanged_score_emits_no_event() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(10);
+        UserProfile::update_profile_score(&1);
+        assert_eq!(score_updated_events(), 1);
+
+        UserProfile::update_profile_score(&1);
+        assert_eq!(score_updated_events(), 1);
+
+        QuestScore::set(11);
+        assert_eq!(UserProfile::update_profile_score(&1), 11);
+        assert_eq!(score_updated_events(), 2);
+    });
+}
+
+#[test]
+fn unchanged_score_still_marks_user_active() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(10);
+        UserProfile::update_profile_score(&1);
+
+        System::set_block_number(20);
+        UserProfile::update_profile_score(&1);
+
+        assert_eq!(UserProfiles::<Test>::get(1).last_active_block, 20);
+        assert_eq!(score_updated_events(), 1);
+    });
+}
+
+#[test]
+fn contributions_saturate() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(u64::MAX);
+        BattleScore::set(5);
+
+        assert_eq!(UserProfile::update_profile_score(&1), u64::MAX);
+        assert_eq!(UserProfile::score_of(&1), u64::MAX);
+    });
+}
+
+#[test]
+fn score_of_is_zero_without_profile() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(UserProfile::score_of(&7), 0);
+    });
+}
+
+#[test]
+fn only_counted_sources_mark_user_active() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(10);
+        UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        assert_eq!(UserProfiles::<Test>::get(1).overall_score, 10);
+        assert_eq!(UserProfiles::<Test>::get(1).last_active_block, 0);
+
+        UserProfile::note_activity(&2, ACTIVITY_GAMEPLAY);
+        assert_eq!(UserProfiles::<Test>::get(2).last_active_block, 1);
+
+        // Deployments choose what counts.
+        CountedActivitySources::set(ACTIVITY_GAMEPLAY | ACTIVITY_DAILY_CLAIM);
+        System::set_block_number(2);
+        UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        assert_eq!(UserProfiles::<Test>::get(1).last_active_block, 2);
+    });
+}
+
+#[test]
+fn activity_reports_write_once_per_user_per_block() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(10);
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+        QuestScore::set(20);
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+        UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        assert_eq!(UserProfile::score_of(&1), 10);
+        assert_eq!(score_updated_events(), 1);
+
+        // Other users have their own limit, and it resets every block.
+        UserProfile::note_activity(&2, ACTIVITY_GAMEPLAY);
+        assert_eq!(UserProfile::score_of(&2), 20);
+        System::set_block_number(2);
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+        assert_eq!(UserProfile::score_of(&1), 20);
+    });
+}
+
+#[test]
+fn counted_report_after_uncounted_one_still_marks_user_active() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+        assert_eq!(UserProfiles::<Test>::get(1).last_active_block, 5);
+        assert_eq!(UserProfile::activity_noted_at(1), Some(5));
+    });
+}
+
+#[test]
+fn uncounted_activity_does_not_reset_inactivity() {
+    new_test_ext().execute_with(|| {
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+
+        // Only claiming for a while leaves the user inactive since block 1.
+        for block in 2..=50 {
+            System::set_block_number(block);
+            UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        }
+        assert_eq!(UserProfiles::<Test>::get(1).last_active_block, 1);
+
+        // Playing again resets it.
+        System::set_block_number(51);
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+        assert_eq!(UserProfiles::<Test>::get(1).last_active_block, 51);
+    });
+}
+
+/// Decays 10% per 10 blocks of inactivity, from a score of 100 earned at block 1.
+fn decaying_user() {
+    DecayPeriod::set(10);
+    QuestScore::set(100);
+    UserProfile::update_profile_score(&1);
+}
+
+#[test]
+fn decay_is_off_without_a_period() {
+    new_test_ext().execute_with(|| {
+        QuestScore::set(100);
+        UserProfile::update_profile_score(&1);
+
+        System::set_block_number(10_000);
+        assert_eq!(UserProfile::effective_score(&1), 100);
+        assert_eq!(UserProfile::decay_score(&1), 100);
+        UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        assert_eq!(UserProfile::score_of(&1), 100);
+    });
+}
+
+#[test]
+fn scores_decay_per_full_period_of_inactivity() {
+    new_test_ext().execute_with(|| {
+        decaying_user();
+
+        System::set_block_number(10);
+        assert_eq!(UserProfile::effective_score(&1), 100);
+        System::set_block_number(11);
+        assert_eq!(UserProfile::effective_score(&1), 90);
+
+        // Reads do not write; persisting compounds the three periods once.
+        System::set_block_number(31);
+        assert_eq!(UserProfile::effective_score(&1), 72);
+        assert_eq!(UserProfile::score_of(&1), 100);
+        assert_eq!(UserProfile::decay_score(&1), 72);
+        System::assert_last_event(RuntimeEvent::UserProfile(Event::ProfileScoreDecayed {
+            user: 1,
+            old_score: 100,
+            new_score: 72,
+            periods: 3,
+        }));
+        assert_eq!(UserProfile::score_of(&1), 72);
+        assert_eq!(UserProfile::decay_score(&1), 72);
+        assert_eq!(UserProfile::effective_score(&1), 72);
+
+        // Only the periods since are owed later.
+        System::set_block_number(41);
+        assert_eq!(UserProfile::decay_score(&1), 64);
+        System::assert_last_event(RuntimeEvent::UserProfile(Event::ProfileScoreDecayed {
+            user: 1,
+            old_score: 72,
+            new_score: 64,
+            periods: 1,
+        }));
+    });
+}
+
+#[test]
+fn decay_stops_at_the_floor() {
+    new_test_ext().execute_with(|| {
+        decaying_user();
+        ScoreFloor::set(50);
+
+        System::set_block_number(1_000_000);
+        assert_eq!(UserProfile::effective_score(&1), 50);
+        assert_eq!(UserProfile::decay_score(&1), 50);
+
+        // A score already below the floor is left alone.
+        QuestScore::set(20);
+        UserProfile::update_profile_score(&2);
+        System::set_block_number(2_000_000);
+        assert_eq!(UserProfile::effective_score(&2), 20);
+    });
+}
+
+#[test]
+fn uncounted_activity_keeps_decay_and_counted_activity_restores_the_score() {
+    new_test_ext().execute_with(|| {
+        decaying_user();
+
+        // Claiming alone re-aggregates the score but leaves the user inactive.
+        System::set_block_number(31);
+        UserProfile::note_activity(&1, ACTIVITY_DAILY_CLAIM);
+        assert_eq!(UserProfile::score_of(&1), 72);
+        assert_eq!(UserProfile::decayed_periods(1), 3);
+        assert_eq!(UserProfile::effective_score(&1), 72);
+
+        System::set_block_number(32);
+        UserProfile::note_activity(&1, ACTIVITY_GAMEPLAY);
+        assert_eq!(UserProfile::score_of(&1), 100);
+        assert!(!DecayedPeriods::<Test>::contains_key(1));
+    });
+}
+
+fn score_of<S: ScoreContributor<u64>>() -> u64 {
+    S::get_score_contribution(&1)
+}
+
+#[test]
+fn every_tuple_size_sums_its_contributors() {
+    assert_eq!(score_of::<(FixedScore<1>,)>(), 1);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>)>(), 3);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>)>(), 6);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>)>(), 10);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>)>(), 15);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>)>(), 21);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>, FixedScore<7>)>(), 28);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>, FixedScore<7>, FixedScore<8>)>(), 36);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>, FixedScore<7>, FixedScore<8>, FixedScore<9>)>(), 45);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>, FixedScore<7>, FixedScore<8>, FixedScore<9>, FixedScore<10>)>(), 55);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>, FixedScore<7>, FixedScore<8>, FixedScore<9>, FixedScore<10>, FixedScore<11>)>(), 66);
+    assert_eq!(score_of::<(FixedScore<1>, FixedScore<2>, FixedScore<3>, FixedScore<4>, FixedScore<5>, FixedScore<6>, FixedScore<7>, FixedScore<8>, FixedScore<9>, FixedScore<10>, FixedScore<11>, FixedScore<12>)>(), 78);
+
+    // Nested tuples still work.
+    assert_eq!(score_of::<((FixedScore<1>, FixedScore<2>), FixedScore<3>)>(), 6);
+}
+
+#[test]
+fn tuple_contributions_saturate() {
+    assert_eq!(score_of::<(FixedScore<{ u64::MAX }>, FixedScore<1>)>(), u64::MAX);
+    assert_eq!(
+        score_of::<(
+            FixedScore<1>,
+            FixedScore<2>,
+            FixedScore<3>,
+            FixedScore<4>,
+            FixedScore<5>,
+            FixedScore<6>,
+            FixedScore<7>,
+            FixedScore<8>,
+            FixedScore<9>,
+            FixedScore<10>,
+            FixedScore<11>,
+            FixedScore<{ u64::MAX - 1 }>,
+        )>(),
+        u64::MAX
+    );
+}
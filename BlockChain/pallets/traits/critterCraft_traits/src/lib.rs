@@ -77,6 +77,8 @@ pub struct SimpleGeneticInfo<DnaHash, Species> {
     pub dna_hash: DnaHash,
     /// The species of the pet
     pub species: Species,
+    /// The pet's generation: 0 for minted pets, one more than the later parent for bred pets
+    pub generation: u32,
 }
 
 /// Pet attribute type for minigames and jobs
@@ -112,6 +114,55 @@ pub struct EnhancedPetInfo<AccountId, BlockNumber> {
     pub is_locked: bool,
 }
 
+// --- Cross-Pallet Call Errors ---
+
+/// Why a care item could not be consumed, so the calling pallet can report the real cause.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CareError {
+    /// The item does not exist
+    ItemNotFound,
+    /// The item is not of the requested category (e.g., a toy used as food)
+    WrongCategory,
+    /// The account does not hold the item
+    NotInInventory,
+    /// Any other failure of the item pallet
+    ConsumptionFailed,
+}
+
+/// Why a call into the pet NFT pallet failed, so the calling pallet can report the real cause.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NftCallError {
+    /// The pet does not exist
+    PetNotFound,
+    /// The account does not own the pet
+    NotOwner,
+    /// The pet is locked and cannot be changed right now
+    PetLocked,
+    /// Any other failure of the pet NFT pallet
+    Failed,
+}
+
+// --- NFT Locks ---
+
+/// The pallet holding a lock on an NFT. Each integrating pallet locks and unlocks under its
+/// own namespace, so a pallet cannot release a lock it did not take.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum LockerId {
+    /// A marketplace listing
+    Marketplace,
+    /// A battle in progress
+    Battle,
+    /// A pet lease
+    Lease,
+    /// A pending two-party trade
+    Trade,
+    /// Any other locker, identified by a pallet-chosen tag. `Other(0)` marks locks taken
+    /// before lockers were recorded.
+    Other(u8),
+    /// A breeding in progress. Declared last so the encoding of the earlier lockers is kept
+    Breeding,
+}
+
 // --- Core NFT Management Traits ---
 
 /// Core NFT management trait for cross-pallet integration
@@ -122,14 +173,20 @@ pub trait SharedNftManager<AccountId, TokenId> {
     /// Check if an NFT is transferable
     fn is_transferable(token_id: &TokenId) -> bool;
     
-    /// Lock an NFT to prevent transfers
-    fn lock_nft(owner: &AccountId, token_id: &TokenId) -> DispatchResult;
+    /// Lock an NFT to prevent transfers, recording `locker` as the lock holder
+    fn lock_nft(owner: &AccountId, token_id: &TokenId, locker: LockerId) -> DispatchResult;
     
-    /// Unlock a previously locked NFT
-    fn unlock_nft(owner: &AccountId, token_id: &TokenId) -> DispatchResult;
+    /// Unlock a previously locked NFT. Fails unless `locker` holds the lock
+    fn unlock_nft(owner: &AccountId, token_id: &TokenId, locker: LockerId) -> DispatchResult;
     
     /// Transfer an NFT between accounts
     fn transfer_nft(from: &AccountId, to: &AccountId, token_id: &TokenId) -> DispatchResult;
+    
+    /// Check if an account has room for another NFT, before spending resources to give it one
+    fn can_receive_pet(account: &AccountId) -> bool;
+    
+    /// Get how many more NFTs an account can receive
+    fn remaining_capacity(account: &AccountId) -> u32;
 }
 
 /// Extended NFT management for advanced operations
@@ -154,11 +211,11 @@ pub trait NftBreedingHandler<AccountId, TokenId, DnaHash, Species> {
     /// Get basic genetic information for a pet
     fn get_pet_simple_genetics(token_id: &TokenId) -> Option<SimpleGeneticInfo<DnaHash, Species>>;
     
-    /// Mint a new pet from breeding
+    /// Mint a new pet from breeding. The offspring's DNA is derived from its parents' by the
+    /// implementation, not supplied by the caller.
     fn mint_pet_from_breeding(
         owner: &AccountId,
         species: Species,
-        dna_hash: DnaHash,
         parent1_id: TokenId,
         parent2_id: TokenId,
         initial_name: BoundedVec<u8, ConstU32<64>>,
@@ -187,7 +244,8 @@ pub trait AdvancedBreedingMechanics<AccountId, TokenId> {
 
 /// Basic care item consumer for pet interactions
 pub trait BasicCareItemConsumer<AccountId, ItemId, CategoryTag, ResultType> {
-    /// Consume an item of a specific category
+    /// Consume an item of a specific category.
+    /// `ResultType` is the error reported on failure, normally `CareError`.
     fn consume_item_of_category(
         owner: &AccountId,
         item_id: &ItemId,
@@ -227,6 +285,15 @@ pub trait NftManagerForItems<AccountId, TokenId, TraitString, BlockNumber> {
         trait_to_grant: TraitString,
     ) -> DispatchResult;
     
+    /// Apply a personality trait to a pet for `duration_blocks` blocks; re-applying refreshes
+    /// the expiry
+    fn apply_temporary_trait(
+        caller: &AccountId,
+        token_id: &TokenId,
+        trait_to_grant: TraitString,
+        duration_blocks: BlockNumber,
+    ) -> DispatchResult;
+    
     /// Apply a breeding assist effect
     fn apply_breeding_assist_effect(
         caller: &AccountId,
@@ -246,8 +313,18 @@ pub trait QuestNftRequirementChecker<AccountId, TokenId, Species> {
     /// Get the level of a pet for quest requirements
     fn get_pet_level_for_quest(token_id: &TokenId) -> Option<u32>;
     
+    /// Get the effective stats of a pet for quest stat requirements
+    fn get_pet_stats_for_quest(token_id: &TokenId) -> Option<PetStats>;
+    
     /// Get the species of a pet for quest requirements
     fn get_pet_species_for_quest(token_id: &TokenId) -> Option<Species>;
+    
+    /// Count the care actions of a kind (the NFT pallet's `CareActionKind` as a `u8`) a pet
+    /// received in the last `window_epochs` care history epochs, including the current one
+    fn care_actions_in_window(token_id: &TokenId, kind: u8, window_epochs: u32) -> u32;
+    
+    /// Check whether a pet has a personality trait, counting unexpired temporary traits
+    fn pet_has_trait_for_quest(token_id: &TokenId, trait_name: &[u8]) -> bool;
 }
 
 /// Advanced quest interaction for pets
@@ -324,19 +401,19 @@ pub trait AdvancedPetManagement<AccountId, BlockNumber> {
     fn get_enhanced_pet_info(pet_id: &PetId) -> Option<EnhancedPetInfo<AccountId, BlockNumber>>;
     
     /// Update pet stats
-    fn update_pet_stats(pet_id: &PetId, stats: PetStats) -> DispatchResult;
+    fn update_pet_stats(pet_id: &PetId, stats: PetStats) -> Result<(), NftCallError>;
     
     /// Add experience to a pet
-    fn add_experience(pet_id: &PetId, experience: u32) -> DispatchResult;
+    fn add_experience(pet_id: &PetId, experience: u32) -> Result<(), NftCallError>;
     
     /// Get pet level
-    fn get_pet_level(pet_id: &PetId) -> Option<u16>;
+    fn get_pet_level(pet_id: &PetId) -> Result<u16, NftCallError>;
     
     /// Get pet attributes
-    fn get_pet_attributes(pet_id: &PetId) -> Option<Vec<(AttributeType, u8)>>;
+    fn get_pet_attributes(pet_id: &PetId) -> Result<Vec<(AttributeType, u8)>, NftCallError>;
     
     /// Update pet state
-    fn update_pet_state(pet_id: &PetId) -> DispatchResult;
+    fn update_pet_state(pet_id: &PetId) -> Result<(), NftCallError>;
     
     /// Check if a pet is neglected
     fn is_pet_neglected(pet_id: &PetId) -> bool;
@@ -345,7 +422,7 @@ pub trait AdvancedPetManagement<AccountId, BlockNumber> {
     fn is_eligible_for_evolution(pet_id: &PetId) -> bool;
     
     /// Evolve a pet
-    fn evolve_pet(owner: &AccountId, pet_id: &PetId) -> DispatchResult;
+    fn evolve_pet(owner: &AccountId, pet_id: &PetId) -> Result<(), NftCallError>;
 }
 
 // --- Pet Status Management Traits ---
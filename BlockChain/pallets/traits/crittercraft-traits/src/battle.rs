@@ -10,6 +10,9 @@ pub trait ItemConsumer<T: Config> {
 
 // --- File: traits/src/battle.rs ---
 use super::{types::PetStats, Config};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
 
 /// A trait for providing battle-related information about a pet.
 pub trait BattleReady<T: Config> {
@@ -17,4 +20,30 @@ pub trait BattleReady<T: Config> {
     fn can_battle(pet_id: &T::PetId) -> bool;
     /// Retrieves the stats relevant for a battle encounter.
     fn battle_stats(pet_id: &T::PetId) -> Option<PetStats>;
-}
\ No newline at end of file
+}
+
+/// A pet's lifetime battle results and current rating.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BattleRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub rating: u16,
+}
+
+/// A read-only view of a pet's battle record, used by pallets that rank or value pets.
+pub trait BattleStatsProvider<PetId> {
+    /// The pet's current battle rating, or `None` if it has never battled.
+    fn battle_rating(pet_id: &PetId) -> Option<u16>;
+
+    /// The pet's full battle record, or `None` if it has never battled.
+    fn battle_record(_pet_id: &PetId) -> Option<BattleRecord> {
+        None
+    }
+}
+
+impl<PetId> BattleStatsProvider<PetId> for () {
+    fn battle_rating(_pet_id: &PetId) -> Option<u16> {
+        None
+    }
+}
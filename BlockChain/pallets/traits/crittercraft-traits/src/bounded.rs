@@ -0,0 +1,103 @@
+//! # Rotating Bounded History
+//!
+//! Helpers for bounded lists of history entries (interactions, notifications, battle moves,
+//! hook statistics) that should never block gameplay when full. Instead of failing, a push
+//! into a full list evicts an existing entry so the new one always lands.
+//!
+//! - `push_rotating` evicts the oldest entry (FIFO).
+//! - `push_evicting_by` evicts the entry with the lowest caller-supplied priority, the oldest
+//!   among equals.
+//!
+//! Both cost at most one pass over the list, so their weight is bounded by the list's bound.
+//! Lists whose fullness is a rule of the game (e.g. owned pets) keep using `try_push`.
+
+use frame_support::{traits::Get, BoundedVec};
+
+/// Appends `value`, evicting the oldest entry first if `vec` is full.
+///
+/// Returns the evicted entry, if any. With a bound of zero nothing can be stored, and
+/// `value` itself is returned.
+pub fn push_rotating<T: Get<u32>, V>(vec: &mut BoundedVec<V, T>, value: V) -> Option<V> {
+    push_evicting_at(vec, value, |_| 0)
+}
+
+/// Appends `value`, evicting the entry with the lowest `priority` first if `vec` is full.
+/// Among entries of equal priority the oldest is evicted.
+///
+/// Returns the evicted entry, if any. With a bound of zero nothing can be stored, and
+/// `value` itself is returned.
+pub fn push_evicting_by<T: Get<u32>, V, K: Ord>(
+    vec: &mut BoundedVec<V, T>,
+    value: V,
+    priority: impl Fn(&V) -> K,
+) -> Option<V> {
+    push_evicting_at(vec, value, |vec| {
+        // `min_by_key` returns the first of several equal minimums, i.e. the oldest.
+        vec.iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| priority(entry))
+            .map(|(index, _)| index)
+            .unwrap_or_default()
+    })
+}
+
+/// Appends `value`, first removing the entry at the index chosen by `victim` if `vec` is full.
+fn push_evicting_at<T: Get<u32>, V>(
+    vec: &mut BoundedVec<V, T>,
+    value: V,
+    victim: impl FnOnce(&BoundedVec<V, T>) -> usize,
+) -> Option<V> {
+    if T::get() == 0 {
+        return Some(value);
+    }
+
+    let evicted = if vec.len() >= T::get() as usize {
+        let index = victim(vec);
+        Some(vec.remove(index))
+    } else {
+        None
+    };
+
+    // There is room now, so the push cannot fail.
+    let _ = vec.try_push(value);
+    evicted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::traits::ConstU32;
+
+    #[test]
+    fn push_rotating_evicts_oldest() {
+        let mut vec = BoundedVec::<u32, ConstU32<3>>::default();
+        for value in 1..=3 {
+            assert_eq!(push_rotating(&mut vec, value), None);
+        }
+
+        assert_eq!(push_rotating(&mut vec, 4), Some(1));
+        assert_eq!(push_rotating(&mut vec, 5), Some(2));
+        assert_eq!(vec.into_inner(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn push_evicting_by_evicts_lowest_priority_oldest_first() {
+        // (priority, id)
+        let mut vec = BoundedVec::<(u8, u32), ConstU32<3>>::default();
+        for entry in [(2, 1), (1, 2), (1, 3)] {
+            assert_eq!(push_evicting_by(&mut vec, entry, |(priority, _)| *priority), None);
+        }
+
+        // Entry 2 is the oldest of the lowest priority; the new entry lands even though its
+        // priority is lower still.
+        assert_eq!(push_evicting_by(&mut vec, (0, 4), |(priority, _)| *priority), Some((1, 2)));
+        assert_eq!(vec.into_inner(), vec![(2, 1), (1, 3), (0, 4)]);
+    }
+
+    #[test]
+    fn zero_bound_stores_nothing() {
+        let mut vec = BoundedVec::<u32, ConstU32<0>>::default();
+        assert_eq!(push_rotating(&mut vec, 1), Some(1));
+        assert!(vec.is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+//! # Golden Test Vectors
+//!
+//! Std-only helpers for checking formula outputs against checked-in fixture files, so a formula
+//! cannot change without the fixtures changing with it. A pallet renders each vector as one
+//! line of JSON, inputs and outputs together, and `check_fixture` compares the lines with the
+//! fixture file:
+//!
+//! ```text
+//! [
+//!   {"level":1,"xp":100,"next_level_xp":100},
+//!   {"level":2,"xp":0,"next_level_xp":200}
+//! ]
+//! ```
+//!
+//! `VectorEntropy` gives the vectors pseudo-random inputs from a fixed seed.
+//!
+//! When a formula changes on purpose, rerun the tests with `CRITTERCRAFT_REGENERATE_VECTORS=1`
+//! to rewrite the fixtures, and commit them with the change. Off-chain simulators can vendor
+//! the fixture files and replay them.
+
+use std::path::Path;
+
+/// The environment variable that makes `check_fixture` rewrite fixtures instead of checking them.
+pub const REGENERATE_VAR: &str = "CRITTERCRAFT_REGENERATE_VECTORS";
+
+/// Renders bytes as `0x`-prefixed lowercase hex.
+pub fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+/// Renders vectors, one JSON object per line, as the contents of a fixture file.
+pub fn render(vectors: &[String]) -> String {
+    let mut out = String::from("[\n");
+    for (index, vector) in vectors.iter().enumerate() {
+        out.push_str("  ");
+        out.push_str(vector);
+        if index + 1 < vectors.len() {
+            out.push(',');
+        }
+        out.push('\n');
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Checks `vectors` against the fixture at `path`, or rewrites the fixture when
+/// `REGENERATE_VAR` is set.
+///
+/// # Panics
+///
+/// If the fixture is missing, or a vector differs from the fixture's. The message names the
+/// first differing vector.
+pub fn check_fixture(path: &Path, vectors: &[String]) {
+    let rendered = render(vectors);
+    if std::env::var_os(REGENERATE_VAR).is_some() {
+        std::fs::write(path, rendered)
+            .unwrap_or_else(|error| panic!("cannot write fixture {}: {}", path.display(), error));
+        return;
+    }
+
+    let fixture = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        panic!("cannot read fixture {}: {}; set {} to generate it", path.display(), error, REGENERATE_VAR)
+    });
+    let expected: Vec<&str> = fixture.lines().collect();
+    let actual: Vec<&str> = rendered.lines().collect();
+    if let Some(line) = (0..expected.len().max(actual.len())).find(|&line| expected.get(line) != actual.get(line)) {
+        panic!(
+            "{} drifted at vector {}:\n  fixture: {}\n  actual:  {}\nIf the formula changed on purpose, rerun with {}=1 and commit the fixture.",
+            path.display(),
+            line.saturating_sub(1),
+            expected.get(line).unwrap_or(&"<none>"),
+            actual.get(line).unwrap_or(&"<none>"),
+            REGENERATE_VAR,
+        );
+    }
+}
+
+/// Pseudo-random vector inputs: SplitMix64 from a fixed seed, simple to reproduce off-chain.
+pub struct VectorEntropy(u64);
+
+impl VectorEntropy {
+    /// Starts the sequence at `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// The next value of the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// The next value of the sequence, reduced below `bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// The next 32 bytes of the sequence, each value in little-endian order.
+    pub fn bytes(&mut self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for chunk in bytes.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_one_vector_per_line() {
+        assert_eq!(hex(&[0x00, 0xab, 0xff]), "0x00abff");
+        assert_eq!(render(&[]), "[\n]\n");
+        assert_eq!(render(&["{\"a\":1}".into(), "{\"a\":2}".into()]), "[\n  {\"a\":1},\n  {\"a\":2}\n]\n");
+    }
+
+    #[test]
+    fn vector_entropy_is_splitmix64() {
+        let mut entropy = VectorEntropy::new(0);
+        assert_eq!(entropy.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(entropy.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn check_fixture_reports_the_drifted_vector() {
+        if std::env::var_os(REGENERATE_VAR).is_some() {
+            return;
+        }
+        let path = std::env::temp_dir().join("crittercraft-golden-test.json");
+        std::fs::write(&path, render(&["{\"a\":1}".into(), "{\"a\":2}".into()])).unwrap();
+        check_fixture(&path, &["{\"a\":1}".into(), "{\"a\":2}".into()]);
+
+        let drifted = std::panic::catch_unwind(|| check_fixture(&path, &["{\"a\":1}".into(), "{\"a\":3}".into()]));
+        let message = *drifted.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("drifted at vector 1"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -19,6 +19,11 @@ pub mod battle;
 pub mod daycare;
 pub mod governance;
 pub mod types;
+pub mod valuation;
+pub mod migration;
+pub mod bounded;
+#[cfg(feature = "std")]
+pub mod golden;
 
 /// The core configuration trait that all other traits in this crate depend on.
 /// (S) - This systematizes the entire interface layer. Any pallet wishing to
@@ -0,0 +1,300 @@
+//! # Storage Migration Support
+//!
+//! Shared building blocks for the storage migrations of the CritterCraft pallets.
+//!
+//! - `VersionedMigration` gates a single migration step on the on-chain storage version,
+//!   so every planned migration can live in its own `OnRuntimeUpgrade` struct and be
+//!   queued in the runtime without running twice.
+//! - `StorageFixture` and the `harness` helpers (std only) let a pallet load a serialized
+//!   pre-upgrade storage snapshot into test externalities, run a migration and assert
+//!   post-conditions: every value decodes, counts are preserved and no keys are orphaned.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+    weights::{RuntimeDbWeight, Weight},
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// A single storage migration step from version `FROM` to version `TO`.
+pub trait MigrationStep {
+    /// Performs the migration and returns the weight consumed.
+    fn migrate() -> Weight;
+
+    /// Captures whatever state `post_upgrade_check` needs. Only used by try-runtime.
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade_state() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+        Ok(Vec::new())
+    }
+
+    /// Verifies the migrated state. Only used by try-runtime.
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade_check(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+        Ok(())
+    }
+}
+
+/// Runs `Step` only when the on-chain storage version of `Pallet` equals `FROM`,
+/// then bumps it to `TO`. Any other on-chain version makes this a no-op.
+pub struct VersionedMigration<const FROM: u16, const TO: u16, Step, Pallet, DbWeight>(
+    PhantomData<(Step, Pallet, DbWeight)>,
+);
+
+impl<const FROM: u16, const TO: u16, Step, Pallet, DbWeight> OnRuntimeUpgrade
+    for VersionedMigration<FROM, TO, Step, Pallet, DbWeight>
+where
+    Step: MigrationStep,
+    Pallet: GetStorageVersion<CurrentStorageVersion = StorageVersion> + PalletInfoAccess,
+    DbWeight: frame_support::traits::Get<RuntimeDbWeight>,
+{
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::on_chain_storage_version();
+        if on_chain_version != FROM {
+            frame_support::log::info!(
+                target: "runtime::crittercraft::migration",
+                "{}: skipping migration {} -> {}, on-chain version is {:?}",
+                Pallet::name(),
+                FROM,
+                TO,
+                on_chain_version
+            );
+            return DbWeight::get().reads(1);
+        }
+
+        let weight = Step::migrate();
+        StorageVersion::new(TO).put::<Pallet>();
+
+        frame_support::log::info!(
+            target: "runtime::crittercraft::migration",
+            "{}: migrated storage from version {} to {}",
+            Pallet::name(),
+            FROM,
+            TO
+        );
+
+        weight.saturating_add(DbWeight::get().reads_writes(1, 1))
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+        // Encode whether the step will run so `post_upgrade` only checks what actually happened.
+        let will_run = Pallet::on_chain_storage_version() == FROM;
+        let state = if will_run { Step::pre_upgrade_state()? } else { Vec::new() };
+        Ok((will_run, state).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+        let (did_run, state): (bool, Vec<u8>) = Decode::decode(&mut &state[..])
+            .map_err(|_| sp_runtime::TryRuntimeError::Other("failed to decode pre-upgrade state"))?;
+        if did_run {
+            frame_support::ensure!(
+                Pallet::on_chain_storage_version() == TO,
+                "storage version was not bumped by the migration"
+            );
+            Step::post_upgrade_check(state)?;
+        }
+        Ok(())
+    }
+}
+
+/// A serialized snapshot of raw storage: `(key, value)` pairs as found on chain.
+/// Fixtures are SCALE-encoded so they can be checked in with `include_bytes!`.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, Debug)]
+pub struct StorageFixture {
+    /// The storage version of the pallet at the time the snapshot was taken.
+    pub storage_version: u16,
+    /// The raw storage entries.
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl StorageFixture {
+    /// Decodes a fixture from its serialized form.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, codec::Error> {
+        Self::decode(&mut bytes)
+    }
+
+    /// Adds a raw entry to the fixture.
+    pub fn with_entry(mut self, key: Vec<u8>, value: Vec<u8>) -> Self {
+        self.entries.push((key, value));
+        self
+    }
+}
+
+/// Helpers for exercising migrations against fixtures inside `TestExternalities`.
+#[cfg(feature = "std")]
+pub mod harness {
+    use super::*;
+
+    /// Writes the fixture into the current externalities and sets `Pallet`'s storage version.
+    pub fn load_fixture<Pallet: PalletInfoAccess>(fixture: &StorageFixture) {
+        for (key, value) in &fixture.entries {
+            sp_io::storage::set(key, value);
+        }
+        StorageVersion::new(fixture.storage_version).put::<Pallet>();
+    }
+
+    /// Returns every raw key stored under `prefix`, in storage order.
+    pub fn keys_with_prefix(prefix: &[u8]) -> Vec<Vec<u8>> {
+        let mut keys = Vec::new();
+        let mut next = sp_io::storage::next_key(prefix);
+        while let Some(key) = next {
+            if !key.starts_with(prefix) {
+                break;
+            }
+            next = sp_io::storage::next_key(&key);
+            keys.push(key);
+        }
+        keys
+    }
+
+    /// Asserts that every value under `prefix` decodes as `V` and returns how many there are.
+    pub fn assert_all_decode<V: Decode>(prefix: &[u8]) -> usize {
+        let keys = keys_with_prefix(prefix);
+        for key in &keys {
+            let raw = sp_io::storage::get(key).expect("key was just iterated; qed");
+            assert!(
+                V::decode(&mut &raw[..]).is_ok(),
+                "value under key 0x{} does not decode as the migrated type",
+                sp_core::hexdisplay::HexDisplay::from(key)
+            );
+        }
+        keys.len()
+    }
+
+    /// Asserts that no key in `map_prefix` lacks a matching key in `index_prefix`.
+    /// Keys are matched on their suffix after the respective prefix (the hashed map key).
+    pub fn assert_no_orphans(map_prefix: &[u8], index_prefix: &[u8]) {
+        for key in keys_with_prefix(map_prefix) {
+            let mut index_key = index_prefix.to_vec();
+            index_key.extend_from_slice(&key[map_prefix.len()..]);
+            assert!(
+                sp_io::storage::exists(&index_key),
+                "orphaned key 0x{} has no counterpart under the index prefix",
+                sp_core::hexdisplay::HexDisplay::from(&key)
+            );
+        }
+    }
+
+    /// Loads `fixture`, runs `Migration` and checks the on-chain version ended at `expected_version`.
+    /// Post-conditions specific to the migration are asserted by the caller afterwards.
+    pub fn run_migration<Pallet, Migration>(fixture: &StorageFixture, expected_version: u16) -> Weight
+    where
+        Pallet: GetStorageVersion<CurrentStorageVersion = StorageVersion> + PalletInfoAccess,
+        Migration: OnRuntimeUpgrade,
+    {
+        load_fixture::<Pallet>(fixture);
+        let weight = Migration::on_runtime_upgrade();
+        assert_eq!(
+            Pallet::on_chain_storage_version(),
+            expected_version,
+            "unexpected storage version after migration"
+        );
+        weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{harness::*, *};
+    use frame_support::storage::unhashed;
+
+    const PREFIX: &[u8] = b"fixture:pets:";
+    const INDEX: &[u8] = b"fixture:owners:";
+
+    /// A stand-in pallet so the version helpers have somewhere to write.
+    struct MockPallet;
+
+    impl PalletInfoAccess for MockPallet {
+        fn index() -> usize { 0 }
+        fn name() -> &'static str { "MockPallet" }
+        fn name_hash() -> [u8; 16] { sp_io::hashing::twox_128(b"MockPallet") }
+        fn module_name() -> &'static str { "mock" }
+        fn crate_version() -> frame_support::traits::CrateVersion {
+            frame_support::traits::CrateVersion::new(0, 1, 0)
+        }
+    }
+
+    impl GetStorageVersion for MockPallet {
+        type CurrentStorageVersion = StorageVersion;
+        fn current_storage_version() -> StorageVersion { StorageVersion::new(1) }
+        fn on_chain_storage_version() -> StorageVersion { StorageVersion::get::<Self>() }
+    }
+
+    /// Widens every `u8` value under `PREFIX` into a `u32`.
+    struct WidenValues;
+
+    impl MigrationStep for WidenValues {
+        fn migrate() -> Weight {
+            for key in keys_with_prefix(PREFIX) {
+                let old: u8 = unhashed::get(&key).unwrap();
+                unhashed::put(&key, &(old as u32));
+            }
+            Weight::zero()
+        }
+    }
+
+    type Migration = VersionedMigration<0, 1, WidenValues, MockPallet, ()>;
+
+    fn synthetic_fixture() -> StorageFixture {
+        let mut fixture = StorageFixture { storage_version: 0, entries: Vec::new() };
+        for id in 0u8..3 {
+            fixture = fixture
+                .with_entry([PREFIX, &[id]].concat(), (id * 10).encode())
+                .with_entry([INDEX, &[id]].concat(), 7u64.encode());
+        }
+        fixture
+    }
+
+    fn new_test_ext() -> sp_io::TestExternalities {
+        sp_io::TestExternalities::default()
+    }
+
+    #[test]
+    fn fixture_roundtrips_through_bytes() {
+        let fixture = synthetic_fixture();
+        assert_eq!(StorageFixture::from_bytes(&fixture.encode()).unwrap(), fixture);
+    }
+
+    #[test]
+    fn migration_runs_and_preserves_counts() {
+        new_test_ext().execute_with(|| {
+            let fixture = StorageFixture::from_bytes(&synthetic_fixture().encode()).unwrap();
+            run_migration::<MockPallet, Migration>(&fixture, 1);
+
+            assert_eq!(assert_all_decode::<u32>(PREFIX), 3);
+            assert_eq!(unhashed::get::<u32>(&[PREFIX, &[2]].concat()), Some(20));
+            assert_no_orphans(PREFIX, INDEX);
+        });
+    }
+
+    #[test]
+    fn migration_is_skipped_for_other_versions() {
+        new_test_ext().execute_with(|| {
+            let mut fixture = synthetic_fixture();
+            fixture.storage_version = 1;
+            run_migration::<MockPallet, Migration>(&fixture, 1);
+
+            // Values were left untouched, i.e. still single bytes.
+            assert_eq!(unhashed::get_raw(&[PREFIX, &[1]].concat()), Some(10u8.encode()));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "orphaned key")]
+    fn orphan_check_detects_missing_index_entries() {
+        new_test_ext().execute_with(|| {
+            let fixture = synthetic_fixture().with_entry([PREFIX, &[9]].concat(), 1u8.encode());
+            run_migration::<MockPallet, Migration>(&fixture, 1);
+            assert_no_orphans(PREFIX, INDEX);
+        });
+    }
+
+    #[test]
+    fn versioned_migration_weight_accounts_for_version_bump() {
+        new_test_ext().execute_with(|| {
+            let weight = run_migration::<MockPallet, Migration>(&synthetic_fixture(), 1);
+            assert_eq!(weight, <() as frame_support::traits::Get<RuntimeDbWeight>>::get().reads_writes(1, 1));
+        });
+    }
+}
@@ -3,8 +3,15 @@
 //! Defines the core interface for creating, managing, and transferring
 //! Non-Fungible Tokens (pets) within the CritterCraft ecosystem.
 
-use super::{types::PetStats, Config};
-use frame_support::dispatch::DispatchResult;
+use super::{
+    types::{DeferredEffect, DeferredEffectId, ListingEligibility, PetStats, ReleaseBlockReason, StatModifier, TransferBlockReason},
+    Config,
+};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    weights::Weight,
+};
+use sp_std::vec::Vec;
 
 /// A unified trait for all core NFT management operations.
 /// (I) - Consolidates `SharedNftManager` and `ExtendedNftManager` into one clear interface.
@@ -25,4 +32,208 @@ pub trait NftManagement<T: Config> {
     /// Mint a new pet NFT and assign it to an owner.
     /// This is the foundational function for creating all new pets.
     fn mint(owner: &T::AccountId, dna: [u8; 32], stats: PetStats) -> Result<T::PetId, DispatchResult>;
-}
\ No newline at end of file
+
+    /// Get the owner of a pet and whether it can be listed, with the reason when it cannot.
+    /// Returns `None` if the pet does not exist.
+    ///
+    /// The default only knows about locks; implementors should override it to report every
+    /// state that blocks a transfer.
+    fn listing_eligibility(pet_id: &T::PetId) -> Option<ListingEligibility<T::AccountId>> {
+        let locked = Self::is_locked(pet_id);
+        Self::owner_of(pet_id).map(|owner| ListingEligibility {
+            owner,
+            transferable: !locked,
+            reason: if locked { Some(TransferBlockReason::Locked) } else { None },
+        })
+    }
+}
+
+/// Reports transfer restrictions that are tracked outside the NFT pallet, such as leases,
+/// quarantine, pending guarded transfers and active battles.
+pub trait TransferRestrictionProvider<PetId> {
+    /// The reason the pet cannot be transferred, or `None` if nothing blocks it.
+    fn transfer_restriction(pet_id: &PetId) -> Option<TransferBlockReason>;
+}
+
+impl<PetId> TransferRestrictionProvider<PetId> for () {
+    fn transfer_restriction(_pet_id: &PetId) -> Option<TransferBlockReason> {
+        None
+    }
+}
+
+/// Reports the account each pet was minted to, which keeps it after the pet is transferred,
+/// released or burned. Used by the marketplace to tie a pet's royalty to its minter.
+pub trait PetMinterProvider<AccountId, PetId> {
+    /// The account the pet was minted to, or bred for, if it was recorded.
+    fn minter_of(pet_id: &PetId) -> Option<AccountId>;
+}
+
+/// A subsystem that holds references to pets, such as leases, parties or social permissions.
+/// The NFT pallet asks every blocker before it releases a pet, and releases nothing while one
+/// objects. Blockers combine as a tuple, asked in order; the first objection wins.
+pub trait ReleaseBlocker<PetId> {
+    /// Why the pet cannot be released, or `None` if this subsystem does not hold it. A reference
+    /// that has expired (a finished lease, a lapsed subscription) should be cleaned up here
+    /// rather than reported, so it never blocks a release for good.
+    fn blocks_release(pet_id: &PetId) -> Option<ReleaseBlockReason>;
+
+    /// Drops every reference this subsystem holds to the pet. Called before a forced release
+    /// destroys the pet, whatever `blocks_release` reports.
+    fn on_forced_release(pet_id: &PetId);
+
+    /// The worst-case weight of `on_forced_release`, charged by the call that forces a release.
+    fn on_forced_release_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl<PetId> ReleaseBlocker<PetId> for () {
+    fn blocks_release(_pet_id: &PetId) -> Option<ReleaseBlockReason> {
+        None
+    }
+
+    fn on_forced_release(_pet_id: &PetId) {}
+}
+
+macro_rules! impl_release_blocker_for_tuples {
+    ($($blocker:ident),+) => {
+        impl<PetId, $($blocker: ReleaseBlocker<PetId>),+> ReleaseBlocker<PetId> for ($($blocker,)+) {
+            fn blocks_release(pet_id: &PetId) -> Option<ReleaseBlockReason> {
+                None$(.or_else(|| $blocker::blocks_release(pet_id)))+
+            }
+
+            fn on_forced_release(pet_id: &PetId) {
+                $($blocker::on_forced_release(pet_id);)+
+            }
+
+            fn on_forced_release_weight() -> Weight {
+                Weight::zero()$(.saturating_add($blocker::on_forced_release_weight()))+
+            }
+        }
+    };
+}
+
+impl_release_blocker_for_tuples!(A);
+impl_release_blocker_for_tuples!(A, B);
+impl_release_blocker_for_tuples!(A, B, C);
+impl_release_blocker_for_tuples!(A, B, C, D);
+impl_release_blocker_for_tuples!(A, B, C, D, E);
+impl_release_blocker_for_tuples!(A, B, C, D, E, F);
+impl_release_blocker_for_tuples!(A, B, C, D, E, F, G);
+impl_release_blocker_for_tuples!(A, B, C, D, E, F, G, H);
+
+/// Tells a live pet from one that was released or burned, for pallets that keep pet IDs around
+/// (battle histories, listings, quest progress) and must fail gracefully once the pet is gone.
+pub trait ExistenceChecker<PetId> {
+    /// Whether the pet exists.
+    fn pet_exists(pet_id: &PetId) -> bool;
+
+    /// Whether the pet existed once and has since been released or burned.
+    fn was_released(pet_id: &PetId) -> bool;
+
+    /// Ok if the pet exists, otherwise an error saying whether it was released or never existed.
+    fn ensure_pet_exists(pet_id: &PetId) -> DispatchResult {
+        if Self::pet_exists(pet_id) {
+            Ok(())
+        } else if Self::was_released(pet_id) {
+            Err(DispatchError::Other("pet was released"))
+        } else {
+            Err(DispatchError::Other("pet does not exist"))
+        }
+    }
+}
+
+impl<PetId> ExistenceChecker<PetId> for () {
+    fn pet_exists(_pet_id: &PetId) -> bool {
+        true
+    }
+
+    fn was_released(_pet_id: &PetId) -> bool {
+        false
+    }
+}
+
+/// A read-only view of the conditions (illnesses, buffs, ...) currently affecting a pet, as
+/// tracked by the pet status pallet.
+pub trait PetConditionProvider<PetId> {
+    /// The ids of the pet's active conditions; empty if it has none or does not exist.
+    fn active_conditions(pet_id: &PetId) -> Vec<u32>;
+
+    /// The combined stat modifier of the pet's active conditions; zero if they do not affect
+    /// stats.
+    fn stat_modifier(_pet_id: &PetId) -> StatModifier {
+        StatModifier::default()
+    }
+}
+
+impl<PetId> PetConditionProvider<PetId> for () {
+    fn active_conditions(_pet_id: &PetId) -> Vec<u32> {
+        Vec::new()
+    }
+}
+
+/// A read-only view of the flat stat bonuses of the equipment a pet wears, as tracked by an
+/// equipment pallet.
+pub trait PetEquipmentProvider<PetId> {
+    /// The combined stat bonus of the pet's equipment; zero if it wears none.
+    fn equipment_bonus(pet_id: &PetId) -> StatModifier;
+}
+
+impl<PetId> PetEquipmentProvider<PetId> for () {
+    fn equipment_bonus(_pet_id: &PetId) -> StatModifier {
+        StatModifier::default()
+    }
+}
+/// A read-only view of a pet's canonical elemental affinity, as stored by the NFT pallet.
+/// The element is the index of the NFT pallet's `ElementType` (0 = Neutral, 1 = Fire, ...).
+pub trait PetElementProvider<PetId> {
+    /// The pet's elemental affinity, or `None` if the pet does not exist.
+    fn element_of(pet_id: &PetId) -> Option<u8>;
+}
+
+impl<PetId> PetElementProvider<PetId> for () {
+    fn element_of(_pet_id: &PetId) -> Option<u8> {
+        None
+    }
+}
+
+/// A read-only view of a pet's display version, which the NFT pallet bumps whenever the pet's
+/// name, visual theme or bio changes. Clients re-fetch display data when it moves.
+pub trait PetDisplayVersionProvider<PetId> {
+    /// The pet's display version; zero if its display data never changed or it does not exist.
+    fn display_version(pet_id: &PetId) -> u32;
+}
+
+impl<PetId> PetDisplayVersionProvider<PetId> for () {
+    fn display_version(_pet_id: &PetId) -> u32 {
+        0
+    }
+}
+
+/// A queue of effects that other pallets ask the NFT pallet to apply asynchronously.
+/// Effects are applied in a later block, at most once, and a failing effect is dropped
+/// without affecting the caller or other queued effects.
+pub trait DeferredEffects<PetId> {
+    /// Queue an effect for a pet. Returns the id to inspect or cancel it with.
+    fn defer_effect(pet_id: &PetId, effect: DeferredEffect) -> Result<DeferredEffectId, DispatchError>;
+
+    /// The pet and effect of a queued effect, or `None` once it has been applied, dropped or cancelled.
+    fn pending_effect(effect_id: DeferredEffectId) -> Option<(PetId, DeferredEffect)>;
+
+    /// Remove a queued effect before it is applied.
+    fn cancel_effect(effect_id: DeferredEffectId) -> DispatchResult;
+}
+
+impl<PetId> DeferredEffects<PetId> for () {
+    fn defer_effect(_pet_id: &PetId, _effect: DeferredEffect) -> Result<DeferredEffectId, DispatchError> {
+        Err(DispatchError::Other("deferred effects are not supported"))
+    }
+
+    fn pending_effect(_effect_id: DeferredEffectId) -> Option<(PetId, DeferredEffect)> {
+        None
+    }
+
+    fn cancel_effect(_effect_id: DeferredEffectId) -> DispatchResult {
+        Err(DispatchError::Other("deferred effects are not supported"))
+    }
+}
@@ -42,4 +42,79 @@ pub struct PetStats {
     pub intelligence: u8,
     pub charisma: u8,
     pub stamina: u8,
-}
\ No newline at end of file
+}
+
+/// A signed adjustment to each of a pet's four core stats, in stat points.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StatModifier {
+    pub strength: i16,
+    pub agility: i16,
+    pub intelligence: i16,
+    pub vitality: i16,
+}
+
+/// Why a pet cannot currently be transferred or listed.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TransferBlockReason {
+    /// The pet is locked by another pallet (e.g., an existing marketplace listing).
+    Locked,
+    /// The pet is leased out to another account.
+    Leased,
+    /// The pet is quarantined (e.g., pending moderation).
+    Quarantined,
+    /// A guarded transfer of the pet is waiting to be confirmed.
+    PendingGuardedTransfer,
+    /// The pet is taking part in a battle.
+    InBattle,
+    /// The pet was acquired too recently; it can be transferred from block `unlocks_at`.
+    OwnershipTooRecent { unlocks_at: u64 },
+    /// The pet must reach `required_level` before it can be transferred.
+    LevelTooLow { required_level: u32 },
+}
+
+/// Why a pet cannot currently be released, as reported by a `ReleaseBlocker`.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ReleaseBlockReason {
+    /// The pet is locked by another pallet (e.g., a marketplace listing or a battle).
+    Locked,
+    /// The pet is offered in a pending trade.
+    PendingTrade,
+    /// The pet is leased out to another account.
+    Leased,
+    /// The pet is a member of a party.
+    InParty,
+    /// Another account holds a social permission over the pet.
+    SocialPermissionTarget,
+    /// The pet has an active auto-care subscription.
+    AutoCareScheduled,
+    /// The pet has a lifecycle reward waiting to be claimed.
+    UnclaimedReward,
+    /// The pet is in an active battle.
+    InBattle,
+}
+
+/// A pet's eligibility for listing, assembled in a single call for the marketplace.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ListingEligibility<AccountId> {
+    /// The current owner of the pet.
+    pub owner: AccountId,
+    /// Whether the pet can be transferred (and so listed) right now.
+    pub transferable: bool,
+    /// Why the pet cannot be transferred; `None` when `transferable` is true.
+    pub reason: Option<TransferBlockReason>,
+}
+
+/// Identifies an effect queued with `DeferredEffects::defer_effect`.
+pub type DeferredEffectId = u64;
+
+/// An effect on a pet that another pallet hands to the NFT pallet to apply in a later block,
+/// instead of applying it inside its own extrinsic.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DeferredEffect {
+    /// Grant experience points, levelling the pet up as usual.
+    GrantXp(u32),
+    /// Raise (positive) or lower (negative) the pet's mood, clamped to the valid range.
+    ChangeMood(i16),
+    /// Notify the pet's owner, with the notification type and priority of the UX system.
+    Notify { notification_type: u8, priority: u8 },
+}
@@ -0,0 +1,11 @@
+//! # Pet Valuation Traits
+//!
+//! Defines a read-only interface for scoring the quality of a pet, used by the
+//! marketplace to suggest listing prices.
+
+/// Provides a deterministic quality score for a pet.
+/// Implementations must be side-effect free and bounded in the storage they read.
+pub trait PetValuation<PetId> {
+    /// The pet's quality score, or `None` if the pet does not exist.
+    fn quality_score(pet_id: &PetId) -> Option<u32>;
+}
@@ -315,6 +315,7 @@ pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Runti
 pub type Signature = sp_runtime::MultiSignature;
 pub type BlockNumber = u64;
 pub type Balance = u128;
+pub type AccountId = u64;
 
 // Define the runtime call enum
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -380,6 +381,75 @@ pub struct ConstU16<const N: u16>;
 // Define the dispatch result type
 pub type DispatchResult = Result<(), &'static str>;
 
+// Implement the runtime APIs served to clients
+sp_api::impl_runtime_apis! {
+    impl nfts::runtime_api::PetNftsApi<Block, AccountId, BlockNumber> for Runtime {
+        fn validate_batch_mint(owner: AccountId, pets: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<nfts::BatchMintValidation> {
+            Nfts::validate_batch_mint(&owner, &pets)
+        }
+
+        fn quality_score(pet_id: nfts::PetId) -> Option<u32> {
+            Nfts::quality_score(pet_id)
+        }
+
+        fn effective_stats(pet_id: nfts::PetId) -> Option<nfts::effective_stats::EffectiveStats> {
+            Nfts::effective_stats(pet_id)
+        }
+
+        fn species_population(species: Vec<u8>) -> nfts::SpeciesPopulationRecord {
+            Nfts::species_population_of(species)
+        }
+
+        fn preview_reaction(pet_id: nfts::PetId, interaction: nfts::kinds::InteractionKind) -> Option<nfts::social::ReactionPreview> {
+            Nfts::preview_reaction(pet_id, interaction)
+        }
+
+        fn pet_lineage(pet_id: nfts::PetId, max_depth: u8) -> Option<nfts::lineage::LineageTree<AccountId>> {
+            Nfts::pet_lineage(pet_id, max_depth)
+        }
+
+        fn descendants_of(pet_id: nfts::PetId, limit: u32) -> Vec<nfts::PetId> {
+            Nfts::descendants_of(pet_id, limit)
+        }
+
+        fn pet_storage_footprint(pet_id: nfts::PetId) -> nfts::footprint::StorageFootprint {
+            Nfts::pet_storage_footprint(pet_id)
+        }
+
+        fn heaviest_pets(limit: u32) -> Vec<(nfts::PetId, u32)> {
+            Nfts::heaviest_pets(limit)
+        }
+
+        fn deprecated_call_counts() -> Vec<(u8, u8, u64)> {
+            Nfts::deprecated_call_counts()
+        }
+
+        fn mint_preview(owner: AccountId, species: Vec<u8>, name: Vec<u8>) -> Option<nfts::mint_preview::MintPreviewDistribution<BlockNumber>> {
+            Nfts::mint_preview(&owner, species, name)
+        }
+
+        fn featured_pets() -> Option<nfts::featured::FeaturedPets<AccountId, BlockNumber>> {
+            Nfts::featured_pets()
+        }
+
+        fn input_catalog() -> nfts::input_catalog::InputCatalog {
+            Nfts::input_catalog()
+        }
+
+        fn get_pet_profile(pet_id: nfts::PetId) -> Option<nfts::ui_bridge::UiPetProfile<AccountId>> {
+            Nfts::get_pet_profile(pet_id)
+        }
+
+        fn get_owned_pets(owner: AccountId) -> Vec<nfts::PetId> {
+            Nfts::get_owned_pets(&owner)
+        }
+
+        fn get_pet_summary(pet_id: nfts::PetId) -> Option<(u32, u8, Vec<u8>, BlockNumber)> {
+            Nfts::get_pet_summary(pet_id)
+        }
+    }
+}
+
 // Define the genesis configuration for the runtime
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default()
@@ -39,6 +39,7 @@ pub trait NftManager<AccountId, LocalPetId> {
 pub struct SimpleGeneticInfo<LocalDnaHashType, LocalSpeciesType> {
     pub dna_hash: LocalDnaHashType,
     pub species: LocalSpeciesType,
+    pub generation: u32, // 0 for minted pets, one more than the later parent for bred pets
     // pub level: u32, // Example: if breeding eligibility depends on level (part of PetNft struct)
     // pub is_ready_for_breeding: bool, // Example: if cooldown is managed in critter-nfts
 }